@@ -1,6 +1,7 @@
 //! Configuration parsing for terrain texture baking.
 
 use anyhow::{Context, Result};
+use ktx2_rw::TranscodeFormat;
 use serde::Deserialize;
 use std::path::Path;
 
@@ -11,10 +12,46 @@ pub struct Config {
 	pub output_dir: String,
 	/// Target size for all textures (square).
 	pub output_size: u32,
+	/// GPU format of the baked arrays (default: ETC2).
+	#[serde(default)]
+	pub format: OutputFormat,
 	/// Layer definitions.
 	pub layers: Vec<LayerConfig>,
 }
 
+/// GPU format the baked arrays are written in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+	/// ETC1S-compressed, transcoded to ETC2. Native on WebGL2 and mobile.
+	#[default]
+	Etc2Rgba,
+	/// UASTC-compressed, transcoded to BC7. Higher quality, desktop GPUs only.
+	Bc7,
+	/// UASTC supercompressed, left for the runtime to transcode. voxel_game
+	/// loads it with its `basis-universal` feature.
+	Uastc,
+}
+
+impl OutputFormat {
+	/// Whether Basis compression runs in UASTC (rather than ETC1S) mode.
+	///
+	/// Only the opt-in `Bc7` and `Uastc` formats do; every array of the
+	/// default format, normal maps included, stays ETC1S.
+	pub fn uses_uastc(self) -> bool {
+		self != OutputFormat::Etc2Rgba
+	}
+
+	/// Target of the transcode after compression; `None` keeps the Basis data.
+	pub fn transcode_format(self) -> Option<TranscodeFormat> {
+		match self {
+			OutputFormat::Etc2Rgba => Some(TranscodeFormat::Etc2Rgba),
+			OutputFormat::Bc7 => Some(TranscodeFormat::Bc7Rgba),
+			OutputFormat::Uastc => None,
+		}
+	}
+}
+
 /// Configuration for a single texture layer.
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
@@ -32,8 +69,12 @@ pub struct TexturedLayer {
 	pub name: String,
 	/// Path to diffuse/albedo texture.
 	pub diffuse: String,
-	/// Path to height map.
-	pub height: String,
+	/// Path to height map. Required unless `height_from_normal` is set.
+	pub height: Option<String>,
+	/// Reconstruct the height channel from the normal map when `height` is
+	/// not given.
+	#[serde(default)]
+	pub height_from_normal: bool,
 	/// Path to normal map.
 	pub normal: String,
 	/// Path to roughness map.
@@ -104,7 +145,6 @@ impl Config {
 
 impl LayerConfig {
 	/// Get the layer name.
-	#[allow(dead_code)]
 	pub fn name(&self) -> &str {
 		match self {
 			LayerConfig::Textured(t) => &t.name,
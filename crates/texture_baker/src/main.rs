@@ -6,17 +6,24 @@
 //! - diffuse_height.ktx2: R=Diffuse.R, G=Diffuse.G, B=Diffuse.B, A=Height
 //! - normal.ktx2: R=Normal.X, G=Normal.Y, B=Normal.Z, A=unused
 //! - material.ktx2: R=Roughness, G=Metallic, B=AO, A=unused
+//!
+//! Each array gets a JSON sidecar (e.g. `diffuse_height.json`) listing the
+//! sources of every array index.
 
 mod config;
+mod meta;
+mod mipmap;
 mod packer;
 
 use anyhow::{Context, Result};
 use clap::Parser;
-use ktx2_rw::{BasisCompressionParams, Ktx2Texture, TranscodeFormat, VkFormat};
+use ktx2_rw::{BasisCompressionParams, Ktx2Texture, VkFormat};
 use std::path::{Path, PathBuf};
 
-use config::Config;
-use packer::PackedLayer;
+use config::{Config, OutputFormat};
+use meta::{ArrayMeta, PackedArray};
+use mipmap::{mip_chain, mip_level_count};
+use packer::{pack_layers, PackedLayer};
 
 /// Terrain texture array baker for voxel framework.
 #[derive(Parser, Debug)]
@@ -30,6 +37,14 @@ struct Args {
 	/// Assets directory (default: inferred from config path).
 	#[arg(short, long)]
 	assets_dir: Option<PathBuf>,
+
+	/// Total worker threads shared by all arrays (default: available parallelism).
+	#[arg(short, long)]
+	jobs: Option<u32>,
+
+	/// GPU format of the baked arrays (default: the config's `format`).
+	#[arg(short, long, value_enum)]
+	format: Option<OutputFormat>,
 }
 
 fn main() -> Result<()> {
@@ -48,19 +63,18 @@ fn main() -> Result<()> {
 
 	let config = Config::load(&args.config)?;
 
+	let output_format = args.format.unwrap_or(config.format);
+
 	println!(
-		"Baking {} layers at {}x{} resolution",
+		"Baking {} layers at {}x{} resolution ({:?})",
 		config.layers.len(),
 		config.output_size,
-		config.output_size
+		config.output_size,
+		output_format
 	);
 
-	// Pack all layers
-	let mut packed_layers = Vec::new();
-	for layer_config in &config.layers {
-		let packed = PackedLayer::from_config(layer_config, &assets_dir, config.output_size)?;
-		packed_layers.push(packed);
-	}
+	// Pack all layers in parallel
+	let mut packed_layers = pack_layers(&config.layers, &assets_dir, config.output_size)?;
 
 	// Pad to 4 layers if needed (using the last layer as fill)
 	while packed_layers.len() < 4 {
@@ -69,6 +83,7 @@ fn main() -> Result<()> {
 			diffuse_height: last.diffuse_height.clone(),
 			normal: last.normal.clone(),
 			material: last.material.clone(),
+			warnings: Vec::new(),
 		});
 	}
 
@@ -80,78 +95,166 @@ fn main() -> Result<()> {
 	// Build and save texture arrays
 	println!("\nBuilding KTX2 arrays...");
 
-	let thread_count = std::thread::available_parallelism()
-		.map(|n| n.get() as u32)
-		.unwrap_or(4);
+	let jobs = args.jobs.filter(|&n| n > 0).unwrap_or_else(|| {
+		std::thread::available_parallelism()
+			.map(|n| n.get() as u32)
+			.unwrap_or(4)
+	});
+
+	let arrays = [
+		// Diffuse+Height array (sRGB for diffuse colors)
+		ArraySpec {
+			file_name: "diffuse_height.ktx2",
+			layers: packed_layers.iter().map(|l| &l.diffuse_height).collect(),
+			format: VkFormat::R8G8B8A8Srgb,
+			is_normal_map: false,
+		},
+		// Normal array (linear)
+		ArraySpec {
+			file_name: "normal.ktx2",
+			layers: packed_layers.iter().map(|l| &l.normal).collect(),
+			format: VkFormat::R8G8B8A8Unorm,
+			is_normal_map: true,
+		},
+		// Material array (linear)
+		ArraySpec {
+			file_name: "material.ktx2",
+			layers: packed_layers.iter().map(|l| &l.material).collect(),
+			format: VkFormat::R8G8B8A8Unorm,
+			is_normal_map: false,
+		},
+	];
 
-	// Diffuse+Height array (sRGB for diffuse colors)
-	build_ktx2_array(
-		&packed_layers.iter().map(|l| &l.diffuse_height).collect::<Vec<_>>(),
-		config.output_size,
-		VkFormat::R8G8B8A8Srgb, // sRGB for color data
-		&output_dir.join("diffuse_height.ktx2"),
-		false,
-		thread_count,
-	)
-	.context("Building diffuse_height.ktx2")?;
-	println!("  ✓ diffuse_height.ktx2");
-
-	// Normal array (linear)
-	build_ktx2_array(
-		&packed_layers.iter().map(|l| &l.normal).collect::<Vec<_>>(),
-		config.output_size,
-		VkFormat::R8G8B8A8Unorm, // Linear for normal data
-		&output_dir.join("normal.ktx2"),
-		true,
-		thread_count,
-	)
-	.context("Building normal.ktx2")?;
-	println!("  ✓ normal.ktx2");
-
-	// Material array (linear)
-	build_ktx2_array(
-		&packed_layers.iter().map(|l| &l.material).collect::<Vec<_>>(),
-		config.output_size,
-		VkFormat::R8G8B8A8Unorm, // Linear for material data
-		&output_dir.join("material.ktx2"),
-		false,
-		thread_count,
-	)
-	.context("Building material.ktx2")?;
-	println!("  ✓ material.ktx2");
+	build_ktx2_arrays(&arrays, config.output_size, output_format, &output_dir, jobs)?;
+
+	// Record which source went into each array index
+	for array in PackedArray::ALL {
+		ArrayMeta::new(
+			array,
+			&config.layers,
+			packed_layers.len(),
+			config.output_size,
+			output_format,
+		)
+		.write(&output_dir, array)?;
+		println!("  ✓ {}.json", array.file_stem());
+	}
 
 	println!("\nDone! Output written to: {}", output_dir.display());
 
 	Ok(())
 }
 
+/// One KTX2 array to bake.
+struct ArraySpec<'a> {
+	/// Output file name inside the output directory.
+	file_name: &'static str,
+	/// One image per array layer.
+	layers: Vec<&'a image::RgbaImage>,
+	format: VkFormat,
+	is_normal_map: bool,
+}
+
+/// Build several KTX2 arrays concurrently within a total thread budget.
+///
+/// At most `jobs` arrays are built at once; the budget is split evenly
+/// between the arrays of each round and handed to Basis compression.
+fn build_ktx2_arrays(
+	arrays: &[ArraySpec],
+	size: u32,
+	output_format: OutputFormat,
+	output_dir: &Path,
+	jobs: u32,
+) -> Result<()> {
+	let jobs = jobs.max(1);
+	let concurrent = (jobs as usize).min(arrays.len()).max(1);
+
+	for round in arrays.chunks(concurrent) {
+		let results: Vec<Result<()>> = std::thread::scope(|scope| {
+			let handles: Vec<_> = round
+				.iter()
+				.enumerate()
+				.map(|(i, spec)| {
+					let thread_count = split_budget(jobs, round.len(), i);
+					scope.spawn(move || {
+						build_ktx2_array(
+							&spec.layers,
+							size,
+							spec.format,
+							output_format,
+							&output_dir.join(spec.file_name),
+							spec.is_normal_map,
+							thread_count,
+						)
+						.with_context(|| format!("Building {}", spec.file_name))
+					})
+				})
+				.collect();
+
+			handles
+				.into_iter()
+				.map(|handle| handle.join().expect("KTX2 build thread panicked"))
+				.collect()
+		});
+
+		for (spec, result) in round.iter().zip(results) {
+			result?;
+			println!("  ✓ {}", spec.file_name);
+		}
+	}
+
+	Ok(())
+}
+
+/// Threads for the `index`-th of `count` workers sharing `jobs` threads.
+///
+/// The remainder goes to the first workers; every worker gets at least one.
+fn split_budget(jobs: u32, count: usize, index: usize) -> u32 {
+	let count = count.max(1) as u32;
+	let extra = u32::from((index as u32) < jobs % count);
+	(jobs / count + extra).max(1)
+}
+
 /// Build a KTX2 2D array texture from packed layers with Basis Universal compression.
+///
+/// Every layer gets a full mip chain down to 1x1; normal map levels are
+/// renormalized after downsampling. `output_format` picks the Basis mode and
+/// the GPU format transcoded to.
 fn build_ktx2_array(
 	layers: &[&image::RgbaImage],
 	size: u32,
 	format: VkFormat,
+	output_format: OutputFormat,
 	output_path: &Path,
 	is_normal_map: bool,
 	thread_count: u32,
 ) -> Result<()> {
 	let num_layers = layers.len() as u32;
+	let num_levels = mip_level_count(size);
 
-	// Create KTX2 texture: 2D array (depth=1, faces=1, levels=1)
-	let mut texture = Ktx2Texture::create(size, size, 1, num_layers, 1, 1, format)
+	// Create KTX2 texture: 2D array (depth=1, faces=1, full mip chain)
+	let mut texture = Ktx2Texture::create(size, size, 1, num_layers, 1, num_levels, format)
 		.context("Failed to create KTX2 texture")?;
 
-	// Set image data for each layer
+	// Set image data for each level of each layer
 	for (layer_idx, layer_data) in layers.iter().enumerate() {
-		let raw_data = layer_data.as_raw();
-		texture
-			.set_image_data(0, layer_idx as u32, 0, raw_data)
-			.with_context(|| format!("Failed to set image data for layer {}", layer_idx))?;
+		for (level, level_data) in mip_chain(layer_data, is_normal_map).iter().enumerate() {
+			texture
+				.set_image_data(level as u32, layer_idx as u32, 0, level_data.as_raw())
+				.with_context(|| {
+					format!(
+						"Failed to set image data for layer {} level {}",
+						layer_idx, level
+					)
+				})?;
+		}
 	}
 
-	// Compress with Basis Universal (ETC1S), then transcode to ETC2 GPU format.
-	// ETC2 is natively supported by WebGL2 and all modern desktop GPUs,
-	// so no runtime transcoder (basis-universal) is needed.
+	// Compress with Basis Universal, then transcode to the GPU format.
+	// ETC2 (the default) is natively supported by WebGL2 and all modern
+	// desktop GPUs, so no runtime transcoder (basis-universal) is needed.
 	let params = BasisCompressionParams::builder()
+		.uastc(output_format.uses_uastc())
 		.quality_level(128)
 		.thread_count(thread_count)
 		.normal_map(is_normal_map)
@@ -159,9 +262,11 @@ fn build_ktx2_array(
 	texture
 		.compress_basis(&params)
 		.context("Basis Universal compression failed")?;
-	texture
-		.transcode_basis(TranscodeFormat::Etc2Rgba)
-		.context("ETC2 transcoding failed")?;
+	if let Some(transcode_format) = output_format.transcode_format() {
+		texture
+			.transcode_basis(transcode_format)
+			.with_context(|| format!("{:?} transcoding failed", output_format))?;
+	}
 
 	// Write to file
 	texture
@@ -170,3 +275,141 @@ fn build_ktx2_array(
 
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn test_layers() -> Vec<image::RgbaImage> {
+		(0..4u8)
+			.map(|layer| {
+				image::RgbaImage::from_fn(16, 16, |x, y| {
+					image::Rgba([x as u8 * 16, y as u8 * 16, layer * 60, 255])
+				})
+			})
+			.collect()
+	}
+
+	fn specs(layers: &[image::RgbaImage]) -> Vec<ArraySpec<'_>> {
+		let formats = [
+			("diffuse_height.ktx2", VkFormat::R8G8B8A8Srgb, false),
+			("normal.ktx2", VkFormat::R8G8B8A8Unorm, true),
+			("material.ktx2", VkFormat::R8G8B8A8Unorm, false),
+		];
+		formats
+			.into_iter()
+			.map(|(file_name, format, is_normal_map)| ArraySpec {
+				file_name,
+				layers: layers.iter().collect(),
+				format,
+				is_normal_map,
+			})
+			.collect()
+	}
+
+	fn bake(jobs: u32, dir_name: &str) -> Vec<Vec<u8>> {
+		let layers = test_layers();
+		let arrays = specs(&layers);
+		let output_dir = std::env::temp_dir()
+			.join(format!("texture_baker_{}_{}", std::process::id(), dir_name));
+		std::fs::create_dir_all(&output_dir).unwrap();
+
+		build_ktx2_arrays(&arrays, 16, OutputFormat::Etc2Rgba, &output_dir, jobs).unwrap();
+
+		let bytes = arrays
+			.iter()
+			.map(|spec| std::fs::read(output_dir.join(spec.file_name)).unwrap())
+			.collect();
+		std::fs::remove_dir_all(&output_dir).unwrap();
+		bytes
+	}
+
+	#[test]
+	fn test_concurrent_bake_matches_sequential() {
+		// jobs = 1: one array at a time, one thread each.
+		// jobs = 3: all arrays at once, still one thread each.
+		let sequential = bake(1, "sequential");
+		let concurrent = bake(3, "concurrent");
+
+		assert_eq!(sequential.len(), 3);
+		assert_eq!(sequential, concurrent);
+	}
+
+	/// `VK_FORMAT_BC7_UNORM_BLOCK` from the Vulkan spec.
+	const VK_FORMAT_BC7_UNORM_BLOCK: u32 = 145;
+
+	/// `levelCount` from a KTX2 header (after the 12-byte identifier and seven u32s).
+	fn ktx2_level_count(bytes: &[u8]) -> u32 {
+		u32::from_le_bytes(bytes[40..44].try_into().unwrap())
+	}
+
+	#[test]
+	fn test_output_has_full_mip_chain() {
+		let layer = image::RgbaImage::from_fn(1024, 1024, |x, y| {
+			image::Rgba([(x / 4) as u8, (y / 4) as u8, 128, 255])
+		});
+		let output_dir = std::env::temp_dir()
+			.join(format!("texture_baker_{}_mips", std::process::id()));
+		std::fs::create_dir_all(&output_dir).unwrap();
+		let output_path = output_dir.join("mips.ktx2");
+
+		build_ktx2_array(
+			&[&layer],
+			1024,
+			VkFormat::R8G8B8A8Unorm,
+			OutputFormat::Etc2Rgba,
+			&output_path,
+			false,
+			1,
+		)
+		.unwrap();
+		let bytes = std::fs::read(&output_path).unwrap();
+		std::fs::remove_dir_all(&output_dir).unwrap();
+
+		assert_eq!(ktx2_level_count(&bytes), 11);
+	}
+
+	#[test]
+	fn test_bc7_output_reports_bc7_vk_format() {
+		let layers = test_layers();
+		let layer_refs: Vec<_> = layers.iter().collect();
+		let output_dir = std::env::temp_dir()
+			.join(format!("texture_baker_{}_bc7", std::process::id()));
+		std::fs::create_dir_all(&output_dir).unwrap();
+		let output_path = output_dir.join("bc7.ktx2");
+
+		build_ktx2_array(
+			&layer_refs,
+			16,
+			VkFormat::R8G8B8A8Unorm,
+			OutputFormat::Bc7,
+			&output_path,
+			false,
+			1,
+		)
+		.unwrap();
+		let bytes = std::fs::read(&output_path).unwrap();
+		std::fs::remove_dir_all(&output_dir).unwrap();
+
+		// vkFormat directly follows the 12-byte identifier
+		let vk_format = u32::from_le_bytes(bytes[12..16].try_into().unwrap());
+		assert_eq!(vk_format, VK_FORMAT_BC7_UNORM_BLOCK);
+	}
+
+	#[test]
+	fn test_uastc_is_opt_in() {
+		assert!(!OutputFormat::default().uses_uastc());
+		assert!(OutputFormat::Bc7.uses_uastc());
+		assert!(OutputFormat::Uastc.uses_uastc());
+		assert!(OutputFormat::Uastc.transcode_format().is_none());
+	}
+
+	#[test]
+	fn test_split_budget() {
+		assert_eq!(split_budget(8, 3, 0), 3);
+		assert_eq!(split_budget(8, 3, 1), 3);
+		assert_eq!(split_budget(8, 3, 2), 2);
+		// Never starve a worker
+		assert_eq!(split_budget(1, 3, 2), 1);
+	}
+}
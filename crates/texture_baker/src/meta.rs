@@ -0,0 +1,230 @@
+//! JSON sidecars describing what each baked array layer was built from.
+//!
+//! Each `<array>.ktx2` gets an `<array>.json` next to it listing, per array
+//! index, the layer name and the source of every packed channel. Padding
+//! layers (duplicates of the last configured layer) are marked as such.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+
+use crate::config::{LayerConfig, OutputFormat};
+
+/// One of the packed arrays written by the baker.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PackedArray {
+	DiffuseHeight,
+	Normal,
+	Material,
+}
+
+impl PackedArray {
+	pub const ALL: [PackedArray; 3] = [
+		PackedArray::DiffuseHeight,
+		PackedArray::Normal,
+		PackedArray::Material,
+	];
+
+	/// File name without extension, shared by the KTX2 and its sidecar.
+	pub fn file_stem(self) -> &'static str {
+		match self {
+			PackedArray::DiffuseHeight => "diffuse_height",
+			PackedArray::Normal => "normal",
+			PackedArray::Material => "material",
+		}
+	}
+
+	/// Channel-packing scheme, one entry per RGBA channel.
+	fn channels(self) -> [&'static str; 4] {
+		match self {
+			PackedArray::DiffuseHeight => ["Diffuse.R", "Diffuse.G", "Diffuse.B", "Height"],
+			PackedArray::Normal => ["Normal.X", "Normal.Y", "Normal.Z", "unused"],
+			PackedArray::Material => ["Roughness", "Metallic", "AO", "unused"],
+		}
+	}
+
+	/// Source of each input map packed into this array for `layer`.
+	fn sources(self, layer: &LayerConfig) -> Vec<SourceMeta> {
+		let source = |map: &str, path: String| SourceMeta {
+			map: map.to_string(),
+			path,
+		};
+		match (self, layer) {
+			(PackedArray::DiffuseHeight, LayerConfig::Textured(t)) => vec![
+				source("diffuse", t.diffuse.clone()),
+				source(
+					"height",
+					t.height
+						.clone()
+						.unwrap_or_else(|| format!("reconstructed from {}", t.normal)),
+				),
+			],
+			(PackedArray::Normal, LayerConfig::Textured(t)) => {
+				vec![source("normal", t.normal.clone())]
+			}
+			(PackedArray::Material, LayerConfig::Textured(t)) => vec![
+				source("roughness", t.roughness.clone()),
+				source(
+					"metallic",
+					t.metallic.clone().unwrap_or_else(|| "solid 0".to_string()),
+				),
+				source("ao", t.ao.clone()),
+			],
+			(PackedArray::DiffuseHeight, LayerConfig::Solid(s)) => vec![
+				source("diffuse", format!("solid {:?}", s.solid_color)),
+				source("height", format!("solid {}", s.height_value)),
+			],
+			(PackedArray::Normal, LayerConfig::Solid(_)) => {
+				vec![source("normal", "solid neutral".to_string())]
+			}
+			(PackedArray::Material, LayerConfig::Solid(s)) => vec![
+				source("roughness", format!("solid {}", s.roughness_value)),
+				source("metallic", format!("solid {}", s.metallic_value)),
+				source("ao", format!("solid {}", s.ao_value)),
+			],
+		}
+	}
+}
+
+/// Sidecar contents for one baked array.
+#[derive(Debug, Serialize)]
+pub struct ArrayMeta {
+	/// KTX2 file this sidecar describes.
+	pub file: String,
+	/// GPU format the array was written in.
+	pub format: String,
+	/// Width and height of each layer.
+	pub size: u32,
+	/// Packing scheme as `R`, `G`, `B`, `A` contents.
+	pub channels: [&'static str; 4],
+	/// One entry per array index, in order.
+	pub layers: Vec<LayerMeta>,
+}
+
+/// One array index of a baked array.
+#[derive(Debug, Serialize)]
+pub struct LayerMeta {
+	pub index: usize,
+	pub name: String,
+	/// Whether this index only pads the array by repeating the last layer.
+	pub padding: bool,
+	pub sources: Vec<SourceMeta>,
+}
+
+/// An input map packed into an array.
+#[derive(Debug, Serialize)]
+pub struct SourceMeta {
+	/// Input map, e.g. `diffuse` or `roughness`.
+	pub map: String,
+	/// Path relative to the assets directory, or a description of the
+	/// generated value.
+	pub path: String,
+}
+
+impl ArrayMeta {
+	/// Describe `array` built from `layers`, padded to `array_layers` entries.
+	pub fn new(
+		array: PackedArray,
+		layers: &[LayerConfig],
+		array_layers: usize,
+		size: u32,
+		format: OutputFormat,
+	) -> Self {
+		let layers = (0..array_layers.max(layers.len()))
+			.filter_map(|index| {
+				let layer = layers.get(index).or(layers.last())?;
+				Some(LayerMeta {
+					index,
+					name: layer.name().to_string(),
+					padding: index >= layers.len(),
+					sources: array.sources(layer),
+				})
+			})
+			.collect();
+
+		Self {
+			file: format!("{}.ktx2", array.file_stem()),
+			format: format!("{:?}", format),
+			size,
+			channels: array.channels(),
+			layers,
+		}
+	}
+
+	/// Write as pretty JSON to `<array>.json` in `output_dir`.
+	pub fn write(&self, output_dir: &Path, array: PackedArray) -> Result<()> {
+		let path = output_dir.join(format!("{}.json", array.file_stem()));
+		let json = serde_json::to_string_pretty(self).context("Failed to serialize array meta")?;
+		std::fs::write(&path, json).with_context(|| format!("Failed to write: {}", path.display()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::{SolidLayer, TexturedLayer};
+
+	fn layers() -> Vec<LayerConfig> {
+		vec![
+			LayerConfig::Textured(TexturedLayer {
+				name: "grass".to_string(),
+				diffuse: "grass/color.png".to_string(),
+				height: None,
+				height_from_normal: true,
+				normal: "grass/normal.png".to_string(),
+				roughness: "grass/roughness.png".to_string(),
+				ao: "grass/ao.png".to_string(),
+				metallic: None,
+			}),
+			LayerConfig::Solid(SolidLayer {
+				name: "snow".to_string(),
+				solid_color: [240, 240, 250],
+				roughness_value: 0.3,
+				ao_value: 1.0,
+				metallic_value: 0.0,
+				height_value: 0.5,
+			}),
+		]
+	}
+
+	#[test]
+	fn test_meta_lists_array_layers_in_order() {
+		let layers = layers();
+		let output_dir =
+			std::env::temp_dir().join(format!("texture_baker_{}_meta", std::process::id()));
+		std::fs::create_dir_all(&output_dir).unwrap();
+
+		let meta = ArrayMeta::new(
+			PackedArray::DiffuseHeight,
+			&layers,
+			4,
+			512,
+			OutputFormat::Bc7,
+		);
+		meta.write(&output_dir, PackedArray::DiffuseHeight).unwrap();
+		let json: serde_json::Value = serde_json::from_str(
+			&std::fs::read_to_string(output_dir.join("diffuse_height.json")).unwrap(),
+		)
+		.unwrap();
+		std::fs::remove_dir_all(&output_dir).unwrap();
+
+		let entries = json["layers"].as_array().unwrap();
+		let names: Vec<&str> = entries
+			.iter()
+			.map(|e| e["name"].as_str().unwrap())
+			.collect();
+		let padding: Vec<bool> = entries
+			.iter()
+			.map(|e| e["padding"].as_bool().unwrap())
+			.collect();
+		assert_eq!(names, ["grass", "snow", "snow", "snow"]);
+		assert_eq!(padding, [false, false, true, true]);
+		assert_eq!(json["file"], "diffuse_height.ktx2");
+		assert_eq!(json["channels"][3], "Height");
+		assert_eq!(entries[0]["sources"][0]["path"], "grass/color.png");
+		assert_eq!(
+			entries[0]["sources"][1]["path"],
+			"reconstructed from grass/normal.png"
+		);
+	}
+}
@@ -0,0 +1,98 @@
+//! Mip chain generation for packed layers.
+//!
+//! Each level halves the previous one with a 2x2 box filter. Normal map
+//! levels are renormalized afterwards, since averaging unit vectors shortens
+//! them and flattens lighting at a distance.
+
+use image::{ImageBuffer, Rgba, RgbaImage};
+
+/// Number of mip levels in a full chain down to 1x1.
+pub fn mip_level_count(size: u32) -> u32 {
+	size.max(1).ilog2() + 1
+}
+
+/// Build the full mip chain of a square image, base level first.
+pub fn mip_chain(base: &RgbaImage, is_normal_map: bool) -> Vec<RgbaImage> {
+	let mut levels = vec![base.clone()];
+	while levels.last().unwrap().width() > 1 {
+		let mut next = downsample(levels.last().unwrap());
+		if is_normal_map {
+			renormalize(&mut next);
+		}
+		levels.push(next);
+	}
+	levels
+}
+
+/// Halve an image with a 2x2 box filter.
+fn downsample(image: &RgbaImage) -> RgbaImage {
+	let (width, height) = image.dimensions();
+	let (out_width, out_height) = ((width / 2).max(1), (height / 2).max(1));
+
+	ImageBuffer::from_fn(out_width, out_height, |x, y| {
+		let mut sum = [0u32; 4];
+		for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+			let px = (x * 2 + dx).min(width - 1);
+			let py = (y * 2 + dy).min(height - 1);
+			let pixel = image.get_pixel(px, py);
+			for (total, &value) in sum.iter_mut().zip(pixel.0.iter()) {
+				*total += value as u32;
+			}
+		}
+		Rgba(sum.map(|total| ((total + 2) / 4) as u8))
+	})
+}
+
+/// Rescale the XYZ of every encoded normal back to unit length.
+fn renormalize(image: &mut RgbaImage) {
+	for pixel in image.pixels_mut() {
+		let decoded = [0, 1, 2].map(|i| pixel[i] as f32 / 255.0 * 2.0 - 1.0);
+		let length = decoded.iter().map(|v| v * v).sum::<f32>().sqrt();
+		if length <= f32::EPSILON {
+			continue;
+		}
+		for (i, value) in decoded.into_iter().enumerate() {
+			pixel[i] = ((value / length * 0.5 + 0.5) * 255.0).round() as u8;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_mip_level_count() {
+		assert_eq!(mip_level_count(1), 1);
+		assert_eq!(mip_level_count(16), 5);
+		assert_eq!(mip_level_count(1024), 11);
+	}
+
+	#[test]
+	fn test_mip_chain_halves_to_one_pixel() {
+		let base = RgbaImage::from_fn(16, 16, |x, _| {
+			Rgba([if x % 2 == 0 { 0 } else { 254 }, 0, 0, 255])
+		});
+		let chain = mip_chain(&base, false);
+
+		let sizes: Vec<u32> = chain.iter().map(|level| level.width()).collect();
+		assert_eq!(sizes, vec![16, 8, 4, 2, 1]);
+		// Alternating columns average out
+		assert_eq!(chain[1].get_pixel(0, 0)[0], 127);
+	}
+
+	#[test]
+	fn test_normal_levels_are_renormalized() {
+		// Normals tilted 45 degrees to +X and -X: their average points up but is short
+		let base = RgbaImage::from_fn(2, 2, |x, _| {
+			let nx = if x == 0 { 37 } else { 218 };
+			Rgba([nx, 128, 218, 255])
+		});
+
+		let plain = mip_chain(&base, false);
+		let normal = mip_chain(&base, true);
+
+		assert!(plain[1].get_pixel(0, 0)[2] < 230);
+		assert_eq!(normal[1].get_pixel(0, 0)[2], 255);
+	}
+}
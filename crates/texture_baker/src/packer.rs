@@ -7,6 +7,7 @@
 
 use anyhow::{Context, Result};
 use image::{ImageBuffer, Rgba, RgbaImage};
+use rayon::prelude::*;
 use std::path::Path;
 
 use crate::config::{LayerConfig, SolidLayer, TexturedLayer};
@@ -19,6 +20,8 @@ pub struct PackedLayer {
 	pub normal: RgbaImage,
 	/// Roughness/Metallic/AO + unused alpha (RGBA).
 	pub material: RgbaImage,
+	/// Validation warnings about source data dropped by packing.
+	pub warnings: Vec<String>,
 }
 
 impl PackedLayer {
@@ -42,26 +45,59 @@ impl PackedLayer {
 	) -> Result<Self> {
 		println!("  Loading textures for layer '{}'...", config.name);
 
-		// Load and resize all source textures
-		let diffuse = load_and_resize(assets_dir.join(&config.diffuse), target_size)
+		// Load, validate and resize all source textures
+		let mut warnings = Vec::new();
+		let mut load = |source: &str| {
+			load_and_resize(assets_dir.join(source), target_size, &mut warnings)
+		};
+		let diffuse = load(&config.diffuse)
 			.with_context(|| format!("Loading diffuse: {}", config.diffuse))?;
-		let height = load_and_resize(assets_dir.join(&config.height), target_size)
-			.with_context(|| format!("Loading height: {}", config.height))?;
-		let normal = load_and_resize(assets_dir.join(&config.normal), target_size)
+		let normal = load(&config.normal)
 			.with_context(|| format!("Loading normal: {}", config.normal))?;
-		let roughness = load_and_resize(assets_dir.join(&config.roughness), target_size)
+		// An explicit height source always wins over reconstruction
+		let height = match &config.height {
+			Some(path) => load(path).with_context(|| format!("Loading height: {}", path))?,
+			None if config.height_from_normal => height_from_normal(&normal),
+			None => anyhow::bail!(
+				"layer '{}' has no height map; set `height` or `height_from_normal = true`",
+				config.name
+			),
+		};
+		let roughness = load(&config.roughness)
 			.with_context(|| format!("Loading roughness: {}", config.roughness))?;
-		let ao = load_and_resize(assets_dir.join(&config.ao), target_size)
-			.with_context(|| format!("Loading ao: {}", config.ao))?;
+		let ao = load(&config.ao).with_context(|| format!("Loading ao: {}", config.ao))?;
 
 		// Metallic is optional, default to black (0)
 		let metallic = if let Some(ref path) = config.metallic {
-			load_and_resize(assets_dir.join(path), target_size)
-				.with_context(|| format!("Loading metallic: {}", path))?
+			load(path).with_context(|| format!("Loading metallic: {}", path))?
 		} else {
 			create_solid(target_size, 0)
 		};
 
+		// Alpha is dropped from every source: warn when it carries data
+		let sources = [
+			("diffuse", &diffuse),
+			("height", &height),
+			("normal", &normal),
+			("roughness", &roughness),
+			("metallic", &metallic),
+			("ao", &ao),
+		];
+		warnings.extend(
+			sources
+				.into_iter()
+				.filter(|(_, image)| !is_channel_constant(image, ALPHA))
+				.map(|(source, _)| {
+					format!(
+						"layer '{}': {} alpha is not constant but is discarded by packing",
+						config.name, source
+					)
+				}),
+		);
+		for warning in &warnings {
+			eprintln!("  warning: {}", warning);
+		}
+
 		// Pack channels
 		let diffuse_height = pack_diffuse_height(&diffuse, &height);
 		let normal_packed = pack_normal(&normal);
@@ -71,6 +107,7 @@ impl PackedLayer {
 			diffuse_height,
 			normal: normal_packed,
 			material,
+			warnings,
 		})
 	}
 
@@ -96,15 +133,68 @@ impl PackedLayer {
 			diffuse_height,
 			normal: normal_packed,
 			material,
+			warnings: Vec::new(),
 		})
 	}
 }
 
-/// Load an image and resize to target dimensions.
-fn load_and_resize<P: AsRef<Path>>(path: P, target_size: u32) -> Result<RgbaImage> {
+/// Pack every layer of a config in parallel, keeping config order.
+pub fn pack_layers(
+	layers: &[LayerConfig],
+	assets_dir: &Path,
+	target_size: u32,
+) -> Result<Vec<PackedLayer>> {
+	layers
+		.par_iter()
+		.map(|layer_config| PackedLayer::from_config(layer_config, assets_dir, target_size))
+		.collect()
+}
+
+/// Index of the alpha channel in RGBA pixels.
+const ALPHA: usize = 3;
+
+/// Check that a channel holds the same value in every pixel.
+fn is_channel_constant(image: &RgbaImage, channel: usize) -> bool {
+	let mut pixels = image.pixels();
+	let Some(first) = pixels.next() else {
+		return true;
+	};
+	pixels.all(|pixel| pixel[channel] == first[channel])
+}
+
+/// Load an image, validate it and resize to target dimensions.
+///
+/// Sources must be power-of-two squares. Other sizes are resized to
+/// `target_size` with a warning pushed to `warnings`.
+fn load_and_resize<P: AsRef<Path>>(
+	path: P,
+	target_size: u32,
+	warnings: &mut Vec<String>,
+) -> Result<RgbaImage> {
 	let path = path.as_ref();
 	let img = image::open(path).with_context(|| format!("Failed to open: {}", path.display()))?;
 
+	let (width, height) = (img.width(), img.height());
+	if width != height || !width.is_power_of_two() {
+		anyhow::bail!(
+			"{} is {}x{}; source textures must be power-of-two squares",
+			path.display(),
+			width,
+			height
+		);
+	}
+	if width == target_size {
+		return Ok(img.to_rgba8());
+	}
+	warnings.push(format!(
+		"{} is {}x{}, resizing to {}x{}",
+		path.display(),
+		width,
+		height,
+		target_size,
+		target_size
+	));
+
 	let resized = img.resize_exact(target_size, target_size, image::imageops::FilterType::Lanczos3);
 	Ok(resized.to_rgba8())
 }
@@ -125,6 +215,66 @@ fn create_neutral_normal(size: u32) -> RgbaImage {
 	ImageBuffer::from_pixel(size, size, Rgba([128, 128, 255, 255]))
 }
 
+/// Reconstruct a grayscale height map from a tangent-space normal map.
+///
+/// Slopes `-x/z` and `y/z` (OpenGL convention, +Y up) are integrated by
+/// cumulative sums, once rows-first and once columns-first, and the two are
+/// averaged to spread out the path dependence. The result is stretched to
+/// the full 0-255 range; a flat map gives a constant mid gray.
+fn height_from_normal(normal: &RgbaImage) -> RgbaImage {
+	let (width, height) = normal.dimensions();
+	let (w, h) = (width as usize, height as usize);
+
+	let mut slope_x = vec![0.0f32; w * h];
+	let mut slope_y = vec![0.0f32; w * h];
+	for (x, y, pixel) in normal.enumerate_pixels() {
+		let [nx, ny, nz] = [0, 1, 2].map(|i| pixel[i] as f32 / 255.0 * 2.0 - 1.0);
+		let nz = nz.max(0.05);
+		let i = y as usize * w + x as usize;
+		slope_x[i] = -nx / nz;
+		// Image rows grow downward while +Y points up
+		slope_y[i] = ny / nz;
+	}
+
+	// Rows-first: down the first column, then along each row
+	let mut rows_first = vec![0.0f32; w * h];
+	// Columns-first: along the first row, then down each column
+	let mut columns_first = vec![0.0f32; w * h];
+	for y in 0..h {
+		for x in 0..w {
+			let i = y * w + x;
+			rows_first[i] = match (x, y) {
+				(0, 0) => 0.0,
+				(0, _) => rows_first[i - w] + slope_y[i],
+				_ => rows_first[i - 1] + slope_x[i],
+			};
+			columns_first[i] = match (x, y) {
+				(0, 0) => 0.0,
+				(_, 0) => columns_first[i - 1] + slope_x[i],
+				_ => columns_first[i - w] + slope_y[i],
+			};
+		}
+	}
+	let heights: Vec<f32> = rows_first
+		.iter()
+		.zip(&columns_first)
+		.map(|(a, b)| (a + b) * 0.5)
+		.collect();
+
+	let min = heights.iter().copied().fold(f32::INFINITY, f32::min);
+	let max = heights.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+	let range = max - min;
+
+	ImageBuffer::from_fn(width, height, |x, y| {
+		let value = if range <= 1e-4 {
+			128
+		} else {
+			((heights[y as usize * w + x as usize] - min) / range * 255.0).round() as u8
+		};
+		Rgba([value, value, value, 255])
+	})
+}
+
 /// Pack diffuse RGB and height into a single RGBA image.
 ///
 /// Output: R=Diffuse.R, G=Diffuse.G, B=Diffuse.B, A=Height.R
@@ -172,3 +322,172 @@ fn pack_material(roughness: &RgbaImage, metallic: &RgbaImage, ao: &RgbaImage) ->
 
 	output
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Write a texture set into a fresh temp dir; the normal map gets `normal_alpha`.
+	fn write_textures(
+		dir_name: &str,
+		normal_alpha: impl Fn(u32, u32) -> u8,
+	) -> std::path::PathBuf {
+		let dir = std::env::temp_dir()
+			.join(format!("texture_baker_{}_{}", std::process::id(), dir_name));
+		std::fs::create_dir_all(&dir).unwrap();
+
+		let gray = create_solid(8, 128);
+		for name in ["diffuse", "height", "roughness", "ao"] {
+			gray.save(dir.join(format!("{}.png", name))).unwrap();
+		}
+		let normal = RgbaImage::from_fn(8, 8, |x, y| Rgba([128, 128, 255, normal_alpha(x, y)]));
+		normal.save(dir.join("normal.png")).unwrap();
+
+		dir
+	}
+
+	fn textured_layer() -> LayerConfig {
+		LayerConfig::Textured(TexturedLayer {
+			name: "rock".to_string(),
+			diffuse: "diffuse.png".to_string(),
+			height: Some("height.png".to_string()),
+			height_from_normal: false,
+			normal: "normal.png".to_string(),
+			roughness: "roughness.png".to_string(),
+			ao: "ao.png".to_string(),
+			metallic: None,
+		})
+	}
+
+	fn solid_layer(name: &str, solid_color: [u8; 3], roughness_value: f32) -> LayerConfig {
+		LayerConfig::Solid(SolidLayer {
+			name: name.to_string(),
+			solid_color,
+			roughness_value,
+			ao_value: 1.0,
+			metallic_value: 0.0,
+			height_value: 0.5,
+		})
+	}
+
+	#[test]
+	fn test_parallel_packing_matches_serial() {
+		let dir = write_textures("parallel", |x, y| (x + y) as u8);
+		let layers = vec![
+			textured_layer(),
+			solid_layer("sand", [200, 180, 120], 0.8),
+			solid_layer("snow", [240, 240, 250], 0.3),
+			textured_layer(),
+			solid_layer("dirt", [90, 60, 40], 0.9),
+		];
+
+		let parallel = pack_layers(&layers, &dir, 8).unwrap();
+		let serial: Vec<PackedLayer> = layers
+			.iter()
+			.map(|layer| PackedLayer::from_config(layer, &dir, 8).unwrap())
+			.collect();
+		std::fs::remove_dir_all(&dir).unwrap();
+
+		assert_eq!(parallel.len(), serial.len());
+		for (p, s) in parallel.iter().zip(&serial) {
+			assert_eq!(p.diffuse_height.as_raw(), s.diffuse_height.as_raw());
+			assert_eq!(p.normal.as_raw(), s.normal.as_raw());
+			assert_eq!(p.material.as_raw(), s.material.as_raw());
+			assert_eq!(p.warnings, s.warnings);
+		}
+	}
+
+	#[test]
+	fn test_flat_normal_reconstructs_constant_height() {
+		let height = height_from_normal(&create_neutral_normal(8));
+		assert!(height.pixels().all(|p| p[0] == 128));
+	}
+
+	#[test]
+	fn test_tilted_normal_reconstructs_gradient() {
+		// Normals leaning toward -X: the surface rises along +X
+		let normal = ImageBuffer::from_pixel(8, 8, Rgba([90, 128, 240, 255]));
+		let height = height_from_normal(&normal);
+
+		for y in 0..8 {
+			let row: Vec<u8> = (0..8).map(|x| height.get_pixel(x, y)[0]).collect();
+			assert!(row.windows(2).all(|w| w[1] > w[0]), "{:?}", row);
+		}
+		assert!(height.get_pixel(0, 0)[0] < 8);
+		assert!(height.get_pixel(7, 0)[0] > 247);
+	}
+
+	#[test]
+	fn test_missing_height_uses_normal_when_enabled() {
+		let dir = write_textures("height_from_normal", |_, _| 255);
+		let layer = |height_from_normal| {
+			LayerConfig::Textured(TexturedLayer {
+				name: "rock".to_string(),
+				diffuse: "diffuse.png".to_string(),
+				height: None,
+				height_from_normal,
+				normal: "normal.png".to_string(),
+				roughness: "roughness.png".to_string(),
+				ao: "ao.png".to_string(),
+				metallic: None,
+			})
+		};
+
+		let missing = PackedLayer::from_config(&layer(false), &dir, 8);
+		let packed = PackedLayer::from_config(&layer(true), &dir, 8).unwrap();
+		std::fs::remove_dir_all(&dir).unwrap();
+
+		assert!(missing.is_err());
+		// The test normal map is flat
+		assert!(packed.diffuse_height.pixels().all(|p| p[3] == 128));
+	}
+
+	#[test]
+	fn test_varied_normal_alpha_warns() {
+		let dir = write_textures("varied_alpha", |x, y| (x * 30 + y) as u8);
+
+		let packed = PackedLayer::from_config(&textured_layer(), &dir, 8).unwrap();
+		std::fs::remove_dir_all(&dir).unwrap();
+
+		assert_eq!(packed.warnings.len(), 1);
+		assert!(packed.warnings[0].contains("normal alpha"));
+		// Packed normal alpha is still forced opaque
+		assert!(packed.normal.pixels().all(|p| p[3] == 255));
+	}
+
+	#[test]
+	fn test_non_power_of_two_source_names_file() {
+		let dir = write_textures("npot", |_, _| 255);
+		create_solid(12, 128).save(dir.join("roughness.png")).unwrap();
+
+		let result = PackedLayer::from_config(&textured_layer(), &dir, 8);
+		std::fs::remove_dir_all(&dir).unwrap();
+
+		let message = format!("{:#}", result.err().unwrap());
+		assert!(message.contains("roughness.png"), "{}", message);
+		assert!(message.contains("12x12"), "{}", message);
+	}
+
+	#[test]
+	fn test_size_mismatch_resizes_with_warning() {
+		let dir = write_textures("mismatch", |_, _| 255);
+
+		let packed = PackedLayer::from_config(&textured_layer(), &dir, 4).unwrap();
+		std::fs::remove_dir_all(&dir).unwrap();
+
+		assert_eq!(packed.diffuse_height.dimensions(), (4, 4));
+		// One warning per loaded source (metallic is generated)
+		assert_eq!(packed.warnings.len(), 5);
+		assert!(packed.warnings.iter().all(|w| w.contains("8x8")));
+	}
+
+	#[test]
+	fn test_constant_alpha_does_not_warn() {
+		let dir = write_textures("constant_alpha", |_, _| 255);
+
+		let packed = PackedLayer::from_config(&textured_layer(), &dir, 8).unwrap();
+		std::fs::remove_dir_all(&dir).unwrap();
+
+		assert!(packed.warnings.is_empty());
+	}
+}
@@ -2,6 +2,7 @@
 
 use bevy::prelude::*;
 use voxel_plugin::octree::OctreeNode;
+use voxel_plugin::pipeline::PresentationHint;
 use voxel_plugin::world::WorldId;
 
 /// Component for mesh entities representing octree chunks.
@@ -14,6 +15,74 @@ pub struct VoxelChunk {
   pub world_id: WorldId,
   /// The octree node this mesh represents.
   pub node: OctreeNode,
+  /// Time spent generating this chunk's mesh, in microseconds.
+  pub timing_us: u64,
+  /// How the chunk should appear, copied from `ReadyChunk::hint` at spawn.
+  pub hint: PresentationHint,
+}
+
+/// Direction of a [`ChunkFade`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FadeDirection {
+  /// Coverage ramps 0 → 1; the fade is removed when it completes.
+  In,
+  /// Coverage ramps 1 → 0; the entity is despawned when it completes.
+  Out,
+}
+
+/// Component dithering a chunk in or out during an LOD transition.
+///
+/// Driven by [`animate_chunk_fades`](crate::systems::fade::animate_chunk_fades).
+#[derive(Component, Clone, Debug)]
+pub struct ChunkFade {
+  pub direction: FadeDirection,
+  /// Seconds since the fade started.
+  pub elapsed: f32,
+  /// Total fade length in seconds.
+  pub duration: f32,
+  /// Material to restore once a fade-in completes; `None` until the chunk's
+  /// material has been swapped for a dithered copy.
+  pub original: Option<Handle<StandardMaterial>>,
+}
+
+impl ChunkFade {
+  pub fn fade_in(duration: f32) -> Self {
+    Self::new(FadeDirection::In, duration)
+  }
+
+  pub fn fade_out(duration: f32) -> Self {
+    Self::new(FadeDirection::Out, duration)
+  }
+
+  fn new(direction: FadeDirection, duration: f32) -> Self {
+    Self {
+      direction,
+      elapsed: 0.0,
+      duration,
+      original: None,
+    }
+  }
+
+  /// Fade progress in [0, 1].
+  pub fn progress(&self) -> f32 {
+    if self.duration <= 0.0 {
+      1.0
+    } else {
+      (self.elapsed / self.duration).clamp(0.0, 1.0)
+    }
+  }
+
+  /// Fraction of the chunk's pixels currently drawn.
+  pub fn alpha(&self) -> f32 {
+    match self.direction {
+      FadeDirection::In => self.progress(),
+      FadeDirection::Out => 1.0 - self.progress(),
+    }
+  }
+
+  pub fn finished(&self) -> bool {
+    self.progress() >= 1.0
+  }
 }
 
 /// Marker component for entities that drive LOD refinement.
@@ -31,3 +100,27 @@ pub struct VoxelChunk {
 /// ```
 #[derive(Component, Default)]
 pub struct VoxelViewer;
+
+/// Binds a `VoxelViewer` to a single world.
+///
+/// Viewers without this component drive every `VoxelWorldRoot`; with it,
+/// only the world whose id matches. Useful for a preview or portal world
+/// that should refine around its own camera.
+///
+/// # Example
+/// ```ignore
+/// commands.spawn((
+///     Camera3d::default(),
+///     VoxelViewer,
+///     ViewerWorld(preview_root.id()),
+/// ));
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ViewerWorld(pub WorldId);
+
+impl ViewerWorld {
+  /// Whether a viewer with this (optional) binding drives `world_id`.
+  pub fn drives(binding: Option<&Self>, world_id: WorldId) -> bool {
+    binding.is_none_or(|bound| bound.0 == world_id)
+  }
+}
@@ -37,7 +37,7 @@ use voxel_plugin::octree::{
   refine, OctreeConfig, OctreeNode, RefinementBudget, RefinementInput, TransitionGroup,
   TransitionType,
 };
-use voxel_plugin::pipeline::AsyncPipeline;
+use voxel_plugin::pipeline::{AsyncPipeline, PresentationHint};
 use voxel_plugin::world::WorldId;
 
 use crate::components::VoxelChunk;
@@ -55,6 +55,7 @@ fn test_config() -> OctreeConfig {
     min_lod: 0,
     max_lod: 6,
     lod_exponent: 1.5,
+    lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
     world_bounds: None,
   }
 }
@@ -474,6 +475,7 @@ fn start_refinement_system(
   // Run refinement
   let input = RefinementInput {
     viewer_pos,
+    view_frustum: None,
     config: test_world.config.clone(),
     prev_leaves: test_world.leaves.clone(),
     budget: RefinementBudget::DEFAULT,
@@ -583,11 +585,15 @@ fn poll_refinement_system(
             .spawn(VoxelChunk {
               node: *node,
               world_id,
+              timing_us: 0,
+              hint: PresentationHint::Immediate,
             })
             .id();
           entity_map.node_to_entity.insert(*node, entity);
         }
       }
+      // Batches are never cancelled here
+      PipelineEvent::GroupsAbandoned { .. } => {}
     }
   }
 
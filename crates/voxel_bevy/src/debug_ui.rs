@@ -12,12 +12,25 @@
 //!     voxel_metrics_ui(ui, &metrics);
 //! });
 //! ```
+//!
+//! Also provides [`draw_octree_gizmos`], a Bevy system drawing each chunk's
+//! octree node bounds as a wireframe box colored by LOD, toggled through the
+//! [`OctreeGizmoOverlay`] resource.
 
 #[cfg(feature = "debug_ui")]
 use egui::{Color32, RichText, Ui};
 
+#[cfg(feature = "debug_ui")]
+use bevy::prelude::*;
+#[cfg(feature = "debug_ui")]
+use voxel_plugin::constants::INTERIOR_CELLS;
 #[cfg(feature = "debug_ui")]
 use voxel_plugin::metrics::WorldMetrics;
+#[cfg(feature = "debug_ui")]
+use voxel_plugin::octree::{OctreeConfig, OctreeNode};
+
+#[cfg(feature = "debug_ui")]
+use crate::world::{VoxelWorldRoot, WorldChunkMap};
 
 /// Render a simple histogram from a slice of u64 values.
 #[cfg(feature = "debug_ui")]
@@ -253,3 +266,79 @@ pub fn voxel_metrics_compact(ui: &mut Ui, metrics: &WorldMetrics) {
         ));
     });
 }
+
+/// Resource toggling the octree node boundary overlay.
+#[cfg(feature = "debug_ui")]
+#[derive(Resource, Clone, Debug)]
+pub struct OctreeGizmoOverlay {
+    /// Whether node boxes are drawn.
+    pub enabled: bool,
+}
+
+#[cfg(feature = "debug_ui")]
+impl Default for OctreeGizmoOverlay {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// Wireframe color for a LOD level (hue steps by a golden-ratio turn).
+#[cfg(feature = "debug_ui")]
+pub fn lod_gizmo_color(lod: i32) -> Color {
+    let hue = (lod as f32 * 0.618_034).fract() * 360.0;
+    Color::hsl(hue, 0.85, 0.55)
+}
+
+/// Unit-cube transform for a node's bounds under the world root's transform.
+///
+/// The box spans `get_node_min` to `get_node_min + get_voxel_size * 28` (the
+/// interior cells a chunk covers) in the root's space, so it lines up with the
+/// chunk meshes wherever the root is placed.
+#[cfg(feature = "debug_ui")]
+pub fn node_gizmo_transform(
+    root_transform: &GlobalTransform,
+    config: &OctreeConfig,
+    node: &OctreeNode,
+) -> GlobalTransform {
+    let min = config.get_node_min(node).as_vec3();
+    let size = (config.get_voxel_size(node.lod) * INTERIOR_CELLS as f64) as f32;
+    let local =
+        Transform::from_translation(min + Vec3::splat(size * 0.5)).with_scale(Vec3::splat(size));
+    root_transform.mul_transform(local)
+}
+
+/// System drawing every chunk's octree node bounds as a wireframe box.
+///
+/// Boxes go through [`node_gizmo_transform`]. Schedule it in `PostUpdate`
+/// after `TransformSystems::Propagate` so a moved root is drawn where it is
+/// this frame. Does nothing unless [`OctreeGizmoOverlay`] is present and
+/// enabled.
+#[cfg(feature = "debug_ui")]
+pub fn draw_octree_gizmos(
+    overlay: Option<Res<OctreeGizmoOverlay>>,
+    mut gizmos: Gizmos,
+    roots: Query<(&VoxelWorldRoot, &GlobalTransform)>,
+    chunk_map: Res<WorldChunkMap>,
+) {
+    if !overlay.is_some_and(|overlay| overlay.enabled) {
+        return;
+    }
+
+    for (root, root_transform) in &roots {
+        let Some(world_chunks) = chunk_map.get_world_chunks(root.id()) else {
+            continue;
+        };
+        let config = root.config();
+
+        for node in world_chunks.keys() {
+            gizmos.cuboid(
+                node_gizmo_transform(root_transform, config, node),
+                lod_gizmo_color(node.lod),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "debug_ui_test.rs"]
+mod debug_ui_test;
@@ -0,0 +1,66 @@
+//! Tests for the octree gizmo overlay.
+
+use bevy::gizmos::AppGizmoBuilder;
+use bevy::prelude::*;
+use voxel_plugin::constants::INTERIOR_CELLS;
+use voxel_plugin::octree::{OctreeConfig, OctreeNode};
+use voxel_plugin::sdf_samplers::GroundPlaneSampler;
+
+use super::{draw_octree_gizmos, lod_gizmo_color, node_gizmo_transform, OctreeGizmoOverlay};
+use crate::world::{VoxelWorldRoot, WorldChunkMap};
+
+#[test]
+fn test_gizmo_system_runs_on_populated_chunk_map() {
+  let mut app = App::new();
+  app.add_plugins(TransformPlugin);
+  app.init_gizmo_group::<DefaultGizmoConfigGroup>();
+  app.init_resource::<OctreeGizmoOverlay>();
+  app.add_systems(
+    PostUpdate,
+    draw_octree_gizmos.after(TransformSystems::Propagate),
+  );
+
+  let root = VoxelWorldRoot::new(
+    OctreeConfig::default(),
+    Box::new(GroundPlaneSampler::new(0.0)),
+  );
+  let mut chunk_map = WorldChunkMap::default();
+  for (i, lod) in (0..4).enumerate() {
+    let entity = app.world_mut().spawn_empty().id();
+    chunk_map.insert(root.id(), OctreeNode::new(i as i32, 0, 0, lod), entity);
+  }
+  app.insert_resource(chunk_map);
+  let root_transform = Transform::from_xyz(100.0, 0.0, 0.0);
+  app.world_mut().spawn((root, root_transform));
+
+  app.update();
+  app.world_mut().resource_mut::<OctreeGizmoOverlay>().enabled = false;
+  app.update();
+}
+
+#[test]
+fn test_node_gizmo_follows_root_transform() {
+  let config = OctreeConfig::default();
+  let node = OctreeNode::new(1, 0, -1, 1);
+  let size = (config.get_voxel_size(node.lod) * INTERIOR_CELLS as f64) as f32;
+  let center = config.get_node_min(&node).as_vec3() + Vec3::splat(size * 0.5);
+
+  let identity = node_gizmo_transform(&GlobalTransform::IDENTITY, &config, &node);
+  assert!(identity.translation().abs_diff_eq(center, 1e-4));
+  assert!(identity.scale().abs_diff_eq(Vec3::splat(size), 1e-4));
+
+  let root = Transform::from_xyz(100.0, -20.0, 5.0)
+    .with_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2))
+    .with_scale(Vec3::splat(2.0));
+  let moved = node_gizmo_transform(&GlobalTransform::from(root), &config, &node);
+  assert!(moved
+    .translation()
+    .abs_diff_eq(root.transform_point(center), 1e-3));
+  assert!(moved.scale().abs_diff_eq(Vec3::splat(size * 2.0), 1e-3));
+}
+
+#[test]
+fn test_lod_colors_differ() {
+  assert_ne!(lod_gizmo_color(0), lod_gizmo_color(1));
+  assert_ne!(lod_gizmo_color(1), lod_gizmo_color(2));
+}
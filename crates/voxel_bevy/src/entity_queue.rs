@@ -26,11 +26,21 @@
 //!     }
 //! });
 //! ```
+//!
+//! # Pooling
+//!
+//! With `pool_high_water > 0`,
+//! [`process_frame_pooled`](EntityQueue::process_frame_pooled) hands the
+//! handler an [`EntityPool`]: released chunk entities are hidden and kept for
+//! the next spawn instead of being despawned, so continuous refinement stops
+//! churning the entity allocator. Entities released while the pool is full
+//! are despawned.
 
 use std::collections::VecDeque;
 // WASM compat: std::time::Instant panics on wasm32
 use web_time::Instant;
 
+use bevy::prelude::*;
 use voxel_plugin::pipeline::CompletedTransition;
 
 /// Configuration for entity queue.
@@ -43,6 +53,8 @@ pub struct EntityQueueConfig {
 	/// Groups are applied atomically, so this is a soft limit -
 	/// we finish the current group even if over budget.
 	pub max_ms_per_frame: f32,
+	/// Maximum hidden entities kept for reuse (0 disables pooling).
+	pub pool_high_water: usize,
 }
 
 impl Default for EntityQueueConfig {
@@ -50,6 +62,95 @@ impl Default for EntityQueueConfig {
 		Self {
 			max_groups_per_frame: 8,
 			max_ms_per_frame: 4.0, // 4ms leaves headroom in 16.6ms frame
+			pool_high_water: 0,
+		}
+	}
+}
+
+/// Hidden chunk entities kept for reuse.
+#[derive(Debug, Default)]
+pub struct EntityPool {
+	entities: Vec<Entity>,
+	high_water: usize,
+	hits: usize,
+	misses: usize,
+}
+
+impl EntityPool {
+	/// Create a pool retaining at most `high_water` entities.
+	pub fn new(high_water: usize) -> Self {
+		Self {
+			high_water,
+			..Default::default()
+		}
+	}
+
+	/// Spawn `bundle`, reusing a pooled entity when one is available.
+	pub fn spawn(&mut self, commands: &mut Commands, bundle: impl Bundle) -> Entity {
+		match self.entities.pop() {
+			Some(entity) => {
+				self.hits += 1;
+				commands
+					.entity(entity)
+					.insert((bundle, Visibility::Inherited));
+				entity
+			}
+			None => {
+				self.misses += 1;
+				commands.spawn(bundle).id()
+			}
+		}
+	}
+
+	/// Return a chunk entity to the pool, or despawn it if the pool is full.
+	///
+	/// Pooled entities are hidden and keep only their transform and
+	/// visibility: the mesh, material, collider, fade, parent and every other
+	/// component are removed, so no chunk, physics or scene query sees them.
+	/// Pooled entities are not scene entities; call [`clear`](Self::clear)
+	/// when the scene is torn down.
+	pub fn release(&mut self, commands: &mut Commands, entity: Entity) {
+		if self.entities.len() >= self.high_water {
+			commands.entity(entity).despawn();
+			return;
+		}
+
+		commands
+			.entity(entity)
+			.retain::<(
+				Transform,
+				GlobalTransform,
+				Visibility,
+				InheritedVisibility,
+				ViewVisibility,
+			)>()
+			.insert(Visibility::Hidden);
+		self.entities.push(entity);
+	}
+
+	/// Number of hidden entities available for reuse.
+	pub fn len(&self) -> usize {
+		self.entities.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.entities.is_empty()
+	}
+
+	/// Spawns served from the pool since creation.
+	pub fn hits(&self) -> usize {
+		self.hits
+	}
+
+	/// Spawns that had to allocate a new entity since creation.
+	pub fn misses(&self) -> usize {
+		self.misses
+	}
+
+	/// Despawn all pooled entities.
+	pub fn clear(&mut self, commands: &mut Commands) {
+		for entity in self.entities.drain(..) {
+			commands.entity(entity).despawn();
 		}
 	}
 }
@@ -58,6 +159,7 @@ impl Default for EntityQueueConfig {
 pub struct EntityQueue {
 	config: EntityQueueConfig,
 	pending_transitions: VecDeque<CompletedTransition>,
+	pool: EntityPool,
 }
 
 /// Statistics from queue processing.
@@ -73,12 +175,17 @@ pub struct QueueStats {
 	pub elapsed_us: u64,
 	/// Transition groups remaining in queue.
 	pub pending_groups: usize,
+	/// Spawns this frame that reused a pooled entity.
+	pub pool_hits: usize,
+	/// Spawns this frame that allocated a new entity.
+	pub pool_misses: usize,
 }
 
 impl EntityQueue {
 	/// Create a new entity queue with the given configuration.
 	pub fn new(config: EntityQueueConfig) -> Self {
 		Self {
+			pool: EntityPool::new(config.pool_high_water),
 			config,
 			pending_transitions: VecDeque::new(),
 		}
@@ -107,11 +214,22 @@ impl EntityQueue {
 	pub fn process_frame<F>(&mut self, mut handler: F) -> QueueStats
 	where
 		F: FnMut(&CompletedTransition),
+	{
+		self.process_frame_pooled(|transition, _| handler(transition))
+	}
+
+	/// Like [`process_frame`](Self::process_frame), passing the entity pool
+	/// to the handler so removed chunks can be released and new chunks
+	/// spawned from it.
+	pub fn process_frame_pooled<F>(&mut self, mut handler: F) -> QueueStats
+	where
+		F: FnMut(&CompletedTransition, &mut EntityPool),
 	{
 		let start = Instant::now();
 		let budget_us = (self.config.max_ms_per_frame * 1000.0) as u64;
 
 		let mut stats = QueueStats::default();
+		let (hits, misses) = (self.pool.hits, self.pool.misses);
 
 		while stats.groups_applied < self.config.max_groups_per_frame {
 			// Check time budget (but always finish at least one group if we started)
@@ -128,13 +246,15 @@ impl EntityQueue {
 			stats.spawns += transition.ready_chunks.len();
 
 			// Apply atomically (handler does despawn + spawn)
-			handler(&transition);
+			handler(&transition, &mut self.pool);
 
 			stats.groups_applied += 1;
 		}
 
 		stats.elapsed_us = start.elapsed().as_micros() as u64;
 		stats.pending_groups = self.pending_transitions.len();
+		stats.pool_hits = self.pool.hits - hits;
+		stats.pool_misses = self.pool.misses - misses;
 
 		stats
 	}
@@ -144,8 +264,14 @@ impl EntityQueue {
 		self.pending_transitions.clear();
 	}
 
+	/// Entity pool used by [`process_frame_pooled`](Self::process_frame_pooled).
+	pub fn pool(&mut self) -> &mut EntityPool {
+		&mut self.pool
+	}
+
 	/// Update configuration.
 	pub fn set_config(&mut self, config: EntityQueueConfig) {
+		self.pool.high_water = config.pool_high_water;
 		self.config = config;
 	}
 }
@@ -159,11 +285,14 @@ impl Default for EntityQueue {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use bevy::ecs::world::CommandQueue;
 	use voxel_plugin::octree::OctreeNode;
 	use voxel_plugin::pipeline::{PresentationHint, ReadyChunk};
 	use voxel_plugin::world::WorldId;
 	use voxel_plugin::MeshOutput;
 
+	use crate::components::{ChunkFade, VoxelChunk};
+
 	fn make_transition(
 		group_key: OctreeNode,
 		remove_count: usize,
@@ -234,6 +363,7 @@ mod tests {
 		let mut queue = EntityQueue::new(EntityQueueConfig {
 			max_groups_per_frame: 2,
 			max_ms_per_frame: 1000.0, // High time budget
+			..Default::default()
 		});
 
 		// Queue 5 transitions
@@ -259,4 +389,102 @@ mod tests {
 		assert_eq!(stats.groups_applied, 1);
 		assert_eq!(stats.pending_groups, 0);
 	}
+
+	#[test]
+	fn test_pool_reuses_entity_for_toggled_node() {
+		let mut world = World::new();
+		let mut queue = EntityQueue::new(EntityQueueConfig {
+			pool_high_water: 4,
+			..Default::default()
+		});
+
+		let node = OctreeNode::new(0, 0, 0, 0);
+		let mut current: Option<Entity> = None;
+		let mut spawned = Vec::new();
+		let mut total_hits = 0;
+		let mut total_misses = 0;
+
+		// Node appears, disappears, reappears... one transition per frame
+		for frame in 0..6 {
+			let present = frame % 2 == 0;
+			let transition = make_transition(node, 0, usize::from(present), false);
+			queue.queue_transitions(vec![transition]);
+
+			let mut commands_queue = CommandQueue::default();
+			let stats = {
+				let mut commands = Commands::new(&mut commands_queue, &world);
+				queue.process_frame_pooled(|transition, pool| {
+					if let Some(entity) = current.take() {
+						pool.release(&mut commands, entity);
+					}
+					for chunk in &transition.ready_chunks {
+						let entity = pool.spawn(
+							&mut commands,
+							VoxelChunk {
+								world_id: chunk.world_id,
+								node: chunk.node,
+								timing_us: chunk.timing_us,
+								hint: chunk.hint.clone(),
+							},
+						);
+						spawned.push(entity);
+						current = Some(entity);
+					}
+				})
+			};
+			commands_queue.apply(&mut world);
+
+			total_hits += stats.pool_hits;
+			total_misses += stats.pool_misses;
+
+			if let Some(entity) = current {
+				assert!(world.get::<VoxelChunk>(entity).is_some());
+				assert_eq!(world.get::<Visibility>(entity), Some(&Visibility::Inherited));
+			}
+		}
+
+		assert_eq!(spawned.len(), 3);
+		assert!(spawned.iter().all(|&entity| entity == spawned[0]));
+		assert_eq!((total_hits, total_misses), (2, 1));
+	}
+
+	#[test]
+	fn test_pool_despawns_beyond_high_water() {
+		let mut world = World::new();
+		let mut pool = EntityPool::new(1);
+		let a = world
+			.spawn((
+				VoxelChunk {
+					world_id: WorldId::new(),
+					node: OctreeNode::new(0, 0, 0, 0),
+					timing_us: 0,
+					hint: PresentationHint::Immediate,
+				},
+				ChunkFade::fade_out(0.25),
+				Mesh3d::default(),
+				MeshMaterial3d::<StandardMaterial>::default(),
+				Transform::from_xyz(1.0, 2.0, 3.0),
+				Name::new("chunk"),
+			))
+			.id();
+		let b = world.spawn(Visibility::Inherited).id();
+
+		let mut commands_queue = CommandQueue::default();
+		{
+			let mut commands = Commands::new(&mut commands_queue, &world);
+			pool.release(&mut commands, a);
+			pool.release(&mut commands, b);
+		}
+		commands_queue.apply(&mut world);
+
+		assert_eq!(pool.len(), 1);
+		assert!(world.get::<VoxelChunk>(a).is_none());
+		assert!(world.get::<ChunkFade>(a).is_none());
+		assert!(world.get::<Mesh3d>(a).is_none());
+		assert!(world.get::<MeshMaterial3d<StandardMaterial>>(a).is_none());
+		assert!(world.get::<Name>(a).is_none());
+		assert!(world.get::<Transform>(a).is_some());
+		assert_eq!(world.get::<Visibility>(a), Some(&Visibility::Hidden));
+		assert!(world.get_entity(b).is_err());
+	}
 }
@@ -8,6 +8,7 @@
 
 pub mod components;
 pub mod entity_queue;
+pub mod physics;
 pub mod resources;
 pub mod systems;
 pub mod world;
@@ -22,10 +23,35 @@ mod presentation_test;
 mod consistency_test;
 
 pub use components::*;
-pub use entity_queue::{EntityQueue, EntityQueueConfig, QueueStats};
+pub use entity_queue::{EntityPool, EntityQueue, EntityQueueConfig, QueueStats};
+pub use physics::{dominant_material, MaterialPhysics, MaterialPhysicsConfig};
+#[cfg(feature = "avian")]
+pub use physics::{
+  chunk_collider, queue_chunk_collider, update_chunk_colliders, ChunkColliderConfig,
+  ChunkColliderSource,
+};
 pub use resources::*;
-pub use systems::entities::{mesh_output_to_bevy, spawn_chunk_entity, spawn_custom_material_chunk_entity};
-pub use world::{VoxelWorldRoot, WorldChunkMap};
+pub use systems::csg::{apply_csg_shapes, CsgOp, CsgOrder, SdfBox, SdfSphere};
+pub use systems::entities::{
+  mesh_output_to_bevy, spawn_chunk_entity, spawn_chunk_entity_with_hint,
+  spawn_custom_material_chunk_entity, spawn_custom_material_chunk_entity_with_hint,
+  ATTRIBUTE_MATERIAL_WEIGHTS_HI,
+};
+pub use systems::fade::{
+  animate_chunk_fades, begin_chunk_fades, ChunkFadeConfig, ChunkFadePlugin, DitherFade,
+  DitherFadeMaterial, DitherFadeParams,
+};
+pub use systems::frustum_culling::{cull_chunks_to_frustum, ChunkFrustumCulled};
+pub use systems::meshing_tasks::{
+  poll_custom_material_meshing_tasks, poll_meshing_tasks, AdaptiveGroupBudget, VoxelMeshingTasks,
+};
+pub use systems::scene_recorder::{record_spawned_chunks, OctreeSceneRecorder, RecordedChunk};
+pub use systems::timing_overlay::{apply_chunk_timing_tint, ChunkTimingOverlay, TimingTint};
+pub use world::{
+  apply_edit_history, load_world_edits, save_world_edits, update_voxel_worlds,
+  world_viewer_positions, ManualWorldUpdates, VoxelEditHistory, VoxelEditSaveFile,
+  VoxelEditSavePlugin, VoxelWorldMaterial, VoxelWorldRoot, WorldChunkMap,
+};
 
 // Re-export metrics types for convenience
 pub use voxel_plugin::metrics::{RollingWindow, WorldMetrics};
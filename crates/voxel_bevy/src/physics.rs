@@ -0,0 +1,279 @@
+//! Per-material physics properties for chunk colliders.
+//!
+//! Surface Nets emits 4 material blend weights per vertex, one per material
+//! layer. A chunk's dominant material is the layer with the largest summed
+//! weight over all its vertices, and [`MaterialPhysicsConfig`] maps it to the
+//! friction/restitution its collider should use (e.g. ice vs rock).
+//!
+//! With the `avian` feature, chunks spawned through `spawn_chunk_entity` keep
+//! a [`ChunkColliderSource`], and [`update_chunk_colliders`] gives them an
+//! Avian trimesh collider while a [`ChunkColliderConfig`] resource is present
+//! and the chunk lies within `max_distance` of a `VoxelViewer`. Distances are
+//! re-checked every frame, so colliders are added and removed as viewers move.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "avian")]
+use avian3d::prelude::{Collider, Friction, Restitution};
+use bevy::prelude::*;
+#[cfg(feature = "avian")]
+use voxel_plugin::surface_nets::is_valid_for_collision;
+use voxel_plugin::types::{MaterialId, MeshOutput};
+
+#[cfg(feature = "avian")]
+use crate::components::VoxelViewer;
+
+/// Friction and restitution of a collider surface.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MaterialPhysics {
+  /// Friction coefficient (0 = frictionless).
+  pub friction: f32,
+  /// Restitution coefficient (0 = no bounce, 1 = perfectly elastic).
+  pub restitution: f32,
+}
+
+impl MaterialPhysics {
+  pub const fn new(friction: f32, restitution: f32) -> Self {
+    Self {
+      friction,
+      restitution,
+    }
+  }
+}
+
+impl Default for MaterialPhysics {
+  fn default() -> Self {
+    Self::new(0.5, 0.0)
+  }
+}
+
+/// Resource mapping material IDs to collider physics properties.
+///
+/// Materials without an entry use `default`.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct MaterialPhysicsConfig {
+  /// Properties for materials without an explicit entry.
+  pub default: MaterialPhysics,
+  /// Per-material overrides.
+  pub materials: HashMap<MaterialId, MaterialPhysics>,
+}
+
+impl MaterialPhysicsConfig {
+  pub fn with_default(mut self, physics: MaterialPhysics) -> Self {
+    self.default = physics;
+    self
+  }
+
+  pub fn with_material(mut self, material: MaterialId, physics: MaterialPhysics) -> Self {
+    self.materials.insert(material, physics);
+    self
+  }
+
+  /// Properties for `material`.
+  pub fn get(&self, material: MaterialId) -> MaterialPhysics {
+    self
+      .materials
+      .get(&material)
+      .copied()
+      .unwrap_or(self.default)
+  }
+
+  /// Properties for a chunk's dominant material, or `default` if the mesh is
+  /// empty.
+  pub fn for_mesh(&self, output: &MeshOutput) -> MaterialPhysics {
+    dominant_material(output).map_or(self.default, |material| self.get(material))
+  }
+}
+
+/// Material layer with the largest summed blend weight over the mesh.
+///
+/// Ties resolve to the lower material ID. Returns `None` for empty meshes.
+pub fn dominant_material(output: &MeshOutput) -> Option<MaterialId> {
+  if output.vertices.is_empty() {
+    return None;
+  }
+
+  // Layers 0-3 from the vertices, 4-7 from `material_weights_hi` (8-material
+  // meshes only)
+  let mut totals = [0.0f32; 8];
+  for vertex in &output.vertices {
+    for (total, weight) in totals.iter_mut().zip(vertex.material_weights) {
+      *total += weight;
+    }
+  }
+  for weights in &output.material_weights_hi {
+    for (total, weight) in totals[4..].iter_mut().zip(weights) {
+      *total += weight;
+    }
+  }
+
+  let best = (1..totals.len()).fold(0, |best, layer| {
+    if totals[layer] > totals[best] {
+      layer
+    } else {
+      best
+    }
+  });
+  Some(best as MaterialId)
+}
+
+/// Resource enabling Avian colliders on spawned chunks.
+///
+/// Chunks farther than `max_distance` from every `VoxelViewer` have no
+/// collider; `update_chunk_colliders` adds it when a viewer comes within
+/// range and removes it when all of them leave.
+#[cfg(feature = "avian")]
+#[derive(Resource, Clone, Debug)]
+pub struct ChunkColliderConfig {
+  /// Whether chunks get colliders.
+  pub enabled: bool,
+  /// Maximum viewer distance to a chunk's center, in world units.
+  pub max_distance: f32,
+}
+
+#[cfg(feature = "avian")]
+impl Default for ChunkColliderConfig {
+  fn default() -> Self {
+    Self {
+      enabled: true,
+      max_distance: 512.0,
+    }
+  }
+}
+
+#[cfg(feature = "avian")]
+impl ChunkColliderConfig {
+  pub fn with_max_distance(mut self, max_distance: f32) -> Self {
+    self.max_distance = max_distance;
+    self
+  }
+}
+
+/// Trimesh collider for a chunk mesh, in the mesh's local coordinates.
+///
+/// Returns `None` for meshes rejected by `is_valid_for_collision` (empty or
+/// fully degenerate).
+#[cfg(feature = "avian")]
+pub fn chunk_collider(output: &MeshOutput) -> Option<Collider> {
+  ChunkColliderSource::new(output).map(|source| source.collider())
+}
+
+/// Collision geometry of a chunk, kept until its collider is needed.
+///
+/// Positions are in the mesh's local coordinates, like the render mesh.
+#[cfg(feature = "avian")]
+#[derive(Component, Clone, Debug)]
+pub struct ChunkColliderSource {
+  vertices: Vec<Vec3>,
+  indices: Vec<[u32; 3]>,
+  /// Center of the mesh bounds, in local coordinates.
+  center: Vec3,
+  material: Option<MaterialId>,
+}
+
+#[cfg(feature = "avian")]
+impl ChunkColliderSource {
+  /// Geometry of `output`, or `None` for meshes rejected by
+  /// `is_valid_for_collision`.
+  pub fn new(output: &MeshOutput) -> Option<Self> {
+    if !is_valid_for_collision(output) {
+      return None;
+    }
+
+    let vertices = output
+      .vertices
+      .iter()
+      .map(|vertex| Vec3::from_array(vertex.position))
+      .collect();
+    let indices = output
+      .indices
+      .chunks_exact(3)
+      .map(|tri| [tri[0] as u32, tri[1] as u32, tri[2] as u32])
+      .collect();
+    let bounds = &output.bounds;
+    let center = (Vec3::from_array(bounds.min) + Vec3::from_array(bounds.max)) * 0.5;
+    Some(Self {
+      vertices,
+      indices,
+      center,
+      material: dominant_material(output),
+    })
+  }
+
+  /// Build the trimesh collider.
+  pub fn collider(&self) -> Collider {
+    Collider::trimesh(self.vertices.clone(), self.indices.clone())
+  }
+}
+
+/// Keep the collision geometry of a freshly spawned chunk entity.
+///
+/// Nothing is built yet: `update_chunk_colliders` builds the collider once a
+/// viewer is in range. Meshes rejected by `is_valid_for_collision` are
+/// skipped.
+#[cfg(feature = "avian")]
+pub fn queue_chunk_collider(commands: &mut Commands, entity: Entity, output: &MeshOutput) {
+  if let Some(source) = ChunkColliderSource::new(output) {
+    commands.entity(entity).insert(source);
+  }
+}
+
+/// System adding and removing chunk colliders as viewers move.
+///
+/// A chunk has a collider while [`ChunkColliderConfig`] is present and
+/// enabled and its center, placed by its `GlobalTransform` (which includes
+/// its world root's transform), is within `max_distance` of a viewer, or
+/// there are no viewers. Friction and restitution come from
+/// [`MaterialPhysicsConfig`] when that resource exists.
+///
+/// Schedule it in `PostUpdate` after `TransformSystems::Propagate`, so chunks
+/// spawned or moved this frame are measured at their final position.
+#[cfg(feature = "avian")]
+pub fn update_chunk_colliders(
+  mut commands: Commands,
+  config: Option<Res<ChunkColliderConfig>>,
+  physics: Option<Res<MaterialPhysicsConfig>>,
+  viewers: Query<&GlobalTransform, With<VoxelViewer>>,
+  chunks: Query<(
+    Entity,
+    &GlobalTransform,
+    &ChunkColliderSource,
+    Has<Collider>,
+  )>,
+) {
+  let enabled = config.as_ref().is_some_and(|config| config.enabled);
+  let max_distance = config.map_or(0.0, |config| config.max_distance);
+
+  for (entity, transform, source, has_collider) in &chunks {
+    let center = transform.transform_point(source.center);
+    let in_range = enabled
+      && viewers
+        .iter()
+        .map(|viewer| viewer.translation().distance(center))
+        .reduce(f32::min)
+        .is_none_or(|distance| distance <= max_distance);
+
+    if in_range && !has_collider {
+      let surface = physics
+        .as_ref()
+        .map_or_else(MaterialPhysics::default, |physics| {
+          source
+            .material
+            .map_or(physics.default, |material| physics.get(material))
+        });
+      commands.entity(entity).insert((
+        source.collider(),
+        Friction::new(surface.friction),
+        Restitution::new(surface.restitution),
+      ));
+    } else if !in_range && has_collider {
+      commands
+        .entity(entity)
+        .remove::<(Collider, Friction, Restitution)>();
+    }
+  }
+}
+
+#[cfg(test)]
+#[path = "physics_test.rs"]
+mod physics_test;
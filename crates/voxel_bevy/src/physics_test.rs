@@ -0,0 +1,217 @@
+//! Tests for per-material collider physics.
+
+use voxel_plugin::constants::{coord_to_index, SAMPLE_SIZE, SAMPLE_SIZE_CB};
+use voxel_plugin::surface_nets;
+use voxel_plugin::types::{sdf_conversion, MaterialId, MeshConfig, MeshOutput, Vertex};
+
+use super::*;
+
+const ROCK: MaterialId = 0;
+const ICE: MaterialId = 2;
+
+fn ice_and_rock() -> MaterialPhysicsConfig {
+  MaterialPhysicsConfig::default()
+    .with_material(ROCK, MaterialPhysics::new(0.9, 0.1))
+    .with_material(ICE, MaterialPhysics::new(0.02, 0.05))
+}
+
+/// Flat ground at y = 16.5 made entirely of `material`.
+fn ground_chunk(material: MaterialId) -> MeshOutput {
+  ground_chunk_with(material, &MeshConfig::default())
+}
+
+fn ground_chunk_with(material: MaterialId, config: &MeshConfig) -> MeshOutput {
+  let mut volume = [0i8; SAMPLE_SIZE_CB];
+  for x in 0..SAMPLE_SIZE {
+    for y in 0..SAMPLE_SIZE {
+      for z in 0..SAMPLE_SIZE {
+        volume[coord_to_index(x, y, z)] = sdf_conversion::to_storage(y as f32 - 16.5, 1.0);
+      }
+    }
+  }
+  let materials = [material; SAMPLE_SIZE_CB];
+  surface_nets::generate(&volume, &materials, config)
+}
+
+fn vertex_with_weights(material_weights: [f32; 4]) -> Vertex {
+  Vertex {
+    material_weights,
+    ..Default::default()
+  }
+}
+
+#[test]
+fn test_ice_chunk_gets_low_friction() {
+  let chunk = ground_chunk(ICE);
+  assert!(!chunk.is_empty());
+  assert_eq!(dominant_material(&chunk), Some(ICE));
+
+  let physics = ice_and_rock().for_mesh(&chunk);
+  assert_eq!(physics, MaterialPhysics::new(0.02, 0.05));
+}
+
+#[test]
+fn test_mixed_chunk_uses_dominant_material() {
+  let mut chunk = MeshOutput::new();
+  chunk.vertices = vec![
+    vertex_with_weights([1.0, 0.0, 0.0, 0.0]),
+    vertex_with_weights([0.0, 0.0, 1.0, 0.0]),
+    vertex_with_weights([0.25, 0.0, 0.75, 0.0]),
+  ];
+
+  assert_eq!(dominant_material(&chunk), Some(ICE));
+  assert_eq!(ice_and_rock().for_mesh(&chunk).friction, 0.02);
+}
+
+#[test]
+fn test_high_material_can_dominate() {
+  let config = MeshConfig::default().with_material_count(8);
+  let chunk = ground_chunk_with(6, &config);
+  assert!(!chunk.is_empty());
+
+  assert_eq!(dominant_material(&chunk), Some(6));
+}
+
+#[test]
+fn test_unmapped_and_empty_chunks_use_default() {
+  let config = ice_and_rock().with_default(MaterialPhysics::new(0.4, 0.2));
+
+  assert_eq!(
+    config.for_mesh(&ground_chunk(1)),
+    MaterialPhysics::new(0.4, 0.2)
+  );
+  assert_eq!(dominant_material(&MeshOutput::new()), None);
+  assert_eq!(config.for_mesh(&MeshOutput::new()), config.default);
+}
+
+#[cfg(feature = "avian")]
+mod colliders {
+  use avian3d::prelude::Collider;
+  use bevy::ecs::system::RunSystemOnce;
+  use bevy::ecs::world::CommandQueue;
+  use voxel_plugin::octree::{OctreeConfig, OctreeNode};
+  use voxel_plugin::world::WorldId;
+
+  use super::*;
+  use crate::components::VoxelViewer;
+  use crate::resources::ChunkEntityMap;
+  use crate::systems::entities::spawn_chunk_entity;
+
+  /// Spawn a chunk at the origin of a world root placed at `root`.
+  fn spawn_at(world: &mut World, output: &MeshOutput, root: Vec3) -> Entity {
+    let mut meshes = Assets::<Mesh>::default();
+    let mut chunk_map = ChunkEntityMap::default();
+    let mut queue = CommandQueue::default();
+    let entity = spawn_chunk_entity(
+      &mut Commands::new(&mut queue, world),
+      &mut meshes,
+      Handle::default(),
+      &mut chunk_map,
+      None,
+      WorldId::new(),
+      OctreeNode::new(0, 0, 0, 0),
+      output,
+      0,
+      &OctreeConfig::default(),
+    );
+    queue.apply(world);
+
+    // Stand-in for transform propagation under the world root.
+    let local = *world.get::<Transform>(entity).unwrap();
+    let global = GlobalTransform::from_translation(root).mul_transform(local);
+    world.entity_mut(entity).insert(global);
+    entity
+  }
+
+  fn spawn(world: &mut World, output: &MeshOutput) -> Entity {
+    spawn_at(world, output, Vec3::ZERO)
+  }
+
+  fn update(world: &mut World) {
+    world.run_system_once(update_chunk_colliders).unwrap();
+  }
+
+  #[test]
+  fn test_non_empty_chunk_gets_collider() {
+    let mut world = World::new();
+    world.insert_resource(ChunkColliderConfig::default());
+    world.insert_resource(ice_and_rock());
+
+    let ground = spawn(&mut world, &ground_chunk(ICE));
+    let empty = spawn(&mut world, &MeshOutput::new());
+    update(&mut world);
+
+    assert!(world.get::<Collider>(ground).is_some());
+    assert_eq!(
+      world.get::<Friction>(ground).unwrap().dynamic_coefficient,
+      0.02
+    );
+    assert!(world.get::<Collider>(empty).is_none());
+    assert!(world.get::<ChunkColliderSource>(empty).is_none());
+  }
+
+  #[test]
+  fn test_distant_chunk_skips_collider() {
+    let mut world = World::new();
+    world.insert_resource(ChunkColliderConfig::default().with_max_distance(100.0));
+    world.spawn((
+      GlobalTransform::from_translation(Vec3::new(1000.0, 0.0, 0.0)),
+      VoxelViewer,
+    ));
+
+    let ground = spawn(&mut world, &ground_chunk(ROCK));
+    update(&mut world);
+
+    assert!(world.get::<Collider>(ground).is_none());
+  }
+
+  #[test]
+  fn test_collider_follows_viewer_distance() {
+    let mut world = World::new();
+    world.insert_resource(ChunkColliderConfig::default().with_max_distance(100.0));
+    let viewer = world
+      .spawn((
+        GlobalTransform::from_translation(Vec3::new(1000.0, 0.0, 0.0)),
+        VoxelViewer,
+      ))
+      .id();
+
+    let ground = spawn(&mut world, &ground_chunk(ROCK));
+    update(&mut world);
+    assert!(world.get::<Collider>(ground).is_none());
+
+    // Approaching the chunk builds its collider.
+    world
+      .entity_mut(viewer)
+      .insert(GlobalTransform::from_translation(Vec3::ZERO));
+    update(&mut world);
+    assert!(world.get::<Collider>(ground).is_some());
+    assert!(world.get::<Friction>(ground).is_some());
+
+    // Leaving again drops it.
+    world
+      .entity_mut(viewer)
+      .insert(GlobalTransform::from_translation(Vec3::new(
+        0.0, 0.0, -1000.0,
+      )));
+    update(&mut world);
+    assert!(world.get::<Collider>(ground).is_none());
+    assert!(world.get::<Friction>(ground).is_none());
+  }
+
+  #[test]
+  fn test_collider_distance_uses_root_transform() {
+    let mut world = World::new();
+    world.insert_resource(ChunkColliderConfig::default().with_max_distance(100.0));
+    world.spawn((GlobalTransform::from_translation(Vec3::ZERO), VoxelViewer));
+
+    // The chunk's local transform is near the viewer, but its world root is
+    // far away.
+    let moved = spawn_at(&mut world, &ground_chunk(ROCK), Vec3::new(1000.0, 0.0, 0.0));
+    let near = spawn(&mut world, &ground_chunk(ROCK));
+    update(&mut world);
+
+    assert!(world.get::<Collider>(moved).is_none());
+    assert!(world.get::<Collider>(near).is_some());
+  }
+}
@@ -197,6 +197,7 @@ fn test_bevy_presentation_consistency() {
     min_lod: 0,
     max_lod: 6,
     lod_exponent: 1.5,
+    lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
     world_bounds: None,
   };
 
@@ -223,9 +224,10 @@ fn test_bevy_presentation_consistency() {
       group_key: node.get_parent(config.max_lod).unwrap_or(*node),
       nodes_to_add: smallvec![*node],
       nodes_to_remove: SmallVec::new(),
+      culled: false,
     });
   }
-  let initial_chunks = process_transitions(world_id, &groups, &sampler, &world_leaves, &config);
+  let initial_chunks = process_transitions(world_id, &groups, &sampler, &world_leaves, &config, None);
 
   // Initialize mock presentation with nodes that have meshes
   let mut presentation = MockBevyPresentation::new();
@@ -275,6 +277,7 @@ fn test_bevy_presentation_consistency() {
     if pending.is_none() {
       let input = RefinementInput {
         viewer_pos,
+        view_frustum: None,
         config: config.clone(),
         prev_leaves: world_leaves.clone(),
         budget: RefinementBudget::DEFAULT,
@@ -302,6 +305,7 @@ fn test_bevy_presentation_consistency() {
           &sampler,
           &world_leaves,
           &config,
+          None,
         );
 
         pending = Some(PendingRefinement {
@@ -366,6 +370,7 @@ fn test_bevy_presentation_rapid_zoom() {
     min_lod: 0,
     max_lod: 6,
     lod_exponent: 1.5,
+    lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
     world_bounds: None,
   };
 
@@ -396,6 +401,7 @@ fn test_bevy_presentation_rapid_zoom() {
       for _iter in 0..20 {
         let input = RefinementInput {
           viewer_pos,
+          view_frustum: None,
           config: config.clone(),
           prev_leaves: world_leaves.clone(),
           budget: RefinementBudget::UNLIMITED,
@@ -421,6 +427,7 @@ fn test_bevy_presentation_rapid_zoom() {
           &sampler,
           &world_leaves,
           &config,
+          None,
         );
 
         presentation.apply_poll_result(
@@ -474,6 +481,7 @@ fn test_bevy_presentation_async_delay() {
     min_lod: 0,
     max_lod: 6,
     lod_exponent: 1.5,
+    lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
     world_bounds: None,
   };
 
@@ -527,6 +535,7 @@ fn test_bevy_presentation_async_delay() {
     if pending.is_none() {
       let input = RefinementInput {
         viewer_pos,
+        view_frustum: None,
         config: config.clone(),
         prev_leaves: world_leaves.clone(),
         budget: RefinementBudget::DEFAULT,
@@ -551,6 +560,7 @@ fn test_bevy_presentation_async_delay() {
           &sampler,
           &world_leaves,
           &config,
+          None,
         );
 
         // Variable delay: 1-5 frames
@@ -612,6 +622,7 @@ fn test_ghost_cells_with_async_gap() {
     min_lod: 0,
     max_lod: 6,
     lod_exponent: 1.5,
+    lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
     world_bounds: None,
   };
 
@@ -638,6 +649,7 @@ fn test_ghost_cells_with_async_gap() {
   for _iter in 0..20 {
     let input = RefinementInput {
       viewer_pos: close_pos,
+      view_frustum: None,
       config: config.clone(),
       prev_leaves: world_leaves.clone(),
       budget: RefinementBudget::UNLIMITED,
@@ -669,6 +681,7 @@ fn test_ghost_cells_with_async_gap() {
     &sampler,
     &leaves_after_subdivide,
     &config,
+    None,
   );
   let subdivide_ready_nodes: Vec<_> = ready_chunks.iter().map(|c| c.node).collect();
   println!(
@@ -684,6 +697,7 @@ fn test_ghost_cells_with_async_gap() {
   for _iter in 0..20 {
     let input = RefinementInput {
       viewer_pos: far_pos,
+      view_frustum: None,
       config: config.clone(),
       prev_leaves: world_leaves.clone(),
       budget: RefinementBudget::UNLIMITED,
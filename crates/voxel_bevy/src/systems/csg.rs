@@ -0,0 +1,168 @@
+//! Declarative CSG shapes composed into voxel worlds.
+//!
+//! Entities with a `CsgOp` and an `SdfSphere` or `SdfBox` become the scene
+//! brushes (`VoxelWorld::brushes`) of every `VoxelWorldRoot`, layered over the
+//! world's sampler. Brushes are not player edits: they are never saved with
+//! `VoxelEditSaveFile` or undone. Designers can carve caves or add platforms
+//! from a scene file without writing edit code:
+//!
+//! ```ignore
+//! commands.spawn((
+//!     SdfSphere { radius: 12.0 },
+//!     CsgOp::Subtract,
+//!     Transform::from_xyz(0.0, 20.0, 0.0),
+//! ));
+//! ```
+//!
+//! Shapes apply in ascending `CsgOrder` (0 when absent), ties broken by
+//! entity, so the result doesn't depend on query iteration order.
+
+use bevy::prelude::*;
+use voxel_plugin::{EditOp, SdfBrush};
+
+use crate::world::{apply_world_batch, VoxelWorldMaterial, VoxelWorldRoot, WorldChunkMap};
+
+/// How a CSG shape combines with the terrain.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+#[reflect(Component)]
+pub enum CsgOp {
+  /// Add the shape (`EditOp::Place`).
+  Union,
+  /// Carve the shape away (`EditOp::Remove`).
+  Subtract,
+  /// Keep only terrain inside the shape (`EditOp::Intersect`).
+  Intersect,
+}
+
+impl CsgOp {
+  pub fn edit_op(self) -> EditOp {
+    match self {
+      CsgOp::Union => EditOp::Place,
+      CsgOp::Subtract => EditOp::Remove,
+      CsgOp::Intersect => EditOp::Intersect,
+    }
+  }
+}
+
+/// Sphere shape centered on the entity's `GlobalTransform` translation.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component)]
+pub struct SdfSphere {
+  pub radius: f32,
+}
+
+/// Axis-aligned box centered on the entity's `GlobalTransform` translation.
+/// Rotation and scale are ignored.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component)]
+pub struct SdfBox {
+  pub half_extents: Vec3,
+}
+
+/// Explicit position of a shape in the CSG sequence; lower applies first.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[reflect(Component)]
+pub struct CsgOrder(pub i32);
+
+/// Shapes whose brush may differ from last frame's.
+type ChangedShape = (
+  With<CsgOp>,
+  Or<(
+    Changed<CsgOp>,
+    Changed<GlobalTransform>,
+    Changed<CsgOrder>,
+    Changed<SdfSphere>,
+    Changed<SdfBox>,
+  )>,
+);
+
+/// System composing all CSG shapes into the worlds' scene brushes.
+///
+/// Newly added worlds get the current shapes. When a shape is spawned,
+/// moved, changed or despawned, every world's brushes are rebuilt and the
+/// leaves they touch remeshed, with chunks replaced like in
+/// `update_voxel_worlds`.
+///
+/// Shape positions come from their `GlobalTransform`, converted into each
+/// world's local space. Schedule it in `PostUpdate` after
+/// `TransformSystems::Propagate`, so shapes spawned or moved this frame
+/// already have their final position.
+pub fn apply_csg_shapes(
+  mut commands: Commands,
+  mut meshes: ResMut<Assets<Mesh>>,
+  mut chunk_map: ResMut<WorldChunkMap>,
+  mut worlds: Query<(Entity, &mut VoxelWorldRoot, Option<&VoxelWorldMaterial>)>,
+  shapes: Query<(
+    Entity,
+    &CsgOp,
+    &GlobalTransform,
+    Option<&CsgOrder>,
+    Option<&SdfSphere>,
+    Option<&SdfBox>,
+  )>,
+  changed_shapes: Query<(), ChangedShape>,
+  mut removed_shapes: RemovedComponents<CsgOp>,
+) {
+  let shapes_changed = removed_shapes.read().count() > 0 || !changed_shapes.is_empty();
+  let mut ordered = None;
+
+  for (root_entity, mut root, material) in &mut worlds {
+    if !shapes_changed && !root.is_added() {
+      continue;
+    }
+
+    let ordered = ordered.get_or_insert_with(|| {
+      let mut ordered: Vec<_> = shapes
+        .iter()
+        .filter(|(.., sphere, cuboid)| sphere.is_some() || cuboid.is_some())
+        .collect();
+      ordered
+        .sort_by_key(|(entity, _, _, order, ..)| (order.copied().unwrap_or_default(), *entity));
+      ordered
+    });
+    let brushes: Vec<SdfBrush> = ordered
+      .iter()
+      .filter_map(|&(_, op, transform, _, sphere, cuboid)| {
+        let center = root
+          .world
+          .viewer_to_local(transform.translation().as_dvec3());
+        let op = op.edit_op();
+        match (sphere, cuboid) {
+          (Some(sphere), _) => Some(SdfBrush::Sphere {
+            center,
+            radius: sphere.radius as f64,
+            op,
+          }),
+          (None, Some(cuboid)) => Some(SdfBrush::Box {
+            center,
+            half_extents: cuboid.half_extents.as_dvec3(),
+            op,
+          }),
+          (None, None) => None,
+        }
+      })
+      .collect();
+    if root.world.brushes == brushes {
+      continue;
+    }
+    if root.world.set_brushes(brushes).is_empty() {
+      continue;
+    }
+
+    let world_id = root.id();
+    let batch = root.world.remesh_dirty();
+    apply_world_batch(
+      &mut commands,
+      &mut meshes,
+      &mut chunk_map,
+      root_entity,
+      world_id,
+      material,
+      batch,
+    );
+  }
+}
+
+#[cfg(test)]
+#[path = "csg_test.rs"]
+mod csg_test;
@@ -0,0 +1,123 @@
+//! Tests for declarative CSG shapes.
+
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use voxel_plugin::constants::coord_to_index;
+use voxel_plugin::octree::{DAabb3, OctreeConfig, OctreeNode};
+use voxel_plugin::sdf_samplers::GroundPlaneSampler;
+
+use super::{apply_csg_shapes, CsgOp, CsgOrder, SdfSphere};
+use crate::world::{VoxelWorldRoot, WorldChunkMap};
+
+fn csg_app() -> App {
+  let mut app = App::new();
+  app.add_plugins(TransformPlugin);
+  app.insert_resource(Assets::<Mesh>::default());
+  app.init_resource::<WorldChunkMap>();
+  app.add_systems(
+    PostUpdate,
+    apply_csg_shapes.after(TransformSystems::Propagate),
+  );
+  app
+}
+
+/// Ground at y = 14.3 with a single LOD 0 leaf at the origin.
+fn ground_world() -> VoxelWorldRoot {
+  let config = OctreeConfig {
+    world_bounds: Some(DAabb3::new(DVec3::ZERO, DVec3::splat(56.0))),
+    ..OctreeConfig::default()
+  };
+  let mut root = VoxelWorldRoot::new(config, Box::new(GroundPlaneSampler::new(14.3)));
+  root.world.leaves.insert(OctreeNode::new(0, 0, 0, 0));
+  root
+}
+
+fn sample_at(app: &App, world: Entity, x: usize, y: usize, z: usize) -> i8 {
+  let root = app.world().get::<VoxelWorldRoot>(world).unwrap();
+  let (volume, _) = root.world.sample_node_volume(&OctreeNode::new(0, 0, 0, 0));
+  volume[coord_to_index(x, y, z)]
+}
+
+#[test]
+fn test_subtract_sphere_carves_air_into_terrain() {
+  let mut app = csg_app();
+  app.world_mut().spawn((
+    SdfSphere { radius: 4.0 },
+    CsgOp::Subtract,
+    Transform::from_xyz(14.0, 14.3, 14.0),
+  ));
+  let world = app.world_mut().spawn(ground_world()).id();
+
+  app.update();
+
+  // Solid ground is air inside the sphere, untouched away from it
+  assert!(sample_at(&app, world, 14, 12, 14) >= 0);
+  assert!(sample_at(&app, world, 2, 12, 2) < 0);
+
+  // The shape is a scene brush, not a saved player edit
+  let root = app.world().get::<VoxelWorldRoot>(world).unwrap();
+  assert_eq!(root.world.brushes.len(), 1);
+  assert!(root.world.edits.is_empty());
+  assert_eq!(root.world.save_edits(), ground_world().world.save_edits());
+}
+
+#[test]
+fn test_shapes_apply_in_csg_order() {
+  let mut app = csg_app();
+  let center = Transform::from_xyz(14.0, 20.0, 14.0);
+  // Spawned first but ordered last: the placed sphere fills the hole
+  app
+    .world_mut()
+    .spawn((SdfSphere { radius: 4.0 }, CsgOp::Union, CsgOrder(1), center));
+  app
+    .world_mut()
+    .spawn((SdfSphere { radius: 2.0 }, CsgOp::Subtract, center));
+  let world = app.world_mut().spawn(ground_world()).id();
+
+  app.update();
+
+  let root = app.world().get::<VoxelWorldRoot>(world).unwrap();
+  assert_eq!(root.world.brushes.len(), 2);
+  assert!(sample_at(&app, world, 14, 20, 14) < 0);
+}
+
+#[test]
+fn test_shapes_spawned_after_world_are_applied_and_removed() {
+  let mut app = csg_app();
+  let world = app.world_mut().spawn(ground_world()).id();
+  app.update();
+  assert!(sample_at(&app, world, 14, 12, 14) < 0);
+
+  let shape = app
+    .world_mut()
+    .spawn((
+      SdfSphere { radius: 4.0 },
+      CsgOp::Subtract,
+      Transform::from_xyz(14.0, 14.3, 14.0),
+    ))
+    .id();
+  app.update();
+  assert!(sample_at(&app, world, 14, 12, 14) >= 0);
+  // The carved leaf was remeshed
+  let world_id = app.world().get::<VoxelWorldRoot>(world).unwrap().id();
+  assert!(app
+    .world()
+    .resource::<WorldChunkMap>()
+    .contains(world_id, &OctreeNode::new(0, 0, 0, 0)));
+
+  // Moving the shape moves the hole
+  app
+    .world_mut()
+    .get_mut::<Transform>(shape)
+    .unwrap()
+    .translation = Vec3::new(42.0, 14.3, 42.0);
+  app.update();
+  assert!(sample_at(&app, world, 14, 12, 14) < 0);
+  assert!(sample_at(&app, world, 42, 12, 42) >= 0);
+
+  app.world_mut().despawn(shape);
+  app.update();
+  assert!(sample_at(&app, world, 42, 12, 42) < 0);
+  let root = app.world().get::<VoxelWorldRoot>(world).unwrap();
+  assert!(root.world.brushes.is_empty());
+}
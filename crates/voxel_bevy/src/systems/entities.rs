@@ -1,14 +1,16 @@
 //! Entity management for voxel chunks.
 
 use bevy::asset::RenderAssetUsages;
-use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::mesh::{Indices, MeshVertexAttribute, PrimitiveTopology, VertexFormat};
 use bevy::pbr::Material;
 use bevy::prelude::*;
 use voxel_plugin::octree::{OctreeConfig, OctreeNode};
+use voxel_plugin::pipeline::PresentationHint;
 use voxel_plugin::types::MeshOutput;
 use voxel_plugin::world::WorldId;
 
 use crate::components::VoxelChunk;
+use crate::entity_queue::EntityPool;
 use crate::resources::ChunkEntityMap;
 use crate::world::WorldChunkMap;
 
@@ -16,10 +18,19 @@ use crate::world::WorldChunkMap;
 /// This uses Bevy's standard ATTRIBUTE_COLOR for compatibility with
 /// ExtendedMaterial and GPU batching.
 
+/// Blend weights of materials 4-7, present only on meshes generated with
+/// `MeshConfig::material_count > 4`.
+pub const ATTRIBUTE_MATERIAL_WEIGHTS_HI: MeshVertexAttribute = MeshVertexAttribute::new(
+  "Vertex_MaterialWeightsHi",
+  0x766f_7801,
+  VertexFormat::Float32x4,
+);
+
 /// Spawn a mesh entity for an octree node.
 ///
 /// If `world_chunk_map` is provided, the chunk is also registered in the
-/// world-aware chunk map for multi-world support.
+/// world-aware chunk map for multi-world support. `timing_us` is the mesh
+/// generation time from `ReadyChunk::timing_us`, kept for diagnostics.
 pub fn spawn_chunk_entity(
   commands: &mut Commands,
   meshes: &mut Assets<Mesh>,
@@ -29,6 +40,41 @@ pub fn spawn_chunk_entity(
   world_id: WorldId,
   node: OctreeNode,
   output: &MeshOutput,
+  timing_us: u64,
+  config: &OctreeConfig,
+) -> Entity {
+  spawn_chunk_entity_with_hint(
+    commands,
+    meshes,
+    material,
+    chunk_map,
+    world_chunk_map,
+    None,
+    world_id,
+    node,
+    output,
+    timing_us,
+    PresentationHint::Immediate,
+    config,
+  )
+}
+
+/// Like [`spawn_chunk_entity`], with the chunk's `ReadyChunk::hint` (read by
+/// the chunk fade systems). With a `pool`, a hidden pooled entity is reused
+/// when one is available.
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_chunk_entity_with_hint(
+  commands: &mut Commands,
+  meshes: &mut Assets<Mesh>,
+  material: Handle<StandardMaterial>,
+  chunk_map: &mut ChunkEntityMap,
+  world_chunk_map: Option<&mut WorldChunkMap>,
+  pool: Option<&mut EntityPool>,
+  world_id: WorldId,
+  node: OctreeNode,
+  output: &MeshOutput,
+  timing_us: u64,
+  hint: PresentationHint,
   config: &OctreeConfig,
 ) -> Entity {
   let mesh = mesh_output_to_bevy(output);
@@ -54,19 +100,29 @@ pub fn spawn_chunk_entity(
   // Transform position = node_min (matches C# OctreeTransform.GetWorldPosition)
   // Mesh vertices are in local [0, ~31] coords, scaled by voxel_size via transform.
   // No offset needed - sample 0 is at node_min, mesh vertex 0 should appear at node_min.
-  let entity = commands
-    .spawn((
-      Mesh3d(mesh_handle),
-      MeshMaterial3d(material),
-      Transform::from_translation(Vec3::new(
-        world_min.x as f32,
-        world_min.y as f32,
-        world_min.z as f32,
-      ))
-      .with_scale(Vec3::splat(voxel_size)),
-      VoxelChunk { world_id, node },
+  let bundle = (
+    Mesh3d(mesh_handle),
+    MeshMaterial3d(material),
+    Transform::from_translation(Vec3::new(
+      world_min.x as f32,
+      world_min.y as f32,
+      world_min.z as f32,
     ))
-    .id();
+    .with_scale(Vec3::splat(voxel_size)),
+    VoxelChunk {
+      world_id,
+      node,
+      timing_us,
+      hint,
+    },
+  );
+  let entity = match pool {
+    Some(pool) => pool.spawn(commands, bundle),
+    None => commands.spawn(bundle).id(),
+  };
+
+  #[cfg(feature = "avian")]
+  crate::physics::queue_chunk_collider(commands, entity, output);
 
   chunk_map.insert(node, entity);
   if let Some(wcm) = world_chunk_map {
@@ -88,6 +144,40 @@ pub fn spawn_custom_material_chunk_entity<M: Material>(
   world_id: WorldId,
   node: OctreeNode,
   output: &MeshOutput,
+  timing_us: u64,
+  config: &OctreeConfig,
+) -> Entity {
+  spawn_custom_material_chunk_entity_with_hint(
+    commands,
+    meshes,
+    material,
+    chunk_map,
+    world_chunk_map,
+    None,
+    world_id,
+    node,
+    output,
+    timing_us,
+    PresentationHint::Immediate,
+    config,
+  )
+}
+
+/// Like [`spawn_custom_material_chunk_entity`], with the chunk's hint and
+/// an optional `pool` as in [`spawn_chunk_entity_with_hint`].
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_custom_material_chunk_entity_with_hint<M: Material>(
+  commands: &mut Commands,
+  meshes: &mut Assets<Mesh>,
+  material: Handle<M>,
+  chunk_map: &mut ChunkEntityMap,
+  world_chunk_map: Option<&mut WorldChunkMap>,
+  pool: Option<&mut EntityPool>,
+  world_id: WorldId,
+  node: OctreeNode,
+  output: &MeshOutput,
+  timing_us: u64,
+  hint: PresentationHint,
   config: &OctreeConfig,
 ) -> Entity {
   let mesh = mesh_output_to_bevy(output);
@@ -96,19 +186,29 @@ pub fn spawn_custom_material_chunk_entity<M: Material>(
   let world_min = config.get_node_min(&node);
   let voxel_size = config.get_voxel_size(node.lod) as f32;
 
-  let entity = commands
-    .spawn((
-      Mesh3d(mesh_handle),
-      MeshMaterial3d(material),
-      Transform::from_translation(Vec3::new(
-        world_min.x as f32,
-        world_min.y as f32,
-        world_min.z as f32,
-      ))
-      .with_scale(Vec3::splat(voxel_size)),
-      VoxelChunk { world_id, node },
+  let bundle = (
+    Mesh3d(mesh_handle),
+    MeshMaterial3d(material),
+    Transform::from_translation(Vec3::new(
+      world_min.x as f32,
+      world_min.y as f32,
+      world_min.z as f32,
     ))
-    .id();
+    .with_scale(Vec3::splat(voxel_size)),
+    VoxelChunk {
+      world_id,
+      node,
+      timing_us,
+      hint,
+    },
+  );
+  let entity = match pool {
+    Some(pool) => pool.spawn(commands, bundle),
+    None => commands.spawn(bundle).id(),
+  };
+
+  #[cfg(feature = "avian")]
+  crate::physics::queue_chunk_collider(commands, entity, output);
 
   chunk_map.insert(node, entity);
   if let Some(wcm) = world_chunk_map {
@@ -149,7 +249,17 @@ pub fn mesh_output_to_bevy(output: &MeshOutput) -> Mesh {
   mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
   // Material blend weights stored as vertex color (RGBA = 4 layer weights)
   mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, material_weights);
+  if !output.material_weights_hi.is_empty() {
+    mesh.insert_attribute(
+      ATTRIBUTE_MATERIAL_WEIGHTS_HI,
+      output.material_weights_hi.clone(),
+    );
+  }
   mesh.insert_indices(Indices::U16(output.indices.clone()));
 
   mesh
 }
+
+#[cfg(test)]
+#[path = "entities_test.rs"]
+mod entities_test;
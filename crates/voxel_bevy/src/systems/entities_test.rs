@@ -0,0 +1,39 @@
+//! Tests for chunk mesh conversion.
+
+use voxel_plugin::constants::{coord_to_index, SAMPLE_SIZE, SAMPLE_SIZE_CB};
+use voxel_plugin::surface_nets;
+use voxel_plugin::types::{sdf_conversion, MeshConfig};
+
+use super::*;
+
+/// Flat ground at y = 16.5 of material 5.
+fn ground(config: &MeshConfig) -> MeshOutput {
+  let mut volume = [0i8; SAMPLE_SIZE_CB];
+  for x in 0..SAMPLE_SIZE {
+    for y in 0..SAMPLE_SIZE {
+      for z in 0..SAMPLE_SIZE {
+        volume[coord_to_index(x, y, z)] = sdf_conversion::to_storage(y as f32 - 16.5, 1.0);
+      }
+    }
+  }
+  surface_nets::generate(&volume, &[5; SAMPLE_SIZE_CB], config)
+}
+
+#[test]
+fn test_high_material_weights_become_attribute() {
+  let output = ground(&MeshConfig::default().with_material_count(8));
+  assert_eq!(output.material_weights_hi.len(), output.vertices.len());
+
+  let mesh = mesh_output_to_bevy(&output);
+  let weights = mesh.attribute(ATTRIBUTE_MATERIAL_WEIGHTS_HI).unwrap();
+  assert_eq!(weights.len(), output.vertices.len());
+}
+
+#[test]
+fn test_default_mesh_has_no_high_weights() {
+  let output = ground(&MeshConfig::default());
+  assert!(output.material_weights_hi.is_empty());
+
+  let mesh = mesh_output_to_bevy(&output);
+  assert!(mesh.attribute(ATTRIBUTE_MATERIAL_WEIGHTS_HI).is_none());
+}
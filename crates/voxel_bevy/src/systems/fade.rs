@@ -0,0 +1,211 @@
+//! Dithered crossfades for LOD transitions.
+//!
+//! Chunks spawned with a `FadeIn`/`FadeOut` presentation hint (subdivide
+//! children and merged parents) fade in, while the nodes they replace are
+//! given a fade-out [`ChunkFade`] and fade out over the same duration before
+//! being despawned:
+//!
+//! ```text
+//! subdivide: parent  1 → 0 (despawn)   children 0 → 1
+//! merge:     children 1 → 0 (despawn)  parent   0 → 1
+//! ```
+//!
+//! Fading chunks render with a [`DitherFadeMaterial`] copy of their
+//! `StandardMaterial`, which discards pixels against a Bayer pattern instead
+//! of alpha blending, so they stay opaque, write depth and keep their
+//! prepass. Fade-ins and fade-outs draw complementary patterns. The original
+//! handle is restored once a fade-in completes. Chunks with custom materials
+//! keep their material but still follow the fade timing.
+
+use bevy::asset::embedded_asset;
+use bevy::pbr::{ExtendedMaterial, MaterialExtension, MaterialPlugin};
+use bevy::prelude::*;
+use bevy::render::render_resource::{AsBindGroup, ShaderType};
+use voxel_plugin::pipeline::PresentationHint;
+
+use crate::components::{ChunkFade, FadeDirection, VoxelChunk};
+
+/// Path to the embedded dither fade shader.
+const DITHER_FADE_SHADER_PATH: &str = "embedded://voxel_bevy/systems/shaders/dither_fade.wgsl";
+
+/// Screen-door fade extension for StandardMaterial.
+///
+/// Uses binding 100 to avoid conflicts with StandardMaterial bindings.
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone, Default)]
+pub struct DitherFade {
+  #[uniform(100)]
+  pub params: DitherFadeParams,
+}
+
+/// Shader parameters for [`DitherFade`].
+#[derive(ShaderType, Debug, Clone, Copy, Default, PartialEq)]
+pub struct DitherFadeParams {
+  /// Fraction of pixels drawn.
+  pub alpha: f32,
+  /// 1.0 for fade-outs, which draw the pixels a fade-in at `1 - alpha`
+  /// leaves out.
+  pub complement: f32,
+  pub _padding: Vec2,
+}
+
+impl DitherFadeParams {
+  fn for_fade(fade: &ChunkFade) -> Self {
+    Self {
+      alpha: fade.alpha(),
+      complement: match fade.direction {
+        FadeDirection::In => 0.0,
+        FadeDirection::Out => 1.0,
+      },
+      _padding: Vec2::ZERO,
+    }
+  }
+}
+
+impl MaterialExtension for DitherFade {
+  fn fragment_shader() -> bevy::shader::ShaderRef {
+    DITHER_FADE_SHADER_PATH.into()
+  }
+
+  fn prepass_fragment_shader() -> bevy::shader::ShaderRef {
+    DITHER_FADE_SHADER_PATH.into()
+  }
+
+  fn deferred_fragment_shader() -> bevy::shader::ShaderRef {
+    DITHER_FADE_SHADER_PATH.into()
+  }
+}
+
+/// Material fading chunks render with.
+pub type DitherFadeMaterial = ExtendedMaterial<StandardMaterial, DitherFade>;
+
+/// Plugin registering [`DitherFadeMaterial`].
+pub struct ChunkFadePlugin;
+
+impl Plugin for ChunkFadePlugin {
+  fn build(&self, app: &mut App) {
+    embedded_asset!(app, "shaders/dither_fade.wgsl");
+    app.add_plugins(MaterialPlugin::<DitherFadeMaterial>::default());
+  }
+}
+
+/// Resource configuring LOD transition fades.
+///
+/// Add [`ChunkFadePlugin`], insert this resource and add
+/// [`begin_chunk_fades`] and [`animate_chunk_fades`] to a schedule to enable
+/// fading.
+#[derive(Resource, Clone, Debug)]
+pub struct ChunkFadeConfig {
+  /// Whether newly spawned chunks fade in.
+  pub enabled: bool,
+  /// Fade length in seconds.
+  pub duration: f32,
+}
+
+impl Default for ChunkFadeConfig {
+  fn default() -> Self {
+    Self {
+      enabled: true,
+      duration: 0.25,
+    }
+  }
+}
+
+impl ChunkFadeConfig {
+  pub fn with_duration(mut self, duration: f32) -> Self {
+    self.duration = duration;
+    self
+  }
+}
+
+/// System starting a fade-in on newly spawned chunks with a fade hint.
+pub fn begin_chunk_fades(
+  mut commands: Commands,
+  config: Option<Res<ChunkFadeConfig>>,
+  chunks: Query<(Entity, &VoxelChunk), Added<VoxelChunk>>,
+) {
+  let Some(config) = config else {
+    return;
+  };
+  if !config.enabled {
+    return;
+  }
+
+  for (entity, chunk) in &chunks {
+    match chunk.hint {
+      PresentationHint::Immediate => {}
+      PresentationHint::FadeIn { .. } | PresentationHint::FadeOut { .. } => {
+        commands
+          .entity(entity)
+          .insert(ChunkFade::fade_in(config.duration));
+      }
+    }
+  }
+}
+
+/// System advancing chunk fades, updating the dither coverage and finishing
+/// completed fades (fade-ins restore their material, fade-outs despawn).
+pub fn animate_chunk_fades(
+  mut commands: Commands,
+  time: Res<Time>,
+  materials: Res<Assets<StandardMaterial>>,
+  mut dither_materials: ResMut<Assets<DitherFadeMaterial>>,
+  mut fades: Query<(
+    Entity,
+    &mut ChunkFade,
+    Option<&MeshMaterial3d<StandardMaterial>>,
+    Option<&MeshMaterial3d<DitherFadeMaterial>>,
+  )>,
+) {
+  let delta = time.delta_secs();
+
+  for (entity, mut fade, material, dithered) in &mut fades {
+    fade.elapsed += delta;
+    let params = DitherFadeParams::for_fade(&fade);
+
+    if let Some(dithered) = dithered {
+      if let Some(dither) = dither_materials.get_mut(&dithered.0) {
+        dither.extension.params = params;
+      }
+
+      if fade.finished() && fade.direction == FadeDirection::In {
+        if let Some(original) = fade.original.take() {
+          commands
+            .entity(entity)
+            .remove::<MeshMaterial3d<DitherFadeMaterial>>()
+            .insert(MeshMaterial3d(original));
+        }
+      }
+    } else if let Some(material) = material.filter(|_| !fade.finished()) {
+      if let Some(base) = materials.get(&material.0) {
+        fade.original = Some(material.0.clone());
+        // Masked, so that the prepass runs the dither shader as well
+        let dither = dither_materials.add(DitherFadeMaterial {
+          base: StandardMaterial {
+            alpha_mode: AlphaMode::Mask(0.5),
+            ..base.clone()
+          },
+          extension: DitherFade { params },
+        });
+        commands
+          .entity(entity)
+          .remove::<MeshMaterial3d<StandardMaterial>>()
+          .insert(MeshMaterial3d(dither));
+      }
+    }
+
+    if fade.finished() {
+      match fade.direction {
+        FadeDirection::In => {
+          commands.entity(entity).remove::<ChunkFade>();
+        }
+        FadeDirection::Out => {
+          commands.entity(entity).despawn();
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+#[path = "fade_test.rs"]
+mod fade_test;
@@ -0,0 +1,139 @@
+//! Tests for LOD transition fades.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use voxel_plugin::octree::OctreeNode;
+use voxel_plugin::pipeline::PresentationHint;
+use voxel_plugin::world::WorldId;
+
+use super::{animate_chunk_fades, begin_chunk_fades, ChunkFadeConfig, DitherFadeMaterial};
+use crate::components::{ChunkFade, FadeDirection, VoxelChunk};
+
+fn fade_app() -> App {
+  let mut app = App::new();
+  app.insert_resource(Time::<()>::default());
+  app.insert_resource(Assets::<StandardMaterial>::default());
+  app.insert_resource(Assets::<DitherFadeMaterial>::default());
+  app.insert_resource(ChunkFadeConfig::default().with_duration(0.5));
+  app.add_systems(Update, (begin_chunk_fades, animate_chunk_fades).chain());
+  app
+}
+
+fn advance(app: &mut App, millis: u64) {
+  app
+    .world_mut()
+    .resource_mut::<Time>()
+    .advance_by(Duration::from_millis(millis));
+  app.update();
+}
+
+fn spawn_chunk(app: &mut App, hint: PresentationHint) -> Entity {
+  let material = app
+    .world_mut()
+    .resource_mut::<Assets<StandardMaterial>>()
+    .add(StandardMaterial::default());
+  app
+    .world_mut()
+    .spawn((
+      MeshMaterial3d(material),
+      VoxelChunk {
+        world_id: WorldId::new(),
+        node: OctreeNode::new(0, 0, 0, 0),
+        timing_us: 0,
+        hint,
+      },
+    ))
+    .id()
+}
+
+fn dither(app: &App, entity: Entity) -> &DitherFadeMaterial {
+  let handle = &app
+    .world()
+    .get::<MeshMaterial3d<DitherFadeMaterial>>(entity)
+    .unwrap()
+    .0;
+  let materials = app.world().resource::<Assets<DitherFadeMaterial>>();
+  materials.get(handle).unwrap()
+}
+
+fn alpha(app: &App, entity: Entity) -> f32 {
+  dither(app, entity).extension.params.alpha
+}
+
+#[test]
+fn test_fade_out_chunk_despawned_after_duration() {
+  let mut app = fade_app();
+  let chunk = spawn_chunk(&mut app, PresentationHint::Immediate);
+  app
+    .world_mut()
+    .entity_mut(chunk)
+    .insert(ChunkFade::fade_out(0.5));
+
+  advance(&mut app, 200);
+  assert!(app.world().get_entity(chunk).is_ok(), "despawned early");
+  let mid_alpha = alpha(&app, chunk);
+  assert!(
+    (mid_alpha - 0.6).abs() < 1e-3,
+    "alpha after 40% of a fade-out: {}",
+    mid_alpha
+  );
+  // Dithered rather than blended, drawing the complement of fade-ins
+  let material = dither(&app, chunk);
+  assert_eq!(material.base.alpha_mode, AlphaMode::Mask(0.5));
+  assert_eq!(material.extension.params.complement, 1.0);
+
+  advance(&mut app, 200);
+  assert!(app.world().get_entity(chunk).is_ok(), "despawned early");
+
+  advance(&mut app, 200);
+  assert!(
+    app.world().get_entity(chunk).is_err(),
+    "fade-out chunk should be despawned once the duration elapses"
+  );
+}
+
+#[test]
+fn test_fade_in_hint_restores_original_material() {
+  let mut app = fade_app();
+  let group_key = OctreeNode::new(0, 0, 0, 1);
+  let chunk = spawn_chunk(&mut app, PresentationHint::FadeIn { group_key });
+  let original = app
+    .world()
+    .get::<MeshMaterial3d<StandardMaterial>>(chunk)
+    .unwrap()
+    .0
+    .clone();
+
+  // A zero-length first frame swaps in the dithered material at alpha 0
+  advance(&mut app, 0);
+  advance(&mut app, 100);
+  let fade = app.world().get::<ChunkFade>(chunk).unwrap();
+  assert_eq!(fade.direction, FadeDirection::In);
+  assert!(alpha(&app, chunk) < 0.5);
+
+  advance(&mut app, 500);
+  assert!(app.world().get::<ChunkFade>(chunk).is_none());
+  assert_eq!(
+    app
+      .world()
+      .get::<MeshMaterial3d<StandardMaterial>>(chunk)
+      .unwrap()
+      .0,
+    original
+  );
+  assert!(app
+    .world()
+    .get::<MeshMaterial3d<DitherFadeMaterial>>(chunk)
+    .is_none());
+}
+
+#[test]
+fn test_immediate_hint_does_not_fade() {
+  let mut app = fade_app();
+  let chunk = spawn_chunk(&mut app, PresentationHint::Immediate);
+
+  advance(&mut app, 100);
+
+  assert!(app.world().get::<ChunkFade>(chunk).is_none());
+}
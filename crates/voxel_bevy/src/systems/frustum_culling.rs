@@ -0,0 +1,96 @@
+//! Chunk visibility from exact octree node bounds.
+//!
+//! Bevy culls each mesh by its own AABB, which is tight for fine chunks but
+//! coarse chunks cover huge regions and survive culling from most angles.
+//! This system tests every chunk's octree node bounds (from
+//! `OctreeConfig::get_node_aabb`, placed by the world root's
+//! `GlobalTransform`) against each viewer frustum and drops the ones outside
+//! it from that viewer's `VisibleEntities`.
+//!
+//! `Visibility` is left alone, and so are light views: chunks culled from a
+//! camera still cast shadows into it. Chunks outside every viewer frustum get
+//! the [`ChunkFrustumCulled`] marker.
+//!
+//! Chunks are visited through `WorldChunkMap`, one world at a time, so the
+//! node → entity lookup needs no per-chunk query filtering.
+
+use std::any::TypeId;
+use std::collections::HashSet;
+
+use bevy::camera::primitives::{Aabb, Frustum};
+use bevy::camera::visibility::VisibleEntities;
+use bevy::math::Affine3A;
+use bevy::prelude::*;
+
+use crate::components::{VoxelChunk, VoxelViewer};
+use crate::world::{VoxelWorldRoot, WorldChunkMap};
+
+/// Marker on chunks whose node bounds lie outside every viewer frustum.
+///
+/// Maintained by [`cull_chunks_to_frustum`]; informational only.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct ChunkFrustumCulled;
+
+/// System removing chunks outside a viewer's frustum from its
+/// `VisibleEntities`.
+///
+/// Uses the `Frustum` of each `VoxelViewer` entity; does nothing when no
+/// viewer has one. Schedule it in `PostUpdate` after
+/// `VisibilitySystems::CheckVisibility`, which rebuilds `VisibleEntities`
+/// every frame.
+pub fn cull_chunks_to_frustum(
+  mut commands: Commands,
+  mut viewers: Query<(&Frustum, Option<&mut VisibleEntities>), With<VoxelViewer>>,
+  roots: Query<(&VoxelWorldRoot, Option<&GlobalTransform>)>,
+  chunk_map: Res<WorldChunkMap>,
+  chunks: Query<Has<ChunkFrustumCulled>, With<VoxelChunk>>,
+) {
+  if viewers.is_empty() {
+    return;
+  }
+
+  let mut outside: Vec<HashSet<Entity>> = vec![HashSet::new(); viewers.iter().count()];
+  for (root, transform) in &roots {
+    let Some(world_chunks) = chunk_map.get_world_chunks(root.id()) else {
+      continue;
+    };
+    let config = root.config();
+    let world_from_local = transform.map_or(Affine3A::IDENTITY, GlobalTransform::affine);
+
+    for (node, &entity) in world_chunks {
+      let Ok(culled) = chunks.get(entity) else {
+        continue;
+      };
+
+      let bounds = config.get_node_aabb(node);
+      let aabb = Aabb::from_min_max(bounds.min.as_vec3(), bounds.max.as_vec3());
+      let mut in_view = false;
+      for ((frustum, _), outside) in viewers.iter().zip(&mut outside) {
+        if frustum.intersects_obb(&aabb, &world_from_local, true, false) {
+          in_view = true;
+        } else {
+          outside.insert(entity);
+        }
+      }
+
+      if in_view && culled {
+        commands.entity(entity).remove::<ChunkFrustumCulled>();
+      } else if !in_view && !culled {
+        commands.entity(entity).insert(ChunkFrustumCulled);
+      }
+    }
+  }
+
+  for ((_, visible), outside) in viewers.iter_mut().zip(&outside) {
+    let Some(mut visible) = visible else {
+      continue;
+    };
+    visible
+      .get_mut(TypeId::of::<Mesh3d>())
+      .retain(|entity| !outside.contains(entity));
+  }
+}
+
+#[cfg(test)]
+#[path = "frustum_culling_test.rs"]
+mod frustum_culling_test;
@@ -0,0 +1,139 @@
+//! Tests for frustum-based chunk visibility.
+
+use std::any::TypeId;
+
+use bevy::camera::primitives::Frustum;
+use bevy::camera::visibility::VisibleEntities;
+use bevy::prelude::*;
+use voxel_plugin::octree::{OctreeConfig, OctreeNode};
+use voxel_plugin::pipeline::PresentationHint;
+use voxel_plugin::sdf_samplers::GroundPlaneSampler;
+
+use super::{cull_chunks_to_frustum, ChunkFrustumCulled};
+use crate::components::{VoxelChunk, VoxelViewer};
+use crate::world::{VoxelWorldRoot, WorldChunkMap};
+
+/// Perspective frustum at the origin looking down -Z.
+fn forward_frustum() -> Frustum {
+  let clip_from_view = Mat4::perspective_infinite_reverse_rh(45f32.to_radians(), 1.0, 0.1);
+  let view_from_world = Mat4::look_to_rh(Vec3::ZERO, Vec3::NEG_Z, Vec3::Y);
+  Frustum::from_clip_from_world(&(clip_from_view * view_from_world))
+}
+
+fn spawn_chunk(app: &mut App, root: &VoxelWorldRoot, node: OctreeNode) -> Entity {
+  let world_id = root.id();
+  let entity = app
+    .world_mut()
+    .spawn((
+      Visibility::Inherited,
+      VoxelChunk {
+        world_id,
+        node,
+        timing_us: 0,
+        hint: PresentationHint::Immediate,
+      },
+    ))
+    .id();
+  app
+    .world_mut()
+    .resource_mut::<WorldChunkMap>()
+    .insert(world_id, node, entity);
+  entity
+}
+
+/// Viewer with `forward_frustum` whose `VisibleEntities` hold `chunks`, as
+/// `check_visibility` would leave them.
+fn spawn_viewer(app: &mut App, chunks: &[Entity]) -> Entity {
+  let mut visible = VisibleEntities::default();
+  visible
+    .get_mut(TypeId::of::<Mesh3d>())
+    .extend_from_slice(chunks);
+  app
+    .world_mut()
+    .spawn((forward_frustum(), visible, VoxelViewer))
+    .id()
+}
+
+fn visible_meshes(app: &App, viewer: Entity) -> Vec<Entity> {
+  app
+    .world()
+    .get::<VisibleEntities>(viewer)
+    .unwrap()
+    .get(TypeId::of::<Mesh3d>())
+    .to_vec()
+}
+
+#[test]
+fn test_chunk_behind_camera_culled() {
+  let mut app = App::new();
+  app.init_resource::<WorldChunkMap>();
+  app.add_systems(Update, cull_chunks_to_frustum);
+
+  let root = VoxelWorldRoot::new(
+    OctreeConfig::default(),
+    Box::new(GroundPlaneSampler::new(0.0)),
+  );
+  // 28-unit nodes: one straddling the view axis ahead, one far behind
+  let ahead = spawn_chunk(&mut app, &root, OctreeNode::new(-1, -1, -3, 0));
+  let behind = spawn_chunk(&mut app, &root, OctreeNode::new(0, 0, 5, 0));
+  app.world_mut().spawn(root);
+  let viewer = spawn_viewer(&mut app, &[ahead, behind]);
+
+  app.update();
+
+  assert_eq!(visible_meshes(&app, viewer), vec![ahead]);
+  let world = app.world();
+  assert!(world.get::<ChunkFrustumCulled>(ahead).is_none());
+  assert!(world.get::<ChunkFrustumCulled>(behind).is_some());
+  // Visibility stays untouched, so the chunk still casts shadows.
+  assert_eq!(
+    world.get::<Visibility>(behind),
+    Some(&Visibility::Inherited)
+  );
+}
+
+#[test]
+fn test_culling_uses_root_transform() {
+  let mut app = App::new();
+  app.init_resource::<WorldChunkMap>();
+  app.add_systems(Update, cull_chunks_to_frustum);
+
+  let root = VoxelWorldRoot::new(
+    OctreeConfig::default(),
+    Box::new(GroundPlaneSampler::new(0.0)),
+  );
+  // Behind the camera in world-local space, but the root is moved forward.
+  let chunk = spawn_chunk(&mut app, &root, OctreeNode::new(0, 0, 5, 0));
+  app.world_mut().spawn((
+    root,
+    GlobalTransform::from_translation(Vec3::new(0.0, 0.0, -400.0)),
+  ));
+  let viewer = spawn_viewer(&mut app, &[chunk]);
+
+  app.update();
+
+  assert_eq!(visible_meshes(&app, viewer), vec![chunk]);
+  assert!(app.world().get::<ChunkFrustumCulled>(chunk).is_none());
+}
+
+#[test]
+fn test_no_viewer_leaves_visibility() {
+  let mut app = App::new();
+  app.init_resource::<WorldChunkMap>();
+  app.add_systems(Update, cull_chunks_to_frustum);
+
+  let root = VoxelWorldRoot::new(
+    OctreeConfig::default(),
+    Box::new(GroundPlaneSampler::new(0.0)),
+  );
+  let behind = spawn_chunk(&mut app, &root, OctreeNode::new(0, 0, 5, 0));
+  app.world_mut().spawn(root);
+
+  app.update();
+
+  assert_eq!(
+    app.world().get::<Visibility>(behind),
+    Some(&Visibility::Inherited)
+  );
+  assert!(app.world().get::<ChunkFrustumCulled>(behind).is_none());
+}
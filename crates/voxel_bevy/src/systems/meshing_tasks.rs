@@ -0,0 +1,503 @@
+//! Chunk meshing on Bevy's `AsyncComputeTaskPool`.
+//!
+//! An alternative to `AsyncPipeline` for apps that want meshing to share
+//! Bevy's task pools instead of a separate rayon pool. Each transition group
+//! becomes one task running the pipeline stages for its nodes:
+//!
+//! ```text
+//! enqueue ──► Task per group: presample_node → mesh_node → compose → present
+//!                 │
+//!                 ▼ poll_meshing_tasks (every frame)
+//!             completed queue ──► ≤ max_groups_per_frame applied per frame,
+//!                                 within max_ms_per_frame
+//!                                 (spawn chunks, despawn nodes_to_remove)
+//! ```
+//!
+//! Tasks finish in any order; applying a bounded number of groups per frame
+//! keeps a burst of finished tasks from landing as one frame spike. Each
+//! task is tagged with a generation, and a finished group is dropped if one
+//! of its nodes was enqueued again since, so an older result never lands
+//! over a newer one. Large
+//! groups (such as the initial leaves of a world) are additionally sliced by
+//! time: once `max_ms_per_frame` is used up, the rest of the group's chunks
+//! wait for the next frame. Chunks of a sliced group that replaces nodes stay
+//! hidden until the whole group is in, so the old nodes are never drawn over
+//! them.
+//!
+//! Chunks use `StandardMaterial` by default; `VoxelMeshingTasks<M>` with
+//! [`poll_custom_material_meshing_tasks`] spawns them with any other
+//! `Material`.
+//!
+//! With an [`AdaptiveGroupBudget`], `max_groups_per_frame` is retuned every
+//! frame from the measured work per applied group (its meshing time in the
+//! task plus the time spent spawning it), so fast machines drain the queue
+//! quickly and slow ones stop hitching.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bevy::pbr::Material;
+use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task};
+use smallvec::SmallVec;
+use voxel_plugin::octree::{OctreeConfig, OctreeNode, TransitionGroup};
+use voxel_plugin::pipeline::composition::compose;
+use voxel_plugin::pipeline::meshing::mesh_node;
+use voxel_plugin::pipeline::presample::presample_node;
+use voxel_plugin::pipeline::presentation::present;
+use voxel_plugin::pipeline::{
+  compute_neighbor_mask, node_mesh_config, MeshInput, ReadyChunk, VolumeSampler, WorkSource,
+};
+use voxel_plugin::types::MeshConfig;
+use voxel_plugin::world::WorldId;
+// WASM compat: std::time::Instant panics on wasm32
+use web_time::Instant;
+
+use super::entities::{despawn_chunk_entity, spawn_custom_material_chunk_entity_with_hint};
+use crate::resources::ChunkEntityMap;
+use crate::world::WorldChunkMap;
+
+/// A transition group whose meshes are ready to be applied.
+struct MeshedGroup {
+  world_id: WorldId,
+  /// Generation the group was enqueued with.
+  generation: u64,
+  config: Arc<OctreeConfig>,
+  nodes_to_add: SmallVec<[OctreeNode; 8]>,
+  nodes_to_remove: SmallVec<[OctreeNode; 8]>,
+  /// Chunks not spawned yet.
+  chunks: VecDeque<ReadyChunk>,
+  /// Chunks spawned in earlier frames and hidden until the group completes.
+  hidden: Vec<Entity>,
+  /// Whether some of the chunks were spawned in earlier frames.
+  started: bool,
+  /// Time the task spent meshing the group, in milliseconds.
+  mesh_ms: f32,
+}
+
+impl MeshedGroup {
+  fn nodes(&self) -> impl Iterator<Item = &OctreeNode> {
+    self.nodes_to_add.iter().chain(&self.nodes_to_remove)
+  }
+}
+
+/// Auto-tuning of `VoxelMeshingTasks::max_groups_per_frame`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdaptiveGroupBudget {
+  /// Work to apply per frame, in milliseconds: the groups' meshing time in
+  /// their tasks plus the time spent spawning their chunks. Keep it below
+  /// `VoxelMeshingTasks::max_ms_per_frame`, or the time slice decides
+  /// instead.
+  pub target_ms: f32,
+  /// Smallest budget the tuning goes down to.
+  pub min_groups: usize,
+  /// Largest budget the tuning goes up to.
+  pub max_groups: usize,
+  /// Frame time above which the budget is at least halved, whatever the
+  /// groups cost. `None` ignores frame time.
+  pub max_frame_ms: Option<f32>,
+}
+
+impl Default for AdaptiveGroupBudget {
+  fn default() -> Self {
+    Self {
+      target_ms: 2.0,
+      min_groups: 1,
+      max_groups: 64,
+      max_frame_ms: None,
+    }
+  }
+}
+
+impl AdaptiveGroupBudget {
+  /// Budget for the next frame after applying `applied` groups costing
+  /// `elapsed_ms` of work, with `current` the budget used for them.
+  ///
+  /// Moves toward the group count that fits `target_ms` at the measured
+  /// cost per group, at most doubling or halving per frame. The budget only
+  /// grows when it was the limit (`applied >= current`).
+  pub fn tune(&self, current: usize, applied: usize, elapsed_ms: f32, frame_ms: f32) -> usize {
+    let current = current.max(1);
+    if applied == 0 {
+      return current.clamp(self.min_groups, self.max_groups);
+    }
+
+    let per_group_ms = (elapsed_ms / applied as f32).max(f32::EPSILON);
+    let ideal = (self.target_ms / per_group_ms).floor() as usize;
+    let mut next = ideal.clamp(current / 2, current * 2);
+    if applied < current {
+      next = next.min(current);
+    }
+    if self.max_frame_ms.is_some_and(|max| frame_ms > max) {
+      next = next.min(current / 2);
+    }
+    next.clamp(self.min_groups, self.max_groups)
+  }
+}
+
+/// Resource tracking meshing tasks running on the `AsyncComputeTaskPool`.
+///
+/// Enqueue transition groups with [`enqueue`](Self::enqueue) and add
+/// [`poll_meshing_tasks`] (or [`poll_custom_material_meshing_tasks`] for
+/// another `M`) to a schedule to spawn the resulting chunks.
+#[derive(Resource)]
+pub struct VoxelMeshingTasks<M: Material = StandardMaterial> {
+  /// Material for spawned chunks.
+  pub material: Handle<M>,
+  /// Maximum finished groups applied per frame.
+  pub max_groups_per_frame: usize,
+  /// Time budget for spawning chunks per frame, in milliseconds. At least
+  /// one chunk is spawned per frame, so meshing always makes progress.
+  pub max_ms_per_frame: f32,
+  /// Retune `max_groups_per_frame` every frame when set.
+  pub adaptive: Option<AdaptiveGroupBudget>,
+  /// Mesh settings of enqueued chunks; voxel size, neighbor mask and world
+  /// origin are set per chunk (see `node_mesh_config`).
+  pub mesh_config: MeshConfig,
+  in_flight: Vec<Task<MeshedGroup>>,
+  completed: VecDeque<MeshedGroup>,
+  /// Generation of the last enqueued group.
+  generation: u64,
+  /// For each node of a pending (running or finished, not yet applied)
+  /// group: the generation of the last group enqueued for it and the number
+  /// of pending groups with it.
+  pending: HashMap<(WorldId, OctreeNode), (u64, usize)>,
+}
+
+impl VoxelMeshingTasks {
+  /// Create the resource, applying up to 4 groups within 4ms per frame.
+  pub fn new(material: Handle<StandardMaterial>) -> Self {
+    Self::for_material(material)
+  }
+}
+
+impl<M: Material> VoxelMeshingTasks<M> {
+  /// Like [`VoxelMeshingTasks::new`], for chunks using a custom material.
+  pub fn for_material(material: Handle<M>) -> Self {
+    Self {
+      material,
+      max_groups_per_frame: 4,
+      max_ms_per_frame: 4.0,
+      adaptive: None,
+      mesh_config: MeshConfig::default(),
+      in_flight: Vec::new(),
+      completed: VecDeque::new(),
+      generation: 0,
+      pending: HashMap::new(),
+    }
+  }
+
+  pub fn with_max_groups_per_frame(mut self, max_groups: usize) -> Self {
+    self.max_groups_per_frame = max_groups.max(1);
+    self
+  }
+
+  pub fn with_max_ms_per_frame(mut self, max_ms: f32) -> Self {
+    self.max_ms_per_frame = max_ms.max(0.0);
+    self
+  }
+
+  pub fn with_adaptive_budget(mut self, adaptive: AdaptiveGroupBudget) -> Self {
+    self.adaptive = Some(adaptive);
+    self
+  }
+
+  pub fn with_mesh_config(mut self, mesh_config: MeshConfig) -> Self {
+    self.mesh_config = mesh_config;
+    self
+  }
+
+  /// Spawn one meshing task per transition group.
+  ///
+  /// Groups still in flight that share a node with a new group are
+  /// superseded: their results are dropped when they finish.
+  pub fn enqueue<S: VolumeSampler + 'static>(
+    &mut self,
+    world_id: WorldId,
+    transition_groups: Vec<TransitionGroup>,
+    sampler: S,
+    leaves: HashSet<OctreeNode>,
+    config: OctreeConfig,
+  ) {
+    let pool = AsyncComputeTaskPool::get();
+    let sampler = Arc::new(sampler);
+    let leaves = Arc::new(leaves);
+    let config = Arc::new(config);
+    let mesh_config = Arc::new(self.mesh_config.clone());
+
+    for group in transition_groups {
+      self.generation += 1;
+      let generation = self.generation;
+      for &node in group.nodes_to_add.iter().chain(&group.nodes_to_remove) {
+        let (latest, count) = self.pending.entry((world_id, node)).or_default();
+        *latest = generation;
+        *count += 1;
+      }
+
+      let sampler = Arc::clone(&sampler);
+      let leaves = Arc::clone(&leaves);
+      let config = Arc::clone(&config);
+      let mesh_config = Arc::clone(&mesh_config);
+
+      self.in_flight.push(pool.spawn(async move {
+        let start = Instant::now();
+        let chunks = mesh_group(
+          world_id,
+          &group,
+          sampler.as_ref(),
+          &leaves,
+          &config,
+          &mesh_config,
+        );
+        MeshedGroup {
+          mesh_ms: start.elapsed().as_secs_f32() * 1000.0,
+          world_id,
+          generation,
+          config,
+          nodes_to_add: group.nodes_to_add,
+          nodes_to_remove: group.nodes_to_remove,
+          chunks: chunks.into(),
+          hidden: Vec::new(),
+          started: false,
+        }
+      }));
+    }
+  }
+
+  /// Move finished tasks to the completed queue without blocking.
+  pub fn collect_finished(&mut self) {
+    let completed = &mut self.completed;
+    self
+      .in_flight
+      .retain_mut(|task| match block_on(future::poll_once(task)) {
+        Some(group) => {
+          completed.push_back(group);
+          false
+        }
+        None => true,
+      });
+  }
+
+  /// Tasks still running.
+  pub fn in_flight(&self) -> usize {
+    self.in_flight.len()
+  }
+
+  /// Finished groups waiting to be applied.
+  pub fn ready_groups(&self) -> usize {
+    self.completed.len()
+  }
+
+  /// No running tasks and no finished groups left to apply.
+  pub fn is_idle(&self) -> bool {
+    self.in_flight.is_empty() && self.completed.is_empty()
+  }
+
+  /// Drop all running tasks and finished groups, e.g. when the world they
+  /// mesh is rebuilt. Chunks already spawned are left alone.
+  pub fn clear(&mut self) {
+    self.in_flight.clear();
+    self.completed.clear();
+    self.pending.clear();
+  }
+
+  /// Whether a node of `group` was enqueued again after it.
+  fn is_superseded(&self, group: &MeshedGroup) -> bool {
+    group.nodes().any(|&node| {
+      self
+        .pending
+        .get(&(group.world_id, node))
+        .is_some_and(|&(latest, _)| latest > group.generation)
+    })
+  }
+
+  /// Stop tracking `group` once it is applied or dropped.
+  fn retire(&mut self, group: &MeshedGroup) {
+    for &node in group.nodes() {
+      let key = (group.world_id, node);
+      if let Some((_, count)) = self.pending.get_mut(&key) {
+        *count -= 1;
+        if *count == 0 {
+          self.pending.remove(&key);
+        }
+      }
+    }
+  }
+}
+
+/// Run presample → meshing → composition → presentation for one group.
+fn mesh_group<S: VolumeSampler>(
+  world_id: WorldId,
+  group: &TransitionGroup,
+  sampler: &S,
+  leaves: &HashSet<OctreeNode>,
+  config: &OctreeConfig,
+  mesh_config: &MeshConfig,
+) -> Vec<ReadyChunk> {
+  let mesh_results = group
+    .nodes_to_add
+    .iter()
+    .filter_map(|&node| {
+      let sampled = presample_node(node, WorkSource::Refinement, sampler, config).volume?;
+      let neighbor_mask = compute_neighbor_mask(&node, leaves, config);
+
+      let result = mesh_node(MeshInput {
+        node,
+        volume: sampled.volume,
+        materials: sampled.materials,
+        config: node_mesh_config(mesh_config, &node, neighbor_mask, config),
+        work_source: WorkSource::Refinement,
+      });
+      (!result.output.is_empty()).then_some(result)
+    })
+    .collect();
+
+  present(world_id, compose(mesh_results, std::slice::from_ref(group)))
+}
+
+/// System collecting finished meshing tasks and applying up to
+/// `max_groups_per_frame` of them within `max_ms_per_frame`. Superseded
+/// groups are dropped without counting against the budget.
+///
+/// A group's removed nodes are despawned once all of its chunks are
+/// spawned, so a group sliced across frames never leaves a hole; a partly
+/// applied group is resumed first next frame, its chunks hidden until then.
+///
+/// With `adaptive` set, the applied groups' meshing time plus the time spent
+/// here, and the previous frame's delta (from `Time`, when present), retune
+/// `max_groups_per_frame` afterwards.
+pub fn poll_meshing_tasks(
+  mut commands: Commands,
+  tasks: Option<ResMut<VoxelMeshingTasks>>,
+  time: Option<Res<Time>>,
+  mut meshes: ResMut<Assets<Mesh>>,
+  mut chunk_map: ResMut<ChunkEntityMap>,
+  mut world_chunk_map: Option<ResMut<WorldChunkMap>>,
+) {
+  if let Some(mut tasks) = tasks {
+    apply_meshed_groups(
+      &mut commands,
+      &mut tasks,
+      time.as_deref(),
+      &mut meshes,
+      &mut chunk_map,
+      world_chunk_map.as_deref_mut(),
+    );
+  }
+}
+
+/// [`poll_meshing_tasks`] for a `VoxelMeshingTasks<M>` resource.
+pub fn poll_custom_material_meshing_tasks<M: Material>(
+  mut commands: Commands,
+  tasks: Option<ResMut<VoxelMeshingTasks<M>>>,
+  time: Option<Res<Time>>,
+  mut meshes: ResMut<Assets<Mesh>>,
+  mut chunk_map: ResMut<ChunkEntityMap>,
+  mut world_chunk_map: Option<ResMut<WorldChunkMap>>,
+) {
+  if let Some(mut tasks) = tasks {
+    apply_meshed_groups(
+      &mut commands,
+      &mut tasks,
+      time.as_deref(),
+      &mut meshes,
+      &mut chunk_map,
+      world_chunk_map.as_deref_mut(),
+    );
+  }
+}
+
+fn apply_meshed_groups<M: Material>(
+  commands: &mut Commands,
+  tasks: &mut VoxelMeshingTasks<M>,
+  time: Option<&Time>,
+  meshes: &mut Assets<Mesh>,
+  chunk_map: &mut ChunkEntityMap,
+  mut world_chunk_map: Option<&mut WorldChunkMap>,
+) {
+  tasks.collect_finished();
+
+  let start = Instant::now();
+  let budget = Duration::from_secs_f32(tasks.max_ms_per_frame / 1000.0);
+
+  let budget_groups = tasks.max_groups_per_frame;
+  let mut applied = 0;
+  let mut mesh_ms = 0.0;
+
+  for _ in 0..budget_groups {
+    let next = loop {
+      match tasks.completed.pop_front() {
+        // A group already partly applied is finished; anything newer is
+        // applied after it
+        Some(group) if !group.started && tasks.is_superseded(&group) => tasks.retire(&group),
+        other => break other,
+      }
+    };
+    let Some(mut group) = next else {
+      break;
+    };
+
+    let mut spawned = Vec::new();
+    while let Some(ready) = group.chunks.pop_front() {
+      spawned.push(spawn_custom_material_chunk_entity_with_hint(
+        commands,
+        meshes,
+        tasks.material.clone(),
+        chunk_map,
+        world_chunk_map.as_deref_mut(),
+        None,
+        ready.world_id,
+        ready.node,
+        &ready.output,
+        ready.timing_us,
+        ready.hint,
+        &group.config,
+      ));
+      if start.elapsed() >= budget && !group.chunks.is_empty() {
+        break;
+      }
+    }
+
+    if !group.chunks.is_empty() {
+      // Out of time mid-group: resume it next frame. Children replacing a
+      // node stay hidden so the node isn't drawn over them meanwhile.
+      if !group.nodes_to_remove.is_empty() {
+        for &entity in &spawned {
+          commands.entity(entity).insert(Visibility::Hidden);
+        }
+        group.hidden.extend(spawned);
+      }
+      group.started = true;
+      tasks.completed.push_front(group);
+      break;
+    }
+
+    for &entity in &group.hidden {
+      commands.entity(entity).try_insert(Visibility::Inherited);
+    }
+    for node in &group.nodes_to_remove {
+      despawn_chunk_entity(commands, chunk_map, node);
+      if let Some(world_chunk_map) = world_chunk_map.as_deref_mut() {
+        world_chunk_map.remove(group.world_id, node);
+      }
+    }
+    tasks.retire(&group);
+    applied += 1;
+    mesh_ms += group.mesh_ms;
+
+    if start.elapsed() >= budget {
+      break;
+    }
+  }
+
+  if let Some(adaptive) = tasks.adaptive {
+    let elapsed_ms = start.elapsed().as_secs_f32() * 1000.0 + mesh_ms;
+    let frame_ms = time.map_or(0.0, |time| time.delta_secs() * 1000.0);
+    tasks.max_groups_per_frame = adaptive.tune(budget_groups, applied, elapsed_ms, frame_ms);
+  }
+}
+
+#[cfg(test)]
+#[path = "meshing_tasks_test.rs"]
+mod meshing_tasks_test;
@@ -0,0 +1,298 @@
+//! Tests for meshing on the AsyncComputeTaskPool.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::tasks::{AsyncComputeTaskPool, TaskPool};
+use voxel_plugin::octree::{OctreeConfig, OctreeNode, TransitionGroup};
+use voxel_plugin::sdf_samplers::GroundPlaneSampler;
+use voxel_plugin::world::WorldId;
+
+use super::{poll_meshing_tasks, AdaptiveGroupBudget, VoxelMeshingTasks};
+use crate::components::VoxelChunk;
+use crate::resources::ChunkEntityMap;
+
+fn meshing_app(max_groups_per_frame: usize) -> App {
+  AsyncComputeTaskPool::get_or_init(TaskPool::default);
+
+  let mut app = App::new();
+  app.insert_resource(Assets::<Mesh>::default());
+  app.insert_resource(ChunkEntityMap::default());
+  app.insert_resource(
+    VoxelMeshingTasks::new(Handle::default()).with_max_groups_per_frame(max_groups_per_frame),
+  );
+  app.add_systems(Update, poll_meshing_tasks);
+  app
+}
+
+fn tasks_app(tasks: VoxelMeshingTasks) -> App {
+  AsyncComputeTaskPool::get_or_init(TaskPool::default);
+
+  let mut app = App::new();
+  app.insert_resource(Assets::<Mesh>::default());
+  app.insert_resource(ChunkEntityMap::default());
+  app.insert_resource(tasks);
+  app.add_systems(Update, poll_meshing_tasks);
+  app
+}
+
+/// Wait until every task finished, without applying any group.
+fn wait_for_tasks(app: &mut App) {
+  for _ in 0..1000 {
+    let mut tasks = app.world_mut().resource_mut::<VoxelMeshingTasks>();
+    tasks.collect_finished();
+    if tasks.in_flight() == 0 {
+      return;
+    }
+    std::thread::sleep(Duration::from_millis(1));
+  }
+}
+
+/// Subdivide groups for LOD 1 parents along +X; the ground plane at y = 10
+/// crosses the bottom row of each parent's children.
+fn enqueue_subdivides(app: &mut App, count: i32) {
+  let groups: Vec<TransitionGroup> = (0..count)
+    .map(|x| TransitionGroup::new_subdivide(OctreeNode::new(x, 0, 0, 1)).unwrap())
+    .collect();
+  let leaves: HashSet<OctreeNode> = groups
+    .iter()
+    .flat_map(|group| group.nodes_to_add.iter().copied())
+    .collect();
+
+  app.world_mut().resource_mut::<VoxelMeshingTasks>().enqueue(
+    WorldId::new(),
+    groups,
+    GroundPlaneSampler::new(10.0),
+    leaves,
+    OctreeConfig::default(),
+  );
+}
+
+fn group_budget(app: &App) -> usize {
+  app
+    .world()
+    .resource::<VoxelMeshingTasks>()
+    .max_groups_per_frame
+}
+
+fn chunk_count(app: &mut App) -> usize {
+  app
+    .world_mut()
+    .query::<&VoxelChunk>()
+    .iter(app.world())
+    .count()
+}
+
+#[test]
+fn test_enqueued_work_spawns_chunk_entities() {
+  let mut app = meshing_app(4);
+  enqueue_subdivides(&mut app, 1);
+
+  for _ in 0..1000 {
+    app.update();
+    if app.world().resource::<VoxelMeshingTasks>().is_idle() {
+      break;
+    }
+    std::thread::sleep(Duration::from_millis(1));
+  }
+
+  assert!(app.world().resource::<VoxelMeshingTasks>().is_idle());
+  // Bottom 4 of the 8 children cross the plane
+  assert_eq!(chunk_count(&mut app), 4);
+  assert_eq!(app.world().resource::<ChunkEntityMap>().map.len(), 4);
+}
+
+#[test]
+fn test_superseded_group_is_dropped() {
+  let mut app = meshing_app(4);
+  let world_id = WorldId::new();
+  let group = TransitionGroup::new_subdivide(OctreeNode::new(0, 0, 0, 1)).unwrap();
+  let leaves: HashSet<OctreeNode> = group.nodes_to_add.iter().copied().collect();
+
+  // The ground moves from the bottom row of children (y = 10) to the top
+  // row (y = 40) before the first task is applied
+  for height in [10.0, 40.0] {
+    app.world_mut().resource_mut::<VoxelMeshingTasks>().enqueue(
+      world_id,
+      vec![group.clone()],
+      GroundPlaneSampler::new(height),
+      leaves.clone(),
+      OctreeConfig::default(),
+    );
+  }
+  wait_for_tasks(&mut app);
+  app.update();
+
+  // Whichever task finished first, only the newer result is applied
+  let tasks = app.world().resource::<VoxelMeshingTasks>();
+  assert!(tasks.is_idle());
+  let chunk_map = app.world().resource::<ChunkEntityMap>();
+  assert_eq!(chunk_map.map.len(), 4);
+  assert!(chunk_map.map.keys().all(|node| node.y == 1));
+}
+
+#[test]
+fn test_completion_spread_across_frames() {
+  let mut app = meshing_app(1);
+  enqueue_subdivides(&mut app, 3);
+
+  // Let every task finish before any are applied
+  wait_for_tasks(&mut app);
+  assert_eq!(
+    app.world().resource::<VoxelMeshingTasks>().ready_groups(),
+    3
+  );
+
+  for applied in 1..=3 {
+    app.update();
+    let tasks = app.world().resource::<VoxelMeshingTasks>();
+    assert_eq!(tasks.ready_groups(), 3 - applied);
+    assert_eq!(chunk_count(&mut app), 4 * applied);
+  }
+}
+
+#[test]
+fn test_tiny_time_slice_spreads_group_across_frames() {
+  let mut app = tasks_app(VoxelMeshingTasks::new(Handle::default()).with_max_ms_per_frame(0.0));
+  enqueue_subdivides(&mut app, 2);
+  wait_for_tasks(&mut app);
+
+  // Out of time after every chunk: one spawn per frame
+  app.update();
+  assert_eq!(chunk_count(&mut app), 1);
+  assert!(!app.world().resource::<VoxelMeshingTasks>().is_idle());
+
+  let mut frames = 1;
+  while !app.world().resource::<VoxelMeshingTasks>().is_idle() {
+    app.update();
+    frames += 1;
+    assert!(frames <= 100, "meshing never completed");
+  }
+
+  assert_eq!(frames, 8);
+  assert_eq!(chunk_count(&mut app), 8);
+  assert_eq!(app.world().resource::<ChunkEntityMap>().map.len(), 8);
+}
+
+#[test]
+fn test_sliced_subdivide_hides_children_until_parent_removed() {
+  let mut app = tasks_app(VoxelMeshingTasks::new(Handle::default()).with_max_ms_per_frame(0.0));
+  let parent_node = OctreeNode::new(0, 0, 0, 1);
+  let parent = app.world_mut().spawn(Visibility::Inherited).id();
+  app
+    .world_mut()
+    .resource_mut::<ChunkEntityMap>()
+    .insert(parent_node, parent);
+  enqueue_subdivides(&mut app, 1);
+  wait_for_tasks(&mut app);
+
+  // 4 children, one per frame: the parent stays and the children stay hidden
+  for spawned in 1..4 {
+    app.update();
+    assert!(app.world().get_entity(parent).is_ok());
+    let mut children = app.world_mut().query::<(&VoxelChunk, &Visibility)>();
+    let visibilities: Vec<_> = children.iter(app.world()).map(|(_, v)| *v).collect();
+    assert_eq!(visibilities.len(), spawned);
+    assert!(visibilities.iter().all(|v| *v == Visibility::Hidden));
+  }
+
+  app.update();
+  assert!(app.world().resource::<VoxelMeshingTasks>().is_idle());
+  assert!(app.world().get_entity(parent).is_err());
+  let mut children = app.world_mut().query::<(&VoxelChunk, &Visibility)>();
+  let visibilities: Vec<_> = children.iter(app.world()).map(|(_, v)| *v).collect();
+  assert_eq!(visibilities.len(), 4);
+  assert!(visibilities.iter().all(|v| *v == Visibility::Inherited));
+}
+
+#[test]
+fn test_slow_groups_drive_adaptive_budget_down() {
+  let adaptive = AdaptiveGroupBudget::default();
+  let mut budget = 32;
+  let mut history = vec![budget];
+
+  // Every group costs 1ms against a 2ms target; the queue never runs dry
+  for _ in 0..8 {
+    budget = adaptive.tune(budget, budget, budget as f32 * 1.0, 16.0);
+    history.push(budget);
+  }
+
+  assert!(history.windows(2).all(|w| w[1] <= w[0]), "{:?}", history);
+  assert_eq!(budget, 2);
+}
+
+#[test]
+fn test_fast_groups_drive_adaptive_budget_up_to_max() {
+  let adaptive = AdaptiveGroupBudget {
+    max_groups: 16,
+    ..Default::default()
+  };
+  let mut budget = 2;
+  for _ in 0..8 {
+    budget = adaptive.tune(budget, budget, budget as f32 * 0.1, 16.0);
+  }
+  assert_eq!(budget, 16);
+
+  // Not growing when the queue ran dry before the budget was reached
+  assert_eq!(adaptive.tune(4, 1, 0.1, 16.0), 4);
+}
+
+#[test]
+fn test_slow_frame_halves_adaptive_budget() {
+  let adaptive = AdaptiveGroupBudget {
+    max_frame_ms: Some(20.0),
+    ..Default::default()
+  };
+  assert_eq!(adaptive.tune(8, 8, 0.8, 40.0), 4);
+  assert_eq!(adaptive.tune(8, 8, 0.8, 16.0), 16);
+}
+
+#[test]
+fn test_adaptive_budget_shrinks_when_groups_are_costly() {
+  // Any real meshing work is far above a 1ns target
+  let adaptive = AdaptiveGroupBudget {
+    target_ms: 0.000_001,
+    ..Default::default()
+  };
+  let mut app = tasks_app(
+    VoxelMeshingTasks::new(Handle::default())
+      .with_max_groups_per_frame(8)
+      .with_max_ms_per_frame(1000.0)
+      .with_adaptive_budget(adaptive),
+  );
+  enqueue_subdivides(&mut app, 16);
+  wait_for_tasks(&mut app);
+
+  app.update();
+  assert_eq!(chunk_count(&mut app), 4 * 8);
+  assert_eq!(group_budget(&app), 4);
+
+  app.update();
+  assert_eq!(chunk_count(&mut app), 4 * 12);
+  assert_eq!(group_budget(&app), 2);
+}
+
+#[test]
+fn test_adaptive_budget_grows_when_groups_are_cheap() {
+  let adaptive = AdaptiveGroupBudget {
+    target_ms: 1_000_000.0,
+    ..Default::default()
+  };
+  let mut app = tasks_app(
+    VoxelMeshingTasks::new(Handle::default())
+      .with_max_groups_per_frame(2)
+      .with_max_ms_per_frame(1000.0)
+      .with_adaptive_budget(adaptive),
+  );
+  enqueue_subdivides(&mut app, 16);
+  wait_for_tasks(&mut app);
+
+  app.update();
+  assert_eq!(chunk_count(&mut app), 4 * 2);
+  assert_eq!(group_budget(&app), 4);
+
+  app.update();
+  assert_eq!(chunk_count(&mut app), 4 * 6);
+  assert_eq!(group_budget(&app), 8);
+}
@@ -1,3 +1,9 @@
 //! Bevy systems for voxel rendering.
 
+pub mod csg;
 pub mod entities;
+pub mod fade;
+pub mod frustum_culling;
+pub mod meshing_tasks;
+pub mod scene_recorder;
+pub mod timing_overlay;
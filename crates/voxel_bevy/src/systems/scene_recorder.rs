@@ -0,0 +1,370 @@
+//! Records spawned chunk meshes into a single exportable glTF scene.
+//!
+//! Insert [`OctreeSceneRecorder`] and add [`record_spawned_chunks`] to
+//! `PostUpdate` after `TransformSystems::Propagate`. Every newly spawned
+//! `VoxelChunk` is copied into the recorder with its `GlobalTransform`, so
+//! the recorder accumulates everything visited while the world refines, placed
+//! under any transform of its world root. Call
+//! [`OctreeSceneRecorder::to_gltf`] or [`OctreeSceneRecorder::save`] to export.
+//!
+//! A node respawned later (e.g. after an edit) replaces its earlier recording.
+//! Chunks of different LODs covering the same region are all kept, one glTF
+//! node each, named `w{world}_lod{lod}_{x}_{y}_{z}`.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use bevy::mesh::{Indices, VertexAttributeValues};
+use bevy::prelude::*;
+use voxel_plugin::octree::OctreeNode;
+use voxel_plugin::world::WorldId;
+
+use crate::components::VoxelChunk;
+
+/// glTF `ARRAY_BUFFER` buffer view target.
+const TARGET_ARRAY_BUFFER: u32 = 34962;
+/// glTF `ELEMENT_ARRAY_BUFFER` buffer view target.
+const TARGET_ELEMENT_ARRAY_BUFFER: u32 = 34963;
+/// glTF `FLOAT` component type.
+const COMPONENT_FLOAT: u32 = 5126;
+/// glTF `UNSIGNED_INT` component type.
+const COMPONENT_UNSIGNED_INT: u32 = 5125;
+
+/// Mesh and transform of one recorded chunk.
+#[derive(Clone, Debug)]
+pub struct RecordedChunk {
+  pub world_id: WorldId,
+  pub node: OctreeNode,
+  /// World transform of the chunk entity (from its `GlobalTransform`).
+  pub transform: Transform,
+  /// Vertex positions in chunk-local space.
+  pub positions: Vec<[f32; 3]>,
+  /// Vertex normals (empty if the mesh had none).
+  pub normals: Vec<[f32; 3]>,
+  /// Triangle list indices.
+  pub indices: Vec<u32>,
+}
+
+impl RecordedChunk {
+  /// glTF node name for this chunk.
+  pub fn name(&self) -> String {
+    format!(
+      "w{}_lod{}_{}_{}_{}",
+      self.world_id.raw(),
+      self.node.lod,
+      self.node.x,
+      self.node.y,
+      self.node.z
+    )
+  }
+}
+
+/// Resource accumulating spawned chunks into a glTF scene.
+#[derive(Resource)]
+pub struct OctreeSceneRecorder {
+  /// Whether newly spawned chunks are recorded.
+  pub enabled: bool,
+  chunks: Vec<RecordedChunk>,
+  index: HashMap<(WorldId, OctreeNode), usize>,
+}
+
+impl Default for OctreeSceneRecorder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl OctreeSceneRecorder {
+  /// Create an empty, enabled recorder.
+  pub fn new() -> Self {
+    Self {
+      enabled: true,
+      chunks: Vec::new(),
+      index: HashMap::new(),
+    }
+  }
+
+  /// Record a chunk mesh with its world transform.
+  ///
+  /// Returns `false` (and records nothing) if the mesh has no `Float32x3`
+  /// positions or no triangles.
+  pub fn record(
+    &mut self,
+    world_id: WorldId,
+    node: OctreeNode,
+    transform: &GlobalTransform,
+    mesh: &Mesh,
+  ) -> bool {
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+      mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+      return false;
+    };
+    let normals = match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+      Some(VertexAttributeValues::Float32x3(normals)) if normals.len() == positions.len() => {
+        normals.clone()
+      }
+      _ => Vec::new(),
+    };
+    let indices: Vec<u32> = match mesh.indices() {
+      Some(Indices::U16(indices)) => indices.iter().map(|&i| i as u32).collect(),
+      Some(Indices::U32(indices)) => indices.clone(),
+      None => (0..positions.len() as u32).collect(),
+    };
+    if positions.is_empty() || indices.is_empty() {
+      return false;
+    }
+
+    let chunk = RecordedChunk {
+      world_id,
+      node,
+      transform: transform.compute_transform(),
+      positions: positions.clone(),
+      normals,
+      indices,
+    };
+
+    match self.index.get(&(world_id, node)) {
+      Some(&i) => self.chunks[i] = chunk,
+      None => {
+        self.index.insert((world_id, node), self.chunks.len());
+        self.chunks.push(chunk);
+      }
+    }
+    true
+  }
+
+  /// Recorded chunks in first-recorded order.
+  pub fn chunks(&self) -> &[RecordedChunk] {
+    &self.chunks
+  }
+
+  /// Number of recorded chunks.
+  pub fn len(&self) -> usize {
+    self.chunks.len()
+  }
+
+  /// Whether nothing has been recorded yet.
+  pub fn is_empty(&self) -> bool {
+    self.chunks.is_empty()
+  }
+
+  /// Drop all recorded chunks.
+  pub fn clear(&mut self) {
+    self.chunks.clear();
+    self.index.clear();
+  }
+
+  /// Export the recording as glTF 2.0 JSON with an embedded buffer.
+  ///
+  /// One mesh and one node per chunk; node transforms are the chunk's world
+  /// transform, so the scene reproduces the world layout.
+  pub fn to_gltf(&self) -> String {
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut views = Vec::new();
+    let mut accessors = Vec::new();
+    let mut meshes = Vec::new();
+    let mut nodes = Vec::new();
+
+    for (i, chunk) in self.chunks.iter().enumerate() {
+      let position_accessor = accessors.len();
+      let (min, max) = bounds(&chunk.positions);
+      let view = push_view(
+        &mut buffer,
+        &mut views,
+        &chunk.positions,
+        TARGET_ARRAY_BUFFER,
+      );
+      accessors.push(format!(
+        r#"{{"bufferView":{},"componentType":{},"count":{},"type":"VEC3","min":{},"max":{}}}"#,
+        view,
+        COMPONENT_FLOAT,
+        chunk.positions.len(),
+        json_array(&min),
+        json_array(&max)
+      ));
+
+      let mut attributes = format!(r#""POSITION":{}"#, position_accessor);
+      if !chunk.normals.is_empty() {
+        let view = push_view(&mut buffer, &mut views, &chunk.normals, TARGET_ARRAY_BUFFER);
+        let _ = write!(attributes, r#","NORMAL":{}"#, accessors.len());
+        accessors.push(format!(
+          r#"{{"bufferView":{},"componentType":{},"count":{},"type":"VEC3"}}"#,
+          view,
+          COMPONENT_FLOAT,
+          chunk.normals.len()
+        ));
+      }
+
+      let index_accessor = accessors.len();
+      let view = push_view(
+        &mut buffer,
+        &mut views,
+        &chunk.indices,
+        TARGET_ELEMENT_ARRAY_BUFFER,
+      );
+      accessors.push(format!(
+        r#"{{"bufferView":{},"componentType":{},"count":{},"type":"SCALAR"}}"#,
+        view,
+        COMPONENT_UNSIGNED_INT,
+        chunk.indices.len()
+      ));
+
+      meshes.push(format!(
+        r#"{{"name":"{}","primitives":[{{"attributes":{{{}}},"indices":{}}}]}}"#,
+        chunk.name(),
+        attributes,
+        index_accessor
+      ));
+
+      let t = &chunk.transform;
+      nodes.push(format!(
+        r#"{{"name":"{}","mesh":{},"translation":{},"rotation":{},"scale":{}}}"#,
+        chunk.name(),
+        i,
+        json_array(&t.translation.to_array()),
+        json_array(&t.rotation.to_array()),
+        json_array(&t.scale.to_array())
+      ));
+    }
+
+    let mut json = String::new();
+    json.push('{');
+    json.push_str(r#""asset":{"version":"2.0","generator":"voxel_bevy OctreeSceneRecorder"}"#);
+    // glTF forbids empty arrays; an empty recording is just the header
+    if !nodes.is_empty() {
+      let scene_nodes: Vec<String> = (0..nodes.len()).map(|i| i.to_string()).collect();
+      let _ = write!(
+        json,
+        r#","scene":0,"scenes":[{{"nodes":[{}]}}],"nodes":[{}],"meshes":[{}],"#,
+        scene_nodes.join(","),
+        nodes.join(","),
+        meshes.join(",")
+      );
+      let _ = write!(
+        json,
+        r#""accessors":[{}],"bufferViews":[{}],"#,
+        accessors.join(","),
+        views.join(",")
+      );
+      let _ = write!(
+        json,
+        r#""buffers":[{{"byteLength":{},"uri":"data:application/octet-stream;base64,{}"}}]"#,
+        buffer.len(),
+        base64_encode(&buffer)
+      );
+    }
+    json.push('}');
+    json
+  }
+
+  /// Write the recording to a `.gltf` file.
+  pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+    std::fs::write(path, self.to_gltf())
+  }
+}
+
+/// System recording newly spawned chunks into [`OctreeSceneRecorder`].
+///
+/// Schedule it in `PostUpdate` after `TransformSystems::Propagate`, so chunks
+/// spawned this frame are recorded at their final position.
+pub fn record_spawned_chunks(
+  recorder: Option<ResMut<OctreeSceneRecorder>>,
+  meshes: Res<Assets<Mesh>>,
+  chunks: Query<(&VoxelChunk, &GlobalTransform, &Mesh3d), Added<VoxelChunk>>,
+) {
+  let Some(mut recorder) = recorder else {
+    return;
+  };
+  if !recorder.enabled {
+    return;
+  }
+
+  for (chunk, transform, mesh) in &chunks {
+    if let Some(mesh) = meshes.get(&mesh.0) {
+      recorder.record(chunk.world_id, chunk.node, transform, mesh);
+    }
+  }
+}
+
+/// Append `data` to `buffer` as a new buffer view; returns the view index.
+fn push_view<T: ToLeBytes>(
+  buffer: &mut Vec<u8>,
+  views: &mut Vec<String>,
+  data: &[T],
+  target: u32,
+) -> usize {
+  let offset = buffer.len();
+  for item in data {
+    item.extend_le_bytes(buffer);
+  }
+  views.push(format!(
+    r#"{{"buffer":0,"byteOffset":{},"byteLength":{},"target":{}}}"#,
+    offset,
+    buffer.len() - offset,
+    target
+  ));
+  views.len() - 1
+}
+
+trait ToLeBytes {
+  fn extend_le_bytes(&self, out: &mut Vec<u8>);
+}
+
+impl ToLeBytes for u32 {
+  fn extend_le_bytes(&self, out: &mut Vec<u8>) {
+    out.extend_from_slice(&self.to_le_bytes());
+  }
+}
+
+impl ToLeBytes for [f32; 3] {
+  fn extend_le_bytes(&self, out: &mut Vec<u8>) {
+    for v in self {
+      out.extend_from_slice(&v.to_le_bytes());
+    }
+  }
+}
+
+/// Component-wise min/max of a non-empty position list.
+fn bounds(positions: &[[f32; 3]]) -> ([f32; 3], [f32; 3]) {
+  let mut min = Vec3::splat(f32::INFINITY);
+  let mut max = Vec3::splat(f32::NEG_INFINITY);
+  for &p in positions {
+    min = min.min(Vec3::from_array(p));
+    max = max.max(Vec3::from_array(p));
+  }
+  (min.to_array(), max.to_array())
+}
+
+fn json_array(values: &[f32]) -> String {
+  let items: Vec<String> = values.iter().map(|v| format!("{:?}", v)).collect();
+  format!("[{}]", items.join(","))
+}
+
+/// Standard base64 (RFC 4648) with padding.
+fn base64_encode(bytes: &[u8]) -> String {
+  const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+  let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+  for group in bytes.chunks(3) {
+    let b = [
+      group[0],
+      group.get(1).copied().unwrap_or(0),
+      group.get(2).copied().unwrap_or(0),
+    ];
+    let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+    for i in 0..4 {
+      if i <= group.len() {
+        out.push(ALPHABET[((n >> (18 - 6 * i)) & 63) as usize] as char);
+      } else {
+        out.push('=');
+      }
+    }
+  }
+  out
+}
+
+#[cfg(test)]
+#[path = "scene_recorder_test.rs"]
+mod scene_recorder_test;
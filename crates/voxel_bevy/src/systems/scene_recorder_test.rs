@@ -0,0 +1,160 @@
+//! Tests for the glTF scene recorder.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::prelude::*;
+use voxel_plugin::octree::OctreeNode;
+use voxel_plugin::pipeline::PresentationHint;
+use voxel_plugin::world::WorldId;
+
+use super::{base64_encode, record_spawned_chunks, OctreeSceneRecorder};
+use crate::components::VoxelChunk;
+
+fn triangle_mesh() -> Mesh {
+  let mut mesh = Mesh::new(
+    PrimitiveTopology::TriangleList,
+    RenderAssetUsages::default(),
+  );
+  mesh.insert_attribute(
+    Mesh::ATTRIBUTE_POSITION,
+    vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+  );
+  mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 3]);
+  mesh.insert_indices(Indices::U16(vec![0, 1, 2]));
+  mesh
+}
+
+fn spawn_chunk(app: &mut App, world_id: WorldId, node: OctreeNode, transform: Transform) -> Entity {
+  let mesh = app
+    .world_mut()
+    .resource_mut::<Assets<Mesh>>()
+    .add(triangle_mesh());
+  app
+    .world_mut()
+    .spawn((
+      Mesh3d(mesh),
+      transform,
+      VoxelChunk {
+        world_id,
+        node,
+        timing_us: 0,
+        hint: PresentationHint::Immediate,
+      },
+    ))
+    .id()
+}
+
+fn recording_app() -> App {
+  let mut app = App::new();
+  app.add_plugins(TransformPlugin);
+  app.insert_resource(Assets::<Mesh>::default());
+  app.insert_resource(OctreeSceneRecorder::new());
+  app.add_systems(
+    PostUpdate,
+    record_spawned_chunks.after(TransformSystems::Propagate),
+  );
+  app
+}
+
+#[test]
+fn test_gltf_has_one_node_per_spawned_chunk() {
+  let mut app = recording_app();
+  let world_id = WorldId::new();
+  let chunks = [
+    (
+      OctreeNode::new(0, 0, 0, 0),
+      Transform::from_xyz(0.0, 0.0, 0.0).with_scale(Vec3::splat(1.0)),
+    ),
+    (
+      OctreeNode::new(1, 0, 0, 0),
+      Transform::from_xyz(28.0, 0.0, 0.0).with_scale(Vec3::splat(1.0)),
+    ),
+    (
+      OctreeNode::new(0, 1, -1, 2),
+      Transform::from_xyz(0.0, 56.0, -56.0).with_scale(Vec3::splat(2.0)),
+    ),
+  ];
+
+  // Spawn across frames, as refinement does
+  spawn_chunk(&mut app, world_id, chunks[0].0, chunks[0].1);
+  app.update();
+  spawn_chunk(&mut app, world_id, chunks[1].0, chunks[1].1);
+  spawn_chunk(&mut app, world_id, chunks[2].0, chunks[2].1);
+  app.update();
+
+  let recorder = app.world().resource::<OctreeSceneRecorder>();
+  assert_eq!(recorder.len(), 3);
+
+  let gltf: serde_json::Value = serde_json::from_str(&recorder.to_gltf()).unwrap();
+  let nodes = gltf["nodes"].as_array().unwrap();
+  assert_eq!(nodes.len(), chunks.len());
+  assert_eq!(gltf["scenes"][0]["nodes"].as_array().unwrap().len(), 3);
+  assert_eq!(gltf["meshes"].as_array().unwrap().len(), 3);
+
+  let as_vec3 = |value: &serde_json::Value| {
+    let v: Vec<f32> = value
+      .as_array()
+      .unwrap()
+      .iter()
+      .map(|c| c.as_f64().unwrap() as f32)
+      .collect();
+    Vec3::new(v[0], v[1], v[2])
+  };
+
+  for (node, (_, transform)) in nodes.iter().zip(&chunks) {
+    assert_eq!(as_vec3(&node["translation"]), transform.translation);
+    assert_eq!(as_vec3(&node["scale"]), transform.scale);
+  }
+  assert_eq!(
+    nodes[2]["name"].as_str().unwrap(),
+    format!("w{}_lod2_0_1_-1", world_id.raw())
+  );
+}
+
+#[test]
+fn test_respawned_node_replaces_recording() {
+  let mut app = recording_app();
+  let world_id = WorldId::new();
+  let node = OctreeNode::new(0, 0, 0, 0);
+
+  spawn_chunk(&mut app, world_id, node, Transform::from_xyz(1.0, 0.0, 0.0));
+  app.update();
+  spawn_chunk(&mut app, world_id, node, Transform::from_xyz(2.0, 0.0, 0.0));
+  app.update();
+
+  let recorder = app.world().resource::<OctreeSceneRecorder>();
+  assert_eq!(recorder.len(), 1);
+  assert_eq!(recorder.chunks()[0].transform.translation.x, 2.0);
+}
+
+#[test]
+fn test_chunk_recorded_under_moved_root() {
+  let mut app = recording_app();
+  let root = app
+    .world_mut()
+    .spawn(Transform::from_xyz(100.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)))
+    .id();
+  let node = OctreeNode::new(0, 0, 0, 0);
+  let chunk = spawn_chunk(
+    &mut app,
+    WorldId::new(),
+    node,
+    Transform::from_xyz(0.0, 28.0, 0.0),
+  );
+  app.world_mut().entity_mut(chunk).insert(ChildOf(root));
+  app.update();
+
+  let recorder = app.world().resource::<OctreeSceneRecorder>();
+  assert_eq!(recorder.len(), 1);
+  let transform = recorder.chunks()[0].transform;
+  assert_eq!(transform.translation, Vec3::new(100.0, 56.0, 0.0));
+  assert_eq!(transform.scale, Vec3::splat(2.0));
+}
+
+#[test]
+fn test_base64_encode() {
+  assert_eq!(base64_encode(b""), "");
+  assert_eq!(base64_encode(b"f"), "Zg==");
+  assert_eq!(base64_encode(b"fo"), "Zm8=");
+  assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+}
@@ -0,0 +1,90 @@
+// Dithered LOD crossfade extension shader
+// Extends StandardMaterial with a screen-door fade: fragments are discarded
+// against a 4x4 Bayer matrix instead of being alpha blended, so fading
+// chunks stay opaque, write depth and take part in the prepass.
+
+#import bevy_pbr::{
+    pbr_fragment::pbr_input_from_standard_material,
+    pbr_functions::alpha_discard,
+}
+
+#ifdef PREPASS_PIPELINE
+#import bevy_pbr::{
+    prepass_io::{VertexOutput, FragmentOutput},
+    pbr_deferred_functions::deferred_output,
+    pbr_prepass_functions::calculate_motion_vector,
+}
+#else
+#import bevy_pbr::{
+    forward_io::{VertexOutput, FragmentOutput},
+    pbr_functions::{apply_pbr_lighting, main_pass_post_lighting_processing},
+}
+#endif
+
+// Fade parameters
+struct DitherFadeParams {
+    alpha: f32,
+    // 1.0 for fade-outs, which use the complementary pattern of fade-ins
+    complement: f32,
+    _padding: vec2<f32>,
+}
+
+// Extension bindings - Bevy 0.18 uses group 3 for materials
+// Using 100+ to avoid StandardMaterial binding conflicts
+@group(3) @binding(100) var<uniform> dither_fade: DitherFadeParams;
+
+// 4x4 Bayer threshold in (0, 1) for a pixel
+fn bayer_threshold(pixel: vec2<f32>) -> f32 {
+    let x = u32(pixel.x) & 3u;
+    let y = u32(pixel.y) & 3u;
+    let a = x ^ y;
+    let index = ((a & 1u) << 3u) | ((y & 1u) << 2u) | (a & 2u) | ((y & 2u) >> 1u);
+    return (f32(index) + 0.5) / 16.0;
+}
+
+// Whether the fade covers a pixel. A fade-in at alpha p and a fade-out at
+// alpha 1 - p cover complementary pixels.
+fn dither_covers(pixel: vec2<f32>) -> bool {
+    var threshold = bayer_threshold(pixel);
+    if dither_fade.complement > 0.5 {
+        threshold = 1.0 - threshold;
+    }
+    return threshold < dither_fade.alpha;
+}
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+    if !dither_covers(in.position.xy) {
+        discard;
+    }
+
+#ifdef PREPASS_PIPELINE
+#ifdef DEFERRED_PREPASS
+    let pbr_input = pbr_input_from_standard_material(in, is_front);
+    let out = deferred_output(in, pbr_input);
+#else
+    var out: FragmentOutput;
+#ifdef UNCLIPPED_DEPTH_ORTHO_EMULATION
+    out.frag_depth = in.unclipped_depth;
+#endif
+#ifdef NORMAL_PREPASS
+    out.normal = vec4<f32>(normalize(in.world_normal) * 0.5 + vec3<f32>(0.5), 1.0);
+#endif
+#ifdef MOTION_VECTOR_PREPASS
+    out.motion_vector = calculate_motion_vector(in.world_position, in.previous_world_position);
+#endif
+#endif
+#else
+    var pbr_input = pbr_input_from_standard_material(in, is_front);
+    pbr_input.material.base_color = alpha_discard(pbr_input.material, pbr_input.material.base_color);
+
+    var out: FragmentOutput;
+    out.color = apply_pbr_lighting(pbr_input);
+    out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+#endif
+
+    return out;
+}
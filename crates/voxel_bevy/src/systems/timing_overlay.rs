@@ -0,0 +1,115 @@
+//! Diagnostic overlay tinting chunks by mesh generation time.
+//!
+//! Uses `VoxelChunk::timing_us` (copied from `ReadyChunk::timing_us` at spawn)
+//! to swap each chunk's `StandardMaterial` for a green/yellow/red tint, making
+//! pathologically slow chunks easy to spot in the viewport.
+//!
+//! ```text
+//! timing_us < fast_threshold_us   → Fast   (green)
+//! timing_us > slow_threshold_us   → Slow   (red)
+//! otherwise                       → Medium (yellow)
+//! ```
+//!
+//! Only chunks rendered with `StandardMaterial` are tinted; custom materials
+//! (e.g. triplanar) are left untouched.
+
+use bevy::prelude::*;
+
+use crate::components::VoxelChunk;
+
+/// Timing bucket for a chunk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimingTint {
+  Fast,
+  Medium,
+  Slow,
+}
+
+/// Resource configuring the chunk timing overlay.
+///
+/// Insert this resource and add [`apply_chunk_timing_tint`] to a schedule to
+/// enable the overlay.
+#[derive(Resource)]
+pub struct ChunkTimingOverlay {
+  /// Whether newly spawned chunks are tinted.
+  pub enabled: bool,
+  /// Chunks faster than this are tinted green.
+  pub fast_threshold_us: u64,
+  /// Chunks slower than this are tinted red.
+  pub slow_threshold_us: u64,
+  /// Material for fast chunks.
+  pub fast: Handle<StandardMaterial>,
+  /// Material for chunks between the thresholds.
+  pub medium: Handle<StandardMaterial>,
+  /// Material for slow chunks.
+  pub slow: Handle<StandardMaterial>,
+}
+
+impl ChunkTimingOverlay {
+  /// Create the overlay with default thresholds (500µs / 5ms).
+  pub fn new(materials: &mut Assets<StandardMaterial>) -> Self {
+    let tint = |color: Color| StandardMaterial {
+      base_color: color,
+      perceptual_roughness: 0.7,
+      cull_mode: None,
+      ..default()
+    };
+
+    Self {
+      enabled: true,
+      fast_threshold_us: 500,
+      slow_threshold_us: 5_000,
+      fast: materials.add(tint(Color::srgb(0.4, 0.8, 0.4))),
+      medium: materials.add(tint(Color::srgb(0.8, 0.8, 0.4))),
+      slow: materials.add(tint(Color::srgb(0.8, 0.4, 0.4))),
+    }
+  }
+
+  /// Set the fast/slow thresholds in microseconds.
+  pub fn with_thresholds(mut self, fast_us: u64, slow_us: u64) -> Self {
+    self.fast_threshold_us = fast_us;
+    self.slow_threshold_us = slow_us;
+    self
+  }
+
+  /// Classify a mesh generation time.
+  pub fn classify(&self, timing_us: u64) -> TimingTint {
+    if timing_us < self.fast_threshold_us {
+      TimingTint::Fast
+    } else if timing_us > self.slow_threshold_us {
+      TimingTint::Slow
+    } else {
+      TimingTint::Medium
+    }
+  }
+
+  /// Material handle for a timing bucket.
+  pub fn material(&self, tint: TimingTint) -> Handle<StandardMaterial> {
+    match tint {
+      TimingTint::Fast => self.fast.clone(),
+      TimingTint::Medium => self.medium.clone(),
+      TimingTint::Slow => self.slow.clone(),
+    }
+  }
+}
+
+/// System tinting newly spawned chunks by their mesh generation time.
+pub fn apply_chunk_timing_tint(
+  overlay: Option<Res<ChunkTimingOverlay>>,
+  mut chunks: Query<(&VoxelChunk, &mut MeshMaterial3d<StandardMaterial>), Added<VoxelChunk>>,
+) {
+  let Some(overlay) = overlay else {
+    return;
+  };
+  if !overlay.enabled {
+    return;
+  }
+
+  for (chunk, mut material) in &mut chunks {
+    material.0 = overlay.material(overlay.classify(chunk.timing_us));
+  }
+}
+
+#[cfg(test)]
+#[path = "timing_overlay_test.rs"]
+mod timing_overlay_test;
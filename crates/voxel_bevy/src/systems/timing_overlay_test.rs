@@ -0,0 +1,87 @@
+//! Tests for the chunk timing overlay.
+
+use bevy::prelude::*;
+use voxel_plugin::octree::OctreeNode;
+use voxel_plugin::pipeline::PresentationHint;
+use voxel_plugin::world::WorldId;
+
+use super::{apply_chunk_timing_tint, ChunkTimingOverlay, TimingTint};
+use crate::components::VoxelChunk;
+
+fn spawn_chunk(app: &mut App, timing_us: u64) -> Entity {
+  app
+    .world_mut()
+    .spawn((
+      MeshMaterial3d::<StandardMaterial>(Handle::default()),
+      VoxelChunk {
+        world_id: WorldId::new(),
+        node: OctreeNode::new(0, 0, 0, 0),
+        timing_us,
+        hint: PresentationHint::Immediate,
+      },
+    ))
+    .id()
+}
+
+#[test]
+fn test_classify_thresholds() {
+  let mut materials = Assets::<StandardMaterial>::default();
+  let overlay = ChunkTimingOverlay::new(&mut materials).with_thresholds(100, 1_000);
+
+  assert_eq!(overlay.classify(50), TimingTint::Fast);
+  assert_eq!(overlay.classify(500), TimingTint::Medium);
+  assert_eq!(overlay.classify(10_000), TimingTint::Slow);
+}
+
+#[test]
+fn test_chunks_tinted_by_timing() {
+  let mut app = App::new();
+  let mut materials = Assets::<StandardMaterial>::default();
+  let overlay = ChunkTimingOverlay::new(&mut materials).with_thresholds(100, 1_000);
+  let fast_handle = overlay.fast.clone();
+  let slow_handle = overlay.slow.clone();
+
+  app.insert_resource(materials);
+  app.insert_resource(overlay);
+  app.add_systems(Update, apply_chunk_timing_tint);
+
+  let fast = spawn_chunk(&mut app, 20);
+  let slow = spawn_chunk(&mut app, 50_000);
+
+  app.update();
+
+  let world = app.world();
+  assert_eq!(
+    world.get::<MeshMaterial3d<StandardMaterial>>(fast).unwrap().0,
+    fast_handle
+  );
+  assert_eq!(
+    world.get::<MeshMaterial3d<StandardMaterial>>(slow).unwrap().0,
+    slow_handle
+  );
+}
+
+#[test]
+fn test_disabled_overlay_leaves_materials() {
+  let mut app = App::new();
+  let mut materials = Assets::<StandardMaterial>::default();
+  let mut overlay = ChunkTimingOverlay::new(&mut materials);
+  overlay.enabled = false;
+
+  app.insert_resource(materials);
+  app.insert_resource(overlay);
+  app.add_systems(Update, apply_chunk_timing_tint);
+
+  let chunk = spawn_chunk(&mut app, 50_000);
+
+  app.update();
+
+  assert_eq!(
+    app
+      .world()
+      .get::<MeshMaterial3d<StandardMaterial>>(chunk)
+      .unwrap()
+      .0,
+    Handle::default()
+  );
+}
@@ -4,12 +4,19 @@
 //! Chunks track which world they belong to via `WorldId`.
 
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
 
+use bevy::math::DVec3;
 use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, IoTaskPool, Task};
 use voxel_plugin::octree::{OctreeConfig, OctreeNode};
-use voxel_plugin::pipeline::VolumeSampler;
-use voxel_plugin::world::WorldId;
-use voxel_plugin::VoxelWorld;
+use voxel_plugin::pipeline::{PresentationBatch, VolumeSampler};
+use voxel_plugin::world::{UpdateResult, WorldId};
+use voxel_plugin::{SdfBrush, VoxelWorld};
+
+use crate::components::{ViewerWorld, VoxelChunk, VoxelViewer};
+use crate::systems::entities::mesh_output_to_bevy;
 
 // =============================================================================
 // VoxelWorldRoot - Component wrapping VoxelWorld for ECS
@@ -17,9 +24,10 @@ use voxel_plugin::VoxelWorld;
 
 /// Component wrapping a VoxelWorld for Bevy ECS.
 ///
-/// Uses type-erased sampler (`Box<dyn VolumeSampler>`) for ECS uniform
-/// component types. Each entity with this component represents an independent
-/// voxel world.
+/// Uses type-erased sampler (`Arc<dyn VolumeSampler>`) for ECS uniform
+/// component types, shared with the meshing `update_voxel_worlds` runs on
+/// the `AsyncComputeTaskPool`. Each entity with this component represents an
+/// independent voxel world.
 ///
 /// # Example
 ///
@@ -32,14 +40,17 @@ use voxel_plugin::VoxelWorld;
 #[derive(Component)]
 pub struct VoxelWorldRoot {
   /// The underlying voxel world state.
-  pub world: VoxelWorld<Box<dyn VolumeSampler>>,
+  pub world: VoxelWorld<Arc<dyn VolumeSampler>>,
+  /// Meshing started by `update_voxel_worlds`, still running.
+  update: Option<Task<UpdateResult>>,
 }
 
 impl VoxelWorldRoot {
   /// Create a new voxel world root with the given config and sampler.
   pub fn new(config: OctreeConfig, sampler: Box<dyn VolumeSampler>) -> Self {
     Self {
-      world: VoxelWorld::new(config, sampler),
+      world: VoxelWorld::new(config, sampler.into()),
+      update: None,
     }
   }
 
@@ -50,7 +61,8 @@ impl VoxelWorldRoot {
     initial_lod: i32,
   ) -> Self {
     Self {
-      world: VoxelWorld::new_with_initial_lod(config, sampler, initial_lod),
+      world: VoxelWorld::new_with_initial_lod(config, sampler.into(), initial_lod),
+      update: None,
     }
   }
 
@@ -65,6 +77,12 @@ impl VoxelWorldRoot {
   pub fn config(&self) -> &OctreeConfig {
     &self.world.config
   }
+
+  /// Whether `update_voxel_worlds` is still meshing an update of this world.
+  #[inline]
+  pub fn is_updating(&self) -> bool {
+    self.update.is_some()
+  }
 }
 
 // =============================================================================
@@ -183,6 +201,339 @@ pub fn sync_world_transforms(
   }
 }
 
+/// Material for the chunks `update_voxel_worlds` spawns in a world.
+///
+/// Put it on the `VoxelWorldRoot` entity; worlds without one use the default
+/// material handle.
+#[derive(Component, Clone, Default)]
+pub struct VoxelWorldMaterial(pub Handle<StandardMaterial>);
+
+/// Positions of the viewers driving `root`, in the world's local space.
+///
+/// Includes every `VoxelViewer` that is unbound or bound to this world with
+/// `ViewerWorld`.
+pub fn world_viewer_positions<'a>(
+  root: &VoxelWorldRoot,
+  viewers: impl IntoIterator<Item = (&'a GlobalTransform, Option<&'a ViewerWorld>)>,
+) -> Vec<DVec3> {
+  viewers
+    .into_iter()
+    .filter(|(_, binding)| ViewerWorld::drives(*binding, root.id()))
+    .map(|(transform, _)| {
+      root
+        .world
+        .viewer_to_local(transform.translation().as_dvec3())
+    })
+    .collect()
+}
+
+/// Opts a `VoxelWorldRoot` out of `update_voxel_worlds`, for apps that
+/// refine and mesh the world themselves.
+#[derive(Component, Clone, Copy, Default)]
+pub struct ManualWorldUpdates;
+
+/// System refining and meshing every `VoxelWorldRoot` around its viewers.
+///
+/// Each world is refined with `VoxelWorld::begin_update_multi` from the
+/// positions given by `world_viewer_positions`, and its meshing runs on the
+/// `AsyncComputeTaskPool`; the chunks are replaced once it completes, and the
+/// world is refined again in the same frame. Worlds with no viewer are left
+/// as is, and chunks meshed before a concurrent edit are remeshed right away.
+///
+/// Chunks are spawned as children of their world's root entity, so they
+/// follow its transform, and are tracked only in `WorldChunkMap` (a node can
+/// exist in several worlds, which `ChunkEntityMap` can't represent).
+pub fn update_voxel_worlds(
+  mut commands: Commands,
+  mut meshes: ResMut<Assets<Mesh>>,
+  mut chunk_map: ResMut<WorldChunkMap>,
+  mut worlds: Query<
+    (Entity, &mut VoxelWorldRoot, Option<&VoxelWorldMaterial>),
+    Without<ManualWorldUpdates>,
+  >,
+  viewers: Query<(&GlobalTransform, Option<&ViewerWorld>), With<VoxelViewer>>,
+) {
+  for (root_entity, mut root, material) in &mut worlds {
+    let root = &mut *root;
+    if let Some(task) = &mut root.update {
+      let Some(result) = block_on(future::poll_once(task)) else {
+        continue;
+      };
+      root.update = None;
+
+      let world_id = root.id();
+      let mut batches = vec![root.world.finish_update(result)];
+      if !root.world.dirty.is_empty() {
+        batches.push(root.world.remesh_dirty());
+      }
+      for batch in batches {
+        apply_world_batch(
+          &mut commands,
+          &mut meshes,
+          &mut chunk_map,
+          root_entity,
+          world_id,
+          material,
+          batch,
+        );
+      }
+    }
+
+    let positions = world_viewer_positions(root, &viewers);
+    let Some((&viewer_pos, additional)) = positions.split_first() else {
+      continue;
+    };
+
+    if let Some(job) = root.world.begin_update_multi(viewer_pos, additional) {
+      root.update = Some(AsyncComputeTaskPool::get().spawn(async move { job.run() }));
+    }
+  }
+}
+
+/// Undo or redo request for one world's edit history.
+///
+/// Handled by `apply_edit_history`, which remeshes the touched chunks in the
+/// same frame.
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoxelEditHistory {
+  /// Take back the world's most recent edit (`VoxelWorld::undo_edit`).
+  Undo(WorldId),
+  /// Reapply the world's last undone edit (`VoxelWorld::redo_edit`).
+  Redo(WorldId),
+}
+
+/// System applying `VoxelEditHistory` requests and remeshing dirty chunks.
+///
+/// Requests for unknown worlds, or with nothing to undo/redo, are ignored.
+/// Chunks are replaced like in `update_voxel_worlds`.
+pub fn apply_edit_history(
+  mut commands: Commands,
+  mut requests: MessageReader<VoxelEditHistory>,
+  mut meshes: ResMut<Assets<Mesh>>,
+  mut chunk_map: ResMut<WorldChunkMap>,
+  mut worlds: Query<(Entity, &mut VoxelWorldRoot, Option<&VoxelWorldMaterial>)>,
+) {
+  for request in requests.read() {
+    let (VoxelEditHistory::Undo(world_id) | VoxelEditHistory::Redo(world_id)) = *request;
+    let Some((root_entity, mut root, material)) =
+      worlds.iter_mut().find(|(_, root, _)| root.id() == world_id)
+    else {
+      continue;
+    };
+
+    let changed = match request {
+      VoxelEditHistory::Undo(_) => root.world.undo_edit(),
+      VoxelEditHistory::Redo(_) => root.world.redo_edit(),
+    };
+    if changed.is_none() {
+      continue;
+    }
+
+    let batch = root.world.remesh_dirty();
+    apply_world_batch(
+      &mut commands,
+      &mut meshes,
+      &mut chunk_map,
+      root_entity,
+      world_id,
+      material,
+      batch,
+    );
+  }
+}
+
+/// File a world's edits are saved to and restored from.
+///
+/// Put it on the `VoxelWorldRoot` entity. `load_world_edits` restores the
+/// file's edits when the component is added; `save_world_edits` rewrites the
+/// file whenever the edits change, once it has been loaded (see
+/// [`VoxelEditSavePlugin`]).
+#[derive(Component)]
+pub struct VoxelEditSaveFile {
+  /// Path of the save file (see `VoxelWorld::save_edits`).
+  pub path: PathBuf,
+  /// Whether `load_world_edits` read `path`, or found it missing. A file
+  /// that failed to read or parse is never overwritten.
+  loaded: bool,
+  /// Edits last read from or written to `path`.
+  saved: Option<Vec<SdfBrush>>,
+  /// Write in flight on the `IoTaskPool`, with the edits it writes.
+  writing: Option<(Vec<SdfBrush>, Task<std::io::Result<()>>)>,
+}
+
+impl VoxelEditSaveFile {
+  pub fn new(path: impl Into<PathBuf>) -> Self {
+    Self {
+      path: path.into(),
+      loaded: false,
+      saved: None,
+      writing: None,
+    }
+  }
+
+  /// Whether a save is still being written.
+  pub fn is_saving(&self) -> bool {
+    self.writing.is_some()
+  }
+
+  /// Whether the file was loaded, so edits may be saved over it.
+  pub fn is_loaded(&self) -> bool {
+    self.loaded
+  }
+}
+
+/// Plugin running `load_world_edits` before `save_world_edits` in `Update`.
+pub struct VoxelEditSavePlugin;
+
+impl Plugin for VoxelEditSavePlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .init_resource::<WorldChunkMap>()
+      .add_systems(Update, (load_world_edits, save_world_edits).chain());
+  }
+}
+
+/// System restoring saved edits into newly added `VoxelEditSaveFile` worlds.
+///
+/// A missing file is not an error (nothing was saved yet). A file that fails
+/// to read or parse, such as one from a newer `EDITS_FORMAT_VERSION`, is left
+/// alone: the world starts unedited and its edits are not saved. Leaves
+/// touched by the restored edits are remeshed like in `apply_edit_history`.
+pub fn load_world_edits(
+  mut commands: Commands,
+  mut meshes: ResMut<Assets<Mesh>>,
+  mut chunk_map: ResMut<WorldChunkMap>,
+  mut worlds: Query<
+    (
+      Entity,
+      &mut VoxelWorldRoot,
+      &mut VoxelEditSaveFile,
+      Option<&VoxelWorldMaterial>,
+    ),
+    Added<VoxelEditSaveFile>,
+  >,
+) {
+  for (root_entity, mut root, mut save_file, material) in &mut worlds {
+    let bytes = match std::fs::read(&save_file.path) {
+      Ok(bytes) => bytes,
+      Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+        save_file.loaded = true;
+        continue;
+      }
+      Err(err) => {
+        warn!("Failed to read edits from {:?}: {}", save_file.path, err);
+        continue;
+      }
+    };
+    if root.world.load_edits(&bytes).is_none() {
+      warn!("Ignoring invalid edit save {:?}", save_file.path);
+      continue;
+    }
+    save_file.loaded = true;
+    save_file.saved = Some(root.world.edits.clone());
+
+    let world_id = root.id();
+    let batch = root.world.remesh_dirty();
+    apply_world_batch(
+      &mut commands,
+      &mut meshes,
+      &mut chunk_map,
+      root_entity,
+      world_id,
+      material,
+      batch,
+    );
+  }
+}
+
+/// System writing each world's edits to its `VoxelEditSaveFile` when they
+/// differ from what the file last held.
+///
+/// Files `load_world_edits` has not loaded are skipped, so it must run first
+/// (see [`VoxelEditSavePlugin`]).
+///
+/// The file is written on the `IoTaskPool`, one write per file at a time:
+/// edits made meanwhile are saved once it finishes. Each write goes to a
+/// `.tmp` file next to `path` that is then renamed over it, so an
+/// interrupted save leaves the previous one intact. Missing parent
+/// directories are created.
+pub fn save_world_edits(mut worlds: Query<(&VoxelWorldRoot, &mut VoxelEditSaveFile)>) {
+  for (root, mut save_file) in &mut worlds {
+    let save_file = &mut *save_file;
+    if let Some((edits, task)) = &mut save_file.writing {
+      let Some(written) = block_on(future::poll_once(task)) else {
+        continue;
+      };
+      match written {
+        Ok(()) => save_file.saved = Some(std::mem::take(edits)),
+        Err(err) => warn!("Failed to save edits to {:?}: {}", save_file.path, err),
+      }
+      save_file.writing = None;
+    }
+
+    if !save_file.loaded || save_file.saved.as_deref() == Some(root.world.edits.as_slice()) {
+      continue;
+    }
+
+    let bytes = root.world.save_edits();
+    let path = save_file.path.clone();
+    let task = IoTaskPool::get().spawn(async move {
+      let mut tmp = path.clone().into_os_string();
+      tmp.push(".tmp");
+      if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+      }
+      std::fs::write(&tmp, &bytes)?;
+      std::fs::rename(&tmp, &path)
+    });
+    save_file.writing = Some((root.world.edits.clone(), task));
+  }
+}
+
+/// Despawn and spawn a world's chunks from a presentation batch.
+///
+/// Spawned chunks are children of `root_entity`, registered in `chunk_map`;
+/// a chunk already spawned for the same node is despawned.
+pub(crate) fn apply_world_batch(
+  commands: &mut Commands,
+  meshes: &mut Assets<Mesh>,
+  chunk_map: &mut WorldChunkMap,
+  root_entity: Entity,
+  world_id: WorldId,
+  material: Option<&VoxelWorldMaterial>,
+  batch: PresentationBatch,
+) {
+  let material = material.map(|m| m.0.clone()).unwrap_or_default();
+
+  for node in &batch.to_despawn {
+    if let Some(entity) = chunk_map.remove(world_id, node) {
+      commands.entity(entity).despawn();
+    }
+  }
+
+  for chunk in batch.to_spawn {
+    let entity = commands
+      .spawn((
+        Mesh3d(meshes.add(mesh_output_to_bevy(&chunk.output))),
+        MeshMaterial3d(material.clone()),
+        Transform::from_translation(chunk.position.as_vec3())
+          .with_scale(Vec3::splat(chunk.scale as f32)),
+        VoxelChunk {
+          world_id,
+          node: chunk.node,
+          timing_us: 0,
+          hint: chunk.hint,
+        },
+        ChildOf(root_entity),
+      ))
+      .id();
+    if let Some(replaced) = chunk_map.remove(world_id, &chunk.node) {
+      commands.entity(replaced).despawn();
+    }
+    chunk_map.insert(world_id, chunk.node, entity);
+  }
+}
+
 /// System to cleanup chunk entities when a VoxelWorldRoot is despawned.
 ///
 /// Since `RemovedComponents` doesn't provide the removed component's data,
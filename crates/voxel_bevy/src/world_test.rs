@@ -1,14 +1,23 @@
 //! Tests for world isolation types.
 
+use bevy::math::DVec3;
 use bevy::prelude::*;
+use bevy::tasks::{AsyncComputeTaskPool, IoTaskPool, TaskPool};
 use voxel_plugin::constants::SAMPLE_SIZE_CB;
+use voxel_plugin::edit::EDITS_FORMAT_VERSION;
 use voxel_plugin::octree::OctreeConfig;
-use voxel_plugin::octree::OctreeNode;
+use voxel_plugin::octree::{DAabb3, OctreeNode};
 use voxel_plugin::pipeline::VolumeSampler;
+use voxel_plugin::sdf_samplers::{GroundPlaneSampler, MetaballsSampler};
 use voxel_plugin::types::{MaterialId, SdfSample};
 use voxel_plugin::world::WorldId;
+use voxel_plugin::{EditOp, SdfBrush};
 
-use super::{VoxelWorldRoot, WorldChunkMap};
+use super::{
+  apply_edit_history, load_world_edits, update_voxel_worlds, ManualWorldUpdates, VoxelEditHistory,
+  VoxelEditSaveFile, VoxelEditSavePlugin, VoxelWorldRoot, WorldChunkMap,
+};
+use crate::components::{ViewerWorld, VoxelChunk, VoxelViewer};
 
 struct MockSampler;
 
@@ -24,6 +33,10 @@ impl VolumeSampler for MockSampler {
     volume.fill(127);
     materials.fill(0);
   }
+
+  fn sample_point(&self, _position: [f64; 3], _voxel_size: f64) -> SdfSample {
+    127
+  }
 }
 
 #[test]
@@ -87,3 +100,361 @@ fn test_voxel_world_root_with_initial_lod() {
 
   assert_eq!(root.world.leaves.len(), 1);
 }
+
+/// Bounded world of seeded metaballs around the origin.
+fn metaballs_world(seed: u32) -> VoxelWorldRoot {
+  let config = OctreeConfig {
+    max_lod: 6,
+    world_bounds: Some(DAabb3::from_center_half_extents(
+      DVec3::ZERO,
+      DVec3::splat(500.0),
+    )),
+    ..OctreeConfig::default()
+  };
+  VoxelWorldRoot::new_with_initial_lod(
+    config,
+    Box::new(MetaballsSampler::random(seed, 6, 200.0)),
+    6,
+  )
+}
+
+fn multi_world_app() -> App {
+  AsyncComputeTaskPool::get_or_init(TaskPool::default);
+  let mut app = App::new();
+  app.insert_resource(Assets::<Mesh>::default());
+  app.init_resource::<WorldChunkMap>();
+  app.add_systems(Update, update_voxel_worlds);
+  app
+}
+
+fn world_chunk_count(app: &App, world_id: WorldId) -> usize {
+  app
+    .world()
+    .resource::<WorldChunkMap>()
+    .get_world_chunks(world_id)
+    .map_or(0, |chunks| chunks.len())
+}
+
+/// Run `update_voxel_worlds` until `done`, giving its meshing tasks time.
+fn update_until(app: &mut App, done: impl Fn(&App) -> bool) {
+  for _ in 0..1000 {
+    app.update();
+    if done(app) {
+      return;
+    }
+    std::thread::sleep(std::time::Duration::from_millis(1));
+  }
+}
+
+#[test]
+fn test_two_worlds_chunks_coexist() {
+  let mut app = multi_world_app();
+
+  let main = metaballs_world(1);
+  let preview = metaballs_world(2);
+  let (main_id, preview_id) = (main.id(), preview.id());
+  let main_entity = app.world_mut().spawn(main).id();
+  let preview_entity = app.world_mut().spawn(preview).id();
+  app
+    .world_mut()
+    .spawn((GlobalTransform::default(), VoxelViewer));
+
+  update_until(&mut app, |app| {
+    world_chunk_count(app, main_id) > 0 && world_chunk_count(app, preview_id) > 0
+  });
+
+  assert!(world_chunk_count(&app, main_id) > 0);
+  assert!(world_chunk_count(&app, preview_id) > 0);
+  let chunk_map = app.world().resource::<WorldChunkMap>();
+  assert_eq!(chunk_map.world_count(), 2);
+
+  // Every chunk is tagged with and parented to its own world
+  for (world_id, root_entity) in [(main_id, main_entity), (preview_id, preview_entity)] {
+    for &entity in chunk_map.get_world_chunks(world_id).unwrap().values() {
+      let chunk = app.world().get::<VoxelChunk>(entity).unwrap();
+      assert_eq!(chunk.world_id, world_id);
+      assert_eq!(
+        app.world().get::<ChildOf>(entity).map(ChildOf::parent),
+        Some(root_entity)
+      );
+    }
+  }
+}
+
+#[test]
+fn test_bound_viewer_drives_only_its_world() {
+  let mut app = multi_world_app();
+
+  let main = metaballs_world(1);
+  let preview = metaballs_world(2);
+  let (main_id, preview_id) = (main.id(), preview.id());
+  app.world_mut().spawn(main);
+  app.world_mut().spawn(preview);
+  app.world_mut().spawn((
+    GlobalTransform::default(),
+    VoxelViewer,
+    ViewerWorld(preview_id),
+  ));
+
+  update_until(&mut app, |app| world_chunk_count(app, preview_id) > 0);
+
+  assert!(world_chunk_count(&app, preview_id) > 0);
+  assert_eq!(world_chunk_count(&app, main_id), 0);
+}
+
+#[test]
+fn test_update_meshes_off_the_main_thread() {
+  let mut app = multi_world_app();
+  let root = metaballs_world(1);
+  let world_id = root.id();
+  let entity = app.world_mut().spawn(root).id();
+  app
+    .world_mut()
+    .spawn((GlobalTransform::default(), VoxelViewer));
+
+  // The first frame only refines and starts meshing
+  app.update();
+  let root = app.world().get::<VoxelWorldRoot>(entity).unwrap();
+  assert!(root.is_updating());
+  assert_eq!(world_chunk_count(&app, world_id), 0);
+
+  update_until(&mut app, |app| world_chunk_count(app, world_id) > 0);
+  assert!(world_chunk_count(&app, world_id) > 0);
+}
+
+#[test]
+fn test_manual_world_is_not_updated() {
+  let mut app = multi_world_app();
+  let main = metaballs_world(1);
+  let manual = metaballs_world(2);
+  let (main_id, manual_id) = (main.id(), manual.id());
+  let initial_leaves = manual.world.leaves.as_set().clone();
+  app.world_mut().spawn(main);
+  let manual_entity = app.world_mut().spawn((manual, ManualWorldUpdates)).id();
+  app
+    .world_mut()
+    .spawn((GlobalTransform::default(), VoxelViewer));
+
+  update_until(&mut app, |app| world_chunk_count(app, main_id) > 0);
+
+  assert!(world_chunk_count(&app, main_id) > 0);
+  assert_eq!(world_chunk_count(&app, manual_id), 0);
+  let manual = app.world().get::<VoxelWorldRoot>(manual_entity).unwrap();
+  assert_eq!(manual.world.leaves.as_set(), &initial_leaves);
+}
+
+#[test]
+fn test_undo_message_restores_volume_and_remeshes() {
+  let mut app = App::new();
+  app.insert_resource(Assets::<Mesh>::default());
+  app.init_resource::<WorldChunkMap>();
+  app.add_message::<VoxelEditHistory>();
+  app.add_systems(Update, apply_edit_history);
+
+  let config = OctreeConfig {
+    world_bounds: Some(DAabb3::new(DVec3::ZERO, DVec3::splat(56.0))),
+    ..OctreeConfig::default()
+  };
+  let mut root = VoxelWorldRoot::new(config, Box::new(GroundPlaneSampler::new(14.3)));
+  let node = OctreeNode::new(0, 0, 0, 0);
+  root.world.leaves.insert(node);
+  let (original, _) = root.world.sample_node_volume(&node);
+
+  root.world.apply_edit(SdfBrush::Sphere {
+    center: DVec3::new(14.0, 14.3, 14.0),
+    radius: 4.0,
+    op: EditOp::Remove,
+  });
+  root.world.remesh_dirty();
+  let world_id = root.id();
+  let root_entity = app.world_mut().spawn(root).id();
+
+  app
+    .world_mut()
+    .write_message(VoxelEditHistory::Undo(world_id));
+  app.update();
+
+  let root = app.world().get::<VoxelWorldRoot>(root_entity).unwrap();
+  assert!(root.world.edits.is_empty());
+  assert_eq!(root.world.sample_node_volume(&node).0, original);
+  // The undone leaf still crosses the ground, so it was respawned
+  assert!(app
+    .world()
+    .resource::<WorldChunkMap>()
+    .contains(world_id, &node));
+}
+
+/// App loading and saving `VoxelEditSaveFile`s.
+fn save_app() -> App {
+  IoTaskPool::get_or_init(TaskPool::default);
+  let mut app = App::new();
+  app.insert_resource(Assets::<Mesh>::default());
+  app.add_plugins(VoxelEditSavePlugin);
+  app
+}
+
+/// Run `save_world_edits` until `entity`'s save has been written.
+fn finish_saving(app: &mut App, entity: Entity) {
+  for _ in 0..1000 {
+    app.update();
+    let save_file = app.world().get::<VoxelEditSaveFile>(entity).unwrap();
+    if !save_file.is_saving() {
+      return;
+    }
+    std::thread::sleep(std::time::Duration::from_millis(1));
+  }
+  panic!("edits never saved");
+}
+
+#[test]
+fn test_unchanged_edits_are_not_rewritten() {
+  let path = std::env::temp_dir()
+    .join(format!("voxel_bevy_unchanged_{}", std::process::id()))
+    .join("world.edits");
+  let mut root = VoxelWorldRoot::new(
+    OctreeConfig::default(),
+    Box::new(GroundPlaneSampler::new(0.0)),
+  );
+  root.world.apply_edit(SdfBrush::Sphere {
+    center: DVec3::ZERO,
+    radius: 4.0,
+    op: EditOp::Remove,
+  });
+
+  let mut app = save_app();
+  let entity = app
+    .world_mut()
+    .spawn((root, VoxelEditSaveFile::new(&path)))
+    .id();
+  finish_saving(&mut app, entity);
+  assert!(path.exists());
+  let mut tmp = path.clone().into_os_string();
+  tmp.push(".tmp");
+  assert!(!std::path::Path::new(&tmp).exists());
+
+  // Nothing changed: no new write
+  std::fs::remove_file(&path).unwrap();
+  app.update();
+  let save_file = app.world().get::<VoxelEditSaveFile>(entity).unwrap();
+  assert!(!save_file.is_saving());
+  assert!(!path.exists());
+
+  // A new edit is saved again
+  app
+    .world_mut()
+    .get_mut::<VoxelWorldRoot>(entity)
+    .unwrap()
+    .world
+    .apply_edit(SdfBrush::Sphere {
+      center: DVec3::splat(8.0),
+      radius: 2.0,
+      op: EditOp::Remove,
+    });
+  finish_saving(&mut app, entity);
+  let rewritten = path.exists();
+  std::fs::remove_dir_all(path.parent().unwrap()).ok();
+  assert!(rewritten);
+}
+
+#[test]
+fn test_saved_edits_reload_into_new_world() {
+  let path = std::env::temp_dir()
+    .join(format!("voxel_edits_{}", std::process::id()))
+    .join("world.edits");
+  let config = OctreeConfig {
+    world_bounds: Some(DAabb3::new(DVec3::ZERO, DVec3::splat(56.0))),
+    ..OctreeConfig::default()
+  };
+  let node = OctreeNode::new(0, 0, 0, 0);
+  let new_root = || {
+    let mut root = VoxelWorldRoot::new(config.clone(), Box::new(GroundPlaneSampler::new(14.3)));
+    root.world.leaves.insert(node);
+    root
+  };
+
+  // Edit and save
+  let mut app = save_app();
+  let mut edited = new_root();
+  edited.world.apply_edit(SdfBrush::Sphere {
+    center: DVec3::new(14.0, 14.3, 14.0),
+    radius: 4.0,
+    op: EditOp::Remove,
+  });
+  let edited_volume = edited.world.sample_node_volume(&node);
+  let saved = app
+    .world_mut()
+    .spawn((edited, VoxelEditSaveFile::new(&path)))
+    .id();
+  finish_saving(&mut app, saved);
+  assert!(path.exists());
+
+  // Restore into a fresh world on spawn
+  let mut app = App::new();
+  app.insert_resource(Assets::<Mesh>::default());
+  app.init_resource::<WorldChunkMap>();
+  app.add_systems(Update, load_world_edits);
+  let root = new_root();
+  let world_id = root.id();
+  let entity = app
+    .world_mut()
+    .spawn((root, VoxelEditSaveFile::new(&path)))
+    .id();
+  app.update();
+  std::fs::remove_dir_all(path.parent().unwrap()).ok();
+
+  let root = app.world().get::<VoxelWorldRoot>(entity).unwrap();
+  assert_eq!(root.world.edits.len(), 1);
+  assert_eq!(root.world.sample_node_volume(&node), edited_volume);
+  assert!(app
+    .world()
+    .resource::<WorldChunkMap>()
+    .contains(world_id, &node));
+}
+
+#[test]
+fn test_unreadable_save_is_left_untouched() {
+  let dir = std::env::temp_dir().join(format!("voxel_bevy_unreadable_{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  let mut newer = b"EDIT".to_vec();
+  newer.extend([EDITS_FORMAT_VERSION + 1, 0]);
+  let saves = [
+    (dir.join("corrupt.edits"), b"not an edit save".to_vec()),
+    (dir.join("newer.edits"), newer),
+  ];
+
+  let mut app = save_app();
+  let mut entities = Vec::new();
+  for (path, bytes) in &saves {
+    std::fs::write(path, bytes).unwrap();
+    let mut root = VoxelWorldRoot::new(
+      OctreeConfig::default(),
+      Box::new(GroundPlaneSampler::new(0.0)),
+    );
+    root.world.apply_edit(SdfBrush::Sphere {
+      center: DVec3::ZERO,
+      radius: 4.0,
+      op: EditOp::Remove,
+    });
+    entities.push(
+      app
+        .world_mut()
+        .spawn((root, VoxelEditSaveFile::new(path)))
+        .id(),
+    );
+  }
+  for _ in 0..10 {
+    app.update();
+  }
+
+  let contents: Vec<_> = saves
+    .iter()
+    .map(|(path, _)| std::fs::read(path).unwrap())
+    .collect();
+  std::fs::remove_dir_all(&dir).ok();
+  for ((_, bytes), (entity, contents)) in saves.iter().zip(entities.into_iter().zip(contents)) {
+    let save_file = app.world().get::<VoxelEditSaveFile>(entity).unwrap();
+    assert!(!save_file.is_loaded());
+    assert!(!save_file.is_saving());
+    assert_eq!(&contents, bytes);
+  }
+}
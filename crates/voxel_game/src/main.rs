@@ -32,9 +32,7 @@ pub async fn wasm_main() {
 
   console_error_panic_hook::set_once();
 
-  let threads = std::thread::available_parallelism()
-    .map(|n| n.get())
-    .unwrap_or(4);
+  let threads = voxel_plugin::threading::recommended_worker_count();
 
   let promise = wasm_bindgen_rayon::init_thread_pool(threads);
   let _ = JsFuture::from(promise).await;
@@ -45,6 +43,11 @@ pub async fn wasm_main() {
 /// Native entry point
 #[cfg(not(target_arch = "wasm32"))]
 fn main() {
+  // Size the meshing pool the same way as WASM (honors VOXEL_THREADS)
+  let _ = rayon::ThreadPoolBuilder::new()
+    .num_threads(voxel_plugin::threading::recommended_worker_count())
+    .build_global();
+
   run();
 }
 
@@ -13,6 +13,7 @@
 use std::collections::HashMap;
 
 use bevy::prelude::*;
+use bevy::camera::visibility::VisibilitySystems;
 use bevy::camera::Exposure;
 use bevy::core_pipeline::tonemapping::Tonemapping;
 use bevy::light::{light_consts::lux, CascadeShadowConfigBuilder};
@@ -20,20 +21,35 @@ use bevy::post_process::bloom::Bloom;
 use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
 use rand::{Rng, SeedableRng};
 use smallvec::SmallVec;
-use voxel_bevy::components::{VoxelChunk, VoxelViewer};
-use voxel_bevy::entity_queue::{EntityQueue, EntityQueueConfig};
+use voxel_bevy::components::{ChunkFade, FadeDirection, ViewerWorld, VoxelChunk, VoxelViewer};
+use voxel_bevy::entity_queue::{EntityPool, EntityQueue, EntityQueueConfig};
 use crate::fly_camera::{fly_camera_input_bundle, update_fly_camera, CameraInputContext, FlyCamera};
 use voxel_bevy::resources::{ChunkEntityMap, VoxelMetricsResource};
-use voxel_bevy::systems::entities::{spawn_chunk_entity, spawn_custom_material_chunk_entity};
-use voxel_bevy::world::{sync_world_transforms, VoxelWorldRoot, WorldChunkMap};
+use voxel_bevy::systems::entities::{
+  spawn_chunk_entity_with_hint, spawn_custom_material_chunk_entity_with_hint,
+};
+use voxel_bevy::systems::fade::{
+  animate_chunk_fades, begin_chunk_fades, ChunkFadeConfig, ChunkFadePlugin,
+};
+use voxel_bevy::systems::frustum_culling::cull_chunks_to_frustum;
+use voxel_bevy::systems::meshing_tasks::{
+	poll_custom_material_meshing_tasks, poll_meshing_tasks, VoxelMeshingTasks,
+};
+use voxel_bevy::world::{
+	sync_world_transforms, update_voxel_worlds, world_viewer_positions, ManualWorldUpdates,
+	VoxelWorldRoot, WorldChunkMap,
+};
 use crate::triplanar_material::{load_baked_terrain_material, LodMaterials, TerrainMaterial, TriplanarMaterial, TriplanarMaterialPlugin};
 #[cfg(feature = "metrics")]
 use voxel_bevy::debug_ui::voxel_metrics_ui;
-use voxel_plugin::noise::FastNoise2Terrain;
+use voxel_plugin::noise::{FastNoise2Terrain, NoiseHeightmapSampler};
 use voxel_plugin::octree::{
 	DAabb3, OctreeConfig, OctreeNode, RefinementBudget, TransitionGroup, TransitionType,
 };
-use voxel_plugin::pipeline::{AsyncPipeline, CompletedTransition, PipelineEvent, ReadyChunk};
+use voxel_plugin::pipeline::{
+	AsyncPipeline, CompletedTransition, PipelineEvent, PresentationHint, ReadyChunk,
+};
+use voxel_plugin::world::WorldId;
 // WASM compat: std::time::Instant panics on wasm32
 use web_time::Instant;
 
@@ -49,31 +65,56 @@ pub struct NoiseLodPlugin;
 impl Plugin for NoiseLodPlugin {
   fn build(&self, app: &mut App) {
     app
-      .add_plugins(TriplanarMaterialPlugin)
+      .add_plugins((TriplanarMaterialPlugin, ChunkFadePlugin))
       .init_resource::<UiSettings>()
       .init_resource::<WorldChunkMap>()
       .init_resource::<AsyncRefinementState>()
       .init_resource::<VoxelMetricsResource>()
+      .init_resource::<ChunkFadeConfig>()
       .add_message::<RebuildWorldEvent>()
       .add_message::<RefineWorldEvent>()
       .add_message::<InitialMeshGenEvent>()
       .add_systems(OnEnter(Scene::NoiseLod), setup)
-      .add_systems(OnExit(Scene::NoiseLod), cleanup_camera)
+      .add_systems(
+        OnExit(Scene::NoiseLod),
+        (cleanup_camera, clear_entity_pool, clear_meshing_tasks),
+      )
       .add_systems(
         Update,
         (
           update_fly_camera.run_if(in_state(Scene::NoiseLod)),
           sync_world_transforms.run_if(in_state(Scene::NoiseLod)),
+          update_voxel_worlds
+            .after(sync_world_transforms)
+            .run_if(in_state(Scene::NoiseLod)),
           toggle_lod_colors.run_if(in_state(Scene::NoiseLod)),
           rebuild_world.run_if(in_state(Scene::NoiseLod)),
           initial_mesh_gen.run_if(in_state(Scene::NoiseLod)),
-          poll_initial_mesh_gen.run_if(in_state(Scene::NoiseLod)),
+          (
+            poll_meshing_tasks,
+            poll_custom_material_meshing_tasks::<TriplanarMaterial>,
+          )
+            .run_if(in_state(Scene::NoiseLod)),
+          (tag_scene_chunks, finish_initial_mesh_gen)
+            .after(poll_meshing_tasks)
+            .after(poll_custom_material_meshing_tasks::<TriplanarMaterial>)
+            .run_if(in_state(Scene::NoiseLod)),
           run_refinement.run_if(in_state(Scene::NoiseLod)),
           poll_refinement.run_if(in_state(Scene::NoiseLod)),
           process_entity_queue.run_if(in_state(Scene::NoiseLod)),
+          (begin_chunk_fades, animate_chunk_fades)
+            .chain()
+            .after(process_entity_queue)
+            .run_if(in_state(Scene::NoiseLod)),
           continuous_refinement.run_if(in_state(Scene::NoiseLod)),
         ),
       )
+      .add_systems(
+        PostUpdate,
+        cull_chunks_to_frustum
+          .after(VisibilitySystems::CheckVisibility)
+          .run_if(in_state(Scene::NoiseLod)),
+      )
       .add_systems(
         EguiPrimaryContextPass,
         (
@@ -97,12 +138,32 @@ fn cleanup_camera(mut commands: Commands, camera_query: Query<Entity, With<crate
 	}
 }
 
+/// Despawn pooled chunk entities when leaving scene (they are not
+/// `SceneEntity`s while hidden in the pool)
+fn clear_entity_pool(mut commands: Commands, mut refinements: Query<&mut WorldRefinement>) {
+	for mut refinement in &mut refinements {
+		refinement.entity_queue.pool().clear(&mut commands);
+	}
+}
+
+/// Drop initial meshing still in flight when leaving scene
+fn clear_meshing_tasks(
+	mut commands: Commands,
+	mut async_state: ResMut<AsyncRefinementState>,
+) {
+	commands.remove_resource::<VoxelMeshingTasks>();
+	commands.remove_resource::<VoxelMeshingTasks<TriplanarMaterial>>();
+	async_state.initial_pending = false;
+}
+
 /// Sampler source selection.
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
 pub enum SamplerSource {
   /// FastNoise2 terrain with caves (native FFI or WASM JS bridge).
   #[default]
   FastNoise2,
+  /// FastNoise2 read as a 2D heightmap (no overhangs, much cheaper).
+  Heightmap,
 }
 
 impl SamplerSource {
@@ -110,6 +171,7 @@ impl SamplerSource {
   pub fn name(&self) -> &'static str {
     match self {
       Self::FastNoise2 => "FastNoise2",
+      Self::Heightmap => "Heightmap",
     }
   }
 }
@@ -183,34 +245,42 @@ impl Default for UiSettings {
 }
 
 /// Refinement state resource.
-/// Tracks entity processing queues and continuous refinement state.
-#[derive(Resource)]
+/// Tracks initial meshing and continuous refinement state.
+#[derive(Resource, Default)]
 struct AsyncRefinementState {
-	/// Pipeline for initial mesh gen.
-	initial_pipeline: AsyncPipeline,
-	/// Pipeline for continuous refinement (non-blocking).
-	refine_pipeline: AsyncPipeline,
-	/// Time-budgeted entity operation queue.
-	entity_queue: EntityQueue,
+	/// Initial leaves are still meshing on the `AsyncComputeTaskPool`
+	/// (`VoxelMeshingTasks`); refinement waits for them.
+	initial_pending: bool,
 	/// Whether continuous refinement is enabled.
 	continuous: bool,
 	/// Frames since last refinement check (for throttling continuous mode).
 	frames_since_check: u32,
+}
+
+/// Per-world refinement state of the scene's `VoxelWorldRoot`s.
+///
+/// Worlds with this component are refined by the scene's own systems, which
+/// color chunks per LOD and crossfade them, so they also carry
+/// `ManualWorldUpdates`; other worlds are updated by `update_voxel_worlds`.
+#[derive(Component)]
+struct WorldRefinement {
+	/// Pipeline for continuous refinement (non-blocking).
+	refine_pipeline: AsyncPipeline,
+	/// Time-budgeted entity operation queue.
+	entity_queue: EntityQueue,
 	/// Transition groups awaiting mesh results from refine_pipeline.
 	pending_transitions: Vec<TransitionGroup>,
 }
 
-impl Default for AsyncRefinementState {
+impl Default for WorldRefinement {
 	fn default() -> Self {
 		Self {
-			initial_pipeline: AsyncPipeline::new(),
 			refine_pipeline: AsyncPipeline::new(),
 			entity_queue: EntityQueue::new(EntityQueueConfig {
 				max_groups_per_frame: 8, // Apply up to 8 transition groups per frame
 				max_ms_per_frame: 4.0,   // 4ms budget
+				pool_high_water: 256,    // Reuse chunk entities across transitions
 			}),
-			continuous: false,
-			frames_since_check: 0,
 			pending_transitions: Vec::new(),
 		}
 	}
@@ -230,6 +300,7 @@ fn create_sampler(
 ) -> Box<dyn voxel_plugin::pipeline::VolumeSampler> {
   match sampler_source {
     SamplerSource::FastNoise2 => Box::new(FastNoise2Terrain::new(seed)),
+    SamplerSource::Heightmap => Box::new(NoiseHeightmapSampler::new(seed)),
   }
 }
 
@@ -301,6 +372,7 @@ fn setup_inner(
 		min_lod: settings.current.min_lod,
 		max_lod: settings.current.max_lod,
 		lod_exponent: settings.current.lod_exponent,
+		lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
 		world_bounds: Some(world_bounds),
 	};
 
@@ -328,162 +400,138 @@ fn setup_inner(
   };
 
   // 5. Spawn VoxelWorldRoot entity (no meshes yet - async will generate them)
-  commands.spawn((world_root, Transform::default(), SceneEntity));
+  commands.spawn((
+    world_root,
+    WorldRefinement::default(),
+    ManualWorldUpdates,
+    Transform::default(),
+    SceneEntity,
+  ));
 
-  // 6. Insert resources
+  // 6. Insert resources (initial meshing is time-sliced on the compute pool)
   commands.insert_resource(ChunkEntityMap::default());
+  commands.insert_resource(
+    VoxelMeshingTasks::new(lod_materials.neutral.clone())
+      .with_max_groups_per_frame(INITIAL_GROUPS_PER_FRAME),
+  );
+  commands.insert_resource(
+    VoxelMeshingTasks::for_material(terrain_material.handle.clone())
+      .with_max_groups_per_frame(INITIAL_GROUPS_PER_FRAME),
+  );
   commands.insert_resource(lod_materials);
   commands.insert_resource(terrain_material);
 }
 
+/// Initial leaves meshed per `VoxelMeshingTasks` task.
+const INITIAL_LEAVES_PER_GROUP: usize = 8;
+
+/// Initial groups applied per frame, within the tasks' 4ms time slice.
+const INITIAL_GROUPS_PER_FRAME: usize = 16;
+
 /// System to generate meshes for initial leaves (runs once at startup).
+///
+/// Leaves are meshed on the `AsyncComputeTaskPool` in small groups, and
+/// `poll_meshing_tasks` spawns them within a per-frame time budget, so the
+/// terrain streams in without a startup frame spike.
 fn initial_mesh_gen(
 	mut events: MessageReader<InitialMeshGenEvent>,
 	mut async_state: ResMut<AsyncRefinementState>,
-	world_roots: Query<&VoxelWorldRoot>,
+	world_roots: Query<&VoxelWorldRoot, With<WorldRefinement>>,
 	settings: Res<UiSettings>,
+	terrain_material: Option<Res<TerrainMaterial>>,
+	standard_tasks: Option<ResMut<VoxelMeshingTasks>>,
+	triplanar_tasks: Option<ResMut<VoxelMeshingTasks<TriplanarMaterial>>>,
 ) {
 	if events.read().next().is_none() {
 		return;
 	}
 
 	// Don't start if already processing
-	if async_state.initial_pipeline.is_busy() {
-		info!("[InitialGen] Pipeline busy, skipping");
+	if async_state.initial_pending {
+		info!("[InitialGen] Initial meshing busy, skipping");
 		return;
 	}
 
-	let Ok(world_root) = world_roots.single() else {
-		warn!("[InitialGen] VoxelWorldRoot not found");
+	let (Some(mut standard_tasks), Some(mut triplanar_tasks)) = (standard_tasks, triplanar_tasks)
+	else {
+		warn!("[InitialGen] VoxelMeshingTasks not found");
 		return;
 	};
 
-	let world_id = world_root.id();
-	let config = world_root.config().clone();
-	let leaves = world_root.world.leaves.as_set().clone();
-
-	// Create a "fake" transition that adds all initial leaves
-	// This tricks the async pipeline into generating meshes for them
-	let initial_nodes: SmallVec<[OctreeNode; 8]> = leaves.iter().copied().collect();
-	let initial_lod = config.suggest_initial_lod();
-	let transition = TransitionGroup {
-		transition_type: TransitionType::Subdivide,
-		group_key: OctreeNode::new(0, 0, 0, initial_lod + 1), // Dummy parent
-		nodes_to_remove: SmallVec::new(),
-		nodes_to_add: initial_nodes,
-	};
+	for world_root in &world_roots {
+		let world_id = world_root.id();
+		let config = world_root.config().clone();
+		let leaves = world_root.world.leaves.as_set().clone();
+
+		// "Fake" transitions that add the initial leaves, a few per task
+		let initial_nodes: Vec<OctreeNode> = leaves.iter().copied().collect();
+		let initial_lod = config.suggest_initial_lod();
+		let transitions: Vec<TransitionGroup> = initial_nodes
+			.chunks(INITIAL_LEAVES_PER_GROUP)
+			.map(|nodes| TransitionGroup {
+				transition_type: TransitionType::Subdivide,
+				group_key: OctreeNode::new(0, 0, 0, initial_lod + 1), // Dummy parent
+				nodes_to_remove: SmallVec::new(),
+				nodes_to_add: nodes.iter().copied().collect(),
+				culled: false,
+			})
+			.collect();
 
-	info!(
-		"[InitialGen] Starting async generation for {} leaves",
-		leaves.len()
-	);
+		info!(
+			"[InitialGen] Starting async generation for {} leaves in {} tasks",
+			leaves.len(),
+			transitions.len()
+		);
 
-	// Create sampler and start async processing (uses old pipeline for initial gen)
-	let started = match settings.current.sampler_source {
-		SamplerSource::FastNoise2 => {
-			let sampler = FastNoise2Terrain::new(settings.current.current_seed);
-			async_state
-				.initial_pipeline
-				.start(world_id, vec![transition], sampler, leaves, config)
+		let use_triplanar = settings.current.use_triplanar && terrain_material.is_some();
+		let sampler = world_root.world.sampler.clone();
+		if use_triplanar {
+			triplanar_tasks.enqueue(world_id, transitions, sampler, leaves, config);
+		} else {
+			standard_tasks.enqueue(world_id, transitions, sampler, leaves, config);
 		}
-	};
-
-	if started {
-		// Enable continuous refinement after initial gen completes
-		async_state.continuous = true;
-	} else {
-		warn!("[InitialGen] Failed to start pipeline");
 	}
+
+	async_state.initial_pending = true;
 }
 
-/// System to poll for initial mesh generation completion.
-fn poll_initial_mesh_gen(
+/// Mark chunks spawned by `poll_meshing_tasks` as scene entities, colored
+/// per LOD like the other chunks when LOD colors are on.
+fn tag_scene_chunks(
 	mut commands: Commands,
-	mut meshes: ResMut<Assets<Mesh>>,
-	mut async_state: ResMut<AsyncRefinementState>,
-	world_roots: Query<&VoxelWorldRoot>,
 	settings: Res<UiSettings>,
 	lod_materials: Option<Res<LodMaterials>>,
-	terrain_material: Option<Res<TerrainMaterial>>,
-	mut chunk_map: Option<ResMut<ChunkEntityMap>>,
-	mut world_chunk_map: ResMut<WorldChunkMap>,
+	mut chunks: Query<
+		(Entity, &VoxelChunk, Option<&mut MeshMaterial3d<StandardMaterial>>),
+		(Added<VoxelChunk>, Without<SceneEntity>),
+	>,
 ) {
-	// Poll for events (non-blocking)
-	let Some(events) = async_state.initial_pipeline.poll_events() else {
-		return;
-	};
-
-	let Some(lod_materials) = lod_materials else {
-		warn!("LodMaterials not available");
-		return;
-	};
+	for (entity, chunk, material) in &mut chunks {
+		commands.entity(entity).insert(SceneEntity);
+		if let (Some(mut material), Some(lod_materials)) = (material, lod_materials.as_ref()) {
+			material.0 = lod_materials.get(chunk.node.lod, settings.current.lod_colors_enabled);
+		}
+	}
+}
 
-	let Ok(world_root) = world_roots.single() else {
-		warn!("VoxelWorldRoot not found");
+/// Enable continuous refinement once the initial leaves are all spawned.
+///
+/// Refinement waits for them: it would otherwise replace leaves whose
+/// chunks are not spawned yet, and those would then appear on top.
+fn finish_initial_mesh_gen(
+	mut async_state: ResMut<AsyncRefinementState>,
+	standard_tasks: Option<Res<VoxelMeshingTasks>>,
+	triplanar_tasks: Option<Res<VoxelMeshingTasks<TriplanarMaterial>>>,
+) {
+	if !async_state.initial_pending {
 		return;
-	};
-
-	let config = world_root.config().clone();
-	let use_lod_colors = settings.current.lod_colors_enabled;
-	let use_triplanar = settings.current.use_triplanar && terrain_material.is_some();
-
-	// Process events - for initial gen, spawn immediately (no queue)
-	for event in events {
-		match event {
-			PipelineEvent::NodesExpired { .. } => {
-				// Initial gen has no expired nodes
-			}
-			PipelineEvent::ChunksReady {
-				world_id,
-				chunks: ready_chunks,
-			} => {
-				info!(
-					"[InitialGen] ChunksReady: {} chunks for world {:?} (triplanar: {})",
-					ready_chunks.len(),
-					world_id,
-					use_triplanar
-				);
-
-				let mut local_chunk_map = ChunkEntityMap::default();
-				let chunk_map_ref = if let Some(ref mut map) = chunk_map {
-					&mut **map
-				} else {
-					&mut local_chunk_map
-				};
-
-				for ready in ready_chunks {
-					if !world_root.world.leaves.contains(&ready.node) {
-						continue;
-					}
-
-					if use_triplanar {
-						spawn_triplanar_chunk_entity_with_marker(
-							&mut commands,
-							&mut meshes,
-							terrain_material.as_ref().unwrap().handle.clone(),
-							chunk_map_ref,
-							&mut world_chunk_map,
-							world_id,
-							ready.node,
-							&ready.output,
-							&config,
-						);
-					} else {
-						spawn_chunk_entity_with_marker(
-							&mut commands,
-							&mut meshes,
-							lod_materials.get(ready.node.lod, use_lod_colors),
-							chunk_map_ref,
-							&mut world_chunk_map,
-							world_id,
-							ready.node,
-							&ready.output,
-							&config,
-						);
-					}
-				}
-			}
-		}
+	}
+	let idle = standard_tasks.is_none_or(|tasks| tasks.is_idle())
+		&& triplanar_tasks.is_none_or(|tasks| tasks.is_idle());
+	if idle {
+		info!("[InitialGen] Initial leaves spawned");
+		async_state.initial_pending = false;
+		async_state.continuous = true;
 	}
 }
 
@@ -526,21 +574,27 @@ fn spawn_chunk_entity_with_marker(
   material: Handle<StandardMaterial>,
   chunk_map: &mut ChunkEntityMap,
   world_chunk_map: &mut WorldChunkMap,
+  pool: Option<&mut EntityPool>,
   world_id: voxel_plugin::world::WorldId,
   node: OctreeNode,
   output: &voxel_plugin::MeshOutput,
+  timing_us: u64,
+  hint: PresentationHint,
   config: &OctreeConfig,
 ) {
   // Use the existing spawn function but add SceneEntity
-  spawn_chunk_entity(
+  spawn_chunk_entity_with_hint(
     commands,
     meshes,
     material,
     chunk_map,
     Some(world_chunk_map),
+    pool,
     world_id,
     node,
     output,
+    timing_us,
+    hint,
     config,
   );
 
@@ -557,21 +611,27 @@ fn spawn_triplanar_chunk_entity_with_marker(
   material: Handle<TriplanarMaterial>,
   chunk_map: &mut ChunkEntityMap,
   world_chunk_map: &mut WorldChunkMap,
+  pool: Option<&mut EntityPool>,
   world_id: voxel_plugin::world::WorldId,
   node: OctreeNode,
   output: &voxel_plugin::MeshOutput,
+  timing_us: u64,
+  hint: PresentationHint,
   config: &OctreeConfig,
 ) {
   // Use the generic spawn function with TriplanarMaterial
-  spawn_custom_material_chunk_entity(
+  spawn_custom_material_chunk_entity_with_hint(
     commands,
     meshes,
     material,
     chunk_map,
     Some(world_chunk_map),
+    pool,
     world_id,
     node,
     output,
+    timing_us,
+    hint,
     config,
   );
 
@@ -764,6 +824,7 @@ fn ui_controls(
 	mut contexts: EguiContexts,
 	mut settings: ResMut<UiSettings>,
 	mut async_state: ResMut<AsyncRefinementState>,
+	refinements: Query<&WorldRefinement>,
 	mut rebuild_events: MessageWriter<RebuildWorldEvent>,
 	mut refine_events: MessageWriter<RefineWorldEvent>,
 ) {
@@ -780,7 +841,10 @@ fn ui_controls(
 			ui.separator();
 
 			// Refinement status
-			let pending_groups = async_state.entity_queue.pending_count();
+			let pending_groups: usize = refinements
+				.iter()
+				.map(|refinement| refinement.entity_queue.pending_count())
+				.sum();
 			let has_pending = pending_groups > 0;
 
 			ui.horizontal(|ui| {
@@ -841,6 +905,11 @@ fn ui_controls(
 							SamplerSource::FastNoise2,
 							"FastNoise2",
 						);
+						ui.selectable_value(
+							&mut settings.current.sampler_source,
+							SamplerSource::Heightmap,
+							"Heightmap",
+						);
 					});
 			});
 
@@ -947,26 +1016,40 @@ fn rebuild_world(
 	mut commands: Commands,
 	mut rebuild_events: MessageReader<RebuildWorldEvent>,
 	mut meshes: ResMut<Assets<Mesh>>,
-	chunks: Query<Entity, With<VoxelChunk>>,
+	chunks: Query<(Entity, &VoxelChunk)>,
 	settings: Res<UiSettings>,
 	lod_materials: Option<Res<LodMaterials>>,
 	terrain_material: Option<Res<TerrainMaterial>>,
-	mut world_roots: Query<&mut VoxelWorldRoot>,
+	mut world_roots: Query<&mut VoxelWorldRoot, With<WorldRefinement>>,
 	mut chunk_map: Option<ResMut<ChunkEntityMap>>,
 	mut world_chunk_map: ResMut<WorldChunkMap>,
+	standard_tasks: Option<ResMut<VoxelMeshingTasks>>,
+	triplanar_tasks: Option<ResMut<VoxelMeshingTasks<TriplanarMaterial>>>,
 ) {
 	let Some(event) = rebuild_events.read().last() else {
 		return;
 	};
 
+	// Initial meshing of the old world must not land in the new one
+	if let Some(mut tasks) = standard_tasks {
+		tasks.clear();
+	}
+	if let Some(mut tasks) = triplanar_tasks {
+		tasks.clear();
+	}
+
 	info!(
 		"[NoiseLod] Rebuilding world with seed: {}, noise: {:?}, triplanar: {}",
 		event.seed, event.sampler_source, settings.current.use_triplanar
 	);
 
-	// Despawn all existing chunks
-	for entity in &chunks {
-		commands.entity(entity).despawn();
+	// Despawn the chunks of the scene's worlds
+	let world_ids: std::collections::HashSet<WorldId> =
+		world_roots.iter().map(|world_root| world_root.id()).collect();
+	for (entity, chunk) in &chunks {
+		if world_ids.contains(&chunk.world_id) {
+			commands.entity(entity).despawn();
+		}
 	}
 
 	// Clear chunk map
@@ -979,107 +1062,113 @@ fn rebuild_world(
 		return;
 	};
 
-	// Get the VoxelWorldRoot
-	let Ok(mut world_root) = world_roots.single_mut() else {
-		warn!("VoxelWorldRoot not found");
-		return;
-	};
-
-	let world_id = world_root.id();
+	// Rebuild every world refined by the scene
+	for mut world_root in &mut world_roots {
+		let world_id = world_root.id();
 
-	// Clear world chunks from WorldChunkMap
-	world_chunk_map.remove_world(world_id);
+		// Clear world chunks from WorldChunkMap
+		world_chunk_map.remove_world(world_id);
 
-	// Update the world's sampler with the new noise source
-	world_root.world.sampler = create_sampler(event.sampler_source, event.seed);
+		// Update the world's sampler with the new noise source
+		world_root.world.sampler = create_sampler(event.sampler_source, event.seed).into();
 
-	// Create new config from current UI settings
-	let world_half_extent = settings.current.world_half_extent;
-	let world_bounds = DAabb3::from_center_half_extents(
-		DVec3::ZERO,
-		DVec3::new(world_half_extent, world_half_extent, world_half_extent),
-	);
-
-	let config = OctreeConfig {
-		voxel_size: settings.current.voxel_size,
-		world_origin: DVec3::new(-world_half_extent, -world_half_extent, -world_half_extent),
-		min_lod: settings.current.min_lod,
-		max_lod: settings.current.max_lod,
-		lod_exponent: settings.current.lod_exponent,
-		world_bounds: Some(world_bounds),
-	};
-
-	// Update world config and recompute leaves
-	world_root.world.config = config.clone();
-	let initial_lod = config.suggest_initial_lod();
-	let initial_leaves: std::collections::HashSet<_> =
-		config.compute_initial_leaves(initial_lod).into_iter().collect();
-	world_root.world.leaves = initial_leaves.into();
-
-	let leaf_nodes: Vec<_> = world_root.world.leaves.iter().copied().collect();
-	let use_lod_colors = settings.current.lod_colors_enabled;
-	let use_triplanar = settings.current.use_triplanar && terrain_material.is_some();
-
-	// Create a fake TransitionGroup to mesh all initial leaves via centralized pipeline
-	let initial_transition = TransitionGroup {
-		transition_type: TransitionType::Subdivide,
-		group_key: OctreeNode::new(0, 0, 0, config.max_lod), // dummy key
-		nodes_to_add: leaf_nodes.iter().copied().collect(),
-		nodes_to_remove: SmallVec::new(),
-	};
-
-	// Use centralized process_transitions for parallel mesh generation
-	let ready_chunks = voxel_plugin::process_transitions(
-		world_id,
-		&[initial_transition],
-		&world_root.world.sampler,
-		world_root.world.leaves.as_set(),
-		&config,
-	);
+		// Create new config from current UI settings
+		let world_half_extent = settings.current.world_half_extent;
+		let world_bounds = DAabb3::from_center_half_extents(
+			DVec3::ZERO,
+			DVec3::new(world_half_extent, world_half_extent, world_half_extent),
+		);
 
-	let mesh_count = ready_chunks.len();
-	let empty_count = leaf_nodes.len() - mesh_count;
+		let config = OctreeConfig {
+			voxel_size: settings.current.voxel_size,
+			world_origin: DVec3::new(-world_half_extent, -world_half_extent, -world_half_extent),
+			min_lod: settings.current.min_lod,
+			max_lod: settings.current.max_lod,
+			lod_exponent: settings.current.lod_exponent,
+			lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
+			world_bounds: Some(world_bounds),
+		};
+
+		// Update world config and recompute leaves
+		world_root.world.config = config.clone();
+		let initial_lod = config.suggest_initial_lod();
+		let initial_leaves: std::collections::HashSet<_> =
+			config.compute_initial_leaves(initial_lod).into_iter().collect();
+		world_root.world.leaves = initial_leaves.into();
+
+		let leaf_nodes: Vec<_> = world_root.world.leaves.iter().copied().collect();
+		let use_lod_colors = settings.current.lod_colors_enabled;
+		let use_triplanar = settings.current.use_triplanar && terrain_material.is_some();
+
+		// Create a fake TransitionGroup to mesh all initial leaves via centralized pipeline
+		let initial_transition = TransitionGroup {
+			transition_type: TransitionType::Subdivide,
+			group_key: OctreeNode::new(0, 0, 0, config.max_lod), // dummy key
+			nodes_to_add: leaf_nodes.iter().copied().collect(),
+			nodes_to_remove: SmallVec::new(),
+			culled: false,
+		};
+
+		// Use centralized process_transitions for parallel mesh generation
+		let ready_chunks = voxel_plugin::process_transitions(
+			world_id,
+			&[initial_transition],
+			&world_root.world.sampler,
+			world_root.world.leaves.as_set(),
+			&config,
+			None,
+		);
 
-	let mut local_chunk_map = ChunkEntityMap::default();
-	let chunk_map_ref = if let Some(ref mut map) = chunk_map {
-		&mut **map
-	} else {
-		&mut local_chunk_map
-	};
+		let mesh_count = ready_chunks.len();
+		let empty_count = leaf_nodes.len() - mesh_count;
 
-	// Sequential: spawn entities from ready chunks
-	for chunk in ready_chunks {
-		if use_triplanar {
-			spawn_triplanar_chunk_entity_with_marker(
-				&mut commands,
-				&mut meshes,
-				terrain_material.as_ref().unwrap().handle.clone(),
-				chunk_map_ref,
-				&mut world_chunk_map,
-				world_id,
-				chunk.node,
-				&chunk.output,
-				&config,
-			);
+		let mut local_chunk_map = ChunkEntityMap::default();
+		let chunk_map_ref = if let Some(ref mut map) = chunk_map {
+			&mut **map
 		} else {
-			spawn_chunk_entity_with_marker(
-				&mut commands,
-				&mut meshes,
-				lod_materials.get(chunk.node.lod, use_lod_colors),
-				chunk_map_ref,
-				&mut world_chunk_map,
-				world_id,
-				chunk.node,
-				&chunk.output,
-				&config,
-			);
+			&mut local_chunk_map
+		};
+
+		// Sequential: spawn entities from ready chunks
+		for chunk in ready_chunks {
+			if use_triplanar {
+				spawn_triplanar_chunk_entity_with_marker(
+					&mut commands,
+					&mut meshes,
+					terrain_material.as_ref().unwrap().handle.clone(),
+					chunk_map_ref,
+					&mut world_chunk_map,
+					None,
+					world_id,
+					chunk.node,
+					&chunk.output,
+					chunk.timing_us,
+					chunk.hint.clone(),
+					&config,
+				);
+			} else {
+				spawn_chunk_entity_with_marker(
+					&mut commands,
+					&mut meshes,
+					lod_materials.get(chunk.node.lod, use_lod_colors),
+					chunk_map_ref,
+					&mut world_chunk_map,
+					None,
+					world_id,
+					chunk.node,
+					&chunk.output,
+					chunk.timing_us,
+					chunk.hint.clone(),
+					&config,
+				);
+			}
 		}
-	}
 
-	info!(
-		"[NoiseLod] Rebuilt world: {} meshes, {} empty chunks (triplanar: {})",
-		mesh_count, empty_count, use_triplanar
-	);
+		info!(
+			"[NoiseLod] Rebuilt world: {} meshes, {} empty chunks (triplanar: {})",
+			mesh_count, empty_count, use_triplanar
+		);
+	}
 }
 
 // =============================================================================
@@ -1092,292 +1181,289 @@ const CONTINUOUS_REFINEMENT_INTERVAL: u32 = 5;
 
 /// System to run refinement when triggered by message.
 ///
-/// Computes LOD transitions synchronously (fast) then dispatches mesh
-/// generation to a background rayon task via `refine_pipeline`.
+/// Computes each world's LOD transitions synchronously (fast) then
+/// dispatches mesh generation to a background rayon task via its
+/// `refine_pipeline`. Worlds whose previous refinement is still in flight
+/// are skipped.
 fn run_refinement(
 	mut refine_events: MessageReader<RefineWorldEvent>,
-	mut async_state: ResMut<AsyncRefinementState>,
-	viewers: Query<&GlobalTransform, With<VoxelViewer>>,
-	mut world_roots: Query<&mut VoxelWorldRoot>,
-	settings: Res<UiSettings>,
+	viewers: Query<(&GlobalTransform, Option<&ViewerWorld>), With<VoxelViewer>>,
+	mut world_roots: Query<(&mut VoxelWorldRoot, &mut WorldRefinement)>,
 	mut metrics: ResMut<VoxelMetricsResource>,
 ) {
 	if refine_events.read().next().is_none() {
 		return;
 	}
 
-	// Don't start new refinement if previous one is still in-flight
-	if async_state.refine_pipeline.is_busy() {
-		return;
-	}
-
-	let Ok(mut world_root) = world_roots.single_mut() else {
-		warn!("VoxelWorldRoot not found");
-		return;
-	};
-
-	// Get viewer position
-	let viewer_pos = viewers
-		.iter()
-		.next()
-		.map(|t| {
-			let p = t.translation();
-			DVec3::new(p.x as f64, p.y as f64, p.z as f64)
-		})
-		.unwrap_or(DVec3::new(0.0, 50.0, 0.0));
-
-	// Set aggressive collapse budget for responsive zoom-out
-	world_root.world.budget = RefinementBudget {
-		max_subdivisions: 32,
-		max_collapses: 128, // 4x more collapses - they're cheap (8 meshes -> 1)
-		..RefinementBudget::DEFAULT
-	};
-
-	// Run sync refinement - computes transitions and updates leaves
-	let refine_start = Instant::now();
-	let output = world_root.world.refine(viewer_pos);
-	let refine_us = refine_start.elapsed().as_micros() as u64;
+	for (mut world_root, mut refinement) in &mut world_roots {
+		// Don't start new refinement if previous one is still in-flight
+		if refinement.refine_pipeline.is_busy() {
+			continue;
+		}
 
-	if output.transition_groups.is_empty() {
-		return;
-	}
+		// Get viewer position (first viewer driving this world)
+		let viewer_pos = world_viewer_positions(&world_root, &viewers)
+			.first()
+			.copied()
+			.unwrap_or(DVec3::new(0.0, 50.0, 0.0));
+
+		// Set aggressive collapse budget for responsive zoom-out
+		world_root.world.budget = RefinementBudget {
+			max_subdivisions: 32,
+			max_collapses: 128, // 4x more collapses - they're cheap (8 meshes -> 1)
+			..RefinementBudget::DEFAULT
+		};
+
+		// Run sync refinement - computes transitions and updates leaves
+		let refine_start = Instant::now();
+		let output = world_root.world.refine(viewer_pos);
+		let refine_us = refine_start.elapsed().as_micros() as u64;
+
+		if output.transition_groups.is_empty() {
+			continue;
+		}
 
-	let world_id = world_root.id();
-	let config = world_root.config().clone();
-	let leaves = world_root.world.leaves.as_set().clone();
+		let world_id = world_root.id();
+		let config = world_root.config().clone();
+		let leaves = world_root.world.leaves.as_set().clone();
 
-	// Store transition groups for later (when mesh results arrive)
-	async_state.pending_transitions = output.transition_groups.clone();
+		// Store transition groups for later (when mesh results arrive)
+		refinement.pending_transitions = output.transition_groups.clone();
 
-	// Create a fresh sampler for the background task
-	let sampler = FastNoise2Terrain::new(settings.current.current_seed);
+		// Share the world's sampler with the background task and dispatch mesh
+		// generation to rayon thread pool (non-blocking)
+		let sampler = world_root.world.sampler.clone();
+		refinement
+			.refine_pipeline
+			.start(world_id, output.transition_groups, sampler, leaves, config);
 
-	// Dispatch mesh generation to rayon thread pool (non-blocking)
-	async_state.refine_pipeline.start(
-		world_id,
-		output.transition_groups,
-		sampler,
-		leaves,
-		config,
-	);
-
-	info!(
-		"[Refine] Dispatched async mesh gen (subdivs: {}, collapses: {})",
-		output.stats.subdivisions_performed,
-		output.stats.collapses_performed,
-	);
+		info!(
+			"[Refine] Dispatched async mesh gen (subdivs: {}, collapses: {})",
+			output.stats.subdivisions_performed,
+			output.stats.collapses_performed,
+		);
 
-	metrics.record_refinement_ops(
-		output.stats.subdivisions_performed as u32,
-		output.stats.collapses_performed as u32,
-	);
-	metrics.record_refine_timing(refine_us);
+		metrics.record_refinement_ops(
+			output.stats.subdivisions_performed as u32,
+			output.stats.collapses_performed as u32,
+		);
+		metrics.record_refine_timing(refine_us);
+	}
 }
 
 /// System to poll for refinement mesh generation results.
 ///
-/// When `refine_pipeline` completes, builds `CompletedTransition`s from the
+/// When a world's `refine_pipeline` completes, builds `CompletedTransition`s from the
 /// ready chunks and queues them to the entity queue for frame-budgeted application.
-fn poll_refinement(
-	mut async_state: ResMut<AsyncRefinementState>,
-	world_roots: Query<&VoxelWorldRoot>,
-) {
-	let Some(events) = async_state.refine_pipeline.poll_events() else {
-		return;
-	};
-
-	let Ok(world_root) = world_roots.single() else {
-		return;
-	};
-
-	let _ = world_root; // used only to confirm world exists
-
-	// Collect all ready chunks from pipeline events
-	let mut all_ready: Vec<ReadyChunk> = Vec::new();
-	for event in events {
-		if let PipelineEvent::ChunksReady { chunks, .. } = event {
-			all_ready.extend(chunks);
+fn poll_refinement(mut refinements: Query<&mut WorldRefinement>) {
+	for mut refinement in &mut refinements {
+		let Some(events) = refinement.refine_pipeline.poll_events() else {
+			continue;
+		};
+
+		// Collect all ready chunks from pipeline events
+		let mut all_ready: Vec<ReadyChunk> = Vec::new();
+		for event in events {
+			if let PipelineEvent::ChunksReady { chunks, .. } = event {
+				all_ready.extend(chunks);
+			}
 		}
-	}
 
-	// Build ready_by_node for O(1) lookup
-	let ready_by_node: HashMap<OctreeNode, ReadyChunk> = all_ready
-		.into_iter()
-		.map(|c| (c.node, c))
-		.collect();
-
-	// Build transitions for entity queue (preserves atomic grouping)
-	let transitions: Vec<_> = std::mem::take(&mut async_state.pending_transitions)
-		.into_iter()
-		.map(|group| {
-			let ready_for_group: Vec<_> = group
-				.nodes_to_add
-				.iter()
-				.filter_map(|node| ready_by_node.get(node))
-				.map(|c| ReadyChunk {
-					world_id: c.world_id,
-					node: c.node,
-					output: c.output.clone(),
-					hint: c.hint.clone(),
-					timing_us: c.timing_us,
-				})
-				.collect();
-
-			CompletedTransition {
-				group_key: group.group_key,
-				is_collapse: matches!(group.transition_type, TransitionType::Merge),
-				nodes_to_remove: group.nodes_to_remove.to_vec(),
-				nodes_to_add: group.nodes_to_add.to_vec(),
-				ready_chunks: ready_for_group,
-			}
-		})
-		.collect();
+		// Build ready_by_node for O(1) lookup
+		let ready_by_node: HashMap<OctreeNode, ReadyChunk> = all_ready
+			.into_iter()
+			.map(|c| (c.node, c))
+			.collect();
+
+		// Build transitions for entity queue (preserves atomic grouping)
+		let transitions: Vec<_> = std::mem::take(&mut refinement.pending_transitions)
+			.into_iter()
+			.map(|group| {
+				let ready_for_group: Vec<_> = group
+					.nodes_to_add
+					.iter()
+					.filter_map(|node| ready_by_node.get(node))
+					.map(|c| ReadyChunk {
+						world_id: c.world_id,
+						node: c.node,
+						output: c.output.clone(),
+						hint: c.hint.clone(),
+						timing_us: c.timing_us,
+					})
+					.collect();
+
+				CompletedTransition {
+					group_key: group.group_key,
+					is_collapse: matches!(group.transition_type, TransitionType::Merge),
+					nodes_to_remove: group.nodes_to_remove.to_vec(),
+					nodes_to_add: group.nodes_to_add.to_vec(),
+					ready_chunks: ready_for_group,
+				}
+			})
+			.collect();
 
-	let num_despawns: usize = transitions.iter().map(|t| t.nodes_to_remove.len()).sum();
-	let num_spawns: usize = transitions.iter().map(|t| t.ready_chunks.len()).sum();
+		let num_despawns: usize = transitions.iter().map(|t| t.nodes_to_remove.len()).sum();
+		let num_spawns: usize = transitions.iter().map(|t| t.ready_chunks.len()).sum();
 
-	async_state.entity_queue.queue_transitions(transitions);
+		refinement.entity_queue.queue_transitions(transitions);
 
-	info!(
-		"[Refine] Mesh gen complete: {} despawns, {} spawns queued",
-		num_despawns,
-		num_spawns,
-	);
+		info!(
+			"[Refine] Mesh gen complete: {} despawns, {} spawns queued",
+			num_despawns,
+			num_spawns,
+		);
+	}
 }
 
 /// System to process queued transition groups atomically.
 ///
 /// Each transition group is applied completely (despawn + spawn) in the
-/// same frame to prevent visual pops.
+/// same frame to prevent visual pops. Every world's queue gets its own
+/// frame budget.
 fn process_entity_queue(
 	mut commands: Commands,
 	mut meshes: ResMut<Assets<Mesh>>,
-	mut async_state: ResMut<AsyncRefinementState>,
-	world_roots: Query<&VoxelWorldRoot>,
-	chunks: Query<(Entity, &VoxelChunk)>,
+	mut world_roots: Query<(&VoxelWorldRoot, &mut WorldRefinement)>,
+	chunks: Query<(Entity, &VoxelChunk, Option<&ChunkFade>)>,
 	settings: Res<UiSettings>,
+	fade_config: Res<ChunkFadeConfig>,
 	lod_materials: Option<Res<LodMaterials>>,
 	terrain_material: Option<Res<TerrainMaterial>>,
 	mut chunk_map: Option<ResMut<ChunkEntityMap>>,
 	mut world_chunk_map: ResMut<WorldChunkMap>,
 	mut metrics: ResMut<VoxelMetricsResource>,
 ) {
-	// Early exit if nothing queued
-	if !async_state.entity_queue.has_pending() {
-		return;
-	}
-
 	let Some(lod_materials) = lod_materials else {
 		return;
 	};
 
-	let Ok(world_root) = world_roots.single() else {
-		return;
-	};
-
-	let config = world_root.config().clone();
-	let world_id = world_root.id();
 	let use_lod_colors = settings.current.lod_colors_enabled;
 	let use_triplanar = settings.current.use_triplanar && terrain_material.is_some();
 
-	// Build node -> entity map for despawn lookups
-	let node_to_entity: HashMap<OctreeNode, Entity> = chunks
-		.iter()
-		.filter(|(_, chunk)| chunk.world_id == world_id)
-		.map(|(entity, chunk)| (chunk.node, entity))
-		.collect();
-
-	let mut local_chunk_map = ChunkEntityMap::default();
-	let chunk_map_ref = if let Some(ref mut map) = chunk_map {
-		&mut **map
-	} else {
-		&mut local_chunk_map
-	};
-
-	// Process transition groups atomically
-	let stats = async_state.entity_queue.process_frame(|transition| {
-		// Phase 1: Despawn old nodes (parent for subdivide, children for merge)
-		for node in &transition.nodes_to_remove {
-			if let Some(&entity) = node_to_entity.get(node) {
-				commands.entity(entity).despawn();
-				chunk_map_ref.map.remove(node);
-				world_chunk_map.remove(world_id, node);
-				// Record despawn metrics (approximate - we don't have vertex/index count here)
-				// For accurate metrics, we'd need to store these in the chunk component
-				metrics.record_chunk_despawn(node.lod, 0, 0);
-			}
+	for (world_root, mut refinement) in &mut world_roots {
+		// Skip worlds with nothing queued
+		if !refinement.entity_queue.has_pending() {
+			continue;
 		}
 
-		// Phase 2: Spawn new chunks (same frame = no pop)
-		for ready in &transition.ready_chunks {
-			// Guard: only spawn if node is still a leaf
-			if !world_root.world.leaves.contains(&ready.node) {
-				continue;
+		let config = world_root.config().clone();
+		let world_id = world_root.id();
+
+		// Build node -> entity map for despawn lookups (chunks already fading out
+		// have been replaced and are despawned by the fade system)
+		let node_to_entity: HashMap<OctreeNode, Entity> = chunks
+			.iter()
+			.filter(|(_, chunk, _)| chunk.world_id == world_id)
+			.filter(|(_, _, fade)| !fade.is_some_and(|fade| fade.direction == FadeDirection::Out))
+			.map(|(entity, chunk, _)| (chunk.node, entity))
+			.collect();
+
+		let mut local_chunk_map = ChunkEntityMap::default();
+		let chunk_map_ref = if let Some(ref mut map) = chunk_map {
+			&mut **map
+		} else {
+			&mut local_chunk_map
+		};
+
+		// Process transition groups atomically, reusing pooled chunk entities
+		let stats = refinement.entity_queue.process_frame_pooled(|transition, pool| {
+			// Phase 1: Despawn old nodes (parent for subdivide, children for merge)
+			for node in &transition.nodes_to_remove {
+				if let Some(&entity) = node_to_entity.get(node) {
+					// Crossfade with the chunks replacing this node (the fade
+					// system despawns it afterwards)
+					if fade_config.enabled {
+						commands
+							.entity(entity)
+							.insert(ChunkFade::fade_out(fade_config.duration));
+					} else {
+						pool.release(&mut commands, entity);
+					}
+					chunk_map_ref.map.remove(node);
+					world_chunk_map.remove(world_id, node);
+					// Record despawn metrics (approximate - we don't have vertex/index count here)
+					// For accurate metrics, we'd need to store these in the chunk component
+					metrics.record_chunk_despawn(node.lod, 0, 0);
+				}
 			}
 
-			// Record spawn metrics
-			metrics.record_chunk_spawn(
-				ready.node.lod,
-				ready.output.vertices.len() as u32,
-				ready.output.indices.len() as u32,
-			);
+			// Phase 2: Spawn new chunks (same frame = no pop)
+			for ready in &transition.ready_chunks {
+				// Guard: only spawn if node is still a leaf
+				if !world_root.world.leaves.contains(&ready.node) {
+					continue;
+				}
 
-			if use_triplanar {
-				spawn_triplanar_chunk_entity_with_marker(
-					&mut commands,
-					&mut meshes,
-					terrain_material.as_ref().unwrap().handle.clone(),
-					chunk_map_ref,
-					&mut world_chunk_map,
-					world_id,
-					ready.node,
-					&ready.output,
-					&config,
-				);
-			} else {
-				spawn_chunk_entity_with_marker(
-					&mut commands,
-					&mut meshes,
-					lod_materials.get(ready.node.lod, use_lod_colors),
-					chunk_map_ref,
-					&mut world_chunk_map,
-					world_id,
-					ready.node,
-					&ready.output,
-					&config,
+				// Record spawn metrics
+				metrics.record_chunk_spawn(
+					ready.node.lod,
+					ready.output.vertices.len() as u32,
+					ready.output.indices.len() as u32,
 				);
+
+				if use_triplanar {
+					spawn_triplanar_chunk_entity_with_marker(
+						&mut commands,
+						&mut meshes,
+						terrain_material.as_ref().unwrap().handle.clone(),
+						chunk_map_ref,
+						&mut world_chunk_map,
+						Some(&mut *pool),
+						world_id,
+						ready.node,
+						&ready.output,
+						ready.timing_us,
+						ready.hint.clone(),
+						&config,
+					);
+				} else {
+					spawn_chunk_entity_with_marker(
+						&mut commands,
+						&mut meshes,
+						lod_materials.get(ready.node.lod, use_lod_colors),
+						chunk_map_ref,
+						&mut world_chunk_map,
+						Some(&mut *pool),
+						world_id,
+						ready.node,
+						&ready.output,
+						ready.timing_us,
+						ready.hint.clone(),
+						&config,
+					);
+				}
 			}
-		}
-	});
+		});
 
-	// Log if there's still work remaining
-	if stats.pending_groups > 0 {
-		info!(
-			"[EntityQueue] Applied {} groups ({} despawns, {} spawns) in {}us. Remaining: {} groups",
-			stats.groups_applied, stats.despawns, stats.spawns, stats.elapsed_us,
-			stats.pending_groups
-		);
+		// Log if there's still work remaining
+		if stats.pending_groups > 0 {
+			info!(
+				"[EntityQueue] Applied {} groups ({} despawns, {} spawns) in {}us. Remaining: {} groups",
+				stats.groups_applied, stats.despawns, stats.spawns, stats.elapsed_us,
+				stats.pending_groups
+			);
+		}
 	}
 }
 
 /// System for continuous automatic refinement based on viewer movement.
 fn continuous_refinement(
 	mut async_state: ResMut<AsyncRefinementState>,
+	refinements: Query<&WorldRefinement>,
 	mut refine_events: MessageWriter<RefineWorldEvent>,
 ) {
 	if !async_state.continuous {
 		return;
 	}
 
-	// Don't trigger new refinement while previous mesh gen is in-flight
-	if async_state.refine_pipeline.is_busy() {
-		return;
-	}
-
+	// Don't trigger new refinement while previous mesh gen is in-flight.
 	// Allow refinement even if entity queue has pending work
 	// This enables pipelining: process entities while computing next refinement
 	// Only block if queue is severely backed up (>32 groups)
-	if async_state.entity_queue.pending_count() > 32 {
+	let any_ready = refinements.iter().any(|refinement| {
+		!refinement.refine_pipeline.is_busy() && refinement.entity_queue.pending_count() <= 32
+	});
+	if !any_ready {
 		return;
 	}
 
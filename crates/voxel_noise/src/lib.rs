@@ -10,8 +10,11 @@
 //! │                     native.rs                               │
 //! │  ┌───────────────────────────────────────────────────────┐  │
 //! │  │ NoiseNode (Rust API)                                  │  │
-//! │  │   - from_encoded()                                    │  │
+//! │  │   - from_encoded() / try_from_encoded()               │  │
+//! │  │   - from_json()                                       │  │
+//! │  │   - describe()                                        │  │
 //! │  │   - gen_uniform_grid_3d()                             │  │
+//! │  │   - gen_uniform_grid_3d_tiled()                       │  │
 //! │  │   - gen_uniform_grid_2d()                             │  │
 //! │  └───────────────────────────────────────────────────────┘  │
 //! │  ┌───────────────────────────────────────────────────────┐  │
@@ -41,7 +44,7 @@
 //! The JS bridge (`js/voxel_noise_bridge.js`) wraps these exports.
 
 mod native;
-pub use native::NoiseNode;
+pub use native::{NoiseError, NoiseNode};
 
 // Re-export wasm_api for Emscripten builds
 #[cfg(all(target_arch = "wasm32", target_os = "emscripten"))]
@@ -55,7 +58,7 @@ pub mod presets {
 
 #[cfg(test)]
 mod tests {
-  use super::{presets, NoiseNode};
+  use super::{presets, NoiseError, NoiseNode};
 
   #[test]
   fn test_simple_terrain() {
@@ -152,6 +155,159 @@ mod tests {
     );
   }
 
+  /// Encoded strings report why they failed to decode.
+  #[test]
+  fn test_try_from_encoded_errors() {
+    assert!(NoiseNode::try_from_encoded(presets::SIMPLE_TERRAIN).is_ok());
+
+    for garbage in ["", "not base64!", "====", "QUJD\n"] {
+      assert_eq!(
+        NoiseNode::try_from_encoded(garbage).err(),
+        Some(NoiseError::InvalidBase64),
+        "{:?} should be rejected as invalid base64",
+        garbage
+      );
+    }
+
+    let truncated = &presets::SIMPLE_TERRAIN[..16];
+    assert!(matches!(
+      NoiseNode::try_from_encoded(truncated),
+      Err(NoiseError::DecodeFailed(_))
+    ));
+    assert!(NoiseNode::from_encoded(truncated).is_none());
+  }
+
+  /// JSON trees build a working node and describe themselves back.
+  #[test]
+  fn test_json_tree_describe() {
+    let json = r#"{
+      "node": "FractalFBm",
+      "Octaves": 4,
+      "Gain": 0.5,
+      "Source": {
+        "node": "DomainWarpGradient",
+        "WarpAmplitude": 0.6,
+        "Source": { "node": "Simplex" }
+      }
+    }"#;
+    let node = NoiseNode::from_json(json).expect("Failed to build node from JSON");
+
+    let mut output = vec![0.0f32; 16 * 16 * 16];
+    node.gen_uniform_grid_3d(&mut output, 0.0, 0.0, 0.0, 16, 16, 16, 0.05, 0.05, 0.05, 1337);
+    assert!(output.iter().any(|&v| v != 0.0), "All values are zero");
+
+    let description = node.describe().expect("JSON nodes describe themselves");
+    assert!(description.contains("FractalFBm"));
+    assert!(description.contains("DomainWarpGradient"));
+    let reparsed = NoiseNode::from_json(&description);
+    assert!(reparsed.is_some(), "Description must round-trip");
+
+    assert!(NoiseNode::from_json(r#"{ "node": "NotANode" }"#).is_none());
+  }
+
+  /// Whole numbers also set float members.
+  #[test]
+  fn test_json_whole_number_sets_float_member() {
+    let json = r#"{ "node": "FractalFBm", "Gain": 1, "Source": { "node": "Simplex" } }"#;
+    assert!(NoiseNode::from_json(json).is_some());
+  }
+
+  /// Presets decode to a node tree FastNoise2 can describe.
+  #[test]
+  fn test_encoded_tree_describe() {
+    let node = NoiseNode::from_encoded(presets::SIMPLE_TERRAIN).unwrap();
+    let description = node.describe().expect("Encoded nodes describe their root");
+
+    assert!(!description.is_empty());
+    assert!(
+      description.contains("FBm") || description.contains("DomainWarp"),
+      "SIMPLE_TERRAIN should decode to FBm/DomainWarp nodes: {}",
+      description
+    );
+  }
+
+  /// Tiled grids wrap: the sample one period along X repeats x = 0.
+  #[test]
+  fn test_tiled_grid_wraps_at_period() {
+    let node =
+      NoiseNode::from_encoded(presets::SIMPLE_TERRAIN).expect("Failed to create noise node");
+
+    const SIZE: usize = 17;
+    const PERIOD: usize = 16;
+    let mut output = vec![0.0f32; SIZE * SIZE * SIZE];
+    node.gen_uniform_grid_3d_tiled(
+      &mut output,
+      0.0,
+      0.0,
+      0.0,
+      SIZE as i32,
+      SIZE as i32,
+      SIZE as i32,
+      1.0,
+      1.0,
+      1.0,
+      [PERIOD as f32, PERIOD as f32, 0.0],
+      1337,
+    );
+
+    assert!(output.iter().any(|&v| v != 0.0), "All values are zero");
+    let mut max_diff: f32 = 0.0;
+    for z in 0..SIZE {
+      for y in 0..SIZE {
+        let row = z * SIZE * SIZE + y * SIZE;
+        max_diff = max_diff.max((output[row] - output[row + PERIOD]).abs());
+      }
+      for x in 0..SIZE {
+        let column = z * SIZE * SIZE + x;
+        max_diff = max_diff.max((output[column] - output[column + PERIOD * SIZE]).abs());
+      }
+    }
+    assert!(
+      max_diff < 1e-4,
+      "Tiled grid does not wrap (max diff: {})",
+      max_diff
+    );
+  }
+
+  /// Tiled grids are continuous across the seam, not just equal at it:
+  /// samples just either side of x = period stay close.
+  #[test]
+  fn test_tiled_grid_continuous_across_seam() {
+    let node =
+      NoiseNode::from_encoded(presets::SIMPLE_TERRAIN).expect("Failed to create noise node");
+
+    const PERIOD: f32 = 16.0;
+    const EPSILON: f32 = 1e-3;
+    const SIZE: usize = 8;
+    // Two X samples straddling the seam, over a spread of Y/Z rows
+    let mut output = vec![0.0f32; 2 * SIZE * SIZE];
+    node.gen_uniform_grid_3d_tiled(
+      &mut output,
+      PERIOD - EPSILON,
+      0.5,
+      0.5,
+      2,
+      SIZE as i32,
+      SIZE as i32,
+      2.0 * EPSILON,
+      1.3,
+      1.3,
+      [PERIOD, PERIOD, 0.0],
+      1337,
+    );
+
+    assert!(output.iter().any(|&v| v != 0.0), "All values are zero");
+    let max_diff = output
+      .chunks_exact(2)
+      .map(|pair| (pair[0] - pair[1]).abs())
+      .fold(0.0, f32::max);
+    assert!(
+      max_diff < 1e-2,
+      "Tiled grid jumps at the seam (max diff: {})",
+      max_diff
+    );
+  }
+
   /// Test edge coherency at sub-voxel sizes (< 1.0)
   #[test]
   fn test_edge_coherency_small_voxel_size() {
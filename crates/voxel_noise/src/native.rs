@@ -6,7 +6,53 @@
 //!
 //! Both pathways use the same underlying NoiseNode implementation.
 
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::os::raw::c_char;
+
 use fastnoise2::SafeNode;
+use fastnoise2_sys as sys;
+use serde_json::{json, Map, Value};
+
+// ============================================================================
+// NoiseError
+// ============================================================================
+
+/// Why an encoded node tree could not be turned into a [`NoiseNode`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NoiseError {
+  /// The string is empty or not in FastNoise2's base64 encoding.
+  InvalidBase64,
+  /// The string is well-formed but FastNoise2 could not build a node tree
+  /// from it (truncated data, unknown node type, version mismatch). Holds
+  /// FastNoise2's message.
+  DecodeFailed(String),
+}
+
+impl fmt::Display for NoiseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      NoiseError::InvalidBase64 => write!(f, "encoded node tree is not valid base64"),
+      NoiseError::DecodeFailed(message) => {
+        write!(f, "failed to decode node tree: {}", message)
+      }
+    }
+  }
+}
+
+impl std::error::Error for NoiseError {}
+
+/// Check `encoded` against FastNoise2's encoding: the base64 alphabet plus
+/// `@` (used for runs of repeated characters), with `=` only as trailing
+/// padding.
+fn is_encoded_node_tree(encoded: &str) -> bool {
+  let data = encoded.trim_end_matches('=');
+  !data.is_empty()
+    && encoded.len() - data.len() <= 2
+    && data
+      .bytes()
+      .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'@'))
+}
 
 // ============================================================================
 // NoiseNode - Rust API (all targets)
@@ -19,21 +65,86 @@ use fastnoise2::SafeNode;
 /// use them in code.
 pub struct NoiseNode {
   inner: SafeNode,
+  /// JSON description the node was built from (None for encoded trees)
+  tree: Option<Value>,
+  /// Encoded string the node was built from (None for JSON trees)
+  encoded: Option<Box<str>>,
 }
 
 impl NoiseNode {
   /// Create a noise node from an encoded node tree string.
   ///
-  /// Returns `None` if the encoded string is invalid.
+  /// Returns `None` if the encoded string is invalid; use
+  /// [`try_from_encoded`](Self::try_from_encoded) to find out why.
   ///
   /// # Example
   /// ```ignore
   /// let node = NoiseNode::from_encoded("DQAFAAAAAAAAQAgAAAAAAD8AAAAAAA==").unwrap();
   /// ```
   pub fn from_encoded(encoded: &str) -> Option<Self> {
+    Self::try_from_encoded(encoded).ok()
+  }
+
+  /// Create a noise node from an encoded node tree string, reporting why
+  /// it failed.
+  pub fn try_from_encoded(encoded: &str) -> Result<Self, NoiseError> {
+    if !is_encoded_node_tree(encoded) {
+      return Err(NoiseError::InvalidBase64);
+    }
+
     SafeNode::from_encoded_node_tree(encoded)
-      .ok()
-      .map(|inner| Self { inner })
+      .map(|inner| Self {
+        inner,
+        tree: None,
+        encoded: Some(encoded.into()),
+      })
+      .map_err(|e| NoiseError::DecodeFailed(e.to_string()))
+  }
+
+  /// Create a noise node from a JSON description of the node tree.
+  ///
+  /// Each node is an object naming its FastNoise2 metadata type in `"node"`;
+  /// every other key sets the member of that name. Numbers with a decimal
+  /// point set float members and integers set int members, strings select
+  /// enum values, and objects are child nodes (node lookups or hybrids).
+  ///
+  /// Returns `None` if the JSON is malformed or names an unknown node or
+  /// member.
+  ///
+  /// # Example
+  /// ```ignore
+  /// let node = NoiseNode::from_json(r#"{
+  ///   "node": "FractalFBm", "Octaves": 4, "Gain": 0.5,
+  ///   "Source": { "node": "Simplex" }
+  /// }"#).unwrap();
+  /// ```
+  pub fn from_json(json: &str) -> Option<Self> {
+    let tree: Value = serde_json::from_str(json).ok()?;
+    let inner = build_node(&tree)?;
+    Some(Self {
+      inner,
+      tree: Some(tree),
+      encoded: None,
+    })
+  }
+
+  /// Human-readable JSON description of the node tree.
+  ///
+  /// Nodes built with `from_json` describe their whole tree (node types and
+  /// parameters) in the format [`from_json`](Self::from_json) reads.
+  ///
+  /// Encoded trees describe what FastNoise2's metadata API reports for the
+  /// decoded root node: its type and member schema, e.g.
+  /// `{ "node": "DomainWarpGradient", "variables": { ... }, "node_lookups":
+  /// ["Source"], "hybrids": [...] }`. The C API has no getters for member
+  /// values or linked child nodes, so those are not included.
+  pub fn describe(&self) -> Option<String> {
+    let tree = match (&self.tree, &self.encoded) {
+      (Some(tree), _) => tree.clone(),
+      (None, Some(encoded)) => describe_encoded(encoded)?,
+      (None, None) => return None,
+    };
+    serde_json::to_string_pretty(&tree).ok()
   }
 
   /// Generate noise values on a uniform 3D grid.
@@ -63,6 +174,98 @@ impl NoiseNode {
     );
   }
 
+  /// Generate noise on a uniform 3D grid that wraps with `period`.
+  ///
+  /// Same layout and arguments as
+  /// [`gen_uniform_grid_3d`](Self::gen_uniform_grid_3d). Each axis with a
+  /// positive `period` component tiles seamlessly: the value at `x` equals
+  /// the value at `x + period[0]`. Axes with a period of 0 (or less) don't
+  /// wrap.
+  ///
+  /// Positions are wrapped into `[0, period)` and the noise is blended with
+  /// copies shifted by one period, weighted by the position within it. The
+  /// whole grid is evaluated once per corner of that blend, so the cost is
+  /// 2^(tiled axes) plain evaluations: ~2x for one axis, ~4x for two (the
+  /// usual torus), ~8x for three. The blend also lowers contrast near the
+  /// middle of each period.
+  pub fn gen_uniform_grid_3d_tiled(
+    &self,
+    output: &mut [f32],
+    x_off: f32,
+    y_off: f32,
+    z_off: f32,
+    x_cnt: i32,
+    y_cnt: i32,
+    z_cnt: i32,
+    x_step: f32,
+    y_step: f32,
+    z_step: f32,
+    period: [f32; 3],
+    seed: i32,
+  ) {
+    let counts = [x_cnt, y_cnt, z_cnt].map(|c| c.max(0) as usize);
+    let count = counts[0] * counts[1] * counts[2];
+    let offsets = [x_off, y_off, z_off];
+    let steps = [x_step, y_step, z_step];
+    let tiled = period.map(|p| p > 0.0);
+
+    // Wrapped position and blend weight of every sample, per axis
+    let mut wrapped: [Vec<f32>; 3] = std::array::from_fn(|_| vec![0.0; count]);
+    let mut weights: [Vec<f32>; 3] = std::array::from_fn(|_| vec![0.0; count]);
+    for i in 0..count {
+      // X-fastest, like the uniform grid
+      let row = i / counts[0];
+      let cell = [i % counts[0], row % counts[1], row / counts[1]];
+      for axis in 0..3 {
+        let position = offsets[axis] + cell[axis] as f32 * steps[axis];
+        if tiled[axis] {
+          let u = position.rem_euclid(period[axis]);
+          wrapped[axis][i] = u;
+          weights[axis][i] = u / period[axis];
+        } else {
+          wrapped[axis][i] = position;
+        }
+      }
+    }
+
+    output[..count].fill(0.0);
+    let mut shifted: [Vec<f32>; 3] = std::array::from_fn(|_| vec![0.0; count]);
+    let mut corner = vec![0.0f32; count];
+
+    for mask in 0..8usize {
+      // Only shift axes that tile
+      if (0..3).any(|axis| mask & (1 << axis) != 0 && !tiled[axis]) {
+        continue;
+      }
+
+      for axis in 0..3 {
+        let shift = period[axis] * ((mask >> axis) & 1) as f32;
+        for (s, &w) in shifted[axis].iter_mut().zip(&wrapped[axis]) {
+          *s = w - shift;
+        }
+      }
+      self.inner.gen_position_array_3d(
+        &mut corner,
+        &shifted[0],
+        &shifted[1],
+        &shifted[2],
+        0.0,
+        0.0,
+        0.0,
+        seed,
+      );
+
+      for (i, value) in corner.iter().enumerate() {
+        let mut weight = 1.0;
+        for axis in (0..3).filter(|&axis| tiled[axis]) {
+          let t = weights[axis][i];
+          weight *= if mask & (1 << axis) != 0 { t } else { 1.0 - t };
+        }
+        output[i] += weight * value;
+      }
+    }
+  }
+
   /// Generate noise values on a uniform 2D grid.
   ///
   /// # Arguments
@@ -92,6 +295,88 @@ impl NoiseNode {
 unsafe impl Send for NoiseNode {}
 unsafe impl Sync for NoiseNode {}
 
+/// Metadata of the root node of an encoded tree, as reported by FastNoise2.
+fn describe_encoded(encoded: &str) -> Option<Value> {
+  let encoded = CString::new(encoded).ok()?;
+
+  // SAFETY: `encoded` is NUL-terminated and outlives the call. The node is
+  // only read for its metadata ID and released right after.
+  let id = unsafe {
+    let node = sys::fnNewFromEncodedNodeTree(encoded.as_ptr(), 0);
+    if node.is_null() {
+      return None;
+    }
+    let id = sys::fnGetMetadataID(node);
+    sys::fnDeleteNodeRef(node);
+    id
+  };
+
+  // SAFETY: `id` comes from FastNoise2 and member indices stay below the
+  // counts it reports for that ID.
+  unsafe {
+    let mut variables = Map::new();
+    for i in 0..sys::fnGetMetadataVariableCount(id) {
+      let kind = match sys::fnGetMetadataVariableType(id, i) {
+        0 => "float",
+        1 => "int",
+        _ => "enum",
+      };
+      variables.insert(c_name(sys::fnGetMetadataVariableName(id, i))?, kind.into());
+    }
+    let node_lookups = (0..sys::fnGetMetadataNodeLookupCount(id))
+      .map(|i| c_name(sys::fnGetMetadataNodeLookupName(id, i)))
+      .collect::<Option<Vec<_>>>()?;
+    let hybrids = (0..sys::fnGetMetadataHybridCount(id))
+      .map(|i| c_name(sys::fnGetMetadataHybridName(id, i)))
+      .collect::<Option<Vec<_>>>()?;
+
+    Some(json!({
+      "node": c_name(sys::fnGetMetadataName(id))?,
+      "variables": variables,
+      "node_lookups": node_lookups,
+      "hybrids": hybrids,
+    }))
+  }
+}
+
+/// Copy a metadata name returned by FastNoise2.
+///
+/// # Safety
+/// `name` must be null or point to a NUL-terminated string.
+unsafe fn c_name(name: *const c_char) -> Option<String> {
+  if name.is_null() {
+    return None;
+  }
+  Some(CStr::from_ptr(name).to_string_lossy().into_owned())
+}
+
+/// Build a FastNoise2 node (and its children) from a JSON description.
+fn build_node(tree: &Value) -> Option<SafeNode> {
+  let members = tree.as_object()?;
+  let mut node = SafeNode::from_name(members.get("node")?.as_str()?).ok()?;
+
+  for (member, value) in members.iter().filter(|(member, _)| *member != "node") {
+    match value {
+      // Whole numbers are ambiguous ("Gain": 1); fall back to a float member
+      Value::Number(n) if n.is_i64() => {
+        let int = n.as_i64()?;
+        if node.set(member, int as i32).is_err() {
+          node.set(member, int as f32).ok()?
+        }
+      }
+      Value::Number(n) => node.set(member, n.as_f64()? as f32).ok()?,
+      Value::String(name) => node.set(member, name.as_str()).ok()?,
+      Value::Object(_) => {
+        let child = build_node(value)?;
+        node.set(member, &child).ok()?
+      }
+      _ => return None,
+    }
+  }
+
+  Some(node)
+}
+
 // ============================================================================
 // WASM C-API Exports (wasm32-emscripten only)
 // ============================================================================
@@ -9,6 +9,9 @@
 //! - **worst_case**: High-frequency noise (surfaces everywhere)
 //! - **realistic**: Terrain with caves/islands (mix of homogeneous/surface)
 //! - **controlled**: Sphere (predictable surface ratio)
+//!
+//! `pipeline/volume_pool` compares allocating volume buffers per node with
+//! reusing them through `VolumePool`.
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use voxel_plugin::{
@@ -16,12 +19,12 @@ use voxel_plugin::{
   octree::{OctreeConfig, OctreeNode},
   pipeline::{
     meshing::mesh_batch,
-    presample::{presample_batch, presample_node},
+    presample::{presample_batch, presample_node, presample_node_pooled, VolumePool},
     types::{MeshInput, VolumeSampler, WorkSource},
   },
   sdf_conversion,
   surface_nets::generate as mesh_generate,
-  MaterialId, MeshConfig, SdfSample,
+  MaterialId, MeshConfig, NormalMode, SdfSample,
 };
 
 // =============================================================================
@@ -52,6 +55,12 @@ impl NoiseSampler {
   pub fn medium_frequency() -> Self {
     Self::new(0.1, 12345)
   }
+
+  /// SDF at world position `p`: hashed value noise scaled to [-10, 10].
+  fn sdf(&self, p: [f64; 3]) -> f64 {
+    let [fx, fy, fz] = p.map(|v| v * self.frequency);
+    hash_noise_3d(fx, fy, fz, self.seed) * 10.0
+  }
 }
 
 impl VolumeSampler for NoiseSampler {
@@ -70,21 +79,17 @@ impl VolumeSampler for NoiseSampler {
           let wy = (grid_offset[1] + y as i64) as f64 * voxel_size;
           let wz = (grid_offset[2] + z as i64) as f64 * voxel_size;
 
-          let fx = wx * self.frequency;
-          let fy = wy * self.frequency;
-          let fz = wz * self.frequency;
-
-          // Simple 3D value noise using hash
-          let value = hash_noise_3d(fx, fy, fz, self.seed);
-
-          // Scale to SDF range then quantize
-          let sdf = value * 10.0;
+          let sdf = self.sdf([wx, wy, wz]);
           volume[idx] = sdf_conversion::to_storage(sdf as f32, voxel_size as f32);
           materials[idx] = 0;
         }
       }
     }
   }
+
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    sdf_conversion::to_storage(self.sdf(position) as f32, voxel_size as f32)
+  }
 }
 
 /// Terrain sampler with Y-bias (realistic scenario).
@@ -125,6 +130,28 @@ impl TerrainSampler {
   pub fn sparse() -> Self {
     Self::new(-16.0, 2.0, 0.05, 42)
   }
+
+  /// SDF at world position `[x, y, z]`: noisy ground with caves carved out.
+  fn sdf(&self, [x, y, z]: [f64; 3]) -> f64 {
+    // Base terrain: distance from surface plane
+    let surface_noise = hash_noise_3d(x * 0.05, 0.0, z * 0.05, self.seed) * self.noise_amplitude;
+    let terrain_sdf = y - (self.surface_y + surface_noise);
+
+    // Cave carving: negative values carve into terrain
+    let cave_noise = hash_noise_3d(
+      x * self.cave_frequency,
+      y * self.cave_frequency,
+      z * self.cave_frequency,
+      self.seed.wrapping_add(1000),
+    );
+    let cave_sdf = if cave_noise > 0.3 {
+      (cave_noise - 0.3) * 20.0 // Carve caves where noise > 0.3
+    } else {
+      0.0
+    };
+
+    terrain_sdf.max(-cave_sdf)
+  }
 }
 
 impl VolumeSampler for TerrainSampler {
@@ -143,25 +170,7 @@ impl VolumeSampler for TerrainSampler {
           let y = (grid_offset[1] + yi as i64) as f64 * voxel_size;
           let z = (grid_offset[2] + zi as i64) as f64 * voxel_size;
 
-          // Base terrain: distance from surface plane
-          let surface_noise =
-            hash_noise_3d(x * 0.05, 0.0, z * 0.05, self.seed) * self.noise_amplitude;
-          let terrain_sdf = y - (self.surface_y + surface_noise);
-
-          // Cave carving: negative values carve into terrain
-          let cave_noise = hash_noise_3d(
-            x * self.cave_frequency,
-            y * self.cave_frequency,
-            z * self.cave_frequency,
-            self.seed.wrapping_add(1000),
-          );
-          let cave_sdf = if cave_noise > 0.3 {
-            (cave_noise - 0.3) * 20.0 // Carve caves where noise > 0.3
-          } else {
-            0.0
-          };
-
-          let final_sdf = terrain_sdf.max(-cave_sdf);
+          let final_sdf = self.sdf([x, y, z]);
           volume[idx] = sdf_conversion::to_storage(final_sdf as f32, voxel_size as f32);
 
           // Simple material based on depth
@@ -177,6 +186,10 @@ impl VolumeSampler for TerrainSampler {
       }
     }
   }
+
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    sdf_conversion::to_storage(self.sdf(position) as f32, voxel_size as f32)
+  }
 }
 
 /// Sphere sampler for controlled benchmarks (predictable surface ratio).
@@ -205,6 +218,14 @@ impl SphereSampler {
   pub fn large() -> Self {
     Self::new([16.0, 16.0, 16.0], 14.0)
   }
+
+  /// SDF at world position `p`: distance to the sphere's surface.
+  fn sdf(&self, p: [f64; 3]) -> f64 {
+    let dx = p[0] - self.center[0];
+    let dy = p[1] - self.center[1];
+    let dz = p[2] - self.center[2];
+    (dx * dx + dy * dy + dz * dz).sqrt() - self.radius
+  }
 }
 
 impl VolumeSampler for SphereSampler {
@@ -223,16 +244,17 @@ impl VolumeSampler for SphereSampler {
           let wy = (grid_offset[1] + yi as i64) as f64 * voxel_size;
           let wz = (grid_offset[2] + zi as i64) as f64 * voxel_size;
 
-          let dx = wx - self.center[0];
-          let dy = wy - self.center[1];
-          let dz = wz - self.center[2];
-          let dist = (dx * dx + dy * dy + dz * dz).sqrt() - self.radius;
+          let dist = self.sdf([wx, wy, wz]);
           volume[idx] = sdf_conversion::to_storage(dist as f32, voxel_size as f32);
           materials[idx] = 0;
         }
       }
     }
   }
+
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    sdf_conversion::to_storage(self.sdf(position) as f32, voxel_size as f32)
+  }
 }
 
 /// Constant sampler for homogeneous baseline.
@@ -262,6 +284,10 @@ impl VolumeSampler for ConstantSampler {
     volume.fill(self.value);
     materials.fill(0);
   }
+
+  fn sample_point(&self, _position: [f64; 3], _voxel_size: f64) -> SdfSample {
+    self.value
+  }
 }
 
 // =============================================================================
@@ -561,6 +587,48 @@ fn bench_meshing_isolated(c: &mut Criterion) {
   group.finish();
 }
 
+/// Compare serial vs parallel normal computation on the worst-case volume.
+///
+/// Normals can't be timed on their own, so each pair meshes the same volume
+/// and only differs in `parallel_normals`.
+fn bench_normals_isolated(c: &mut Criterion) {
+  let mut group = c.benchmark_group("isolated/normals");
+  let config = test_config();
+  let node = test_node();
+
+  let noise_vol = sample_full_volume(&NoiseSampler::worst_case(), &node, &config);
+
+  let modes = [
+    ("gradient", NormalMode::Gradient),
+    ("interpolated", NormalMode::InterpolatedGradient),
+    ("geometry", NormalMode::Geometry),
+  ];
+
+  for (name, mode) in modes {
+    for (label, parallel) in [("serial", false), ("parallel", true)] {
+      let mesh_cfg = mesh_config()
+        .with_normal_mode(mode)
+        .with_parallel_normals(parallel);
+
+      group.bench_with_input(
+        BenchmarkId::new(label, format!("noise_worst/{}", name)),
+        &mesh_cfg,
+        |b, cfg| {
+          b.iter(|| {
+            mesh_generate(
+              black_box(&noise_vol.0),
+              black_box(&noise_vol.1),
+              black_box(cfg),
+            )
+          })
+        },
+      );
+    }
+  }
+
+  group.finish();
+}
+
 /// Benchmark just the sampling operation (no presample or mesh).
 fn bench_sampling_isolated(c: &mut Criterion) {
   let mut group = c.benchmark_group("isolated/sampling");
@@ -931,10 +999,65 @@ fn bench_homogeneous_ratio(c: &mut Criterion) {
   group.finish();
 }
 
+// =============================================================================
+// Volume Pool Benchmarks
+// =============================================================================
+
+/// Allocation-per-node vs pooled volume buffers on a 512-node batch.
+///
+/// Each node is presampled, meshed if it has a surface, and its buffer
+/// dropped (allocating) or returned to the pool (pooled).
+fn bench_volume_pool(c: &mut Criterion) {
+  let mut group = c.benchmark_group("pipeline/volume_pool");
+  let octree_config = test_config();
+  let mesh_cfg = mesh_config();
+  let terrain = TerrainSampler::standard();
+
+  let nodes: Vec<OctreeNode> = (0..8)
+    .flat_map(|x| (0..8).flat_map(move |y| (0..8).map(move |z| OctreeNode::new(x, y, z, 0))))
+    .collect(); // 512 nodes
+
+  group.bench_function("allocate/512", |b| {
+    b.iter(|| {
+      let outputs: Vec<_> = nodes
+        .iter()
+        .filter_map(|node| pipeline_on_demand(&terrain, node, &octree_config, &mesh_cfg))
+        .collect();
+      black_box(outputs)
+    })
+  });
+
+  let pool = VolumePool::new(16);
+  group.bench_function("pooled/512", |b| {
+    b.iter(|| {
+      let outputs: Vec<_> = nodes
+        .iter()
+        .filter_map(|&node| {
+          let presampled = presample_node_pooled(
+            node,
+            WorkSource::Refinement,
+            &terrain,
+            &octree_config,
+            &pool,
+          );
+          let sampled = presampled.volume?;
+          let output = mesh_generate(&sampled.volume, &sampled.materials, &mesh_cfg);
+          pool.release(sampled);
+          Some(output)
+        })
+        .collect();
+      black_box(outputs)
+    })
+  });
+
+  group.finish();
+}
+
 criterion_group!(
   isolated,
   bench_presample_isolated,
   bench_meshing_isolated,
+  bench_normals_isolated,
   bench_sampling_isolated,
 );
 
@@ -943,6 +1066,7 @@ criterion_group!(
   bench_pipeline_single_chunk,
   bench_pipeline_batch,
   bench_homogeneous_ratio,
+  bench_volume_pool,
 );
 
 criterion_main!(isolated, pipeline);
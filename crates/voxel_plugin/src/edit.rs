@@ -0,0 +1,1147 @@
+//! SDF edits layered on top of a world's base sampler.
+//!
+//! Edits are stored as analytic brushes and combined with the base SDF at
+//! sample time using CSG: `Place` is a union (`min(base, brush)`), `Remove`
+//! a subtraction (`max(base, -brush)`) and `Intersect` an intersection
+//! (`max(base, brush)`). The base sampler is never modified, so edits apply
+//! consistently at every LOD.
+//!
+//! `PlaceMaterial` is a `Place` that also paints its material into the
+//! samples it turns solid; samples that were already solid keep theirs.
+//!
+//! `Smooth` replaces every sample inside the brush with the average of itself
+//! and its 6 axis neighbors in the field left by the edits before it. Blocks
+//! with `Smooth` edits are edited with a margin of one sample per such edit,
+//! so neighbors past the edge of a 32³ block see those edits too, and a
+//! sample shared by two chunks gets the same value in both. The margin is
+//! capped at `MAX_SMOOTH_MARGIN` samples; past that many overlapping `Smooth`
+//! edits, samples near a block's faces read unsmoothed neighbors.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use glam::DVec3;
+
+use crate::constants::{SAMPLE_SIZE, SAMPLE_SIZE_CB};
+use crate::octree::codec::{self, Reader};
+use crate::octree::DAabb3;
+use crate::pipeline::VolumeSampler;
+use crate::types::{sdf_conversion, MaterialId, Sdf16, SdfSample};
+
+/// How a brush combines with the existing SDF.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditOp {
+  /// Add material (CSG union).
+  Place,
+  /// Add material (CSG union), writing the material where air becomes
+  /// solid.
+  PlaceMaterial(MaterialId),
+  /// Carve material away (CSG subtraction).
+  Remove,
+  /// Blur the SDF inside the brush, keeping material ids.
+  Smooth,
+  /// Keep only material inside the brush (CSG intersection). Affects the
+  /// whole world: everything outside the brush becomes air.
+  Intersect,
+}
+
+impl EditOp {
+  /// `PlaceMaterial` with `material` clamped to the last of
+  /// `material_count` IDs (see `MeshConfig::material_count`).
+  pub fn place(material: MaterialId, material_count: u8) -> Self {
+    EditOp::PlaceMaterial(material.min(material_count.saturating_sub(1)))
+  }
+}
+
+/// Analytic brush applied with `VoxelWorld::apply_edit`.
+///
+/// Positions are in the world's local (octree) space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SdfBrush {
+  Sphere {
+    center: DVec3,
+    radius: f64,
+    op: EditOp,
+  },
+  Box {
+    center: DVec3,
+    half_extents: DVec3,
+    op: EditOp,
+  },
+}
+
+impl SdfBrush {
+  /// The CSG operation of this brush.
+  pub fn op(&self) -> EditOp {
+    match *self {
+      SdfBrush::Sphere { op, .. } | SdfBrush::Box { op, .. } => op,
+    }
+  }
+
+  /// Signed distance from `p` to the brush surface (negative inside).
+  pub fn distance(&self, p: DVec3) -> f64 {
+    match *self {
+      SdfBrush::Sphere { center, radius, .. } => (p - center).length() - radius,
+      SdfBrush::Box {
+        center,
+        half_extents,
+        ..
+      } => {
+        let q = (p - center).abs() - half_extents;
+        q.max(DVec3::ZERO).length() + q.max_element().min(0.0)
+      }
+    }
+  }
+
+  /// Bounding box of the brush shape.
+  pub fn aabb(&self) -> DAabb3 {
+    match *self {
+      SdfBrush::Sphere { center, radius, .. } => {
+        DAabb3::from_center_half_extents(center, DVec3::splat(radius))
+      }
+      SdfBrush::Box {
+        center,
+        half_extents,
+        ..
+      } => DAabb3::from_center_half_extents(center, half_extents),
+    }
+  }
+
+  /// Bounds of every sample this brush can change at `voxel_size`.
+  ///
+  /// Quantized samples saturate a fraction of a voxel away from the surface,
+  /// so one voxel of margin is enough. `Intersect` brushes are unbounded.
+  pub fn influence_aabb(&self, voxel_size: f64) -> DAabb3 {
+    if self.op() == EditOp::Intersect {
+      return DAabb3::new(DVec3::NEG_INFINITY, DVec3::INFINITY);
+    }
+    let aabb = self.aabb();
+    DAabb3::new(
+      aabb.min - DVec3::splat(voxel_size),
+      aabb.max + DVec3::splat(voxel_size),
+    )
+  }
+
+  /// Combine a quantized base sample with this brush.
+  ///
+  /// `Smooth` needs neighboring samples and leaves `base` unchanged here.
+  #[inline]
+  fn combine(&self, base: SdfSample, p: DVec3, voxel_size: f64) -> SdfSample {
+    let brush = sdf_conversion::to_storage(self.distance(p) as f32, voxel_size as f32);
+    match self.op() {
+      EditOp::Place | EditOp::PlaceMaterial(_) => base.min(brush),
+      EditOp::Remove => base.max(-brush),
+      EditOp::Intersect => base.max(brush),
+      EditOp::Smooth => base,
+    }
+  }
+}
+
+/// Apply `edits` in order to a sampled 32³ block.
+///
+/// Sample (x, y, z) lies at `(grid_offset + [x, y, z]) * voxel_size + phase`.
+/// `Place` edits write their material into `materials` for every sample they
+/// turn from air to solid. `Smooth` edits are skipped: they read samples
+/// outside the block and are applied by `EditedSampler`.
+pub fn apply_edits(
+  edits: &[SdfBrush],
+  grid_offset: [i64; 3],
+  voxel_size: f64,
+  phase: DVec3,
+  volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+  materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+) {
+  let (origin, block) = block_bounds(grid_offset, voxel_size, phase);
+
+  for brush in edits {
+    if brush.op() != EditOp::Smooth && brush.influence_aabb(voxel_size).overlaps(&block) {
+      combine_grid(brush, origin, SAMPLE_SIZE, voxel_size, volume, materials);
+    }
+  }
+}
+
+/// Combine a `Place`, `Remove` or `Intersect` brush with a `size`³ grid of
+/// samples (X-slowest) whose sample (0, 0, 0) lies at `origin`.
+fn combine_grid(
+  brush: &SdfBrush,
+  origin: DVec3,
+  size: usize,
+  voxel_size: f64,
+  volume: &mut [SdfSample],
+  materials: &mut [MaterialId],
+) {
+  let placed = match brush.op() {
+    EditOp::PlaceMaterial(material) => Some(material),
+    EditOp::Place | EditOp::Remove | EditOp::Intersect | EditOp::Smooth => None,
+  };
+
+  let [xs, ys, zs] = grid_range(&brush.influence_aabb(voxel_size), origin, size, voxel_size);
+  for xi in xs {
+    for yi in ys.clone() {
+      for zi in zs.clone() {
+        let p = origin + DVec3::new(xi as f64, yi as f64, zi as f64) * voxel_size;
+        let idx = (xi * size + yi) * size + zi;
+        let base = volume[idx];
+        volume[idx] = brush.combine(base, p, voxel_size);
+        if let Some(material) = placed {
+          if base >= 0 && volume[idx] < 0 {
+            materials[idx] = material;
+          }
+        }
+      }
+    }
+  }
+}
+
+/// Apply a `Smooth` brush to a grid laid out as in [`combine_grid`].
+///
+/// Samples on the faces of the grid lack neighbors and keep their value, so
+/// each `Smooth` edit leaves one fewer layer of valid samples.
+fn smooth_grid(
+  brush: &SdfBrush,
+  origin: DVec3,
+  size: usize,
+  voxel_size: f64,
+  volume: &mut [SdfSample],
+) {
+  let inner = |range: Range<usize>| range.start.max(1)..range.end.min(size - 1);
+  let [xs, ys, zs] = grid_range(&brush.aabb(), origin, size, voxel_size).map(inner);
+  let sx = size * size;
+  let mut smoothed = Vec::new();
+  for xi in xs {
+    for yi in ys.clone() {
+      for zi in zs.clone() {
+        let p = origin + DVec3::new(xi as f64, yi as f64, zi as f64) * voxel_size;
+        if brush.distance(p) > 0.0 {
+          continue;
+        }
+        let idx = (xi * size + yi) * size + zi;
+        let neighbors = [idx - sx, idx + sx, idx - size, idx + size, idx - 1, idx + 1];
+        let sum = neighbors
+          .iter()
+          .fold(volume[idx] as i32, |sum, &i| sum + volume[i] as i32);
+        smoothed.push((idx, (sum as f32 / SMOOTH_TAPS as f32).round() as SdfSample));
+      }
+    }
+  }
+  for (idx, value) in smoothed {
+    volume[idx] = value;
+  }
+}
+
+/// Indices along each axis of the samples of a grid laid out as in
+/// [`combine_grid`] that lie inside `bounds`.
+fn grid_range(bounds: &DAabb3, origin: DVec3, size: usize, voxel_size: f64) -> [Range<usize>; 3] {
+  std::array::from_fn(|axis| {
+    let first = ((bounds.min[axis] - origin[axis]) / voxel_size).ceil();
+    let last = ((bounds.max[axis] - origin[axis]) / voxel_size).floor();
+    // Float-to-int casts saturate, so unbounded brushes cover the whole grid
+    (first.max(0.0) as usize).min(size)..((last + 1.0).max(0.0) as usize).min(size)
+  })
+}
+
+/// Magic bytes identifying encoded edits.
+const EDITS_MAGIC: &[u8; 4] = b"EDIT";
+
+/// Current version of the edits save format.
+pub const EDITS_FORMAT_VERSION: u8 = 2;
+
+/// Encode an edit list for saving.
+///
+/// Layout: magic "EDIT", version, edit count, then per edit a shape tag
+/// (0 sphere, 1 box), an op tag (0 remove, 1 place, 2 smooth, 3 intersect,
+/// 4 place material followed by the material), the center and the radius or
+/// half extents (little-endian f64).
+pub fn edits_to_bytes(edits: &[SdfBrush]) -> Vec<u8> {
+  let mut out = Vec::with_capacity(8 + edits.len() * 34);
+  out.extend_from_slice(EDITS_MAGIC);
+  out.push(EDITS_FORMAT_VERSION);
+  codec::write_varint(&mut out, edits.len() as u64);
+
+  for brush in edits {
+    let (shape, center, extents) = match *brush {
+      SdfBrush::Sphere { center, radius, .. } => (0, center, vec![radius]),
+      SdfBrush::Box {
+        center,
+        half_extents,
+        ..
+      } => (1, center, half_extents.to_array().to_vec()),
+    };
+    out.push(shape);
+    match brush.op() {
+      EditOp::Remove => out.push(0),
+      EditOp::Place => out.push(1),
+      EditOp::Smooth => out.push(2),
+      EditOp::Intersect => out.push(3),
+      EditOp::PlaceMaterial(material) => out.extend_from_slice(&[4, material]),
+    }
+    for value in center.to_array().into_iter().chain(extents) {
+      codec::write_f64(&mut out, value);
+    }
+  }
+  out
+}
+
+/// Decode an edit list written by [`edits_to_bytes`].
+///
+/// Returns `None` for a wrong magic or version, an unknown tag, a
+/// non-finite or negative size, truncated data, or trailing bytes.
+pub fn edits_from_bytes(bytes: &[u8]) -> Option<Vec<SdfBrush>> {
+  let mut reader = Reader::new(bytes);
+  if reader.read_bytes(EDITS_MAGIC.len())? != EDITS_MAGIC {
+    return None;
+  }
+  if reader.read_u8()? != EDITS_FORMAT_VERSION {
+    return None;
+  }
+
+  let count = usize::try_from(reader.read_varint()?).ok()?;
+  // Each edit takes at least 34 bytes; don't trust `count` for allocation
+  let mut edits = Vec::with_capacity(count.min(reader.remaining() / 34));
+  for _ in 0..count {
+    let shape = reader.read_u8()?;
+    let op = match reader.read_u8()? {
+      0 => EditOp::Remove,
+      1 => EditOp::Place,
+      2 => EditOp::Smooth,
+      3 => EditOp::Intersect,
+      4 => EditOp::PlaceMaterial(reader.read_u8()?),
+      _ => return None,
+    };
+    let center = reader.read_dvec3()?;
+    if !center.is_finite() {
+      return None;
+    }
+    let brush = match shape {
+      0 => SdfBrush::Sphere {
+        center,
+        radius: reader.read_f64()?,
+        op,
+      },
+      1 => SdfBrush::Box {
+        center,
+        half_extents: reader.read_dvec3()?,
+        op,
+      },
+      _ => return None,
+    };
+    let size = brush.aabb().max - center;
+    if !size.is_finite() || size.min_element() < 0.0 {
+      return None;
+    }
+    edits.push(brush);
+  }
+
+  if reader.remaining() != 0 {
+    return None;
+  }
+  Some(edits)
+}
+
+/// Position of sample (0, 0, 0) and the bounds of a 32³ block.
+fn block_bounds(grid_offset: [i64; 3], voxel_size: f64, phase: DVec3) -> (DVec3, DAabb3) {
+  let origin = DVec3::new(
+    grid_offset[0] as f64,
+    grid_offset[1] as f64,
+    grid_offset[2] as f64,
+  ) * voxel_size
+    + phase;
+  let block = DAabb3::new(
+    origin,
+    origin + DVec3::splat((SAMPLE_SIZE - 1) as f64 * voxel_size),
+  );
+  (origin, block)
+}
+
+/// Samples averaged by `Smooth`: the sample itself and its 6 axis neighbors.
+const SMOOTH_TAPS: i32 = 7;
+
+/// Largest margin, in samples, a block is grown by for `Smooth` edits. Caps
+/// the grown grid at 64³ however many strokes overlap.
+pub const MAX_SMOOTH_MARGIN: usize = 16;
+
+/// Sampler view combining a base sampler with a list of edits.
+///
+/// Base samples are normalised to negative-inside (see
+/// `VolumeSampler::sdf_convention`) before the edits apply, so the view is
+/// always negative-inside.
+pub struct EditedSampler<'a, S: ?Sized> {
+  /// Base terrain sampler.
+  pub base: &'a S,
+  /// Scene brushes applied before `edits` (see `VoxelWorld::brushes`).
+  pub brushes: &'a [SdfBrush],
+  /// Edits applied on top of the base SDF, oldest first.
+  pub edits: &'a [SdfBrush],
+}
+
+impl<S: VolumeSampler + ?Sized> EditedSampler<'_, S> {
+  /// Whether neither brushes nor edits change the base SDF.
+  fn is_unedited(&self) -> bool {
+    self.brushes.is_empty() && self.edits.is_empty()
+  }
+
+  /// Sample a block from `base`, normalised to negative-inside so the edits
+  /// combine with it in one convention.
+  fn sample_base(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    if phase == [0.0; 3] {
+      self
+        .base
+        .sample_volume(grid_offset, voxel_size, volume, materials);
+    } else {
+      self
+        .base
+        .sample_volume_with_phase(grid_offset, voxel_size, phase, volume, materials);
+    }
+    self.base.sdf_convention().normalize(volume.as_mut_slice());
+  }
+
+  /// The brushes, then the edits, in the order they apply.
+  fn all_edits(&self) -> Cow<'_, [SdfBrush]> {
+    if self.brushes.is_empty() {
+      Cow::Borrowed(self.edits)
+    } else {
+      Cow::Owned([self.brushes, self.edits].concat())
+    }
+  }
+
+  /// Apply the brushes, then the edits, in order, to a block sampled from
+  /// `base`.
+  fn apply(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    let edits = self.all_edits();
+    let phase_offset = DVec3::from_array(phase);
+    let (_, block) = block_bounds(grid_offset, voxel_size, phase_offset);
+    let margin = smooth_margin(&edits, &block, voxel_size);
+    if margin == 0 {
+      apply_edits(
+        &edits,
+        grid_offset,
+        voxel_size,
+        phase_offset,
+        volume,
+        materials,
+      );
+    } else {
+      self.apply_with_margin(
+        &edits,
+        margin,
+        grid_offset,
+        voxel_size,
+        phase,
+        volume,
+        materials,
+      );
+    }
+  }
+
+  /// Apply `edits` to the block grown by `margin` samples on every side,
+  /// then copy the block back into `volume` and `materials`.
+  ///
+  /// The grown block is sampled from `base` once, as 32³ tiles, so every
+  /// `Smooth` edit reads the field left by all the edits before it, past the
+  /// edge of the block as well. Every value is then a function of its world
+  /// position alone, which keeps the samples chunks share identical.
+  #[allow(clippy::too_many_arguments)]
+  fn apply_with_margin(
+    &self,
+    edits: &[SdfBrush],
+    margin: usize,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    let size = SAMPLE_SIZE + 2 * margin;
+    let start = grid_offset.map(|offset| offset - margin as i64);
+    let mut grid = vec![0; size * size * size];
+    let mut grid_materials = vec![0; size * size * size];
+
+    let tiles = size.div_ceil(SAMPLE_SIZE);
+    let mut tile = Box::new([0; SAMPLE_SIZE_CB]);
+    let mut tile_materials = Box::new([0; SAMPLE_SIZE_CB]);
+    for tx in 0..tiles {
+      for ty in 0..tiles {
+        for tz in 0..tiles {
+          let corner = [tx * SAMPLE_SIZE, ty * SAMPLE_SIZE, tz * SAMPLE_SIZE];
+          let offset = [
+            start[0] + corner[0] as i64,
+            start[1] + corner[1] as i64,
+            start[2] + corner[2] as i64,
+          ];
+          self.sample_base(offset, voxel_size, phase, &mut tile, &mut tile_materials);
+
+          for x in 0..SAMPLE_SIZE.min(size - corner[0]) {
+            for y in 0..SAMPLE_SIZE.min(size - corner[1]) {
+              for z in 0..SAMPLE_SIZE.min(size - corner[2]) {
+                let from = x * SAMPLE_SIZE * SAMPLE_SIZE + y * SAMPLE_SIZE + z;
+                let to = ((corner[0] + x) * size + corner[1] + y) * size + corner[2] + z;
+                grid[to] = tile[from];
+                grid_materials[to] = tile_materials[from];
+              }
+            }
+          }
+        }
+      }
+    }
+
+    let (origin, _) = block_bounds(start, voxel_size, DVec3::from_array(phase));
+    let bounds = DAabb3::new(
+      origin,
+      origin + DVec3::splat((size - 1) as f64 * voxel_size),
+    );
+    for brush in edits {
+      if !brush.influence_aabb(voxel_size).overlaps(&bounds) {
+        continue;
+      }
+      if brush.op() == EditOp::Smooth {
+        smooth_grid(brush, origin, size, voxel_size, &mut grid);
+      } else {
+        combine_grid(
+          brush,
+          origin,
+          size,
+          voxel_size,
+          &mut grid,
+          &mut grid_materials,
+        );
+      }
+    }
+
+    for x in 0..SAMPLE_SIZE {
+      for y in 0..SAMPLE_SIZE {
+        for z in 0..SAMPLE_SIZE {
+          let from = ((x + margin) * size + y + margin) * size + z + margin;
+          let to = x * SAMPLE_SIZE * SAMPLE_SIZE + y * SAMPLE_SIZE + z;
+          volume[to] = grid[from];
+          materials[to] = grid_materials[from];
+        }
+      }
+    }
+  }
+
+  /// Sample `position` with `edits` applied to the `(2 * margin + 1)`³
+  /// samples around it, sampled from `base` one point at a time, which is
+  /// all `margin` `Smooth` edits read.
+  fn sample_point_with_margin(
+    &self,
+    edits: &[SdfBrush],
+    margin: usize,
+    position: DVec3,
+    voxel_size: f64,
+  ) -> SdfSample {
+    let size = 2 * margin + 1;
+    let origin = position - DVec3::splat(margin as f64 * voxel_size);
+    let convention = self.base.sdf_convention();
+    let mut grid = Vec::with_capacity(size * size * size);
+    for xi in 0..size {
+      for yi in 0..size {
+        for zi in 0..size {
+          let p = origin + DVec3::new(xi as f64, yi as f64, zi as f64) * voxel_size;
+          grid.push(convention.to_native(self.base.sample_point(p.to_array(), voxel_size)));
+        }
+      }
+    }
+
+    let mut materials = vec![0; grid.len()];
+    for brush in edits {
+      if brush.op() == EditOp::Smooth {
+        smooth_grid(brush, origin, size, voxel_size, &mut grid);
+      } else {
+        combine_grid(brush, origin, size, voxel_size, &mut grid, &mut materials);
+      }
+    }
+    grid[(margin * size + margin) * size + margin]
+  }
+}
+
+/// Margin, in samples, the samples in `region` need for the `Smooth` edits in
+/// `edits`: one per `Smooth` edit that can reach them, as each reads one
+/// sample further out than the edits after it, up to `MAX_SMOOTH_MARGIN`.
+fn smooth_margin(edits: &[SdfBrush], region: &DAabb3, voxel_size: f64) -> usize {
+  let smooths = edits.iter().filter(|edit| edit.op() == EditOp::Smooth);
+  let reach = DVec3::splat(smooths.clone().count() as f64 * voxel_size);
+  let reachable = DAabb3::new(region.min - reach, region.max + reach);
+  smooths
+    .filter(|brush| brush.influence_aabb(voxel_size).overlaps(&reachable))
+    .count()
+    .min(MAX_SMOOTH_MARGIN)
+}
+
+impl<S: VolumeSampler + ?Sized> VolumeSampler for EditedSampler<'_, S> {
+  fn sample_volume(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    self.sample_base(grid_offset, voxel_size, [0.0; 3], volume, materials);
+    self.apply(grid_offset, voxel_size, [0.0; 3], volume, materials);
+  }
+
+  fn sample_volume_with_phase(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    self.sample_base(grid_offset, voxel_size, phase, volume, materials);
+    self.apply(grid_offset, voxel_size, phase, volume, materials);
+  }
+
+  /// The base sampler's normals, unless edits exist (brushes have none).
+  fn sample_normals(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    normals: &mut [[f32; 3]; SAMPLE_SIZE_CB],
+  ) -> bool {
+    if !self.is_unedited() {
+      return false;
+    }
+    let base = &self.base;
+    if !base.sample_normals(grid_offset, voxel_size, phase, normals) {
+      return false;
+    }
+    base
+      .sdf_convention()
+      .normalize_normals(normals.as_mut_slice());
+    true
+  }
+
+  /// The base sampler's 16-bit samples, unless edits exist (brushes are
+  /// applied in i8 storage, so edited volumes are widened instead).
+  fn sample_volume16(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [Sdf16; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    if self.is_unedited() {
+      self
+        .base
+        .sample_volume16(grid_offset, voxel_size, phase, volume, materials);
+      self.base.sdf_convention().normalize(volume.as_mut_slice());
+      return;
+    }
+
+    let mut narrow = Box::new([0; SAMPLE_SIZE_CB]);
+    self.sample_volume_with_phase(grid_offset, voxel_size, phase, &mut narrow, materials);
+    for (out, &sample) in volume.iter_mut().zip(narrow.iter()) {
+      *out = sample as Sdf16;
+    }
+  }
+
+  /// The base sampler's point sample with the brushes, then the edits,
+  /// combined in order. A `Smooth` edit that can reach `position` needs the
+  /// samples around it, so such points also sample the few around them.
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    let p = DVec3::from_array(position);
+    let edits = self.all_edits();
+    let margin = smooth_margin(&edits, &DAabb3::new(p, p), voxel_size);
+    if margin > 0 {
+      return self.sample_point_with_margin(&edits, margin, p, voxel_size);
+    }
+
+    edits
+      .iter()
+      .filter(|brush| brush.influence_aabb(voxel_size).contains_point(p))
+      .fold(
+        self
+          .base
+          .sdf_convention()
+          .to_native(self.base.sample_point(position, voxel_size)),
+        |value, brush| brush.combine(value, p, voxel_size),
+      )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::sdf_samplers::GroundPlaneSampler;
+
+  fn sample(sampler: &impl VolumeSampler) -> Box<[SdfSample; SAMPLE_SIZE_CB]> {
+    let mut volume = Box::new([0i8; SAMPLE_SIZE_CB]);
+    let mut materials = Box::new([0u8; SAMPLE_SIZE_CB]);
+    sampler.sample_volume([0, 0, 0], 1.0, &mut volume, &mut materials);
+    volume
+  }
+
+  fn at(volume: &[SdfSample; SAMPLE_SIZE_CB], x: usize, y: usize, z: usize) -> SdfSample {
+    volume[x * SAMPLE_SIZE * SAMPLE_SIZE + y * SAMPLE_SIZE + z]
+  }
+
+  #[test]
+  fn test_remove_carves_into_base() {
+    let base = GroundPlaneSampler::new(16.0);
+    let edits = [SdfBrush::Sphere {
+      center: DVec3::new(16.0, 16.0, 16.0),
+      radius: 4.0,
+      op: EditOp::Remove,
+    }];
+    let volume = sample(&EditedSampler {
+      base: &base,
+      brushes: &[],
+      edits: &edits,
+    });
+
+    // Carved below the ground inside the sphere
+    assert!(at(&volume, 16, 14, 16) > 0);
+    // Base SDF untouched away from the brush
+    assert!(at(&volume, 2, 14, 2) < 0);
+    assert!(at(&volume, 2, 20, 2) > 0);
+  }
+
+  #[test]
+  fn test_place_adds_to_base() {
+    let base = GroundPlaneSampler::new(16.0);
+    let edits = [SdfBrush::Box {
+      center: DVec3::new(16.0, 22.0, 16.0),
+      half_extents: DVec3::splat(3.0),
+      op: EditOp::Place,
+    }];
+    let volume = sample(&EditedSampler {
+      base: &base,
+      brushes: &[],
+      edits: &edits,
+    });
+
+    assert!(at(&volume, 16, 22, 16) < 0);
+    assert!(at(&volume, 2, 22, 2) > 0);
+    assert!(at(&volume, 16, 10, 16) < 0);
+  }
+
+  #[test]
+  fn test_edits_apply_in_order() {
+    let base = GroundPlaneSampler::new(16.0);
+    let center = DVec3::new(16.0, 22.0, 16.0);
+    let place = SdfBrush::Sphere {
+      center,
+      radius: 4.0,
+      op: EditOp::Place,
+    };
+    let remove = SdfBrush::Sphere {
+      center,
+      radius: 2.0,
+      op: EditOp::Remove,
+    };
+    let volume = sample(&EditedSampler {
+      base: &base,
+      brushes: &[],
+      edits: &[place, remove],
+    });
+
+    // Hollow shell: center carved, shell solid
+    assert!(at(&volume, 16, 22, 16) > 0);
+    assert!(at(&volume, 16, 25, 16) < 0);
+  }
+
+  #[test]
+  fn test_box_distance() {
+    let brush = SdfBrush::Box {
+      center: DVec3::ZERO,
+      half_extents: DVec3::new(1.0, 2.0, 3.0),
+      op: EditOp::Place,
+    };
+    assert_eq!(brush.distance(DVec3::ZERO), -1.0);
+    assert_eq!(brush.distance(DVec3::new(3.0, 0.0, 0.0)), 2.0);
+  }
+
+  #[test]
+  fn test_place_writes_material_only_in_newly_solid_cells() {
+    let base = GroundPlaneSampler::new(16.0);
+    // Straddles the ground: the lower half is already solid
+    let edits = [SdfBrush::Sphere {
+      center: DVec3::new(16.0, 16.0, 16.0),
+      radius: 5.0,
+      op: EditOp::place(2, 4),
+    }];
+    let edited = EditedSampler {
+      base: &base,
+      brushes: &[],
+      edits: &edits,
+    };
+
+    let mut base_volume = Box::new([0i8; SAMPLE_SIZE_CB]);
+    let mut volume = Box::new([0i8; SAMPLE_SIZE_CB]);
+    let mut materials = Box::new([0u8; SAMPLE_SIZE_CB]);
+    base.sample_volume([0, 0, 0], 1.0, &mut base_volume, &mut materials);
+    edited.sample_volume([0, 0, 0], 1.0, &mut volume, &mut materials);
+
+    let mut painted = 0;
+    for idx in 0..SAMPLE_SIZE_CB {
+      let newly_solid = base_volume[idx] >= 0 && volume[idx] < 0;
+      assert_eq!(materials[idx], if newly_solid { 2 } else { 0 }, "{}", idx);
+      painted += newly_solid as usize;
+    }
+    assert!(painted > 0);
+  }
+
+  #[test]
+  fn test_place_material_clamped_to_material_count() {
+    assert_eq!(EditOp::place(6, 4), EditOp::PlaceMaterial(3));
+    assert_eq!(EditOp::place(6, 8), EditOp::PlaceMaterial(6));
+  }
+
+  /// Hashed noise in [-60, 60] at a grid position.
+  fn noise([x, y, z]: [i64; 3]) -> SdfSample {
+    ((x * 73_856_093 ^ y * 19_349_663 ^ z * 83_492_791).rem_euclid(121) - 60) as SdfSample
+  }
+
+  /// Sampler returning [`noise`] per grid position.
+  struct NoiseSampler;
+
+  impl VolumeSampler for NoiseSampler {
+    fn sample_volume(
+      &self,
+      grid_offset: [i64; 3],
+      _voxel_size: f64,
+      volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+      materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+    ) {
+      for xi in 0..SAMPLE_SIZE {
+        for yi in 0..SAMPLE_SIZE {
+          for zi in 0..SAMPLE_SIZE {
+            let idx = xi * SAMPLE_SIZE * SAMPLE_SIZE + yi * SAMPLE_SIZE + zi;
+            volume[idx] = noise([
+              grid_offset[0] + xi as i64,
+              grid_offset[1] + yi as i64,
+              grid_offset[2] + zi as i64,
+            ]);
+            materials[idx] = 3;
+          }
+        }
+      }
+    }
+
+    fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+      noise(position.map(|v| (v / voxel_size).round() as i64))
+    }
+  }
+
+  fn variance(values: &[f64]) -> f64 {
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64
+  }
+
+  #[test]
+  fn test_smooth_reduces_variance_inside_brush() {
+    let center = DVec3::new(16.0, 16.0, 16.0);
+    let edits = [SdfBrush::Sphere {
+      center,
+      radius: 6.0,
+      op: EditOp::Smooth,
+    }];
+    let base = sample(&NoiseSampler);
+    let mut smoothed = Box::new([0i8; SAMPLE_SIZE_CB]);
+    let mut materials = Box::new([0u8; SAMPLE_SIZE_CB]);
+    EditedSampler {
+      base: &NoiseSampler,
+      brushes: &[],
+      edits: &edits,
+    }
+    .sample_volume([0, 0, 0], 1.0, &mut smoothed, &mut materials);
+
+    let inside: Vec<usize> = (0..SAMPLE_SIZE_CB)
+      .filter(|&idx| {
+        let (x, y, z) = (
+          idx / (SAMPLE_SIZE * SAMPLE_SIZE),
+          idx / SAMPLE_SIZE % SAMPLE_SIZE,
+          idx % SAMPLE_SIZE,
+        );
+        (DVec3::new(x as f64, y as f64, z as f64) - center).length() < 5.0
+      })
+      .collect();
+    let before: Vec<f64> = inside.iter().map(|&idx| base[idx] as f64).collect();
+    let after: Vec<f64> = inside.iter().map(|&idx| smoothed[idx] as f64).collect();
+
+    assert!(
+      variance(&after) < variance(&before) / 2.0,
+      "variance {} -> {}",
+      variance(&before),
+      variance(&after)
+    );
+    // Materials untouched, samples outside the brush unchanged
+    assert!(materials.iter().all(|&m| m == 3));
+    assert_eq!(at(&smoothed, 2, 2, 2), at(&base, 2, 2, 2));
+  }
+
+  /// [`noise`] after `passes` applications of the `Smooth` `brush`,
+  /// evaluated directly at a grid position.
+  fn smoothed_noise(brush: &SdfBrush, passes: usize, p: [i64; 3]) -> SdfSample {
+    if passes == 0 {
+      return noise(p);
+    }
+    let before = |q: [i64; 3]| smoothed_noise(brush, passes - 1, q);
+    if brush.distance(DVec3::new(p[0] as f64, p[1] as f64, p[2] as f64)) > 0.0 {
+      return before(p);
+    }
+
+    let mut sum = before(p) as i32;
+    for axis in 0..3 {
+      for step in [-1, 1] {
+        let mut q = p;
+        q[axis] += step;
+        sum += before(q) as i32;
+      }
+    }
+    (sum as f32 / SMOOTH_TAPS as f32).round() as SdfSample
+  }
+
+  #[test]
+  fn test_repeated_smooth_reads_smoothed_neighbors() {
+    // Crosses the block's +x face, so the neighbors past it count too
+    let brush = SdfBrush::Sphere {
+      center: DVec3::new(30.0, 16.0, 16.0),
+      radius: 4.0,
+      op: EditOp::Smooth,
+    };
+    let smoothed = sample(&EditedSampler {
+      base: &NoiseSampler,
+      brushes: &[],
+      edits: &[brush; 3],
+    });
+
+    for x in 26..SAMPLE_SIZE {
+      for y in 14..19 {
+        for z in 14..19 {
+          assert_eq!(
+            at(&smoothed, x, y, z),
+            smoothed_noise(&brush, 3, [x as i64, y as i64, z as i64]),
+            "({}, {}, {})",
+            x,
+            y,
+            z
+          );
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn test_sample_point_reads_smoothed_neighbors() {
+    let brush = SdfBrush::Sphere {
+      center: DVec3::new(30.0, 16.0, 16.0),
+      radius: 4.0,
+      op: EditOp::Smooth,
+    };
+    let edited = EditedSampler {
+      base: &NoiseSampler,
+      brushes: &[],
+      edits: &[brush; 3],
+    };
+
+    for x in 25..36 {
+      let p = [x, 16, 17];
+      assert_eq!(
+        edited.sample_point(p.map(|v| v as f64), 1.0),
+        smoothed_noise(&brush, 3, p),
+        "x = {}",
+        x
+      );
+    }
+  }
+
+  #[test]
+  fn test_smooth_margin_is_capped() {
+    let brush = SdfBrush::Sphere {
+      center: DVec3::new(16.0, 16.0, 16.0),
+      radius: 4.0,
+      op: EditOp::Smooth,
+    };
+    let (_, block) = block_bounds([0, 0, 0], 1.0, DVec3::ZERO);
+    assert_eq!(smooth_margin(&[brush; 3], &block, 1.0), 3);
+    assert_eq!(smooth_margin(&[brush; 200], &block, 1.0), MAX_SMOOTH_MARGIN);
+
+    // Still only smooths inside the brush
+    let base = sample(&NoiseSampler);
+    let smoothed = sample(&EditedSampler {
+      base: &NoiseSampler,
+      brushes: &[],
+      edits: &[brush; 40],
+    });
+    assert_eq!(at(&smoothed, 2, 2, 2), at(&base, 2, 2, 2));
+    assert_ne!(at(&smoothed, 16, 16, 16), at(&base, 16, 16, 16));
+  }
+
+  #[test]
+  fn test_smooth_matches_across_chunk_boundary() {
+    // Brushes straddling the boundary between blocks at x offsets 0 and 28
+    let edits = [
+      SdfBrush::Sphere {
+        center: DVec3::new(29.5, 16.0, 16.0),
+        radius: 5.0,
+        op: EditOp::Smooth,
+      },
+      SdfBrush::Sphere {
+        center: DVec3::new(31.0, 18.0, 16.0),
+        radius: 4.0,
+        op: EditOp::Smooth,
+      },
+    ];
+    let edited = EditedSampler {
+      base: &NoiseSampler,
+      brushes: &[],
+      edits: &edits,
+    };
+    let mut left = Box::new([0i8; SAMPLE_SIZE_CB]);
+    let mut right = Box::new([0i8; SAMPLE_SIZE_CB]);
+    let mut materials = Box::new([0u8; SAMPLE_SIZE_CB]);
+    edited.sample_volume([0, 0, 0], 1.0, &mut left, &mut materials);
+    edited.sample_volume([28, 0, 0], 1.0, &mut right, &mut materials);
+
+    for x in 0..SAMPLE_SIZE - 28 {
+      for y in 0..SAMPLE_SIZE {
+        for z in 0..SAMPLE_SIZE {
+          assert_eq!(at(&left, x + 28, y, z), at(&right, x, y, z));
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn test_sample_point_matches_volume() {
+    let base = GroundPlaneSampler::new(16.0);
+    let edits = [
+      SdfBrush::Sphere {
+        center: DVec3::new(16.0, 16.0, 16.0),
+        radius: 4.0,
+        op: EditOp::Remove,
+      },
+      SdfBrush::Box {
+        center: DVec3::new(24.0, 18.0, 24.0),
+        half_extents: DVec3::splat(2.0),
+        op: EditOp::Place,
+      },
+      SdfBrush::Sphere {
+        center: DVec3::new(8.0, 16.0, 8.0),
+        radius: 3.0,
+        op: EditOp::Smooth,
+      },
+    ];
+
+    let points = [
+      (16, 13, 16),
+      (16, 20, 16),
+      (24, 19, 24),
+      (8, 16, 8),
+      (2, 15, 2),
+    ];
+    // Without and with a `Smooth` edit (sampled from a block near it)
+    for edits in [&edits[..2], &edits[..]] {
+      let edited = EditedSampler {
+        base: &base,
+        brushes: &[],
+        edits,
+      };
+      let volume = sample(&edited);
+      for (x, y, z) in points {
+        let position = [x as f64, y as f64, z as f64];
+        assert_eq!(edited.sample_point(position, 1.0), at(&volume, x, y, z));
+      }
+    }
+  }
+
+  #[test]
+  fn test_edits_bytes_round_trip() {
+    let edits = vec![
+      SdfBrush::Sphere {
+        center: DVec3::new(1.0, -2.5, 3.0),
+        radius: 4.0,
+        op: EditOp::Remove,
+      },
+      SdfBrush::Box {
+        center: DVec3::new(16.0, 22.0, 16.0),
+        half_extents: DVec3::new(1.0, 2.0, 3.0),
+        op: EditOp::place(2, 4),
+      },
+      SdfBrush::Sphere {
+        center: DVec3::ZERO,
+        radius: 0.5,
+        op: EditOp::Smooth,
+      },
+      SdfBrush::Box {
+        center: DVec3::ZERO,
+        half_extents: DVec3::splat(8.0),
+        op: EditOp::Intersect,
+      },
+      SdfBrush::Sphere {
+        center: DVec3::splat(-4.0),
+        radius: 2.0,
+        op: EditOp::Place,
+      },
+    ];
+
+    let bytes = edits_to_bytes(&edits);
+    assert_eq!(edits_from_bytes(&bytes), Some(edits));
+    assert_eq!(edits_from_bytes(&edits_to_bytes(&[])), Some(Vec::new()));
+  }
+
+  #[test]
+  fn test_edits_from_bytes_rejects_bad_input() {
+    let bytes = edits_to_bytes(&[SdfBrush::Sphere {
+      center: DVec3::ZERO,
+      radius: 1.0,
+      op: EditOp::Remove,
+    }]);
+    for len in 0..bytes.len() {
+      assert!(edits_from_bytes(&bytes[..len]).is_none());
+    }
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(edits_from_bytes(&trailing).is_none());
+
+    let mut bad_op = bytes.clone();
+    bad_op[7] = 9;
+    assert!(edits_from_bytes(&bad_op).is_none());
+
+    let negative = edits_to_bytes(&[SdfBrush::Sphere {
+      center: DVec3::ZERO,
+      radius: -1.0,
+      op: EditOp::Remove,
+    }]);
+    assert!(edits_from_bytes(&negative).is_none());
+  }
+
+  #[test]
+  fn test_intersect_keeps_only_solid_inside_brush() {
+    let base = GroundPlaneSampler::new(16.0);
+    let edits = [SdfBrush::Sphere {
+      center: DVec3::new(16.0, 14.0, 16.0),
+      radius: 4.0,
+      op: EditOp::Intersect,
+    }];
+    let volume = sample(&EditedSampler {
+      base: &base,
+      brushes: &[],
+      edits: &edits,
+    });
+
+    // Solid ground inside the sphere stays, ground outside it is cleared
+    assert!(at(&volume, 16, 13, 16) < 0);
+    assert!(at(&volume, 2, 10, 2) > 0);
+    // Air inside the sphere stays air
+    assert!(at(&volume, 16, 17, 16) > 0);
+  }
+}
@@ -45,7 +45,8 @@ pub use constants::{
 };
 pub use edge_table::{EDGE_CORNERS, EDGE_TABLE};
 pub use types::{
-  sdf_conversion, MaterialId, MeshConfig, MeshOutput, MinMaxAABB, NormalMode, SdfSample, Vertex,
+  sdf_conversion, MaterialId, MeshAlgorithm, MeshConfig, MeshOutput, MinMaxAABB, NormalMode, Sdf16,
+  SdfConvention, SdfSample, SdfValue, UvMode, Vertex,
 };
 
 // Surface Nets module
@@ -64,18 +65,30 @@ pub use pipeline::{
 
 // World isolation - multi-world support
 pub mod world;
-pub use world::{VoxelWorld, WorldId};
+pub use world::{RayHit, RaycastJob, UpdateJob, UpdateResult, VoxelWorld, WorldId};
+
+// SDF edits layered over a world's sampler
+pub mod edit;
+pub use edit::{EditOp, SdfBrush};
 
 // Noise generation with FastNoise2 (native + WASM)
 pub mod noise;
-pub use noise::FastNoise2Terrain;
+pub use noise::{FastNoise2Terrain, NoiseHeightmapSampler, TerrainShaping};
 
 // Simple SDF samplers for testing
 pub mod sdf_samplers;
 pub use sdf_samplers::{
-  BoxSampler, GroundPlaneSampler, Metaball, MetaballsSampler, SphereSampler, TiltedPlaneSampler,
+  BoxSampler, GroundPlaneSampler, HeightmapFilter, HeightmapSampler, Metaball, MetaballsSampler,
+  SphereSampler, TiltedPlaneSampler,
 };
 
+// Raymarched sampler previews (dev tool, feature-gated)
+#[cfg(feature = "preview")]
+pub mod preview;
+
+// Worker thread count selection
+pub mod threading;
+
 // Metrics collection (feature-gated)
 pub mod metrics;
 pub use metrics::{WorldMetrics, RollingWindow, COLLECT_METRICS};
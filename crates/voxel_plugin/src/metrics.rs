@@ -17,12 +17,23 @@
 //! // Record mesh timing:
 //! metrics.record_mesh_timing(timing_us);
 //! ```
+//!
+//! # Idle Detection
+//!
+//! After `idle_timeout_frames` consecutive refinements without transitions,
+//! the world is considered idle: refine timings stop being sampled and
+//! snapshots report `idle` with a `sample_count` of 0, so a UI can show
+//! "idle" instead of stale numbers. The next transition resumes sampling.
 
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 #[cfg(feature = "metrics")]
 use std::sync::atomic::Ordering;
 use std::sync::atomic::AtomicBool;
 
+use crate::octree::OctreeNode;
+use crate::pipeline::{PresentationBatch, ProcessingStats};
+use crate::types::Vertex;
+
 /// Runtime toggle for metrics collection.
 /// Set to false to disable metrics gathering at runtime.
 pub static COLLECT_METRICS: AtomicBool = AtomicBool::new(true);
@@ -123,6 +134,39 @@ impl RollingWindow<u64> {
         }
     }
 
+    /// Value below which `p` percent of the window falls (`p` in 0..=100).
+    ///
+    /// Sorts a copy of the window and interpolates linearly between the two
+    /// nearest ranks. Returns 0 for an empty window.
+    pub fn percentile(&self, p: f32) -> u64 {
+        if self.buffer.is_empty() {
+            return 0;
+        }
+        let mut sorted: Vec<u64> = self.buffer.iter().copied().collect();
+        sorted.sort_unstable();
+
+        let rank = (p.clamp(0.0, 100.0) as f64 / 100.0) * (sorted.len() - 1) as f64;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        let t = rank - lower as f64;
+        (sorted[lower] as f64 + (sorted[upper] as f64 - sorted[lower] as f64) * t).round() as u64
+    }
+
+    /// Median of the window.
+    pub fn p50(&self) -> u64 {
+        self.percentile(50.0)
+    }
+
+    /// 95th percentile of the window.
+    pub fn p95(&self) -> u64 {
+        self.percentile(95.0)
+    }
+
+    /// 99th percentile of the window.
+    pub fn p99(&self) -> u64 {
+        self.percentile(99.0)
+    }
+
     /// Extract histogram stats for FFI export.
     pub fn stats(&self) -> TimingStats {
         let (min, max) = self.min_max().unwrap_or((0, 0));
@@ -131,6 +175,7 @@ impl RollingWindow<u64> {
             avg_us: self.average() as u64,
             min_us: min,
             max_us: max,
+            p95_us: self.p95(),
             sample_count: self.len() as u32,
         }
     }
@@ -149,7 +194,9 @@ pub struct TimingStats {
     pub min_us: u64,
     /// Maximum in window in microseconds.
     pub max_us: u64,
-    /// Number of samples in window (up to 128).
+    /// 95th percentile of window in microseconds.
+    pub p95_us: u64,
+    /// Number of samples in window (up to 128). 0 while the world is idle.
     pub sample_count: u32,
 }
 
@@ -178,6 +225,27 @@ pub struct MetricsSnapshot {
     pub total_subdivisions: u64,
     /// Total collapses this session.
     pub total_collapses: u64,
+    /// No transitions for `idle_timeout_frames` refinements; timing stats
+    /// are stale.
+    pub idle: bool,
+    /// Total presample stage time this session (summed over workers).
+    pub total_presample_us: u64,
+    /// Total meshing stage time this session (summed over workers).
+    pub total_meshing_us: u64,
+    /// Total composition stage time this session.
+    pub total_composition_us: u64,
+    /// Total presentation stage time this session.
+    pub total_presentation_us: u64,
+    /// Vertices across currently presented chunks.
+    pub current_vertices: u64,
+    /// Indices across currently presented chunks.
+    pub current_indices: u64,
+    /// Highest `current_vertices` seen.
+    pub peak_vertices: u64,
+    /// Highest `current_indices` seen.
+    pub peak_indices: u64,
+    /// Highest approximate mesh memory of presented chunks, in bytes.
+    pub peak_mesh_memory_bytes: u64,
 }
 
 impl Default for RollingWindow<u64> {
@@ -242,8 +310,48 @@ pub struct WorldMetrics {
     pub total_subdivisions: u64,
     /// Total collapses this session.
     pub total_collapses: u64,
+
+    // Idle detection
+    /// Consecutive refinements that produced no transitions.
+    pub idle_frames: u32,
+    /// Refinements without transitions before metrics go idle (0 = never).
+    pub idle_timeout_frames: u32,
+
+    // Pipeline stage breakdown (cumulative, from `ProcessingStats`)
+    /// Total presample stage time in microseconds (summed over workers).
+    pub total_presample_us: u64,
+    /// Total meshing stage time in microseconds (summed over workers).
+    pub total_meshing_us: u64,
+    /// Total composition stage time in microseconds.
+    pub total_composition_us: u64,
+    /// Total presentation stage time in microseconds.
+    pub total_presentation_us: u64,
+
+    // Presented geometry (from `PresentationBatch`es)
+    /// Vertices across currently presented chunks.
+    pub current_vertices: u64,
+    /// Indices across currently presented chunks.
+    pub current_indices: u64,
+    /// Highest `current_vertices` this session.
+    pub peak_vertices: u64,
+    /// Highest `current_indices` this session.
+    pub peak_indices: u64,
+    /// Highest approximate mesh memory of presented chunks, in bytes.
+    pub peak_mesh_memory_bytes: u64,
+    /// Vertex and index count of each presented chunk, so despawns can be
+    /// subtracted.
+    presented: HashMap<OctreeNode, (u64, u64)>,
 }
 
+/// Approximate mesh memory: one `Vertex` per vertex, 4 bytes per index.
+fn mesh_bytes(vertices: u64, indices: u64) -> u64 {
+    vertices * std::mem::size_of::<Vertex>() as u64 + indices * 4
+}
+
+/// Default number of transition-free refinements before metrics go idle
+/// (~1 second at 60fps).
+pub const DEFAULT_IDLE_TIMEOUT_FRAMES: u32 = 60;
+
 impl Default for WorldMetrics {
     fn default() -> Self {
         Self {
@@ -267,6 +375,18 @@ impl Default for WorldMetrics {
             last_collapses: 0,
             total_subdivisions: 0,
             total_collapses: 0,
+            idle_frames: 0,
+            idle_timeout_frames: DEFAULT_IDLE_TIMEOUT_FRAMES,
+            total_presample_us: 0,
+            total_meshing_us: 0,
+            total_composition_us: 0,
+            total_presentation_us: 0,
+            current_vertices: 0,
+            current_indices: 0,
+            peak_vertices: 0,
+            peak_indices: 0,
+            peak_mesh_memory_bytes: 0,
+            presented: HashMap::new(),
         }
     }
 }
@@ -293,15 +413,66 @@ impl WorldMetrics {
         self.last_mesh_us = 0;
         self.last_subdivisions = 0;
         self.last_collapses = 0;
+        self.idle_frames = 0;
+        // Peaks restart from what is still presented
+        self.peak_vertices = self.current_vertices;
+        self.peak_indices = self.current_indices;
+        self.peak_mesh_memory_bytes = mesh_bytes(self.current_vertices, self.current_indices);
         // Don't reset cumulative counters
     }
 
+    /// Start a new measurement session: zero the cumulative counters and
+    /// clear the rolling windows.
+    ///
+    /// LOD distribution and presented geometry describe the current world
+    /// and are kept; peaks restart from the presented geometry.
+    pub fn reset_counters(&mut self) {
+        self.mesh_timings.clear();
+        self.refine_timings.clear();
+        self.sample_timings.clear();
+        self.last_refine_us = 0;
+        self.last_mesh_us = 0;
+        self.total_chunks_generated = 0;
+        self.total_refine_calls = 0;
+        self.total_chunks_meshed = 0;
+        self.total_transitions = 0;
+        self.last_subdivisions = 0;
+        self.last_collapses = 0;
+        self.total_subdivisions = 0;
+        self.total_collapses = 0;
+        self.idle_frames = 0;
+        self.total_presample_us = 0;
+        self.total_meshing_us = 0;
+        self.total_composition_us = 0;
+        self.total_presentation_us = 0;
+        self.peak_vertices = self.current_vertices;
+        self.peak_indices = self.current_indices;
+        self.peak_mesh_memory_bytes = mesh_bytes(self.current_vertices, self.current_indices);
+    }
+
+    /// Whether the world has been static for `idle_timeout_frames`
+    /// refinements.
+    pub fn is_idle(&self) -> bool {
+        self.idle_timeout_frames > 0 && self.idle_frames >= self.idle_timeout_frames
+    }
+
     /// Create FFI-safe snapshot with computed stats from rolling windows.
+    ///
+    /// While idle, every `sample_count` is reported as 0.
     pub fn snapshot(&self) -> MetricsSnapshot {
+        let idle = self.is_idle();
+        let stats = |window: &RollingWindow<u64>| {
+            let mut stats = window.stats();
+            if idle {
+                stats.sample_count = 0;
+            }
+            stats
+        };
+
         MetricsSnapshot {
-            refine: self.refine_timings.stats(),
-            mesh: self.mesh_timings.stats(),
-            sample: self.sample_timings.stats(),
+            refine: stats(&self.refine_timings),
+            mesh: stats(&self.mesh_timings),
+            sample: stats(&self.sample_timings),
             total_refine_calls: self.total_refine_calls,
             total_chunks_meshed: self.total_chunks_meshed,
             total_transitions: self.total_transitions,
@@ -309,6 +480,16 @@ impl WorldMetrics {
             last_collapses: self.last_collapses,
             total_subdivisions: self.total_subdivisions,
             total_collapses: self.total_collapses,
+            idle,
+            total_presample_us: self.total_presample_us,
+            total_meshing_us: self.total_meshing_us,
+            total_composition_us: self.total_composition_us,
+            total_presentation_us: self.total_presentation_us,
+            current_vertices: self.current_vertices,
+            current_indices: self.current_indices,
+            peak_vertices: self.peak_vertices,
+            peak_indices: self.peak_indices,
+            peak_mesh_memory_bytes: self.peak_mesh_memory_bytes,
         }
     }
 
@@ -321,18 +502,30 @@ impl WorldMetrics {
     }
 
     /// Record a refinement timing and increment call count.
+    ///
+    /// The timing is not sampled while idle.
     pub fn record_refine_timing(&mut self, timing_us: u64) {
         if is_enabled() {
-            self.refine_timings.push(timing_us);
-            self.last_refine_us = timing_us;
+            if !self.is_idle() {
+                self.refine_timings.push(timing_us);
+                self.last_refine_us = timing_us;
+            }
             self.total_refine_calls += 1;
         }
     }
 
-    /// Record transition groups processed count.
+    /// Record transition groups processed by one refinement.
+    ///
+    /// Drives idle detection: a refinement with no transitions counts
+    /// towards `idle_timeout_frames`, any transition resets it.
     pub fn record_transitions(&mut self, count: usize) {
         if is_enabled() {
             self.total_transitions += count as u64;
+            if count == 0 {
+                self.idle_frames = self.idle_frames.saturating_add(1);
+            } else {
+                self.idle_frames = 0;
+            }
         }
     }
 
@@ -353,6 +546,68 @@ impl WorldMetrics {
         }
     }
 
+    /// Accumulate the per-stage breakdown of one pipeline run.
+    pub fn record_stage_timings(&mut self, stats: &ProcessingStats) {
+        if is_enabled() {
+            self.total_presample_us += stats.presample_us;
+            self.total_meshing_us += stats.meshing_us;
+            self.total_composition_us += stats.composition_us;
+            self.total_presentation_us += stats.presentation_us;
+        }
+    }
+
+    /// Track presented geometry through a batch the bridge will apply.
+    ///
+    /// Despawned nodes (including children removed by a collapse) subtract
+    /// their counts before spawned chunks add theirs, then peaks are updated.
+    pub fn record_presentation(&mut self, batch: &PresentationBatch) {
+        self.record_presented(
+            batch.to_despawn.iter().copied(),
+            batch.to_spawn.iter().map(|chunk| {
+                (
+                    chunk.node,
+                    chunk.output.vertices.len() as u64,
+                    chunk.output.indices.len() as u64,
+                )
+            }),
+        );
+    }
+
+    /// Track presented geometry for bridges that don't build a
+    /// `PresentationBatch`: `spawned` lists each chunk's node, vertex count
+    /// and index count.
+    ///
+    /// Tracking runs even while collection is disabled at runtime, so the
+    /// current and peak geometry are right as soon as it is re-enabled.
+    pub fn record_presented(
+        &mut self,
+        despawned: impl IntoIterator<Item = OctreeNode>,
+        spawned: impl IntoIterator<Item = (OctreeNode, u64, u64)>,
+    ) {
+        for node in despawned {
+            if let Some((vertices, indices)) = self.presented.remove(&node) {
+                self.current_vertices -= vertices;
+                self.current_indices -= indices;
+            }
+        }
+        for (node, vertices, indices) in spawned {
+            // A respawn without a despawn replaces the old mesh
+            let replaced = self.presented.insert(node, (vertices, indices));
+            if let Some((old_vertices, old_indices)) = replaced {
+                self.current_vertices -= old_vertices;
+                self.current_indices -= old_indices;
+            }
+            self.current_vertices += vertices;
+            self.current_indices += indices;
+        }
+
+        self.peak_vertices = self.peak_vertices.max(self.current_vertices);
+        self.peak_indices = self.peak_indices.max(self.current_indices);
+        self.peak_mesh_memory_bytes = self
+            .peak_mesh_memory_bytes
+            .max(mesh_bytes(self.current_vertices, self.current_indices));
+    }
+
     /// Record a sample timing.
     pub fn record_sample_timing(&mut self, timing_us: u64) {
         if is_enabled() {
@@ -371,8 +626,7 @@ impl WorldMetrics {
         self.vertices_per_lod[lod_idx] += vertex_count as u64;
         self.indices_per_lod[lod_idx] += index_count as u64;
 
-        // Approximate memory: 32 bytes per vertex, 4 bytes per index
-        let chunk_memory = (vertex_count as u64 * 32) + (index_count as u64 * 4);
+        let chunk_memory = mesh_bytes(vertex_count as u64, index_count as u64);
         self.mesh_memory_bytes += chunk_memory;
 
         self.visible_nodes += 1;
@@ -393,7 +647,7 @@ impl WorldMetrics {
         self.indices_per_lod[lod_idx] =
             self.indices_per_lod[lod_idx].saturating_sub(index_count as u64);
 
-        let chunk_memory = (vertex_count as u64 * 32) + (index_count as u64 * 4);
+        let chunk_memory = mesh_bytes(vertex_count as u64, index_count as u64);
         self.mesh_memory_bytes = self.mesh_memory_bytes.saturating_sub(chunk_memory);
 
         self.visible_nodes = self.visible_nodes.saturating_sub(1);
@@ -434,6 +688,8 @@ impl WorldMetrics {
 #[cfg(all(test, feature = "metrics"))]
 mod tests {
     use super::*;
+    use crate::pipeline::{ChunkPresentation, PresentationHint};
+    use crate::types::MeshOutput;
 
     #[test]
     fn test_rolling_window() {
@@ -458,6 +714,30 @@ mod tests {
         assert_eq!(max, 40);
     }
 
+    #[test]
+    fn test_rolling_window_percentiles() {
+        // 1..=100 with a single large outlier replacing 100
+        let mut window = RollingWindow::new(100);
+        for value in 1..100u64 {
+            window.push(value);
+        }
+        window.push(10_000);
+
+        assert!((49..=52).contains(&window.p50()), "p50 = {}", window.p50());
+        assert!((94..=97).contains(&window.p95()), "p95 = {}", window.p95());
+        // p99 sits between the last regular value and the outlier
+        assert!(window.p99() >= 99 && window.p99() < 10_000);
+        assert_eq!(window.percentile(0.0), 1);
+        assert_eq!(window.percentile(100.0), 10_000);
+        assert_eq!(window.stats().p95_us, window.p95());
+
+        // Sorting a copy leaves the ring buffer order intact
+        assert_eq!(window.last(), Some(&10_000));
+        assert_eq!(window.iter().next(), Some(&1));
+
+        assert_eq!(RollingWindow::<u64>::new(4).p99(), 0);
+    }
+
     #[test]
     fn test_world_metrics() {
         let mut metrics = WorldMetrics::new();
@@ -490,4 +770,76 @@ mod tests {
         assert_eq!(metrics.avg_mesh_timing_us(), 2000.0);
         assert_eq!(metrics.last_mesh_us, 3000);
     }
+
+    fn chunk(node: OctreeNode, vertices: usize) -> ChunkPresentation {
+        ChunkPresentation {
+            node,
+            position: glam::DVec3::ZERO,
+            scale: 1.0,
+            output: MeshOutput {
+                vertices: vec![Vertex::default(); vertices],
+                indices: vec![0; vertices * 3],
+                ..Default::default()
+            },
+            hint: PresentationHint::Immediate,
+        }
+    }
+
+    #[test]
+    fn test_collapse_restores_presented_vertex_count() {
+        let mut metrics = WorldMetrics::new();
+        let parent = OctreeNode::new(0, 0, 0, 1);
+        let children: Vec<OctreeNode> = (0..8).map(|i| parent.get_child(i).unwrap()).collect();
+
+        metrics.record_presentation(&PresentationBatch {
+            to_despawn: vec![],
+            to_spawn: vec![chunk(parent, 100)],
+        });
+        assert_eq!(metrics.current_vertices, 100);
+
+        // Subdivide: the parent goes, its children arrive
+        metrics.record_presentation(&PresentationBatch {
+            to_despawn: vec![parent],
+            to_spawn: children.iter().map(|&child| chunk(child, 40)).collect(),
+        });
+        assert_eq!(metrics.current_vertices, 320);
+        assert_eq!(metrics.current_indices, 960);
+
+        // Collapse: the children go, the parent comes back
+        metrics.record_presentation(&PresentationBatch {
+            to_despawn: children,
+            to_spawn: vec![chunk(parent, 100)],
+        });
+        assert_eq!(metrics.current_vertices, 100);
+        assert_eq!(metrics.current_indices, 300);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.peak_vertices, 320);
+        assert_eq!(snapshot.peak_indices, 960);
+        assert_eq!(
+            snapshot.peak_mesh_memory_bytes,
+            320 * std::mem::size_of::<Vertex>() as u64 + 960 * 4
+        );
+    }
+
+    #[test]
+    fn test_stage_timings_accumulate() {
+        let mut metrics = WorldMetrics::new();
+        let stats = ProcessingStats {
+            presample_us: 10,
+            meshing_us: 20,
+            composition_us: 3,
+            presentation_us: 4,
+            ..Default::default()
+        };
+
+        metrics.record_stage_timings(&stats);
+        metrics.record_stage_timings(&stats);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.total_presample_us, 20);
+        assert_eq!(snapshot.total_meshing_us, 40);
+        assert_eq!(snapshot.total_composition_us, 6);
+        assert_eq!(snapshot.total_presentation_us, 8);
+    }
 }
@@ -0,0 +1,197 @@
+//! FastNoise2-based 2D heightmap sampler implementing VolumeSampler.
+
+use super::{presets, NoiseNode};
+use crate::constants::{SAMPLE_SIZE, SAMPLE_SIZE_CB};
+use crate::pipeline::VolumeSampler;
+use crate::types::{sdf_conversion, MaterialId, Sdf16, SdfSample};
+
+/// Volume sampler that reads a FastNoise2 node tree as a 2D heightfield.
+///
+/// Generates one `SAMPLE_SIZE²` grid of 2D noise per chunk (one value per
+/// XZ column) instead of a full 3D grid, so sampling costs a fraction of
+/// [`FastNoise2Terrain`](super::FastNoise2Terrain). The terrain has no
+/// overhangs or caves.
+///
+/// Height formula: `height = base_height + noise * amplitude`
+///
+/// SDF: `y - height(x, z)` (vertical distance, negative below the surface)
+#[derive(Clone)]
+pub struct NoiseHeightmapSampler {
+  encoded: &'static str,
+  /// World units the noise displaces the surface by (default: 64.0)
+  pub amplitude: f32,
+  /// World Y of the surface where the noise is zero (default: 0.0)
+  pub base_height: f64,
+  /// Frequency multiplier for noise sampling (default: 0.005)
+  /// Smaller = wider hills
+  pub frequency: f32,
+  pub seed: i32,
+}
+
+impl NoiseHeightmapSampler {
+  /// Create a heightmap sampler with the default preset.
+  pub fn new(seed: i32) -> Self {
+    Self::with_encoded(presets::SIMPLE_TERRAIN, seed)
+  }
+
+  /// Create a heightmap sampler with a custom encoded noise graph.
+  ///
+  /// Encoded strings can be exported from FastNoise2's NoiseTool application.
+  pub fn with_encoded(encoded: &'static str, seed: i32) -> Self {
+    Self {
+      encoded,
+      amplitude: 64.0,
+      base_height: 0.0,
+      frequency: 0.005,
+      seed,
+    }
+  }
+
+  /// Set how far (in world units) the noise displaces the surface.
+  pub fn with_amplitude(mut self, amplitude: f64) -> Self {
+    self.amplitude = amplitude as f32;
+    self
+  }
+
+  /// Set the world Y of the surface where the noise is zero.
+  pub fn with_base_height(mut self, base_height: f64) -> Self {
+    self.base_height = base_height;
+    self
+  }
+
+  /// Set frequency multiplier for noise sampling.
+  ///
+  /// Smaller values = wider terrain features.
+  pub fn with_frequency(mut self, frequency: f64) -> Self {
+    self.frequency = frequency as f32;
+    self
+  }
+
+  /// Surface heights of a chunk's columns, in world units.
+  ///
+  /// Layout is X-fastest (FastNoise2 2D): `heights[z * SAMPLE_SIZE + x]`.
+  pub fn column_heights(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+  ) -> Vec<f64> {
+    const SIZE: i32 = SAMPLE_SIZE as i32;
+
+    let world_x = (grid_offset[0] as f64 * voxel_size + phase[0]) as f32 * self.frequency;
+    let world_z = (grid_offset[2] as f64 * voxel_size + phase[2]) as f32 * self.frequency;
+    // Step must scale with voxel_size for chunk boundary coherency
+    let step = voxel_size as f32 * self.frequency;
+
+    let node = NoiseNode::from_encoded(self.encoded).expect("Invalid encoded node tree");
+    let mut noise = vec![0.0f32; SAMPLE_SIZE * SAMPLE_SIZE];
+    node.gen_uniform_grid_2d(
+      &mut noise, world_x, world_z, SIZE, SIZE, step, step, self.seed,
+    );
+
+    noise
+      .into_iter()
+      .map(|n| self.base_height + (n * self.amplitude) as f64)
+      .collect()
+  }
+
+  /// Fill a volume with `quantize(y - height)` per sample.
+  fn fill<T>(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [T; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+    quantize: impl Fn(f32, f32) -> T,
+  ) {
+    let heights = self.column_heights(grid_offset, voxel_size, phase);
+
+    // Heights are X-fastest; the volume is X-slowest, Z-fastest
+    for xi in 0..SAMPLE_SIZE {
+      for zi in 0..SAMPLE_SIZE {
+        let height = heights[zi * SAMPLE_SIZE + xi];
+
+        for yi in 0..SAMPLE_SIZE {
+          let wy = (grid_offset[1] + yi as i64) as f64 * voxel_size + phase[1];
+          let sdf = wy - height;
+
+          let idx = xi * SAMPLE_SIZE * SAMPLE_SIZE + yi * SAMPLE_SIZE + zi;
+          volume[idx] = quantize(sdf as f32, voxel_size as f32);
+          materials[idx] = 0;
+        }
+      }
+    }
+  }
+}
+
+impl VolumeSampler for NoiseHeightmapSampler {
+  #[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, name = "noise::sample_heightmap")
+  )]
+  fn sample_volume(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    self.sample_volume_with_phase(grid_offset, voxel_size, [0.0; 3], volume, materials);
+  }
+
+  fn sample_volume_with_phase(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    self.fill(
+      grid_offset,
+      voxel_size,
+      phase,
+      volume,
+      materials,
+      sdf_conversion::to_storage,
+    );
+  }
+
+  fn sample_volume16(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [Sdf16; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    self.fill(
+      grid_offset,
+      voxel_size,
+      phase,
+      volume,
+      materials,
+      sdf_conversion::to_storage16,
+    );
+  }
+
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    let step = voxel_size as f32 * self.frequency;
+    let node = NoiseNode::from_encoded(self.encoded).expect("Invalid encoded node tree");
+    let mut noise = [0.0f32];
+    node.gen_uniform_grid_2d(
+      &mut noise,
+      position[0] as f32 * self.frequency,
+      position[2] as f32 * self.frequency,
+      1,
+      1,
+      step,
+      step,
+      self.seed,
+    );
+
+    let height = self.base_height + (noise[0] * self.amplitude) as f64;
+    sdf_conversion::to_storage((position[1] - height) as f32, voxel_size as f32)
+  }
+}
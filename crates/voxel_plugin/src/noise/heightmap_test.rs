@@ -0,0 +1,76 @@
+//! Tests for the 2D noise heightmap sampler.
+
+use super::NoiseHeightmapSampler;
+use crate::constants::{SAMPLE_SIZE, SAMPLE_SIZE_CB};
+use crate::pipeline::VolumeSampler;
+
+/// Every column is solid strictly below its sampled height and air above,
+/// with the SDF increasing monotonically up the column.
+#[test]
+fn test_sign_change_at_sampled_height() {
+  // Surface within ±8 of y = 0, inside a chunk spanning y = -16..15
+  let sampler = NoiseHeightmapSampler::new(1337)
+    .with_amplitude(8.0)
+    .with_frequency(0.05);
+  let grid_offset = [-16, -16, -16];
+  let voxel_size = 1.0;
+
+  let mut volume = [0i8; SAMPLE_SIZE_CB];
+  let mut materials = [0u8; SAMPLE_SIZE_CB];
+  sampler.sample_volume(grid_offset, voxel_size, &mut volume, &mut materials);
+  let heights = sampler.column_heights(grid_offset, voxel_size, [0.0; 3]);
+
+  assert!(
+    heights.iter().any(|&h| (h - heights[0]).abs() > 0.5),
+    "Heightmap should not be flat"
+  );
+
+  for x in 0..SAMPLE_SIZE {
+    for z in 0..SAMPLE_SIZE {
+      let height = heights[z * SAMPLE_SIZE + x];
+      let mut previous = i8::MIN;
+
+      for y in 0..SAMPLE_SIZE {
+        let sdf = volume[x * SAMPLE_SIZE * SAMPLE_SIZE + y * SAMPLE_SIZE + z];
+        assert!(
+          sdf >= previous,
+          "SDF must not decrease up column ({}, {})",
+          x,
+          z
+        );
+        previous = sdf;
+
+        // Samples within a quantization step of the surface may round to 0
+        let wy = (grid_offset[1] + y as i64) as f64 * voxel_size;
+        if (wy - height).abs() > 0.01 {
+          assert_eq!(
+            sdf < 0,
+            wy < height,
+            "Sign at y={} should match height {} in column ({}, {})",
+            wy,
+            height,
+            x,
+            z
+          );
+        }
+      }
+    }
+  }
+}
+
+/// Adjacent chunks read the same heights along their shared columns.
+#[test]
+fn test_column_heights_coherent_across_chunks() {
+  let sampler = NoiseHeightmapSampler::new(1337);
+  let a = sampler.column_heights([0, 0, 0], 1.0, [0.0; 3]);
+  let b = sampler.column_heights([28, 0, 0], 1.0, [0.0; 3]);
+
+  for z in 0..SAMPLE_SIZE {
+    for overlap in 0..4 {
+      assert_eq!(
+        a[z * SAMPLE_SIZE + 28 + overlap],
+        b[z * SAMPLE_SIZE + overlap]
+      );
+    }
+  }
+}
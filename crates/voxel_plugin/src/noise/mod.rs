@@ -5,7 +5,8 @@
 //! - WASM: Uses wasm-bindgen to call JS bridge to Emscripten module
 //!
 //! The `FastNoise2Terrain` sampler uses a single 3D noise graph directly
-//! as SDF values. Works identically on native and WASM.
+//! as SDF values; `NoiseHeightmapSampler` reads the graph in 2D as a
+//! heightfield. Both work identically on native and WASM.
 
 // Platform-specific NoiseNode implementations
 #[cfg(target_arch = "wasm32")]
@@ -21,7 +22,16 @@ pub use wasm::NoiseNode;
 mod terrain;
 #[cfg(test)]
 mod terrain_test;
-pub use terrain::FastNoise2Terrain;
+#[cfg(test)]
+#[path = "mod_test.rs"]
+mod mod_test;
+pub use terrain::{FastNoise2Terrain, TerrainShaping};
+
+// Heightmap sampler (2D noise, one value per column)
+mod heightmap;
+#[cfg(test)]
+mod heightmap_test;
+pub use heightmap::NoiseHeightmapSampler;
 
 
 // Re-export presets
@@ -45,46 +55,43 @@ use crate::types::SdfSample;
 ///
 /// Returns true if meshing is needed, false if chunk can be skipped.
 pub fn has_surface_crossing(volume: &[SdfSample; SAMPLE_SIZE_CB]) -> bool {
-  // Check x-axis edges (x varies, y and z fixed)
-  for y in 0..SAMPLE_SIZE {
-    for z in 0..SAMPLE_SIZE {
-      for x in 0..(SAMPLE_SIZE - 1) {
-        let i0 = coord_to_index(x, y, z);
-        let i1 = coord_to_index(x + 1, y, z);
-        if (volume[i0] < 0) != (volume[i1] < 0) {
-          return true;
-        }
-      }
-    }
-  }
+  count_crossings(volume, 1) > 0
+}
 
-  // Check y-axis edges (y varies, x and z fixed)
-  for x in 0..SAMPLE_SIZE {
-    for z in 0..SAMPLE_SIZE {
-      for y in 0..(SAMPLE_SIZE - 1) {
-        let i0 = coord_to_index(x, y, z);
-        let i1 = coord_to_index(x, y + 1, z);
-        if (volume[i0] < 0) != (volume[i1] < 0) {
-          return true;
-        }
-      }
-    }
-  }
+/// Count all surface crossings (sign-change edges) in the volume.
+///
+/// Unlike `has_surface_crossing` this scans every edge, so it costs a full
+/// pass. The count approximates surface area and is used to schedule dense
+/// chunks first.
+pub fn surface_crossing_count(volume: &[SdfSample; SAMPLE_SIZE_CB]) -> usize {
+  count_crossings(volume, usize::MAX)
+}
+
+/// Count sign-change edges along all three axes, stopping once `limit` is
+/// reached.
+fn count_crossings(volume: &[SdfSample; SAMPLE_SIZE_CB], limit: usize) -> usize {
+  // Unit step per axis: x, y, z edges
+  const AXES: [[usize; 3]; 3] = [[1, 0, 0], [0, 1, 0], [0, 0, 1]];
 
-  // Check z-axis edges (z varies, x and y fixed)
-  for x in 0..SAMPLE_SIZE {
-    for y in 0..SAMPLE_SIZE {
-      for z in 0..(SAMPLE_SIZE - 1) {
-        let i0 = coord_to_index(x, y, z);
-        let i1 = coord_to_index(x, y, z + 1);
-        if (volume[i0] < 0) != (volume[i1] < 0) {
-          return true;
+  let mut count = 0;
+  for [dx, dy, dz] in AXES {
+    for x in 0..(SAMPLE_SIZE - dx) {
+      for y in 0..(SAMPLE_SIZE - dy) {
+        for z in 0..(SAMPLE_SIZE - dz) {
+          let i0 = coord_to_index(x, y, z);
+          let i1 = coord_to_index(x + dx, y + dy, z + dz);
+          if (volume[i0] < 0) != (volume[i1] < 0) {
+            count += 1;
+            if count >= limit {
+              return count;
+            }
+          }
         }
       }
     }
   }
 
-  false // No surface crossings found
+  count
 }
 
 /// Check if a volume is entirely air or solid (can skip meshing).
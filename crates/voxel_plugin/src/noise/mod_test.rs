@@ -0,0 +1,45 @@
+//! Tests for surface crossing detection.
+
+use super::*;
+
+/// Solid below `z = 16`, air above.
+fn half_solid_volume() -> [SdfSample; SAMPLE_SIZE_CB] {
+  let mut volume = [1i8; SAMPLE_SIZE_CB];
+  for x in 0..SAMPLE_SIZE {
+    for y in 0..SAMPLE_SIZE {
+      for z in 0..16 {
+        volume[coord_to_index(x, y, z)] = -1;
+      }
+    }
+  }
+  volume
+}
+
+#[test]
+fn test_half_solid_crossings_on_one_slab() {
+  let volume = half_solid_volume();
+
+  // Only z-edges between z=15 and z=16 cross: one per (x, y) column
+  assert_eq!(surface_crossing_count(&volume), SAMPLE_SIZE * SAMPLE_SIZE);
+  assert!(has_surface_crossing(&volume));
+}
+
+#[test]
+fn test_homogeneous_volume_has_no_crossings() {
+  let air = [1i8; SAMPLE_SIZE_CB];
+  let solid = [-1i8; SAMPLE_SIZE_CB];
+
+  assert_eq!(surface_crossing_count(&air), 0);
+  assert_eq!(surface_crossing_count(&solid), 0);
+  assert!(!has_surface_crossing(&air));
+  assert!(!has_surface_crossing(&solid));
+}
+
+#[test]
+fn test_single_solid_sample_counts_all_axes() {
+  let mut volume = [1i8; SAMPLE_SIZE_CB];
+  volume[coord_to_index(5, 5, 5)] = -1;
+
+  // Six neighbours, one crossing edge each
+  assert_eq!(surface_crossing_count(&volume), 6);
+}
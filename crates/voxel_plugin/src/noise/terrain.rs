@@ -5,14 +5,52 @@ use crate::constants::{SAMPLE_SIZE, SAMPLE_SIZE_CB};
 use crate::pipeline::VolumeSampler;
 use crate::types::{sdf_conversion, MaterialId, SdfSample};
 
+/// Per-sample reshaping of the base noise, applied before SDF conversion.
+///
+/// The shaped modes fold the noise into a height in `[0, 1]`, which only
+/// makes sense as the displacement of a ground plane: a shaped terrain is
+/// always a heightfield over `base_height` (y = 0 when unset), never a pure
+/// 3D volume.
+///
+/// A pure function of each sample, so chunk boundaries stay coherent.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TerrainShaping {
+  /// Use the noise as-is (rolling hills).
+  #[default]
+  None,
+  /// Height `1 - |n|`: sharp crests where the noise crosses zero (mountain
+  /// ridges).
+  Ridged,
+  /// Height `|n|`: rounded, puffy lobes with creases along the zero
+  /// crossings.
+  Billow,
+}
+
+impl TerrainShaping {
+  /// Reshape a single noise sample (a height in `[0, 1]` for the shaped
+  /// modes).
+  #[inline]
+  pub fn apply(self, n: f32) -> f32 {
+    match self {
+      TerrainShaping::None => n,
+      TerrainShaping::Ridged => 1.0 - n.abs(),
+      TerrainShaping::Billow => n.abs(),
+    }
+  }
+}
+
 /// Volume sampler using a single FastNoise2 encoded node tree.
 ///
 /// Samples a 3D noise graph as SDF values for volumetric shapes.
 /// The noise output is scaled to properly utilize the i8 quantization range.
 ///
-/// SDF formula: `sdf = noise * scale`
+/// SDF formula: `sdf = noise * scale * amplitude`, plus `y - base_height`
+/// when a base height is set (noise then displaces a ground plane instead of
+/// filling the whole volume). With [`TerrainShaping`] the shaped noise is a
+/// height instead: `sdf = y - base_height - shaped * scale * amplitude`.
 ///
-/// Where `sdf < 0` is solid and `sdf > 0` is air.
+/// Where `sdf < 0` is solid and `sdf > 0` is air. Amplitude, frequency and
+/// base height are uniform across the world, so chunk edges stay coherent.
 ///
 /// **Important:** FastNoise2 typically outputs [-1, 1]. To avoid quantization
 /// stepping artifacts, set `scale` to utilize more of the ±10.0 storage range.
@@ -28,7 +66,18 @@ pub struct FastNoise2Terrain {
   /// Frequency multiplier for noise sampling (default: 0.1)
   /// Smaller = larger terrain features
   pub frequency: f32,
+  /// Extra multiplier on the scaled noise, in world units (default: 1.0)
+  pub amplitude: f32,
+  /// World Y of the ground plane the noise displaces (default: none, the
+  /// noise alone defines the volume)
+  pub base_height: Option<f64>,
   pub seed: i32,
+  /// Seed blended towards by `blend`
+  pub blend_seed: i32,
+  /// Blend factor from `seed` (0.0) to `blend_seed` (1.0)
+  pub blend: f32,
+  /// Post-process applied to each noise sample (default: none)
+  pub shaping: TerrainShaping,
 }
 
 impl FastNoise2Terrain {
@@ -38,7 +87,12 @@ impl FastNoise2Terrain {
 			encoded: presets::SIMPLE_TERRAIN,
 			scale: 8.0,  // Use most of ±10.0 quantization range
 			frequency: 0.1,
+			amplitude: 1.0,
+			base_height: None,
 			seed,
+			blend_seed: seed,
+			blend: 0.0,
+			shaping: TerrainShaping::None,
 		}
 	}
 
@@ -50,7 +104,12 @@ impl FastNoise2Terrain {
 			encoded,
 			scale: 8.0,
 			frequency: 0.1,
+			amplitude: 1.0,
+			base_height: None,
 			seed,
+			blend_seed: seed,
+			blend: 0.0,
+			shaping: TerrainShaping::None,
 		}
 	}
 
@@ -71,6 +130,108 @@ impl FastNoise2Terrain {
     self.frequency = frequency;
     self
   }
+
+  /// Like [`with_frequency`](Self::with_frequency), for callers holding f64
+  /// world parameters.
+  pub fn with_frequency_f64(self, frequency: f64) -> Self {
+    self.with_frequency(frequency as f32)
+  }
+
+  /// Set the amplitude the noise is multiplied by on top of `scale`.
+  ///
+  /// Doubling it doubles the SDF range (and, with a base height, the height
+  /// of hills) without re-encoding the node tree.
+  pub fn with_amplitude(mut self, amplitude: f64) -> Self {
+    self.amplitude = amplitude as f32;
+    self
+  }
+
+  /// Turn the volume into a ground plane at world Y `base_height`, displaced
+  /// by the noise.
+  pub fn with_base_height(mut self, base_height: f64) -> Self {
+    self.base_height = Some(base_height);
+    self
+  }
+
+  /// Set the per-sample shaping applied to the base noise.
+  ///
+  /// Turns the same node tree into ridged mountains or billowy hills
+  /// without re-encoding it. Shaped terrain is a heightfield over
+  /// `base_height` (y = 0 when unset) rising up to `scale * amplitude`.
+  pub fn with_shaping(mut self, shaping: TerrainShaping) -> Self {
+    self.shaping = shaping;
+    self
+  }
+
+  /// Blend the noise of two seeds: `from_seed` at `t = 0`, `to_seed` at
+  /// `t = 1`, linearly interpolated in between.
+  ///
+  /// Animating `t` over a few frames (with the world remeshing) turns a
+  /// seed change into a smooth morph instead of a pop. Intermediate values
+  /// generate noise twice per chunk.
+  pub fn set_seed_blend(&mut self, from_seed: i32, to_seed: i32, t: f32) {
+    self.seed = from_seed;
+    self.blend_seed = to_seed;
+    self.blend = t.clamp(0.0, 1.0);
+  }
+
+  /// Generate a grid of raw noise (FastNoise2 X-fastest layout).
+  fn generate_noise(&self, node: &NoiseNode, origin: [f32; 3], step: f32, seed: i32) -> Vec<f32> {
+    const SIZE: i32 = SAMPLE_SIZE as i32;
+
+    let mut noise = vec![0.0f32; SAMPLE_SIZE_CB];
+    node.gen_uniform_grid_3d(
+      &mut noise, origin[0], origin[1], origin[2], SIZE, SIZE, SIZE, step, step, step, seed,
+    );
+    noise
+  }
+
+  /// Blended, shaped noise at a single world position (unscaled).
+  fn noise_at(&self, node: &NoiseNode, position: [f64; 3], voxel_size: f64) -> f32 {
+    let step = voxel_size as f32 * self.frequency;
+    let generate = |seed: i32| {
+      let mut noise = [0.0f32];
+      node.gen_uniform_grid_3d(
+        &mut noise,
+        position[0] as f32 * self.frequency,
+        position[1] as f32 * self.frequency,
+        position[2] as f32 * self.frequency,
+        1,
+        1,
+        1,
+        step,
+        step,
+        step,
+        seed,
+      );
+      noise[0]
+    };
+
+    let mut value = generate(if self.blend >= 1.0 {
+      self.blend_seed
+    } else {
+      self.seed
+    });
+    if self.blend > 0.0 && self.blend < 1.0 {
+      value += (generate(self.blend_seed) - value) * self.blend;
+    }
+    if self.shaping != TerrainShaping::None {
+      value = self.shaping.apply(value);
+    }
+    value
+  }
+
+  /// SDF in world units for a (blended, shaped) noise value at `world_y`.
+  fn sdf(&self, noise: f32, world_y: f64) -> f32 {
+    // Noise typically [-1, 1], scale converts to world units
+    let displacement = noise * self.scale * self.amplitude;
+    match (self.shaping, self.base_height) {
+      (TerrainShaping::None, None) => displacement,
+      (TerrainShaping::None, Some(base_height)) => displacement + (world_y - base_height) as f32,
+      // Shaped noise is never negative, so it raises the ground plane
+      (_, base_height) => (world_y - base_height.unwrap_or(0.0)) as f32 - displacement,
+    }
+  }
 }
 
 impl VolumeSampler for FastNoise2Terrain {
@@ -81,14 +242,25 @@ impl VolumeSampler for FastNoise2Terrain {
     voxel_size: f64,
     volume: &mut [SdfSample; SAMPLE_SIZE_CB],
     materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    self.sample_volume_with_phase(grid_offset, voxel_size, [0.0; 3], volume, materials);
+  }
+
+  fn sample_volume_with_phase(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
   ) {
     const SIZE: usize = SAMPLE_SIZE;
 
-    // Convert grid_offset to world position, then scale by frequency
-    // frequency controls terrain feature size: smaller = larger features
-    let world_x = (grid_offset[0] as f64 * voxel_size) as f32 * self.frequency;
-    let world_y = (grid_offset[1] as f64 * voxel_size) as f32 * self.frequency;
-    let world_z = (grid_offset[2] as f64 * voxel_size) as f32 * self.frequency;
+    // Convert grid_offset (+ sub-voxel phase) to world position, then scale by
+    // frequency. Frequency controls terrain feature size: smaller = larger features
+    let world_x = (grid_offset[0] as f64 * voxel_size + phase[0]) as f32 * self.frequency;
+    let world_y = (grid_offset[1] as f64 * voxel_size + phase[1]) as f32 * self.frequency;
+    let world_z = (grid_offset[2] as f64 * voxel_size + phase[2]) as f32 * self.frequency;
     // Step must scale with voxel_size for chunk boundary coherency
     let step = voxel_size as f32 * self.frequency;
 
@@ -96,20 +268,24 @@ impl VolumeSampler for FastNoise2Terrain {
     let node = NoiseNode::from_encoded(self.encoded).expect("Invalid encoded node tree");
 
     // Generate 3D noise directly using fork's float offset API
-    let mut noise = vec![0.0f32; SAMPLE_SIZE_CB];
-    node.gen_uniform_grid_3d(
-      &mut noise,
-      world_x,
-      world_y,
-      world_z,
-      SIZE as i32,
-      SIZE as i32,
-      SIZE as i32,
-      step,
-      step,
-      step,
-      self.seed,
-    );
+    let origin = [world_x, world_y, world_z];
+    let seed = if self.blend >= 1.0 {
+      self.blend_seed
+    } else {
+      self.seed
+    };
+    let mut noise = self.generate_noise(&node, origin, step, seed);
+    if self.blend > 0.0 && self.blend < 1.0 {
+      let target = self.generate_noise(&node, origin, step, self.blend_seed);
+      for (value, target) in noise.iter_mut().zip(&target) {
+        *value += (target - *value) * self.blend;
+      }
+    }
+    if self.shaping != TerrainShaping::None {
+      for value in noise.iter_mut() {
+        *value = self.shaping.apply(*value);
+      }
+    }
 
     // Convert noise to SDF with scale
     // CRITICAL: Remap axis ordering from FastNoise2 to volume layout
@@ -124,14 +300,15 @@ impl VolumeSampler for FastNoise2Terrain {
       // FastNoise2 index: X-fastest layout
       let fn_idx = z * SIZE * SIZE + y * SIZE + x;
 
+      // World Y = grid_offset.y * voxel_size + local_y * voxel_size
+      let world_y = (grid_offset[1] + y as i64) as f64 * voxel_size + phase[1];
+
       // Scale noise to world units, then quantize with voxel-size awareness
-      // Noise typically [-1, 1], scale converts to world units
-      let sdf = noise[fn_idx] * self.scale;
+      let sdf = self.sdf(noise[fn_idx], world_y);
       volume[vol_idx] = sdf_conversion::to_storage(sdf, voxel_size as f32);
 
       // Assign material based on world height with noise variation
-      // World Y = grid_offset.y * voxel_size + local_y * voxel_size
-      let world_y = grid_offset[1] as f32 * voxel_size as f32 + y as f32 * voxel_size as f32;
+      let world_y = world_y as f32;
 
       // Use noise value for variation
       let noise_val = noise[fn_idx];
@@ -152,4 +329,10 @@ impl VolumeSampler for FastNoise2Terrain {
       };
     }
   }
+
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    let node = NoiseNode::from_encoded(self.encoded).expect("Invalid encoded node tree");
+    let sdf = self.sdf(self.noise_at(&node, position, voxel_size), position[1]);
+    sdf_conversion::to_storage(sdf, voxel_size as f32)
+  }
 }
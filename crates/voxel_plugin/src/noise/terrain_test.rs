@@ -3,7 +3,7 @@
 //! These tests verify that adjacent chunks produce identical SDF values
 //! at their shared edges when sampled through the full pipeline.
 
-use super::FastNoise2Terrain;
+use super::{FastNoise2Terrain, TerrainShaping};
 use crate::constants::SAMPLE_SIZE;
 use crate::octree::{OctreeConfig, OctreeNode};
 use crate::pipeline::sample_volume_for_node;
@@ -18,6 +18,7 @@ fn test_terrain_edge_coherency_full_pipeline() {
 		min_lod: 0,
 		max_lod: 6,
 		lod_exponent: 1.5,
+		lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
 		world_bounds: None,
 	};
 
@@ -79,6 +80,7 @@ fn test_terrain_edge_coherency_small_voxel() {
 		min_lod: 0,
 		max_lod: 6,
 		lod_exponent: 1.5,
+		lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
 		world_bounds: None,
 	};
 
@@ -127,6 +129,98 @@ fn test_terrain_edge_coherency_small_voxel() {
   );
 }
 
+/// Ridged shaping is a per-sample transform, so adjacent chunks still agree.
+#[test]
+fn test_terrain_edge_coherency_ridged() {
+  let sampler = FastNoise2Terrain::new(1337).with_shaping(TerrainShaping::Ridged);
+  let config = OctreeConfig {
+    voxel_size: 1.0,
+    world_origin: glam::DVec3::ZERO,
+    min_lod: 0,
+    max_lod: 6,
+    lod_exponent: 1.5,
+    lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
+    world_bounds: None,
+  };
+
+  let node_a = OctreeNode::new(0, 0, 0, 0);
+  let node_b = OctreeNode::new(1, 0, 0, 0);
+
+  let sampled_a = sample_volume_for_node(&node_a, &sampler, &config);
+  let sampled_b = sample_volume_for_node(&node_b, &sampler, &config);
+
+  // Ridged output must differ from the unshaped terrain
+  let plain = sample_volume_for_node(&node_a, &FastNoise2Terrain::new(1337), &config);
+  assert_ne!(sampled_a.volume, plain.volume);
+
+  let mut mismatches = 0;
+  let mut max_diff: i16 = 0;
+
+  for y in 0..SAMPLE_SIZE {
+    for z in 0..SAMPLE_SIZE {
+      for overlap_idx in 0..4 {
+        let a_x = 28 + overlap_idx;
+        let b_x = overlap_idx;
+
+        let a_idx = a_x * SAMPLE_SIZE * SAMPLE_SIZE + y * SAMPLE_SIZE + z;
+        let b_idx = b_x * SAMPLE_SIZE * SAMPLE_SIZE + y * SAMPLE_SIZE + z;
+
+        let diff = (sampled_a.volume[a_idx] as i16 - sampled_b.volume[b_idx] as i16).abs();
+        if diff > 0 {
+          mismatches += 1;
+          max_diff = max_diff.max(diff);
+        }
+      }
+    }
+  }
+
+  assert_eq!(
+    mismatches, 0,
+    "Found {} edge sample mismatches with ridged shaping (max diff: {})",
+    mismatches, max_diff
+  );
+}
+
+/// Every shaping mode yields a surface: the chunk holds solid and air.
+#[test]
+fn test_terrain_shaping_produces_surface() {
+  let config = OctreeConfig {
+    voxel_size: 1.0,
+    world_origin: glam::DVec3::ZERO,
+    min_lod: 0,
+    max_lod: 6,
+    lod_exponent: 1.5,
+    lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
+    world_bounds: None,
+  };
+  let node = OctreeNode::new(0, 0, 0, 0);
+
+  for shaping in [
+    TerrainShaping::None,
+    TerrainShaping::Ridged,
+    TerrainShaping::Billow,
+  ] {
+    for base_height in [None, Some(12.0)] {
+      let mut sampler = FastNoise2Terrain::new(1337).with_shaping(shaping);
+      if let Some(base_height) = base_height {
+        sampler = sampler.with_base_height(base_height);
+      }
+      let sampled = sample_volume_for_node(&node, &sampler, &config);
+
+      let solid = sampled.volume.iter().filter(|&&v| v < 0).count();
+      let air = sampled.volume.iter().filter(|&&v| v > 0).count();
+      assert!(
+        solid > 0 && air > 0,
+        "{:?} (base height {:?}) has no surface: {} solid, {} air",
+        shaping,
+        base_height,
+        solid,
+        air
+      );
+    }
+  }
+}
+
 /// Debug test: Print the world positions being sampled for adjacent chunks.
 #[test]
 fn test_debug_world_positions() {
@@ -136,6 +230,7 @@ fn test_debug_world_positions() {
 		min_lod: 0,
 		max_lod: 6,
 		lod_exponent: 1.5,
+		lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
 		world_bounds: None,
 	};
 
@@ -185,3 +280,118 @@ fn test_debug_world_positions() {
     b_sample_0_world
   );
 }
+
+/// Coarse and fine LODs must sample the same lattice even when the world
+/// origin is not aligned to the coarse voxel size.
+#[test]
+fn test_terrain_coarse_samples_coincide_with_children() {
+	let sampler = FastNoise2Terrain::new(1337);
+	let config = OctreeConfig {
+		voxel_size: 1.0,
+		world_origin: glam::DVec3::new(1.0, -3.0, 5.0),
+		min_lod: 0,
+		max_lod: 6,
+		lod_exponent: 1.5,
+		lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
+		world_bounds: None,
+	};
+
+	let coarse = OctreeNode::new(0, 0, 0, 1);
+	let fine = coarse.get_child(0).expect("LOD 1 node has children");
+
+	let coarse_vol = sample_volume_for_node(&coarse, &sampler, &config);
+	let fine_vol = sample_volume_for_node(&fine, &sampler, &config);
+
+	// Coarse sample i coincides with fine sample 2i. Values near zero may
+	// quantize differently between voxel sizes, so only compare clear signs.
+	let mut mismatches = 0;
+	for x in 0..SAMPLE_SIZE / 2 {
+		for y in 0..SAMPLE_SIZE / 2 {
+			for z in 0..SAMPLE_SIZE / 2 {
+				let c = coarse_vol.volume[x * SAMPLE_SIZE * SAMPLE_SIZE + y * SAMPLE_SIZE + z];
+				let f = fine_vol.volume
+					[(2 * x) * SAMPLE_SIZE * SAMPLE_SIZE + (2 * y) * SAMPLE_SIZE + (2 * z)];
+				if c.abs() >= 2 && f.abs() >= 2 && (c < 0) != (f < 0) {
+					mismatches += 1;
+				}
+			}
+		}
+	}
+
+	assert_eq!(
+		mismatches, 0,
+		"Coarse samples should lie on the fine sample lattice"
+	);
+}
+
+/// Sample one chunk of `sampler` at a fixed offset.
+fn sample_chunk(sampler: &FastNoise2Terrain) -> Vec<crate::types::SdfSample> {
+	use crate::constants::SAMPLE_SIZE_CB;
+	use crate::pipeline::VolumeSampler;
+
+	let mut volume = [0i8; SAMPLE_SIZE_CB];
+	let mut materials = [0u8; SAMPLE_SIZE_CB];
+	sampler.sample_volume([-16, -16, -16], 1.0, &mut volume, &mut materials);
+	volume.to_vec()
+}
+
+/// Seed blending hits both endpoints exactly and interpolates in between.
+#[test]
+fn test_seed_blend_interpolates_between_seeds() {
+	let from = sample_chunk(&FastNoise2Terrain::new(1));
+	let to = sample_chunk(&FastNoise2Terrain::new(2));
+	assert_ne!(from, to, "Different seeds should produce different noise");
+
+	let mut sampler = FastNoise2Terrain::new(7);
+	sampler.set_seed_blend(1, 2, 0.0);
+	assert_eq!(sample_chunk(&sampler), from);
+
+	sampler.set_seed_blend(1, 2, 1.0);
+	assert_eq!(sample_chunk(&sampler), to);
+
+	sampler.set_seed_blend(1, 2, 0.5);
+	let half = sample_chunk(&sampler);
+	assert_ne!(half, from);
+	assert_ne!(half, to);
+	for ((&h, &a), &b) in half.iter().zip(&from).zip(&to) {
+		assert!(
+			(a.min(b)..=a.max(b)).contains(&h),
+			"Blended sample {} should lie between {} and {}",
+			h, a, b
+		);
+	}
+}
+
+/// Amplitude is a uniform multiplier: doubling it doubles the SDF range.
+#[test]
+fn test_doubling_amplitude_doubles_sdf_range() {
+  // Small scale keeps both ranges clear of the i8 clamp
+  let base = FastNoise2Terrain::new(1337).with_scale(0.02);
+  let range = |sampler: &FastNoise2Terrain| {
+    let volume = sample_chunk(sampler);
+    let min = *volume.iter().min().unwrap() as i32;
+    let max = *volume.iter().max().unwrap() as i32;
+    assert!(min > -127 && max < 127, "SDF range should not be clamped");
+    max - min
+  };
+
+  let single = range(&base.clone().with_amplitude(1.0));
+  let double = range(&base.with_amplitude(2.0));
+  assert!(single > 10, "Noise should span a measurable range");
+  // Each endpoint rounds independently, so allow a couple of steps
+  assert!(
+    (double - 2 * single).abs() <= 2,
+    "Doubled amplitude range {} should be twice {}",
+    double,
+    single
+  );
+}
+
+#[test]
+fn test_frequency_f64_matches_f32_builder() {
+  let terrain = FastNoise2Terrain::new(1);
+  assert_eq!(
+    terrain.clone().with_frequency_f64(0.05).frequency,
+    terrain.with_frequency(0.05).frequency
+  );
+}
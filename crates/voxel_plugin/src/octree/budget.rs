@@ -1,7 +1,8 @@
 //! Rate limiting configuration for octree refinement.
 //!
 //! Prevents frame spikes from unbounded cascading operations by limiting
-//! the number of subdivisions and collapses per frame.
+//! the number of subdivisions and collapses (and optionally the time spent
+//! applying them) per frame.
 
 /// Rate limiting configuration for octree refinement.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -16,6 +17,9 @@ pub struct RefinementBudget {
   /// Maximum neighbor enforcement iterations per frame.
   /// Prevents runaway cascading when many neighbors need fixing.
   pub max_neighbor_iterations: usize,
+  /// Wall-clock limit in milliseconds for applying collapses and
+  /// subdivisions (0 = unlimited). Neighbor enforcement is not time-limited.
+  pub max_millis: u32,
 }
 
 impl RefinementBudget {
@@ -25,6 +29,7 @@ impl RefinementBudget {
     max_collapses: 32,
     max_relative_lod: 1,
     max_neighbor_iterations: 4,
+    max_millis: 0,
   };
 
   /// Unlimited budget for testing or special cases.
@@ -33,6 +38,7 @@ impl RefinementBudget {
     max_collapses: usize::MAX,
     max_relative_lod: 1,
     max_neighbor_iterations: usize::MAX,
+    max_millis: 0,
   };
 
   /// Budget with neighbor enforcement disabled.
@@ -41,6 +47,7 @@ impl RefinementBudget {
     max_collapses: 32,
     max_relative_lod: 0,
     max_neighbor_iterations: 0,
+    max_millis: 0,
   };
 
   /// Check if neighbor enforcement is enabled.
@@ -60,6 +67,12 @@ impl RefinementBudget {
   pub fn can_collapse(&self, performed: usize) -> bool {
     self.max_collapses == 0 || performed < self.max_collapses
   }
+
+  /// Check if the time budget allows more work.
+  #[inline]
+  pub fn has_time(&self, elapsed_millis: u64) -> bool {
+    self.max_millis == 0 || elapsed_millis < self.max_millis as u64
+  }
 }
 
 impl Default for RefinementBudget {
@@ -158,6 +171,20 @@ mod tests {
     assert!(budget.can_collapse(1000));
   }
 
+  #[test]
+  fn test_has_time() {
+    let budget = RefinementBudget {
+      max_millis: 4,
+      ..Default::default()
+    };
+    assert!(budget.has_time(0));
+    assert!(budget.has_time(3));
+    assert!(!budget.has_time(4));
+
+    // 0 = unlimited
+    assert!(RefinementBudget::DEFAULT.has_time(u64::MAX));
+  }
+
   #[test]
   fn test_stats_totals() {
     let stats = RefinementStats {
@@ -0,0 +1,83 @@
+//! Minimal binary encoding helpers for octree and edit save data.
+//!
+//! Integers use zigzag + LEB128 varints, floats are little-endian. Reading
+//! never panics: malformed or truncated input yields `None`.
+
+/// Append an unsigned LEB128 varint.
+pub(crate) fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+  while value >= 0x80 {
+    out.push((value as u8) | 0x80);
+    value >>= 7;
+  }
+  out.push(value as u8);
+}
+
+/// Append a signed integer as a zigzag varint (small magnitudes stay short).
+pub(crate) fn write_signed(out: &mut Vec<u8>, value: i32) {
+  let zigzag = ((value << 1) ^ (value >> 31)) as u32;
+  write_varint(out, zigzag as u64);
+}
+
+/// Append a little-endian f64.
+pub(crate) fn write_f64(out: &mut Vec<u8>, value: f64) {
+  out.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Cursor over an encoded buffer.
+pub(crate) struct Reader<'a> {
+  bytes: &'a [u8],
+  pos: usize,
+}
+
+impl<'a> Reader<'a> {
+  pub(crate) fn new(bytes: &'a [u8]) -> Self {
+    Self { bytes, pos: 0 }
+  }
+
+  /// Bytes not yet consumed.
+  pub(crate) fn remaining(&self) -> usize {
+    self.bytes.len() - self.pos
+  }
+
+  pub(crate) fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+    let end = self.pos.checked_add(len)?;
+    let slice = self.bytes.get(self.pos..end)?;
+    self.pos = end;
+    Some(slice)
+  }
+
+  pub(crate) fn read_u8(&mut self) -> Option<u8> {
+    self.read_bytes(1).map(|b| b[0])
+  }
+
+  pub(crate) fn read_varint(&mut self) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+      let byte = self.read_u8()?;
+      value |= ((byte & 0x7f) as u64) << shift;
+      if byte & 0x80 == 0 {
+        return Some(value);
+      }
+    }
+    // More than 10 bytes: not a valid u64 varint
+    None
+  }
+
+  pub(crate) fn read_signed(&mut self) -> Option<i32> {
+    let zigzag = u32::try_from(self.read_varint()?).ok()?;
+    Some(((zigzag >> 1) as i32) ^ -((zigzag & 1) as i32))
+  }
+
+  pub(crate) fn read_f64(&mut self) -> Option<f64> {
+    let bytes = self.read_bytes(8)?;
+    Some(f64::from_le_bytes(bytes.try_into().ok()?))
+  }
+
+  pub(crate) fn read_dvec3(&mut self) -> Option<glam::DVec3> {
+    Some(glam::DVec3::new(
+      self.read_f64()?,
+      self.read_f64()?,
+      self.read_f64()?,
+    ))
+  }
+}
@@ -4,14 +4,18 @@
 use glam::DVec3;
 
 use super::bounds::DAabb3;
+use super::codec::{self, Reader};
 use super::OctreeNode;
 use crate::constants::INTERIOR_CELLS;
 
 /// Number of voxels per cell for world size calculations.
 pub const VOXELS_PER_CELL: usize = INTERIOR_CELLS; // 28
 
+/// Magic bytes at the start of an encoded config.
+const CONFIG_MAGIC: &[u8; 4] = b"OCTC";
+
 /// Configuration for octree refinement and world coordinate mapping.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct OctreeConfig {
 	/// Base voxel size in world units.
 	pub voxel_size: f64,
@@ -29,12 +33,20 @@ pub struct OctreeConfig {
 	/// threshold = cell_size * 2^lod_exponent
 	pub lod_exponent: f64,
 
+	/// Hysteresis band as a fraction of the threshold.
+	/// Subdivide below threshold * (1 - h), collapse at threshold * (1 + h),
+	/// so a viewer hovering at a threshold doesn't flip LODs every frame.
+	pub lod_hysteresis: f64,
+
 	/// Optional world bounds - nodes outside are ignored.
 	/// None = unbounded (backward compatible).
 	pub world_bounds: Option<DAabb3>,
 }
 
 impl OctreeConfig {
+	/// Default hysteresis band (±5% of the threshold).
+	pub const DEFAULT_LOD_HYSTERESIS: f64 = 0.05;
+
 	/// Calculate cell size at given LOD.
 	/// cell_size = voxel_size * VOXELS_PER_CELL * 2^LOD
 	#[inline]
@@ -58,6 +70,34 @@ impl OctreeConfig {
 		cell_size * lod_scale
 	}
 
+	/// Distance below which a node at `lod` subdivides.
+	#[inline]
+	pub fn get_subdivide_threshold(&self, lod: i32) -> f64 {
+		self.get_threshold(lod) * (1.0 - self.lod_hysteresis)
+	}
+
+	/// Distance at or above which a node at `lod` collapses its children.
+	#[inline]
+	pub fn get_collapse_threshold(&self, lod: i32) -> f64 {
+		self.get_threshold(lod) * (1.0 + self.lod_hysteresis)
+	}
+
+	/// LOD a node centered at `node_center` should have for `viewer_pos`.
+	///
+	/// Same distance-to-LOD mapping as `refine`: a node at `lod` subdivides
+	/// while its center is closer than `get_threshold(lod)`, so this is the
+	/// coarsest LOD whose threshold the distance reaches, clamped to
+	/// `[min_lod, max_lod]`. Ignores `lod_hysteresis`, so leaves produced by
+	/// `refine` can lag by one LOD right at a threshold.
+	pub fn desired_lod(&self, node_center: DVec3, viewer_pos: DVec3) -> i32 {
+		let distance = node_center.distance(viewer_pos);
+		let mut lod = self.max_lod;
+		while lod > self.min_lod && distance < self.get_threshold(lod) {
+			lod -= 1;
+		}
+		lod
+	}
+
 	/// Get world-space minimum corner of a node.
 	#[inline]
 	pub fn get_node_min(&self, node: &OctreeNode) -> DVec3 {
@@ -70,6 +110,35 @@ impl OctreeConfig {
 			)
 	}
 
+	/// Get the integer sample grid offset and sub-voxel phase of a node.
+	///
+	/// Samples are anchored on `world_origin` so every LOD samples the same
+	/// lattice: sample N lies at `(grid_offset + N) * voxel_size + phase`,
+	/// which equals `node_min + N * voxel_size` exactly. A coarse node's
+	/// samples therefore coincide with every other sample of its children.
+	#[inline]
+	pub fn get_sample_grid(&self, node: &OctreeNode) -> ([i64; 3], DVec3) {
+		let voxel_size = self.get_voxel_size(node.lod);
+		let origin_grid = (self.world_origin / voxel_size).round();
+		let cells = VOXELS_PER_CELL as i64;
+		let grid_offset = [
+			origin_grid.x as i64 + node.x as i64 * cells,
+			origin_grid.y as i64 + node.y as i64 * cells,
+			origin_grid.z as i64 + node.z as i64 * cells,
+		];
+		(grid_offset, self.get_sample_phase(node.lod))
+	}
+
+	/// Sub-voxel residual of `world_origin` relative to the LOD's voxel lattice.
+	///
+	/// Zero when the origin is a multiple of the LOD voxel size. Always within
+	/// `[-voxel_size / 2, voxel_size / 2]`.
+	#[inline]
+	pub fn get_sample_phase(&self, lod: i32) -> DVec3 {
+		let voxel_size = self.get_voxel_size(lod);
+		self.world_origin - (self.world_origin / voxel_size).round() * voxel_size
+	}
+
 	/// Get world-space center of a node.
 	#[inline]
 	pub fn get_node_center(&self, node: &OctreeNode) -> DVec3 {
@@ -77,6 +146,16 @@ impl OctreeConfig {
 		self.get_node_min(node) + DVec3::splat(cell_size * 0.5)
 	}
 
+	/// Get the node at `lod` whose cell contains `world_pos`.
+	///
+	/// Cells are half-open (`[min, min + cell_size)`), so a point on a shared
+	/// face belongs to the node on the positive side.
+	#[inline]
+	pub fn get_node_containing(&self, world_pos: DVec3, lod: i32) -> OctreeNode {
+		let (x, y, z) = self.world_to_grid(world_pos, lod);
+		OctreeNode::new(x, y, z, lod)
+	}
+
 	/// Get world-space AABB of a node.
 	#[inline]
 	pub fn get_node_aabb(&self, node: &OctreeNode) -> DAabb3 {
@@ -103,6 +182,8 @@ impl OctreeConfig {
 
 	/// Compute initial leaves that cover the world bounds at target LOD.
 	///
+	/// Bounds may be anisotropic: each axis is covered independently, so a
+	/// flat or elongated world seeds a rectangular grid rather than a cube.
 	/// Returns an empty vec if no world bounds are set.
 	pub fn compute_initial_leaves(&self, target_lod: i32) -> Vec<OctreeNode> {
 		let Some(bounds) = &self.world_bounds else {
@@ -132,7 +213,8 @@ impl OctreeConfig {
 	/// Suggest an initial LOD based on world bounds.
 	///
 	/// Tries to find an LOD where the world fits in a reasonable number of cells
-	/// (targeting roughly 2-4 cells per axis for the initial view).
+	/// (targeting roughly 2-4 cells along the longest axis for the initial
+	/// view). Shorter axes of anisotropic bounds get proportionally fewer cells.
 	pub fn suggest_initial_lod(&self) -> i32 {
 		let Some(bounds) = &self.world_bounds else {
 			return self.max_lod / 2; // Default fallback
@@ -158,6 +240,83 @@ impl OctreeConfig {
 		lod.clamp(self.min_lod, self.max_lod)
 	}
 
+	/// Current version of the config save format.
+	pub const FORMAT_VERSION: u8 = 1;
+
+	/// Encode the config, e.g. to store alongside saved leaves.
+	///
+	/// Layout: magic "OCTC", version, then every field in declaration order
+	/// (floats little-endian, LODs as zigzag varints, bounds behind a flag).
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut out = Vec::with_capacity(96);
+		out.extend_from_slice(CONFIG_MAGIC);
+		out.push(Self::FORMAT_VERSION);
+		codec::write_f64(&mut out, self.voxel_size);
+		for axis in self.world_origin.to_array() {
+			codec::write_f64(&mut out, axis);
+		}
+		codec::write_signed(&mut out, self.min_lod);
+		codec::write_signed(&mut out, self.max_lod);
+		codec::write_f64(&mut out, self.lod_exponent);
+		codec::write_f64(&mut out, self.lod_hysteresis);
+		match &self.world_bounds {
+			None => out.push(0),
+			Some(bounds) => {
+				out.push(1);
+				for axis in bounds.min.to_array().into_iter().chain(bounds.max.to_array()) {
+					codec::write_f64(&mut out, axis);
+				}
+			}
+		}
+		out
+	}
+
+	/// Decode a config written by [`to_bytes`](Self::to_bytes).
+	///
+	/// Returns `None` for a wrong magic or version, malformed data, or
+	/// trailing bytes.
+	pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+		let mut reader = Reader::new(bytes);
+		if reader.read_bytes(CONFIG_MAGIC.len())? != CONFIG_MAGIC {
+			return None;
+		}
+		if reader.read_u8()? != Self::FORMAT_VERSION {
+			return None;
+		}
+
+		let voxel_size = reader.read_f64()?;
+		let world_origin = reader.read_dvec3()?;
+		let min_lod = reader.read_signed()?;
+		let max_lod = reader.read_signed()?;
+		let lod_exponent = reader.read_f64()?;
+		let lod_hysteresis = reader.read_f64()?;
+		let world_bounds = match reader.read_u8()? {
+			0 => None,
+			1 => {
+				let min = reader.read_dvec3()?;
+				let max = reader.read_dvec3()?;
+				if !min.cmple(max).all() {
+					return None;
+				}
+				Some(DAabb3::new(min, max))
+			}
+			_ => return None,
+		};
+
+		if reader.remaining() != 0 {
+			return None;
+		}
+		Some(Self {
+			voxel_size,
+			world_origin,
+			min_lod,
+			max_lod,
+			lod_exponent,
+			lod_hysteresis,
+			world_bounds,
+		})
+	}
+
 	/// Convert world position to grid coordinates at given LOD.
 	fn world_to_grid(&self, world_pos: DVec3, lod: i32) -> (i32, i32, i32) {
 		let cell_size = self.get_cell_size(lod);
@@ -178,6 +337,7 @@ impl Default for OctreeConfig {
 			min_lod: 0,
 			max_lod: 30,
 			lod_exponent: 0.0,
+			lod_hysteresis: Self::DEFAULT_LOD_HYSTERESIS,
 			world_bounds: None,
 		}
 	}
@@ -94,6 +94,22 @@ fn test_lod_threshold_with_exponent() {
   );
 }
 
+/// Hysteresis splits the threshold into a subdivide/collapse band.
+#[test]
+fn test_hysteresis_band_around_threshold() {
+  let mut config = OctreeConfig::default();
+  config.lod_hysteresis = 0.1;
+
+  let threshold = config.get_threshold(2);
+  assert!((config.get_subdivide_threshold(2) - threshold * 0.9).abs() < 1e-9);
+  assert!((config.get_collapse_threshold(2) - threshold * 1.1).abs() < 1e-9);
+
+  // No hysteresis - both match the plain threshold
+  config.lod_hysteresis = 0.0;
+  assert_eq!(config.get_subdivide_threshold(2), threshold);
+  assert_eq!(config.get_collapse_threshold(2), threshold);
+}
+
 /// Different exponents produce different thresholds.
 #[test]
 fn test_lod_exponent_affects_threshold() {
@@ -121,3 +137,55 @@ fn test_lod_exponent_affects_threshold() {
     "Exponent 2 quadruples threshold"
   );
 }
+
+/// Config survives a save/load round trip, including world bounds.
+#[test]
+fn test_config_bytes_round_trip() {
+  let config = OctreeConfig {
+    voxel_size: 0.5,
+    world_origin: DVec3::new(-100.0, 2.5, 1e9),
+    min_lod: 1,
+    max_lod: 12,
+    lod_exponent: 1.5,
+    lod_hysteresis: 0.1,
+    world_bounds: Some(DAabb3::new(DVec3::splat(-500.0), DVec3::splat(500.0))),
+  };
+
+  assert_eq!(OctreeConfig::from_bytes(&config.to_bytes()), Some(config));
+  let unbounded = OctreeConfig::default();
+  assert_eq!(OctreeConfig::from_bytes(&unbounded.to_bytes()), Some(unbounded));
+}
+
+/// Corrupt or foreign config data is rejected without panicking.
+#[test]
+fn test_config_bytes_rejects_bad_input() {
+  let mut bytes = OctreeConfig::default().to_bytes();
+  assert!(OctreeConfig::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+
+  bytes[4] = OctreeConfig::FORMAT_VERSION + 1;
+  assert!(OctreeConfig::from_bytes(&bytes).is_none());
+  assert!(OctreeConfig::from_bytes(&[]).is_none());
+}
+
+/// Anisotropic (4:1:4) bounds seed a flat rectangular grid, not a cube.
+#[test]
+fn test_initial_leaves_non_cubic_bounds() {
+  // 4 × 1 × 4 cells at LOD 0, inset by 1 so no node merely touches a face
+  let config = OctreeConfig {
+    world_bounds: Some(DAabb3::new(
+      DVec3::splat(1.0),
+      DVec3::new(4.0 * 28.0 - 1.0, 28.0 - 1.0, 4.0 * 28.0 - 1.0),
+    )),
+    ..OctreeConfig::default()
+  };
+
+  let lod = config.suggest_initial_lod();
+  assert_eq!(lod, 0);
+
+  let leaves: std::collections::HashSet<_> =
+    config.compute_initial_leaves(lod).into_iter().collect();
+  let expected: std::collections::HashSet<_> = (0..4)
+    .flat_map(|x| (0..4).map(move |z| OctreeNode::new(x, 0, z, 0)))
+    .collect();
+  assert_eq!(leaves, expected);
+}
@@ -0,0 +1,107 @@
+//! View frustum with double precision for huge worlds.
+
+use glam::{DMat4, DVec3, DVec4};
+
+use super::DAabb3;
+
+/// View frustum described by 6 inward-facing planes.
+///
+/// Each plane is `(normal.x, normal.y, normal.z, d)`; a point `p` is on the
+/// inside when `normal · p + d >= 0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frustum {
+	/// Left, right, bottom, top, near, far.
+	pub planes: [DVec4; 6],
+}
+
+impl Frustum {
+	/// Create a frustum from 6 inward-facing planes.
+	pub fn new(planes: [DVec4; 6]) -> Self {
+		Self { planes }
+	}
+
+	/// Extract the frustum from a view-projection matrix.
+	///
+	/// Assumes a `[0, 1]` clip-space depth range (wgpu / Bevy / Unity HDRP
+	/// conventions). Planes are normalized.
+	pub fn from_view_projection(view_proj: DMat4) -> Self {
+		let r0 = view_proj.row(0);
+		let r1 = view_proj.row(1);
+		let r2 = view_proj.row(2);
+		let r3 = view_proj.row(3);
+
+		let planes = [r3 + r0, r3 - r0, r3 + r1, r3 - r1, r2, r3 - r2].map(|plane| {
+			let length = plane.truncate().length();
+			if length > 0.0 {
+				plane / length
+			} else {
+				plane
+			}
+		});
+
+		Self { planes }
+	}
+
+	/// Check if an AABB lies fully outside at least one plane.
+	///
+	/// Conservative: boxes near frustum corners may be reported as visible.
+	#[inline]
+	pub fn is_aabb_outside(&self, aabb: &DAabb3) -> bool {
+		self.planes.iter().any(|plane| {
+			// Corner furthest along the plane normal
+			let normal = plane.truncate();
+			let corner = DVec3::select(normal.cmpge(DVec3::ZERO), aabb.max, aabb.min);
+			normal.dot(corner) + plane.w < 0.0
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Axis-aligned box frustum spanning `[-10, 10]³`.
+	fn box_frustum() -> Frustum {
+		Frustum::new([
+			DVec4::new(1.0, 0.0, 0.0, 10.0),
+			DVec4::new(-1.0, 0.0, 0.0, 10.0),
+			DVec4::new(0.0, 1.0, 0.0, 10.0),
+			DVec4::new(0.0, -1.0, 0.0, 10.0),
+			DVec4::new(0.0, 0.0, 1.0, 10.0),
+			DVec4::new(0.0, 0.0, -1.0, 10.0),
+		])
+	}
+
+	#[test]
+	fn test_aabb_inside() {
+		let aabb = DAabb3::new(DVec3::splat(-1.0), DVec3::splat(1.0));
+		assert!(!box_frustum().is_aabb_outside(&aabb));
+	}
+
+	#[test]
+	fn test_aabb_straddling() {
+		let aabb = DAabb3::new(DVec3::splat(5.0), DVec3::splat(15.0));
+		assert!(!box_frustum().is_aabb_outside(&aabb));
+	}
+
+	#[test]
+	fn test_aabb_outside() {
+		let aabb = DAabb3::new(DVec3::new(11.0, 0.0, 0.0), DVec3::new(20.0, 1.0, 1.0));
+		assert!(box_frustum().is_aabb_outside(&aabb));
+	}
+
+	#[test]
+	fn test_from_view_projection_orthographic() {
+		// Orthographic view of [-10, 10]² looking down -Z, depth 0.1..100
+		let proj = DMat4::orthographic_rh(-10.0, 10.0, -10.0, 10.0, 0.1, 100.0);
+		let frustum = Frustum::from_view_projection(proj);
+
+		let in_front = DAabb3::new(DVec3::new(-1.0, -1.0, -6.0), DVec3::new(1.0, 1.0, -4.0));
+		let behind = DAabb3::new(DVec3::new(-1.0, -1.0, 4.0), DVec3::new(1.0, 1.0, 6.0));
+		let beside = DAabb3::new(DVec3::new(20.0, -1.0, -6.0), DVec3::new(22.0, 1.0, -4.0));
+
+		assert!(!frustum.is_aabb_outside(&in_front));
+		assert!(frustum.is_aabb_outside(&behind));
+		assert!(frustum.is_aabb_outside(&beside));
+	}
+}
@@ -2,10 +2,23 @@
 //!
 //! The tree structure is implicit: parent/child relationships are computed
 //! on-demand via coordinate math. Only leaves are stored.
+//!
+//! # Save Format
+//!
+//! ```text
+//! magic "OCTL" | version u8 | count varint | count × (lod, x, y, z)
+//! ```
+//!
+//! Coordinates are zigzag varints, nodes sorted by `(lod, x, y, z)` so the
+//! same set always encodes to the same bytes.
 
 use std::collections::HashSet;
 
-use super::OctreeNode;
+use super::codec::{self, Reader};
+use super::{OctreeConfig, OctreeNode};
+
+/// Magic bytes at the start of encoded leaves.
+const LEAVES_MAGIC: &[u8; 4] = b"OCTL";
 
 /// Implicit octree - leaves ARE the state.
 ///
@@ -68,6 +81,65 @@ impl OctreeLeaves {
   pub fn as_set(&self) -> &HashSet<OctreeNode> {
     &self.leaves
   }
+
+  /// Current version of the leaves save format.
+  pub const FORMAT_VERSION: u8 = 1;
+
+  /// Encode the leaves for saving.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut nodes: Vec<_> = self.leaves.iter().copied().collect();
+    nodes.sort_unstable_by_key(|n| (n.lod, n.x, n.y, n.z));
+
+    let mut out = Vec::with_capacity(8 + nodes.len() * 4);
+    out.extend_from_slice(LEAVES_MAGIC);
+    out.push(Self::FORMAT_VERSION);
+    codec::write_varint(&mut out, nodes.len() as u64);
+    for node in nodes {
+      codec::write_signed(&mut out, node.lod);
+      codec::write_signed(&mut out, node.x);
+      codec::write_signed(&mut out, node.y);
+      codec::write_signed(&mut out, node.z);
+    }
+    out
+  }
+
+  /// Decode leaves written by [`to_bytes`](Self::to_bytes).
+  ///
+  /// Returns `None` for a wrong magic or version, truncated data, or
+  /// trailing bytes.
+  pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+    let mut reader = Reader::new(bytes);
+    if reader.read_bytes(LEAVES_MAGIC.len())? != LEAVES_MAGIC {
+      return None;
+    }
+    if reader.read_u8()? != Self::FORMAT_VERSION {
+      return None;
+    }
+
+    let count = usize::try_from(reader.read_varint()?).ok()?;
+    // Each node takes at least 4 bytes; don't trust `count` for allocation
+    let mut leaves = HashSet::with_capacity(count.min(reader.remaining() / 4));
+    for _ in 0..count {
+      let lod = reader.read_signed()?;
+      let x = reader.read_signed()?;
+      let y = reader.read_signed()?;
+      let z = reader.read_signed()?;
+      leaves.insert(OctreeNode::new(x, y, z, lod));
+    }
+
+    if reader.remaining() != 0 {
+      return None;
+    }
+    Some(Self { leaves })
+  }
+
+  /// Check that every leaf is within the config's LOD range and world
+  /// bounds (e.g. after loading leaves saved with a different config).
+  pub fn is_valid_for(&self, config: &OctreeConfig) -> bool {
+    self.leaves.iter().all(|node| {
+      (config.min_lod..=config.max_lod).contains(&node.lod) && config.node_overlaps_bounds(node)
+    })
+  }
 }
 
 impl Default for OctreeLeaves {
@@ -23,3 +23,55 @@ fn test_single_leaf_at_min_lod_no_subdivide() {
   assert_eq!(leaf.lod, 0);
   assert!(leaf.get_child(0).is_none(), "LOD 0 node cannot subdivide");
 }
+
+// =========================================================================
+// Save / Load
+// =========================================================================
+
+/// A few thousand leaves survive a save/load round trip.
+#[test]
+fn test_leaves_bytes_round_trip() {
+  let mut leaves = OctreeLeaves::new();
+  for i in 0..4000 {
+    leaves.insert(OctreeNode::new(i % 37 - 18, i / 37 - 50, (i * 7) % 101 - 3000, i % 6));
+  }
+  leaves.insert(OctreeNode::new(i32::MIN, i32::MAX, 0, 30));
+
+  let bytes = leaves.to_bytes();
+  let loaded = OctreeLeaves::from_bytes(&bytes).expect("round trip should decode");
+
+  assert_eq!(loaded.as_set(), leaves.as_set());
+  assert_eq!(loaded.to_bytes(), bytes, "Encoding should be deterministic");
+}
+
+/// A version mismatch returns None rather than panicking.
+#[test]
+fn test_leaves_bytes_version_mismatch() {
+  let mut bytes = OctreeLeaves::new_with_initial(5).to_bytes();
+  bytes[4] = OctreeLeaves::FORMAT_VERSION + 1;
+  assert!(OctreeLeaves::from_bytes(&bytes).is_none());
+}
+
+/// Truncated or garbage data returns None rather than panicking.
+#[test]
+fn test_leaves_bytes_rejects_bad_input() {
+  let bytes = OctreeLeaves::new_with_initial(5).to_bytes();
+  for len in 0..bytes.len() {
+    assert!(OctreeLeaves::from_bytes(&bytes[..len]).is_none());
+  }
+  assert!(OctreeLeaves::from_bytes(&[0xff; 64]).is_none());
+}
+
+/// Loaded leaves can be checked against the config they'll be used with.
+#[test]
+fn test_leaves_valid_for_config() {
+  let leaves = OctreeLeaves::new_with_initial(5);
+  let config = OctreeConfig::default();
+  assert!(leaves.is_valid_for(&config));
+
+  let shallow = OctreeConfig {
+    max_lod: 4,
+    ..OctreeConfig::default()
+  };
+  assert!(!leaves.is_valid_for(&shallow));
+}
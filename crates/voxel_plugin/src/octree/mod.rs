@@ -25,7 +25,9 @@
 
 pub mod bounds;
 pub mod budget;
+pub(crate) mod codec;
 pub mod config;
+pub mod frustum;
 pub mod leaves;
 pub mod node;
 pub mod refinement;
@@ -35,9 +37,10 @@ pub mod transition;
 pub use bounds::DAabb3;
 pub use budget::{RefinementBudget, RefinementStats};
 pub use config::OctreeConfig;
+pub use frustum::Frustum;
 pub use leaves::OctreeLeaves;
 pub use node::OctreeNode;
-pub use refinement::{refine, RefinementInput, RefinementOutput};
+pub use refinement::{refine, refine_multi, RefinementInput, RefinementOutput};
 pub use transition::{TransitionGroup, TransitionType};
 
 #[cfg(test)]
@@ -17,18 +17,37 @@
 //! To prevent T-junction artifacts at LOD boundaries, the algorithm enforces
 //! a maximum LOD difference between adjacent nodes. By default, adjacent nodes
 //! can differ by at most 1 LOD level.
+//!
+//! # Hysteresis
+//!
+//! Subdivision and collapse use thresholds on either side of
+//! `OctreeConfig::get_threshold` (see `lod_hysteresis`), so a viewer hovering
+//! at a boundary doesn't cause subdivide/collapse thrashing.
+//!
+//! # Frustum Hint
+//!
+//! With `view_frustum` set, transition groups whose bounds lie fully outside
+//! the frustum are flagged `culled`. Culling never changes which leaves are
+//! produced - refinement stays purely distance-based.
+//!
+//! # Multiple Viewers
+//!
+//! [`refine_multi`] uses the distance to the nearest of several viewers
+//! wherever [`refine`] uses the distance to the single viewer.
 
 use std::collections::HashSet;
 
 use glam::DVec3;
 
 use super::budget::{RefinementBudget, RefinementStats};
-use super::{OctreeConfig, OctreeNode, TransitionGroup};
+use super::{Frustum, OctreeConfig, OctreeNode, TransitionGroup};
 
 /// Input for refinement calculation.
 pub struct RefinementInput {
   /// Viewer position in world space (double precision for huge worlds).
   pub viewer_pos: DVec3,
+  /// Optional view frustum used to flag culled transition groups.
+  pub view_frustum: Option<Frustum>,
   /// Octree configuration with LOD thresholds.
   pub config: OctreeConfig,
   /// Current set of leaf nodes.
@@ -240,9 +259,19 @@ fn enforce_neighbor_gradation(
   neighbor_subdivisions
 }
 
+/// Distance from `point` to the nearest viewer.
+#[inline]
+fn nearest_viewer_distance_squared(viewers: &[DVec3], point: DVec3) -> f64 {
+  viewers
+    .iter()
+    .map(|viewer| viewer.distance_squared(point))
+    .fold(f64::INFINITY, f64::min)
+}
+
 /// Main refinement function.
 ///
 /// Determines which nodes to subdivide or merge based on viewer distance.
+/// Equivalent to [`refine_multi`] with no additional viewers.
 ///
 /// # Algorithm Phases
 ///
@@ -254,8 +283,37 @@ fn enforce_neighbor_gradation(
 /// 4. **Apply collapses**: Shed distant load first (budget-limited)
 /// 5. **Apply subdivisions**: Add nearby detail (budget-limited)
 /// 6. **Enforce neighbors**: Fix LOD gradation to prevent T-junctions
-#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, name = "octree::refine"))]
+#[cfg_attr(
+  feature = "tracing",
+  tracing::instrument(skip_all, name = "octree::refine", fields(leaves = input.prev_leaves.len()))
+)]
 pub fn refine(input: RefinementInput) -> RefinementOutput {
+  refine_multi(input, &[])
+}
+
+/// Refinement for several viewers (split-screen, spectators).
+///
+/// `input.viewer_pos` and every position in `additional_viewers` are treated
+/// equally: each node's desired LOD comes from its nearest viewer, so the tree
+/// stays fine around all of them. The budget caps subdivisions and collapses
+/// across the combined set, nearest-to-any-viewer first.
+#[cfg_attr(
+  feature = "tracing",
+  tracing::instrument(
+    skip_all,
+    name = "octree::refine_multi",
+    fields(leaves = input.prev_leaves.len(), viewers = additional_viewers.len() + 1)
+  )
+)]
+pub fn refine_multi(input: RefinementInput, additional_viewers: &[DVec3]) -> RefinementOutput {
+  let viewers: smallvec::SmallVec<[DVec3; 4]> = std::iter::once(input.viewer_pos)
+    .chain(additional_viewers.iter().copied())
+    .collect();
+  let viewer_distance_sq = |point: DVec3| nearest_viewer_distance_squared(&viewers, point);
+  // WASM compat: std::time::Instant panics on wasm32
+  let start = web_time::Instant::now();
+  let has_time = |budget: &RefinementBudget| budget.has_time(start.elapsed().as_millis() as u64);
+
   let mut next_leaves = input.prev_leaves.clone();
   let mut to_subdivide: Vec<OctreeNode> = Vec::new();
   let mut coarsen_candidates: HashSet<OctreeNode> = HashSet::new();
@@ -274,8 +332,8 @@ pub fn refine(input: RefinementInput) -> RefinementOutput {
       // Check subdivision (LOD > MinLOD)
       if node.lod > input.config.min_lod {
         let center = input.config.get_node_center(node);
-        let dist = input.viewer_pos.distance(center);
-        let threshold = input.config.get_threshold(node.lod);
+        let dist = viewer_distance_sq(center).sqrt();
+        let threshold = input.config.get_subdivide_threshold(node.lod);
 
         if dist < threshold {
           to_subdivide.push(*node);
@@ -287,8 +345,8 @@ pub fn refine(input: RefinementInput) -> RefinementOutput {
       if node.lod < input.config.max_lod {
         if let Some(parent) = node.get_parent(input.config.max_lod) {
           let parent_center = input.config.get_node_center(&parent);
-          let parent_dist = input.viewer_pos.distance(parent_center);
-          let parent_threshold = input.config.get_threshold(parent.lod);
+          let parent_dist = viewer_distance_sq(parent_center).sqrt();
+          let parent_threshold = input.config.get_collapse_threshold(parent.lod);
 
           if parent_dist >= parent_threshold {
             coarsen_candidates.insert(parent);
@@ -310,7 +368,6 @@ pub fn refine(input: RefinementInput) -> RefinementOutput {
 
   // Phase 3: Sort by priority
   let config = &input.config;
-  let viewer_pos = input.viewer_pos;
 
   let mut to_subdivide = to_subdivide;
   let mut valid_coarsen = valid_coarsen;
@@ -319,15 +376,15 @@ pub fn refine(input: RefinementInput) -> RefinementOutput {
     let _span = tracing::info_span!("sort_by_priority").entered();
     // Subdivisions: closest first (highest priority)
     to_subdivide.sort_by(|a, b| {
-      let da = viewer_pos.distance_squared(config.get_node_center(a));
-      let db = viewer_pos.distance_squared(config.get_node_center(b));
+      let da = viewer_distance_sq(config.get_node_center(a));
+      let db = viewer_distance_sq(config.get_node_center(b));
       da.partial_cmp(&db).unwrap()
     });
 
     // Collapses: farthest first (shed distant load)
     valid_coarsen.sort_by(|a, b| {
-      let da = viewer_pos.distance_squared(config.get_node_center(a));
-      let db = viewer_pos.distance_squared(config.get_node_center(b));
+      let da = viewer_distance_sq(config.get_node_center(a));
+      let db = viewer_distance_sq(config.get_node_center(b));
       db.partial_cmp(&da).unwrap() // Reversed!
     });
   }
@@ -339,7 +396,7 @@ pub fn refine(input: RefinementInput) -> RefinementOutput {
     #[cfg(feature = "tracing")]
     let _span = tracing::info_span!("apply_collapses").entered();
     for parent in valid_coarsen.into_iter() {
-      if !input.budget.can_collapse(stats.collapses_performed) {
+      if !input.budget.can_collapse(stats.collapses_performed) || !has_time(&input.budget) {
         break;
       }
       apply_merge(&parent, &mut next_leaves, &mut transition_groups);
@@ -352,7 +409,7 @@ pub fn refine(input: RefinementInput) -> RefinementOutput {
     #[cfg(feature = "tracing")]
     let _span = tracing::info_span!("apply_subdivisions").entered();
     for node in to_subdivide.into_iter() {
-      if !input.budget.can_subdivide(stats.subdivisions_performed) {
+      if !input.budget.can_subdivide(stats.subdivisions_performed) || !has_time(&input.budget) {
         break;
       }
       // Skip if already removed by a collapse
@@ -378,11 +435,17 @@ pub fn refine(input: RefinementInput) -> RefinementOutput {
 
   // Sort transition groups by proximity (for presentation priority)
   transition_groups.sort_by(|a, b| {
-    let da = viewer_pos.distance_squared(config.get_node_center(&a.group_key));
-    let db = viewer_pos.distance_squared(config.get_node_center(&b.group_key));
+    let da = viewer_distance_sq(config.get_node_center(&a.group_key));
+    let db = viewer_distance_sq(config.get_node_center(&b.group_key));
     da.partial_cmp(&db).unwrap()
   });
 
+  if let Some(frustum) = &input.view_frustum {
+    for group in &mut transition_groups {
+      group.culled = frustum.is_aabb_outside(&config.get_node_aabb(&group.group_key));
+    }
+  }
+
   RefinementOutput {
     next_leaves,
     transition_groups,
@@ -1,5 +1,6 @@
 use super::*;
 use crate::octree::TransitionType;
+use glam::DVec4;
 
 // =========================================================================
 // Batch 6: Refinement Core Tests
@@ -172,6 +173,7 @@ fn test_subdivide_priority_closest_first() {
   // Viewer at origin - near_node is closer
   let input = RefinementInput {
     viewer_pos: DVec3::ZERO,
+    view_frustum: None,
     config,
     prev_leaves: leaves,
     budget: RefinementBudget {
@@ -215,6 +217,7 @@ fn test_separate_budgets_enforced() {
 
   let input = RefinementInput {
     viewer_pos: DVec3::ZERO,
+    view_frustum: None,
     config,
     prev_leaves: leaves,
     budget: RefinementBudget {
@@ -253,6 +256,7 @@ fn test_stats_tracking() {
 
   let input = RefinementInput {
     viewer_pos: DVec3::ZERO,
+    view_frustum: None,
     config,
     prev_leaves: leaves,
     budget: RefinementBudget::UNLIMITED,
@@ -289,6 +293,7 @@ fn test_viewer_very_far_collapses_to_coarse() {
   // Viewer very far away - should want to merge
   let input = RefinementInput {
     viewer_pos: DVec3::new(100000.0, 100000.0, 100000.0),
+    view_frustum: None,
     config,
     prev_leaves: leaves,
     budget: RefinementBudget::UNLIMITED,
@@ -314,6 +319,7 @@ fn test_viewer_at_node_center_subdivides() {
 
   let input = RefinementInput {
     viewer_pos: center,
+    view_frustum: None,
     config,
     prev_leaves: leaves,
     budget: RefinementBudget::UNLIMITED,
@@ -350,6 +356,7 @@ fn test_neighbor_enforcement_disabled() {
 
   let input = RefinementInput {
     viewer_pos: DVec3::ZERO,
+    view_frustum: None,
     config,
     prev_leaves: leaves,
     budget: RefinementBudget::NO_NEIGHBOR_ENFORCEMENT,
@@ -457,6 +464,7 @@ fn test_neighbor_enforcement_skips_boundary_nodes_with_partial_children() {
 		min_lod: 0,
 		max_lod: 20,
 		lod_exponent: 0.0,
+		lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
 		// Bounds start at x=0, so nodes with negative x are partially out of bounds
 		world_bounds: Some(DAabb3::new(
 			DVec3::new(0.0, 0.0, 0.0),
@@ -481,6 +489,7 @@ fn test_neighbor_enforcement_skips_boundary_nodes_with_partial_children() {
 
 	let input = RefinementInput {
 		viewer_pos: DVec3::new(50.0, 50.0, 50.0),
+		view_frustum: None,
 		config,
 		prev_leaves: leaves,
 		budget: RefinementBudget {
@@ -488,6 +497,7 @@ fn test_neighbor_enforcement_skips_boundary_nodes_with_partial_children() {
 			max_collapses: 0,
 			max_relative_lod: 1,
 			max_neighbor_iterations: 50,
+			max_millis: 0,
 		},
 	};
 
@@ -527,6 +537,7 @@ fn test_apply_subdivide_produces_partial_children_at_boundary() {
 		min_lod: 0,
 		max_lod: 20,
 		lod_exponent: 0.0,
+		lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
 		world_bounds: Some(DAabb3::new(
 			DVec3::new(0.0, 0.0, 0.0),
 			DVec3::new(1000.0, 1000.0, 1000.0),
@@ -580,6 +591,7 @@ fn test_boundary_subdivision_does_not_explode() {
 		min_lod: 0,
 		max_lod: 20,
 		lod_exponent: 2.0, // Higher exponent = narrower LOD bands
+		lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
 		world_bounds: Some(DAabb3::new(
 			DVec3::new(0.0, 0.0, 0.0),
 			DVec3::new(50000.0, 50000.0, 50000.0),
@@ -601,6 +613,7 @@ fn test_boundary_subdivision_does_not_explode() {
 
 	let input = RefinementInput {
 		viewer_pos: DVec3::new(14.0, 14.0, 14.0), // Viewer at fine node center
+		view_frustum: None,
 		config,
 		prev_leaves: leaves,
 		budget: RefinementBudget {
@@ -609,6 +622,7 @@ fn test_boundary_subdivision_does_not_explode() {
 			max_relative_lod: 1,
 			// Allow enough iterations to see the full cascade
 			max_neighbor_iterations: 20,
+			max_millis: 0,
 		},
 	};
 
@@ -643,3 +657,252 @@ fn test_boundary_subdivision_does_not_explode() {
 		output.stats.neighbor_subdivisions_performed
 	);
 }
+
+// =========================================================================
+// Multi-Viewer Refinement
+// =========================================================================
+
+/// A row of LOD 3 nodes along +X with viewers at the first and last node.
+fn multi_viewer_setup() -> (OctreeConfig, HashSet<OctreeNode>, DVec3, DVec3) {
+  let config = OctreeConfig::default();
+  let leaves: HashSet<_> = (0..8).map(|x| OctreeNode::new(x, 0, 0, 3)).collect();
+  let left = config.get_node_center(&OctreeNode::new(0, 0, 0, 3));
+  let right = config.get_node_center(&OctreeNode::new(7, 0, 0, 3));
+  (config, leaves, left, right)
+}
+
+/// Two viewers on opposite sides refine both ends; the middle stays coarse.
+#[test]
+fn test_refine_multi_subdivides_near_each_viewer() {
+  let (config, leaves, left, right) = multi_viewer_setup();
+
+  let input = RefinementInput {
+    viewer_pos: left,
+    view_frustum: None,
+    config,
+    prev_leaves: leaves,
+    budget: RefinementBudget::UNLIMITED,
+  };
+
+  let output = refine_multi(input, &[right]);
+
+  assert!(
+    !output.next_leaves.contains(&OctreeNode::new(0, 0, 0, 3)),
+    "Node under the first viewer should subdivide"
+  );
+  assert!(
+    !output.next_leaves.contains(&OctreeNode::new(7, 0, 0, 3)),
+    "Node under the second viewer should subdivide"
+  );
+  for x in 3..=4 {
+    assert!(
+      output.next_leaves.contains(&OctreeNode::new(x, 0, 0, 3)),
+      "Middle node {} should stay coarse",
+      x
+    );
+  }
+}
+
+/// Without the second viewer, the far end is left alone.
+#[test]
+fn test_refine_single_viewer_ignores_far_end() {
+  let (config, leaves, left, _) = multi_viewer_setup();
+
+  let input = RefinementInput {
+    viewer_pos: left,
+    view_frustum: None,
+    config,
+    prev_leaves: leaves,
+    budget: RefinementBudget::UNLIMITED,
+  };
+
+  let output = refine(input);
+
+  assert!(!output.next_leaves.contains(&OctreeNode::new(0, 0, 0, 3)));
+  assert!(output.next_leaves.contains(&OctreeNode::new(7, 0, 0, 3)));
+}
+
+/// The subdivision budget is shared across all viewers.
+#[test]
+fn test_refine_multi_budget_spans_viewers() {
+  let (config, leaves, left, right) = multi_viewer_setup();
+
+  let input = RefinementInput {
+    viewer_pos: left,
+    view_frustum: None,
+    config,
+    prev_leaves: leaves,
+    budget: RefinementBudget {
+      max_subdivisions: 1,
+      ..RefinementBudget::NO_NEIGHBOR_ENFORCEMENT
+    },
+  };
+
+  let output = refine_multi(input, &[right]);
+
+  assert_eq!(output.stats.subdivisions_performed, 1);
+  let remaining = [0, 7]
+    .iter()
+    .filter(|&&x| output.next_leaves.contains(&OctreeNode::new(x, 0, 0, 3)))
+    .count();
+  assert_eq!(remaining, 1, "Exactly one viewer's node should subdivide");
+}
+
+// =========================================================================
+// Hysteresis
+// =========================================================================
+
+/// Sweep a viewer slowly outward across a node's threshold, jittering back
+/// and forth around it. Returns the number of transitions produced.
+fn sweep_across_threshold(lod_hysteresis: f64) -> usize {
+  let config = OctreeConfig {
+    lod_hysteresis,
+    ..OctreeConfig::default()
+  };
+  let parent = OctreeNode::new(0, 0, 0, 3);
+  let center = config.get_node_center(&parent);
+  let threshold = config.get_threshold(parent.lod);
+
+  // Start subdivided, viewer inside the threshold
+  let mut leaves: HashSet<_> = (0..8u8).filter_map(|o| parent.get_child(o)).collect();
+  let mut transitions = 0;
+
+  const STEPS: usize = 40;
+  for step in 0..STEPS {
+    let sweep = 0.97 + 0.06 * step as f64 / (STEPS - 1) as f64;
+    let jitter = if step % 2 == 0 { -0.02 } else { 0.02 };
+    let distance = threshold * (sweep + jitter);
+
+    let output = refine(RefinementInput {
+      viewer_pos: center + DVec3::X * distance,
+      view_frustum: None,
+      config: config.clone(),
+      prev_leaves: leaves,
+      budget: RefinementBudget::UNLIMITED,
+    });
+    transitions += output.transition_groups.len();
+    leaves = output.next_leaves;
+  }
+
+  transitions
+}
+
+/// A viewer hovering at a threshold transitions at most once.
+#[test]
+fn test_hysteresis_prevents_thrashing() {
+  let transitions = sweep_across_threshold(OctreeConfig::DEFAULT_LOD_HYSTERESIS);
+  assert!(
+    transitions <= 1,
+    "Expected at most one transition, got {}",
+    transitions
+  );
+}
+
+/// Without hysteresis the same sweep flips back and forth.
+#[test]
+fn test_no_hysteresis_thrashes() {
+  let transitions = sweep_across_threshold(0.0);
+  assert!(
+    transitions > 1,
+    "Expected repeated flips without hysteresis, got {}",
+    transitions
+  );
+}
+
+// =========================================================================
+// Desired LOD
+// =========================================================================
+
+/// `desired_lod` agrees with refinement: a near node maps to a finer LOD and
+/// subdivides, a far node maps to a coarse LOD and stays.
+#[test]
+fn test_desired_lod_matches_refinement() {
+  let config = OctreeConfig {
+    lod_hysteresis: 0.0,
+    ..OctreeConfig::default()
+  };
+  let node = OctreeNode::new(0, 0, 0, 5);
+  let center = config.get_node_center(&node);
+
+  let subdivides = |viewer_pos: DVec3| {
+    let output = refine(RefinementInput {
+      viewer_pos,
+      view_frustum: None,
+      config: config.clone(),
+      prev_leaves: [node].into_iter().collect(),
+      budget: RefinementBudget::NO_NEIGHBOR_ENFORCEMENT,
+    });
+    !output.next_leaves.contains(&node)
+  };
+
+  // Near: viewer at the node's center wants the finest LOD
+  let near = center;
+  assert_eq!(config.desired_lod(center, near), config.min_lod);
+  assert!(subdivides(near));
+
+  // Far: three thresholds away wants a coarser LOD than the node has
+  let far = center + DVec3::X * config.get_threshold(node.lod) * 3.0;
+  assert!(config.desired_lod(center, far) > node.lod);
+  assert!(!subdivides(far));
+
+  // Just inside / outside the node's own threshold
+  let threshold = config.get_threshold(node.lod);
+  let inside = center + DVec3::X * threshold * 0.99;
+  let outside = center + DVec3::X * threshold * 1.01;
+  assert_eq!(config.desired_lod(center, inside), node.lod - 1);
+  assert_eq!(config.desired_lod(center, outside), node.lod);
+  assert!(subdivides(inside));
+  assert!(!subdivides(outside));
+}
+
+// =========================================================================
+// Frustum Hint
+// =========================================================================
+
+/// Groups fully outside the frustum are flagged culled; straddling ones are
+/// not. Leaves are updated either way.
+#[test]
+fn test_frustum_flags_culled_groups() {
+  let config = OctreeConfig {
+    lod_exponent: 2.0,
+    ..OctreeConfig::default()
+  };
+  let straddling = OctreeNode::new(0, 0, 0, 3);
+  let outside = OctreeNode::new(-2, 0, 0, 3);
+  let leaves: HashSet<_> = [straddling, outside].into_iter().collect();
+
+  // Only visible half-space: x >= center of `straddling`
+  let cut = config.get_node_center(&straddling).x;
+  let always = DVec4::new(0.0, 0.0, 0.0, 1.0);
+  let frustum = Frustum::new([
+    DVec4::new(1.0, 0.0, 0.0, -cut),
+    always,
+    always,
+    always,
+    always,
+    always,
+  ]);
+
+  let output = refine(RefinementInput {
+    viewer_pos: config.get_node_center(&straddling),
+    view_frustum: Some(frustum),
+    config,
+    prev_leaves: leaves,
+    budget: RefinementBudget::NO_NEIGHBOR_ENFORCEMENT,
+  });
+
+  let culled = |key: OctreeNode| {
+    output
+      .transition_groups
+      .iter()
+      .find(|g| g.group_key == key)
+      .map(|g| g.culled)
+      .expect("node should subdivide")
+  };
+  assert!(culled(outside), "Node outside a plane should be culled");
+  assert!(!culled(straddling), "Straddling node should not be culled");
+
+  // Culled nodes are still tracked as leaves
+  assert!(!output.next_leaves.contains(&outside));
+  assert!(output.next_leaves.contains(&outside.get_child(0).unwrap()));
+}
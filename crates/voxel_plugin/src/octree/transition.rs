@@ -20,7 +20,7 @@ pub enum TransitionType {
 ///
 /// All nodes in a group transition together. Group key is always the parent
 /// node.
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct TransitionGroup {
   /// Type of transition.
   pub transition_type: TransitionType,
@@ -37,6 +37,12 @@ pub struct TransitionGroup {
   /// - Subdivide: 1 parent
   /// - Merge: 8 children
   pub nodes_to_remove: SmallVec<[OctreeNode; 8]>,
+
+  /// Group bounds lie fully outside the view frustum passed to `refine`.
+  ///
+  /// Leaves are still updated (physics needs them); the presentation layer
+  /// may defer meshing until the group comes into view.
+  pub culled: bool,
 }
 
 impl TransitionGroup {
@@ -65,6 +71,7 @@ impl TransitionGroup {
 			group_key: parent,
 			nodes_to_add,
 			nodes_to_remove,
+			culled: false,
 		})
 	}
 
@@ -93,6 +100,7 @@ impl TransitionGroup {
 			group_key: parent,
 			nodes_to_add: children,
 			nodes_to_remove,
+			culled: false,
 		})
 	}
 
@@ -113,6 +121,7 @@ impl TransitionGroup {
       group_key: parent,
       nodes_to_add,
       nodes_to_remove: children,
+      culled: false,
     })
   }
 }
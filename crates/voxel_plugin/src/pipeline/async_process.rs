@@ -17,26 +17,87 @@
 //!     }
 //! }
 //! ```
+//!
+//! # Batch Size Cap
+//!
+//! With `with_max_batch_nodes(n)`, `start` splits the transition groups into
+//! sub-batches of at most `n` nodes to mesh (groups are never split). Only one
+//! sub-batch is in flight at a time; the next is spawned when the previous
+//! one is polled, so each `poll_events` result covers a single sub-batch and
+//! the pipeline stays busy until all have been delivered.
+//!
+//! # Cancellation
+//!
+//! Each `start` is identified by a [`BatchId`] (see `current_batch`).
+//! `cancel(batch_id)` raises an atomic flag that the presample and meshing
+//! stages check between nodes; remaining work is abandoned and queued
+//! sub-batches are dropped. The batch still completes through `poll_events`
+//! with the groups that finished. A group is applied whole or not at all:
+//! its `NodesExpired` nodes are only reported with all of its chunks, and
+//! groups left unfinished (dropped sub-batches included) are reported in
+//! `GroupsAbandoned` so they can be enqueued again.
+//!
+//! # Executor
+//!
+//! Batches run on the global rayon pool unless `with_executor` supplies a
+//! [`TaskExecutor`] with a dedicated pool.
 
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
-use crossbeam_channel::{self as channel, Receiver, TryRecvError};
+use crossbeam_channel::{self as channel, Receiver, Sender, TryRecvError};
 
-use super::process::process_transitions;
-use crate::octree::{OctreeConfig, OctreeNode, TransitionGroup};
+use super::process::process_transitions_cancellable;
+use crate::octree::{OctreeConfig, OctreeNode, TransitionGroup, TransitionType};
 use crate::pipeline::types::{PipelineEvent, ReadyChunk, VolumeSampler};
+use crate::threading::TaskExecutor;
 use crate::world::WorldId;
 
+/// Chunks of a finished sub-batch and the groups it abandoned.
+type BatchResult = (Vec<ReadyChunk>, Vec<TransitionGroup>);
+
+/// Spawns `process_transitions_cancellable` for one sub-batch, capturing the
+/// sampler, leaves, config and cancellation flag of the batch.
+type BatchSpawner = Box<dyn Fn(Vec<TransitionGroup>, Sender<BatchResult>) + Send + Sync>;
+
+/// Identifies one `AsyncPipeline::start` call (all of its sub-batches).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct BatchId(u64);
+
+impl BatchId {
+  /// Get the raw ID value.
+  pub fn raw(&self) -> u64 {
+    self.0
+  }
+}
+
 /// Non-blocking async pipeline processor.
 ///
 /// Wraps `process_transitions` to run on rayon's thread pool without blocking
 /// the main thread. Uses channels for result delivery.
 pub struct AsyncPipeline {
   /// Receiver for the pending task's result (None if idle)
-  receiver: Option<Receiver<Vec<ReadyChunk>>>,
+  receiver: Option<Receiver<BatchResult>>,
   /// Stored when start() is called, emitted with poll_events()
   pending_world_id: Option<WorldId>,
   pending_expired_nodes: Vec<OctreeNode>,
+  /// Groups of sub-batches dropped by `cancel`
+  pending_abandoned: Vec<TransitionGroup>,
+  /// Maximum nodes to mesh per sub-batch (None = unlimited)
+  max_batch_nodes: Option<usize>,
+  /// Sub-batches waiting for the current one to be polled
+  queued_batches: VecDeque<Vec<TransitionGroup>>,
+  /// Spawner for queued sub-batches (None if idle)
+  spawner: Option<BatchSpawner>,
+  /// Batch started by the last `start` call (None if idle)
+  current_batch: Option<BatchId>,
+  /// Cancellation flag shared with the current batch's workers
+  cancel_flag: Option<Arc<AtomicBool>>,
+  /// Counter for generating BatchIds
+  next_batch_id: u64,
+  /// Pool the batches run on
+  executor: TaskExecutor,
 }
 
 impl AsyncPipeline {
@@ -48,17 +109,55 @@ impl AsyncPipeline {
       receiver: None,
       pending_world_id: None,
       pending_expired_nodes: Vec::new(),
+      pending_abandoned: Vec::new(),
+      max_batch_nodes: None,
+      queued_batches: VecDeque::new(),
+      spawner: None,
+      current_batch: None,
+      cancel_flag: None,
+      next_batch_id: 1,
+      executor: TaskExecutor::global(),
     }
   }
 
+  /// Run batches on `executor` instead of the global rayon pool.
+  pub fn with_executor(mut self, executor: TaskExecutor) -> Self {
+    self.executor = executor;
+    self
+  }
+
+  /// Executor batches run on.
+  pub fn executor(&self) -> &TaskExecutor {
+    &self.executor
+  }
+
+  /// Cap the number of nodes meshed per sub-batch.
+  ///
+  /// Bounds peak mesh memory and time-to-first-result for large transitions.
+  pub fn with_max_batch_nodes(mut self, max_nodes: usize) -> Self {
+    self.max_batch_nodes = Some(max_nodes.max(1));
+    self
+  }
+
+  /// Maximum nodes to mesh per sub-batch (None = unlimited).
+  pub fn max_batch_nodes(&self) -> Option<usize> {
+    self.max_batch_nodes
+  }
+
   /// Check if a task is currently running.
   pub fn is_busy(&self) -> bool {
     self.receiver.is_some()
   }
 
+  /// Batch currently in flight (None if idle).
+  pub fn current_batch(&self) -> Option<BatchId> {
+    self.current_batch
+  }
+
   /// Start processing transitions (non-blocking).
   ///
-  /// Returns `true` if processing started, `false` if already busy.
+  /// Returns `true` if processing started, `false` if already busy. The new
+  /// batch's id is available from `current_batch`.
   pub fn start<S: VolumeSampler + Clone + 'static>(
     &mut self,
     world_id: WorldId,
@@ -71,25 +170,53 @@ impl AsyncPipeline {
       return false;
     }
 
-    // Extract nodes_to_remove from all transition groups for NodesExpired event
-    self.pending_expired_nodes = transition_groups
+    self.pending_world_id = Some(world_id);
+    self.queued_batches = split_batches(transition_groups, self.max_batch_nodes);
+    self.current_batch = Some(BatchId(self.next_batch_id));
+    self.next_batch_id += 1;
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    self.cancel_flag = Some(Arc::clone(&cancelled));
+
+    let leaves = Arc::new(leaves);
+    let executor = self.executor.clone();
+    self.spawner = Some(Box::new(move |groups, sender| {
+      let sampler = sampler.clone();
+      let leaves = Arc::clone(&leaves);
+      let config = config.clone();
+      let cancelled = Arc::clone(&cancelled);
+
+      // Spawn processing on the executor's thread pool
+      executor.spawn(move || {
+        let result = process_transitions_cancellable(
+          world_id, &groups, &sampler, &leaves, &config, &cancelled,
+        );
+        // Ignore send error (receiver dropped = task cancelled)
+        let _ = sender.send(result);
+      });
+    }));
+
+    self.spawn_next_batch();
+    true
+  }
+
+  /// Spawn the next queued sub-batch (an empty batch if none were queued).
+  fn spawn_next_batch(&mut self) {
+    let groups = self.queued_batches.pop_front().unwrap_or_default();
+
+    // Extract nodes_to_remove from the batch's groups for NodesExpired event
+    self.pending_expired_nodes = groups
       .iter()
       .flat_map(|group| group.nodes_to_remove.iter().copied())
       .collect();
-    self.pending_world_id = Some(world_id);
 
     // Create channel for result
     let (sender, receiver) = channel::bounded(1);
     self.receiver = Some(receiver);
 
-    // Spawn processing on rayon's thread pool
-    rayon::spawn(move || {
-      let result = process_transitions(world_id, &transition_groups, &sampler, &leaves, &config);
-      // Ignore send error (receiver dropped = task cancelled)
-      let _ = sender.send(result);
-    });
-
-    true
+    if let Some(spawner) = &self.spawner {
+      spawner(groups, sender);
+    }
   }
 
   /// Poll for pipeline events (non-blocking).
@@ -97,6 +224,10 @@ impl AsyncPipeline {
   /// Returns `Some(events)` when processing completes, with events in order:
   /// 1. `NodesExpired` - nodes that should be despawned
   /// 2. `ChunksReady` - new meshes to spawn
+  /// 3. `GroupsAbandoned` - groups a cancelled batch did not apply
+  ///
+  /// With a batch cap, each result covers one sub-batch and the next
+  /// sub-batch starts as soon as this one is returned.
   ///
   /// Returns `None` if still running or no task was started.
   pub fn poll_events(&mut self) -> Option<Vec<PipelineEvent>> {
@@ -104,48 +235,134 @@ impl AsyncPipeline {
     let world_id = self.pending_world_id?;
 
     match receiver.try_recv() {
-      Ok(chunks) => {
-        self.receiver = None;
-        self.pending_world_id = None;
-
-        let expired_nodes = std::mem::take(&mut self.pending_expired_nodes);
-
-        let mut events = Vec::with_capacity(2);
-
-        // NodesExpired always comes first (despawn before spawn)
-        if !expired_nodes.is_empty() {
-          events.push(PipelineEvent::NodesExpired {
-            world_id,
-            nodes: expired_nodes,
-          });
-        }
-
-        // ChunksReady with new meshes
-        if !chunks.is_empty() {
-          events.push(PipelineEvent::ChunksReady { world_id, chunks });
-        }
-
-        Some(events)
-      }
+      Ok(result) => Some(self.complete_batch(world_id, result)),
       Err(TryRecvError::Empty) => None, // Still running
       Err(TryRecvError::Disconnected) => {
         // Sender dropped without sending (shouldn't happen)
-        self.receiver = None;
-        self.pending_world_id = None;
-        self.pending_expired_nodes.clear();
+        self.abort();
         None
       }
     }
   }
 
-  /// Cancel any pending task.
+  /// Block until all outstanding work (every queued sub-batch) has finished.
+  ///
+  /// Returns the events of every remaining sub-batch in order, as if
+  /// `poll_events` had been called until idle. Intended for tests and
+  /// screenshot tooling.
   ///
-  /// Note: The task will still run to completion on the worker thread,
-  /// but results will be discarded.
-  pub fn cancel(&mut self) {
+  /// Must not be called from a rayon worker thread: the pool could be left
+  /// without a free thread to run the batch.
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn block_until_idle(&mut self) -> Vec<PipelineEvent> {
+    let mut events = Vec::new();
+
+    while let (Some(receiver), Some(world_id)) = (&self.receiver, self.pending_world_id) {
+      match receiver.recv() {
+        Ok(result) => events.extend(self.complete_batch(world_id, result)),
+        Err(_) => {
+          // Sender dropped without sending (shouldn't happen)
+          self.abort();
+          break;
+        }
+      }
+    }
+
+    events
+  }
+
+  /// Turn a finished sub-batch into events and start the next one.
+  fn complete_batch(
+    &mut self,
+    world_id: WorldId,
+    (chunks, mut abandoned): BatchResult,
+  ) -> Vec<PipelineEvent> {
+    let mut expired_nodes = std::mem::take(&mut self.pending_expired_nodes);
+    if !abandoned.is_empty() {
+      // Abandoned groups keep the nodes they would have removed
+      let kept: HashSet<OctreeNode> = abandoned
+        .iter()
+        .flat_map(|group| group.nodes_to_remove.iter().copied())
+        .collect();
+      expired_nodes.retain(|node| !kept.contains(node));
+    }
+    abandoned.append(&mut self.pending_abandoned);
+
+    if self.queued_batches.is_empty() {
+      self.receiver = None;
+      self.pending_world_id = None;
+      self.spawner = None;
+      self.current_batch = None;
+      self.cancel_flag = None;
+    } else {
+      self.spawn_next_batch();
+    }
+
+    let mut events = Vec::with_capacity(3);
+
+    // NodesExpired always comes first (despawn before spawn)
+    if !expired_nodes.is_empty() {
+      events.push(PipelineEvent::NodesExpired {
+        world_id,
+        nodes: expired_nodes,
+      });
+    }
+
+    // ChunksReady with new meshes
+    if !chunks.is_empty() {
+      events.push(PipelineEvent::ChunksReady { world_id, chunks });
+    }
+
+    if !abandoned.is_empty() {
+      events.push(PipelineEvent::GroupsAbandoned {
+        world_id,
+        groups: abandoned,
+      });
+    }
+
+    events
+  }
+
+  /// Cancel the batch `batch_id` if it is still in flight.
+  ///
+  /// Workers stop before their next node and queued sub-batches are
+  /// dropped. The in-flight sub-batch still completes through `poll_events`
+  /// (or `block_until_idle`) with the groups that finished, possibly none;
+  /// its unfinished groups and those of the dropped sub-batches are reported
+  /// in `GroupsAbandoned`, and the nodes they would have removed are not
+  /// expired.
+  ///
+  /// Returns `false` if `batch_id` is not the current batch (already
+  /// delivered or never started).
+  pub fn cancel(&mut self, batch_id: BatchId) -> bool {
+    if self.current_batch != Some(batch_id) {
+      return false;
+    }
+
+    if let Some(flag) = &self.cancel_flag {
+      flag.store(true, Ordering::Relaxed);
+    }
+    self
+      .pending_abandoned
+      .extend(self.queued_batches.drain(..).flatten());
+    true
+  }
+
+  /// Abandon any pending task and discard its results.
+  ///
+  /// Workers are signalled to stop, but no events are delivered for the
+  /// abandoned batch.
+  pub fn abort(&mut self) {
+    if let Some(flag) = self.cancel_flag.take() {
+      flag.store(true, Ordering::Relaxed);
+    }
     self.receiver = None;
     self.pending_world_id = None;
     self.pending_expired_nodes.clear();
+    self.pending_abandoned.clear();
+    self.queued_batches.clear();
+    self.spawner = None;
+    self.current_batch = None;
   }
 
   /// Get the number of worker threads in rayon's pool.
@@ -160,6 +377,47 @@ impl Default for AsyncPipeline {
   }
 }
 
+/// Number of nodes a group sends through meshing.
+fn group_mesh_nodes(group: &TransitionGroup) -> usize {
+  match group.transition_type {
+    TransitionType::Subdivide => group.nodes_to_add.len(),
+    TransitionType::Merge => 1,
+  }
+}
+
+/// Split groups into sub-batches of at most `max_nodes` nodes to mesh.
+///
+/// Groups stay atomic, so a single group larger than the cap forms its own
+/// batch.
+fn split_batches(
+  groups: Vec<TransitionGroup>,
+  max_nodes: Option<usize>,
+) -> VecDeque<Vec<TransitionGroup>> {
+  let Some(max_nodes) = max_nodes else {
+    return VecDeque::from([groups]);
+  };
+
+  let mut batches = VecDeque::new();
+  let mut current = Vec::new();
+  let mut current_nodes = 0;
+
+  for group in groups {
+    let nodes = group_mesh_nodes(&group);
+    if !current.is_empty() && current_nodes + nodes > max_nodes {
+      batches.push_back(std::mem::take(&mut current));
+      current_nodes = 0;
+    }
+    current_nodes += nodes;
+    current.push(group);
+  }
+
+  if !current.is_empty() || batches.is_empty() {
+    batches.push_back(current);
+  }
+
+  batches
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -187,6 +445,14 @@ mod tests {
         }
       }
     }
+
+    fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> i8 {
+      if position[2] / voxel_size < 16.0 {
+        -1
+      } else {
+        1
+      }
+    }
   }
 
   #[test]
@@ -224,4 +490,199 @@ mod tests {
     // No transitions = no events (empty Vec)
     assert!(result.unwrap().is_empty());
   }
+
+  #[test]
+  fn test_async_pipeline_splits_oversized_batch() {
+    const MAX_NODES: usize = 10;
+    let mut pipeline = AsyncPipeline::new().with_max_batch_nodes(MAX_NODES);
+
+    let world_id = WorldId::new();
+    let config = OctreeConfig::default();
+
+    // 4 subdivisions = 32 nodes to mesh, far above the cap
+    let groups: Vec<_> = (0..4)
+      .map(|x| TransitionGroup::new_subdivide(OctreeNode::new(x, 0, 0, 1)).unwrap())
+      .collect();
+    let leaves: HashSet<_> = groups
+      .iter()
+      .flat_map(|g| g.nodes_to_add.iter().copied())
+      .collect();
+
+    assert!(pipeline.start(world_id, groups, TestSampler, leaves, config));
+
+    let mut rounds = 0;
+    let mut total_chunks = 0;
+    let mut total_expired = 0;
+    for _ in 0..5000 {
+      if let Some(events) = pipeline.poll_events() {
+        rounds += 1;
+        for event in events {
+          match event {
+            PipelineEvent::ChunksReady { chunks, .. } => {
+              assert!(
+                chunks.len() <= MAX_NODES,
+                "Round {} delivered {} chunks, cap is {}",
+                rounds,
+                chunks.len(),
+                MAX_NODES
+              );
+              total_chunks += chunks.len();
+            }
+            PipelineEvent::NodesExpired { nodes, .. } => total_expired += nodes.len(),
+            PipelineEvent::GroupsAbandoned { .. } => panic!("Nothing was cancelled"),
+          }
+        }
+      }
+      if !pipeline.is_busy() {
+        break;
+      }
+      std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+
+    assert!(!pipeline.is_busy(), "All sub-batches should complete");
+    assert!(rounds > 1, "Oversized batch should be delivered in multiple rounds");
+    assert_eq!(total_chunks, 32);
+    assert_eq!(total_expired, 4);
+  }
+
+  #[test]
+  fn test_pipeline_runs_on_dedicated_executor() {
+    let executor = TaskExecutor::with_threads(2).unwrap();
+    let mut pipeline = AsyncPipeline::new().with_executor(executor);
+    assert_eq!(pipeline.executor().available_threads(), 2);
+
+    let parent = OctreeNode::new(0, 0, 0, 1);
+    let group = TransitionGroup::new_subdivide(parent).unwrap();
+    let leaves: HashSet<_> = group.nodes_to_add.iter().copied().collect();
+
+    let config = OctreeConfig::default();
+    assert!(pipeline.start(WorldId::new(), vec![group], TestSampler, leaves, config));
+    let events = pipeline.block_until_idle();
+
+    let total_chunks: usize = events
+      .iter()
+      .map(|event| match event {
+        PipelineEvent::ChunksReady { chunks, .. } => chunks.len(),
+        PipelineEvent::NodesExpired { .. } | PipelineEvent::GroupsAbandoned { .. } => 0,
+      })
+      .sum();
+    assert_eq!(total_chunks, 8);
+  }
+
+  #[test]
+  fn test_block_until_idle_delivers_all_batches() {
+    let mut pipeline = AsyncPipeline::new().with_max_batch_nodes(10);
+
+    let world_id = WorldId::new();
+    let groups: Vec<_> = (0..4)
+      .map(|x| TransitionGroup::new_subdivide(OctreeNode::new(x, 0, 0, 1)).unwrap())
+      .collect();
+    let leaves: HashSet<_> = groups
+      .iter()
+      .flat_map(|g| g.nodes_to_add.iter().copied())
+      .collect();
+
+    assert!(pipeline.start(world_id, groups, TestSampler, leaves, OctreeConfig::default()));
+
+    let events = pipeline.block_until_idle();
+
+    assert!(!pipeline.is_busy());
+    let total_chunks: usize = events
+      .iter()
+      .map(|event| match event {
+        PipelineEvent::ChunksReady { chunks, .. } => chunks.len(),
+        PipelineEvent::NodesExpired { .. } | PipelineEvent::GroupsAbandoned { .. } => 0,
+      })
+      .sum();
+    assert_eq!(total_chunks, 32);
+    assert!(pipeline.block_until_idle().is_empty());
+  }
+
+  #[test]
+  fn test_cancel_right_after_start_returns_partial_result() {
+    let mut pipeline = AsyncPipeline::new().with_max_batch_nodes(8);
+
+    let world_id = WorldId::new();
+    // 16 subdivisions = 128 nodes to mesh across 16 sub-batches
+    let groups: Vec<_> = (0..16)
+      .map(|x| TransitionGroup::new_subdivide(OctreeNode::new(x, 0, 0, 1)).unwrap())
+      .collect();
+    let leaves: HashSet<_> = groups
+      .iter()
+      .flat_map(|g| g.nodes_to_add.iter().copied())
+      .collect();
+
+    assert!(pipeline.start(world_id, groups, TestSampler, leaves, OctreeConfig::default()));
+    let batch_id = pipeline.current_batch().unwrap();
+    assert!(pipeline.cancel(batch_id));
+
+    // Poll (not block) so a regression shows up as a failure, not a hang
+    let mut events = Vec::new();
+    for _ in 0..5000 {
+      if let Some(batch_events) = pipeline.poll_events() {
+        events.extend(batch_events);
+      }
+      if !pipeline.is_busy() {
+        break;
+      }
+      std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+
+    assert!(!pipeline.is_busy(), "Cancelled batch should still complete");
+    assert_eq!(pipeline.current_batch(), None);
+    let total_chunks: usize = events
+      .iter()
+      .map(|event| match event {
+        PipelineEvent::ChunksReady { chunks, .. } => chunks.len(),
+        PipelineEvent::NodesExpired { .. } | PipelineEvent::GroupsAbandoned { .. } => 0,
+      })
+      .sum();
+    // Queued sub-batches are dropped: at most the first one was meshed
+    assert!(
+      total_chunks <= 8,
+      "Got {} chunks after cancel",
+      total_chunks
+    );
+    // ...and every parent is either expired with all of its children
+    // delivered, or kept and reported with its abandoned group
+    let mut expired = HashSet::new();
+    let mut abandoned = HashSet::new();
+    let mut delivered = HashSet::new();
+    for event in &events {
+      match event {
+        PipelineEvent::NodesExpired { nodes, .. } => expired.extend(nodes.iter().copied()),
+        PipelineEvent::ChunksReady { chunks, .. } => {
+          delivered.extend(chunks.iter().map(|chunk| chunk.node))
+        }
+        PipelineEvent::GroupsAbandoned { groups, .. } => {
+          abandoned.extend(groups.iter().map(|group| group.group_key))
+        }
+      }
+    }
+    assert_eq!(expired.len() + abandoned.len(), 16);
+    assert!(expired.is_disjoint(&abandoned));
+    assert!(abandoned.len() >= 15);
+    for parent in &expired {
+      let group = TransitionGroup::new_subdivide(*parent).unwrap();
+      assert!(group
+        .nodes_to_add
+        .iter()
+        .all(|child| delivered.contains(child)));
+    }
+
+    // Stale ids are ignored
+    assert!(!pipeline.cancel(batch_id));
+  }
+
+  #[test]
+  fn test_split_batches_keeps_groups_atomic() {
+    let groups: Vec<_> = (0..3)
+      .map(|x| TransitionGroup::new_subdivide(OctreeNode::new(x, 0, 0, 1)).unwrap())
+      .collect();
+
+    assert_eq!(split_batches(groups.clone(), None).len(), 1);
+    assert_eq!(split_batches(groups.clone(), Some(16)).len(), 2);
+    // A cap below a single group still makes progress
+    assert_eq!(split_batches(groups, Some(4)).len(), 3);
+  }
 }
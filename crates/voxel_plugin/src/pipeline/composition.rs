@@ -55,6 +55,14 @@ pub struct CompositionOutput {
 ///    - Merge: collect mesh for parent
 ///
 /// 3. Create GroupedMesh for each TransitionGroup with matching meshes
+#[cfg_attr(
+  feature = "tracing",
+  tracing::instrument(
+    skip_all,
+    name = "composition::compose",
+    fields(meshes = mesh_results.len(), groups = transition_groups.len())
+  )
+)]
 pub fn compose(
   mesh_results: Vec<MeshResult>,
   transition_groups: &[TransitionGroup],
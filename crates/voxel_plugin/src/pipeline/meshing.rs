@@ -28,7 +28,27 @@ use rayon::prelude::*;
 use web_time::Instant;
 
 use super::types::{MeshInput, MeshResult};
+use crate::octree::{OctreeConfig, OctreeNode};
 use crate::surface_nets;
+use crate::types::MeshConfig;
+
+/// Mesh settings for `node`: `base` with the node's voxel size, neighbor
+/// mask and world origin (its minimum corner).
+///
+/// Every path that meshes octree nodes builds its config here, so their
+/// chunks match.
+pub fn node_mesh_config(
+  base: &MeshConfig,
+  node: &OctreeNode,
+  neighbor_mask: u8,
+  config: &OctreeConfig,
+) -> MeshConfig {
+  base
+    .clone()
+    .with_voxel_size(config.get_voxel_size(node.lod) as f32)
+    .with_neighbor_mask(neighbor_mask as u32)
+    .with_world_origin(config.get_node_min(node).as_vec3().to_array())
+}
 
 /// Mesh a single node using surface nets algorithm.
 ///
@@ -48,13 +48,21 @@ pub mod test_utils;
 
 // Re-exports
 // Async entry point (non-blocking, cross-platform)
-pub use async_process::AsyncPipeline;
+pub use async_process::{AsyncPipeline, BatchId};
+// Per-node mesh settings shared by every meshing path
+pub use meshing::node_mesh_config;
 // Presample helpers for direct sampling (e.g., startup, debugging)
-pub use presample::sample_volume_for_node;
+pub use presample::{
+	sample_normals_for_node, sample_volume16_for_node, sample_volume_for_node, VolumePool,
+};
 // Synchronous entry point
-pub use process::{process_transitions, process_transitions_timed, ProcessingStats};
+pub use process::{
+	compute_neighbor_mask, process_invalidations, process_invalidations_with_mesh_config,
+	process_transitions, process_transitions_cancellable, process_transitions_timed,
+	process_transitions_timed_with_mesh_config, ProcessingStats,
+};
 pub use types::{
 	ChunkPresentation, CompletedTransition, Epoch, GroupedMesh, MeshInput, MeshResult, NodeMesh,
 	PipelineEvent, PresampleOutput, PresentationBatch, PresentationHint, ReadyChunk, SampledVolume,
-	VolumeSampler, WorkSource,
+	SampledVolume16, VolumeSampler, WorkSource,
 };
@@ -2,13 +2,103 @@
 //!
 //! Samples full 32³ volume and detects homogeneous regions.
 //! Homogeneous chunks (all solid or all air) skip meshing entirely.
+//!
+//! Volume buffers can be drawn from a [`VolumePool`] to avoid allocating
+//! 64KB per node under heavy refinement.
+
+use std::sync::Mutex;
 
 use rayon::prelude::*;
 
-use super::types::{PresampleOutput, SampledVolume, VolumeSampler, WorkSource};
+use super::types::{PresampleOutput, SampledVolume, SampledVolume16, VolumeSampler, WorkSource};
 use crate::constants::SAMPLE_SIZE_CB;
 use crate::noise::has_surface_crossing;
 use crate::octree::{OctreeConfig, OctreeNode};
+use crate::types::SdfValue;
+
+/// Buffers retained by [`VolumePool::global`] (64KB each).
+pub const DEFAULT_VOLUME_POOL_CAPACITY: usize = 64;
+
+/// Bounded pool of reusable 32³ volume + material buffers.
+///
+/// `acquire` falls back to allocation when the pool is empty; `release`
+/// drops the buffer when the pool is full. Reused buffers are zeroed on
+/// `acquire`, so a sampler that leaves samples untouched never exposes those
+/// of an earlier volume.
+pub struct VolumePool {
+  free: Mutex<Vec<SampledVolume>>,
+  capacity: usize,
+}
+
+impl VolumePool {
+  /// Create an empty pool retaining at most `capacity` buffers.
+  pub const fn new(capacity: usize) -> Self {
+    Self {
+      free: Mutex::new(Vec::new()),
+      capacity,
+    }
+  }
+
+  /// Process-wide pool used by the refinement pipeline.
+  pub fn global() -> &'static VolumePool {
+    static GLOBAL: VolumePool = VolumePool::new(DEFAULT_VOLUME_POOL_CAPACITY);
+    &GLOBAL
+  }
+
+  /// Take a zeroed buffer from the pool, allocating if it is empty.
+  pub fn acquire(&self) -> SampledVolume {
+    let reused = self.lock().pop();
+    match reused {
+      Some(mut sampled) => {
+        sampled.volume.fill(0);
+        sampled.materials.fill(0);
+        sampled
+      }
+      None => SampledVolume {
+        volume: Box::new([0i8; SAMPLE_SIZE_CB]),
+        materials: Box::new([0u8; SAMPLE_SIZE_CB]),
+        normals: None,
+        volume16: None,
+      },
+    }
+  }
+
+  /// Return a buffer for reuse. Dropped if the pool is full; its normals
+  /// and 16-bit samples are never pooled.
+  pub fn release(&self, mut sampled: SampledVolume) {
+    sampled.normals = None;
+    sampled.volume16 = None;
+    let mut free = self.lock();
+    if free.len() < self.capacity {
+      free.push(sampled);
+    }
+  }
+
+  /// Number of buffers currently pooled.
+  pub fn len(&self) -> usize {
+    self.lock().len()
+  }
+
+  /// Whether the pool currently holds no buffers.
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Maximum number of buffers retained.
+  pub fn capacity(&self) -> usize {
+    self.capacity
+  }
+
+  /// Drop all pooled buffers.
+  pub fn clear(&self) {
+    self.lock().clear();
+  }
+
+  fn lock(&self) -> std::sync::MutexGuard<'_, Vec<SampledVolume>> {
+    // The free list holds no invariants a panic could break
+    self.free.lock().unwrap_or_else(|e| e.into_inner())
+  }
+}
 
 /// Sample the full 32³ volume for a node using VolumeSampler.
 ///
@@ -19,33 +109,159 @@ use crate::octree::{OctreeConfig, OctreeNode};
 ///
 /// This ensures adjacent chunks use identical integer offsets for
 /// overlapping samples, eliminating floating-point precision divergence.
+///
+/// When `world_origin` is not aligned to the LOD's voxel size, the residual
+/// is passed to the sampler as a sub-voxel phase so that coarse and fine
+/// LODs sample a shared lattice (see `OctreeConfig::get_sample_grid`).
+///
+/// Samples are normalised to negative-inside (see
+/// `VolumeSampler::sdf_convention`).
 pub fn sample_volume_for_node<S: VolumeSampler + ?Sized>(
   node: &OctreeNode,
   sampler: &S,
   config: &OctreeConfig,
 ) -> SampledVolume {
-  let mut volume = Box::new([0i8; SAMPLE_SIZE_CB]);
-  let mut materials = Box::new([0u8; SAMPLE_SIZE_CB]);
+  let mut sampled = SampledVolume {
+    volume: Box::new([0i8; SAMPLE_SIZE_CB]),
+    materials: Box::new([0u8; SAMPLE_SIZE_CB]),
+    normals: None,
+    volume16: None,
+  };
+  sample_into(node, sampler, config, &mut sampled);
+  sampled
+}
+
+/// Like [`sample_volume_for_node`], but samples into a buffer from `pool`.
+pub fn sample_volume_for_node_pooled<S: VolumeSampler + ?Sized>(
+  node: &OctreeNode,
+  sampler: &S,
+  config: &OctreeConfig,
+  pool: &VolumePool,
+) -> SampledVolume {
+  let mut sampled = pool.acquire();
+  sample_into(node, sampler, config, &mut sampled);
+  sampled
+}
+
+/// Like [`sample_volume_for_node`], with 16-bit SDF samples
+/// (`VolumeSampler::sample_volume16`).
+pub fn sample_volume16_for_node<S: VolumeSampler + ?Sized>(
+  node: &OctreeNode,
+  sampler: &S,
+  config: &OctreeConfig,
+) -> SampledVolume16 {
+  let mut sampled = SampledVolume16 {
+    volume: Box::new([0i16; SAMPLE_SIZE_CB]),
+    materials: Box::new([0u8; SAMPLE_SIZE_CB]),
+  };
+  let (grid_offset, phase) = config.get_sample_grid(node);
+  sampler.sample_volume16(
+    grid_offset,
+    config.get_voxel_size(node.lod),
+    phase.to_array(),
+    &mut sampled.volume,
+    &mut sampled.materials,
+  );
+  sampler
+    .sdf_convention()
+    .normalize(sampled.volume.as_mut_slice());
+  sampled
+}
+
+/// Like [`sample_volume16_for_node`], into a buffer from `pool`: the 16-bit
+/// samples go to `volume16`, and `volume` holds them saturated to i8.
+pub fn sample_volume16_for_node_pooled<S: VolumeSampler + ?Sized>(
+  node: &OctreeNode,
+  sampler: &S,
+  config: &OctreeConfig,
+  pool: &VolumePool,
+) -> SampledVolume {
+  let mut sampled = pool.acquire();
+  let mut volume16 = Box::new([0i16; SAMPLE_SIZE_CB]);
+  let (grid_offset, phase) = config.get_sample_grid(node);
+  sampler.sample_volume16(
+    grid_offset,
+    config.get_voxel_size(node.lod),
+    phase.to_array(),
+    &mut volume16,
+    &mut sampled.materials,
+  );
+  sampler.sdf_convention().normalize(volume16.as_mut_slice());
+  for (out, &sample) in sampled.volume.iter_mut().zip(volume16.iter()) {
+    *out = sample.saturate();
+  }
+  sampled.volume16 = Some(volume16);
+  sampled
+}
+
+/// Analytic normals of a node (`VolumeSampler::sample_normals`), at the
+/// positions `sample_volume_for_node` samples.
+///
+/// `None` if the sampler has no analytic normals.
+pub fn sample_normals_for_node<S: VolumeSampler + ?Sized>(
+  node: &OctreeNode,
+  sampler: &S,
+  config: &OctreeConfig,
+) -> Option<Box<[[f32; 3]; SAMPLE_SIZE_CB]>> {
+  let (grid_offset, phase) = config.get_sample_grid(node);
+  let mut normals = Box::new([[0.0; 3]; SAMPLE_SIZE_CB]);
+  if !sampler.sample_normals(
+    grid_offset,
+    config.get_voxel_size(node.lod),
+    phase.to_array(),
+    &mut normals,
+  ) {
+    return None;
+  }
+  sampler
+    .sdf_convention()
+    .normalize_normals(normals.as_mut_slice());
+  Some(normals)
+}
 
-  let node_min = config.get_node_min(node);
+fn sample_into<S: VolumeSampler + ?Sized>(
+  node: &OctreeNode,
+  sampler: &S,
+  config: &OctreeConfig,
+  sampled: &mut SampledVolume,
+) {
   let voxel_size = config.get_voxel_size(node.lod);
 
-  // Convert to integer grid coordinates to avoid floating-point precision issues.
-  // This matches C# FastNoise2Sampler: gridStart = (int3)round(worldMin / voxelSize)
-  let grid_offset = [
-    (node_min.x / voxel_size).round() as i64,
-    (node_min.y / voxel_size).round() as i64,
-    (node_min.z / voxel_size).round() as i64,
-  ];
+  // Integer grid anchored on world_origin; the sub-voxel remainder of the
+  // origin becomes the phase. For an aligned origin this is identical to
+  // C# FastNoise2Sampler: gridStart = (int3)round(worldMin / voxelSize)
+  let (grid_offset, phase) = config.get_sample_grid(node);
 
-  sampler.sample_volume(grid_offset, voxel_size, &mut volume, &mut materials);
+  if phase == glam::DVec3::ZERO {
+    sampler.sample_volume(
+      grid_offset,
+      voxel_size,
+      &mut sampled.volume,
+      &mut sampled.materials,
+    );
+  } else {
+    sampler.sample_volume_with_phase(
+      grid_offset,
+      voxel_size,
+      phase.to_array(),
+      &mut sampled.volume,
+      &mut sampled.materials,
+    );
+  }
 
-  SampledVolume { volume, materials }
+  // Everything downstream (crossing checks, meshing) is negative-inside
+  sampler
+    .sdf_convention()
+    .normalize(sampled.volume.as_mut_slice());
 }
 
 /// Presample a single node: sample volume, check homogeneity.
 ///
 /// Returns `Some(volume)` if surface may exist, `None` if homogeneous.
+#[cfg_attr(
+  feature = "tracing",
+  tracing::instrument(skip_all, name = "presample::presample_node", fields(lod = node.lod))
+)]
 pub fn presample_node<S: VolumeSampler>(
   node: OctreeNode,
   work_source: WorkSource,
@@ -67,7 +283,42 @@ pub fn presample_node<S: VolumeSampler>(
   }
 }
 
+/// Like [`presample_node`], drawing the buffer from `pool`.
+///
+/// Homogeneous volumes are returned to the pool immediately; callers should
+/// `release` surface volumes once meshed.
+#[cfg_attr(
+  feature = "tracing",
+  tracing::instrument(skip_all, name = "presample::presample_node_pooled", fields(lod = node.lod))
+)]
+pub fn presample_node_pooled<S: VolumeSampler>(
+  node: OctreeNode,
+  work_source: WorkSource,
+  sampler: &S,
+  config: &OctreeConfig,
+  pool: &VolumePool,
+) -> PresampleOutput {
+  let sampled = sample_volume_for_node_pooled(&node, sampler, config, pool);
+
+  let volume = if has_surface_crossing(&sampled.volume) {
+    Some(sampled)
+  } else {
+    pool.release(sampled);
+    None
+  };
+
+  PresampleOutput {
+    node,
+    volume,
+    work_source,
+  }
+}
+
 /// Presample multiple nodes in parallel using rayon.
+#[cfg_attr(
+  feature = "tracing",
+  tracing::instrument(skip_all, name = "presample::presample_batch", fields(nodes = nodes.len()))
+)]
 pub fn presample_batch<S: VolumeSampler>(
   nodes: Vec<(OctreeNode, WorkSource)>,
   sampler: &S,
@@ -83,6 +334,27 @@ pub fn presample_batch<S: VolumeSampler>(
     .collect()
 }
 
+/// Like [`presample_batch`], drawing buffers from `pool`.
+#[cfg_attr(
+  feature = "tracing",
+  tracing::instrument(
+    skip_all,
+    name = "presample::presample_batch_pooled",
+    fields(nodes = nodes.len())
+  )
+)]
+pub fn presample_batch_pooled<S: VolumeSampler>(
+  nodes: Vec<(OctreeNode, WorkSource)>,
+  sampler: &S,
+  config: &OctreeConfig,
+  pool: &VolumePool,
+) -> Vec<PresampleOutput> {
+  nodes
+    .into_par_iter()
+    .map(|(node, work_source)| presample_node_pooled(node, work_source, sampler, config, pool))
+    .collect()
+}
+
 #[cfg(test)]
 #[path = "presample_test.rs"]
 mod presample_test;
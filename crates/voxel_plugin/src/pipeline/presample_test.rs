@@ -2,12 +2,16 @@
 //!
 //! Sample full 32³ volume, check homogeneity.
 
-use super::{presample_batch, presample_node};
-use crate::constants::SAMPLE_SIZE_CB;
-use crate::octree::OctreeNode;
+use glam::DVec3;
+
+use super::{
+  presample_batch, presample_node, presample_node_pooled, sample_volume_for_node, VolumePool,
+};
+use crate::constants::{SAMPLE_SIZE, SAMPLE_SIZE_CB};
+use crate::octree::{OctreeConfig, OctreeNode};
 use crate::pipeline::test_utils::*;
 use crate::pipeline::types::{VolumeSampler, WorkSource};
-use crate::types::{MaterialId, SdfSample};
+use crate::types::{sdf_conversion, MaterialId, SdfSample};
 
 // =============================================================================
 // Batch 1: Homogeneity Detection
@@ -276,6 +280,10 @@ fn test_samples_at_correct_positions_lod_0() {
       volume.fill(-10);
       materials.fill(0);
     }
+
+    fn sample_point(&self, _position: [f64; 3], _voxel_size: f64) -> SdfSample {
+      -10
+    }
   }
 
   let sampler = PositionRecorder {
@@ -346,3 +354,139 @@ fn test_samples_at_correct_positions_lod_3() {
     cell_size
   );
 }
+
+// =============================================================================
+// Batch 7: LOD Sample Alignment
+// =============================================================================
+
+#[test]
+fn test_coarse_samples_coincide_with_child_samples_unaligned_origin() {
+  // Origin of 1.0 is aligned to LOD 0 voxels (1.0) but not LOD 1 voxels (2.0),
+  // so without a sub-voxel phase the coarse grid would be shifted by a voxel.
+  let config = OctreeConfig {
+    world_origin: DVec3::splat(1.0),
+    ..test_config()
+  };
+  let sampler = SphereSampler::new(DVec3::new(15.3, 14.7, 16.1), 10.0);
+
+  let coarse = OctreeNode::new(0, 0, 0, 1);
+  let fine = coarse.get_child(0).expect("LOD 1 node has children");
+  assert_eq!(config.get_node_min(&coarse), config.get_node_min(&fine));
+
+  let coarse_vol = sample_volume_for_node(&coarse, &sampler, &config);
+  let fine_vol = sample_volume_for_node(&fine, &sampler, &config);
+
+  let coarse_voxel = config.get_voxel_size(coarse.lod) as f32;
+  let fine_voxel = config.get_voxel_size(fine.lod) as f32;
+  let tolerance = (coarse_voxel + fine_voxel) / sdf_conversion::BASE_SCALE;
+
+  let mut compared = 0;
+  // Coarse sample i lies on fine sample 2i for all samples inside the child.
+  for x in 0..SAMPLE_SIZE / 2 {
+    for y in 0..SAMPLE_SIZE / 2 {
+      for z in 0..SAMPLE_SIZE / 2 {
+        let c = coarse_vol.volume[x * SAMPLE_SIZE * SAMPLE_SIZE + y * SAMPLE_SIZE + z];
+        let f = fine_vol.volume
+          [(2 * x) * SAMPLE_SIZE * SAMPLE_SIZE + (2 * y) * SAMPLE_SIZE + (2 * z)];
+
+        // Samples quantized to zero may round either way; all others must agree.
+        assert!(
+          c.signum() * f.signum() >= 0,
+          "Sign mismatch at coarse ({}, {}, {}): coarse={}, fine={}",
+          x,
+          y,
+          z,
+          c,
+          f
+        );
+
+        if c.abs() < 127 && f.abs() < 127 {
+          let c_world = sdf_conversion::to_float(c, coarse_voxel);
+          let f_world = sdf_conversion::to_float(f, fine_voxel);
+          assert!(
+            (c_world - f_world).abs() <= tolerance,
+            "Shared sample ({}, {}, {}) differs: coarse={}, fine={}",
+            x,
+            y,
+            z,
+            c_world,
+            f_world
+          );
+          compared += 1;
+        }
+      }
+    }
+  }
+
+  assert!(compared > 0, "Expected unsaturated samples near the surface");
+}
+
+// =============================================================================
+// Volume Pool
+// =============================================================================
+
+#[test]
+fn test_pool_reuses_released_buffer() {
+  let pool = VolumePool::new(4);
+  let sampled = pool.acquire();
+  let ptr = sampled.volume.as_ptr();
+
+  pool.release(sampled);
+  assert_eq!(pool.len(), 1);
+
+  let reused = pool.acquire();
+  assert_eq!(reused.volume.as_ptr(), ptr);
+  assert!(pool.is_empty());
+}
+
+#[test]
+fn test_pool_zeroes_reused_buffer() {
+  let pool = VolumePool::new(4);
+  let mut sampled = pool.acquire();
+  sampled.volume.fill(-7);
+  sampled.materials.fill(3);
+  pool.release(sampled);
+
+  let reused = pool.acquire();
+  assert!(reused.volume.iter().all(|&sample| sample == 0));
+  assert!(reused.materials.iter().all(|&material| material == 0));
+}
+
+#[test]
+fn test_pool_is_bounded() {
+  let pool = VolumePool::new(2);
+  let buffers: Vec<_> = (0..5).map(|_| pool.acquire()).collect();
+
+  for sampled in buffers {
+    pool.release(sampled);
+  }
+
+  assert_eq!(pool.len(), 2);
+}
+
+#[test]
+fn test_pooled_presample_reclaims_homogeneous() {
+  let pool = VolumePool::new(8);
+  let config = test_config();
+  let node = OctreeNode::new(0, 0, 0, 0);
+
+  let output = presample_node_pooled(
+    node,
+    WorkSource::Refinement,
+    &ConstantSampler::all_air(),
+    &config,
+    &pool,
+  );
+  assert!(output.volume.is_none());
+  assert_eq!(pool.len(), 1);
+
+  // A reused (dirty) buffer must sample the same as a fresh one
+  let sampler = SphereSampler::new(DVec3::splat(16.0), 10.0);
+  let pooled = presample_node_pooled(node, WorkSource::Refinement, &sampler, &config, &pool);
+  let fresh = presample_node(node, WorkSource::Refinement, &sampler, &config);
+  assert!(pool.is_empty());
+  assert_eq!(
+    pooled.volume.unwrap().volume[..],
+    fresh.volume.unwrap().volume[..]
+  );
+}
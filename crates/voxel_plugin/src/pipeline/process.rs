@@ -17,19 +17,26 @@
 //!     &sampler,
 //!     &leaves,
 //!     &config,
+//!     None, // or Some(&|done, total| ...) for a loading bar
 //! );
 //!
 //! // Game engine: spawn/despawn entities based on ready_chunks
 //! ```
 
 use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
 
 use rayon::prelude::*;
 
 use super::composition::compose;
-use super::presample::sample_volume_for_node;
-use super::presentation::present;
-use super::types::{ReadyChunk, VolumeSampler, WorkSource};
+use super::meshing::node_mesh_config;
+use super::presample::{
+  sample_normals_for_node, sample_volume16_for_node_pooled, sample_volume_for_node_pooled,
+  VolumePool,
+};
+use super::presentation::{present, present_ungrouped};
+use super::types::{MeshResult, ReadyChunk, SampledVolume, VolumeSampler, WorkSource};
 use crate::noise::has_surface_crossing;
 use crate::octree::{OctreeConfig, OctreeNode, TransitionGroup, TransitionType};
 use crate::types::MeshConfig;
@@ -38,7 +45,7 @@ use crate::world::WorldId;
 /// Compute neighbor mask for seam handling.
 ///
 /// Detects which faces have coarser LOD neighbors (LOD diff > 0).
-fn compute_neighbor_mask(
+pub fn compute_neighbor_mask(
   node: &OctreeNode,
   leaves: &HashSet<OctreeNode>,
   config: &OctreeConfig,
@@ -82,6 +89,179 @@ fn compute_neighbor_mask(
 // canonical locations (noise module and presample module respectively)
 // to avoid code duplication.
 
+/// Presample and mesh nodes in parallel (stages 2 and 3).
+///
+/// Each node is meshed by the task that sampled it and its volume returned
+/// to the pool right away, so at most one volume per worker is alive.
+/// Volumes with no surface crossings (all solid or all air) are skipped.
+///
+/// `cancelled` is checked before each node; once set, the remaining nodes
+/// are skipped. Returns the finished meshes and the skipped nodes.
+///
+/// `progress` is called as `(done, total)` on the calling thread once per
+/// node, after each round of `PROGRESS_NODES_PER_THREAD` nodes per worker.
+/// `done` counts the surface chunks meshed so far. `total` starts at the node
+/// count and drops as nodes turn out empty (or are skipped), so the last call
+/// has `done == total ==` the number of surface chunks.
+///
+/// Per-node presample and meshing time is added to `timings`.
+///
+/// Each chunk is meshed with `mesh_config`, its voxel size, neighbor mask
+/// and world origin set per node. With `use_sdf16` set, volumes are sampled
+/// with 16-bit values. With `prefer_analytic_normals` set, the sampler's
+/// analytic normals are sampled along with each volume that has a surface.
+#[allow(clippy::too_many_arguments)]
+#[cfg_attr(
+  feature = "tracing",
+  tracing::instrument(skip_all, name = "pipeline::presample_and_mesh", fields(nodes = nodes.len()))
+)]
+fn presample_and_mesh<S: VolumeSampler>(
+  nodes: Vec<OctreeNode>,
+  work_source: WorkSource,
+  sampler: &S,
+  leaves: &HashSet<OctreeNode>,
+  config: &OctreeConfig,
+  mesh_config: &MeshConfig,
+  cancelled: &AtomicBool,
+  progress: Option<&dyn Fn(usize, usize)>,
+  timings: &StageTimings,
+) -> (Vec<MeshResult>, Vec<OctreeNode>) {
+  let process = |node: OctreeNode| {
+    if cancelled.load(Ordering::Relaxed) {
+      return Err(node);
+    }
+
+    // Stage 2: presample, skipping volumes with no surface crossings
+    let sample_start = web_time::Instant::now();
+    let mut sampled = if mesh_config.use_sdf16 {
+      sample_volume16_for_node_pooled(&node, sampler, config, VolumePool::global())
+    } else {
+      sample_volume_for_node_pooled(&node, sampler, config, VolumePool::global())
+    };
+
+    if !has_surface_crossing(&sampled.volume) {
+      StageTimings::add(&timings.presample_ns, sample_start.elapsed());
+      VolumePool::global().release(sampled);
+      return Ok(None);
+    }
+
+    if mesh_config.prefer_analytic_normals {
+      sampled.normals = sample_normals_for_node(&node, sampler, config);
+    }
+    let sample_elapsed = sample_start.elapsed();
+    StageTimings::add(&timings.presample_ns, sample_elapsed);
+
+    // Stage 3: mesh
+    Ok(mesh_sampled_node(
+      node,
+      sampled,
+      sample_elapsed.as_micros() as u64,
+      work_source,
+      leaves,
+      config,
+      mesh_config,
+      timings,
+    ))
+  };
+
+  let outcomes: Vec<_> = match progress {
+    None => nodes.into_par_iter().map(process).collect(),
+    Some(progress) => {
+      // Process in rounds and report between them, so the callback only
+      // ever runs on this thread
+      let mut total = nodes.len();
+      let round_size = rayon::current_num_threads().max(1) * PROGRESS_NODES_PER_THREAD;
+      let mut outcomes = Vec::with_capacity(total);
+      let mut done = 0;
+
+      for round in nodes.chunks(round_size) {
+        let round_start = outcomes.len();
+        outcomes.par_extend(round.par_iter().copied().map(&process));
+
+        for outcome in &outcomes[round_start..] {
+          match outcome {
+            Ok(Some(_)) => done += 1,
+            Ok(None) | Err(_) => total -= 1,
+          }
+          progress(done, total);
+        }
+      }
+      outcomes
+    }
+  };
+
+  let mut results = Vec::with_capacity(outcomes.len());
+  let mut skipped = Vec::new();
+  for outcome in outcomes {
+    match outcome {
+      Ok(Some(result)) => results.push(result),
+      Ok(None) => {}
+      Err(node) => skipped.push(node),
+    }
+  }
+  (results, skipped)
+}
+
+/// Nodes meshed per worker thread between progress reports.
+const PROGRESS_NODES_PER_THREAD: usize = 4;
+
+/// Mesh one presampled node (stage 3), from its 16-bit samples and with its
+/// analytic normals if it has them.
+///
+/// Returns `None` when the volume produces no triangles.
+#[allow(clippy::too_many_arguments)]
+fn mesh_sampled_node(
+  node: OctreeNode,
+  sampled: SampledVolume,
+  sample_us: u64,
+  work_source: WorkSource,
+  leaves: &HashSet<OctreeNode>,
+  config: &OctreeConfig,
+  mesh_config: &MeshConfig,
+  timings: &StageTimings,
+) -> Option<MeshResult> {
+  // Start timing for this mesh
+  let mesh_start = web_time::Instant::now();
+
+  // Compute neighbor mask for seam handling
+  let neighbor_mask = compute_neighbor_mask(&node, leaves, config);
+
+  let mesh_config = node_mesh_config(mesh_config, &node, neighbor_mask, config);
+
+  // Generate mesh
+  let output = match &sampled.volume16 {
+    Some(volume16) => crate::surface_nets::generate16_with_normals(
+      volume16,
+      &sampled.materials,
+      sampled.normals.as_deref(),
+      &mesh_config,
+    ),
+    None => crate::surface_nets::generate_with_normals(
+      &sampled.volume,
+      &sampled.materials,
+      sampled.normals.as_deref(),
+      &mesh_config,
+    ),
+  };
+  VolumePool::global().release(sampled);
+
+  let mesh_elapsed = mesh_start.elapsed();
+  StageTimings::add(&timings.meshing_ns, mesh_elapsed);
+
+  if output.is_empty() {
+    return None;
+  }
+
+  let timing_us = sample_us + mesh_elapsed.as_micros() as u64;
+
+  Some(MeshResult {
+    node,
+    output,
+    timing_us,
+    work_source,
+  })
+}
+
 /// Process transition groups through the full pipeline.
 ///
 /// This is a synchronous function that uses rayon internally for parallelism.
@@ -94,6 +274,12 @@ fn compute_neighbor_mask(
 /// * `sampler` - Volume sampler for noise/terrain
 /// * `leaves` - Current leaf set (for neighbor mask computation)
 /// * `config` - Octree configuration
+/// * `progress` - Optional `(done, total)` callback for loading bars. Called on
+///   the calling thread (never concurrently) once per node, in bursts as each
+///   short round of meshing finishes. `done` counts surface chunks meshed;
+///   `total` starts at the number of nodes to mesh and drops as empty ones are
+///   found, ending at the number of surface chunks. Pass `None` when no
+///   feedback is needed.
 ///
 /// # Returns
 ///
@@ -105,105 +291,294 @@ pub fn process_transitions<S: VolumeSampler>(
   sampler: &S,
   leaves: &HashSet<OctreeNode>,
   config: &OctreeConfig,
+  progress: Option<&dyn Fn(usize, usize)>,
 ) -> Vec<ReadyChunk> {
+  let never_cancelled = AtomicBool::new(false);
+  run_transitions(
+    world_id,
+    transition_groups,
+    sampler,
+    leaves,
+    config,
+    &MeshConfig::default(),
+    &never_cancelled,
+    progress,
+    &StageTimings::default(),
+  )
+  .0
+}
+
+/// Process transition groups, stopping early once `cancelled` is set.
+///
+/// Same as `process_transitions`, but the presample and meshing stages check
+/// `cancelled` between nodes. After cancellation the remaining nodes are
+/// skipped, and every group with a skipped node is abandoned whole: none of
+/// its chunks are presented. Returns the chunks of the complete groups and
+/// the abandoned groups.
+pub fn process_transitions_cancellable<S: VolumeSampler>(
+  world_id: WorldId,
+  transition_groups: &[TransitionGroup],
+  sampler: &S,
+  leaves: &HashSet<OctreeNode>,
+  config: &OctreeConfig,
+  cancelled: &AtomicBool,
+) -> (Vec<ReadyChunk>, Vec<TransitionGroup>) {
+  run_transitions(
+    world_id,
+    transition_groups,
+    sampler,
+    leaves,
+    config,
+    &MeshConfig::default(),
+    cancelled,
+    None,
+    &StageTimings::default(),
+  )
+}
+
+/// Shared body of `process_transitions` and its cancellable and timed
+/// variants, returning the chunks and the groups abandoned after
+/// cancellation.
+#[allow(clippy::too_many_arguments)]
+fn run_transitions<S: VolumeSampler>(
+  world_id: WorldId,
+  transition_groups: &[TransitionGroup],
+  sampler: &S,
+  leaves: &HashSet<OctreeNode>,
+  config: &OctreeConfig,
+  mesh_config: &MeshConfig,
+  cancelled: &AtomicBool,
+  progress: Option<&dyn Fn(usize, usize)>,
+  timings: &StageTimings,
+) -> (Vec<ReadyChunk>, Vec<TransitionGroup>) {
   if transition_groups.is_empty() {
-    return Vec::new();
+    return (Vec::new(), Vec::new());
   }
 
   // Collect all nodes that need meshing
-  let nodes_to_mesh: Vec<OctreeNode> = transition_groups
+  let nodes: Vec<OctreeNode> = transition_groups
     .iter()
-    .flat_map(|group| match group.transition_type {
-      TransitionType::Subdivide => group.nodes_to_add.iter().copied().collect::<Vec<_>>(),
-      TransitionType::Merge => vec![group.group_key],
-    })
+    .flat_map(|group| nodes_to_mesh(group).iter().copied())
     .collect();
 
-  if nodes_to_mesh.is_empty() {
-    return Vec::new();
+  if nodes.is_empty() {
+    return (Vec::new(), Vec::new());
   }
 
-  // Stage 2 & 3: Parallel presample + meshing
-  let mesh_results: Vec<_> = nodes_to_mesh
-    .into_par_iter()
-    .filter_map(|node| {
-      // Start timing for this mesh
-      let mesh_start = web_time::Instant::now();
-
-      // Presample using centralized helper
-      let sampled = sample_volume_for_node(&node, sampler, config);
+  let (mesh_results, skipped) = presample_and_mesh(
+    nodes,
+    WorkSource::Refinement,
+    sampler,
+    leaves,
+    config,
+    mesh_config,
+    cancelled,
+    progress,
+    timings,
+  );
 
-      // Skip volumes with no surface crossings (all solid or all air)
-      if !has_surface_crossing(&sampled.volume) {
-        return None;
-      }
+  // A group with a skipped node is abandoned along with its finished meshes
+  let mut complete = Vec::new();
+  let mut abandoned = Vec::new();
+  if !skipped.is_empty() {
+    let skipped: HashSet<OctreeNode> = skipped.into_iter().collect();
+    (complete, abandoned) = transition_groups.iter().cloned().partition(|group| {
+      !nodes_to_mesh(group)
+        .iter()
+        .any(|node| skipped.contains(node))
+    });
+  }
+  let transition_groups = if abandoned.is_empty() {
+    transition_groups
+  } else {
+    &complete[..]
+  };
 
-      // Compute neighbor mask for seam handling
-      let neighbor_mask = compute_neighbor_mask(&node, leaves, config);
+  // Stage 4: Composition
+  let compose_start = web_time::Instant::now();
+  let composition_output = compose(mesh_results, transition_groups);
+  StageTimings::add(&timings.composition_ns, compose_start.elapsed());
 
-      // Create mesh config
-      let voxel_size = config.get_voxel_size(node.lod);
-      let mesh_config = MeshConfig::default()
-        .with_voxel_size(voxel_size as f32)
-        .with_neighbor_mask(neighbor_mask as u32);
+  // Stage 5: Presentation
+  let present_start = web_time::Instant::now();
+  let chunks = present(world_id, composition_output);
+  StageTimings::add(&timings.presentation_ns, present_start.elapsed());
 
-      // Generate mesh
-      let output = crate::surface_nets::generate(&sampled.volume, &sampled.materials, &mesh_config);
+  (chunks, abandoned)
+}
 
-      if output.is_empty() {
-        return None;
-      }
+/// Nodes a group sends through meshing: the children of a subdivide, the
+/// parent of a merge.
+fn nodes_to_mesh(group: &TransitionGroup) -> &[OctreeNode] {
+  match group.transition_type {
+    TransitionType::Subdivide => &group.nodes_to_add,
+    TransitionType::Merge => std::slice::from_ref(&group.group_key),
+  }
+}
 
-      let timing_us = mesh_start.elapsed().as_micros() as u64;
+/// Remesh nodes whose volume changed (e.g. after an edit).
+///
+/// Runs presample → meshing → presentation with `WorkSource::Invalidation`,
+/// so composition is bypassed and every chunk gets an `Immediate` hint.
+/// Nodes whose edited volume no longer contains a surface produce no chunk;
+/// callers should despawn the previous mesh for every invalidated node.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, name = "pipeline::process_invalidations"))]
+pub fn process_invalidations<S: VolumeSampler>(
+  world_id: WorldId,
+  nodes: &[OctreeNode],
+  sampler: &S,
+  leaves: &HashSet<OctreeNode>,
+  config: &OctreeConfig,
+) -> Vec<ReadyChunk> {
+  process_invalidations_with_mesh_config(
+    world_id,
+    nodes,
+    sampler,
+    leaves,
+    config,
+    &MeshConfig::default(),
+  )
+}
 
-      Some(super::types::MeshResult {
-        node,
-        output,
-        timing_us,
-        work_source: WorkSource::Refinement,
-      })
-    })
-    .collect();
+/// Like `process_invalidations`, meshing each chunk with `mesh_config`
+/// (its voxel size, neighbor mask and world origin are set per node).
+pub fn process_invalidations_with_mesh_config<S: VolumeSampler>(
+  world_id: WorldId,
+  nodes: &[OctreeNode],
+  sampler: &S,
+  leaves: &HashSet<OctreeNode>,
+  config: &OctreeConfig,
+  mesh_config: &MeshConfig,
+) -> Vec<ReadyChunk> {
+  if nodes.is_empty() {
+    return Vec::new();
+  }
 
-  // Stage 4: Composition
-  let composition_output = compose(mesh_results, transition_groups);
+  let (mesh_results, _) = presample_and_mesh(
+    nodes.to_vec(),
+    WorkSource::Invalidation,
+    sampler,
+    leaves,
+    config,
+    mesh_config,
+    &AtomicBool::new(false),
+    None,
+    &StageTimings::default(),
+  );
 
-  // Stage 5: Presentation
-  present(world_id, composition_output)
+  present_ungrouped(world_id, mesh_results)
 }
 
 /// Process transitions with timing information.
 ///
-/// Same as `process_transitions` but returns timing stats.
+/// Same as `process_transitions` but returns timing stats, including a
+/// per-stage breakdown (see `ProcessingStats`).
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, name = "pipeline::process_transitions_timed"))]
 pub fn process_transitions_timed<S: VolumeSampler>(
   world_id: WorldId,
   transition_groups: &[TransitionGroup],
   sampler: &S,
   leaves: &HashSet<OctreeNode>,
   config: &OctreeConfig,
+) -> (Vec<ReadyChunk>, ProcessingStats) {
+  process_transitions_timed_with_mesh_config(
+    world_id,
+    transition_groups,
+    sampler,
+    leaves,
+    config,
+    &MeshConfig::default(),
+  )
+}
+
+/// Like `process_transitions_timed`, meshing each chunk with `mesh_config`
+/// (its voxel size, neighbor mask and world origin are set per node).
+pub fn process_transitions_timed_with_mesh_config<S: VolumeSampler>(
+  world_id: WorldId,
+  transition_groups: &[TransitionGroup],
+  sampler: &S,
+  leaves: &HashSet<OctreeNode>,
+  config: &OctreeConfig,
+  mesh_config: &MeshConfig,
 ) -> (Vec<ReadyChunk>, ProcessingStats) {
   // WASM compat: std::time::Instant panics on wasm32
   use web_time::Instant;
 
+  let timings = StageTimings::default();
   let start = Instant::now();
-  let chunks = process_transitions(world_id, transition_groups, sampler, leaves, config);
+  let (chunks, _) = run_transitions(
+    world_id,
+    transition_groups,
+    sampler,
+    leaves,
+    config,
+    mesh_config,
+    &AtomicBool::new(false),
+    None,
+    &timings,
+  );
   let total_us = start.elapsed().as_micros() as u64;
 
   let stats = ProcessingStats {
     chunk_count: chunks.len(),
     total_us,
+    presample_us: StageTimings::micros(&timings.presample_ns),
+    meshing_us: StageTimings::micros(&timings.meshing_ns),
+    composition_us: StageTimings::micros(&timings.composition_ns),
+    presentation_us: StageTimings::micros(&timings.presentation_ns),
   };
 
   (chunks, stats)
 }
 
 /// Statistics from pipeline processing.
+///
+/// Stage times come from a monotonic clock. Presample and meshing run on the
+/// rayon pool and are summed over all workers, so with several threads they
+/// can exceed `total_us`; single-threaded, the stages add up to roughly
+/// `total_us`.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct ProcessingStats {
   /// Number of chunks produced.
   pub chunk_count: usize,
   /// Total processing time in microseconds.
   pub total_us: u64,
+  /// Time spent sampling volumes and checking for surface crossings.
+  pub presample_us: u64,
+  /// Time spent in surface nets, including neighbor masks.
+  pub meshing_us: u64,
+  /// Time spent grouping mesh results by transition.
+  pub composition_us: u64,
+  /// Time spent building ready chunks and presentation hints.
+  pub presentation_us: u64,
+}
+
+impl ProcessingStats {
+  /// Sum of the per-stage times.
+  pub fn stage_sum_us(&self) -> u64 {
+    self.presample_us + self.meshing_us + self.composition_us + self.presentation_us
+  }
+}
+
+/// Per-stage time accumulators shared by rayon workers.
+///
+/// Kept in nanoseconds so truncation doesn't add up over many small nodes.
+#[derive(Default)]
+struct StageTimings {
+  presample_ns: AtomicU64,
+  meshing_ns: AtomicU64,
+  composition_ns: AtomicU64,
+  presentation_ns: AtomicU64,
+}
+
+impl StageTimings {
+  fn add(counter: &AtomicU64, elapsed: Duration) {
+    counter.fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+  }
+
+  fn micros(counter: &AtomicU64) -> u64 {
+    counter.load(Ordering::Relaxed) / 1_000
+  }
 }
 
 #[cfg(test)]
@@ -234,6 +609,125 @@ mod tests {
         }
       }
     }
+
+    fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> i8 {
+      if position[2] / voxel_size < 16.0 {
+        -1
+      } else {
+        1
+      }
+    }
+  }
+
+  /// Shallow slope `y = 16 + 0.3x` in sample units, of material 2.
+  struct SlopeSampler;
+
+  impl SlopeSampler {
+    fn fill<T>(
+      volume: &mut [T; SAMPLE_SIZE_CB],
+      materials: &mut [u8; SAMPLE_SIZE_CB],
+      quantize: impl Fn(f32, f32) -> T,
+    ) {
+      for x in 0..32 {
+        for y in 0..32 {
+          for z in 0..32 {
+            let idx = x * 32 * 32 + y * 32 + z;
+            volume[idx] = quantize(y as f32 - 16.0 - 0.3 * x as f32, 1.0);
+            materials[idx] = 2;
+          }
+        }
+      }
+    }
+  }
+
+  impl VolumeSampler for SlopeSampler {
+    fn sample_volume(
+      &self,
+      _grid_offset: [i64; 3],
+      _voxel_size: f64,
+      volume: &mut [i8; SAMPLE_SIZE_CB],
+      materials: &mut [u8; SAMPLE_SIZE_CB],
+    ) {
+      Self::fill(volume, materials, crate::types::sdf_conversion::to_storage);
+    }
+
+    fn sample_volume16(
+      &self,
+      _grid_offset: [i64; 3],
+      _voxel_size: f64,
+      _phase: [f64; 3],
+      volume: &mut [i16; SAMPLE_SIZE_CB],
+      materials: &mut [u8; SAMPLE_SIZE_CB],
+    ) {
+      Self::fill(volume, materials, crate::types::sdf_conversion::to_storage16);
+    }
+
+    fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> i8 {
+      let [x, y, _] = position.map(|v| (v / voxel_size) as f32);
+      crate::types::sdf_conversion::to_storage(y - 16.0 - 0.3 * x, 1.0)
+    }
+  }
+
+  /// Records the names of spans created on the current thread.
+  #[cfg(feature = "tracing")]
+  #[derive(Default)]
+  struct SpanNames(std::sync::Arc<std::sync::Mutex<Vec<&'static str>>>);
+
+  #[cfg(feature = "tracing")]
+  impl tracing::Subscriber for SpanNames {
+    fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+      true
+    }
+
+    fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+      let mut names = self.0.lock().unwrap();
+      names.push(span.metadata().name());
+      tracing::span::Id::from_u64(names.len() as u64)
+    }
+
+    fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+
+    fn event(&self, _event: &tracing::Event<'_>) {}
+
+    fn enter(&self, _span: &tracing::span::Id) {}
+
+    fn exit(&self, _span: &tracing::span::Id) {}
+  }
+
+  #[cfg(feature = "tracing")]
+  #[test]
+  fn test_refine_and_process_emit_spans() {
+    let names = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let subscriber = SpanNames(names.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+      let config = OctreeConfig::default();
+      let parent = OctreeNode::new(0, 0, 0, 2);
+      crate::octree::refine(crate::octree::RefinementInput {
+        viewer_pos: glam::DVec3::ZERO,
+        view_frustum: None,
+        config: config.clone(),
+        prev_leaves: [parent].into_iter().collect(),
+        budget: crate::octree::RefinementBudget::default(),
+      });
+
+      let leaves: HashSet<_> = (0..8).filter_map(|octant| parent.get_child(octant)).collect();
+      let transition = TransitionGroup::new_subdivide(parent).unwrap();
+      process_transitions(WorldId::new(), &[transition], &TestSampler, &leaves, &config, None);
+    });
+
+    let names = names.lock().unwrap();
+    for expected in [
+      "octree::refine",
+      "octree::refine_multi",
+      "pipeline::process_transitions",
+      "pipeline::presample_and_mesh",
+      "composition::compose",
+    ] {
+      assert!(names.contains(&expected), "missing {} in {:?}", expected, names);
+    }
   }
 
   #[test]
@@ -243,7 +737,7 @@ mod tests {
     let sampler = TestSampler;
     let leaves = HashSet::new();
 
-    let result = process_transitions(world_id, &[], &sampler, &leaves, &config);
+    let result = process_transitions(world_id, &[], &sampler, &leaves, &config, None);
     assert!(result.is_empty());
   }
 
@@ -265,7 +759,7 @@ mod tests {
     // Create subdivide transition
     let transition = TransitionGroup::new_subdivide(parent).unwrap();
 
-    let result = process_transitions(world_id, &[transition], &sampler, &leaves, &config);
+    let result = process_transitions(world_id, &[transition], &sampler, &leaves, &config, None);
 
     // Should produce chunks for non-empty children
     assert!(!result.is_empty());
@@ -280,4 +774,307 @@ mod tests {
       }
     }
   }
+
+  #[test]
+  fn test_progress_reports_every_node() {
+    let world_id = WorldId::new();
+    let config = OctreeConfig::default();
+
+    // 4 subdivisions = 32 children, all crossing the TestSampler surface
+    let groups: Vec<_> = (0..4)
+      .map(|x| TransitionGroup::new_subdivide(OctreeNode::new(x, 0, 0, 1)).unwrap())
+      .collect();
+    let leaves: HashSet<_> = groups
+      .iter()
+      .flat_map(|g| g.nodes_to_add.iter().copied())
+      .collect();
+
+    let calls = std::cell::RefCell::new(Vec::new());
+    let progress = |done: usize, total: usize| calls.borrow_mut().push((done, total));
+    let result = process_transitions(
+      world_id,
+      &groups,
+      &TestSampler,
+      &leaves,
+      &config,
+      Some(&progress),
+    );
+
+    let calls = calls.into_inner();
+    assert_eq!(result.len(), 32);
+    assert_eq!(calls.len(), 32);
+    assert_eq!(calls.last(), Some(&(32, 32)));
+    assert!(calls.windows(2).all(|w| w[1].0 == w[0].0 + 1));
+  }
+
+  #[test]
+  fn test_progress_counts_surface_chunks() {
+    use crate::sdf_samplers::GroundPlaneSampler;
+
+    let world_id = WorldId::new();
+    let config = OctreeConfig::default();
+
+    // 2 subdivisions = 16 children; the plane crosses the bottom 8 halfway
+    // up, leaving the top 8 all air
+    let sampler = GroundPlaneSampler::new(config.get_cell_size(0) * 0.5);
+    let groups: Vec<_> = (0..2)
+      .map(|x| TransitionGroup::new_subdivide(OctreeNode::new(x, 0, 0, 1)).unwrap())
+      .collect();
+    let leaves: HashSet<_> = groups
+      .iter()
+      .flat_map(|g| g.nodes_to_add.iter().copied())
+      .collect();
+
+    let calls = std::cell::RefCell::new(Vec::new());
+    let progress = |done: usize, total: usize| calls.borrow_mut().push((done, total));
+    let result = process_transitions(
+      world_id,
+      &groups,
+      &sampler,
+      &leaves,
+      &config,
+      Some(&progress),
+    );
+
+    let calls = calls.into_inner();
+    assert_eq!(result.len(), 8);
+    assert_eq!(calls.len(), 16, "one report per node");
+    assert_eq!(calls.last(), Some(&(8, 8)));
+    assert!(calls
+      .iter()
+      .all(|&(done, total)| done <= total && total <= 16));
+    assert!(calls
+      .windows(2)
+      .all(|w| w[1].0 >= w[0].0 && w[1].1 <= w[0].1));
+  }
+
+  #[test]
+  fn test_ready_chunks_keep_group_order() {
+    let world_id = WorldId::new();
+    let config = OctreeConfig::default();
+
+    // Refinement emits groups nearest-first; far is submitted first here
+    let far = OctreeNode::new(6, 0, 0, 2);
+    let near = OctreeNode::new(0, 0, 0, 2);
+    let groups = vec![
+      TransitionGroup::new_subdivide(far).unwrap(),
+      TransitionGroup::new_subdivide(near).unwrap(),
+    ];
+    let leaves: HashSet<_> = groups
+      .iter()
+      .flat_map(|g| g.nodes_to_add.iter().copied())
+      .collect();
+
+    let result = process_transitions(world_id, &groups, &TestSampler, &leaves, &config, None);
+
+    let group_keys: Vec<OctreeNode> = result
+      .iter()
+      .map(|chunk| match &chunk.hint {
+        super::super::types::PresentationHint::FadeIn { group_key } => *group_key,
+        _ => panic!("Expected FadeIn hint"),
+      })
+      .collect();
+    let first_near = group_keys.iter().position(|key| *key == near).unwrap();
+    assert!(group_keys[..first_near].iter().all(|key| *key == far));
+    assert!(group_keys[first_near..].iter().all(|key| *key == near));
+    assert_eq!(first_near, 8);
+  }
+
+  #[test]
+  fn test_stage_timings_sum_to_total_single_threaded() {
+    let world_id = WorldId::new();
+    let config = OctreeConfig::default();
+
+    let groups: Vec<_> = (0..4)
+      .map(|x| TransitionGroup::new_subdivide(OctreeNode::new(x, 0, 0, 1)).unwrap())
+      .collect();
+    let leaves: HashSet<_> = groups
+      .iter()
+      .flat_map(|g| g.nodes_to_add.iter().copied())
+      .collect();
+
+    let pool = rayon::ThreadPoolBuilder::new()
+      .num_threads(1)
+      .build()
+      .unwrap();
+    let (chunks, stats) =
+      pool.install(|| process_transitions_timed(world_id, &groups, &TestSampler, &leaves, &config));
+
+    assert_eq!(stats.chunk_count, chunks.len());
+    assert!(stats.presample_us > 0);
+    assert!(stats.meshing_us > 0);
+
+    // One worker: stages can't overlap, and only bookkeeping between them
+    // goes unaccounted
+    let sum = stats.stage_sum_us();
+    assert!(sum <= stats.total_us, "{:?}", stats);
+    assert!(
+      stats.total_us - sum <= stats.total_us / 10 + 200,
+      "{:?}",
+      stats
+    );
+  }
+
+  /// `TestSampler` that records which rayon worker sampled each volume.
+  #[derive(Default)]
+  struct ThreadRecordingSampler {
+    threads: std::sync::Mutex<HashSet<Option<usize>>>,
+  }
+
+  impl VolumeSampler for ThreadRecordingSampler {
+    fn sample_volume(
+      &self,
+      grid_offset: [i64; 3],
+      voxel_size: f64,
+      volume: &mut [i8; SAMPLE_SIZE_CB],
+      materials: &mut [u8; SAMPLE_SIZE_CB],
+    ) {
+      self
+        .threads
+        .lock()
+        .unwrap()
+        .insert(rayon::current_thread_index());
+      TestSampler.sample_volume(grid_offset, voxel_size, volume, materials);
+    }
+
+    fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> i8 {
+      TestSampler.sample_point(position, voxel_size)
+    }
+  }
+
+  /// `TestSampler` that raises `cancelled` once it has sampled `samples`
+  /// volumes.
+  struct CancellingSampler<'a> {
+    samples: std::sync::atomic::AtomicUsize,
+    cancelled: &'a AtomicBool,
+  }
+
+  impl VolumeSampler for CancellingSampler<'_> {
+    fn sample_volume(
+      &self,
+      grid_offset: [i64; 3],
+      voxel_size: f64,
+      volume: &mut [i8; SAMPLE_SIZE_CB],
+      materials: &mut [u8; SAMPLE_SIZE_CB],
+    ) {
+      if self.samples.fetch_sub(1, Ordering::Relaxed) == 1 {
+        self.cancelled.store(true, Ordering::Relaxed);
+      }
+      TestSampler.sample_volume(grid_offset, voxel_size, volume, materials);
+    }
+
+    fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> i8 {
+      TestSampler.sample_point(position, voxel_size)
+    }
+  }
+
+  #[test]
+  fn test_cancelled_groups_are_abandoned_whole() {
+    let config = OctreeConfig::default();
+    let groups: Vec<_> = (0..4)
+      .map(|x| TransitionGroup::new_subdivide(OctreeNode::new(x, 0, 0, 1)).unwrap())
+      .collect();
+    let leaves: HashSet<_> = groups
+      .iter()
+      .flat_map(|g| g.nodes_to_add.iter().copied())
+      .collect();
+    let executor = crate::threading::TaskExecutor::single_threaded().unwrap();
+
+    // Cancelled halfway through the second group's children
+    let cancelled = AtomicBool::new(false);
+    let sampler = CancellingSampler {
+      samples: 12.into(),
+      cancelled: &cancelled,
+    };
+    let (chunks, abandoned) = executor.install(|| {
+      process_transitions_cancellable(
+        WorldId::new(),
+        &groups,
+        &sampler,
+        &leaves,
+        &config,
+        &cancelled,
+      )
+    });
+
+    // The finished children of the second group are dropped with it
+    let nodes: Vec<OctreeNode> = chunks.iter().map(|c| c.node).collect();
+    assert_eq!(nodes, groups[0].nodes_to_add.to_vec());
+    let abandoned: Vec<OctreeNode> = abandoned.iter().map(|g| g.group_key).collect();
+    let expected: Vec<OctreeNode> = groups[1..].iter().map(|g| g.group_key).collect();
+    assert_eq!(abandoned, expected);
+  }
+
+  #[test]
+  fn test_single_threaded_runs_are_identical_and_ordered() {
+    let config = OctreeConfig::default();
+    let groups: Vec<_> = (0..4)
+      .map(|x| TransitionGroup::new_subdivide(OctreeNode::new(x, 0, 0, 1)).unwrap())
+      .collect();
+    let leaves: HashSet<_> = groups
+      .iter()
+      .flat_map(|g| g.nodes_to_add.iter().copied())
+      .collect();
+    let executor = crate::threading::TaskExecutor::single_threaded().unwrap();
+    let sampler = ThreadRecordingSampler::default();
+
+    let world_id = WorldId::new();
+    let run = || {
+      executor.install(|| process_transitions(world_id, &groups, &sampler, &leaves, &config, None))
+    };
+    let first = run();
+    let second = run();
+
+    // Every volume was sampled on the executor's only worker
+    let threads = sampler.threads.into_inner().unwrap();
+    assert_eq!(threads, HashSet::from([Some(0)]));
+
+    // Input order: groups in order, each group's children in order
+    let expected: Vec<OctreeNode> = groups
+      .iter()
+      .flat_map(|g| g.nodes_to_add.iter().copied())
+      .collect();
+    let nodes = |chunks: &[ReadyChunk]| chunks.iter().map(|c| c.node).collect::<Vec<_>>();
+    assert_eq!(nodes(&first), expected);
+    assert_eq!(nodes(&second), expected);
+
+    for (a, b) in first.iter().zip(&second) {
+      assert_eq!(a.output.vertices, b.output.vertices);
+      assert_eq!(a.output.indices, b.output.indices);
+    }
+  }
+
+  #[test]
+  fn test_sdf16_mesh_config_meshes_16bit_samples_with_materials() {
+    let config = OctreeConfig::default();
+    let node = OctreeNode::new(0, 0, 0, 0);
+    let leaves: HashSet<_> = [node].into_iter().collect();
+    let mesh = |mesh_config: MeshConfig| {
+      let mut chunks = process_invalidations_with_mesh_config(
+        WorldId::new(),
+        &[node],
+        &SlopeSampler,
+        &leaves,
+        &config,
+        &mesh_config,
+      );
+      chunks.remove(0).output
+    };
+    // Largest vertical distance from a vertex to the slope
+    let worst_error = |output: &crate::types::MeshOutput| {
+      output
+        .vertices
+        .iter()
+        .map(|v| (v.position[1] - 16.0 - 0.3 * v.position[0]).abs())
+        .fold(0.0f32, f32::max)
+    };
+
+    let narrow = mesh(MeshConfig::default());
+    let wide = mesh(MeshConfig::default().with_sdf16(true));
+
+    assert!(!wide.vertices.is_empty());
+    assert!(worst_error(&wide) < 0.01, "16-bit error {}", worst_error(&wide));
+    assert!(worst_error(&wide) < worst_error(&narrow));
+    assert!(wide.vertices.iter().all(|v| v.material_weights[2] > 0.99));
+  }
 }
@@ -11,7 +11,7 @@ use super::types::{MeshResult, VolumeSampler, WorkSource};
 use crate::constants::{SAMPLE_SIZE, SAMPLE_SIZE_CB};
 use crate::octree::{OctreeConfig, OctreeNode, TransitionGroup};
 use crate::surface_nets;
-use crate::types::{sdf_conversion, MaterialId, MeshConfig, MeshOutput, SdfSample};
+use crate::types::{sdf_conversion, MaterialId, MeshConfig, MeshOutput, SdfConvention, SdfSample};
 
 // =============================================================================
 // Mock Volume Samplers
@@ -45,16 +45,28 @@ impl VolumeSampler for SphereSampler {
     volume: &mut [SdfSample; SAMPLE_SIZE_CB],
     materials: &mut [MaterialId; SAMPLE_SIZE_CB],
   ) {
+    self.sample_volume_with_phase(grid_offset, voxel_size, [0.0; 3], volume, materials);
+  }
+
+  fn sample_volume_with_phase(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    let phase = DVec3::from_array(phase);
     for x in 0..SAMPLE_SIZE {
       for y in 0..SAMPLE_SIZE {
         for z in 0..SAMPLE_SIZE {
           let idx = x * SAMPLE_SIZE * SAMPLE_SIZE + y * SAMPLE_SIZE + z;
-          // world_pos = (grid_offset + sample_index) * voxel_size
+          // world_pos = (grid_offset + sample_index) * voxel_size + phase
           let world_pos = DVec3::new(
             (grid_offset[0] + x as i64) as f64 * voxel_size,
             (grid_offset[1] + y as i64) as f64 * voxel_size,
             (grid_offset[2] + z as i64) as f64 * voxel_size,
-          );
+          ) + phase;
           let dist = (world_pos - self.center).length() - self.radius;
           volume[idx] = sdf_conversion::to_storage(dist as f32, voxel_size as f32);
           materials[idx] = 0;
@@ -62,6 +74,11 @@ impl VolumeSampler for SphereSampler {
       }
     }
   }
+
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    let dist = (DVec3::from_array(position) - self.center).length() - self.radius;
+    sdf_conversion::to_storage(dist as f32, voxel_size as f32)
+  }
 }
 
 /// Constant SDF sampler - returns same value everywhere.
@@ -106,6 +123,10 @@ impl VolumeSampler for ConstantSampler {
     volume.fill(self.value);
     materials.fill(self.material);
   }
+
+  fn sample_point(&self, _position: [f64; 3], _voxel_size: f64) -> SdfSample {
+    self.value
+  }
 }
 
 /// Plane SDF - divides space by a plane.
@@ -152,6 +173,11 @@ impl VolumeSampler for PlaneSampler {
       }
     }
   }
+
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    let dist = (DVec3::from_array(position) - self.point).dot(self.normal);
+    sdf_conversion::to_storage(dist as f32, voxel_size as f32)
+  }
 }
 
 /// Corner-controlled sampler for testing specific corner configurations.
@@ -224,7 +250,7 @@ impl CornerSampler {
   }
 
   /// Sample single point with trilinear interpolation.
-  fn sample_point(&self, world_pos: DVec3) -> SdfSample {
+  fn interpolate(&self, world_pos: DVec3) -> SdfSample {
     // Normalize position to [0, 1] within chunk bounds
     let size = self.max - self.min;
     let t = (world_pos - self.min) / size;
@@ -263,12 +289,16 @@ impl VolumeSampler for CornerSampler {
             (grid_offset[1] + y as i64) as f64 * voxel_size,
             (grid_offset[2] + z as i64) as f64 * voxel_size,
           );
-          volume[idx] = self.sample_point(world_pos);
+          volume[idx] = self.interpolate(world_pos);
           materials[idx] = 0;
         }
       }
     }
   }
+
+  fn sample_point(&self, position: [f64; 3], _voxel_size: f64) -> SdfSample {
+    self.interpolate(DVec3::from_array(position))
+  }
 }
 
 /// Counting sampler wrapper - tracks number of sample_volume() calls.
@@ -309,6 +339,28 @@ impl<S: VolumeSampler> VolumeSampler for CountingSampler<S> {
       .inner
       .sample_volume(grid_offset, voxel_size, volume, materials)
   }
+
+  fn sample_volume_with_phase(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    self.sample_count.fetch_add(1, Ordering::SeqCst);
+    self
+      .inner
+      .sample_volume_with_phase(grid_offset, voxel_size, phase, volume, materials)
+  }
+
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    self.inner.sample_point(position, voxel_size)
+  }
+
+  fn sdf_convention(&self) -> SdfConvention {
+    self.inner.sdf_convention()
+  }
 }
 
 // =============================================================================
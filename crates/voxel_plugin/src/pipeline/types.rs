@@ -71,12 +71,14 @@
 //! └─────────────────────────────────────────────────────────────────────────────┘
 //! ```
 
+use std::sync::Arc;
+
 use glam::DVec3;
 use smallvec::SmallVec;
 
 use crate::constants::SAMPLE_SIZE_CB;
-use crate::octree::{OctreeNode, TransitionType};
-use crate::types::{MaterialId, MeshConfig, MeshOutput, SdfSample};
+use crate::octree::{OctreeNode, TransitionGroup, TransitionType};
+use crate::types::{MaterialId, MeshConfig, MeshOutput, Sdf16, SdfConvention, SdfSample};
 use crate::world::WorldId;
 
 // =============================================================================
@@ -107,6 +109,12 @@ pub enum WorkSource {
 pub struct SampledVolume {
   pub volume: Box<[SdfSample; SAMPLE_SIZE_CB]>,
   pub materials: Box<[MaterialId; SAMPLE_SIZE_CB]>,
+  /// Analytic normals (`VolumeSampler::sample_normals`), sampled when the
+  /// chunk is meshed with `MeshConfig::prefer_analytic_normals`.
+  pub normals: Option<Box<[[f32; 3]; SAMPLE_SIZE_CB]>>,
+  /// 16-bit samples, when the chunk is meshed with `MeshConfig::use_sdf16`;
+  /// `volume` then holds them saturated to i8.
+  pub volume16: Option<Box<[Sdf16; SAMPLE_SIZE_CB]>>,
 }
 
 impl std::fmt::Debug for SampledVolume {
@@ -115,6 +123,19 @@ impl std::fmt::Debug for SampledVolume {
   }
 }
 
+/// Sampled volume with 16-bit SDF samples (see `surface_nets::generate16`).
+#[derive(Clone)]
+pub struct SampledVolume16 {
+  pub volume: Box<[Sdf16; SAMPLE_SIZE_CB]>,
+  pub materials: Box<[MaterialId; SAMPLE_SIZE_CB]>,
+}
+
+impl std::fmt::Debug for SampledVolume16 {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "SampledVolume16([32³ samples])")
+  }
+}
+
 /// Output from presampling a single node.
 pub struct PresampleOutput {
   /// The node that was presampled.
@@ -157,6 +178,88 @@ pub trait VolumeSampler: Send + Sync {
     volume: &mut [SdfSample; SAMPLE_SIZE_CB],
     materials: &mut [MaterialId; SAMPLE_SIZE_CB],
   );
+
+  /// Sample a 32x32x32 SDF volume shifted by a sub-voxel phase.
+  ///
+  /// Sample at grid position (x, y, z) corresponds to world position:
+  /// `world_pos = (grid_offset + [x, y, z]) * voxel_size + phase`
+  ///
+  /// The phase is the residual of `world_origin` that does not fall on the
+  /// LOD's voxel lattice (see `OctreeConfig::get_sample_phase`). Honouring it
+  /// keeps a coarse node's samples exactly on its children's sample grid,
+  /// which removes the surface shift (pop) on LOD transitions.
+  ///
+  /// The default implementation ignores the phase and falls back to
+  /// `sample_volume`, which is exact whenever `world_origin` is aligned.
+  fn sample_volume_with_phase(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    let _ = phase;
+    self.sample_volume(grid_offset, voxel_size, volume, materials)
+  }
+
+  /// Sample analytic surface normals (unit SDF gradients) at the same 32³
+  /// positions as `sample_volume_with_phase`.
+  ///
+  /// Quantized i8 samples lose precision on smooth fields, so samplers with
+  /// a closed-form gradient can provide exact normals here; see
+  /// `MeshConfig::prefer_analytic_normals`. Returns false (leaving `normals`
+  /// untouched) if the sampler has no analytic normals, which is the default.
+  fn sample_normals(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    normals: &mut [[f32; 3]; SAMPLE_SIZE_CB],
+  ) -> bool {
+    let _ = (grid_offset, voxel_size, phase, normals);
+    false
+  }
+
+  /// Sample a 32x32x32 volume with 16-bit SDF values
+  /// (`sdf_conversion::to_storage16`), at the same positions as
+  /// `sample_volume_with_phase`.
+  ///
+  /// i8 storage saturates a tenth of a voxel from the surface, which shows as
+  /// terracing on shallow slopes. Samplers that can evaluate their SDF
+  /// directly should override this; the default widens the i8 samples and so
+  /// gains no precision.
+  fn sample_volume16(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [Sdf16; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    let mut narrow = Box::new([0; SAMPLE_SIZE_CB]);
+    self.sample_volume_with_phase(grid_offset, voxel_size, phase, &mut narrow, materials);
+    for (out, &sample) in volume.iter_mut().zip(narrow.iter()) {
+      *out = sample as Sdf16;
+    }
+  }
+
+  /// Sample the SDF at a single world position, quantized as `sample_volume`
+  /// quantizes at `voxel_size`.
+  ///
+  /// For point queries (raycasts) that need a few samples, not a block.
+  /// Samplers evaluate their SDF at `position` directly; there is no default,
+  /// since sampling a whole 32³ block per point would allocate 64 KB a call.
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample;
+
+  /// Sign convention of the samples (and normals) this sampler writes.
+  ///
+  /// Samples are normalised to negative-inside where the crate reads them,
+  /// so positive-inside samplers only need to declare it here. Defaults to
+  /// `SdfConvention::NegativeInside`.
+  fn sdf_convention(&self) -> SdfConvention {
+    SdfConvention::NegativeInside
+  }
 }
 
 /// Blanket impl for boxed trait objects.
@@ -170,6 +273,100 @@ impl VolumeSampler for Box<dyn VolumeSampler> {
   ) {
     (**self).sample_volume(grid_offset, voxel_size, volume, materials)
   }
+
+  fn sample_volume_with_phase(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    (**self).sample_volume_with_phase(grid_offset, voxel_size, phase, volume, materials)
+  }
+
+  fn sample_normals(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    normals: &mut [[f32; 3]; SAMPLE_SIZE_CB],
+  ) -> bool {
+    (**self).sample_normals(grid_offset, voxel_size, phase, normals)
+  }
+
+  fn sample_volume16(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [Sdf16; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    (**self).sample_volume16(grid_offset, voxel_size, phase, volume, materials)
+  }
+
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    (**self).sample_point(position, voxel_size)
+  }
+
+  fn sdf_convention(&self) -> SdfConvention {
+    (**self).sdf_convention()
+  }
+}
+
+/// Blanket impl for shared trait objects, e.g. for samplers handed to
+/// background meshing (see `VoxelWorld::begin_update_multi`).
+impl VolumeSampler for Arc<dyn VolumeSampler> {
+  fn sample_volume(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    (**self).sample_volume(grid_offset, voxel_size, volume, materials)
+  }
+
+  fn sample_volume_with_phase(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    (**self).sample_volume_with_phase(grid_offset, voxel_size, phase, volume, materials)
+  }
+
+  fn sample_normals(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    normals: &mut [[f32; 3]; SAMPLE_SIZE_CB],
+  ) -> bool {
+    (**self).sample_normals(grid_offset, voxel_size, phase, normals)
+  }
+
+  fn sample_volume16(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [Sdf16; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    (**self).sample_volume16(grid_offset, voxel_size, phase, volume, materials)
+  }
+
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    (**self).sample_point(position, voxel_size)
+  }
+
+  fn sdf_convention(&self) -> SdfConvention {
+    (**self).sdf_convention()
+  }
 }
 
 // =============================================================================
@@ -302,7 +499,7 @@ impl std::fmt::Debug for ReadyChunk {
 /// and handle presentation: spawning/despawning engine-specific entities.
 ///
 /// Events are always emitted in order: `NodesExpired` before `ChunksReady`
-/// for the same refinement cycle.
+/// before `GroupsAbandoned` for the same refinement cycle.
 #[derive(Debug)]
 pub enum PipelineEvent {
   /// Nodes should be despawned (their meshes are now stale).
@@ -322,6 +519,16 @@ pub enum PipelineEvent {
     world_id: WorldId,
     chunks: Vec<ReadyChunk>,
   },
+
+  /// Transition groups a cancelled batch did not apply.
+  ///
+  /// None of their nodes were expired or presented, so the nodes they would
+  /// have removed are still presented. Integration should enqueue them again
+  /// (or roll back their leaves).
+  GroupsAbandoned {
+    world_id: WorldId,
+    groups: Vec<TransitionGroup>,
+  },
 }
 
 // =============================================================================
@@ -0,0 +1,144 @@
+//! Raymarched preview images of a `VolumeSampler`.
+//!
+//! A quick sanity check for a sampler before committing it to a full world:
+//! each pixel marches its ray through the sampled SDF and is shaded by the
+//! surface normal at the first hit. Dev tool only; it samples whole 32³
+//! blocks on demand and is far too slow for runtime use.
+
+use std::collections::HashMap;
+
+use glam::DVec3;
+use image::{Rgba, RgbaImage};
+
+use crate::constants::SAMPLE_SIZE_CB;
+use crate::pipeline::VolumeSampler;
+use crate::types::{MaterialId, SdfSample};
+use crate::world::{sample_trilinear, sdf_gradient};
+
+/// Color of pixels whose ray hits nothing (transparent).
+pub const BACKGROUND: Rgba<u8> = Rgba([0, 0, 0, 0]);
+
+/// Samples between block origins; blocks overlap by one sample so trilinear
+/// lookups never leave a block.
+const BLOCK_STRIDE: i64 = 31;
+
+/// Pinhole camera for [`raymarch_preview`].
+#[derive(Clone, Copy, Debug)]
+pub struct PreviewCamera {
+  pub position: DVec3,
+  pub target: DVec3,
+  /// Vertical field of view in degrees.
+  pub fov_y_deg: f64,
+  /// Sample spacing in world units; rays step half of this.
+  pub voxel_size: f64,
+  /// Maximum ray length in world units.
+  pub max_distance: f64,
+}
+
+impl PreviewCamera {
+  /// Camera at `position` looking at `target`, 60° FOV, unit voxels and
+  /// 256 units of view distance.
+  pub fn looking_at(position: DVec3, target: DVec3) -> Self {
+    Self {
+      position,
+      target,
+      fov_y_deg: 60.0,
+      voxel_size: 1.0,
+      max_distance: 256.0,
+    }
+  }
+
+  pub fn with_fov(mut self, fov_y_deg: f64) -> Self {
+    self.fov_y_deg = fov_y_deg;
+    self
+  }
+
+  pub fn with_voxel_size(mut self, voxel_size: f64) -> Self {
+    self.voxel_size = voxel_size;
+    self
+  }
+
+  pub fn with_max_distance(mut self, max_distance: f64) -> Self {
+    self.max_distance = max_distance;
+    self
+  }
+}
+
+/// Lazily sampled 32³ blocks on the preview's voxel lattice.
+struct BlockCache<'a, S> {
+  sampler: &'a S,
+  voxel_size: f64,
+  blocks: HashMap<[i64; 3], Box<[SdfSample; SAMPLE_SIZE_CB]>>,
+}
+
+impl<S: VolumeSampler> BlockCache<'_, S> {
+  /// Interpolated SDF (storage units) and gradient at `position`.
+  fn sample(&mut self, position: DVec3) -> (f64, DVec3) {
+    let grid = position / self.voxel_size;
+    let block = (grid / BLOCK_STRIDE as f64).floor();
+    let key = [block.x as i64, block.y as i64, block.z as i64];
+    let local = grid - block * BLOCK_STRIDE as f64;
+
+    let (sampler, voxel_size) = (self.sampler, self.voxel_size);
+    let volume = self.blocks.entry(key).or_insert_with(|| {
+      let mut volume = Box::new([0; SAMPLE_SIZE_CB]);
+      let mut materials: Box<[MaterialId; SAMPLE_SIZE_CB]> = Box::new([0; SAMPLE_SIZE_CB]);
+      let offset = key.map(|k| k * BLOCK_STRIDE);
+      sampler.sample_volume(offset, voxel_size, &mut volume, &mut materials);
+      sampler.sdf_convention().normalize(volume.as_mut_slice());
+      volume
+    });
+    (sample_trilinear(volume, local), sdf_gradient(volume, local))
+  }
+}
+
+/// Render `sampler` from `camera` into a `width` x `height` image.
+///
+/// Hits are shaded by normal (`rgb = normal * 0.5 + 0.5`, opaque); misses
+/// are [`BACKGROUND`].
+pub fn raymarch_preview<S: VolumeSampler>(
+  sampler: &S,
+  camera: &PreviewCamera,
+  width: u32,
+  height: u32,
+) -> RgbaImage {
+  let mut image = RgbaImage::from_pixel(width, height, BACKGROUND);
+  let Some(forward) = (camera.target - camera.position).try_normalize() else {
+    return image;
+  };
+  let right = forward.cross(DVec3::Y).try_normalize().unwrap_or(DVec3::X);
+  let up = right.cross(forward);
+  let half_height = (camera.fov_y_deg.to_radians() * 0.5).tan();
+  let half_width = half_height * width as f64 / height.max(1) as f64;
+
+  let mut cache = BlockCache {
+    sampler,
+    voxel_size: camera.voxel_size,
+    blocks: HashMap::new(),
+  };
+  let step = 0.5 * camera.voxel_size;
+
+  for (px, py, pixel) in image.enumerate_pixels_mut() {
+    let ndc_x = (px as f64 + 0.5) / width as f64 * 2.0 - 1.0;
+    let ndc_y = 1.0 - (py as f64 + 0.5) / height as f64 * 2.0;
+    let dir = (forward + right * (ndc_x * half_width) + up * (ndc_y * half_height)).normalize();
+
+    let mut t = 0.0;
+    while t <= camera.max_distance {
+      let (value, gradient) = cache.sample(camera.position + dir * t);
+      if value < 0.0 {
+        let normal = gradient.try_normalize().unwrap_or(-dir);
+        let rgb = (normal * 0.5 + 0.5) * 255.0;
+        *pixel = Rgba([rgb.x as u8, rgb.y as u8, rgb.z as u8, 255]);
+        break;
+      }
+      t += step;
+    }
+  }
+
+  image
+}
+
+#[cfg(test)]
+#[path = "preview_test.rs"]
+mod preview_test;
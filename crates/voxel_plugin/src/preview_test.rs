@@ -0,0 +1,28 @@
+use super::*;
+use crate::sdf_samplers::SphereSampler;
+
+#[test]
+fn test_sphere_preview_hits_center_and_misses_corners() {
+  let sampler = SphereSampler::new(10.0);
+  let camera =
+    PreviewCamera::looking_at(DVec3::new(0.0, 0.0, -40.0), DVec3::ZERO).with_max_distance(80.0);
+  let image = raymarch_preview(&sampler, &camera, 32, 32);
+
+  // Sphere faces the camera: center pixels hit with a normal pointing back
+  // at the camera (-Z, blue channel low)
+  for (x, y) in [(15, 15), (16, 16), (15, 16), (16, 15)] {
+    let pixel = image.get_pixel(x, y);
+    assert_eq!(pixel[3], 255, "center pixel ({}, {}) should hit", x, y);
+    assert!(pixel[2] < 64, "normal should face the camera: {:?}", pixel);
+  }
+
+  for (x, y) in [(0, 0), (31, 0), (0, 31), (31, 31)] {
+    assert_eq!(
+      *image.get_pixel(x, y),
+      BACKGROUND,
+      "corner ({}, {}) should miss",
+      x,
+      y
+    );
+  }
+}
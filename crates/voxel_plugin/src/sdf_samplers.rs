@@ -4,9 +4,14 @@
 //! easy to verify visually. Use them to test chunk tiling coherency
 //! without noise generation complexity.
 
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use glam::DVec3;
+
 use crate::constants::{SAMPLE_SIZE, SAMPLE_SIZE_CB};
 use crate::pipeline::VolumeSampler;
-use crate::types::{sdf_conversion, MaterialId, SdfSample};
+use crate::types::{sdf_conversion, MaterialId, Sdf16, SdfSample, SdfValue};
 
 /// Tilted plane SDF sampler.
 ///
@@ -47,6 +52,13 @@ impl TiltedPlaneSampler {
     self.angle = degrees.to_radians();
     self
   }
+
+  /// The plane's SDF as a function of world position: distance to the plane
+  /// tilted around Z, whose normal is (sin(angle), cos(angle), 0).
+  fn sdf(&self) -> impl Fn([f64; 3]) -> f64 + '_ {
+    let (sin_a, cos_a) = self.angle.sin_cos();
+    move |p| (p[1] - self.height) * cos_a - p[0] * sin_a
+  }
 }
 
 impl VolumeSampler for TiltedPlaneSampler {
@@ -57,27 +69,35 @@ impl VolumeSampler for TiltedPlaneSampler {
     volume: &mut [SdfSample; SAMPLE_SIZE_CB],
     materials: &mut [MaterialId; SAMPLE_SIZE_CB],
   ) {
-    let cos_a = self.angle.cos();
-    let sin_a = self.angle.sin();
+    self.sample_volume_with_phase(grid_offset, voxel_size, [0.0; 3], volume, materials);
+  }
 
-    for xi in 0..SAMPLE_SIZE {
-      for yi in 0..SAMPLE_SIZE {
-        for zi in 0..SAMPLE_SIZE {
-          // World position = (grid_offset + sample_index) * voxel_size
-          let wx = (grid_offset[0] + xi as i64) as f64 * voxel_size;
-          let wy = (grid_offset[1] + yi as i64) as f64 * voxel_size;
-          let _wz = (grid_offset[2] + zi as i64) as f64 * voxel_size;
+  fn sample_volume_with_phase(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    let sdf = self.sdf();
+    fill_volume(grid_offset, voxel_size, phase, volume, materials, sdf);
+  }
 
-          // Tilted plane SDF: distance to plane tilted around Z axis
-          // Plane normal = (sin(angle), cos(angle), 0)
-          let sdf = (wy - self.height) * cos_a - wx * sin_a;
+  fn sample_volume16(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [Sdf16; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    let sdf = self.sdf();
+    fill_volume(grid_offset, voxel_size, phase, volume, materials, sdf);
+  }
 
-          let idx = xi * SAMPLE_SIZE * SAMPLE_SIZE + yi * SAMPLE_SIZE + zi;
-          volume[idx] = sdf_conversion::to_storage(sdf as f32, voxel_size as f32);
-          materials[idx] = 0;
-        }
-      }
-    }
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    sdf_conversion::to_storage(self.sdf()(position) as f32, voxel_size as f32)
   }
 }
 
@@ -114,6 +134,11 @@ impl SphereSampler {
     self.center = center;
     self
   }
+
+  /// The sphere's SDF as a function of world position: `|p - center| - radius`.
+  fn sdf(&self) -> impl Fn([f64; 3]) -> f64 + '_ {
+    |p| (DVec3::from_array(p) - DVec3::from_array(self.center)).length() - self.radius
+  }
 }
 
 impl VolumeSampler for SphereSampler {
@@ -124,27 +149,49 @@ impl VolumeSampler for SphereSampler {
     volume: &mut [SdfSample; SAMPLE_SIZE_CB],
     materials: &mut [MaterialId; SAMPLE_SIZE_CB],
   ) {
-    for xi in 0..SAMPLE_SIZE {
-      for yi in 0..SAMPLE_SIZE {
-        for zi in 0..SAMPLE_SIZE {
-          // World position = (grid_offset + sample_index) * voxel_size
-          let wx = (grid_offset[0] + xi as i64) as f64 * voxel_size;
-          let wy = (grid_offset[1] + yi as i64) as f64 * voxel_size;
-          let wz = (grid_offset[2] + zi as i64) as f64 * voxel_size;
-
-          // Sphere SDF: distance to surface = |p - center| - radius
-          let dx = wx - self.center[0];
-          let dy = wy - self.center[1];
-          let dz = wz - self.center[2];
-          let dist = (dx * dx + dy * dy + dz * dz).sqrt();
-          let sdf = dist - self.radius;
+    self.sample_volume_with_phase(grid_offset, voxel_size, [0.0; 3], volume, materials);
+  }
 
-          let idx = xi * SAMPLE_SIZE * SAMPLE_SIZE + yi * SAMPLE_SIZE + zi;
-          volume[idx] = sdf_conversion::to_storage(sdf as f32, voxel_size as f32);
-          materials[idx] = 0;
-        }
-      }
-    }
+  fn sample_volume_with_phase(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    let sdf = self.sdf();
+    fill_volume(grid_offset, voxel_size, phase, volume, materials, sdf);
+  }
+
+  fn sample_normals(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    normals: &mut [[f32; 3]; SAMPLE_SIZE_CB],
+  ) -> bool {
+    // Gradient of |p - center| is the radial direction
+    fill_normals(grid_offset, voxel_size, phase, normals, |p| {
+      std::array::from_fn(|axis| p[axis] - self.center[axis])
+    });
+    true
+  }
+
+  fn sample_volume16(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [Sdf16; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    let sdf = self.sdf();
+    fill_volume(grid_offset, voxel_size, phase, volume, materials, sdf);
+  }
+
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    sdf_conversion::to_storage(self.sdf()(position) as f32, voxel_size as f32)
   }
 }
 
@@ -167,6 +214,12 @@ impl GroundPlaneSampler {
   pub fn new(height: f64) -> Self {
     Self { height }
   }
+
+  /// The plane's SDF as a function of world position: positive above,
+  /// negative below.
+  fn sdf(&self) -> impl Fn([f64; 3]) -> f64 + '_ {
+    |p| p[1] - self.height
+  }
 }
 
 impl VolumeSampler for GroundPlaneSampler {
@@ -177,21 +230,35 @@ impl VolumeSampler for GroundPlaneSampler {
     volume: &mut [SdfSample; SAMPLE_SIZE_CB],
     materials: &mut [MaterialId; SAMPLE_SIZE_CB],
   ) {
-    for xi in 0..SAMPLE_SIZE {
-      for yi in 0..SAMPLE_SIZE {
-        for zi in 0..SAMPLE_SIZE {
-          // World position Y
-          let wy = (grid_offset[1] + yi as i64) as f64 * voxel_size;
+    self.sample_volume_with_phase(grid_offset, voxel_size, [0.0; 3], volume, materials);
+  }
 
-          // Ground plane SDF: positive above, negative below
-          let sdf = wy - self.height;
+  fn sample_volume_with_phase(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    let sdf = self.sdf();
+    fill_volume(grid_offset, voxel_size, phase, volume, materials, sdf);
+  }
 
-          let idx = xi * SAMPLE_SIZE * SAMPLE_SIZE + yi * SAMPLE_SIZE + zi;
-          volume[idx] = sdf_conversion::to_storage(sdf as f32, voxel_size as f32);
-          materials[idx] = 0;
-        }
-      }
-    }
+  fn sample_volume16(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [Sdf16; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    let sdf = self.sdf();
+    fill_volume(grid_offset, voxel_size, phase, volume, materials, sdf);
+  }
+
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    sdf_conversion::to_storage(self.sdf()(position) as f32, voxel_size as f32)
   }
 }
 
@@ -227,6 +294,15 @@ impl BoxSampler {
     self.center = center;
     self
   }
+
+  /// The box's SDF as a function of world position.
+  fn sdf(&self) -> impl Fn([f64; 3]) -> f64 + '_ {
+    |p| {
+      let q = (DVec3::from_array(p) - DVec3::from_array(self.center)).abs()
+        - DVec3::from_array(self.half_extents);
+      q.max(DVec3::ZERO).length() + q.max_element().min(0.0)
+    }
+  }
 }
 
 impl VolumeSampler for BoxSampler {
@@ -237,24 +313,169 @@ impl VolumeSampler for BoxSampler {
     volume: &mut [SdfSample; SAMPLE_SIZE_CB],
     materials: &mut [MaterialId; SAMPLE_SIZE_CB],
   ) {
-    for xi in 0..SAMPLE_SIZE {
-      for yi in 0..SAMPLE_SIZE {
-        for zi in 0..SAMPLE_SIZE {
-          let wx = (grid_offset[0] + xi as i64) as f64 * voxel_size;
-          let wy = (grid_offset[1] + yi as i64) as f64 * voxel_size;
-          let wz = (grid_offset[2] + zi as i64) as f64 * voxel_size;
+    self.sample_volume_with_phase(grid_offset, voxel_size, [0.0; 3], volume, materials);
+  }
+
+  fn sample_volume_with_phase(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    let sdf = self.sdf();
+    fill_volume(grid_offset, voxel_size, phase, volume, materials, sdf);
+  }
+
+  fn sample_volume16(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [Sdf16; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    let sdf = self.sdf();
+    fill_volume(grid_offset, voxel_size, phase, volume, materials, sdf);
+  }
+
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    sdf_conversion::to_storage(self.sdf()(position) as f32, voxel_size as f32)
+  }
+}
+
+/// Texel filtering for [`HeightmapSampler`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum HeightmapFilter {
+  /// Nearest texel (stepped terrain between texels).
+  Nearest,
+  /// Bilinear interpolation of the 4 surrounding texels.
+  #[default]
+  Bilinear,
+}
+
+/// Heightmap SDF sampler.
+///
+/// Drives terrain from an externally authored height grid (GIS data, painted
+/// heightmaps). Heights are in world units and laid out row-major with X
+/// fastest: `heights[z * width + x]`. Texels are spaced `world_scale` apart
+/// and the grid is centered on the world origin in XZ. Lookups outside the
+/// grid clamp to the nearest edge texel. Heights between texels are
+/// bilinearly interpolated by default.
+///
+/// SDF: `y - height(x, z)` (vertical distance, negative below the surface)
+#[derive(Clone)]
+pub struct HeightmapSampler {
+  /// Height values in world units (shared so clones stay cheap).
+  pub heights: Arc<[f32]>,
+  /// Number of texels along X.
+  pub width: usize,
+  /// Number of texels along Z.
+  pub depth: usize,
+  /// World units between adjacent texels.
+  pub world_scale: f64,
+  /// World XZ position of texel (0, 0).
+  pub origin: [f64; 2],
+  /// Filtering between texels.
+  pub filter: HeightmapFilter,
+}
 
-          // Box SDF
-          let dx = (wx - self.center[0]).abs() - self.half_extents[0];
-          let dy = (wy - self.center[1]).abs() - self.half_extents[1];
-          let dz = (wz - self.center[2]).abs() - self.half_extents[2];
+impl HeightmapSampler {
+  /// Create a heightmap sampler centered on the world origin.
+  ///
+  /// # Panics
+  /// If `width` or `depth` is zero, or `heights.len() != width * depth`.
+  pub fn new(heights: Vec<f32>, width: usize, depth: usize, world_scale: f64) -> Self {
+    assert!(
+      width > 0 && depth > 0,
+      "heightmap must be at least one texel wide and deep"
+    );
+    assert_eq!(
+      heights.len(),
+      width * depth,
+      "heightmap data must contain width * depth samples"
+    );
+    let origin = [
+      -((width - 1) as f64) * world_scale * 0.5,
+      -((depth - 1) as f64) * world_scale * 0.5,
+    ];
+    Self {
+      heights: heights.into(),
+      width,
+      depth,
+      world_scale,
+      origin,
+      filter: HeightmapFilter::default(),
+    }
+  }
+
+  /// Set the world XZ position of texel (0, 0).
+  pub fn with_origin(mut self, origin: [f64; 2]) -> Self {
+    self.origin = origin;
+    self
+  }
 
-          let outside = (dx.max(0.0).powi(2) + dy.max(0.0).powi(2) + dz.max(0.0).powi(2)).sqrt();
-          let inside = dx.max(dy).max(dz).min(0.0);
-          let sdf = outside + inside;
+  /// Set texel filtering.
+  pub fn with_filter(mut self, filter: HeightmapFilter) -> Self {
+    self.filter = filter;
+    self
+  }
+
+  /// Height at a texel, clamped to the grid.
+  #[inline]
+  fn texel(&self, x: i64, z: i64) -> f64 {
+    let x = x.clamp(0, self.width as i64 - 1) as usize;
+    let z = z.clamp(0, self.depth as i64 - 1) as usize;
+    self.heights[z * self.width + x] as f64
+  }
+
+  /// Height at a world XZ position, filtered per `self.filter`.
+  pub fn height_at(&self, wx: f64, wz: f64) -> f64 {
+    let u = (wx - self.origin[0]) / self.world_scale;
+    let v = (wz - self.origin[1]) / self.world_scale;
 
+    match self.filter {
+      HeightmapFilter::Nearest => self.texel(u.round() as i64, v.round() as i64),
+      HeightmapFilter::Bilinear => {
+        // Clamp first so the edge texels extend flat past the grid
+        let u = u.clamp(0.0, (self.width - 1) as f64);
+        let v = v.clamp(0.0, (self.depth - 1) as f64);
+        let x0 = u.floor() as i64;
+        let z0 = v.floor() as i64;
+        let fx = u - x0 as f64;
+        let fz = v - z0 as f64;
+
+        let h00 = self.texel(x0, z0);
+        let h10 = self.texel(x0 + 1, z0);
+        let h01 = self.texel(x0, z0 + 1);
+        let h11 = self.texel(x0 + 1, z0 + 1);
+
+        let h0 = h00 + (h10 - h00) * fx;
+        let h1 = h01 + (h11 - h01) * fx;
+        h0 + (h1 - h0) * fz
+      }
+    }
+  }
+  /// Fill a block like `fill_volume`, with one height lookup per column.
+  fn fill<T: SdfValue>(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [T; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    for xi in 0..SAMPLE_SIZE {
+      let wx = (grid_offset[0] + xi as i64) as f64 * voxel_size + phase[0];
+      for zi in 0..SAMPLE_SIZE {
+        let wz = (grid_offset[2] + zi as i64) as f64 * voxel_size + phase[2];
+        let height = self.height_at(wx, wz);
+
+        for yi in 0..SAMPLE_SIZE {
+          let wy = (grid_offset[1] + yi as i64) as f64 * voxel_size + phase[1];
           let idx = xi * SAMPLE_SIZE * SAMPLE_SIZE + yi * SAMPLE_SIZE + zi;
-          volume[idx] = sdf_conversion::to_storage(sdf as f32, voxel_size as f32);
+          volume[idx] = T::quantize((wy - height) as f32, voxel_size as f32);
           materials[idx] = 0;
         }
       }
@@ -262,17 +483,75 @@ impl VolumeSampler for BoxSampler {
   }
 }
 
+impl VolumeSampler for HeightmapSampler {
+  fn sample_volume(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    self.sample_volume_with_phase(grid_offset, voxel_size, [0.0; 3], volume, materials);
+  }
+
+  fn sample_volume_with_phase(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    self.fill(grid_offset, voxel_size, phase, volume, materials);
+  }
+
+  fn sample_volume16(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [Sdf16; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    self.fill(grid_offset, voxel_size, phase, volume, materials);
+  }
+
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    let sdf = position[1] - self.height_at(position[0], position[2]);
+    sdf_conversion::to_storage(sdf as f32, voxel_size as f32)
+  }
+}
+
 /// Metaball (blobby) SDF sampler.
 ///
 /// Creates organic blob-like shapes using multiple spherical influences.
 /// Each metaball contributes `strength / distance²` to the field.
 /// The surface appears where the combined field equals the threshold.
-#[derive(Clone)]
+///
+/// Balls drift along their `velocity` as the sampler's time advances
+/// (`set_time`), so resampling the same node at a new time morphs the blobs.
+/// Time is stored as atomic `f64` bits so it can change through a shared
+/// reference while the sampler is in use.
 pub struct MetaballsSampler {
   /// Individual metaballs
-  pub balls: Vec<Metaball>,
+  balls: Vec<Metaball>,
+  /// Base centers of `balls`, kept alongside so they can be borrowed
+  centers: Vec<DVec3>,
   /// Field threshold for surface (default: 1.0)
   pub threshold: f64,
+  /// Animation time in seconds, as `f64` bits
+  time: AtomicU64,
+}
+
+impl Clone for MetaballsSampler {
+  fn clone(&self) -> Self {
+    Self {
+      balls: self.balls.clone(),
+      centers: self.centers.clone(),
+      threshold: self.threshold,
+      time: AtomicU64::new(self.time.load(Ordering::Relaxed)),
+    }
+  }
 }
 
 /// A single metaball influence.
@@ -284,16 +563,63 @@ pub struct Metaball {
   pub radius: f64,
   /// Strength of the influence (typically 1.0)
   pub strength: f64,
+  /// Drift in world units per second of sampler time
+  pub velocity: [f64; 3],
+}
+
+impl Metaball {
+  /// Center position at `time` seconds.
+  pub fn center_at(&self, time: f64) -> [f64; 3] {
+    std::array::from_fn(|axis| self.center[axis] + self.velocity[axis] * time)
+  }
 }
 
 impl MetaballsSampler {
   /// Create a new metaballs sampler with the given balls and threshold.
   pub fn new(balls: Vec<Metaball>, threshold: f64) -> Self {
-    Self { balls, threshold }
+    let centers = balls
+      .iter()
+      .map(|ball| DVec3::from_array(ball.center))
+      .collect();
+    Self {
+      balls,
+      centers,
+      threshold,
+      time: AtomicU64::new(0.0f64.to_bits()),
+    }
+  }
+
+  /// Animation time in seconds.
+  pub fn time(&self) -> f64 {
+    f64::from_bits(self.time.load(Ordering::Relaxed))
+  }
+
+  /// Set the animation time in seconds. Affects subsequent samples only;
+  /// already meshed chunks must be resampled to pick it up.
+  pub fn set_time(&self, time: f64) {
+    self.time.store(time.to_bits(), Ordering::Relaxed);
+  }
+
+  /// Individual metaballs, as passed to `new`.
+  pub fn balls(&self) -> &[Metaball] {
+    &self.balls
+  }
+
+  /// Base centers (time 0) of all balls, in `balls` order.
+  pub fn centers(&self) -> &[DVec3] {
+    &self.centers
   }
 
   /// Create a random arrangement of metaballs using a seed.
   /// Generates `count` metaballs scattered within a bounding region.
+  ///
+  /// The layout is stable across runs and platforms, and pinned by tests.
+  /// It uses xorshift32 (shifts 13, 17, 5; a zero seed becomes 1) mapped to
+  /// `[0, 1]` as `next / u32::MAX`. Each ball draws 4 values in order:
+  /// `x`, `y`, `z` as `(r * 2 - 1) * extent`, then radius as
+  /// `extent * (0.1 + r * 0.3)`. Velocities come from a second stream
+  /// seeded with `seed ^ 0x9E37_79B9`, 3 values per ball, drawn after all
+  /// positions.
   pub fn random(seed: u32, count: usize, extent: f64) -> Self {
     let mut balls = Vec::with_capacity(count);
     let mut rng = XorShift32::new(seed);
@@ -311,13 +637,49 @@ impl MetaballsSampler {
         center: [x, y, z],
         radius,
         strength: 1.0,
+        velocity: [0.0; 3],
       });
     }
 
-    Self {
-      balls,
-      threshold: 1.0,
+    // Separate stream so layouts match those from before balls could move.
+    // Drift up to 10% of the extent per second along each axis.
+    let mut velocity_rng = XorShift32::new(seed ^ 0x9E37_79B9);
+    for ball in &mut balls {
+      ball.velocity = std::array::from_fn(|_| (velocity_rng.next_f64() * 2.0 - 1.0) * extent * 0.1);
+    }
+
+    Self::new(balls, 1.0)
+  }
+
+  /// The SDF at the current time as a function of world position: negative
+  /// inside (field above the threshold), positive outside, approximating
+  /// distance by the threshold crossing.
+  fn sdf(&self) -> impl Fn([f64; 3]) -> f64 + '_ {
+    let time = self.time();
+    let centers: Vec<[f64; 3]> = self.balls.iter().map(|ball| ball.center_at(time)).collect();
+    move |p| self.threshold - self.field(&centers, p)
+  }
+
+  /// Combined field of all balls at `p`, given their current `centers`.
+  fn field(&self, centers: &[[f64; 3]], p: [f64; 3]) -> f64 {
+    let mut field = 0.0;
+    for (ball, center) in self.balls.iter().zip(centers) {
+      let dx = p[0] - center[0];
+      let dy = p[1] - center[1];
+      let dz = p[2] - center[2];
+      let dist_sq = dx * dx + dy * dy + dz * dz;
+
+      // Avoid division by zero, use ball radius squared as falloff
+      let r_sq = ball.radius * ball.radius;
+      if dist_sq < r_sq * 0.01 {
+        // Very close to center - large contribution
+        field += ball.strength * 100.0;
+      } else {
+        // Standard metaball falloff: strength * (r² / d²)
+        field += ball.strength * r_sq / dist_sq;
+      }
     }
+    field
   }
 }
 
@@ -329,42 +691,121 @@ impl VolumeSampler for MetaballsSampler {
     volume: &mut [SdfSample; SAMPLE_SIZE_CB],
     materials: &mut [MaterialId; SAMPLE_SIZE_CB],
   ) {
-    for xi in 0..SAMPLE_SIZE {
-      for yi in 0..SAMPLE_SIZE {
-        for zi in 0..SAMPLE_SIZE {
-          // World position = (grid_offset + sample_index) * voxel_size
-          let wx = (grid_offset[0] + xi as i64) as f64 * voxel_size;
-          let wy = (grid_offset[1] + yi as i64) as f64 * voxel_size;
-          let wz = (grid_offset[2] + zi as i64) as f64 * voxel_size;
-
-          // Compute combined metaball field value
-          let mut field = 0.0;
-          for ball in &self.balls {
-            let dx = wx - ball.center[0];
-            let dy = wy - ball.center[1];
-            let dz = wz - ball.center[2];
-            let dist_sq = dx * dx + dy * dy + dz * dz;
-
-            // Avoid division by zero, use ball radius squared as falloff
-            let r_sq = ball.radius * ball.radius;
-            if dist_sq < r_sq * 0.01 {
-              // Very close to center - large contribution
-              field += ball.strength * 100.0;
-            } else {
-              // Standard metaball falloff: strength * (r² / d²)
-              field += ball.strength * r_sq / dist_sq;
-            }
-          }
-
-          // Convert to SDF: negative inside (field > threshold), positive outside
-          // Approximate distance using threshold crossing
-          let sdf = self.threshold - field;
+    self.sample_volume_with_phase(grid_offset, voxel_size, [0.0; 3], volume, materials);
+  }
 
-          let idx = xi * SAMPLE_SIZE * SAMPLE_SIZE + yi * SAMPLE_SIZE + zi;
-          volume[idx] = sdf_conversion::to_storage(sdf as f32, voxel_size as f32);
-          materials[idx] = 0;
+  fn sample_volume_with_phase(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    let sdf = self.sdf();
+    fill_volume(grid_offset, voxel_size, phase, volume, materials, sdf);
+  }
+
+  fn sample_volume16(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    volume: &mut [Sdf16; SAMPLE_SIZE_CB],
+    materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  ) {
+    let sdf = self.sdf();
+    fill_volume(grid_offset, voxel_size, phase, volume, materials, sdf);
+  }
+
+  fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+    sdf_conversion::to_storage(self.sdf()(position) as f32, voxel_size as f32)
+  }
+
+  fn sample_normals(
+    &self,
+    grid_offset: [i64; 3],
+    voxel_size: f64,
+    phase: [f64; 3],
+    normals: &mut [[f32; 3]; SAMPLE_SIZE_CB],
+  ) -> bool {
+    let time = self.time();
+    let centers: Vec<[f64; 3]> = self.balls.iter().map(|ball| ball.center_at(time)).collect();
+
+    // sdf = threshold - Σ s·r²/d², so ∇sdf = Σ 2·s·r²·(p - c)/d⁴ (the
+    // clamped core contributes a constant, hence no gradient)
+    fill_normals(grid_offset, voxel_size, phase, normals, |p| {
+      let mut gradient = [0.0; 3];
+      for (ball, center) in self.balls.iter().zip(&centers) {
+        let d: [f64; 3] = std::array::from_fn(|axis| p[axis] - center[axis]);
+        let dist_sq = d[0] * d[0] + d[1] * d[1] + d[2] * d[2];
+        let r_sq = ball.radius * ball.radius;
+        if dist_sq < r_sq * 0.01 {
+          continue;
+        }
+        let scale = 2.0 * ball.strength * r_sq / (dist_sq * dist_sq);
+        for (g, d) in gradient.iter_mut().zip(d) {
+          *g += scale * d;
         }
       }
+      gradient
+    });
+    true
+  }
+}
+
+/// Fill `normals` with the normalized `gradient` at each sample's world
+/// position, as in `fill_volume`. Zero gradients fall back to +Y.
+fn fill_normals(
+  grid_offset: [i64; 3],
+  voxel_size: f64,
+  phase: [f64; 3],
+  normals: &mut [[f32; 3]; SAMPLE_SIZE_CB],
+  gradient: impl Fn([f64; 3]) -> [f64; 3],
+) {
+  for xi in 0..SAMPLE_SIZE {
+    for yi in 0..SAMPLE_SIZE {
+      for zi in 0..SAMPLE_SIZE {
+        let p = [
+          (grid_offset[0] + xi as i64) as f64 * voxel_size + phase[0],
+          (grid_offset[1] + yi as i64) as f64 * voxel_size + phase[1],
+          (grid_offset[2] + zi as i64) as f64 * voxel_size + phase[2],
+        ];
+        let g = gradient(p);
+        let len = (g[0] * g[0] + g[1] * g[1] + g[2] * g[2]).sqrt();
+        let idx = xi * SAMPLE_SIZE * SAMPLE_SIZE + yi * SAMPLE_SIZE + zi;
+        normals[idx] = if len > 0.0 {
+          g.map(|v| (v / len) as f32)
+        } else {
+          [0.0, 1.0, 0.0]
+        };
+      }
+    }
+  }
+}
+
+/// Fill `volume` with `sdf` at each sample's world position
+/// (`(grid_offset + index) * voxel_size + phase`), and `materials` with 0.
+fn fill_volume<T: SdfValue>(
+  grid_offset: [i64; 3],
+  voxel_size: f64,
+  phase: [f64; 3],
+  volume: &mut [T; SAMPLE_SIZE_CB],
+  materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+  sdf: impl Fn([f64; 3]) -> f64,
+) {
+  for xi in 0..SAMPLE_SIZE {
+    for yi in 0..SAMPLE_SIZE {
+      for zi in 0..SAMPLE_SIZE {
+        let p = [
+          (grid_offset[0] + xi as i64) as f64 * voxel_size + phase[0],
+          (grid_offset[1] + yi as i64) as f64 * voxel_size + phase[1],
+          (grid_offset[2] + zi as i64) as f64 * voxel_size + phase[2],
+        ];
+        let idx = xi * SAMPLE_SIZE * SAMPLE_SIZE + yi * SAMPLE_SIZE + zi;
+        volume[idx] = T::quantize(sdf(p) as f32, voxel_size as f32);
+        materials[idx] = 0;
+      }
     }
   }
 }
@@ -444,6 +885,178 @@ mod tests {
     assert!(has_positive && has_negative, "Ground plane should split the volume");
   }
 
+  #[test]
+  fn point_samples_match_volumes() {
+    let heights: Vec<f32> = (0..16).map(|i| (i % 4) as f32 * 4.0).collect();
+    let samplers: Vec<Box<dyn VolumeSampler>> = vec![
+      Box::new(TiltedPlaneSampler::default()),
+      Box::new(SphereSampler::new(10.0)),
+      Box::new(GroundPlaneSampler::new(2.5)),
+      Box::new(BoxSampler::new([6.0, 3.0, 9.0])),
+      Box::new(HeightmapSampler::new(heights, 4, 4, 8.0)),
+      Box::new(MetaballsSampler::random(42, 5, 20.0)),
+    ];
+
+    let mut volume = [0i8; SAMPLE_SIZE_CB];
+    let mut materials = [0u8; SAMPLE_SIZE_CB];
+    for sampler in &samplers {
+      sampler.sample_volume([-16, -16, -16], 0.5, &mut volume, &mut materials);
+      for (i, &expected) in volume.iter().enumerate().step_by(97) {
+        let index = [
+          i / (SAMPLE_SIZE * SAMPLE_SIZE),
+          i / SAMPLE_SIZE % SAMPLE_SIZE,
+          i % SAMPLE_SIZE,
+        ];
+        let position = index.map(|v| (v as i64 - 16) as f64 * 0.5);
+        assert_eq!(
+          sampler.sample_point(position, 0.5),
+          expected,
+          "at {:?}",
+          position
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn phased_i8_and_sdf16_samples_match() {
+    let heights: Vec<f32> = (0..16).map(|i| (i % 4) as f32 * 4.0).collect();
+    let samplers: Vec<Box<dyn VolumeSampler>> = vec![
+      Box::new(TiltedPlaneSampler::default()),
+      Box::new(SphereSampler::new(10.0)),
+      Box::new(GroundPlaneSampler::new(2.5)),
+      Box::new(BoxSampler::new([6.0, 3.0, 9.0])),
+      Box::new(HeightmapSampler::new(heights, 4, 4, 8.0)),
+      Box::new(MetaballsSampler::random(42, 5, 20.0)),
+    ];
+    let phase = [0.13, 0.31, 0.07];
+
+    let mut volume = Box::new([0i8; SAMPLE_SIZE_CB]);
+    let mut unphased = Box::new([0i8; SAMPLE_SIZE_CB]);
+    let mut volume16 = Box::new([0i16; SAMPLE_SIZE_CB]);
+    let mut materials = Box::new([0u8; SAMPLE_SIZE_CB]);
+    for sampler in &samplers {
+      sampler.sample_volume_with_phase([-16; 3], 0.5, phase, &mut volume, &mut materials);
+      sampler.sample_volume16([-16; 3], 0.5, phase, &mut volume16, &mut materials);
+      sampler.sample_volume([-16; 3], 0.5, &mut unphased, &mut materials);
+
+      assert_ne!(volume, unphased, "phase should move the sample positions");
+      for (i, (&v8, &v16)) in volume.iter().zip(volume16.iter()).enumerate() {
+        assert_eq!(v8, v16.clamp(-127, 127) as i8, "at sample {}", i);
+      }
+      for (i, &expected) in volume.iter().enumerate().step_by(97) {
+        let index = [
+          i / (SAMPLE_SIZE * SAMPLE_SIZE),
+          i / SAMPLE_SIZE % SAMPLE_SIZE,
+          i % SAMPLE_SIZE,
+        ];
+        let position: [f64; 3] =
+          std::array::from_fn(|axis| (index[axis] as i64 - 16) as f64 * 0.5 + phase[axis]);
+        assert_eq!(
+          sampler.sample_point(position, 0.5),
+          expected,
+          "at {:?}",
+          position
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn phased_normals_follow_phased_positions() {
+    let sampler = SphereSampler::new(10.0).with_center([1.0, -2.0, 0.5]);
+    let phase = [0.13, 0.31, 0.07];
+
+    let mut normals = Box::new([[0.0f32; 3]; SAMPLE_SIZE_CB]);
+    let mut unphased = Box::new([[0.0f32; 3]; SAMPLE_SIZE_CB]);
+    assert!(sampler.sample_normals([-16; 3], 0.5, phase, &mut normals));
+    assert!(sampler.sample_normals([-16; 3], 0.5, [0.0; 3], &mut unphased));
+    assert_ne!(normals, unphased, "phase should move the sample positions");
+
+    for (i, normal) in normals.iter().enumerate().step_by(97) {
+      let index = [
+        i / (SAMPLE_SIZE * SAMPLE_SIZE),
+        i / SAMPLE_SIZE % SAMPLE_SIZE,
+        i % SAMPLE_SIZE,
+      ];
+      let position = DVec3::from_array(std::array::from_fn(|axis| {
+        (index[axis] as i64 - 16) as f64 * 0.5 + phase[axis]
+      }));
+      let expected = (position - DVec3::from_array(sampler.center)).normalize();
+      for axis in 0..3 {
+        assert!(
+          (normal[axis] as f64 - expected[axis]).abs() < 1e-5,
+          "at {:?}: {:?} != {:?}",
+          position,
+          normal,
+          expected
+        );
+      }
+    }
+  }
+
+  #[test]
+  #[should_panic(expected = "at least one texel")]
+  fn heightmap_rejects_empty_grid() {
+    HeightmapSampler::new(Vec::new(), 0, 4, 1.0);
+  }
+
+  #[test]
+  fn heightmap_surface_follows_ramp() {
+    // 4x4 ramp rising along X: height = 4 * x_texel
+    let heights: Vec<f32> = (0..16).map(|i| (i % 4) as f32 * 4.0).collect();
+    let sampler = HeightmapSampler::new(heights, 4, 4, 8.0).with_origin([0.0, 0.0]);
+
+    assert_eq!(sampler.height_at(0.0, 0.0), 0.0);
+    assert_eq!(sampler.height_at(16.0, 8.0), 8.0);
+    assert_eq!(sampler.height_at(1000.0, 0.0), 12.0, "Clamps past the edge");
+
+    let mut volume = [0i8; SAMPLE_SIZE_CB];
+    let mut materials = [0u8; SAMPLE_SIZE_CB];
+    sampler.sample_volume([0, -4, 0], 1.0, &mut volume, &mut materials);
+
+    let has_positive = volume.iter().any(|&v| v > 0);
+    let has_negative = volume.iter().any(|&v| v < 0);
+    assert!(has_positive && has_negative, "Heightmap should cross the volume");
+  }
+
+  #[test]
+  fn heightmap_bilinear_smooths_diagonal_ramp() {
+    // 8x8 ramp rising along the diagonal: height = 2 * (x + z)
+    let heights: Vec<f32> = (0..64).map(|i| ((i % 8) + (i / 8)) as f32 * 2.0).collect();
+    let bilinear = HeightmapSampler::new(heights, 8, 8, 4.0).with_origin([0.0, 0.0]);
+    let nearest = bilinear.clone().with_filter(HeightmapFilter::Nearest);
+
+    // Walk the diagonal in quarter-texel steps
+    let profile = |sampler: &HeightmapSampler| -> Vec<f64> {
+      (0..=28)
+        .map(|i| {
+          let w = i as f64;
+          sampler.height_at(w, w)
+        })
+        .collect()
+    };
+    let smooth = profile(&bilinear);
+    let stepped = profile(&nearest);
+
+    for pair in smooth.windows(2) {
+      assert!(
+        pair[1] > pair[0],
+        "Bilinear heights must rise monotonically: {:?}",
+        smooth
+      );
+      assert!((pair[1] - pair[0] - 1.0).abs() < 1e-9, "Bilinear slope should be constant");
+    }
+
+    let flat_steps = stepped.windows(2).filter(|p| p[1] == p[0]).count();
+    let max_jump = stepped
+      .windows(2)
+      .map(|p| p[1] - p[0])
+      .fold(0.0, f64::max);
+    assert!(flat_steps > 0, "Nearest sampling should plateau between texels");
+    assert!(max_jump >= 4.0, "Nearest sampling should jump between texels");
+  }
+
   #[test]
   fn metaballs_creates_surface() {
     // Use random generation with a fixed seed for reproducibility
@@ -477,4 +1090,61 @@ mod tests {
       assert_eq!(b1.radius, b2.radius);
     }
   }
+
+  #[test]
+  fn metaballs_animate_over_time() {
+    let sampler = MetaballsSampler::random(42, 5, 20.0);
+    let mut at_start = [0i8; SAMPLE_SIZE_CB];
+    let mut later = [0i8; SAMPLE_SIZE_CB];
+    let mut materials = [0u8; SAMPLE_SIZE_CB];
+
+    sampler.sample_volume([-16, -16, -16], 1.0, &mut at_start, &mut materials);
+    sampler.set_time(2.0);
+    assert_eq!(sampler.time(), 2.0);
+    sampler.sample_volume([-16, -16, -16], 1.0, &mut later, &mut materials);
+    assert_ne!(at_start, later, "Balls should have moved");
+
+    // Rewinding reproduces the original volume
+    sampler.set_time(0.0);
+    let mut rewound = [0i8; SAMPLE_SIZE_CB];
+    sampler.sample_volume([-16, -16, -16], 1.0, &mut rewound, &mut materials);
+    assert_eq!(at_start, rewound);
+  }
+
+  #[test]
+  fn metaballs_random_layout_is_pinned() {
+    // Changing these values changes every seeded metaball world
+    let sampler = MetaballsSampler::random(42, 3, 100.0);
+    let expected = [
+      (
+        DVec3::new(-99.47122149157134, 32.06239550655299, -77.80858263788014),
+        35.48130706545927,
+      ),
+      (
+        DVec3::new(75.08787833505492, -32.87780627908135, 72.92177494916174),
+        26.984839466629744,
+      ),
+      (
+        DVec3::new(45.296425545889974, -50.3014777205655, 39.18697660304302),
+        33.602449063584785,
+      ),
+    ];
+
+    let centers = sampler.centers();
+    assert_eq!(centers.len(), expected.len());
+    for ((center, ball), (expected_center, expected_radius)) in
+      centers.iter().zip(&sampler.balls).zip(expected)
+    {
+      assert!(
+        center.distance(expected_center) < 1e-9,
+        "center {} != {}",
+        center,
+        expected_center
+      );
+      assert!((ball.radius - expected_radius).abs() < 1e-9);
+    }
+
+    // Same seed, same layout
+    assert_eq!(MetaballsSampler::random(42, 3, 100.0).centers(), centers);
+  }
 }
@@ -0,0 +1,114 @@
+//! Per-vertex ambient occlusion.
+//!
+//! Cheap baked AO for stylized shading: for each vertex, count solid samples
+//! in a cube of `2 * radius` samples per axis centered on the vertex's cell,
+//! then map the solid fraction to an openness term:
+//!
+//! ```text
+//! solid fraction   ao
+//! ──────────────   ───
+//!      ≤ 0.5       1.0   (flat or convex - open)
+//!        0.75      0.5
+//!        1.0       0.0   (buried - occluded)
+//!
+//! ao = clamp(2 * (1 - solid_fraction), 0, 1)
+//! ```
+//!
+//! Solid counts come from a 3D summed-volume table built once per mesh, so
+//! each vertex costs 8 table lookups regardless of radius. Neighborhoods that
+//! are entirely solid or air early-out without the division.
+
+use crate::constants::*;
+use crate::types::{MeshOutput, SdfSample};
+
+/// Table stride: one extra row per axis for the zero border.
+const TABLE_SIZE: usize = SAMPLE_SIZE + 1;
+
+#[inline]
+fn table_index(x: usize, y: usize, z: usize) -> usize {
+  x * TABLE_SIZE * TABLE_SIZE + y * TABLE_SIZE + z
+}
+
+/// Build a summed-volume table of solid samples.
+///
+/// `table[x+1][y+1][z+1]` = number of solid samples in `[0..=x]×[0..=y]×[0..=z]`.
+fn build_solid_table(volume: &[SdfSample; SAMPLE_SIZE_CB]) -> Vec<u32> {
+  let mut table = vec![0u32; TABLE_SIZE * TABLE_SIZE * TABLE_SIZE];
+
+  for x in 0..SAMPLE_SIZE {
+    for y in 0..SAMPLE_SIZE {
+      for z in 0..SAMPLE_SIZE {
+        let solid = (volume[coord_to_index(x, y, z)] < 0) as u32;
+        table[table_index(x + 1, y + 1, z + 1)] = (solid
+          + table[table_index(x, y + 1, z + 1)]
+          + table[table_index(x + 1, y, z + 1)]
+          + table[table_index(x + 1, y + 1, z)]
+          + table[table_index(x, y, z)])
+          - (table[table_index(x, y, z + 1)]
+            + table[table_index(x, y + 1, z)]
+            + table[table_index(x + 1, y, z)]);
+      }
+    }
+  }
+
+  table
+}
+
+/// Count solid samples in the inclusive box `[lo, hi]`.
+#[inline]
+fn count_solid(table: &[u32], lo: [usize; 3], hi: [usize; 3]) -> u32 {
+  let [x0, y0, z0] = lo;
+  let [x1, y1, z1] = [hi[0] + 1, hi[1] + 1, hi[2] + 1];
+
+  // Inclusion-exclusion; add before subtracting to stay in unsigned range
+  (table[table_index(x1, y1, z1)]
+    + table[table_index(x0, y0, z1)]
+    + table[table_index(x0, y1, z0)]
+    + table[table_index(x1, y0, z0)])
+    - (table[table_index(x0, y1, z1)]
+      + table[table_index(x1, y0, z1)]
+      + table[table_index(x1, y1, z0)]
+      + table[table_index(x0, y0, z0)])
+}
+
+/// Compute AO for all vertices.
+///
+/// `radius` is in samples (clamped to at least 1). Skirt vertices (negative
+/// cell positions) keep `ao = 1.0`.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, name = "ao::compute"))]
+pub fn compute(volume: &[SdfSample; SAMPLE_SIZE_CB], output: &mut MeshOutput, radius: u32) {
+  if output.vertices.is_empty() {
+    return;
+  }
+
+  let table = build_solid_table(volume);
+  let r = radius.max(1) as i32;
+  let last = (SAMPLE_SIZE - 1) as i32;
+
+  for vertex in &mut output.vertices {
+    let cell = vertex.cell_position;
+    if cell.iter().any(|&c| c < 0) {
+      continue;
+    }
+
+    // Cell spans samples [c, c + 1]; extend `radius - 1` beyond on each side
+    let lo = cell.map(|c| (c + 1 - r).clamp(0, last) as usize);
+    let hi = cell.map(|c| (c + r).clamp(0, last) as usize);
+
+    let total = (0..3).map(|i| (hi[i] - lo[i] + 1) as u32).product::<u32>();
+    let solid = count_solid(&table, lo, hi);
+
+    vertex.ao = if solid == 0 {
+      1.0
+    } else if solid == total {
+      0.0
+    } else {
+      let solid_fraction = solid as f32 / total as f32;
+      (2.0 * (1.0 - solid_fraction)).clamp(0.0, 1.0)
+    };
+  }
+}
+
+#[cfg(test)]
+#[path = "ao_test.rs"]
+mod ao_test;
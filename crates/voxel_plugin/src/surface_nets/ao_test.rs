@@ -0,0 +1,81 @@
+use super::*;
+use crate::surface_nets::{generate, volume_from_sdf};
+use crate::types::MeshConfig;
+
+/// Ground plane at y=16 with a narrow shaft (radius 2) carved down to y=6
+/// at the center of the XZ plane.
+fn create_pit_sdf() -> [SdfSample; SAMPLE_SIZE_CB] {
+  volume_from_sdf(|[x, y, z]| {
+    let dist_xz = (x - 16.0).hypot(z - 16.0);
+    let ground = y - 16.0;
+    let shaft = (2.0 - dist_xz).min(y - 6.0);
+    ground.max(shaft)
+  })
+}
+
+#[test]
+fn test_pit_bottom_more_occluded_than_plane() {
+  let volume = create_pit_sdf();
+  let materials = [0u8; SAMPLE_SIZE_CB];
+  let config = MeshConfig::default().with_ao(true);
+
+  let output = generate(&volume, &materials, &config);
+  assert!(!output.is_empty());
+
+  // Deepest vertex sits on the pit floor
+  let pit = output
+    .vertices
+    .iter()
+    .min_by(|a, b| a.position[1].total_cmp(&b.position[1]))
+    .unwrap();
+  assert!(pit.position[1] < 8.0, "Expected a pit floor vertex, got {:?}", pit.position);
+
+  // Vertex on the open plane, far from the pit
+  let plane = output
+    .vertices
+    .iter()
+    .find(|v| {
+      let dx = v.position[0] - 16.0;
+      let dz = v.position[2] - 16.0;
+      v.position[1] > 15.0 && (dx * dx + dz * dz).sqrt() > 8.0
+    })
+    .unwrap();
+
+  assert!(
+    pit.ao < plane.ao,
+    "Pit AO {} should be below plane AO {}",
+    pit.ao,
+    plane.ao
+  );
+  assert!((0.0..=1.0).contains(&pit.ao));
+  assert!((0.0..=1.0).contains(&plane.ao));
+}
+
+#[test]
+fn test_ao_skipped_by_default() {
+  let volume = create_pit_sdf();
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let output = generate(&volume, &materials, &MeshConfig::default());
+
+  assert!(output.vertices.iter().all(|v| v.ao == 1.0));
+}
+
+#[test]
+fn test_summed_volume_table_matches_brute_force() {
+  let volume = create_pit_sdf();
+  let table = build_solid_table(&volume);
+
+  let lo = [13, 4, 14];
+  let hi = [18, 9, 17];
+  let mut expected = 0;
+  for x in lo[0]..=hi[0] {
+    for y in lo[1]..=hi[1] {
+      for z in lo[2]..=hi[2] {
+        expected += (volume[coord_to_index(x, y, z)] < 0) as u32;
+      }
+    }
+  }
+
+  assert_eq!(count_solid(&table, lo, hi), expected);
+}
@@ -0,0 +1,81 @@
+//! Sealed air cavity detection.
+//!
+//! Air pockets fully enclosed by solid inside a chunk produce closed inner
+//! shells that can never be seen. With `MeshConfig::skip_enclosed_cavities`
+//! the mesher flood-fills air from the chunk boundary and skips every cell
+//! whose air corners were not reached.
+//!
+//! ```text
+//! seeds     = air samples on the 6 faces of the 32³ block
+//! reachable = flood fill through air, 26-connected
+//! enclosed  = air && !reachable
+//! ```
+//!
+//! The fill is conservative: air touching the chunk boundary is assumed to
+//! connect to open air beyond it, so a cavity straddling two chunks is still
+//! meshed in both. 26-connectivity joins air samples that only touch at an
+//! edge or corner, so the fill never separates regions the mesher connects.
+//! All air corners of a cell are 26-neighbours, so a cell is either fully
+//! enclosed or not at all, and every quad of an enclosed shell is skipped.
+
+use crate::constants::*;
+use crate::types::SdfSample;
+
+/// Mark air samples not connected to the chunk boundary.
+///
+/// Returns `None` when the chunk has no enclosed air (the common case).
+pub(super) fn find_enclosed_air(volume: &[SdfSample; SAMPLE_SIZE_CB]) -> Option<Vec<bool>> {
+  let is_air = |idx: usize| volume[idx] >= 0;
+  let last = SAMPLE_SIZE - 1;
+
+  let mut reached = vec![false; SAMPLE_SIZE_CB];
+  let mut stack: Vec<[usize; 3]> = Vec::new();
+
+  for x in 0..SAMPLE_SIZE {
+    for y in 0..SAMPLE_SIZE {
+      for z in 0..SAMPLE_SIZE {
+        let on_face = [x, y, z].iter().any(|&c| c == 0 || c == last);
+        let idx = coord_to_index(x, y, z);
+        if on_face && is_air(idx) {
+          reached[idx] = true;
+          stack.push([x, y, z]);
+        }
+      }
+    }
+  }
+
+  while let Some([x, y, z]) = stack.pop() {
+    for nx in x.saturating_sub(1)..=(x + 1).min(last) {
+      for ny in y.saturating_sub(1)..=(y + 1).min(last) {
+        for nz in z.saturating_sub(1)..=(z + 1).min(last) {
+          let idx = coord_to_index(nx, ny, nz);
+          if !reached[idx] && is_air(idx) {
+            reached[idx] = true;
+            stack.push([nx, ny, nz]);
+          }
+        }
+      }
+    }
+  }
+
+  let mut any_enclosed = false;
+  for (idx, reached) in reached.iter_mut().enumerate() {
+    // Reuse the buffer: true now means "enclosed air"
+    *reached = is_air(idx) && !*reached;
+    any_enclosed |= *reached;
+  }
+
+  any_enclosed.then_some(reached)
+}
+
+/// Whether any air corner of the cell at `base_idx` is enclosed.
+#[inline]
+pub(super) fn is_enclosed_cell(enclosed: &[bool], base_idx: usize) -> bool {
+  CORNER_OFFSETS
+    .iter()
+    .any(|&offset| enclosed[base_idx + offset])
+}
+
+#[cfg(test)]
+#[path = "cavities_test.rs"]
+mod cavities_test;
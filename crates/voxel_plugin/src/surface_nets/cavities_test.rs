@@ -0,0 +1,67 @@
+use super::*;
+use crate::surface_nets::{generate, volume_from_sdf};
+use crate::types::{MeshConfig, SdfSample};
+
+/// Solid below `ground_y` with an air sphere carved out beneath it.
+fn create_ground_with_cavity(
+  ground_y: f32,
+  cavity_center: [f32; 3],
+  cavity_radius: f32,
+) -> [SdfSample; SAMPLE_SIZE_CB] {
+  volume_from_sdf(|p| {
+    let [dx, dy, dz]: [f32; 3] = std::array::from_fn(|axis| p[axis] - cavity_center[axis]);
+    let cavity = cavity_radius - (dx * dx + dy * dy + dz * dz).sqrt();
+    let ground = p[1] - ground_y;
+    ground.max(cavity)
+  })
+}
+
+#[test]
+fn test_open_air_is_not_enclosed() {
+  let volume = create_ground_with_cavity(16.5, [0.0; 3], 0.0);
+  assert!(find_enclosed_air(&volume).is_none());
+}
+
+#[test]
+fn test_sealed_cavity_is_enclosed() {
+  let volume = create_ground_with_cavity(24.5, [16.0, 10.0, 16.0], 5.0);
+  let enclosed = find_enclosed_air(&volume).expect("cavity should be enclosed");
+
+  assert!(enclosed[coord_to_index(16, 10, 16)]);
+  // Open air above the ground is reachable from the boundary
+  assert!(!enclosed[coord_to_index(16, 28, 16)]);
+  // Solid is never marked
+  assert!(!enclosed[coord_to_index(16, 2, 16)]);
+}
+
+#[test]
+fn test_cavity_touching_boundary_is_conservatively_open() {
+  // Cavity pokes through the x = 0 face: it may continue in the neighbour
+  let volume = create_ground_with_cavity(24.5, [1.0, 10.0, 16.0], 5.0);
+  assert!(find_enclosed_air(&volume).is_none());
+}
+
+#[test]
+fn test_skip_enclosed_cavities_drops_inner_shell() {
+  let ground_y = 24.5;
+  let volume = create_ground_with_cavity(ground_y, [16.0, 10.0, 16.0], 5.0);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let meshed = generate(&volume, &materials, &MeshConfig::default());
+  let skipped = generate(
+    &volume,
+    &materials,
+    &MeshConfig::default().with_skip_enclosed_cavities(true),
+  );
+
+  // Without the flag the cavity shell is meshed below the ground
+  assert!(meshed.vertices.iter().any(|v| v.position[1] < 20.0));
+
+  // With it only the ground surface remains
+  assert!(!skipped.indices.is_empty());
+  assert!(skipped.indices.len() < meshed.indices.len());
+  assert!(
+    skipped.vertices.iter().all(|v| v.position[1] > 20.0),
+    "inner shell vertices should be skipped"
+  );
+}
@@ -6,8 +6,10 @@
 //! Also provides geometry-based normal calculation from triangle faces.
 
 use glam::Vec3A;
+use rayon::prelude::*;
 
-use crate::types::MeshOutput;
+use crate::constants::{coord_to_index, SAMPLE_SIZE, SAMPLE_SIZE_CB};
+use crate::types::{MeshOutput, SdfValue, Vertex};
 
 /// Compute gradient normal from 8 corner samples using SIMD.
 ///
@@ -144,6 +146,62 @@ pub fn compute_interpolated(samples: &[f32; 8], frac: [f32; 3]) -> [f32; 3] {
   [normalized.x, normalized.y, normalized.z]
 }
 
+/// Estimate the mean curvature of the isosurface in the cell at `cell`.
+///
+/// Evaluates `div(∇f / |∇f|) / 2` from the SDF gradient and full Hessian,
+/// each taken by central differences at the cell's 8 corners and averaged
+/// to the cell center. Corners on the volume boundary use the stencil of the
+/// nearest interior sample. The result is in inverse sample units: positive
+/// where the surface bulges towards air (`1 / radius` on a sphere), negative
+/// in cavities and 0 on planes. Returns 0 when the gradient vanishes.
+///
+/// The stencil reaches one sample past the cell, where i8 volumes have
+/// saturated (a tenth of a voxel from the surface), so the estimate is only
+/// accurate on 16-bit volumes.
+pub fn compute_curvature<S: SdfValue>(volume: &[S; SAMPLE_SIZE_CB], cell: [usize; 3]) -> f32 {
+  let mut g = Vec3A::ZERO;
+  let mut h = [Vec3A::ZERO; 3];
+
+  for corner in 0..8 {
+    let center: [usize; 3] =
+      std::array::from_fn(|axis| (cell[axis] + ((corner >> axis) & 1)).clamp(1, SAMPLE_SIZE - 2));
+    // Sample at `center` offset by `da` along axis `a` and `db` along `b`
+    let f = |a: usize, da: isize, b: usize, db: isize| {
+      let [x, y, z]: [usize; 3] = std::array::from_fn(|axis| {
+        let offset = da * (axis == a) as isize + db * (axis == b) as isize;
+        center[axis].wrapping_add_signed(offset)
+      });
+      volume[coord_to_index(x, y, z)].to_voxels()
+    };
+    let f0 = f(0, 0, 0, 0);
+
+    g += Vec3A::from_array(std::array::from_fn(|a| (f(a, 1, a, 0) - f(a, -1, a, 0)) * 0.5));
+    for (a, row) in h.iter_mut().enumerate() {
+      *row += Vec3A::from_array(std::array::from_fn(|b| {
+        if a == b {
+          f(a, 1, a, 0) - 2.0 * f0 + f(a, -1, a, 0)
+        } else {
+          (f(a, 1, b, 1) - f(a, 1, b, -1) - f(a, -1, b, 1) + f(a, -1, b, -1)) * 0.25
+        }
+      }));
+    }
+  }
+
+  // Averages over the 8 corners
+  let g = g * 0.125;
+  let h = h.map(|row| row * 0.125);
+
+  let len_sq = g.length_squared();
+  if len_sq < 1e-8 {
+    return 0.0;
+  }
+
+  // (|g|² tr(H) - gᵀHg) / (2|g|³)
+  let trace = h[0].x + h[1].y + h[2].z;
+  let hg = Vec3A::new(h[0].dot(g), h[1].dot(g), h[2].dot(g));
+  (len_sq * trace - g.dot(hg)) / (2.0 * len_sq * len_sq.sqrt())
+}
+
 // =============================================================================
 // Geometry-based normal recalculation
 // =============================================================================
@@ -172,58 +230,163 @@ pub fn recalculate_from_geometry(output: &mut MeshOutput) {
   let vertices = &mut output.vertices;
 
   for tri in indices.chunks_exact(3) {
-    let i0 = tri[0] as usize;
-    let i1 = tri[1] as usize;
-    let i2 = tri[2] as usize;
-
-    let p0 = Vec3A::from_array(vertices[i0].position);
-    let p1 = Vec3A::from_array(vertices[i1].position);
-    let p2 = Vec3A::from_array(vertices[i2].position);
+    let Some(weighted) = angle_weighted_normals(vertices, tri) else {
+      continue;
+    };
 
-    // Edge vectors from each vertex
-    let e01 = p1 - p0;
-    let e02 = p2 - p0;
-    let e12 = p2 - p1;
+    for (&i, w) in tri.iter().zip(weighted) {
+      add_to_normal(&mut vertices[i as usize].normal, &w.to_array());
+    }
+  }
 
-    // Face normal (normalized for angle weighting)
-    let face_normal = e01.cross(e02);
-    let face_len_sq = face_normal.length_squared();
+  // Normalize all normals
+  for vertex in &mut output.vertices {
+    vertex.normal = normalize_or_up(Vec3A::from_array(vertex.normal));
+  }
+}
 
-    // Skip degenerate triangles
-    if face_len_sq < 1e-12 {
+/// Data-parallel variant of [`recalculate_from_geometry`].
+///
+/// Two phases keep the parallel parts free of shared writes:
+/// 1. Per-triangle weighted face normals into a scratch vec (parallel)
+/// 2. Scatter into per-vertex sums in triangle order (serial), then
+///    normalize (parallel)
+///
+/// Sums are accumulated in the same order as the serial path, so results are
+/// bit-identical.
+#[cfg_attr(
+  feature = "tracing",
+  tracing::instrument(skip_all, name = "gradient::recalculate_from_geometry_parallel")
+)]
+pub fn recalculate_from_geometry_parallel(output: &mut MeshOutput) {
+  let vertices = &output.vertices;
+  let weighted: Vec<Option<[Vec3A; 3]>> = output
+    .indices
+    .par_chunks_exact(3)
+    .map(|tri| angle_weighted_normals(vertices, tri))
+    .collect();
+
+  let mut sums = vec![Vec3A::ZERO; output.vertices.len()];
+  for (tri, w) in output.indices.chunks_exact(3).zip(&weighted) {
+    let Some(w) = w else {
       continue;
+    };
+    for (&i, n) in tri.iter().zip(w) {
+      sums[i as usize] += *n;
     }
+  }
 
-    let face_normal_unit = face_normal * face_len_sq.sqrt().recip();
+  output
+    .vertices
+    .par_iter_mut()
+    .zip(sums.par_iter())
+    .for_each(|(vertex, &n)| vertex.normal = normalize_or_up(n));
+}
 
-    // Compute angle at each vertex and accumulate weighted normal
-    // Angle at v0: between edges e01 and e02
-    let angle0 = vertex_angle(e01, e02);
-    let weighted0 = face_normal_unit * angle0;
-    add_to_normal(&mut vertices[i0].normal, &weighted0.to_array());
+/// Angle-weighted face normal contribution for each corner of a triangle.
+///
+/// Returns `None` for degenerate triangles.
+#[inline]
+fn angle_weighted_normals(vertices: &[Vertex], tri: &[u16]) -> Option<[Vec3A; 3]> {
+  let p0 = Vec3A::from_array(vertices[tri[0] as usize].position);
+  let p1 = Vec3A::from_array(vertices[tri[1] as usize].position);
+  let p2 = Vec3A::from_array(vertices[tri[2] as usize].position);
+
+  // Edge vectors from each vertex
+  let e01 = p1 - p0;
+  let e02 = p2 - p0;
+  let e12 = p2 - p1;
+
+  // Face normal (normalized for angle weighting)
+  let face_normal = e01.cross(e02);
+  let face_len_sq = face_normal.length_squared();
+
+  // Skip degenerate triangles
+  if face_len_sq < 1e-12 {
+    return None;
+  }
+
+  let face_normal_unit = face_normal * face_len_sq.sqrt().recip();
 
-    // Angle at v1: between edges -e01 and e12
-    let angle1 = vertex_angle(-e01, e12);
-    let weighted1 = face_normal_unit * angle1;
-    add_to_normal(&mut vertices[i1].normal, &weighted1.to_array());
+  // Angle at v0: between edges e01 and e02
+  // Angle at v1: between edges -e01 and e12
+  // Angle at v2: between edges -e02 and -e12
+  Some([
+    face_normal_unit * vertex_angle(e01, e02),
+    face_normal_unit * vertex_angle(-e01, e12),
+    face_normal_unit * vertex_angle(-e02, -e12),
+  ])
+}
 
-    // Angle at v2: between edges -e02 and -e12
-    let angle2 = vertex_angle(-e02, -e12);
-    let weighted2 = face_normal_unit * angle2;
-    add_to_normal(&mut vertices[i2].normal, &weighted2.to_array());
+/// Split every triangle onto its own three vertices and give them the
+/// triangle's face normal (faceted shading).
+///
+/// Copies carry all attributes of the shared vertex (material weights, cell
+/// position, ...), its displaced position and high material weights;
+/// `boundary_indices` are split too. The vertex count becomes the number of
+/// indices. Returns false and leaves the mesh untouched if that would
+/// overflow u16 indices.
+#[cfg_attr(
+  feature = "tracing",
+  tracing::instrument(skip_all, name = "gradient::flatten")
+)]
+pub fn flatten(output: &mut MeshOutput) -> bool {
+  let count = output.indices.len() + output.boundary_indices.len();
+  if count > u16::MAX as usize + 1 {
+    return false;
   }
 
-  // Normalize all normals
-  for vertex in &mut output.vertices {
-    let n = Vec3A::from_array(vertex.normal);
-    let len_sq = n.length_squared();
-    if len_sq < 1e-12 {
-      vertex.normal = [0.0, 1.0, 0.0]; // Fallback to up
-    } else {
-      let normalized = n * len_sq.sqrt().recip();
-      vertex.normal = normalized.to_array();
+  let shared = std::mem::take(&mut output.vertices);
+  let shared_displaced = std::mem::take(&mut output.displaced_positions);
+  let shared_hi = std::mem::take(&mut output.material_weights_hi);
+  let mut vertices = Vec::with_capacity(count);
+  let mut displaced = Vec::with_capacity(count);
+
+  for indices in [&mut output.indices, &mut output.boundary_indices] {
+    for index in indices.iter_mut() {
+      let vertex = shared[*index as usize];
+      displaced.push(
+        shared_displaced
+          .get(*index as usize)
+          .copied()
+          .unwrap_or(vertex.position),
+      );
+      if let Some(&hi) = shared_hi.get(*index as usize) {
+        output.material_weights_hi.push(hi);
+      }
+      *index = vertices.len() as u16;
+      vertices.push(vertex);
+    }
+
+    for tri in indices.chunks_exact(3) {
+      let [a, b, c] =
+        [tri[0], tri[1], tri[2]].map(|i| Vec3A::from_array(vertices[i as usize].position));
+      let face_normal = (b - a).cross(c - a);
+      // Degenerate triangles keep their geometry normals
+      if face_normal.length_squared() < 1e-12 {
+        continue;
+      }
+      let normal = face_normal.normalize().to_array();
+      for &i in tri {
+        vertices[i as usize].normal = normal;
+      }
     }
   }
+
+  output.vertices = vertices;
+  output.displaced_positions = displaced;
+  true
+}
+
+/// Normalize an accumulated normal, falling back to up when degenerate.
+#[inline]
+fn normalize_or_up(n: Vec3A) -> [f32; 3] {
+  let len_sq = n.length_squared();
+  if len_sq < 1e-12 {
+    [0.0, 1.0, 0.0] // Fallback to up
+  } else {
+    (n * len_sq.sqrt().recip()).to_array()
+  }
 }
 
 /// Compute the angle between two edge vectors at a vertex.
@@ -1,4 +1,5 @@
 use super::*;
+use crate::types::{sdf_conversion, Sdf16};
 
 fn approx_eq(a: [f32; 3], b: [f32; 3], epsilon: f32) -> bool {
   (a[0] - b[0]).abs() < epsilon && (a[1] - b[1]).abs() < epsilon && (a[2] - b[2]).abs() < epsilon
@@ -161,3 +162,70 @@ fn test_interpolated_corners_use_one_sided_differences() {
     expected
   );
 }
+
+/// Sample an SDF at every volume position, in 16-bit storage.
+fn volume16(sdf: impl Fn(f32, f32, f32) -> f32) -> Box<[Sdf16; SAMPLE_SIZE_CB]> {
+  let mut volume = Box::new([0; SAMPLE_SIZE_CB]);
+  for x in 0..SAMPLE_SIZE {
+    for y in 0..SAMPLE_SIZE {
+      for z in 0..SAMPLE_SIZE {
+        let value = sdf(x as f32, y as f32, z as f32);
+        volume[coord_to_index(x, y, z)] = sdf_conversion::to_storage16(value, 1.0);
+      }
+    }
+  }
+  volume
+}
+
+/// Sphere of `radius` around the volume center.
+fn sphere(radius: f32) -> impl Fn(f32, f32, f32) -> f32 {
+  move |x, y, z| Vec3A::new(x - 16.0, y - 16.0, z - 16.0).length() - radius
+}
+
+/// `1 / distance` from the volume center to the center of `cell`.
+fn inverse_center_distance(cell: [usize; 3]) -> f32 {
+  let center = Vec3A::new(cell[0] as f32, cell[1] as f32, cell[2] as f32) + Vec3A::splat(0.5);
+  (center - Vec3A::splat(16.0)).length().recip()
+}
+
+#[test]
+fn test_curvature_flat_is_zero() {
+  let level = volume16(|_, y, _| y - 4.5);
+  assert!(compute_curvature(&level, [3, 4, 5]).abs() < 1e-6);
+
+  let tilted = volume16(|x, y, z| (x + 2.0 * y - z) / 6f32.sqrt());
+  assert!(compute_curvature(&tilted, [3, 4, 5]).abs() < 1e-3);
+
+  assert_eq!(compute_curvature(&[0i16; SAMPLE_SIZE_CB], [3, 4, 5]), 0.0);
+}
+
+#[test]
+fn test_curvature_sphere_sign_and_scale() {
+  const RADIUS: f32 = 10.0;
+  let volume = volume16(sphere(RADIUS));
+
+  // Cell straddling the surface along the (1, 1, 1) diagonal
+  let diagonal = [(16.0 + RADIUS / 3f32.sqrt() - 0.5) as usize; 3];
+  let convex = compute_curvature(&volume, diagonal);
+  let expected = inverse_center_distance(diagonal);
+  assert!((convex - expected).abs() < 0.05 * expected, "{} vs {}", convex, expected);
+
+  // Same surface seen from inside (a spherical cavity) flips the sign
+  let cavity = compute_curvature(&volume16(|x, y, z| -sphere(RADIUS)(x, y, z)), diagonal);
+  assert!((cavity + convex).abs() < 1e-6, "{} vs {}", cavity, convex);
+
+  // Tighter spheres curve more
+  let d_small = [(16.0 + RADIUS / 2.0 / 3f32.sqrt() - 0.5) as usize; 3];
+  assert!(compute_curvature(&volume16(sphere(RADIUS / 2.0)), d_small) > convex);
+}
+
+#[test]
+fn test_curvature_sphere_pole() {
+  // At the pole the surface curves along the X and Z grid axes only, which
+  // takes the pure second derivatives
+  let volume = volume16(sphere(10.0));
+  let pole = [15, 25, 15];
+  let curvature = compute_curvature(&volume, pole);
+  let expected = inverse_center_distance(pole);
+  assert!((curvature - expected).abs() < 0.05 * expected, "{} vs {}", curvature, expected);
+}
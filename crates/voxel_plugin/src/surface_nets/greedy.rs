@@ -0,0 +1,237 @@
+//! Greedy merging of flat, axis-aligned Surface Nets quads.
+//!
+//! On blocky terrain (flat floors, walls) Surface Nets still emits one quad
+//! per cell. With `MeshAlgorithm::Greedy`, a quad whose four vertices sit at
+//! their cell centres on a shared axis-aligned plane and carry the same
+//! material blend is collected during the geometry pass instead of emitted.
+//! Collected quads are then merged into maximal rectangles per plane. A
+//! rectangle keeps every vertex on its outline and is fanned from one
+//! interior vertex, so triangles sharing its edges still meet it at vertices
+//! (no T-junctions); only the other interior vertices are dropped:
+//!
+//! ```text
+//!   ●───●───●───●           ●───●───●───●
+//!   │   │   │   │           │           │
+//!   ●───●───●───●    ──►    ●   ●       ●
+//!   │   │   │   │           │           │
+//!   ●───●───●───●           ●───●───●───●
+//! ```
+//!
+//! Everything else (curved cells, LOD seam vertices, the overlap region) is
+//! triangulated as plain Surface Nets. Vertices left without triangles are
+//! dropped at the end.
+
+use std::collections::BTreeMap;
+
+use super::lod_seams;
+use crate::constants::{LAST_INTERIOR_CELL, SAMPLE_SIZE};
+use crate::types::{MeshOutput, MinMaxAABB};
+
+/// Identity of the plane and material a flat quad lies on; only quads with
+/// equal keys merge.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct PlaneKey {
+  /// Plane coordinate along the quad's axis (f32 bits).
+  offset: u32,
+  /// Material weights, low then high slots (f32 bits).
+  weights: [u32; 8],
+  /// Winding flip of the quad.
+  flip: bool,
+}
+
+impl PlaneKey {
+  fn of(output: &MeshOutput, index: u16, axis: usize, flip: bool) -> Self {
+    let vertex = &output.vertices[index as usize];
+    let hi = output.material_weights_hi.get(index as usize);
+    let weights = std::array::from_fn(|i| match i {
+      0..=3 => vertex.material_weights[i].to_bits(),
+      _ => hi.map_or(0, |hi| hi[i - 4].to_bits()),
+    });
+    Self {
+      offset: vertex.position[axis].to_bits(),
+      weights,
+      flip,
+    }
+  }
+}
+
+#[derive(Clone, Copy)]
+struct FlatQuad {
+  key: PlaneKey,
+  /// Vertices in `emit_triangles` order: A (pos), B (pos - u - v),
+  /// C (pos - u), D (pos - v).
+  vertices: [u16; 4],
+}
+
+/// Flat quads collected during the geometry pass, keyed by (axis, layer) and
+/// laid out on a `SAMPLE_SIZE²` grid of (u, v) cell coordinates.
+pub(super) struct FlatQuads {
+  transition_bits: u32,
+  planes: BTreeMap<(usize, usize), Vec<Option<FlatQuad>>>,
+}
+
+impl FlatQuads {
+  pub(super) fn new(transition_bits: u32) -> Self {
+    Self {
+      transition_bits,
+      planes: BTreeMap::new(),
+    }
+  }
+
+  /// Collect the quad of cell `pos`'s `axis` edge if it can be merged.
+  ///
+  /// Returns false if the quad must be emitted as usual.
+  pub(super) fn try_collect(
+    &mut self,
+    output: &MeshOutput,
+    axis: usize,
+    pos: [usize; 3],
+    vertices: [u16; 4],
+    flip: bool,
+  ) -> bool {
+    // Overlap-region quads are left to the boundary filter
+    if pos.iter().any(|&c| c > LAST_INTERIOR_CELL) {
+      return false;
+    }
+
+    let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+    let key = PlaneKey::of(output, vertices[0], axis, flip);
+    let mergeable = vertices.iter().all(|&index| {
+      let vertex = &output.vertices[index as usize];
+      let centred = |a: usize| vertex.position[a] - vertex.cell_position[a] as f32 == 0.5;
+      PlaneKey::of(output, index, axis, flip) == key
+        && centred(u)
+        && centred(v)
+        && !(self.transition_bits != 0
+          && lod_seams::is_boundary_vertex(vertex.cell_position, self.transition_bits))
+    });
+    if !mergeable {
+      return false;
+    }
+
+    let plane = self
+      .planes
+      .entry((axis, pos[axis]))
+      .or_insert_with(|| vec![None; SAMPLE_SIZE * SAMPLE_SIZE]);
+    plane[pos[u] * SAMPLE_SIZE + pos[v]] = Some(FlatQuad { key, vertices });
+    true
+  }
+
+  /// Merge the collected quads into rectangles, emit them and drop the
+  /// vertices no triangle uses any more.
+  pub(super) fn emit(self, output: &mut MeshOutput) {
+    if self.planes.is_empty() {
+      return;
+    }
+
+    for mut plane in self.planes.into_values() {
+      for u0 in 0..SAMPLE_SIZE {
+        for v0 in 0..SAMPLE_SIZE {
+          let Some(start) = plane[u0 * SAMPLE_SIZE + v0] else {
+            continue;
+          };
+          let matches = |plane: &[Option<FlatQuad>], u: usize, v: usize| {
+            plane[u * SAMPLE_SIZE + v].is_some_and(|quad| quad.key == start.key)
+          };
+
+          // Grow along v, then along u while the whole next row matches
+          let mut v1 = v0;
+          while v1 + 1 < SAMPLE_SIZE && matches(&plane, u0, v1 + 1) {
+            v1 += 1;
+          }
+          let mut u1 = u0;
+          while u1 + 1 < SAMPLE_SIZE && (v0..=v1).all(|v| matches(&plane, u1 + 1, v)) {
+            u1 += 1;
+          }
+
+          let quad = |u: usize, v: usize| plane[u * SAMPLE_SIZE + v].unwrap().vertices;
+          let (nu, nv) = (u1 - u0 + 1, v1 - v0 + 1);
+
+          // Strips one quad wide have no interior vertices to drop
+          if nu < 2 || nv < 2 {
+            for u in u0..=u1 {
+              for v in v0..=v1 {
+                super::push_quad(output, quad(u, v), false, start.key.flip);
+              }
+            }
+          } else {
+            // Vertex (i, j) of the rectangle's (nu + 1) x (nv + 1) vertex grid
+            let vertex = |i: usize, j: usize| match (i, j) {
+              (0, 0) => quad(u0, v0)[1],
+              (0, _) => quad(u0, v0 + j - 1)[2],
+              (_, 0) => quad(u0 + i - 1, v0)[3],
+              _ => quad(u0 + i - 1, v0 + j - 1)[0],
+            };
+            // Every vertex on the outline, counter-clockwise in (u, v), so
+            // edges shared with neighbouring triangles are split where
+            // they are and leave no T-junctions
+            let outline: Vec<u16> = (0..nu)
+              .map(|i| vertex(i, 0))
+              .chain((0..nv).map(|j| vertex(nu, j)))
+              .chain((1..=nu).rev().map(|i| vertex(i, nv)))
+              .chain((1..=nv).rev().map(|j| vertex(0, j)))
+              .collect();
+            let centre = vertex(nu / 2, nv / 2);
+            for (k, &a) in outline.iter().enumerate() {
+              let b = outline[(k + 1) % outline.len()];
+              let triangle = if start.key.flip {
+                [centre, b, a]
+              } else {
+                [centre, a, b]
+              };
+              output.indices.extend_from_slice(&triangle);
+            }
+          }
+
+          for u in u0..=u1 {
+            plane[u * SAMPLE_SIZE + v0..=u * SAMPLE_SIZE + v1].fill(None);
+          }
+        }
+      }
+    }
+
+    remove_unused_vertices(output);
+  }
+}
+
+/// Drop vertices no triangle references (interiors of merged rectangles).
+fn remove_unused_vertices(output: &mut MeshOutput) {
+  let mut used = vec![false; output.vertices.len()];
+  for &index in &output.indices {
+    used[index as usize] = true;
+  }
+
+  let mut remap = vec![None; output.vertices.len()];
+  let mut vertices = Vec::with_capacity(output.vertices.len());
+  let mut displaced = Vec::with_capacity(output.vertices.len());
+  let mut bounds = MinMaxAABB::empty();
+  for (i, vertex) in output.vertices.iter().enumerate() {
+    if !used[i] {
+      continue;
+    }
+    let position = output
+      .displaced_positions
+      .get(i)
+      .copied()
+      .unwrap_or(vertex.position);
+    remap[i] = Some(vertices.len() as u16);
+    vertices.push(*vertex);
+    displaced.push(position);
+    bounds.encapsulate(position);
+  }
+
+  for index in &mut output.indices {
+    *index = remap[*index as usize].expect("indexed vertices are kept");
+  }
+  let mut kept = used.iter();
+  output
+    .material_weights_hi
+    .retain(|_| kept.next() == Some(&true));
+  output.vertices = vertices;
+  output.displaced_positions = displaced;
+  output.bounds = bounds;
+}
+
+#[cfg(test)]
+#[path = "greedy_test.rs"]
+mod greedy_test;
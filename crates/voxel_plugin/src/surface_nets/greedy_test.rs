@@ -0,0 +1,145 @@
+use std::collections::BTreeSet;
+
+use super::*;
+use crate::constants::*;
+use crate::surface_nets::{generate, volume_from_sdf};
+use crate::types::{MeshAlgorithm, MeshConfig, SdfSample};
+
+/// Solid below y = 15.5, air above: a flat floor across the whole chunk.
+fn create_half_chunk() -> [SdfSample; SAMPLE_SIZE_CB] {
+  volume_from_sdf(|[_, y, _]| y - 15.5)
+}
+
+/// A plateau at y = 12.5 for x < 14 rising as a 0.4 slope beyond it.
+fn create_sloped_terrain() -> [SdfSample; SAMPLE_SIZE_CB] {
+  volume_from_sdf(|[x, y, _]| y - (12.5 + 0.4 * (x - 14.0).max(0.0)))
+}
+
+/// Edges used by exactly one triangle, keyed by their end positions.
+fn open_edges(output: &MeshOutput) -> BTreeSet<[[u32; 3]; 2]> {
+  let position = |i: u16| output.vertices[i as usize].position.map(f32::to_bits);
+  let mut counts = BTreeMap::new();
+  for tri in output.indices.chunks_exact(3) {
+    for (i, j) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+      let (a, b) = (position(i), position(j));
+      *counts.entry([a.min(b), a.max(b)]).or_insert(0) += 1;
+    }
+  }
+  counts
+    .into_iter()
+    .filter(|&(_, count)| count == 1)
+    .map(|(edge, _)| edge)
+    .collect()
+}
+
+fn surface_area(output: &MeshOutput) -> f32 {
+  output
+    .indices
+    .chunks_exact(3)
+    .map(|tri| {
+      let p = |i: usize| glam::Vec3A::from_array(output.vertices[tri[i] as usize].position);
+      (p(1) - p(0)).cross(p(2) - p(0)).length() * 0.5
+    })
+    .sum()
+}
+
+#[test]
+fn test_half_chunk_merges_into_few_triangles() {
+  let volume = create_half_chunk();
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let nets = generate(&volume, &materials, &MeshConfig::default());
+  let greedy = generate(
+    &volume,
+    &materials,
+    &MeshConfig::default().with_mesh_algorithm(MeshAlgorithm::Greedy),
+  );
+
+  let (nets_tris, greedy_tris) = (nets.indices.len() / 3, greedy.indices.len() / 3);
+  assert!(nets_tris > 1000, "surface nets triangles: {}", nets_tris);
+  assert!(
+    greedy_tris * 4 < nets_tris,
+    "greedy {} vs surface nets {} triangles",
+    greedy_tris,
+    nets_tris
+  );
+  assert!(greedy.vertices.len() < nets.vertices.len());
+
+  // Same floor, no holes
+  let (nets_area, greedy_area) = (surface_area(&nets), surface_area(&greedy));
+  assert!(
+    (nets_area - greedy_area).abs() < 1e-2,
+    "area {} vs {}",
+    greedy_area,
+    nets_area
+  );
+}
+
+#[test]
+fn test_seam_vertices_are_not_merged() {
+  let volume = create_half_chunk();
+  let materials = [0u8; SAMPLE_SIZE_CB];
+  let mask = lod_seams::FACE_NEG_X;
+  let config = MeshConfig {
+    neighbor_mask: mask,
+    ..Default::default()
+  };
+
+  let nets = generate(&volume, &materials, &config);
+  let greedy = generate(
+    &volume,
+    &materials,
+    &config.clone().with_mesh_algorithm(MeshAlgorithm::Greedy),
+  );
+  assert!(greedy.indices.len() < nets.indices.len());
+
+  // Every seam vertex survives at its displaced position
+  let interior = |cell: [i32; 3]| cell.iter().all(|&c| c <= LAST_INTERIOR_CELL as i32);
+  let mut seam_vertices = 0;
+  for vertex in &nets.vertices {
+    if !interior(vertex.cell_position) || !lod_seams::is_boundary_vertex(vertex.cell_position, mask)
+    {
+      continue;
+    }
+    seam_vertices += 1;
+    assert!(
+      greedy
+        .vertices
+        .iter()
+        .any(|v| v.cell_position == vertex.cell_position && v.position == vertex.position),
+      "seam vertex at cell {:?} was merged away",
+      vertex.cell_position
+    );
+  }
+  assert!(seam_vertices > 0);
+}
+
+#[test]
+fn test_sloped_terrain_has_no_cracks() {
+  let volume = create_sloped_terrain();
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let nets = generate(&volume, &materials, &MeshConfig::default());
+  let greedy = generate(
+    &volume,
+    &materials,
+    &MeshConfig::default().with_mesh_algorithm(MeshAlgorithm::Greedy),
+  );
+  assert!(greedy.vertices.len() < nets.vertices.len());
+
+  // Merged plateau quads meet the slope without T-junctions: the only open
+  // edges are the chunk's outline, as in plain Surface Nets
+  assert_eq!(open_edges(&greedy), open_edges(&nets));
+  assert!(greedy.indices.chunks_exact(3).all(|tri| {
+    let p = |i: usize| glam::Vec3A::from_array(greedy.vertices[tri[i] as usize].position);
+    (p(1) - p(0)).cross(p(2) - p(0)).length_squared() > 1e-12
+  }));
+
+  let (nets_area, greedy_area) = (surface_area(&nets), surface_area(&greedy));
+  assert!(
+    (nets_area - greedy_area).abs() < 1e-2,
+    "area {} vs {}",
+    greedy_area,
+    nets_area
+  );
+}
@@ -112,7 +112,7 @@
 
 use crate::constants::*;
 use crate::edge_table::EDGE_TABLE;
-use crate::types::{sdf_conversion, SdfSample};
+use crate::types::SdfValue;
 
 // Neighbor mask bit positions (must match Unity NeighborMask)
 
@@ -319,8 +319,8 @@ pub fn is_boundary_vertex(cell_pos: [i32; 3], mask: u32) -> bool {
 ///
 /// Resamples the SDF at stride-2 resolution (parent cell) and computes
 /// the vertex position as the coarser LOD would.
-pub fn compute_displaced_position(
-  volume: &[SdfSample; SAMPLE_SIZE_CB],
+pub fn compute_displaced_position<S: SdfValue>(
+  volume: &[S; SAMPLE_SIZE_CB],
   cell_pos: [i32; 3],
   original_position: [f32; 3],
 ) -> [f32; 3] {
@@ -349,9 +349,9 @@ pub fn compute_displaced_position(
     let idx = coord_to_index(sample_x, sample_y, sample_z);
     let sdf = volume[idx];
     // Use proper SDF scaling for smooth interpolation (1.0 for cell-local calculations)
-    samples[corner] = sdf_conversion::to_float(sdf, 1.0);
+    samples[corner] = sdf.to_voxels();
 
-    if sdf < 0 {
+    if sdf.is_solid() {
       corner_mask |= 1 << corner;
     }
   }
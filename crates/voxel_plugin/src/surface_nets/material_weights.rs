@@ -54,6 +54,49 @@ pub fn compute(
   weights
 }
 
+/// Like [`compute`], for up to 8 materials (IDs 0-7, higher IDs clamped to
+/// 7).
+///
+/// Returns `(weights for 0-3, weights for 4-7)`, together summing to 1.0.
+pub fn compute8(
+  materials: &[MaterialId; SAMPLE_SIZE_CB],
+  corner_mask: u8,
+  base_idx: usize,
+) -> ([f32; 4], [f32; 4]) {
+  let mut weights = [0.0f32; 8];
+
+  for corner in 0..8 {
+    if (corner_mask & (1 << corner)) == 0 {
+      continue;
+    }
+    let mat_id = materials[base_idx + CORNER_OFFSETS[corner]] as usize;
+    weights[mat_id.min(7)] += 1.0;
+  }
+
+  let sum: f32 = weights.iter().sum();
+  if sum > 0.0001 {
+    let inv_sum = 1.0 / sum;
+    for weight in &mut weights {
+      *weight *= inv_sum;
+    }
+  } else {
+    weights[0] = 1.0;
+  }
+
+  let (lo, hi) = weights.split_at(4);
+  (lo.try_into().unwrap(), hi.try_into().unwrap())
+}
+
+/// Weight differences below this are ties for material-aware splitting.
+pub const SPLIT_EPSILON: f32 = 1e-4;
+
+/// L1 distance between two weight vectors (0 = same blend, 2 = disjoint
+/// materials).
+#[inline]
+pub fn distance(a: &[f32; 4], b: &[f32; 4]) -> f32 {
+  a.iter().zip(b).map(|(a, b)| (a - b).abs()).sum()
+}
+
 #[cfg(test)]
 #[path = "material_weights_test.rs"]
 mod material_weights_test;
@@ -65,3 +65,34 @@ fn test_weights_sum_to_one() {
   let sum: f32 = weights.iter().sum();
   assert!((sum - 1.0).abs() < 0.001);
 }
+
+#[test]
+fn test_eight_materials_fill_hi_slots() {
+  let mut materials = [0u8; SAMPLE_SIZE_CB];
+  // Corners 0-1: material 0, corners 2-4: material 4, corners 5-7: material 7
+  for (corner, material) in [(2, 4), (3, 4), (4, 4), (5, 7), (6, 7), (7, 7)] {
+    materials[CORNER_OFFSETS[corner]] = material;
+  }
+
+  let (lo, hi) = compute8(&materials, 0xFF, 0);
+
+  assert_eq!(lo, [0.25, 0.0, 0.0, 0.0]);
+  assert_eq!(hi, [0.375, 0.0, 0.0, 0.375]);
+
+  // The 4-material path clamps the same cell into slot 3
+  assert_eq!(compute(&materials, 0xFF, 0), [0.25, 0.0, 0.0, 0.75]);
+}
+
+#[test]
+fn test_eight_materials_match_four_for_low_ids() {
+  let mut materials = [0u8; SAMPLE_SIZE_CB];
+  for corner in 0..8 {
+    materials[CORNER_OFFSETS[corner]] = (corner % 4) as u8;
+  }
+
+  for corner_mask in [0x01, 0x0F, 0x5A, 0xFF] {
+    let (lo, hi) = compute8(&materials, corner_mask, 0);
+    assert_eq!(lo, compute(&materials, corner_mask, 0));
+    assert_eq!(hi, [0.0; 4]);
+  }
+}
@@ -69,6 +69,7 @@
 //! │                    PHASE 4: Normal Calculation                  │
 //! │  Option A: Gradient normals (computed in Phase 2)               │
 //! │  Option B: Geometry normals (post-process from triangles)       │
+//! │  Optional: UVs, then tangents along them (Lengyel)              │
 //! └─────────────────────────────────────────────────────────────────┘
 //!                               │
 //!                               ▼
@@ -133,19 +134,59 @@
 //! 8. **Triangulation**: Emit triangles for active edges connecting to previous
 //!    cells
 
+mod ao;
+mod cavities;
 mod corner_mask;
 mod gradient;
+mod greedy;
 mod lod_seams;
 mod material_weights;
+mod region;
+mod simplify;
+mod skirts;
+mod tangents;
+mod uvs;
 mod vertex_calc;
+mod weld;
 
 pub use lod_seams::NeighborMask;
+pub use region::{generate_region, VoxelRegion};
+pub use skirts::SKIRT_CELL;
+pub use vertex_calc::{edge_crossings, EdgeId};
+pub use weld::DEFAULT_WELD_EPSILON;
+
+use rayon::prelude::*;
 
 use crate::constants::*;
 use crate::edge_table::*;
 use crate::types::sdf_conversion;
 use crate::types::*;
 
+/// Vertex count below which `MeshConfig::parallel_normals` is ignored;
+/// smaller meshes finish faster than rayon can split the work.
+pub const PARALLEL_NORMALS_MIN_VERTICES: usize = 2048;
+
+#[cfg(test)]
+thread_local! {
+  /// Cells passed to `process_cell_geometry` on this thread.
+  static CELL_VISITS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Test volume holding `sdf` evaluated at each sample's `[x, y, z]` index,
+/// stored with a voxel size of 1.
+#[cfg(test)]
+pub(crate) fn volume_from_sdf(sdf: impl Fn([f32; 3]) -> f32) -> [SdfSample; SAMPLE_SIZE_CB] {
+  let mut volume = [0i8; SAMPLE_SIZE_CB];
+  for x in 0..SAMPLE_SIZE {
+    for y in 0..SAMPLE_SIZE {
+      for z in 0..SAMPLE_SIZE {
+        let p = [x as f32, y as f32, z as f32];
+        volume[coord_to_index(x, y, z)] = sdf_conversion::to_storage(sdf(p), 1.0);
+      }
+    }
+  }
+  volume
+}
 
 // =============================================================================
 // Pass-based meshing pipeline
@@ -173,6 +214,14 @@ use crate::types::*;
 // - Pass 4: LOD stitching refinement
 //
 
+/// Cell to vertex index lookup used during triangulation.
+///
+/// `get` returns -1 for cells without a vertex or outside the storage.
+trait CellIndices {
+  fn get(&self, x: usize, y: usize, z: usize) -> i32;
+  fn set(&mut self, x: usize, y: usize, z: usize, value: i32);
+}
+
 /// Index buffer for tracking vertex indices during triangulation.
 /// Uses a checkerboard ping-pong pattern for memory efficiency.
 struct IndexBuffer {
@@ -201,6 +250,13 @@ impl IndexBuffer {
     }
   }
 
+  #[allow(dead_code)]
+  fn clear(&mut self) {
+    self.data.fill(-1);
+  }
+}
+
+impl CellIndices for IndexBuffer {
   #[inline]
   fn get(&self, x: usize, y: usize, z: usize) -> i32 {
     let idx = self.calculate_index(x, y, z);
@@ -218,11 +274,6 @@ impl IndexBuffer {
       self.data[idx] = value;
     }
   }
-
-  #[allow(dead_code)]
-  fn clear(&mut self) {
-    self.data.fill(-1);
-  }
 }
 
 /// Generate mesh from SDF volume using Naive Surface Nets algorithm.
@@ -241,6 +292,94 @@ pub fn generate(
   volume: &[SdfSample; SAMPLE_SIZE_CB],
   materials: &[MaterialId; SAMPLE_SIZE_CB],
   config: &MeshConfig,
+) -> MeshOutput {
+  generate_with_normals(volume, materials, None, config)
+}
+
+/// Like [`generate`], with per-sample analytic normals from
+/// `VolumeSampler::sample_normals`.
+///
+/// When `normals` is given and `config.prefer_analytic_normals` is set,
+/// vertex normals are trilinearly interpolated from `normals` at the vertex
+/// position instead of derived per `config.normal_mode`.
+#[cfg_attr(
+  feature = "tracing",
+  tracing::instrument(skip_all, name = "surface_nets::generate_with_normals")
+)]
+pub fn generate_with_normals(
+  volume: &[SdfSample; SAMPLE_SIZE_CB],
+  materials: &[MaterialId; SAMPLE_SIZE_CB],
+  normals: Option<&[[f32; 3]; SAMPLE_SIZE_CB]>,
+  config: &MeshConfig,
+) -> MeshOutput {
+  // Corner classification and every later pass assume negative-inside
+  let native;
+  let volume = if config.sdf_convention == SdfConvention::NegativeInside {
+    volume
+  } else {
+    native = to_native_volume(volume, config.sdf_convention);
+    &*native
+  };
+
+  generate_samples(volume, volume, materials, normals, config)
+}
+
+/// Like [`generate`], for 16-bit SDF samples ([`Sdf16`]).
+///
+/// Vertex positions (including LOD seam displacement) are interpolated from
+/// the full 16-bit samples, which keeps shallow slopes from snapping to the
+/// i8 quantization steps. Normals, cavity detection and the later passes
+/// read the volume saturated to i8, so they match [`generate`].
+#[cfg_attr(
+  feature = "tracing",
+  tracing::instrument(skip_all, name = "surface_nets::generate16")
+)]
+pub fn generate16(
+  volume: &[Sdf16; SAMPLE_SIZE_CB],
+  materials: &[MaterialId; SAMPLE_SIZE_CB],
+  config: &MeshConfig,
+) -> MeshOutput {
+  generate16_with_normals(volume, materials, None, config)
+}
+
+/// Like [`generate16`], with per-sample analytic normals (see
+/// [`generate_with_normals`]).
+#[cfg_attr(
+  feature = "tracing",
+  tracing::instrument(skip_all, name = "surface_nets::generate16_with_normals")
+)]
+pub fn generate16_with_normals(
+  volume: &[Sdf16; SAMPLE_SIZE_CB],
+  materials: &[MaterialId; SAMPLE_SIZE_CB],
+  normals: Option<&[[f32; 3]; SAMPLE_SIZE_CB]>,
+  config: &MeshConfig,
+) -> MeshOutput {
+  let native;
+  let volume = if config.sdf_convention == SdfConvention::NegativeInside {
+    volume
+  } else {
+    native = to_native_volume(volume, config.sdf_convention);
+    &*native
+  };
+
+  let mut narrow = Box::new([0; SAMPLE_SIZE_CB]);
+  for (out, &sample) in narrow.iter_mut().zip(volume.iter()) {
+    *out = sample.saturate();
+  }
+
+  generate_samples(volume, &narrow, materials, normals, config)
+}
+
+/// Mesh a native negative-inside volume.
+///
+/// The geometry pass reads `volume`; every other pass reads `narrow`, the
+/// same field in i8 storage.
+fn generate_samples<S: SdfValue>(
+  volume: &[S; SAMPLE_SIZE_CB],
+  narrow: &[SdfSample; SAMPLE_SIZE_CB],
+  materials: &[MaterialId; SAMPLE_SIZE_CB],
+  normals: Option<&[[f32; 3]; SAMPLE_SIZE_CB]>,
+  config: &MeshConfig,
 ) -> MeshOutput {
   let mut output = MeshOutput::new();
   let mut index_buffer = IndexBuffer::new();
@@ -248,6 +387,19 @@ pub fn generate(
   // Extract transition bits once (skip ALL_SAME_LOD flag at bit 0)
   let transition_bits = config.neighbor_mask & lod_seams::ALL_TRANSITION_BITS;
 
+  // Sealed air pockets whose inner shells can be skipped (optional)
+  let enclosed = if config.skip_enclosed_cavities {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("cavity_pass").entered();
+    cavities::find_enclosed_air(narrow)
+  } else {
+    None
+  };
+
+  // Flat quads set aside for greedy merging (optional)
+  let mut flat = (config.mesh_algorithm == MeshAlgorithm::Greedy)
+    .then(|| greedy::FlatQuads::new(transition_bits));
+
   // =========================================================================
   // Pass 1: Geometry
   // =========================================================================
@@ -270,30 +422,119 @@ pub fn generate(
             &mut output,
             config,
             transition_bits,
+            enclosed.as_deref(),
+            flat.as_mut(),
           );
         }
       }
     }
   }
 
+  // =========================================================================
+  // Pass 1b: Greedy Merge (optional)
+  // =========================================================================
+  if let Some(flat) = flat {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("greedy_pass").entered();
+    flat.emit(&mut output);
+  }
+
+  let analytic = normals.filter(|_| config.prefer_analytic_normals);
+  finish_mesh(narrow, analytic, output, config)
+}
+
+/// Copy of `volume` converted to the native negative-inside convention.
+fn to_native_volume<S: SdfValue>(
+  volume: &[S; SAMPLE_SIZE_CB],
+  convention: SdfConvention,
+) -> Box<[S; SAMPLE_SIZE_CB]> {
+  let mut native = Box::new([S::default(); SAMPLE_SIZE_CB]);
+  for (out, &sample) in native.iter_mut().zip(volume.iter()) {
+    *out = match convention {
+      SdfConvention::NegativeInside => sample,
+      SdfConvention::PositiveInside => sample.negate(),
+    };
+  }
+  native
+}
+
+/// Run every pass after the geometry pass on `output`.
+///
+/// `analytic` normals, if given, replace the `normal_mode` normals.
+fn finish_mesh(
+  volume: &[SdfSample; SAMPLE_SIZE_CB],
+  analytic: Option<&[[f32; 3]; SAMPLE_SIZE_CB]>,
+  mut output: MeshOutput,
+  config: &MeshConfig,
+) -> MeshOutput {
   // =========================================================================
   // Pass 2: Boundary Triangle Filter
   // =========================================================================
   // Remove triangles where ALL vertices are in the overlap region.
   // This prevents Z-fighting at chunk boundaries while keeping all valid geometry.
-  {
+  // Watertight mode already skipped overlap quads during emission.
+  if config.filter_boundary && !config.watertight {
     #[cfg(feature = "tracing")]
     let _span = tracing::info_span!("boundary_filter_pass").entered();
-    filter_boundary_triangles(&mut output);
+    filter_boundary_triangles(&mut output, config.debug_keep_boundary);
+  }
+
+  // =========================================================================
+  // Pass 2b: Vertex Welding (optional)
+  // =========================================================================
+  // Merge coincident vertices before normals so geometry normals are smooth
+  // across the shared vertex.
+  if config.weld_vertices {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("weld_pass").entered();
+    weld::weld(&mut output, config.weld_epsilon);
+  }
+
+  // =========================================================================
+  // Pass 2c: Simplification (optional)
+  // =========================================================================
+  // Collapse near-coplanar regions; seam bands stay untouched so neighbouring
+  // chunks still line up.
+  if let Some(angle_deg) = config.simplify_angle_deg {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("simplify_pass").entered();
+    simplify::simplify(&mut output, angle_deg);
   }
 
   // =========================================================================
   // Pass 3: Normals
   // =========================================================================
-  {
+  // Skirts are open geometry, so watertight meshes never get them.
+  let skirts = config.skirt_depth != 0.0 && !config.watertight;
+  if !config.positions_only || skirts {
     #[cfg(feature = "tracing")]
     let _span = tracing::info_span!("normal_pass").entered();
-    compute_normals(volume, &mut output, config);
+    match analytic {
+      Some(normals) => interpolate_analytic_normals(normals, &mut output, config.parallel_normals),
+      None => compute_normals(volume, &mut output, config),
+    }
+  }
+
+  // =========================================================================
+  // Pass 3b: LOD Skirts (optional)
+  // =========================================================================
+  // Extrude open boundary edges along the inward normal to hide LOD cracks.
+  if skirts {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("skirt_pass").entered();
+    skirts::append(&mut output, config.skirt_depth);
+  }
+
+  // =========================================================================
+  // Pass 3c: Flat Shading (optional)
+  // =========================================================================
+  // Give every triangle its own vertices carrying the face normal. Meshes too
+  // dense for u16 indices once split keep their smooth normals, reported in
+  // `flat_shading_fallback`.
+  if config.normal_mode == NormalMode::Flat && !config.positions_only {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("flat_shading_pass").entered();
+    output.flat_shading_fallback = !gradient::flatten(&mut output);
   }
 
   // =========================================================================
@@ -304,6 +545,38 @@ pub fn generate(
     return MeshOutput::default();
   }
 
+  // Collision meshes stop here
+  if config.positions_only {
+    return output;
+  }
+
+  // =========================================================================
+  // Pass 5: UVs (optional)
+  // =========================================================================
+  if config.uv_mode != UvMode::None {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("uv_pass").entered();
+    uvs::compute(&mut output, config);
+  }
+
+  // =========================================================================
+  // Pass 6: Tangents (optional, along the UVs, so only with UVs)
+  // =========================================================================
+  if config.generate_tangents && config.uv_mode != UvMode::None {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("tangent_pass").entered();
+    tangents::compute(&mut output);
+  }
+
+  // =========================================================================
+  // Pass 7: Ambient Occlusion (optional)
+  // =========================================================================
+  if config.compute_ao {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("ao_pass").entered();
+    ao::compute(volume, &mut output, config.ao_radius);
+  }
+
   output
 }
 
@@ -312,7 +585,7 @@ pub fn generate(
 /// A valid mesh for collision requires:
 /// - At least 3 distinct vertices
 /// - At least 1 non-degenerate triangle (non-zero area)
-fn is_valid_for_collision(output: &MeshOutput) -> bool {
+pub fn is_valid_for_collision(output: &MeshOutput) -> bool {
   // Need at least 3 vertices and 1 triangle
   if output.vertices.len() < 3 || output.indices.len() < 3 {
     return false;
@@ -370,7 +643,11 @@ fn is_valid_for_collision(output: &MeshOutput) -> bool {
 ///
 /// This is more permissive than per-emission filtering and handles edge cases
 /// where triangles straddle the boundary.
-fn filter_boundary_triangles(output: &mut MeshOutput) {
+///
+/// With `keep_boundary`, discarded triangles are moved to
+/// `output.boundary_indices` instead of being dropped. Either way they are
+/// counted in `output.filtered_triangle_count`.
+fn filter_boundary_triangles(output: &mut MeshOutput, keep_boundary: bool) {
   let vertices = &output.vertices;
   let last_interior = LAST_INTERIOR_CELL as i32;
 
@@ -404,6 +681,12 @@ fn filter_boundary_triangles(output: &mut MeshOutput) {
     // Keep triangle if at least one vertex is inside
     if !(a_outside && b_outside && c_outside) {
       new_indices.extend_from_slice(triangle);
+      continue;
+    }
+
+    output.filtered_triangle_count += 1;
+    if keep_boundary {
+      output.boundary_indices.extend_from_slice(triangle);
     }
   }
 
@@ -411,27 +694,34 @@ fn filter_boundary_triangles(output: &mut MeshOutput) {
 }
 
 /// Compute normals for all vertices based on the configured mode.
+///
+/// Runs data-parallel when `config.parallel_normals` is set and the mesh has
+/// at least [`PARALLEL_NORMALS_MIN_VERTICES`] vertices.
 fn compute_normals(
   volume: &[SdfSample; SAMPLE_SIZE_CB],
   output: &mut MeshOutput,
   config: &MeshConfig,
 ) {
+  let parallel =
+    config.parallel_normals && output.vertices.len() >= PARALLEL_NORMALS_MIN_VERTICES;
+
   match config.normal_mode {
     NormalMode::Gradient => {
       // Compute gradient normals from cell corner samples
-      compute_gradient_normals(volume, output);
+      compute_gradient_normals(volume, output, parallel);
     }
     NormalMode::InterpolatedGradient => {
       // Compute gradient normals interpolated to vertex position
-      compute_interpolated_gradient_normals(volume, output);
+      compute_interpolated_gradient_normals(volume, output, parallel);
     }
-    NormalMode::Geometry => {
-      // Compute normals from triangle geometry
-      gradient::recalculate_from_geometry(output);
+    NormalMode::Geometry | NormalMode::Flat => {
+      // Compute normals from triangle geometry (Flat splits vertices after
+      // skirts, which need the shared topology)
+      recalculate_geometry_normals(output, parallel);
     }
     NormalMode::Blended { blend_distance } => {
       // First compute geometry normals
-      gradient::recalculate_from_geometry(output);
+      recalculate_geometry_normals(output, parallel);
 
       // Then blend with gradient at boundaries
       blend_boundary_normals(volume, output, blend_distance);
@@ -439,9 +729,33 @@ fn compute_normals(
   }
 }
 
+fn recalculate_geometry_normals(output: &mut MeshOutput, parallel: bool) {
+  if parallel {
+    gradient::recalculate_from_geometry_parallel(output);
+  } else {
+    gradient::recalculate_from_geometry(output);
+  }
+}
+
+/// Apply a per-vertex normal function, serially or across the rayon pool.
+fn for_each_vertex<F>(output: &mut MeshOutput, parallel: bool, f: F)
+where
+  F: Fn(&mut Vertex) + Sync + Send,
+{
+  if parallel {
+    output.vertices.par_iter_mut().for_each(f);
+  } else {
+    output.vertices.iter_mut().for_each(f);
+  }
+}
+
 /// Compute gradient normals for all vertices.
-fn compute_gradient_normals(volume: &[SdfSample; SAMPLE_SIZE_CB], output: &mut MeshOutput) {
-  for vertex in &mut output.vertices {
+fn compute_gradient_normals(
+  volume: &[SdfSample; SAMPLE_SIZE_CB],
+  output: &mut MeshOutput,
+  parallel: bool,
+) {
+  for_each_vertex(output, parallel, |vertex| {
     let [x, y, z] = vertex.cell_position;
     let base_idx = coord_to_index(x as usize, y as usize, z as usize);
 
@@ -450,7 +764,7 @@ fn compute_gradient_normals(volume: &[SdfSample; SAMPLE_SIZE_CB], output: &mut M
       std::array::from_fn(|i| sdf_conversion::to_float(volume[base_idx + CORNER_OFFSETS[i]], 1.0));
 
     vertex.normal = gradient::compute(&samples);
-  }
+  });
 }
 
 /// Compute interpolated gradient normals using vertex position within cell.
@@ -461,8 +775,9 @@ fn compute_gradient_normals(volume: &[SdfSample; SAMPLE_SIZE_CB], output: &mut M
 fn compute_interpolated_gradient_normals(
   volume: &[SdfSample; SAMPLE_SIZE_CB],
   output: &mut MeshOutput,
+  parallel: bool,
 ) {
-  for vertex in &mut output.vertices {
+  for_each_vertex(output, parallel, |vertex| {
     let [cx, cy, cz] = vertex.cell_position;
     let base_idx = coord_to_index(cx as usize, cy as usize, cz as usize);
 
@@ -475,7 +790,40 @@ fn compute_interpolated_gradient_normals(
     let frac = [px - cx as f32, py - cy as f32, pz - cz as f32];
 
     vertex.normal = gradient::compute_interpolated(&samples, frac);
-  }
+  });
+}
+
+/// Trilinearly interpolate per-sample analytic normals at each vertex.
+fn interpolate_analytic_normals(
+  normals: &[[f32; 3]; SAMPLE_SIZE_CB],
+  output: &mut MeshOutput,
+  parallel: bool,
+) {
+  use glam::Vec3A;
+
+  let parallel = parallel && output.vertices.len() >= PARALLEL_NORMALS_MIN_VERTICES;
+  for_each_vertex(output, parallel, |vertex| {
+    let [cx, cy, cz] = vertex.cell_position;
+    let base_idx = coord_to_index(cx as usize, cy as usize, cz as usize);
+    let [px, py, pz] = vertex.position;
+    let frac = [px - cx as f32, py - cy as f32, pz - cz as f32].map(|f| f.clamp(0.0, 1.0));
+
+    // CORNER_OFFSETS[i] is corner (i & 1, (i >> 1) & 1, (i >> 2) & 1)
+    let mut normal = Vec3A::ZERO;
+    for (i, offset) in CORNER_OFFSETS.iter().enumerate() {
+      let w = (0..3)
+        .map(|axis| {
+          if (i >> axis) & 1 == 1 {
+            frac[axis]
+          } else {
+            1.0 - frac[axis]
+          }
+        })
+        .product::<f32>();
+      normal += Vec3A::from_array(normals[base_idx + offset]) * w;
+    }
+    vertex.normal = normal.try_normalize().unwrap_or(Vec3A::Y).to_array();
+  });
 }
 
 /// Blend geometry normals with gradient normals at chunk boundaries.
@@ -525,24 +873,31 @@ fn blend_boundary_normals(
 ///
 /// Creates vertices with placeholder normals. Actual normals are computed
 /// in the normal pass.
-fn process_cell_geometry(
-  volume: &[SdfSample; SAMPLE_SIZE_CB],
+#[allow(clippy::too_many_arguments)]
+fn process_cell_geometry<S: SdfValue, B: CellIndices>(
+  volume: &[S; SAMPLE_SIZE_CB],
   materials: &[MaterialId; SAMPLE_SIZE_CB],
   pos: [usize; 3],
-  index_buffer: &mut IndexBuffer,
+  index_buffer: &mut B,
   output: &mut MeshOutput,
-  _config: &MeshConfig,
+  config: &MeshConfig,
   transition_bits: u32,
+  enclosed: Option<&[bool]>,
+  flat: Option<&mut greedy::FlatQuads>,
 ) {
   use vertex_calc::Vec3A;
 
+  #[cfg(test)]
+  CELL_VISITS.with(|visits| visits.set(visits.get() + 1));
+
   let [x, y, z] = pos;
 
   // Sample 8 corners of the cube
   let base_idx = coord_to_index(x, y, z);
 
-  // Load raw i8 samples for corner mask
-  let raw_samples: [i8; 8] = std::array::from_fn(|i| volume[base_idx + CORNER_OFFSETS[i]]);
+  // Load corner samples; the corner mask only needs their i8 signs
+  let corners: [S; 8] = std::array::from_fn(|i| volume[base_idx + CORNER_OFFSETS[i]]);
+  let raw_samples: [i8; 8] = corners.map(SdfValue::saturate);
 
   // Build corner mask for material weights and triangulation winding
   let corner_mask = corner_mask::build(raw_samples);
@@ -552,15 +907,39 @@ fn process_cell_geometry(
     return;
   }
 
+  // Inner wall of a sealed cavity: never visible
+  if enclosed.is_some_and(|enclosed| cavities::is_enclosed_cell(enclosed, base_idx)) {
+    return;
+  }
+
   // Convert to f32 for vertex calculations
-  let samples: [f32; 8] = std::array::from_fn(|i| sdf_conversion::to_float(raw_samples[i], 1.0));
+  let samples: [f32; 8] = corners.map(SdfValue::to_voxels);
 
   // Compute vertex position using direct edge iteration (returns Vec3A)
   let cell_origin = Vec3A::new(x as f32, y as f32, z as f32);
-  let position = cell_origin + vertex_calc::compute_position_direct(&samples);
+  let mut local = vertex_calc::compute_position_direct(&samples);
+  if config.vertex_refinement_steps > 0 {
+    local = vertex_calc::refine_position_newton(&samples, local, config.vertex_refinement_steps);
+  }
+  let position = cell_origin + local;
 
   // Compute material weights
-  let material_weights = material_weights::compute(materials, corner_mask, base_idx);
+  let (material_weights, material_weights_hi) = if config.positions_only {
+    ([1.0, 0.0, 0.0, 0.0], [0.0; 4])
+  } else if config.material_count > 4 {
+    material_weights::compute8(materials, corner_mask, base_idx)
+  } else {
+    (
+      material_weights::compute(materials, corner_mask, base_idx),
+      [0.0; 4],
+    )
+  };
+
+  let curvature = if config.compute_curvature && !config.positions_only {
+    gradient::compute_curvature(volume, pos)
+  } else {
+    0.0
+  };
 
   // Check for boundary vertex and compute displaced position
   let cell_pos = [x as i32, y as i32, z as i32];
@@ -581,8 +960,15 @@ fn process_cell_geometry(
     normal: [0.0, 1.0, 0.0], // Placeholder
     material_weights,
     cell_position: cell_pos,
+    tangent: [1.0, 0.0, 0.0, 1.0], // Placeholder (computed in tangent pass)
+    uv: [0.0; 2], // Placeholder (computed in UV pass)
+    ao: 1.0, // Placeholder (computed in AO pass)
+    curvature,
   });
   output.displaced_positions.push(displaced_pos);
+  if config.material_count > 4 {
+    output.material_weights_hi.push(material_weights_hi);
+  }
   output.bounds.encapsulate(displaced_pos);
 
   // Look up edge mask for triangulation (still needed for determining which quads
@@ -590,7 +976,15 @@ fn process_cell_geometry(
   let edge_mask = EDGE_TABLE[corner_mask as usize];
 
   // Emit triangles for active edges
-  emit_triangles(pos, edge_mask, corner_mask, index_buffer, output);
+  emit_triangles(
+    pos,
+    edge_mask,
+    corner_mask,
+    index_buffer,
+    output,
+    config,
+    flat,
+  );
 }
 
 /// Emit triangles for active edges of a cell.
@@ -601,14 +995,26 @@ fn process_cell_geometry(
 /// Note: Triangles are emitted liberally here. Z-fighting prevention is handled
 /// by post-processing in `filter_boundary_triangles()` which removes triangles
 /// where ALL vertices are in the overlap region.
-fn emit_triangles(
+///
+/// With `config.watertight`, a quad is skipped as a whole when any of its 4
+/// vertices is in the overlap region, so the chunk never carries half-quads
+/// whose open edges overhang the boundary.
+///
+/// With `config.material_aware_split`, the diagonal joining the vertices with
+/// the most similar material weights wins over the shorter one.
+///
+/// Quads accepted by `flat` are left for the greedy merge instead.
+fn emit_triangles<B: CellIndices>(
   pos: [usize; 3],
   edge_mask: u16,
   corner_mask: u8,
-  index_buffer: &IndexBuffer,
+  index_buffer: &B,
   output: &mut MeshOutput,
+  config: &MeshConfig,
+  mut flat: Option<&mut greedy::FlatQuads>,
 ) {
   let [x, y, z] = pos;
+  let strict = config.watertight;
 
   // Determine winding order based on corner 0
   // Flip if corner 0 is outside (positive SDF)
@@ -630,6 +1036,12 @@ fn emit_triangles(
       continue;
     }
 
+    // Strict: the quad's other vertices are at pos - u / pos - v, so `pos`
+    // holds the largest cell coordinates on every axis.
+    if strict && pos_arr.iter().any(|&c| c > LAST_INTERIOR_CELL) {
+      continue;
+    }
+
     // Get 4 vertex indices forming the quad
     let v_a = index_buffer.get(x, y, z);
 
@@ -653,6 +1065,13 @@ fn emit_triangles(
       continue;
     }
 
+    let quad = [v_a, v_b, v_c, v_d].map(|v| v as u16);
+    if let Some(flat) = flat.as_deref_mut() {
+      if flat.try_collect(output, axis, pos_arr, quad, flip) {
+        continue;
+      }
+    }
+
     // Diagonal optimization: split along the shorter diagonal for better triangle quality.
     // Quad layout:
     //   v_c --- v_a
@@ -674,33 +1093,57 @@ fn emit_triangles(
       + (pos_c[1] - pos_d[1]).powi(2)
       + (pos_c[2] - pos_d[2]).powi(2);
 
-    let use_ab_diagonal = dist_ab_sq < dist_cd_sq;
-
-    // Emit two triangles forming the quad
-    if use_ab_diagonal {
-      // Split along A-B diagonal
-      if flip {
-        output.indices.extend_from_slice(&[
-          v_a as u16, v_b as u16, v_c as u16, v_a as u16, v_d as u16, v_b as u16,
-        ]);
-      } else {
-        output.indices.extend_from_slice(&[
-          v_a as u16, v_b as u16, v_d as u16, v_a as u16, v_c as u16, v_b as u16,
-        ]);
+    let mut use_ab_diagonal = dist_ab_sq < dist_cd_sq;
+
+    // Material-aware: splitting along A-B puts A and B in both triangles, so
+    // the triangles' summed weight variation is lowest along the diagonal
+    // whose endpoints have the closest weights
+    if config.material_aware_split {
+      let weights = |v: i32| &output.vertices[v as usize].material_weights;
+      let weights_hi = |v: i32| output.material_weights_hi.get(v as usize);
+      let diff = |a: i32, b: i32| {
+        material_weights::distance(weights(a), weights(b))
+          + weights_hi(a)
+            .zip(weights_hi(b))
+            .map_or(0.0, |(a, b)| material_weights::distance(a, b))
+      };
+      let (diff_ab, diff_cd) = (diff(v_a, v_b), diff(v_c, v_d));
+      if (diff_ab - diff_cd).abs() > material_weights::SPLIT_EPSILON {
+        use_ab_diagonal = diff_ab < diff_cd;
       }
+    }
+
+    push_quad(output, quad, use_ab_diagonal, flip);
+  }
+}
+
+/// Emit quad A, B, C, D (layout in `emit_triangles`) as two triangles split
+/// along A-B (`use_ab_diagonal`) or C-D.
+fn push_quad(output: &mut MeshOutput, quad: [u16; 4], use_ab_diagonal: bool, flip: bool) {
+  let [v_a, v_b, v_c, v_d] = quad;
+  if use_ab_diagonal {
+    // Split along A-B diagonal
+    if flip {
+      output
+        .indices
+        .extend_from_slice(&[v_a, v_b, v_c, v_a, v_d, v_b]);
     } else {
-      // Split along C-D diagonal (must maintain same winding as A-B case)
-      if flip {
-        // CCW winding: (C,A,D), (D,B,C)
-        output.indices.extend_from_slice(&[
-          v_c as u16, v_a as u16, v_d as u16, v_d as u16, v_b as u16, v_c as u16,
-        ]);
-      } else {
-        // CW winding: (C,D,A), (C,B,D)
-        output.indices.extend_from_slice(&[
-          v_c as u16, v_d as u16, v_a as u16, v_c as u16, v_b as u16, v_d as u16,
-        ]);
-      }
+      output
+        .indices
+        .extend_from_slice(&[v_a, v_b, v_d, v_a, v_c, v_b]);
+    }
+  } else {
+    // Split along C-D diagonal (must maintain same winding as A-B case)
+    if flip {
+      // CCW winding: (C,A,D), (D,B,C)
+      output
+        .indices
+        .extend_from_slice(&[v_c, v_a, v_d, v_d, v_b, v_c]);
+    } else {
+      // CW winding: (C,D,A), (C,B,D)
+      output
+        .indices
+        .extend_from_slice(&[v_c, v_d, v_a, v_c, v_b, v_d]);
     }
   }
 }
@@ -1,22 +1,10 @@
+use glam::Vec3A;
+
 use super::*;
 use crate::types::sdf_conversion;
 
 fn create_sphere_sdf(radius: f32, center: [f32; 3]) -> [SdfSample; SAMPLE_SIZE_CB] {
-  let mut volume = [0i8; SAMPLE_SIZE_CB];
-  for x in 0..SAMPLE_SIZE {
-    for y in 0..SAMPLE_SIZE {
-      for z in 0..SAMPLE_SIZE {
-        let dx = x as f32 - center[0];
-        let dy = y as f32 - center[1];
-        let dz = z as f32 - center[2];
-        let dist = (dx * dx + dy * dy + dz * dz).sqrt();
-        let sdf = dist - radius;
-        // Use proper SDF scaling for storage (voxel_size=1.0 for cell-based tests)
-        volume[coord_to_index(x, y, z)] = sdf_conversion::to_storage(sdf, 1.0);
-      }
-    }
-  }
-  volume
+  volume_from_sdf(|p| (Vec3A::from_array(p) - Vec3A::from_array(center)).length() - radius)
 }
 
 #[test]
@@ -162,6 +150,75 @@ fn test_normal_mode_blended() {
   );
 }
 
+#[test]
+fn test_normal_mode_flat_splits_vertices() {
+  let volume = create_sphere_sdf(8.0, [16.0, 16.0, 16.0]);
+  let mut materials = [0u8; SAMPLE_SIZE_CB];
+  for (i, m) in materials.iter_mut().enumerate() {
+    *m = (i % 3) as u8;
+  }
+  let config = MeshConfig::new().with_normal_mode(NormalMode::Flat);
+
+  let output = generate(&volume, &materials, &config);
+  let shared = generate(&volume, &materials, &MeshConfig::new());
+
+  assert!(!output.is_empty());
+  assert!(!output.flat_shading_fallback);
+  assert_eq!(output.vertices.len(), output.indices.len());
+  assert_eq!(output.displaced_positions.len(), output.vertices.len());
+
+  // Every vertex is referenced by exactly one triangle
+  let mut refs = vec![0u32; output.vertices.len()];
+  for &i in &output.indices {
+    refs[i as usize] += 1;
+  }
+  assert!(refs.iter().all(|&r| r == 1));
+
+  // All three corners carry the face normal and attributes of a shared vertex
+  for (tri, shared_tri) in output
+    .indices
+    .chunks_exact(3)
+    .zip(shared.indices.chunks_exact(3))
+  {
+    let normal = output.vertices[tri[0] as usize].normal;
+    for (&i, &j) in tri.iter().zip(shared_tri) {
+      let vertex = &output.vertices[i as usize];
+      let original = &shared.vertices[j as usize];
+      assert_eq!(vertex.normal, normal);
+      assert_eq!(vertex.position, original.position);
+      assert_eq!(vertex.material_weights, original.material_weights);
+      assert_eq!(vertex.cell_position, original.cell_position);
+    }
+  }
+  assert!(
+    normals_are_normalized(&output),
+    "Flat normals should be normalized"
+  );
+}
+
+#[test]
+fn test_normal_mode_flat_reports_index_overflow() {
+  // Alternating signs put a surface through every cell, far more triangle
+  // corners than u16 indices can address once split
+  let volume = volume_from_sdf(|[x, y, z]| {
+    if (x + y + z) as usize % 2 == 0 {
+      -0.5
+    } else {
+      0.5
+    }
+  });
+  let materials = [0u8; SAMPLE_SIZE_CB];
+  let config = MeshConfig::new().with_normal_mode(NormalMode::Flat);
+
+  let output = generate(&volume, &materials, &config);
+  let smooth = generate(&volume, &materials, &MeshConfig::new());
+
+  assert!(output.indices.len() > u16::MAX as usize + 1);
+  assert!(output.flat_shading_fallback);
+  assert_eq!(output.vertices.len(), smooth.vertices.len());
+  assert_eq!(output.indices, smooth.indices);
+}
+
 #[test]
 fn test_normal_modes_produce_different_results() {
   let volume = create_sphere_sdf(8.0, [16.0, 16.0, 16.0]);
@@ -202,3 +259,413 @@ fn test_normal_modes_produce_different_results() {
   );
 }
 
+/// Horizontal plane `y = 16.3` spanning the whole chunk, overlap included.
+fn create_plane_sdf() -> [SdfSample; SAMPLE_SIZE_CB] {
+  volume_from_sdf(|[_, y, _]| y - 16.3)
+}
+
+#[test]
+fn test_boundary_triangles_removed_by_default() {
+  let volume = create_plane_sdf();
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let output = generate(&volume, &materials, &MeshConfig::default());
+
+  assert!(!output.is_empty());
+  assert!(output.boundary_indices.is_empty());
+}
+
+#[test]
+fn test_debug_keep_boundary_tags_overlap_triangles() {
+  let volume = create_plane_sdf();
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let plain = generate(&volume, &materials, &MeshConfig::default());
+  let debug = generate(
+    &volume,
+    &materials,
+    &MeshConfig::default().with_debug_keep_boundary(true),
+  );
+
+  // Regular triangles are untouched; overlap triangles live in their own range
+  assert_eq!(debug.indices, plain.indices);
+  assert!(
+    !debug.boundary_indices.is_empty(),
+    "Plane crosses the overlap region, expected tagged triangles"
+  );
+  assert_eq!(debug.boundary_indices.len() % 3, 0);
+
+  let last_interior = LAST_INTERIOR_CELL as i32;
+  let outside = |i: u16| {
+    debug.vertices[i as usize]
+      .cell_position
+      .iter()
+      .any(|&c| c > last_interior)
+  };
+  for tri in debug.boundary_indices.chunks_exact(3) {
+    assert!(
+      tri.iter().all(|&i| outside(i)),
+      "Tagged triangle {:?} has an interior vertex",
+      tri
+    );
+  }
+}
+
+#[test]
+fn test_disabling_boundary_filter_keeps_counted_triangles() {
+  let volume = create_plane_sdf();
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let filtered = generate(&volume, &materials, &MeshConfig::default());
+  let unfiltered = generate(
+    &volume,
+    &materials,
+    &MeshConfig::default().with_filter_boundary(false),
+  );
+
+  assert!(filtered.filtered_triangle_count > 0);
+  assert_eq!(unfiltered.filtered_triangle_count, 0);
+  assert_eq!(
+    unfiltered.triangle_count() - filtered.triangle_count(),
+    filtered.filtered_triangle_count
+  );
+}
+
+#[test]
+fn test_parallel_normals_match_serial() {
+  let volume = create_sphere_sdf(14.0, [16.0, 16.0, 16.0]);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let modes = [
+    NormalMode::Gradient,
+    NormalMode::InterpolatedGradient,
+    NormalMode::Geometry,
+    NormalMode::Blended {
+      blend_distance: 2.0,
+    },
+  ];
+
+  for mode in modes {
+    let config = MeshConfig::new().with_normal_mode(mode);
+    let serial = generate(&volume, &materials, &config);
+    let parallel = generate(
+      &volume,
+      &materials,
+      &config.clone().with_parallel_normals(true),
+    );
+
+    assert!(
+      serial.vertices.len() >= PARALLEL_NORMALS_MIN_VERTICES,
+      "Sphere too small to take the parallel path ({} vertices)",
+      serial.vertices.len()
+    );
+    assert_eq!(
+      serial.vertices, parallel.vertices,
+      "Parallel normals differ from serial for {:?}",
+      mode
+    );
+  }
+}
+
+/// Number of triangles using each edge, keyed by sorted vertex indices.
+fn edge_counts(output: &MeshOutput) -> std::collections::HashMap<(u16, u16), u32> {
+  let mut counts = std::collections::HashMap::new();
+  for tri in output.indices.chunks_exact(3) {
+    for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+      *counts.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+    }
+  }
+  counts
+}
+
+#[test]
+fn test_watertight_sphere_is_closed() {
+  // Enclosed by the chunk: cells 6..22, clear of the overlap region
+  let volume = create_sphere_sdf(8.0, [14.0, 14.0, 14.0]);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+  let config = MeshConfig::new().with_watertight(true);
+  let output = generate(&volume, &materials, &config);
+  assert!(output.triangle_count() > 100);
+
+  let edges = edge_counts(&output);
+  let open_edges: Vec<_> = edges.iter().filter(|&(_, &count)| count != 2).collect();
+  assert!(
+    open_edges.is_empty(),
+    "{} edges not shared by exactly two triangles, e.g. {:?}",
+    open_edges.len(),
+    open_edges.first()
+  );
+
+  // One closed genus-0 surface: V - E + F = 2
+  let used: std::collections::HashSet<u16> = output.indices.iter().copied().collect();
+  let euler = used.len() as i64 - edges.len() as i64 + output.triangle_count() as i64;
+  assert_eq!(euler, 2);
+}
+
+#[test]
+fn test_vertex_refinement_moves_vertices_onto_sphere() {
+  // Unsaturated SDF (10 levels per voxel) so the cell interpolant is curved
+  let radius = 8.0f32;
+  let center = Vec3A::splat(14.0);
+  let mut volume = [0i8; SAMPLE_SIZE_CB];
+  for x in 0..SAMPLE_SIZE {
+    for y in 0..SAMPLE_SIZE {
+      for z in 0..SAMPLE_SIZE {
+        let p = Vec3A::new(x as f32, y as f32, z as f32);
+        let sdf = (p - center).length() - radius;
+        volume[coord_to_index(x, y, z)] = (sdf * 10.0).clamp(-127.0, 127.0).round() as i8;
+      }
+    }
+  }
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let mean_error = |config: &MeshConfig| {
+    let output = generate(&volume, &materials, config);
+    assert!(!output.vertices.is_empty());
+    let total: f32 = output
+      .vertices
+      .iter()
+      .map(|v| ((Vec3A::from_array(v.position) - center).length() - radius).abs())
+      .sum();
+    total / output.vertices.len() as f32
+  };
+
+  let centroid_error = mean_error(&MeshConfig::new());
+  let refined_error = mean_error(&MeshConfig::new().with_vertex_refinement_steps(3));
+
+  assert!(
+    refined_error < centroid_error,
+    "Refined error {} should be below centroid error {}",
+    refined_error,
+    centroid_error
+  );
+}
+
+#[test]
+fn test_positive_inside_convention_matches_negative_inside() {
+  // Unsaturated SDF with exact zeros, which are air under both conventions
+  let center = Vec3A::new(14.0, 15.5, 13.0);
+  let mut negative_inside = [0i8; SAMPLE_SIZE_CB];
+  for x in 0..SAMPLE_SIZE {
+    for y in 0..SAMPLE_SIZE {
+      for z in 0..SAMPLE_SIZE {
+        let p = Vec3A::new(x as f32, y as f32, z as f32);
+        let sdf = (p - center).length() - 7.5;
+        negative_inside[coord_to_index(x, y, z)] = (sdf * 10.0).clamp(-127.0, 127.0).round() as i8;
+      }
+    }
+  }
+  let positive_inside = negative_inside.map(|v| -v);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let native = generate(&negative_inside, &materials, &MeshConfig::default());
+  let flipped = generate(
+    &positive_inside,
+    &materials,
+    &MeshConfig::default().with_sdf_convention(SdfConvention::PositiveInside),
+  );
+
+  assert!(!native.vertices.is_empty());
+  assert_eq!(native.vertices, flipped.vertices);
+  assert_eq!(native.indices, flipped.indices);
+
+  // Meshing the flipped volume as negative-inside turns the sphere inside out
+  let misread = generate(&positive_inside, &materials, &MeshConfig::default());
+  assert_ne!(native.indices, misread.indices);
+}
+
+#[test]
+fn test_positions_only_keeps_geometry() {
+  let volume = create_sphere_sdf(8.0, [16.0, 16.0, 16.0]);
+  let mut materials = [0u8; SAMPLE_SIZE_CB];
+  materials[SAMPLE_SIZE_CB / 2..].fill(2);
+  let config = MeshConfig::default()
+    .with_tangents(true)
+    .with_uv_mode(UvMode::Triplanar);
+
+  let full = generate(&volume, &materials, &config);
+  let collision = generate(&volume, &materials, &config.with_positions_only(true));
+
+  assert!(!full.vertices.is_empty());
+  assert_eq!(collision.vertices.len(), full.vertices.len());
+  assert_eq!(collision.indices, full.indices);
+  for (c, f) in collision.vertices.iter().zip(&full.vertices) {
+    assert_eq!(c.position, f.position);
+    assert_eq!(c.normal, Vertex::default().normal);
+    assert_eq!(c.material_weights, Vertex::default().material_weights);
+    assert_eq!(c.uv, Vertex::default().uv);
+  }
+  assert!(full.vertices.iter().any(|v| v.material_weights[2] > 0.0));
+}
+
+#[test]
+fn test_analytic_normals_point_radially() {
+  use crate::pipeline::VolumeSampler;
+  use crate::sdf_samplers::SphereSampler;
+
+  let sampler = SphereSampler::new(10.0);
+  let grid_offset = [-16, -16, -16];
+  let mut volume = [0i8; SAMPLE_SIZE_CB];
+  let mut materials = [0u8; SAMPLE_SIZE_CB];
+  let mut normals = [[0.0f32; 3]; SAMPLE_SIZE_CB];
+  sampler.sample_volume(grid_offset, 1.0, &mut volume, &mut materials);
+  assert!(sampler.sample_normals(grid_offset, 1.0, [0.0; 3], &mut normals));
+
+  // Largest 1 - cos(angle) between vertex normals and the radial direction
+  let worst_error = |output: &MeshOutput| {
+    output
+      .vertices
+      .iter()
+      .map(|v| {
+        let radial = (Vec3A::from_array(v.position) - Vec3A::splat(16.0)).normalize();
+        1.0 - Vec3A::from_array(v.normal).dot(radial)
+      })
+      .fold(0.0f32, f32::max)
+  };
+
+  let config = MeshConfig::default().with_analytic_normals(true);
+  let analytic = generate_with_normals(&volume, &materials, Some(&normals), &config);
+  let gradient = generate(&volume, &materials, &config);
+
+  assert!(!analytic.vertices.is_empty());
+  assert_eq!(analytic.indices, gradient.indices);
+  let (analytic_error, gradient_error) = (worst_error(&analytic), worst_error(&gradient));
+  assert!(analytic_error < 1e-3, "analytic error {}", analytic_error);
+  assert!(analytic_error < gradient_error);
+
+  // Without the flag the analytic normals are ignored
+  let ignored = generate_with_normals(&volume, &materials, Some(&normals), &MeshConfig::default());
+  assert_eq!(ignored.vertices, gradient.vertices);
+}
+
+#[test]
+fn test_sdf16_reduces_position_error_on_shallow_ramp() {
+  use crate::pipeline::VolumeSampler;
+  use crate::sdf_samplers::TiltedPlaneSampler;
+
+  // 5° ramp: nearly every Y edge crossing lies further than i8's ±0.1 voxel
+  // range from its samples, so i8 interpolation snaps toward mid-edge
+  let sampler = TiltedPlaneSampler::new()
+    .with_height(16.3)
+    .with_angle_degrees(5.0);
+  let (sin_a, cos_a) = sampler.angle.sin_cos();
+  let mut volume = [0i8; SAMPLE_SIZE_CB];
+  let mut volume16 = [0i16; SAMPLE_SIZE_CB];
+  let mut materials = [0u8; SAMPLE_SIZE_CB];
+  sampler.sample_volume([0; 3], 1.0, &mut volume, &mut materials);
+  sampler.sample_volume16([0; 3], 1.0, [0.0; 3], &mut volume16, &mut materials);
+
+  // Largest distance from a vertex to the true plane, in voxels
+  let worst_error = |output: &MeshOutput| {
+    output
+      .vertices
+      .iter()
+      .map(|v| {
+        let [x, y, _] = v.position.map(f64::from);
+        ((y - sampler.height) * cos_a - x * sin_a).abs()
+      })
+      .fold(0.0f64, f64::max)
+  };
+
+  let config = MeshConfig::default();
+  let narrow = generate(&volume, &materials, &config);
+  let wide = generate16(&volume16, &materials, &config);
+
+  assert!(!wide.vertices.is_empty());
+  assert_eq!(wide.vertices.len(), narrow.vertices.len());
+  let (narrow_error, wide_error) = (worst_error(&narrow), worst_error(&wide));
+  assert!(wide_error < 0.01, "i16 error {}", wide_error);
+  assert!(
+    wide_error < narrow_error,
+    "i16 error {} vs i8 error {}",
+    wide_error,
+    narrow_error
+  );
+}
+
+#[test]
+fn test_material_aware_split_sharpens_material_boundary() {
+  const STONE: MaterialId = 0;
+  const GRASS: MaterialId = 1;
+
+  // Flat ground at y = 16.5 with a disc of grass in stone: the disc's rim
+  // crosses quads at every orientation, so both diagonals are needed
+  let mut volume = [0i8; SAMPLE_SIZE_CB];
+  let mut materials = [STONE; SAMPLE_SIZE_CB];
+  for x in 0..SAMPLE_SIZE {
+    for y in 0..SAMPLE_SIZE {
+      for z in 0..SAMPLE_SIZE {
+        let idx = coord_to_index(x, y, z);
+        volume[idx] = sdf_conversion::to_storage(y as f32 - 16.5, 1.0);
+        let (dx, dz) = (x as f32 - 16.0, z as f32 - 16.0);
+        if dx * dx + dz * dz < 64.0 {
+          materials[idx] = GRASS;
+        }
+      }
+    }
+  }
+
+  // Summed pairwise weight distance within each triangle
+  let variation = |output: &MeshOutput| -> f32 {
+    output
+      .indices
+      .chunks_exact(3)
+      .map(|tri| {
+        let w = |i: usize| &output.vertices[tri[i] as usize].material_weights;
+        material_weights::distance(w(0), w(1))
+          + material_weights::distance(w(1), w(2))
+          + material_weights::distance(w(2), w(0))
+      })
+      .sum()
+  };
+
+  let plain = generate(&volume, &materials, &MeshConfig::default());
+  let split = generate(
+    &volume,
+    &materials,
+    &MeshConfig::default().with_material_aware_split(true),
+  );
+
+  assert_eq!(split.vertices.len(), plain.vertices.len());
+  assert_eq!(split.indices.len(), plain.indices.len());
+  let (plain_variation, split_variation) = (variation(&plain), variation(&split));
+  assert!(
+    split_variation < plain_variation,
+    "split {} vs plain {}",
+    split_variation,
+    plain_variation
+  );
+}
+
+#[test]
+fn test_curvature_only_when_enabled() {
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  // 16-bit samples keep the field unsaturated around the surface
+  let mut sphere = [0i16; SAMPLE_SIZE_CB];
+  for x in 0..SAMPLE_SIZE {
+    for y in 0..SAMPLE_SIZE {
+      for z in 0..SAMPLE_SIZE {
+        let d = Vec3A::new(x as f32, y as f32, z as f32) - Vec3A::splat(16.0);
+        let sdf = d.length() - 10.0;
+        sphere[coord_to_index(x, y, z)] = sdf_conversion::to_storage16(sdf, 1.0);
+      }
+    }
+  }
+
+  let plain = generate16(&sphere, &materials, &MeshConfig::default());
+  assert!(!plain.is_empty());
+  assert!(plain.vertices.iter().all(|v| v.curvature == 0.0));
+
+  let config = MeshConfig::default().with_curvature(true);
+  let curved = generate16(&sphere, &materials, &config);
+  assert_eq!(curved.vertices.len(), plain.vertices.len());
+  let total: f32 = curved.vertices.iter().map(|v| v.curvature).sum();
+  let mean = total / curved.vertices.len() as f32;
+  // 1 / radius, give or take the vertices' offset from their cell centers
+  assert!((mean - 0.1).abs() < 0.005, "Mean sphere curvature {}", mean);
+
+  let flat = generate(&create_plane_sdf(), &materials, &config);
+  assert!(!flat.is_empty());
+  assert!(flat.vertices.iter().all(|v| v.curvature.abs() < 1e-6));
+}
@@ -0,0 +1,253 @@
+//! Partial remeshing of an edited sub-region of a chunk.
+//!
+//! A small brush changes a handful of samples, yet a full remesh re-runs the
+//! geometry pass over all 31³ cells. [`generate_region`] instead rebuilds only
+//! the cells around the modified samples and merges them into the chunk's
+//! existing mesh: vertices outside the region are kept as they are.
+//!
+//! A quad is owned by the cell that emits it, which holds the largest cell
+//! coordinate of its 4 vertices on every axis. Quads owned by a rebuilt cell,
+//! or by a cell one step past the region on the positive side (whose quads
+//! reach back into it), are re-emitted; all other triangles are kept.
+
+use super::*;
+
+/// Inclusive AABB of modified samples within a chunk's 32³ volume.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VoxelRegion {
+  pub min: [usize; 3],
+  pub max: [usize; 3],
+}
+
+impl VoxelRegion {
+  /// The whole volume.
+  pub const FULL: Self = Self {
+    min: [0; 3],
+    max: [SAMPLE_SIZE - 1; 3],
+  };
+
+  /// Region spanning samples `min..=max`, clamped to the volume.
+  pub fn new(min: [usize; 3], max: [usize; 3]) -> Self {
+    let last = SAMPLE_SIZE - 1;
+    Self {
+      min: min.map(|v| v.min(last)),
+      max: std::array::from_fn(|axis| max[axis].clamp(min[axis].min(last), last)),
+    }
+  }
+
+  /// Smallest region containing both.
+  pub fn union(&self, other: &Self) -> Self {
+    Self {
+      min: std::array::from_fn(|axis| self.min[axis].min(other.min[axis])),
+      max: std::array::from_fn(|axis| self.max[axis].max(other.max[axis])),
+    }
+  }
+
+  /// Whether the sample at `pos` lies in the region.
+  pub fn contains(&self, pos: [usize; 3]) -> bool {
+    (0..3).all(|axis| (self.min[axis]..=self.max[axis]).contains(&pos[axis]))
+  }
+
+  /// Inclusive range of cells to rebuild: every cell with a modified corner
+  /// sample, plus a one-cell margin.
+  pub fn cell_range(&self) -> ([usize; 3], [usize; 3]) {
+    let last = SAMPLE_SIZE - 2;
+    (
+      self.min.map(|v| v.saturating_sub(2)),
+      self.max.map(|v| (v + 1).min(last)),
+    )
+  }
+}
+
+/// Dense cell to vertex index table covering every cell of the volume.
+///
+/// Unlike `IndexBuffer` it keeps every x slice, so cells outside the
+/// rebuilt range can be seeded with the existing mesh's vertices.
+struct CellIndexGrid {
+  data: Vec<i32>,
+}
+
+impl CellIndexGrid {
+  const CELLS: usize = SAMPLE_SIZE - 1;
+
+  fn new() -> Self {
+    Self {
+      data: vec![-1; Self::CELLS * Self::CELLS * Self::CELLS],
+    }
+  }
+
+  #[inline]
+  fn index(x: usize, y: usize, z: usize) -> Option<usize> {
+    let cells = Self::CELLS;
+    if x < cells && y < cells && z < cells {
+      Some((x * cells + y) * cells + z)
+    } else {
+      None
+    }
+  }
+}
+
+impl CellIndices for CellIndexGrid {
+  #[inline]
+  fn get(&self, x: usize, y: usize, z: usize) -> i32 {
+    Self::index(x, y, z).map_or(-1, |idx| self.data[idx])
+  }
+
+  #[inline]
+  fn set(&mut self, x: usize, y: usize, z: usize, value: i32) {
+    if let Some(idx) = Self::index(x, y, z) {
+      self.data[idx] = value;
+    }
+  }
+}
+
+/// Remesh only the cells around `region` and merge them into `existing`.
+///
+/// `existing` must be the mesh `generate` produced for this volume before
+/// the samples in `region` changed, with the same `config`. Vertices and
+/// triangles away from the region are carried over; the geometry pass runs
+/// only over [`VoxelRegion::cell_range`]. Per-vertex passes (normals,
+/// tangents, UVs, AO) then run over the merged mesh.
+///
+/// Falls back to a full `generate` when `existing` is empty or `config`
+/// enables a pass that doesn't preserve the cell to vertex mapping (welding,
+/// simplification, greedy merging, skirts, kept boundary triangles) or
+/// depends on the whole volume (cavity skipping).
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, name = "surface_nets::generate_region"))]
+pub fn generate_region(
+  volume: &[SdfSample; SAMPLE_SIZE_CB],
+  materials: &[MaterialId; SAMPLE_SIZE_CB],
+  config: &MeshConfig,
+  existing: &MeshOutput,
+  region: &VoxelRegion,
+) -> MeshOutput {
+  let needs_full = config.weld_vertices
+    || config.simplify_angle_deg.is_some()
+    || config.mesh_algorithm == MeshAlgorithm::Greedy
+    || (config.skirt_depth != 0.0 && !config.watertight)
+    || config.debug_keep_boundary
+    || config.skip_enclosed_cavities;
+  if existing.is_empty() || needs_full {
+    return generate(volume, materials, config);
+  }
+
+  let native;
+  let volume = if config.sdf_convention == SdfConvention::NegativeInside {
+    volume
+  } else {
+    native = to_native_volume(volume, config.sdf_convention);
+    &*native
+  };
+
+  let (cell_min, cell_max) = region.cell_range();
+  // Owners of quads touching a rebuilt vertex
+  let owner_max = cell_max.map(|v| (v + 1).min(SAMPLE_SIZE - 2));
+  let in_range = |pos: [i32; 3], max: &[usize; 3]| {
+    (0..3).all(|axis| pos[axis] >= cell_min[axis] as i32 && pos[axis] <= max[axis] as i32)
+  };
+
+  let mut output = MeshOutput::new();
+  let mut grid = CellIndexGrid::new();
+
+  // Carry over vertices outside the rebuilt cells
+  let mut remap = vec![-1i32; existing.vertices.len()];
+  for (i, vertex) in existing.vertices.iter().enumerate() {
+    let cell = vertex.cell_position;
+    if in_range(cell, &cell_max) {
+      continue;
+    }
+    let index = output.vertices.len() as i32;
+    remap[i] = index;
+    grid.set(cell[0] as usize, cell[1] as usize, cell[2] as usize, index);
+    let displaced = existing
+      .displaced_positions
+      .get(i)
+      .copied()
+      .unwrap_or(vertex.position);
+    output.vertices.push(*vertex);
+    output.displaced_positions.push(displaced);
+    if config.material_count > 4 {
+      let hi = existing.material_weights_hi.get(i).copied();
+      output.material_weights_hi.push(hi.unwrap_or([0.0; 4]));
+    }
+    output.bounds.encapsulate(vertex.position);
+  }
+
+  // Carry over triangles whose owning cell is untouched
+  for triangle in existing.indices.chunks_exact(3) {
+    let Some(cells) = triangle
+      .iter()
+      .map(|&i| existing.vertices.get(i as usize).map(|v| v.cell_position))
+      .collect::<Option<Vec<_>>>()
+    else {
+      continue;
+    };
+    let owner: [i32; 3] = std::array::from_fn(|axis| cells.iter().map(|c| c[axis]).max().unwrap());
+    if in_range(owner, &owner_max) {
+      continue;
+    }
+    if triangle.iter().all(|&i| remap[i as usize] >= 0) {
+      output
+        .indices
+        .extend(triangle.iter().map(|&i| remap[i as usize] as u16));
+    }
+  }
+
+  let transition_bits = config.neighbor_mask & lod_seams::ALL_TRANSITION_BITS;
+
+  // Geometry pass over the rebuilt cells only
+  {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("geometry_pass").entered();
+    for x in cell_min[0]..=cell_max[0] {
+      for y in cell_min[1]..=cell_max[1] {
+        for z in cell_min[2]..=cell_max[2] {
+          process_cell_geometry(
+            volume,
+            materials,
+            [x, y, z],
+            &mut grid,
+            &mut output,
+            config,
+            transition_bits,
+            None,
+            None,
+          );
+        }
+      }
+    }
+  }
+
+  // Re-emit quads of kept cells just past the region that reach back into it
+  for x in cell_min[0]..=owner_max[0] {
+    for y in cell_min[1]..=owner_max[1] {
+      for z in cell_min[2]..=owner_max[2] {
+        if x <= cell_max[0] && y <= cell_max[1] && z <= cell_max[2] {
+          continue;
+        }
+        let base_idx = coord_to_index(x, y, z);
+        let raw_samples: [i8; 8] = std::array::from_fn(|i| volume[base_idx + CORNER_OFFSETS[i]]);
+        let corner_mask = corner_mask::build(raw_samples);
+        if corner_mask == 0 || corner_mask == 255 {
+          continue;
+        }
+        let edge_mask = EDGE_TABLE[corner_mask as usize];
+        emit_triangles(
+          [x, y, z],
+          edge_mask,
+          corner_mask,
+          &grid,
+          &mut output,
+          config,
+          None,
+        );
+      }
+    }
+  }
+
+  finish_mesh(volume, None, output, config)
+}
+
+#[cfg(test)]
+#[path = "region_test.rs"]
+mod region_test;
@@ -0,0 +1,107 @@
+use super::*;
+use crate::surface_nets::{generate, volume_from_sdf, CELL_VISITS};
+use crate::types::{MeshConfig, SdfSample};
+
+/// Ground plane at `ground_y`, optionally with a sphere carved out of it.
+fn create_ground(ground_y: f32, carve: Option<([f32; 3], f32)>) -> [SdfSample; SAMPLE_SIZE_CB] {
+  volume_from_sdf(|p| {
+    let mut sdf = p[1] - ground_y;
+    if let Some((center, radius)) = carve {
+      let [dx, dy, dz]: [f32; 3] = std::array::from_fn(|axis| p[axis] - center[axis]);
+      sdf = sdf.max(radius - (dx * dx + dy * dy + dz * dz).sqrt());
+    }
+    sdf
+  })
+}
+
+/// Bounding region of every sample that differs between two volumes.
+fn changed_region(
+  before: &[SdfSample; SAMPLE_SIZE_CB],
+  after: &[SdfSample; SAMPLE_SIZE_CB],
+) -> Option<VoxelRegion> {
+  let mut region: Option<VoxelRegion> = None;
+  for x in 0..SAMPLE_SIZE {
+    for y in 0..SAMPLE_SIZE {
+      for z in 0..SAMPLE_SIZE {
+        let idx = coord_to_index(x, y, z);
+        if before[idx] != after[idx] {
+          let sample = VoxelRegion::new([x, y, z], [x, y, z]);
+          region = Some(region.map_or(sample, |r| r.union(&sample)));
+        }
+      }
+    }
+  }
+  region
+}
+
+/// Triangles as position triples, rotated to a canonical start vertex and
+/// sorted, so meshes with different vertex orders compare equal.
+fn triangle_set(output: &MeshOutput) -> Vec<[[u32; 3]; 3]> {
+  let mut triangles: Vec<[[u32; 3]; 3]> = output
+    .indices
+    .chunks_exact(3)
+    .map(|tri| {
+      let p: [[u32; 3]; 3] =
+        std::array::from_fn(|i| output.vertices[tri[i] as usize].position.map(f32::to_bits));
+      let start = (0..3).min_by_key(|&i| p[i]).unwrap();
+      std::array::from_fn(|i| p[(start + i) % 3])
+    })
+    .collect();
+  triangles.sort_unstable();
+  triangles
+}
+
+fn count_cell_visits<F: FnOnce() -> MeshOutput>(f: F) -> (MeshOutput, usize) {
+  CELL_VISITS.with(|visits| visits.set(0));
+  let output = f();
+  (output, CELL_VISITS.with(|visits| visits.get()))
+}
+
+#[test]
+fn test_corner_edit_only_visits_nearby_cells() {
+  let config = MeshConfig::default();
+  let materials = [0u8; SAMPLE_SIZE_CB];
+  let before = create_ground(16.5, None);
+  let after = create_ground(16.5, Some(([2.0, 16.0, 2.0], 3.0)));
+  let existing = generate(&before, &materials, &config);
+
+  let region = changed_region(&before, &after).expect("carve should change samples");
+  assert!(
+    region.max.iter().all(|&v| v <= 6),
+    "edit should stay in one corner"
+  );
+
+  let (partial, visits) =
+    count_cell_visits(|| generate_region(&after, &materials, &config, &existing, &region));
+
+  let (cell_min, cell_max) = region.cell_range();
+  let expected: usize = (0..3)
+    .map(|axis| cell_max[axis] - cell_min[axis] + 1)
+    .product();
+  assert_eq!(visits, expected);
+  assert!(visits < (SAMPLE_SIZE - 1).pow(3) / 50);
+
+  // Merged mesh matches a full remesh of the edited volume
+  let full = generate(&after, &materials, &config);
+  assert_eq!(partial.vertices.len(), full.vertices.len());
+  assert_eq!(triangle_set(&partial), triangle_set(&full));
+  assert_ne!(triangle_set(&partial), triangle_set(&existing));
+}
+
+#[test]
+fn test_empty_existing_mesh_falls_back_to_full_generate() {
+  let config = MeshConfig::default();
+  let materials = [0u8; SAMPLE_SIZE_CB];
+  let volume = create_ground(16.5, None);
+  let region = VoxelRegion::new([0; 3], [2; 3]);
+
+  let (output, visits) = count_cell_visits(|| {
+    generate_region(&volume, &materials, &config, &MeshOutput::new(), &region)
+  });
+
+  assert_eq!(visits, (SAMPLE_SIZE - 1).pow(3));
+  assert_eq!(
+    triangle_set(&output),
+    triangle_set(&generate(&volume, &materials, &config))
+  );
+}
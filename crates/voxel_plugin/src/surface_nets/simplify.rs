@@ -0,0 +1,323 @@
+//! Edge-collapse simplification for distant LOD chunks.
+//!
+//! Surface Nets emits one vertex per surface cell regardless of flatness, so
+//! coarse chunks of gentle terrain carry far more triangles than they need.
+//! This pass collapses a vertex `u` onto a neighbour `v` (half-edge collapse)
+//! when the fan around `u` is near-coplanar:
+//!
+//! ```text
+//!    ●───●───●           ●───●───●
+//!    │ ╲ │ ╱ │           │ ╲   ╱ │
+//!    ●───u───v    ──►    ●─────v─┤   u removed, its fan re-attached to v
+//!    │ ╱ │ ╲ │           │ ╱   ╲ │
+//!    ●───●───●           ●───●───●
+//! ```
+//!
+//! A collapse is rejected unless:
+//! - `u` is not locked (seam bands near the chunk faces, skirts, open edges)
+//! - every face in `u`'s fan is within `angle` of the fan's average normal
+//! - the edge is manifold and passes the link condition (the only common
+//!   neighbours of `u` and `v` are the two triangles' opposite vertices)
+//! - no re-attached triangle flips or rotates by more than `angle`
+//!
+//! Vertices are never moved, only removed, so shared chunk-boundary vertices
+//! stay bit-identical with the neighbouring chunk.
+
+use glam::Vec3A;
+
+use crate::constants::LAST_INTERIOR_CELL;
+use crate::types::{MeshOutput, MinMaxAABB};
+
+/// Cells within this distance of the negative chunk face are shared with the
+/// neighbouring chunk's overlap region and must not be removed.
+const SEAM_MARGIN: i32 = 2;
+
+/// Upper bound on sweeps over the vertex list.
+const MAX_PASSES: usize = 16;
+
+/// Whether a vertex must survive simplification.
+#[inline]
+fn is_seam_cell(cell: [i32; 3]) -> bool {
+  cell
+    .iter()
+    .any(|&c| c <= SEAM_MARGIN || c >= LAST_INTERIOR_CELL as i32)
+}
+
+/// Unnormalized face normal of a triangle.
+#[inline]
+fn face_normal(positions: &[Vec3A], tri: [u32; 3]) -> Vec3A {
+  let p0 = positions[tri[0] as usize];
+  let p1 = positions[tri[1] as usize];
+  let p2 = positions[tri[2] as usize];
+  (p1 - p0).cross(p2 - p0)
+}
+
+/// Simplify the mesh by collapsing edges in near-coplanar regions.
+///
+/// `angle_deg` is the maximum normal deviation allowed for a collapse.
+/// Runs before the normal pass; removed vertices are compacted out.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, name = "simplify::simplify"))]
+pub fn simplify(output: &mut MeshOutput, angle_deg: f32) {
+  let vertex_count = output.vertices.len();
+  if vertex_count == 0 || output.indices.len() < 3 {
+    return;
+  }
+
+  let cos_limit = angle_deg.to_radians().cos();
+  let positions: Vec<Vec3A> = output
+    .vertices
+    .iter()
+    .map(|v| Vec3A::from_array(v.position))
+    .collect();
+
+  let mut triangles: Vec<[u32; 3]> = output
+    .indices
+    .chunks_exact(3)
+    .map(|t| [t[0] as u32, t[1] as u32, t[2] as u32])
+    .collect();
+  let mut tri_alive = vec![true; triangles.len()];
+
+  // Vertex → incident triangles (may contain dead entries; filtered on use)
+  let mut vertex_tris: Vec<Vec<usize>> = vec![Vec::new(); vertex_count];
+  for (t, tri) in triangles.iter().enumerate() {
+    for &i in tri {
+      vertex_tris[i as usize].push(t);
+    }
+  }
+
+  let mut locked: Vec<bool> = output
+    .vertices
+    .iter()
+    .map(|v| is_seam_cell(v.cell_position))
+    .collect();
+  lock_open_edges(&triangles, &mut locked);
+
+  let mut vertex_alive = vec![true; vertex_count];
+
+  for _ in 0..MAX_PASSES {
+    let mut collapsed = 0;
+
+    for u in 0..vertex_count {
+      if locked[u] || !vertex_alive[u] {
+        continue;
+      }
+
+      vertex_tris[u].retain(|&t| tri_alive[t]);
+      if vertex_tris[u].is_empty() {
+        continue;
+      }
+
+      if !is_flat_fan(&positions, &triangles, &vertex_tris[u], cos_limit) {
+        continue;
+      }
+
+      let Some(v) = find_collapse_target(
+        u,
+        &positions,
+        &triangles,
+        &tri_alive,
+        &vertex_tris,
+        cos_limit,
+      ) else {
+        continue;
+      };
+
+      // Perform u → v: drop triangles on edge uv, re-attach the rest to v
+      for t in std::mem::take(&mut vertex_tris[u]) {
+        let tri = &mut triangles[t];
+        if tri.contains(&(v as u32)) {
+          tri_alive[t] = false;
+        } else {
+          for i in tri.iter_mut() {
+            if *i == u as u32 {
+              *i = v as u32;
+            }
+          }
+          vertex_tris[v].push(t);
+        }
+      }
+      vertex_alive[u] = false;
+      collapsed += 1;
+    }
+
+    if collapsed == 0 {
+      break;
+    }
+  }
+
+  compact(output, &triangles, &tri_alive, &vertex_alive);
+}
+
+/// Lock vertices on edges used by a single triangle (mesh border / holes).
+fn lock_open_edges(triangles: &[[u32; 3]], locked: &mut [bool]) {
+  let mut edge_counts: std::collections::HashMap<(u32, u32), u32> =
+    std::collections::HashMap::with_capacity(triangles.len() * 3);
+  for tri in triangles {
+    for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+      *edge_counts.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+    }
+  }
+  for ((a, b), count) in edge_counts {
+    if count != 2 {
+      locked[a as usize] = true;
+      locked[b as usize] = true;
+    }
+  }
+}
+
+/// Whether every face around a vertex is within the angle limit of the
+/// fan's average normal.
+fn is_flat_fan(
+  positions: &[Vec3A],
+  triangles: &[[u32; 3]],
+  fan: &[usize],
+  cos_limit: f32,
+) -> bool {
+  let mut normals = Vec::with_capacity(fan.len());
+  let mut sum = Vec3A::ZERO;
+  for &t in fan {
+    let n = face_normal(positions, triangles[t]);
+    if n.length_squared() < 1e-12 {
+      return false;
+    }
+    let n = n.normalize();
+    normals.push(n);
+    sum += n;
+  }
+
+  if sum.length_squared() < 1e-12 {
+    return false;
+  }
+  let average = sum.normalize();
+  normals.iter().all(|n| n.dot(average) >= cos_limit)
+}
+
+/// Find a neighbour `v` that `u` can collapse onto without breaking the mesh.
+fn find_collapse_target(
+  u: usize,
+  positions: &[Vec3A],
+  triangles: &[[u32; 3]],
+  tri_alive: &[bool],
+  vertex_tris: &[Vec<usize>],
+  cos_limit: f32,
+) -> Option<usize> {
+  let neighbours = |w: usize| -> Vec<u32> {
+    let mut out: Vec<u32> = vertex_tris[w]
+      .iter()
+      .filter(|&&t| tri_alive[t])
+      .flat_map(|&t| triangles[t])
+      .filter(|&i| i != w as u32)
+      .collect();
+    out.sort_unstable();
+    out.dedup();
+    out
+  };
+
+  let fan = &vertex_tris[u];
+  let u_neighbours = neighbours(u);
+
+  'candidates: for &v in &u_neighbours {
+    let v = v as usize;
+
+    // Manifold edge: exactly two triangles share uv
+    let shared: Vec<usize> = fan
+      .iter()
+      .copied()
+      .filter(|&t| triangles[t].contains(&(v as u32)))
+      .collect();
+    if shared.len() != 2 {
+      continue;
+    }
+
+    // Link condition: common neighbours are exactly the opposite vertices
+    let mut opposite: Vec<u32> = shared
+      .iter()
+      .flat_map(|&t| triangles[t])
+      .filter(|&i| i != u as u32 && i != v as u32)
+      .collect();
+    opposite.sort_unstable();
+    opposite.dedup();
+
+    let v_neighbours = neighbours(v);
+    let common: Vec<u32> = u_neighbours
+      .iter()
+      .copied()
+      .filter(|i| v_neighbours.binary_search(i).is_ok())
+      .collect();
+    if common != opposite {
+      continue;
+    }
+
+    // No flips: re-attached faces must keep their orientation
+    for &t in fan {
+      let tri = triangles[t];
+      if tri.contains(&(v as u32)) {
+        continue;
+      }
+
+      let before = face_normal(positions, tri);
+      let after = face_normal(
+        positions,
+        tri.map(|i| if i == u as u32 { v as u32 } else { i }),
+      );
+      if after.length_squared() < 1e-12 || before.normalize().dot(after.normalize()) < cos_limit {
+        continue 'candidates;
+      }
+    }
+
+    return Some(v);
+  }
+
+  None
+}
+
+/// Rebuild vertex and index buffers from the surviving elements.
+fn compact(
+  output: &mut MeshOutput,
+  triangles: &[[u32; 3]],
+  tri_alive: &[bool],
+  vertex_alive: &[bool],
+) {
+  let mut remap = vec![u16::MAX; vertex_alive.len()];
+  let mut vertices = Vec::with_capacity(output.vertices.len());
+  let mut displaced = Vec::with_capacity(output.vertices.len());
+  let mut bounds = MinMaxAABB::empty();
+
+  for (i, vertex) in output.vertices.iter().enumerate() {
+    if !vertex_alive[i] {
+      continue;
+    }
+    remap[i] = vertices.len() as u16;
+    vertices.push(*vertex);
+    let displaced_pos = output
+      .displaced_positions
+      .get(i)
+      .copied()
+      .unwrap_or(vertex.position);
+    displaced.push(displaced_pos);
+    bounds.encapsulate(vertex.position);
+    bounds.encapsulate(displaced_pos);
+  }
+
+  output.indices = triangles
+    .iter()
+    .zip(tri_alive)
+    .filter(|&(_, &alive)| alive)
+    .flat_map(|(tri, _)| tri.map(|i| remap[i as usize]))
+    .collect();
+  // Boundary (debug) triangles only touch locked overlap vertices
+  for index in &mut output.boundary_indices {
+    *index = remap[*index as usize];
+  }
+  let mut alive = vertex_alive.iter();
+  output
+    .material_weights_hi
+    .retain(|_| alive.next() == Some(&true));
+  output.vertices = vertices;
+  output.displaced_positions = displaced;
+  output.bounds = bounds;
+}
+
+#[cfg(test)]
+#[path = "simplify_test.rs"]
+mod simplify_test;
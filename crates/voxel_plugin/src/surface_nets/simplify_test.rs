@@ -0,0 +1,138 @@
+use super::*;
+use crate::constants::*;
+use crate::surface_nets::{generate, lod_seams, volume_from_sdf};
+use crate::types::MeshConfig;
+
+/// Every vertex, including its LOD-displaced position, lies inside `bounds`.
+fn assert_within_bounds(output: &MeshOutput) {
+  let bounds = output.bounds;
+  let positions = output.vertices.iter().map(|v| &v.position);
+  for p in positions.chain(&output.displaced_positions) {
+    for axis in 0..3 {
+      assert!(
+        (bounds.min[axis]..=bounds.max[axis]).contains(&p[axis]),
+        "{:?} outside bounds {:?}",
+        p,
+        bounds
+      );
+    }
+  }
+}
+
+#[test]
+fn test_flat_plane_collapses() {
+  let volume = volume_from_sdf(|[_, y, _]| y - 16.3);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let plain = generate(&volume, &materials, &MeshConfig::default());
+  let simplified = generate(
+    &volume,
+    &materials,
+    &MeshConfig::default().with_simplify_angle(Some(5.0)),
+  );
+
+  // Only the seam bands near the chunk faces keep their full density
+  assert!(
+    simplified.triangle_count() * 2 < plain.triangle_count(),
+    "Expected the flat interior to collapse: {} -> {} triangles",
+    plain.triangle_count(),
+    simplified.triangle_count()
+  );
+  assert!(simplified.vertices.len() < plain.vertices.len());
+  assert!(simplified
+    .indices
+    .iter()
+    .all(|&i| (i as usize) < simplified.vertices.len()));
+
+  // Still a plane, still facing up, no flipped faces
+  for tri in simplified.indices.chunks_exact(3) {
+    let p: [Vec3A; 3] =
+      std::array::from_fn(|k| Vec3A::from_array(simplified.vertices[tri[k] as usize].position));
+    let n = (p[1] - p[0]).cross(p[2] - p[0]);
+    assert!(n.length_squared() > 1e-12, "Degenerate triangle {:?}", tri);
+    assert!(p.iter().all(|q| (q.y - 16.5).abs() < 1e-4));
+  }
+}
+
+#[test]
+fn test_seam_vertices_preserved() {
+  let volume = volume_from_sdf(|[_, y, _]| y - 16.3);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let plain = generate(&volume, &materials, &MeshConfig::default());
+  let simplified = generate(
+    &volume,
+    &materials,
+    &MeshConfig::default().with_simplify_angle(Some(5.0)),
+  );
+
+  let seam = |out: &MeshOutput| -> Vec<[f32; 3]> {
+    out
+      .vertices
+      .iter()
+      .filter(|v| v.cell_position[0] >= LAST_INTERIOR_CELL as i32)
+      .map(|v| v.position)
+      .collect()
+  };
+  assert_eq!(seam(&plain), seam(&simplified));
+}
+
+#[test]
+fn test_sphere_keeps_silhouette() {
+  let center = Vec3A::splat(16.0);
+  let radius = 10.0;
+  let volume = volume_from_sdf(|p| (Vec3A::from_array(p) - center).length() - radius);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let plain = generate(&volume, &materials, &MeshConfig::default());
+  let simplified = generate(
+    &volume,
+    &materials,
+    &MeshConfig::default().with_simplify_angle(Some(5.0)),
+  );
+
+  assert!(simplified.triangle_count() <= plain.triangle_count());
+
+  // Bounds unchanged: extreme vertices are never removed on a curved surface
+  for axis in 0..3 {
+    assert!((simplified.bounds.min[axis] - plain.bounds.min[axis]).abs() < 0.5);
+    assert!((simplified.bounds.max[axis] - plain.bounds.max[axis]).abs() < 0.5);
+  }
+  assert_within_bounds(&simplified);
+
+  // Triangles still hug the sphere
+  for tri in simplified.indices.chunks_exact(3) {
+    let centroid = tri
+      .iter()
+      .map(|&i| Vec3A::from_array(simplified.vertices[i as usize].position))
+      .sum::<Vec3A>()
+      / 3.0;
+    let dist = (centroid - center).length();
+    assert!(
+      (radius - 1.0..=radius + 0.6).contains(&dist),
+      "Triangle centroid at distance {} cuts through the sphere",
+      dist
+    );
+  }
+}
+
+#[test]
+fn test_simplify_disabled_by_default() {
+  assert_eq!(MeshConfig::default().simplify_angle_deg, None);
+}
+
+#[test]
+fn test_simplified_bounds_contain_displaced_vertices() {
+  let center = Vec3A::splat(16.0);
+  let volume = volume_from_sdf(|p| (Vec3A::from_array(p) - center).length() - 14.0);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+  // Coarser neighbours on every side, so seam vertices are displaced
+  let config = MeshConfig::default()
+    .with_neighbor_mask(lod_seams::ALL_TRANSITION_BITS)
+    .with_simplify_angle(Some(5.0));
+
+  let simplified = generate(&volume, &materials, &config);
+
+  assert!(!simplified.is_empty());
+  assert_within_bounds(&simplified);
+}
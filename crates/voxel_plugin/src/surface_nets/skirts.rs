@@ -0,0 +1,100 @@
+//! LOD skirt generation.
+//!
+//! Displacement in `lod_seams` matches fine/coarse boundaries along shared
+//! faces, but diagonal neighbours can still leave hairline cracks. Skirts hide
+//! them: every open (boundary) edge of the mesh is extruded along the inward
+//! normal, forming a hidden wall below the surface.
+//!
+//! ```text
+//!   surface ──a━━━━━━b──   (boundary edge a→b)
+//!             ┃      ┃
+//!             ┃skirt ┃     a' = a - normal(a) * depth
+//!             ┃      ┃     b' = b - normal(b) * depth
+//!             a'━━━━━b'
+//! ```
+//!
+//! Skirt vertices are tagged with [`SKIRT_CELL`] in `cell_position`, which
+//! the boundary filter treats as interior, so they are never discarded.
+
+use std::collections::HashMap;
+
+use glam::Vec3A;
+
+use crate::types::{MeshOutput, Vertex};
+
+/// Sentinel `cell_position` for skirt vertices.
+pub const SKIRT_CELL: [i32; 3] = [-1, -1, -1];
+
+/// Append skirts below all boundary edges.
+///
+/// Requires normals to be computed first (Pass 3). `depth` is in voxel units.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, name = "skirts::append"))]
+pub fn append(output: &mut MeshOutput, depth: f32) {
+  // Count triangle usage per undirected edge; boundary edges are used once
+  let mut edge_counts: HashMap<(u16, u16), u32> = HashMap::with_capacity(output.indices.len());
+  for tri in output.indices.chunks_exact(3) {
+    for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+      if a != b {
+        *edge_counts.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+      }
+    }
+  }
+
+  let mut skirt_of: HashMap<u16, u16> = HashMap::new();
+  let mut skirt_indices = Vec::new();
+
+  // Walk triangles in order so output is deterministic
+  for t in 0..output.indices.len() / 3 {
+    let tri = [
+      output.indices[t * 3],
+      output.indices[t * 3 + 1],
+      output.indices[t * 3 + 2],
+    ];
+
+    for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+      if a == b || edge_counts[&(a.min(b), a.max(b))] != 1 {
+        continue;
+      }
+
+      let a_skirt = skirt_vertex(output, &mut skirt_of, a, depth);
+      let b_skirt = skirt_vertex(output, &mut skirt_of, b, depth);
+
+      // Traverse the edge as b→a, like a neighbouring triangle would, so the
+      // skirt keeps the surface's winding
+      skirt_indices.extend_from_slice(&[b, a, a_skirt, b, a_skirt, b_skirt]);
+    }
+  }
+
+  output.indices.extend(skirt_indices);
+}
+
+/// Get (or create) the skirt vertex hanging below `source`.
+fn skirt_vertex(
+  output: &mut MeshOutput,
+  skirt_of: &mut HashMap<u16, u16>,
+  source: u16,
+  depth: f32,
+) -> u16 {
+  *skirt_of.entry(source).or_insert_with(|| {
+    let src = output.vertices[source as usize];
+    let normal = Vec3A::from_array(src.normal);
+    let position = (Vec3A::from_array(src.position) - normal * depth).to_array();
+
+    let index = output.vertices.len() as u16;
+    output.vertices.push(Vertex {
+      position,
+      cell_position: SKIRT_CELL,
+      ..src
+    });
+    output.displaced_positions.push(position);
+    if let Some(&hi) = output.material_weights_hi.get(source as usize) {
+      output.material_weights_hi.push(hi);
+    }
+    output.bounds.encapsulate(position);
+    index
+  })
+}
+
+#[cfg(test)]
+#[path = "skirts_test.rs"]
+mod skirts_test;
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use super::*;
+use crate::constants::*;
+use crate::surface_nets::{generate, volume_from_sdf};
+use crate::types::{MeshConfig, SdfSample};
+
+/// Slope `y = 10 + 0.25 * x` (world units), sampled at `origin + i * voxel_size`.
+fn create_slope_sdf(origin: [f32; 3], voxel_size: f32) -> [SdfSample; SAMPLE_SIZE_CB] {
+  let inv_len = (1.0f32 + 0.25 * 0.25).sqrt().recip();
+  volume_from_sdf(|[x, y, _]| {
+    let wx = origin[0] + x * voxel_size;
+    let wy = origin[1] + y * voxel_size;
+    // In voxels of this chunk, as the volume is stored at a voxel size of 1
+    (wy - (10.0 + 0.25 * wx)) * inv_len / voxel_size
+  })
+}
+
+fn edge_counts(indices: &[u16]) -> HashMap<(u16, u16), u32> {
+  let mut counts = HashMap::new();
+  for tri in indices.chunks_exact(3) {
+    for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+      if a != b {
+        *counts.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+      }
+    }
+  }
+  counts
+}
+
+#[test]
+fn test_skirts_close_surface_rim() {
+  let volume = create_slope_sdf([0.0; 3], 1.0);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let plain = generate(&volume, &materials, &MeshConfig::default());
+  let skirted = generate(
+    &volume,
+    &materials,
+    &MeshConfig::default().with_skirt_depth(2.0),
+  );
+
+  let plain_counts = edge_counts(&plain.indices);
+  let rim_edges: Vec<(u16, u16)> = plain_counts
+    .iter()
+    .filter(|(_, &count)| count == 1)
+    .map(|(&edge, _)| edge)
+    .collect();
+  assert!(!rim_edges.is_empty(), "Chunk surface should have an open rim");
+
+  // Surface vertices and triangles come first and are unchanged
+  assert_eq!(
+    &skirted.vertices[..plain.vertices.len()],
+    &plain.vertices[..]
+  );
+  assert_eq!(&skirted.indices[..plain.indices.len()], &plain.indices[..]);
+
+  // Every former rim edge is now shared with a skirt triangle
+  let skirted_counts = edge_counts(&skirted.indices);
+  for edge in &rim_edges {
+    assert_eq!(skirted_counts[edge], 2, "Rim edge {:?} still open", edge);
+  }
+
+  // Skirt vertices are tagged and hang `depth` below the surface
+  let skirt_vertices = &skirted.vertices[plain.vertices.len()..];
+  assert!(!skirt_vertices.is_empty());
+  for v in skirt_vertices {
+    assert_eq!(v.cell_position, SKIRT_CELL);
+  }
+  assert_eq!(skirted.displaced_positions.len(), skirted.vertices.len());
+}
+
+#[test]
+fn test_skirts_disabled_by_default() {
+  let volume = create_slope_sdf([0.0; 3], 1.0);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let output = generate(&volume, &materials, &MeshConfig::default());
+
+  assert!(output.vertices.iter().all(|v| v.cell_position != SKIRT_CELL));
+}
+
+#[test]
+fn test_fine_coarse_pair_has_no_gap_with_skirts() {
+  // Fine chunk (voxel 1) at the origin, coarse chunk (voxel 2) starting at
+  // the fine chunk's last interior cell.
+  let materials = [0u8; SAMPLE_SIZE_CB];
+  let coarse_origin = [LAST_INTERIOR_CELL as f32, 0.0, 0.0];
+  let config = MeshConfig::default().with_skirt_depth(4.0);
+
+  let fine = generate(&create_slope_sdf([0.0; 3], 1.0), &materials, &config);
+  let coarse = generate(
+    &create_slope_sdf(coarse_origin, 2.0),
+    &materials,
+    &config.clone().with_voxel_size(2.0),
+  );
+  assert!(!fine.is_empty() && !coarse.is_empty());
+
+  let coarse_surface: Vec<Vec3A> = coarse
+    .vertices
+    .iter()
+    .filter(|v| v.cell_position != SKIRT_CELL)
+    .map(|v| Vec3A::from_array(coarse_origin) + Vec3A::from_array(v.position) * 2.0)
+    .collect();
+
+  // Looking along the seam, the fine skirt must reach below the coarse
+  // surface next to it, so no background shows through between the two.
+  let mut checked = 0;
+  for v in fine.vertices.iter().filter(|v| v.cell_position == SKIRT_CELL) {
+    let p = Vec3A::from_array(v.position);
+    if p.x < LAST_INTERIOR_CELL as f32 {
+      continue;
+    }
+
+    let nearest = coarse_surface
+      .iter()
+      .min_by(|a, b| {
+        let da = (a.x - p.x).powi(2) + (a.z - p.z).powi(2);
+        let db = (b.x - p.x).powi(2) + (b.z - p.z).powi(2);
+        da.total_cmp(&db)
+      })
+      .unwrap();
+
+    assert!(
+      p.y <= nearest.y,
+      "Fine skirt at {:?} ends above coarse surface at {:?}",
+      p,
+      nearest
+    );
+    checked += 1;
+  }
+  assert!(checked > 0, "Expected fine skirt vertices along the seam");
+}
@@ -0,0 +1,84 @@
+//! Per-vertex tangent generation (Lengyel's method).
+//!
+//! Tangents follow the mesh's UVs (`Vertex::uv`, from the UV pass), so the
+//! tangent frame lines up with the directions a normal map is sampled in.
+//! Per-triangle dU/dV directions are accumulated per vertex, then
+//! Gram-Schmidt orthogonalized against the vertex normal, as MikkTSpace does.
+//! Without UVs there is nothing to align to, so the pass is skipped.
+//!
+//! Handedness is stored in `tangent.w` (±1) so the shader can rebuild the
+//! bitangent as `cross(normal, tangent.xyz) * tangent.w`.
+
+use glam::Vec3A;
+
+use crate::types::MeshOutput;
+
+/// Compute tangents for all vertices from triangle geometry and UVs.
+///
+/// Requires normals (Pass 3) and UVs (Pass 5) to be computed first.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, name = "tangents::compute"))]
+pub fn compute(output: &mut MeshOutput) {
+  let vertex_count = output.vertices.len();
+  let mut tan = vec![Vec3A::ZERO; vertex_count];
+  let mut bitan = vec![Vec3A::ZERO; vertex_count];
+
+  for tri in output.indices.chunks_exact(3) {
+    let i0 = tri[0] as usize;
+    let i1 = tri[1] as usize;
+    let i2 = tri[2] as usize;
+
+    let p0 = Vec3A::from_array(output.vertices[i0].position);
+    let p1 = Vec3A::from_array(output.vertices[i1].position);
+    let p2 = Vec3A::from_array(output.vertices[i2].position);
+    let uv0 = output.vertices[i0].uv;
+    let uv1 = output.vertices[i1].uv;
+    let uv2 = output.vertices[i2].uv;
+
+    let e1 = p1 - p0;
+    let e2 = p2 - p0;
+
+    let du1 = uv1[0] - uv0[0];
+    let dv1 = uv1[1] - uv0[1];
+    let du2 = uv2[0] - uv0[0];
+    let dv2 = uv2[1] - uv0[1];
+
+    let det = du1 * dv2 - du2 * dv1;
+    if det.abs() < 1e-12 {
+      continue;
+    }
+    let r = det.recip();
+
+    let sdir = (e1 * dv2 - e2 * dv1) * r;
+    let tdir = (e2 * du1 - e1 * du2) * r;
+
+    for &i in &[i0, i1, i2] {
+      tan[i] += sdir;
+      bitan[i] += tdir;
+    }
+  }
+
+  for (i, vertex) in output.vertices.iter_mut().enumerate() {
+    let n = Vec3A::from_array(vertex.normal);
+
+    // Gram-Schmidt: remove the normal component
+    let mut t = tan[i] - n * n.dot(tan[i]);
+    if t.length_squared() < 1e-12 {
+      // No usable UV gradient - pick any direction perpendicular to the normal
+      t = n.any_orthonormal_vector();
+    } else {
+      t = t.normalize();
+    }
+
+    let w = if n.cross(t).dot(bitan[i]) < 0.0 {
+      -1.0
+    } else {
+      1.0
+    };
+
+    vertex.tangent = [t.x, t.y, t.z, w];
+  }
+}
+
+#[cfg(test)]
+#[path = "tangents_test.rs"]
+mod tangents_test;
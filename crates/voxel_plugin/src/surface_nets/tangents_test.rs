@@ -0,0 +1,109 @@
+use super::*;
+use crate::constants::*;
+use crate::surface_nets::{generate, volume_from_sdf};
+use crate::types::{MeshConfig, UvMode};
+
+#[test]
+fn test_sphere_tangents_orthonormal_to_normal() {
+  let volume = volume_from_sdf(|p| (Vec3A::from_array(p) - Vec3A::splat(16.0)).length() - 10.0);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+  let config = MeshConfig::default()
+    .with_tangents(true)
+    .with_uv_mode(UvMode::Triplanar);
+
+  let output = generate(&volume, &materials, &config);
+  assert!(!output.is_empty());
+
+  for (i, v) in output.vertices.iter().enumerate() {
+    let n = Vec3A::from_array(v.normal);
+    let t = Vec3A::new(v.tangent[0], v.tangent[1], v.tangent[2]);
+
+    assert!(
+      (t.length() - 1.0).abs() < 1e-3,
+      "Vertex {} tangent not unit length: {:?}",
+      i,
+      v.tangent
+    );
+    assert!(
+      n.dot(t).abs() < 1e-3,
+      "Vertex {} tangent not orthogonal to normal: dot={}",
+      i,
+      n.dot(t)
+    );
+    assert!(
+      v.tangent[3] == 1.0 || v.tangent[3] == -1.0,
+      "Vertex {} handedness must be ±1, got {}",
+      i,
+      v.tangent[3]
+    );
+  }
+}
+
+#[test]
+fn test_tangents_skipped_by_default() {
+  let volume = volume_from_sdf(|p| (Vec3A::from_array(p) - Vec3A::splat(16.0)).length() - 10.0);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let output = generate(&volume, &materials, &MeshConfig::default());
+
+  assert!(output
+    .vertices
+    .iter()
+    .all(|v| v.tangent == [1.0, 0.0, 0.0, 1.0]));
+}
+
+#[test]
+fn test_tangents_skipped_without_uvs() {
+  let volume = volume_from_sdf(|p| (Vec3A::from_array(p) - Vec3A::splat(16.0)).length() - 10.0);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+  let config = MeshConfig::default().with_tangents(true);
+
+  let output = generate(&volume, &materials, &config);
+
+  assert!(!output.is_empty());
+  assert!(output
+    .vertices
+    .iter()
+    .all(|v| v.tangent == [1.0, 0.0, 0.0, 1.0]));
+}
+
+/// Single quad in the XZ plane facing +Y, with UVs from `uv(x, z)`.
+fn flat_quad(uv: impl Fn(f32, f32) -> [f32; 2]) -> MeshOutput {
+  let mut output = MeshOutput::new();
+  for p in [
+    [0.0, 0.0, 0.0],
+    [1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0],
+    [1.0, 0.0, 1.0],
+  ] {
+    output.vertices.push(crate::types::Vertex {
+      position: p,
+      normal: [0.0, 1.0, 0.0],
+      uv: uv(p[0], p[2]),
+      ..Default::default()
+    });
+  }
+  output.indices = vec![0, 2, 1, 1, 2, 3];
+  output
+}
+
+#[test]
+fn test_flat_quad_tangent_follows_u_axis() {
+  // U along +X: the tangent points along +X
+  let mut output = flat_quad(|x, z| [x, z]);
+  compute(&mut output);
+  let handedness = output.vertices[0].tangent[3];
+  for v in &output.vertices {
+    assert!((v.tangent[0] - 1.0).abs() < 1e-5, "tangent {:?}", v.tangent);
+    assert!(v.tangent[1].abs() < 1e-5 && v.tangent[2].abs() < 1e-5);
+  }
+
+  // U along +Z (UVs swapped): the tangent follows it, with flipped handedness
+  let mut output = flat_quad(|x, z| [z, x]);
+  compute(&mut output);
+  for v in &output.vertices {
+    assert!((v.tangent[2] - 1.0).abs() < 1e-5, "tangent {:?}", v.tangent);
+    assert!(v.tangent[0].abs() < 1e-5 && v.tangent[1].abs() < 1e-5);
+    assert_eq!(v.tangent[3], -handedness);
+  }
+}
@@ -0,0 +1,50 @@
+//! Per-vertex UV generation.
+//!
+//! Vertex positions are chunk-local in voxel units, so UVs are computed from
+//! the reconstructed world position:
+//!
+//! ```text
+//! world = config.world_origin + position * config.voxel_size
+//! ```
+//!
+//! Because adjacent chunks produce identical world positions for shared
+//! vertices, world-space UVs are continuous across chunk boundaries.
+
+use glam::Vec3A;
+
+use crate::types::{MeshConfig, MeshOutput, UvMode};
+
+/// Compute UVs for all vertices based on the configured mode.
+///
+/// Requires normals to be computed first (Pass 3) for `UvMode::Triplanar`.
+pub fn compute(output: &mut MeshOutput, config: &MeshConfig) {
+  let origin = Vec3A::from_array(config.world_origin);
+  let voxel_size = config.voxel_size;
+
+  match config.uv_mode {
+    UvMode::None => {}
+    UvMode::WorldXZ { scale } => {
+      for vertex in &mut output.vertices {
+        let world = origin + Vec3A::from_array(vertex.position) * voxel_size;
+        vertex.uv = [world.x * scale, world.z * scale];
+      }
+    }
+    UvMode::Triplanar => {
+      for vertex in &mut output.vertices {
+        let world = origin + Vec3A::from_array(vertex.position) * voxel_size;
+        let n = Vec3A::from_array(vertex.normal).abs();
+        vertex.uv = if n.x >= n.y && n.x >= n.z {
+          [world.z, world.y]
+        } else if n.y >= n.z {
+          [world.x, world.z]
+        } else {
+          [world.x, world.y]
+        };
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+#[path = "uvs_test.rs"]
+mod uvs_test;
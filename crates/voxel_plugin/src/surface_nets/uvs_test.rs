@@ -0,0 +1,82 @@
+use super::*;
+use crate::constants::*;
+use crate::surface_nets::{generate, volume_from_sdf};
+use crate::types::SdfSample;
+
+/// Tilted plane sampled at world positions `origin + index`.
+fn create_plane_sdf(origin: [f32; 3]) -> [SdfSample; SAMPLE_SIZE_CB] {
+  volume_from_sdf(|p| {
+    let [wx, wy, wz]: [f32; 3] = std::array::from_fn(|axis| origin[axis] + p[axis]);
+    wy - (12.0 + 0.3 * wx + 0.2 * wz)
+  })
+}
+
+#[test]
+fn test_uvs_default_none() {
+  let volume = create_plane_sdf([0.0; 3]);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let output = generate(&volume, &materials, &MeshConfig::default());
+
+  assert!(!output.is_empty());
+  assert!(output.vertices.iter().all(|v| v.uv == [0.0, 0.0]));
+}
+
+#[test]
+fn test_world_xz_uvs_continuous_across_chunks() {
+  let materials = [0u8; SAMPLE_SIZE_CB];
+  let scale = 0.25;
+  let origin_a = [0.0, 0.0, 0.0];
+  let origin_b = [INTERIOR_CELLS as f32, 0.0, 0.0];
+
+  let config_a = MeshConfig::default()
+    .with_uv_mode(UvMode::WorldXZ { scale })
+    .with_world_origin(origin_a);
+  let config_b = config_a.clone().with_world_origin(origin_b);
+
+  let mesh_a = generate(&create_plane_sdf(origin_a), &materials, &config_a);
+  let mesh_b = generate(&create_plane_sdf(origin_b), &materials, &config_b);
+  assert!(!mesh_a.is_empty() && !mesh_b.is_empty());
+
+  // Vertices in A's overlap region coincide with vertices in B's first cells
+  let mut shared = 0;
+  for va in &mesh_a.vertices {
+    let wa = [va.position[0] + origin_a[0], va.position[1], va.position[2]];
+    for vb in &mesh_b.vertices {
+      let wb = [vb.position[0] + origin_b[0], vb.position[1], vb.position[2]];
+      let same_pos = (0..3).all(|i| (wa[i] - wb[i]).abs() < 1e-4);
+      if !same_pos {
+        continue;
+      }
+      shared += 1;
+      assert!(
+        (va.uv[0] - vb.uv[0]).abs() < 1e-4 && (va.uv[1] - vb.uv[1]).abs() < 1e-4,
+        "UV discontinuity at {:?}: {:?} vs {:?}",
+        wa,
+        va.uv,
+        vb.uv
+      );
+    }
+  }
+
+  assert!(shared > 0, "Adjacent chunks should share boundary vertices");
+}
+
+#[test]
+fn test_world_xz_uvs_scale_world_position() {
+  let materials = [0u8; SAMPLE_SIZE_CB];
+  let origin = [56.0, 0.0, -28.0];
+  let config = MeshConfig::default()
+    .with_voxel_size(2.0)
+    .with_uv_mode(UvMode::WorldXZ { scale: 0.5 })
+    .with_world_origin(origin);
+
+  let output = generate(&create_plane_sdf([0.0; 3]), &materials, &config);
+
+  for v in &output.vertices {
+    let wx = origin[0] + v.position[0] * 2.0;
+    let wz = origin[2] + v.position[2] * 2.0;
+    assert!((v.uv[0] - wx * 0.5).abs() < 1e-3);
+    assert!((v.uv[1] - wz * 0.5).abs() < 1e-3);
+  }
+}
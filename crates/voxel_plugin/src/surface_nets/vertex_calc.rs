@@ -3,6 +3,10 @@
 //! Optimized implementation using direct edge iteration and SIMD vectors.
 
 pub use glam::Vec3A;
+use smallvec::SmallVec;
+
+/// Index into [`CUBE_EDGES`] (0-11).
+pub type EdgeId = u8;
 
 /// Precomputed corner positions within unit cube.
 /// Layout matches corner index bits: corner i = (x=bit0, y=bit1, z=bit2)
@@ -44,19 +48,9 @@ pub fn compute_position_direct(samples: &[f32; 8]) -> Vec3A {
   let mut sum = Vec3A::ZERO;
   let mut count = 0u32;
 
-  for &[c0, c1] in &CUBE_EDGES {
-    let s0 = samples[c0];
-    let s1 = samples[c1];
-
-    // Check if edge crosses surface (signs differ)
-    if (s0 < 0.0) != (s1 < 0.0) {
-      // Interpolation factor for zero-crossing
-      let t = s0 / (s0 - s1);
-
-      // Lerp between corner positions
-      let p0 = CORNER_POSITIONS[c0];
-      let p1 = CORNER_POSITIONS[c1];
-      sum += p0 + t * (p1 - p0);
+  for edge in 0..CUBE_EDGES.len() {
+    if let Some(crossing) = edge_crossing(samples, edge) {
+      sum += crossing;
       count += 1;
     }
   }
@@ -68,6 +62,80 @@ pub fn compute_position_direct(samples: &[f32; 8]) -> Vec3A {
   sum / count as f32
 }
 
+/// Refine a cell-local vertex position toward the zero isosurface.
+///
+/// Runs `steps` Newton iterations on the trilinear interpolant of the 8
+/// corner samples, `p -= f(p) * ∇f(p) / |∇f(p)|²`, clamped to the unit cell.
+/// On curved surfaces this moves the centroid of linear edge crossings back
+/// onto the surface. Stops early where the gradient vanishes.
+#[inline]
+pub fn refine_position_newton(samples: &[f32; 8], position: Vec3A, steps: u8) -> Vec3A {
+  let mut p = position;
+
+  for _ in 0..steps {
+    let (value, gradient) = trilinear_with_gradient(samples, p);
+    let gradient_sq = gradient.length_squared();
+    if gradient_sq < 1e-12 {
+      break;
+    }
+    p = (p - gradient * (value / gradient_sq)).clamp(Vec3A::ZERO, Vec3A::ONE);
+  }
+
+  p
+}
+
+/// Trilinear interpolation of the corner samples and its gradient at `p`.
+#[inline]
+fn trilinear_with_gradient(samples: &[f32; 8], p: Vec3A) -> (f32, Vec3A) {
+  let mut value = 0.0;
+  let mut gradient = Vec3A::ZERO;
+
+  for (corner, &sample) in samples.iter().enumerate() {
+    let c = CORNER_POSITIONS[corner];
+    // Per-axis weight: p for the far corner, 1 - p for the near one
+    let w = Vec3A::ONE - c + p * (2.0 * c - Vec3A::ONE);
+    // d(weight)/dp per axis: +1 or -1
+    let dw = 2.0 * c - Vec3A::ONE;
+
+    value += sample * w.x * w.y * w.z;
+    gradient += sample * Vec3A::new(dw.x * w.y * w.z, w.x * dw.y * w.z, w.x * w.y * dw.z);
+  }
+
+  (value, gradient)
+}
+
+/// Interpolated surface crossing on a single cube edge, if its signs differ.
+#[inline]
+fn edge_crossing(samples: &[f32; 8], edge: usize) -> Option<Vec3A> {
+  let [c0, c1] = CUBE_EDGES[edge];
+  let s0 = samples[c0];
+  let s1 = samples[c1];
+
+  // Check if edge crosses surface (signs differ)
+  if (s0 < 0.0) == (s1 < 0.0) {
+    return None;
+  }
+
+  // Interpolation factor for zero-crossing
+  let t = s0 / (s0 - s1);
+
+  // Lerp between corner positions
+  let p0 = CORNER_POSITIONS[c0];
+  let p1 = CORNER_POSITIONS[c1];
+  Some(p0 + t * (p1 - p0))
+}
+
+/// Per-edge surface crossing points within the unit cell.
+///
+/// Returns the interpolated crossing for every active edge (the same points
+/// `compute_position_direct` averages), in edge order. Useful for surface
+/// area estimates or decal placement where the centroid isn't enough.
+pub fn edge_crossings(samples: &[f32; 8]) -> SmallVec<[(EdgeId, Vec3A); 12]> {
+  (0..CUBE_EDGES.len())
+    .filter_map(|edge| edge_crossing(samples, edge).map(|p| (edge as EdgeId, p)))
+    .collect()
+}
+
 #[cfg(test)]
 #[path = "vertex_calc_test.rs"]
 mod vertex_calc_test;
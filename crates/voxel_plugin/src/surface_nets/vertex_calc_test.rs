@@ -77,3 +77,61 @@ fn test_cube_edges_valid() {
     assert_ne!(c0, c1, "edge {} connects corner to itself", i);
   }
 }
+
+#[test]
+fn test_edge_crossings_planar_surface() {
+  // Plane x = 0.25: sdf = x - 0.25, so every X edge crosses at x = 0.25
+  let samples: [f32; 8] = std::array::from_fn(|i| CORNER_POSITIONS[i].x - 0.25);
+
+  let crossings = edge_crossings(&samples);
+
+  // Only the 4 X-axis edges cross
+  let edges: Vec<EdgeId> = crossings.iter().map(|&(edge, _)| edge).collect();
+  assert_eq!(edges, vec![0, 5, 8, 11]);
+
+  for &(edge, p) in &crossings {
+    let [c0, c1] = CUBE_EDGES[edge as usize];
+    let expected = CORNER_POSITIONS[c0].lerp(CORNER_POSITIONS[c1], 0.25);
+    assert!(
+      (p - expected).length() < 1e-6,
+      "Edge {} crossing {:?} != analytic {:?}",
+      edge,
+      p,
+      expected
+    );
+  }
+
+  // Centroid is the average of the crossings
+  let centroid = compute_position_direct(&samples);
+  assert!((centroid.x - 0.25).abs() < 1e-6);
+}
+
+#[test]
+fn test_edge_crossings_empty_for_homogeneous_cell() {
+  assert!(edge_crossings(&[1.0; 8]).is_empty());
+  assert!(edge_crossings(&[-1.0; 8]).is_empty());
+}
+
+#[test]
+fn test_newton_refinement_lands_on_isosurface() {
+  // Curved field: f = |p - (0, 0, 0)|² - 0.8², sampled at the corners
+  let samples: [f32; 8] = std::array::from_fn(|i| CORNER_POSITIONS[i].length_squared() - 0.64);
+
+  let centroid = compute_position_direct(&samples);
+  let refined = refine_position_newton(&samples, centroid, 4);
+
+  let residual = |p: Vec3A| {
+    let (value, _) = trilinear_with_gradient(&samples, p);
+    value.abs()
+  };
+  assert!(residual(refined) < 1e-4);
+  assert!(residual(refined) < residual(centroid));
+}
+
+#[test]
+fn test_newton_refinement_zero_steps_is_identity() {
+  let samples = [-1.0, -1.0, -1.0, -1.0, 1.0, 1.0, 1.0, 1.0];
+  let centroid = compute_position_direct(&samples);
+
+  assert_eq!(refine_position_newton(&samples, centroid, 0), centroid);
+}
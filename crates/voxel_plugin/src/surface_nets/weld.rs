@@ -0,0 +1,159 @@
+//! Vertex welding pass.
+//!
+//! Surface Nets places one vertex per surface cell, but neighbouring cells can
+//! still land on (nearly) the same point - e.g. when every edge crossing of
+//! several cells sits on a shared zero-valued corner. Such duplicates split
+//! smooth-shading normals and inflate the vertex buffer.
+//!
+//! Positions are quantized to an `epsilon` grid and hashed. Each vertex is
+//! compared against the 27 surrounding buckets, so pairs straddling a bucket
+//! boundary still merge:
+//!
+//! ```text
+//! key = floor(position / epsilon)
+//! candidates = buckets[key + {-1, 0, 1}³]
+//! merge if |position - candidate| <= epsilon
+//! ```
+//!
+//! Merged vertices keep the first vertex's position and cell position;
+//! normals and material weights are averaged. Indices are rewritten but no
+//! triangles are removed, so the triangle count is unchanged.
+
+use std::collections::HashMap;
+
+use glam::Vec3A;
+
+use crate::types::{MeshOutput, MinMaxAABB, Vertex};
+
+/// Default welding distance in voxel units.
+pub const DEFAULT_WELD_EPSILON: f32 = 1e-4;
+
+/// Merge vertices closer than `epsilon` and rewrite indices.
+///
+/// Must run after triangulation. Runs before the normal pass so geometry
+/// normals see the shared topology.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, name = "weld::weld"))]
+pub fn weld(output: &mut MeshOutput, epsilon: f32) {
+  let vertex_count = output.vertices.len();
+  if vertex_count == 0 || epsilon <= 0.0 {
+    return;
+  }
+
+  let inv_epsilon = epsilon.recip();
+  let epsilon_sq = epsilon * epsilon;
+  let bucket_key = |p: Vec3A| -> [i32; 3] {
+    let q = (p * inv_epsilon).floor();
+    [q.x as i32, q.y as i32, q.z as i32]
+  };
+
+  let mut buckets: HashMap<[i32; 3], Vec<u16>> = HashMap::with_capacity(vertex_count);
+  let mut remap: Vec<u16> = Vec::with_capacity(vertex_count);
+
+  let mut merged: Vec<Vertex> = Vec::with_capacity(vertex_count);
+  let mut displaced: Vec<[f32; 3]> = Vec::with_capacity(vertex_count);
+  let mut normal_sums: Vec<Vec3A> = Vec::with_capacity(vertex_count);
+  let mut weight_sums: Vec<[[f32; 4]; 2]> = Vec::with_capacity(vertex_count);
+  let mut counts: Vec<u32> = Vec::with_capacity(vertex_count);
+
+  for (i, vertex) in output.vertices.iter().enumerate() {
+    let p = Vec3A::from_array(vertex.position);
+    let key = bucket_key(p);
+
+    let mut found = None;
+    'search: for dx in -1..=1 {
+      for dy in -1..=1 {
+        for dz in -1..=1 {
+          let Some(candidates) = buckets.get(&[key[0] + dx, key[1] + dy, key[2] + dz]) else {
+            continue;
+          };
+          for &c in candidates {
+            let q = Vec3A::from_array(merged[c as usize].position);
+            if q.distance_squared(p) <= epsilon_sq {
+              found = Some(c);
+              break 'search;
+            }
+          }
+        }
+      }
+    }
+
+    let target = match found {
+      Some(target) => target,
+      None => {
+        let target = merged.len() as u16;
+        merged.push(*vertex);
+        displaced.push(
+          output
+            .displaced_positions
+            .get(i)
+            .copied()
+            .unwrap_or(vertex.position),
+        );
+        normal_sums.push(Vec3A::ZERO);
+        weight_sums.push([[0.0; 4]; 2]);
+        counts.push(0);
+        buckets.entry(key).or_default().push(target);
+        target
+      }
+    };
+
+    let t = target as usize;
+    normal_sums[t] += Vec3A::from_array(vertex.normal);
+    let [lo, hi] = &mut weight_sums[t];
+    for (sum, w) in lo.iter_mut().zip(vertex.material_weights) {
+      *sum += w;
+    }
+    let vertex_hi = output.material_weights_hi.get(i).copied();
+    for (sum, w) in hi.iter_mut().zip(vertex_hi.unwrap_or([0.0; 4])) {
+      *sum += w;
+    }
+    counts[t] += 1;
+    remap.push(target);
+  }
+
+  // Nothing merged - leave the mesh untouched
+  if merged.len() == vertex_count {
+    return;
+  }
+
+  for (t, vertex) in merged.iter_mut().enumerate() {
+    if counts[t] < 2 {
+      continue;
+    }
+
+    let n = normal_sums[t];
+    if n.length_squared() > 1e-12 {
+      vertex.normal = n.normalize().to_array();
+    }
+
+    let inv_count = (counts[t] as f32).recip();
+    let [lo, hi] = &mut weight_sums[t];
+    vertex.material_weights = lo.map(|w| w * inv_count);
+    *hi = hi.map(|w| w * inv_count);
+  }
+
+  for index in output
+    .indices
+    .iter_mut()
+    .chain(output.boundary_indices.iter_mut())
+  {
+    *index = remap[*index as usize];
+  }
+
+  let mut bounds = MinMaxAABB::empty();
+  for (vertex, &displaced_pos) in merged.iter().zip(&displaced) {
+    bounds.encapsulate(vertex.position);
+    bounds.encapsulate(displaced_pos);
+  }
+
+  output.vertices = merged;
+  output.displaced_positions = displaced;
+  if !output.material_weights_hi.is_empty() {
+    output.material_weights_hi = weight_sums.into_iter().map(|[_, hi]| hi).collect();
+  }
+  output.bounds = bounds;
+}
+
+#[cfg(test)]
+#[path = "weld_test.rs"]
+mod weld_test;
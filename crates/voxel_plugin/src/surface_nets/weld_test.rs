@@ -0,0 +1,123 @@
+use super::*;
+use crate::constants::*;
+use crate::surface_nets::{generate, lod_seams, volume_from_sdf};
+use crate::types::{MeshConfig, SdfSample};
+
+/// Sphere with a single zero-valued sample at its centre.
+///
+/// Zero counts as air, so each of the 8 cells around that sample has seven
+/// solid corners and one air corner; all their edge crossings sit exactly on
+/// the zero sample, giving 8 coincident vertices.
+fn create_sphere_with_pinhole(radius: f32, center: [usize; 3]) -> [SdfSample; SAMPLE_SIZE_CB] {
+  let mut volume = volume_from_sdf(|p| {
+    let [dx, dy, dz]: [f32; 3] = std::array::from_fn(|axis| p[axis] - center[axis] as f32);
+    (dx * dx + dy * dy + dz * dz).sqrt() - radius
+  });
+  volume[coord_to_index(center[0], center[1], center[2])] = 0;
+  volume
+}
+
+#[test]
+fn test_welded_sphere_drops_vertices_keeps_triangles() {
+  let volume = create_sphere_with_pinhole(10.0, [16, 16, 16]);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let plain = generate(&volume, &materials, &MeshConfig::default());
+  let welded = generate(
+    &volume,
+    &materials,
+    &MeshConfig::default().with_weld_vertices(true),
+  );
+
+  assert!(!plain.is_empty());
+  assert!(
+    welded.vertices.len() + 7 <= plain.vertices.len(),
+    "Expected the 8 pinhole vertices to weld: {} -> {}",
+    plain.vertices.len(),
+    welded.vertices.len()
+  );
+  assert_eq!(welded.triangle_count(), plain.triangle_count());
+  assert_eq!(welded.displaced_positions.len(), welded.vertices.len());
+  assert!(welded
+    .indices
+    .iter()
+    .all(|&i| (i as usize) < welded.vertices.len()));
+}
+
+#[test]
+fn test_welded_bounds_contain_displaced_vertices() {
+  let volume = create_sphere_with_pinhole(14.0, [16, 16, 16]);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+  // Coarser neighbours on every side, so seam vertices are displaced
+  let config = MeshConfig::default()
+    .with_neighbor_mask(lod_seams::ALL_TRANSITION_BITS)
+    .with_weld_vertices(true);
+
+  let welded = generate(&volume, &materials, &config);
+
+  assert!(!welded.is_empty());
+  let bounds = welded.bounds;
+  let positions = welded.vertices.iter().map(|v| &v.position);
+  for p in positions.chain(&welded.displaced_positions) {
+    for axis in 0..3 {
+      assert!(
+        (bounds.min[axis]..=bounds.max[axis]).contains(&p[axis]),
+        "{:?} outside bounds {:?}",
+        p,
+        bounds
+      );
+    }
+  }
+}
+
+#[test]
+fn test_weld_disabled_by_default() {
+  let volume = create_sphere_with_pinhole(10.0, [16, 16, 16]);
+  let materials = [0u8; SAMPLE_SIZE_CB];
+
+  let a = generate(&volume, &materials, &MeshConfig::default());
+  let b = generate(
+    &volume,
+    &materials,
+    &MeshConfig::default().with_weld_vertices(false),
+  );
+
+  assert_eq!(a.vertices.len(), b.vertices.len());
+  assert_eq!(a.indices, b.indices);
+}
+
+#[test]
+fn test_weld_averages_attributes_and_rewrites_indices() {
+  // Two triangles sharing an edge, but with the shared edge duplicated
+  // (offset well below epsilon, straddling a bucket boundary).
+  let mut output = MeshOutput::new();
+  let positions = [
+    [0.0, 0.0, 0.0],
+    [1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0],
+    [1.0, 0.00005, 0.0],
+    [0.00005, 0.0, 1.0],
+    [1.0, 0.0, 1.0],
+  ];
+  for (i, p) in positions.into_iter().enumerate() {
+    let weights = if i < 3 {
+      [1.0, 0.0, 0.0, 0.0]
+    } else {
+      [0.0, 1.0, 0.0, 0.0]
+    };
+    output.vertices.push(Vertex {
+      position: p,
+      material_weights: weights,
+      ..Default::default()
+    });
+    output.displaced_positions.push(p);
+  }
+  output.indices = vec![0, 2, 1, 3, 4, 5];
+
+  weld(&mut output, DEFAULT_WELD_EPSILON);
+
+  assert_eq!(output.vertices.len(), 4);
+  assert_eq!(output.indices, vec![0, 2, 1, 1, 2, 3]);
+  assert_eq!(output.vertices[1].material_weights, [0.5, 0.5, 0.0, 0.0]);
+  assert_eq!(output.vertices[3].material_weights, [0.0, 1.0, 0.0, 0.0]);
+}
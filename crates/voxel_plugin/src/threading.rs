@@ -0,0 +1,389 @@
+//! Worker thread count selection.
+//!
+//! Centralizes how entry points size their thread pools so every demo and
+//! host agrees on the same rules:
+//!
+//! 1. `VOXEL_THREADS` environment variable, if set to a positive integer
+//! 2. `std::thread::available_parallelism()`
+//! 3. [`FALLBACK_WORKERS`] when neither is available (e.g. WASM, where env
+//!    vars and parallelism queries are unsupported)
+//!
+//! The result is always clamped to `[MIN_WORKERS, MAX_WORKERS]`.
+//!
+//! # Executors
+//!
+//! [`TaskExecutor`] is where pipeline work runs: the global rayon pool, or a
+//! dedicated pool built from a [`ThreadPoolConfig`]. On wasm32 the global
+//! pool is the one the bridge sets up with `wasm_bindgen_rayon`, and threads
+//! can't be spawned from Rust, so every executor shares it.
+//!
+//! `TaskExecutor::single_threaded()` runs everything on one worker, so
+//! pipeline stages (`presample_batch`, `mesh_batch`, `process_transitions`)
+//! run serially through `install` in a reproducible order, for tests and
+//! debugging.
+//!
+//! `spawn_batch` maps a closure over inputs on the executor's pool and
+//! returns a [`BatchHandle`] to poll; on wasm32 without workers it runs
+//! synchronously instead.
+
+use std::sync::Arc;
+
+use crossbeam_channel::{self as channel, Receiver, TryRecvError};
+use rayon::prelude::*;
+
+/// Environment variable overriding the worker count.
+pub const THREADS_ENV: &str = "VOXEL_THREADS";
+
+/// Lower bound on the recommended worker count.
+pub const MIN_WORKERS: usize = 1;
+
+/// Upper bound on the recommended worker count.
+pub const MAX_WORKERS: usize = 64;
+
+/// Worker count used when the platform can't report its parallelism.
+pub const FALLBACK_WORKERS: usize = 4;
+
+/// How a [`TaskExecutor`] sizes its pool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThreadPoolConfig {
+  /// Dedicated worker count (clamped to `[MIN_WORKERS, MAX_WORKERS]`);
+  /// `None` shares the global rayon pool.
+  pub threads: Option<usize>,
+  /// Name prefix for dedicated worker threads.
+  pub thread_name: String,
+}
+
+impl Default for ThreadPoolConfig {
+  fn default() -> Self {
+    Self {
+      threads: None,
+      thread_name: "voxel-worker".to_string(),
+    }
+  }
+}
+
+impl ThreadPoolConfig {
+  pub fn with_threads(mut self, threads: usize) -> Self {
+    self.threads = Some(threads);
+    self
+  }
+
+  pub fn with_thread_name(mut self, thread_name: impl Into<String>) -> Self {
+    self.thread_name = thread_name.into();
+    self
+  }
+}
+
+/// Runs pipeline work on the global rayon pool or a dedicated one.
+///
+/// Cheap to clone; clones share the same pool, whose threads exit when the
+/// last clone is dropped.
+#[derive(Clone, Default)]
+pub struct TaskExecutor {
+  /// Dedicated pool (None = global rayon pool).
+  pool: Option<Arc<rayon::ThreadPool>>,
+}
+
+impl std::fmt::Debug for TaskExecutor {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("TaskExecutor")
+      .field("dedicated", &self.is_dedicated())
+      .field("available_threads", &self.available_threads())
+      .finish()
+  }
+}
+
+impl TaskExecutor {
+  /// Executor on the global rayon pool.
+  pub fn global() -> Self {
+    Self { pool: None }
+  }
+
+  /// Executor with a dedicated pool of `threads` workers.
+  ///
+  /// On wasm32 this returns the global pool initialized by the bridge.
+  pub fn with_threads(threads: usize) -> Result<Self, rayon::ThreadPoolBuildError> {
+    Self::from_config(&ThreadPoolConfig::default().with_threads(threads))
+  }
+
+  /// Executor with a single worker: parallel iterators run serially, in
+  /// input order.
+  pub fn single_threaded() -> Result<Self, rayon::ThreadPoolBuildError> {
+    Self::from_config(
+      &ThreadPoolConfig::default()
+        .with_threads(1)
+        .with_thread_name("voxel-serial"),
+    )
+  }
+
+  /// Executor described by `config`.
+  pub fn from_config(config: &ThreadPoolConfig) -> Result<Self, rayon::ThreadPoolBuildError> {
+    #[cfg(target_arch = "wasm32")]
+    {
+      let _ = config;
+      Ok(Self::global())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+      let Some(threads) = config.threads else {
+        return Ok(Self::global());
+      };
+      let prefix = config.thread_name.clone();
+      let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads.clamp(MIN_WORKERS, MAX_WORKERS))
+        .thread_name(move |i| format!("{}-{}", prefix, i))
+        .build()?;
+      Ok(Self {
+        pool: Some(Arc::new(pool)),
+      })
+    }
+  }
+
+  /// Whether this executor owns its pool rather than using the global one.
+  pub fn is_dedicated(&self) -> bool {
+    self.pool.is_some()
+  }
+
+  /// Number of worker threads work is spread over (at least 1).
+  pub fn available_threads(&self) -> usize {
+    let threads = match &self.pool {
+      Some(pool) => pool.current_num_threads(),
+      None => rayon::current_num_threads(),
+    };
+    threads.max(MIN_WORKERS)
+  }
+
+  /// Run `task` asynchronously on this executor's pool.
+  pub fn spawn(&self, task: impl FnOnce() + Send + 'static) {
+    match &self.pool {
+      Some(pool) => pool.spawn(task),
+      None => rayon::spawn(task),
+    }
+  }
+
+  /// Whether work can run off the calling thread.
+  ///
+  /// Always true natively; on wasm32 only once the bridge has initialized
+  /// worker threads.
+  pub fn has_workers(&self) -> bool {
+    #[cfg(target_arch = "wasm32")]
+    {
+      self.pool.is_some() || rayon::current_num_threads() > 1
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+      true
+    }
+  }
+
+  /// Map `f` over `inputs` in parallel on this executor's pool.
+  ///
+  /// Results keep input order. Without workers (see `has_workers`) the batch
+  /// runs synchronously and the handle is ready immediately.
+  pub fn spawn_batch<I, T, F>(&self, inputs: Vec<I>, f: F) -> BatchHandle<T>
+  where
+    I: Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> T + Send + Sync + 'static,
+  {
+    let (sender, receiver) = channel::bounded(1);
+
+    if self.has_workers() {
+      self.spawn(move || {
+        let results: Vec<T> = inputs.into_par_iter().map(f).collect();
+        // Ignore send error (receiver dropped = handle discarded)
+        let _ = sender.send(results);
+      });
+    } else {
+      let _ = sender.send(inputs.into_iter().map(f).collect());
+    }
+
+    BatchHandle {
+      receiver: Some(receiver),
+    }
+  }
+
+  /// Run `op` inside this executor's pool, so its parallel iterators use
+  /// the pool's workers, and wait for the result.
+  pub fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+    match &self.pool {
+      Some(pool) => pool.install(op),
+      None => op(),
+    }
+  }
+}
+
+/// Results of a `TaskExecutor::spawn_batch` call.
+#[derive(Debug)]
+pub struct BatchHandle<T> {
+  /// Receiver for the results (None once delivered)
+  receiver: Option<Receiver<Vec<T>>>,
+}
+
+impl<T> BatchHandle<T> {
+  /// Take the results if the batch has finished (non-blocking).
+  ///
+  /// Returns `Some` exactly once; later calls return `None`.
+  pub fn poll(&mut self) -> Option<Vec<T>> {
+    let receiver = self.receiver.as_ref()?;
+    match receiver.try_recv() {
+      Ok(results) => {
+        self.receiver = None;
+        Some(results)
+      }
+      Err(TryRecvError::Empty) => None,
+      // Worker panicked: nothing will ever arrive
+      Err(TryRecvError::Disconnected) => {
+        self.receiver = None;
+        None
+      }
+    }
+  }
+
+  /// Whether the results have been taken (or can never arrive).
+  pub fn is_done(&self) -> bool {
+    self.receiver.is_none()
+  }
+
+  /// Block until the batch finishes and take its results.
+  ///
+  /// Don't call on the browser main thread; poll instead. Returns an empty
+  /// Vec if the results were already taken or a worker panicked.
+  pub fn wait(mut self) -> Vec<T> {
+    self
+      .receiver
+      .take()
+      .and_then(|receiver| receiver.recv().ok())
+      .unwrap_or_default()
+  }
+}
+
+/// Recommended number of worker threads for this process.
+///
+/// Deterministic for a given environment: the same override and hardware
+/// always produce the same count.
+pub fn recommended_worker_count() -> usize {
+  let env_override = std::env::var(THREADS_ENV).ok();
+  let available = std::thread::available_parallelism()
+    .ok()
+    .map(|n| n.get());
+
+  resolve_worker_count(env_override.as_deref(), available)
+}
+
+/// Resolve the worker count from an optional override and detected
+/// parallelism.
+///
+/// Overrides that don't parse as a positive integer are ignored.
+pub fn resolve_worker_count(env_override: Option<&str>, available: Option<usize>) -> usize {
+  let requested = env_override
+    .and_then(|value| value.trim().parse::<usize>().ok())
+    .filter(|&n| n > 0)
+    .or(available)
+    .unwrap_or(FALLBACK_WORKERS);
+
+  requested.clamp(MIN_WORKERS, MAX_WORKERS)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_env_override_is_honored() {
+    assert_eq!(resolve_worker_count(Some("3"), Some(16)), 3);
+    assert_eq!(resolve_worker_count(Some(" 12 "), Some(2)), 12);
+  }
+
+  #[test]
+  fn test_invalid_override_falls_back_to_available() {
+    assert_eq!(resolve_worker_count(Some("lots"), Some(8)), 8);
+    assert_eq!(resolve_worker_count(Some("0"), Some(8)), 8);
+    assert_eq!(resolve_worker_count(Some(""), Some(8)), 8);
+  }
+
+  #[test]
+  fn test_fallback_when_parallelism_unknown() {
+    assert_eq!(resolve_worker_count(None, None), FALLBACK_WORKERS);
+    assert_eq!(resolve_worker_count(Some("bad"), None), FALLBACK_WORKERS);
+  }
+
+  #[test]
+  fn test_clamp_bounds() {
+    assert_eq!(resolve_worker_count(Some("100000"), None), MAX_WORKERS);
+    assert_eq!(resolve_worker_count(None, Some(1000)), MAX_WORKERS);
+    assert_eq!(resolve_worker_count(None, Some(1)), MIN_WORKERS);
+  }
+
+  #[test]
+  fn test_global_executor_has_threads() {
+    let executor = TaskExecutor::global();
+    assert!(!executor.is_dedicated());
+    assert!(executor.available_threads() >= 1);
+  }
+
+  #[test]
+  fn test_dedicated_executor_respects_thread_count() {
+    let executor = TaskExecutor::with_threads(3).unwrap();
+    assert!(executor.is_dedicated());
+    assert_eq!(executor.available_threads(), 3);
+    assert_eq!(executor.install(rayon::current_num_threads), 3);
+
+    // Zero is clamped up rather than rejected
+    assert_eq!(
+      TaskExecutor::with_threads(0).unwrap().available_threads(),
+      MIN_WORKERS
+    );
+
+    let config = ThreadPoolConfig::default().with_thread_name("test-pool");
+    assert!(!TaskExecutor::from_config(&config).unwrap().is_dedicated());
+  }
+
+  #[test]
+  fn test_spawn_runs_on_dedicated_pool() {
+    let executor = TaskExecutor::from_config(
+      &ThreadPoolConfig::default()
+        .with_threads(2)
+        .with_thread_name("spawn-test"),
+    )
+    .unwrap();
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    executor.spawn(move || {
+      let name = std::thread::current().name().map(str::to_string);
+      sender.send(name).unwrap();
+    });
+
+    let name = receiver.recv().unwrap().unwrap();
+    assert!(name.starts_with("spawn-test-"), "{}", name);
+  }
+
+  #[test]
+  fn test_spawn_batch_keeps_input_order() {
+    let executor = TaskExecutor::with_threads(4).unwrap();
+    let inputs: Vec<u64> = (0..1000).collect();
+
+    let mut handle = executor.spawn_batch(inputs, |x| x * x);
+    let results = loop {
+      if let Some(results) = handle.poll() {
+        break results;
+      }
+      std::thread::sleep(std::time::Duration::from_millis(1));
+    };
+
+    assert!(handle.is_done());
+    assert!(handle.poll().is_none());
+    assert_eq!(results, (0..1000).map(|x| x * x).collect::<Vec<u64>>());
+
+    let global = TaskExecutor::global().spawn_batch(vec![2, 3], |x: i32| x * x);
+    assert_eq!(global.wait(), vec![4, 9]);
+  }
+
+  #[test]
+  fn test_recommended_count_within_bounds() {
+    let count = recommended_worker_count();
+    assert!((MIN_WORKERS..=MAX_WORKERS).contains(&count));
+  }
+}
@@ -1,9 +1,82 @@
 //! Core data types for Surface Nets meshing.
 
+use glam::DVec3;
+
 /// Signed distance field sample value.
 /// Negative = inside/solid, Positive = outside/air.
 pub type SdfSample = i8;
 
+/// 16-bit signed distance field sample.
+///
+/// Same scale as [`SdfSample`] (see [`sdf_conversion`]) but saturates ~258×
+/// further from the surface, so edge crossings stay exact on gentle slopes
+/// where i8 samples clamp and stair-step. Twice the memory; opt-in.
+pub type Sdf16 = i16;
+
+/// Sample types Surface Nets can mesh ([`SdfSample`], [`Sdf16`]).
+pub trait SdfValue: Copy + Default + Send + Sync + 'static {
+  /// Quantize an SDF value in world units (see [`sdf_conversion`]).
+  fn quantize(sdf: f32, voxel_size: f32) -> Self;
+
+  /// Value in voxel units.
+  fn to_voxels(self) -> f32;
+
+  /// Value clamped to i8 storage (sign preserved).
+  fn saturate(self) -> SdfSample;
+
+  /// Sign-flipped value (saturating).
+  fn negate(self) -> Self;
+
+  #[inline]
+  fn is_solid(self) -> bool {
+    self.saturate() < 0
+  }
+}
+
+impl SdfValue for SdfSample {
+  #[inline]
+  fn quantize(sdf: f32, voxel_size: f32) -> Self {
+    sdf_conversion::to_storage(sdf, voxel_size)
+  }
+
+  #[inline]
+  fn to_voxels(self) -> f32 {
+    sdf_conversion::to_float(self, 1.0)
+  }
+
+  #[inline]
+  fn saturate(self) -> SdfSample {
+    self
+  }
+
+  #[inline]
+  fn negate(self) -> Self {
+    self.saturating_neg()
+  }
+}
+
+impl SdfValue for Sdf16 {
+  #[inline]
+  fn quantize(sdf: f32, voxel_size: f32) -> Self {
+    sdf_conversion::to_storage16(sdf, voxel_size)
+  }
+
+  #[inline]
+  fn to_voxels(self) -> f32 {
+    sdf_conversion::to_float16(self, 1.0)
+  }
+
+  #[inline]
+  fn saturate(self) -> SdfSample {
+    self.clamp(-127, 127) as SdfSample
+  }
+
+  #[inline]
+  fn negate(self) -> Self {
+    self.saturating_neg()
+  }
+}
+
 /// Normal computation mode for mesh generation.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum NormalMode {
@@ -27,6 +100,12 @@ pub enum NormalMode {
     /// Cells from boundary where blending starts (typically 2-4).
     blend_distance: f32,
   },
+
+  /// Hard per-face normals for a faceted, low-poly look. Every triangle gets
+  /// its own three vertices (copies of the shared ones), so the vertex count
+  /// becomes 3x the triangle count. Meshes too large for u16 indices after
+  /// splitting keep shared vertices with geometry normals.
+  Flat,
 }
 
 impl Default for NormalMode {
@@ -35,6 +114,93 @@ impl Default for NormalMode {
   }
 }
 
+/// UV generation mode for mesh generation.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum UvMode {
+  /// No UVs generated; `Vertex::uv` stays at [0, 0].
+  #[default]
+  None,
+
+  /// Project world position onto the plane of the dominant normal axis
+  /// (matches the projection a triplanar shader would pick).
+  Triplanar,
+
+  /// Project world position onto the XZ plane.
+  /// `uv = world.xz * scale`, continuous across chunk boundaries.
+  WorldXZ {
+    /// UV units per world unit.
+    scale: f32,
+  },
+}
+
+/// Meshing algorithm used by `surface_nets::generate`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum MeshAlgorithm {
+  /// One vertex per surface cell everywhere.
+  #[default]
+  SurfaceNets,
+
+  /// Surface Nets, with flat same-material axis-aligned regions merged into
+  /// large quads. LOD seam vertices and the overlap region stay plain
+  /// Surface Nets.
+  Greedy,
+}
+
+/// Sign convention of SDF samples.
+///
+/// The crate works in `NegativeInside` internally. Samplers declare their
+/// convention with `VolumeSampler::sdf_convention`, and their samples are
+/// normalised where the crate reads them (presampling and `EditedSampler`),
+/// so edits, raycasts and `surface_height` see one convention.
+/// `MeshConfig::sdf_convention` covers volumes handed to the mesher directly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SdfConvention {
+  /// Negative inside solid, zero or positive in air (native).
+  #[default]
+  NegativeInside,
+  /// Positive inside solid, zero or negative in air.
+  PositiveInside,
+}
+
+impl SdfConvention {
+  /// Whether `sample` is solid under this convention.
+  #[inline]
+  pub fn is_solid(self, sample: SdfSample) -> bool {
+    match self {
+      SdfConvention::NegativeInside => sample < 0,
+      SdfConvention::PositiveInside => sample > 0,
+    }
+  }
+
+  /// Convert `sample` to the native negative-inside convention.
+  #[inline]
+  pub fn to_native(self, sample: SdfSample) -> SdfSample {
+    match self {
+      SdfConvention::NegativeInside => sample,
+      SdfConvention::PositiveInside => sample.saturating_neg(),
+    }
+  }
+
+  /// Convert `samples` in place to the native negative-inside convention.
+  pub fn normalize<T: SdfValue>(self, samples: &mut [T]) {
+    if self == SdfConvention::PositiveInside {
+      for sample in samples {
+        *sample = sample.negate();
+      }
+    }
+  }
+
+  /// Convert SDF gradients in place to point out of the solid, as native
+  /// gradients do.
+  pub fn normalize_normals(self, normals: &mut [[f32; 3]]) {
+    if self == SdfConvention::PositiveInside {
+      for normal in normals {
+        *normal = normal.map(|v| -v);
+      }
+    }
+  }
+}
+
 /// Material identifier (0-3 for 4-material blending).
 pub type MaterialId = u8;
 
@@ -86,6 +252,21 @@ pub mod sdf_conversion {
     let sdf_in_voxels = value as f32 / BASE_SCALE;
     sdf_in_voxels * voxel_size
   }
+
+  /// Convert float SDF to 16-bit storage. Same scale as [`to_storage`],
+  /// clamped to ±32767 instead of ±127.
+  #[inline(always)]
+  pub fn to_storage16(sdf: f32, voxel_size: f32) -> i16 {
+    let sdf_in_voxels = sdf / voxel_size;
+    (sdf_in_voxels * BASE_SCALE).clamp(-32767.0, 32767.0).round() as i16
+  }
+
+  /// Convert 16-bit storage back to float SDF in world units.
+  #[inline(always)]
+  pub fn to_float16(value: i16, voxel_size: f32) -> f32 {
+    let sdf_in_voxels = value as f32 / BASE_SCALE;
+    sdf_in_voxels * voxel_size
+  }
 }
 
 /// Output vertex with all mesh attributes.
@@ -103,6 +284,24 @@ pub struct Vertex {
 
   /// Original cell position for debugging/LOD.
   pub cell_position: [i32; 3],
+
+  /// Tangent (xyz, unit vector) with bitangent handedness in w (±1).
+  /// Only populated when `MeshConfig::generate_tangents` is set and UVs are
+  /// generated.
+  pub tangent: [f32; 4],
+
+  /// Texture coordinates. Only populated when `MeshConfig::uv_mode` is not
+  /// `UvMode::None`.
+  pub uv: [f32; 2],
+
+  /// Baked ambient occlusion (0 = occluded, 1 = open). Only populated when
+  /// `MeshConfig::compute_ao` is set.
+  pub ao: f32,
+
+  /// Mean curvature of the surface in inverse voxel units: positive on
+  /// convex bumps and edges, negative in cavities, 0 on flat ground. Only
+  /// populated when `MeshConfig::compute_curvature` is set.
+  pub curvature: f32,
 }
 
 impl Default for Vertex {
@@ -112,6 +311,10 @@ impl Default for Vertex {
       normal: [0.0, 1.0, 0.0],
       material_weights: [1.0, 0.0, 0.0, 0.0],
       cell_position: [0; 3],
+      tangent: [1.0, 0.0, 0.0, 1.0],
+      uv: [0.0; 2],
+      ao: 1.0,
+      curvature: 0.0,
     }
   }
 }
@@ -169,9 +372,35 @@ pub struct MeshOutput {
   /// Uses u16 since Surface Nets on 32³ volume produces at most 32,768 vertices.
   pub indices: Vec<u16>,
 
+  /// 32-bit triangle indices, used instead of `indices` (which is then empty)
+  /// when the mesh has more vertices than u16 can address. Only `merge`
+  /// produces such meshes; chunk meshes always fit `indices`.
+  pub indices32: Vec<u32>,
+
+  /// Overlap-region triangles that the boundary filter would discard, kept
+  /// as a separate index range for seam debugging. Only populated when
+  /// `MeshConfig::debug_keep_boundary` is set.
+  pub boundary_indices: Vec<u16>,
+
+  /// Triangles the boundary filter removed from `indices` (0 when
+  /// `MeshConfig::filter_boundary` is off). For `generate_region` output
+  /// this covers only the rebuilt cells. Useful when diagnosing missing
+  /// walls at chunk seams.
+  pub filtered_triangle_count: usize,
+
+  /// Set when `NormalMode::Flat` could not split the vertices (the split
+  /// mesh would need more vertices than u16 indices can address), so the
+  /// mesh kept smooth shared-vertex normals instead of facets.
+  pub flat_shading_fallback: bool,
+
   /// Displaced positions for LOD seam vertices (parallel to vertices).
   pub displaced_positions: Vec<[f32; 3]>,
 
+  /// Blend weights for materials 4-7 (parallel to vertices, whose
+  /// `material_weights` then hold 0-3; all 8 sum to 1.0). Only populated
+  /// when `MeshConfig::material_count` is 8, empty otherwise.
+  pub material_weights_hi: Vec<[f32; 4]>,
+
   /// Bounding box encompassing all vertices.
   pub bounds: MinMaxAABB,
 }
@@ -185,7 +414,12 @@ impl MeshOutput {
   pub fn clear(&mut self) {
     self.vertices.clear();
     self.indices.clear();
+    self.indices32.clear();
+    self.boundary_indices.clear();
+    self.filtered_triangle_count = 0;
+    self.flat_shading_fallback = false;
     self.displaced_positions.clear();
+    self.material_weights_hi.clear();
     self.bounds = MinMaxAABB::empty();
   }
 
@@ -196,7 +430,101 @@ impl MeshOutput {
 
   /// Number of triangles in the mesh.
   pub fn triangle_count(&self) -> usize {
-    self.indices.len() / 3
+    (self.indices.len() + self.indices32.len()) / 3
+  }
+
+  /// Indices as u16 (no conversion), or `None` if the mesh needs 32-bit
+  /// indices.
+  pub fn indices_u16(&self) -> Option<&[u16]> {
+    self.indices32.is_empty().then_some(self.indices.as_slice())
+  }
+
+  /// Indices as u32, widened from `indices` unless the mesh already uses
+  /// 32-bit indices.
+  pub fn indices_u32(&self) -> Vec<u32> {
+    if self.indices32.is_empty() {
+      self.indices.iter().map(|&i| i as u32).collect()
+    } else {
+      self.indices32.clone()
+    }
+  }
+
+  /// Combine several chunk meshes into one (prop baking, draw-call
+  /// batching).
+  ///
+  /// Each chunk's positions are shifted by its offset, vertices are
+  /// concatenated and indices re-based.
+  ///
+  /// If the combined vertex count exceeds what u16 indices can address
+  /// (65,536), the result falls back to 32-bit `indices32` and drops the
+  /// debug `boundary_indices`.
+  pub fn merge(chunks: &[(DVec3, MeshOutput)]) -> MeshOutput {
+    let vertex_count: usize = chunks.iter().map(|(_, c)| c.vertices.len()).sum();
+    let wide = vertex_count > u16::MAX as usize + 1;
+    let index_count: usize = chunks.iter().map(|(_, c)| c.indices.len()).sum();
+
+    let has_weights_hi = chunks
+      .iter()
+      .any(|(_, c)| !c.material_weights_hi.is_empty());
+    let mut merged = MeshOutput {
+      vertices: Vec::with_capacity(vertex_count),
+      indices: Vec::with_capacity(if wide { 0 } else { index_count }),
+      indices32: Vec::with_capacity(if wide { index_count } else { 0 }),
+      displaced_positions: Vec::with_capacity(vertex_count),
+      flat_shading_fallback: chunks.iter().any(|(_, c)| c.flat_shading_fallback),
+      ..Default::default()
+    };
+
+    for (offset, chunk) in chunks {
+      let base = merged.vertices.len();
+      let shift = |p: [f32; 3]| {
+        (*offset + DVec3::from_array(p.map(f64::from)))
+          .as_vec3()
+          .to_array()
+      };
+
+      for (i, vertex) in chunk.vertices.iter().enumerate() {
+        let position = shift(vertex.position);
+        merged.vertices.push(Vertex {
+          position,
+          ..*vertex
+        });
+        merged.displaced_positions.push(
+          chunk
+            .displaced_positions
+            .get(i)
+            .copied()
+            .map_or(position, shift),
+        );
+        merged.bounds.encapsulate(position);
+      }
+      if has_weights_hi {
+        // Chunks meshed with 4 materials weigh nothing in 4-7
+        merged
+          .material_weights_hi
+          .extend_from_slice(&chunk.material_weights_hi);
+        merged
+          .material_weights_hi
+          .resize(merged.vertices.len(), [0.0; 4]);
+      }
+
+      if wide {
+        merged
+          .indices32
+          .extend(chunk.indices.iter().map(|&i| (base + i as usize) as u32));
+      } else {
+        let base = base as u16;
+        merged
+          .indices
+          .extend(chunk.indices.iter().map(|&i| base + i));
+        merged
+          .boundary_indices
+          .extend(chunk.boundary_indices.iter().map(|&i| base + i));
+      }
+      merged.filtered_triangle_count += chunk.filtered_triangle_count;
+    }
+
+    merged
   }
 }
 
@@ -218,6 +546,99 @@ pub struct MeshConfig {
 
   /// Apply MicroSplat-compatible weight encoding.
   pub use_microsplat_encoding: bool,
+
+  /// Generate per-vertex tangents for tangent-space normal mapping.
+  /// Tangents follow the UVs, so they are only generated when `uv_mode` is
+  /// not `UvMode::None`.
+  pub generate_tangents: bool,
+
+  /// UV generation mode.
+  pub uv_mode: UvMode,
+
+  /// World-space position of sample (0, 0, 0), used for world-space UVs.
+  pub world_origin: [f32; 3],
+
+  /// Merge coincident vertices after triangulation.
+  pub weld_vertices: bool,
+
+  /// Welding distance in voxel units (used when `weld_vertices` is set).
+  pub weld_epsilon: f32,
+
+  /// Collapse edges in near-coplanar regions whose normals deviate by less
+  /// than this angle (degrees). `None` disables simplification.
+  pub simplify_angle_deg: Option<f32>,
+
+  /// Depth of LOD skirts below boundary edges, in voxel units (0 = off).
+  /// Skirt vertices are tagged with `surface_nets::SKIRT_CELL`.
+  pub skirt_depth: f32,
+
+  /// Compute normals across the rayon pool for large meshes (see
+  /// `surface_nets::PARALLEL_NORMALS_MIN_VERTICES`).
+  pub parallel_normals: bool,
+
+  /// Keep overlap-region triangles in `MeshOutput::boundary_indices` instead
+  /// of discarding them (debug shaders can highlight them).
+  pub debug_keep_boundary: bool,
+
+  /// Remove triangles lying entirely in the overlap region, which the
+  /// neighbouring chunk also emits (default: true). Turning it off keeps
+  /// them in `indices`, e.g. to rule the filter out when debugging seams.
+  pub filter_boundary: bool,
+
+  /// Skip whole quads that touch the overlap region instead of filtering
+  /// triangles permissively, and never add skirts. A surface enclosed by a
+  /// single chunk comes out closed (every edge shared by two triangles).
+  pub watertight: bool,
+
+  /// Bake per-vertex ambient occlusion into `Vertex::ao`.
+  pub compute_ao: bool,
+
+  /// Estimate surface curvature into `Vertex::curvature` (for cavity and
+  /// edge-highlight shading).
+  pub compute_curvature: bool,
+
+  /// AO sampling radius in samples around each vertex's cell.
+  pub ao_radius: u32,
+
+  /// Newton steps moving each vertex from the edge-crossing centroid toward
+  /// the isosurface of its cell (0 = centroid only). Improves accuracy on
+  /// curved surfaces; 2-3 steps are usually enough.
+  pub vertex_refinement_steps: u8,
+
+  /// Skip the inner walls of air cavities sealed inside the chunk. Detection
+  /// is per chunk and conservative: air touching the chunk boundary is
+  /// treated as open, so cavities spanning chunks are still meshed.
+  pub skip_enclosed_cavities: bool,
+
+  /// Sign convention of the input volume.
+  pub sdf_convention: SdfConvention,
+
+  /// Only produce positions and indices (e.g. for physics proxies): skips
+  /// material weights, normals, tangents, UVs and AO, leaving those fields
+  /// at their placeholders. Normals are still computed when skirts need them.
+  pub positions_only: bool,
+
+  /// Use analytic normals from `VolumeSampler::sample_normals`, when passed
+  /// to `surface_nets::generate_with_normals`, instead of `normal_mode`.
+  pub prefer_analytic_normals: bool,
+
+  /// Sample 16-bit SDF values (`VolumeSampler::sample_volume16`) and mesh
+  /// them with `surface_nets::generate16` in the refinement pipeline, so
+  /// shallow slopes don't snap to the i8 quantization steps.
+  pub use_sdf16: bool,
+
+  /// Split quads along the diagonal whose endpoints have the most similar
+  /// material weights (falling back to the shorter diagonal on ties), so
+  /// each triangle stays closer to a single material at material borders.
+  pub material_aware_split: bool,
+
+  /// Number of material IDs blended per vertex: 4 (IDs 0-3, the default) or
+  /// 8 (IDs 0-7, with 4-7 in `MeshOutput::material_weights_hi`). Higher IDs
+  /// are clamped to the last slot.
+  pub material_count: u8,
+
+  /// Meshing algorithm.
+  pub mesh_algorithm: MeshAlgorithm,
 }
 
 impl Default for MeshConfig {
@@ -227,6 +648,29 @@ impl Default for MeshConfig {
       neighbor_mask: 0,
       normal_mode: NormalMode::default(),
       use_microsplat_encoding: false,
+      generate_tangents: false,
+      uv_mode: UvMode::None,
+      world_origin: [0.0; 3],
+      weld_vertices: false,
+      weld_epsilon: crate::surface_nets::DEFAULT_WELD_EPSILON,
+      simplify_angle_deg: None,
+      skirt_depth: 0.0,
+      parallel_normals: false,
+      debug_keep_boundary: false,
+      filter_boundary: true,
+      watertight: false,
+      compute_ao: false,
+      compute_curvature: false,
+      ao_radius: 2,
+      vertex_refinement_steps: 0,
+      skip_enclosed_cavities: false,
+      sdf_convention: SdfConvention::NegativeInside,
+      positions_only: false,
+      prefer_analytic_normals: false,
+      use_sdf16: false,
+      material_aware_split: false,
+      material_count: 4,
+      mesh_algorithm: MeshAlgorithm::SurfaceNets,
     }
   }
 }
@@ -256,6 +700,122 @@ impl MeshConfig {
     self
   }
 
+  pub fn with_tangents(mut self, generate: bool) -> Self {
+    self.generate_tangents = generate;
+    self
+  }
+
+  pub fn with_uv_mode(mut self, mode: UvMode) -> Self {
+    self.uv_mode = mode;
+    self
+  }
+
+  pub fn with_world_origin(mut self, origin: [f32; 3]) -> Self {
+    self.world_origin = origin;
+    self
+  }
+
+  pub fn with_weld_vertices(mut self, weld: bool) -> Self {
+    self.weld_vertices = weld;
+    self
+  }
+
+  pub fn with_weld_epsilon(mut self, epsilon: f32) -> Self {
+    self.weld_epsilon = epsilon;
+    self
+  }
+
+  pub fn with_simplify_angle(mut self, angle_deg: Option<f32>) -> Self {
+    self.simplify_angle_deg = angle_deg;
+    self
+  }
+
+  pub fn with_skirt_depth(mut self, depth: f32) -> Self {
+    self.skirt_depth = depth;
+    self
+  }
+
+  pub fn with_parallel_normals(mut self, parallel: bool) -> Self {
+    self.parallel_normals = parallel;
+    self
+  }
+
+  pub fn with_debug_keep_boundary(mut self, keep: bool) -> Self {
+    self.debug_keep_boundary = keep;
+    self
+  }
+
+  pub fn with_filter_boundary(mut self, filter: bool) -> Self {
+    self.filter_boundary = filter;
+    self
+  }
+
+  pub fn with_watertight(mut self, watertight: bool) -> Self {
+    self.watertight = watertight;
+    self
+  }
+
+  pub fn with_ao(mut self, compute: bool) -> Self {
+    self.compute_ao = compute;
+    self
+  }
+
+  pub fn with_curvature(mut self, compute: bool) -> Self {
+    self.compute_curvature = compute;
+    self
+  }
+
+  pub fn with_ao_radius(mut self, radius: u32) -> Self {
+    self.ao_radius = radius;
+    self
+  }
+
+  pub fn with_vertex_refinement_steps(mut self, steps: u8) -> Self {
+    self.vertex_refinement_steps = steps;
+    self
+  }
+
+  pub fn with_skip_enclosed_cavities(mut self, skip: bool) -> Self {
+    self.skip_enclosed_cavities = skip;
+    self
+  }
+
+  pub fn with_sdf_convention(mut self, convention: SdfConvention) -> Self {
+    self.sdf_convention = convention;
+    self
+  }
+
+  pub fn with_positions_only(mut self, positions_only: bool) -> Self {
+    self.positions_only = positions_only;
+    self
+  }
+
+  pub fn with_analytic_normals(mut self, prefer: bool) -> Self {
+    self.prefer_analytic_normals = prefer;
+    self
+  }
+
+  pub fn with_sdf16(mut self, enabled: bool) -> Self {
+    self.use_sdf16 = enabled;
+    self
+  }
+
+  pub fn with_material_aware_split(mut self, enabled: bool) -> Self {
+    self.material_aware_split = enabled;
+    self
+  }
+
+  /// Blend 4 or 8 materials per vertex; any count above 4 selects 8.
+  pub fn with_material_count(mut self, count: u8) -> Self {
+    self.material_count = if count > 4 { 8 } else { 4 };
+    self
+  }
+
+  pub fn with_mesh_algorithm(mut self, algorithm: MeshAlgorithm) -> Self {
+    self.mesh_algorithm = algorithm;
+    self
+  }
+
   /// Legacy compatibility: set gradient normals (true) or geometry normals
   /// (false).
   #[deprecated(note = "Use with_normal_mode instead")]
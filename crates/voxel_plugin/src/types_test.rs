@@ -115,3 +115,79 @@ fn test_normal_mode_variants() {
     _ => panic!("Expected Blended mode"),
   }
 }
+
+// Index accessor tests
+#[test]
+fn test_index_accessors() {
+  let mut output = MeshOutput::new();
+  output.vertices = vec![Vertex::default(); 3];
+  output.indices = vec![0, 1, 2];
+
+  assert_eq!(output.indices_u32(), vec![0, 1, 2]);
+  assert_eq!(output.indices_u16(), Some(&[0, 1, 2][..]));
+}
+
+#[test]
+fn test_merge_adjacent_chunks() {
+  let chunk = |corner: f32| {
+    let mut output = MeshOutput::new();
+    for position in [[corner, 0.0, 0.0], [28.0, 1.0, 0.0], [0.0, 0.0, 28.0]] {
+      output.vertices.push(Vertex {
+        position,
+        ..Default::default()
+      });
+      output.bounds.encapsulate(position);
+    }
+    output.indices = vec![0, 1, 2];
+    output
+  };
+  let a = chunk(0.0);
+  let b = chunk(5.0);
+
+  let merged = MeshOutput::merge(&[
+    (glam::DVec3::ZERO, a.clone()),
+    (glam::DVec3::new(28.0, 0.0, 0.0), b.clone()),
+  ]);
+
+  assert_eq!(merged.vertices.len(), a.vertices.len() + b.vertices.len());
+  assert_eq!(merged.indices.len(), a.indices.len() + b.indices.len());
+  assert_eq!(merged.indices, vec![0, 1, 2, 3, 4, 5]);
+  assert_eq!(merged.vertices[3].position, [33.0, 0.0, 0.0]);
+
+  // Bounds enclose both chunks (second one shifted by +28 on X)
+  assert_eq!(merged.bounds.min, [0.0, 0.0, 0.0]);
+  assert_eq!(merged.bounds.max, [56.0, 1.0, 28.0]);
+}
+
+#[test]
+fn test_merge_falls_back_to_u32_past_65536_vertices() {
+  let chunk = |count: usize| {
+    let mut output = MeshOutput::new();
+    output.vertices = vec![Vertex::default(); count];
+    output.indices = vec![0, 1, (count - 1) as u16];
+    output
+  };
+  let half = u16::MAX as usize / 2 + 1;
+
+  // Exactly 65,536 vertices still fit
+  let merged = MeshOutput::merge(&[
+    (glam::DVec3::ZERO, chunk(half)),
+    (glam::DVec3::ZERO, chunk(half)),
+  ]);
+  assert!(merged.indices32.is_empty());
+  assert_eq!(merged.indices_u16().unwrap()[5], u16::MAX);
+
+  // One more switches to 32-bit indices instead of wrapping
+  let merged = MeshOutput::merge(&[
+    (glam::DVec3::ZERO, chunk(half)),
+    (glam::DVec3::ZERO, chunk(half + 1)),
+  ]);
+  assert_eq!(merged.vertices.len(), 65_537);
+  assert!(merged.indices.is_empty());
+  assert_eq!(merged.indices_u16(), None);
+  assert_eq!(
+    merged.indices_u32(),
+    vec![0, 1, half as u32 - 1, half as u32, half as u32 + 1, 65_536]
+  );
+  assert_eq!(merged.triangle_count(), 2);
+}
@@ -4,14 +4,24 @@
 //! Multiple worlds can exist independently (overworld, dioramas, voxel
 //! characters).
 
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use glam::{DAffine3, DVec3};
 
-use crate::octree::{OctreeConfig, OctreeLeaves, RefinementBudget, RefinementInput, RefinementOutput};
+use crate::constants::{coord_to_index, SAMPLE_SIZE, SAMPLE_SIZE_CB};
+use crate::edit::{edits_from_bytes, edits_to_bytes, EditedSampler, SdfBrush};
+use crate::octree::{
+  DAabb3, OctreeConfig, OctreeLeaves, OctreeNode, RefinementBudget, RefinementInput,
+  RefinementOutput,
+};
 use crate::pipeline::{
-  process_transitions, ChunkPresentation, PresentationBatch, ReadyChunk, VolumeSampler,
+  process_invalidations_with_mesh_config, process_transitions_timed_with_mesh_config,
+  sample_volume_for_node, ChunkPresentation, PresentationBatch, ProcessingStats, ReadyChunk,
+  VolumeSampler,
 };
+use crate::surface_nets::VoxelRegion;
+use crate::types::{MaterialId, MeshConfig, SdfSample};
 #[cfg(feature = "metrics")]
 use crate::metrics::WorldMetrics;
 
@@ -46,6 +56,23 @@ impl Default for WorldId {
   }
 }
 
+// =============================================================================
+// RayHit - surface raycast result
+// =============================================================================
+
+/// First surface crossing found by [`VoxelWorld::raycast`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+  /// Interpolated crossing point (local space).
+  pub position: DVec3,
+  /// Unit surface normal at the hit, pointing out of the solid.
+  pub normal: DVec3,
+  /// Distance from the ray origin to `position`.
+  pub distance: f64,
+  /// Leaf whose samples contain the hit.
+  pub node: OctreeNode,
+}
+
 // =============================================================================
 // VoxelWorld<S> - per-world state container
 // =============================================================================
@@ -72,6 +99,20 @@ impl Default for WorldId {
 /// let output = world.refine(viewer_local_pos);
 /// // Apply output.transition_groups to spawn/despawn chunks
 /// ```
+///
+/// # Edits
+///
+/// `apply_edit()` layers an SDF brush over the sampler and marks the leaves
+/// it touches dirty; `remesh_dirty()` remeshes them through the pipeline's
+/// invalidation path. Edits persist, so chunks meshed later by refinement
+/// include them too.
+///
+/// The last `undo_limit` edits can be taken back with `undo_edit()` and
+/// reapplied with `redo_edit()`; both dirty the leaves the edit touches.
+///
+/// Brushes owned by the scene rather than the player (e.g. declarative CSG
+/// shapes) go in `brushes` through `set_brushes()`. They apply before the
+/// edits and are neither saved nor undoable.
 pub struct VoxelWorld<S: VolumeSampler> {
   /// Unique world identifier.
   pub id: WorldId,
@@ -92,6 +133,34 @@ pub struct VoxelWorld<S: VolumeSampler> {
   /// Refinement budget (limits per-frame work).
   pub budget: RefinementBudget,
 
+  /// Mesh settings of the world's chunks (e.g. `prefer_analytic_normals`).
+  /// Voxel size, neighbor mask and world origin are set per chunk.
+  pub mesh_config: MeshConfig,
+
+  /// Scene brushes applied before `edits`. Not saved by `save_edits()`.
+  pub brushes: Vec<SdfBrush>,
+
+  /// SDF edits combined with the sampler's output, oldest first.
+  pub edits: Vec<SdfBrush>,
+
+  /// Edits taken back by `undo_edit()`, most recent last. Cleared by
+  /// `apply_edit()`.
+  pub undone_edits: Vec<SdfBrush>,
+
+  /// Maximum number of recent edits `undo_edit()` can take back. Older
+  /// edits become permanent, which bounds `undone_edits`.
+  pub undo_limit: usize,
+
+  /// How many of the newest `edits` can still be undone.
+  undo_depth: usize,
+
+  /// Leaves whose mesh is stale after an edit.
+  pub dirty: HashSet<OctreeNode>,
+
+  /// Modified sample region of each dirty leaf, for partial remeshing with
+  /// [`surface_nets::generate_region`](crate::surface_nets::generate_region).
+  pub dirty_regions: HashMap<OctreeNode, VoxelRegion>,
+
   /// World metrics (timing histograms, counters).
   /// Only available when compiled with `metrics` feature.
   #[cfg(feature = "metrics")]
@@ -99,6 +168,9 @@ pub struct VoxelWorld<S: VolumeSampler> {
 }
 
 impl<S: VolumeSampler> VoxelWorld<S> {
+  /// Default `undo_limit`.
+  pub const DEFAULT_UNDO_LIMIT: usize = 64;
+
   /// Create a new world with identity transform.
   pub fn new(config: OctreeConfig, sampler: S) -> Self {
     Self {
@@ -108,6 +180,14 @@ impl<S: VolumeSampler> VoxelWorld<S> {
       sampler,
       transform: DAffine3::IDENTITY,
       budget: RefinementBudget::DEFAULT,
+      mesh_config: MeshConfig::default(),
+      brushes: Vec::new(),
+      edits: Vec::new(),
+      undone_edits: Vec::new(),
+      undo_limit: Self::DEFAULT_UNDO_LIMIT,
+      undo_depth: 0,
+      dirty: HashSet::new(),
+      dirty_regions: HashMap::new(),
       #[cfg(feature = "metrics")]
       metrics: WorldMetrics::default(),
     }
@@ -122,6 +202,14 @@ impl<S: VolumeSampler> VoxelWorld<S> {
       sampler,
       transform: DAffine3::IDENTITY,
       budget: RefinementBudget::DEFAULT,
+      mesh_config: MeshConfig::default(),
+      brushes: Vec::new(),
+      edits: Vec::new(),
+      undone_edits: Vec::new(),
+      undo_limit: Self::DEFAULT_UNDO_LIMIT,
+      undo_depth: 0,
+      dirty: HashSet::new(),
+      dirty_regions: HashMap::new(),
       #[cfg(feature = "metrics")]
       metrics: WorldMetrics::default(),
     }
@@ -153,6 +241,125 @@ impl<S: VolumeSampler> VoxelWorld<S> {
     self.transform.transform_point3(local_pos)
   }
 
+  /// Find the current leaf containing a point (local space).
+  ///
+  /// Walks from the coarsest LOD present in the leaves down to `min_lod`.
+  /// A point exactly on a face shared by two leaves resolves to the leaf on
+  /// the positive side of that face (see
+  /// [`OctreeConfig::get_node_containing`]).
+  ///
+  /// Returns `None` if the point is outside `world_bounds` or no leaf covers
+  /// it.
+  pub fn leaf_at(&self, world_pos: DVec3) -> Option<OctreeNode> {
+    leaf_containing(&self.config, &self.leaves, world_pos)
+  }
+
+  /// Height of the highest air-to-solid transition in the column at
+  /// `(x, z)` (local space).
+  ///
+  /// Steps down from the top of `world_bounds` (or of the current leaves
+  /// when unbounded) on point samples of the edited sampler, with a step
+  /// coarse enough to cover the column in at most 128 samples, then bisects
+  /// the first air/solid bracket down to a `min_lod` voxel. The height is
+  /// interpolated across the final bracket, so it is accurate to within a
+  /// voxel.
+  ///
+  /// Returns `None` if no solid sample is found.
+  pub fn surface_height(&self, x: f64, z: f64) -> Option<f64> {
+    let (bottom, top) = self.vertical_extent()?;
+    let voxel_size = self.config.get_voxel_size(self.config.min_lod);
+    let sampler = self.edited_sampler();
+    let sample = |y: f64| sampler.sample_point([x, y, z], voxel_size);
+
+    let mut lod = self.config.min_lod;
+    while lod < self.config.max_lod && self.config.get_voxel_size(lod) * 128.0 < top - bottom {
+      lod += 1;
+    }
+    let step = self.config.get_voxel_size(lod);
+
+    // Coarse pass: bracket the first solid sample below air
+    let (mut air_y, mut air_value) = (top, sample(top));
+    if air_value < 0 {
+      return Some(top);
+    }
+    let (mut solid_y, mut solid_value) = loop {
+      if air_y <= bottom {
+        return None;
+      }
+      let y = (air_y - step).max(bottom);
+      let value = sample(y);
+      if value < 0 {
+        break (y, value);
+      }
+      (air_y, air_value) = (y, value);
+    };
+
+    // Fine pass: bisect the bracket down to one voxel
+    while air_y - solid_y > voxel_size {
+      let y = 0.5 * (air_y + solid_y);
+      let value = sample(y);
+      if value < 0 {
+        (solid_y, solid_value) = (y, value);
+      } else {
+        (air_y, air_value) = (y, value);
+      }
+    }
+
+    let t = air_value as f64 / (air_value as f64 - solid_value as f64);
+    Some(air_y - t * (air_y - solid_y))
+  }
+
+  /// Vertical range searched by `surface_height`.
+  fn vertical_extent(&self) -> Option<(f64, f64)> {
+    if let Some(bounds) = &self.config.world_bounds {
+      return Some((bounds.min.y, bounds.max.y));
+    }
+    self
+      .leaves
+      .iter()
+      .map(|node| self.config.get_node_aabb(node))
+      .map(|aabb| (aabb.min.y, aabb.max.y))
+      .reduce(|(a_min, a_max), (b_min, b_max)| (a_min.min(b_min), a_max.max(b_max)))
+  }
+
+  /// Cast a ray against the SDF (local space).
+  ///
+  /// Marches from `origin` along `dir` through the current leaves, half a
+  /// leaf voxel per step, reading single points from the edited sampler at
+  /// the leaf's voxel size. The first air-to-solid crossing is interpolated
+  /// between the last two samples; the normal is the SDF gradient there. A
+  /// ray starting inside solid hits at its origin. Stretches not covered by
+  /// any leaf are stepped through at `min_lod` resolution without sampling.
+  ///
+  /// Returns `None` if `dir` is zero, nothing solid lies within `max_dist`,
+  /// or the ray misses `world_bounds`. Unbounded worlds need a finite
+  /// `max_dist`.
+  pub fn raycast(&self, origin: DVec3, dir: DVec3, max_dist: f64) -> Option<RayHit> {
+    raycast_leaves(
+      &self.config,
+      &self.leaves,
+      &self.edited_sampler(),
+      origin,
+      dir,
+      max_dist,
+    )
+  }
+
+  /// Copy what `raycast` reads into a [`RaycastJob`], so rays can be cast
+  /// without holding on to the world (e.g. after releasing its lock).
+  pub fn begin_raycast(&self) -> RaycastJob<S>
+  where
+    S: Clone,
+  {
+    RaycastJob {
+      sampler: self.sampler.clone(),
+      brushes: self.brushes.clone(),
+      edits: self.edits.clone(),
+      config: self.config.clone(),
+      leaves: OctreeLeaves::from(self.leaves.as_set().clone()),
+    }
+  }
+
   /// Refine the octree based on viewer position.
   ///
   /// Returns transition groups describing chunks to spawn/despawn.
@@ -168,17 +375,30 @@ impl<S: VolumeSampler> VoxelWorld<S> {
   /// }
   /// ```
   pub fn refine(&mut self, viewer_pos: DVec3) -> RefinementOutput {
+    self.refine_multi(viewer_pos, &[])
+  }
+
+  /// Refine the octree around several viewers (local space).
+  ///
+  /// Each node's LOD follows its nearest viewer; the world budget is shared
+  /// across all of them. See [`crate::octree::refine_multi`].
+  pub fn refine_multi(
+    &mut self,
+    viewer_pos: DVec3,
+    additional_viewers: &[DVec3],
+  ) -> RefinementOutput {
     #[cfg(feature = "metrics")]
     let start = web_time::Instant::now();
 
     let input = RefinementInput {
       viewer_pos,
+      view_frustum: None,
       config: self.config.clone(),
       prev_leaves: self.leaves.as_set().clone(),
       budget: self.budget,
     };
 
-    let output = crate::octree::refine(input);
+    let output = crate::octree::refine_multi(input, additional_viewers);
 
     // Update leaves to match refinement output
     self.leaves = OctreeLeaves::from(output.next_leaves.clone());
@@ -186,8 +406,9 @@ impl<S: VolumeSampler> VoxelWorld<S> {
     #[cfg(feature = "metrics")]
     {
       let elapsed = start.elapsed().as_micros() as u64;
-      self.metrics.record_refine_timing(elapsed);
+      // Transitions first: they decide whether this timing is sampled
       self.metrics.record_transitions(output.transition_groups.len());
+      self.metrics.record_refine_timing(elapsed);
     }
 
     output
@@ -214,35 +435,349 @@ impl<S: VolumeSampler> VoxelWorld<S> {
   /// }
   /// ```
   pub fn update(&mut self, viewer_pos: DVec3) -> PresentationBatch {
+    self.update_multi(viewer_pos, &[])
+  }
+
+  /// Update world state around several viewers (local space).
+  ///
+  /// Same as `update`, refining with `refine_multi`.
+  pub fn update_multi(
+    &mut self,
+    viewer_pos: DVec3,
+    additional_viewers: &[DVec3],
+  ) -> PresentationBatch {
     // 1. Run refinement (updates self.leaves, records timing if metrics enabled)
-    let output = self.refine(viewer_pos);
+    let output = self.refine_multi(viewer_pos, additional_viewers);
 
     if output.transition_groups.is_empty() {
       return PresentationBatch::default();
     }
 
+    self.process_refinement(&output)
+  }
+
+  /// Refine and process repeatedly until the octree settles.
+  ///
+  /// Blocks until refinement produces no transitions or `max_iterations`
+  /// rounds have run. Intended for tests and screenshot tooling that need a
+  /// fully converged world in one call.
+  ///
+  /// Returns the presentation batch of every round that had transitions, in
+  /// order; apply them all to reach the settled state.
+  pub fn refine_until_stable(
+    &mut self,
+    viewer_pos: DVec3,
+    max_iterations: usize,
+  ) -> Vec<PresentationBatch> {
+    let mut batches = Vec::new();
+
+    for _ in 0..max_iterations {
+      let output = self.refine(viewer_pos);
+      if output.transition_groups.is_empty() {
+        break;
+      }
+      batches.push(self.process_refinement(&output));
+    }
+
+    batches
+  }
+
+  /// Start an update whose meshing can run on another thread.
+  ///
+  /// Refines like `update_multi`, so the leaves change right away, and
+  /// returns the meshing as an [`UpdateJob`], or `None` when no transition
+  /// is needed. `run` the job anywhere and pass its result to
+  /// `finish_update`. Don't refine again until then: the job's chunks are
+  /// only valid for these leaves.
+  pub fn begin_update_multi(
+    &mut self,
+    viewer_pos: DVec3,
+    additional_viewers: &[DVec3],
+  ) -> Option<UpdateJob<S>>
+  where
+    S: Clone,
+  {
+    let output = self.refine_multi(viewer_pos, additional_viewers);
+    if output.transition_groups.is_empty() {
+      return None;
+    }
+
+    Some(UpdateJob {
+      world_id: self.id,
+      output,
+      sampler: self.sampler.clone(),
+      brushes: self.brushes.clone(),
+      edits: self.edits.clone(),
+      config: self.config.clone(),
+      mesh_config: self.mesh_config.clone(),
+    })
+  }
+
+  /// Build the presentation batch of a job from `begin_update_multi`.
+  ///
+  /// Leaves meshed by the job while brushes or edits changed are marked
+  /// dirty, so the next `remesh_dirty()` replaces their stale meshes.
+  pub fn finish_update(&mut self, result: UpdateResult) -> PresentationBatch {
+    if result.brushes != self.brushes || result.edits != self.edits {
+      let full = VoxelRegion::new([0; 3], [SAMPLE_SIZE - 1; 3]);
+      for group in &result.output.transition_groups {
+        for &node in &group.nodes_to_add {
+          if self.leaves.contains(&node) {
+            self.dirty.insert(node);
+            self.dirty_regions.insert(node, full);
+          }
+        }
+      }
+    }
+    self.present_refinement(&result.output, result.ready_chunks, &result.stats)
+  }
+
+  /// Mesh the transitions of a refinement and build the presentation batch.
+  fn process_refinement(&mut self, output: &RefinementOutput) -> PresentationBatch {
     // 2. Process transitions through pipeline (parallel via rayon)
-    let ready_chunks = process_transitions(
+    let (ready_chunks, stats) = process_transitions_timed_with_mesh_config(
       self.id,
       &output.transition_groups,
-      &self.sampler,
+      &self.edited_sampler(),
       self.leaves.as_set(),
       &self.config,
+      &self.mesh_config,
     );
 
-    // 3. Record mesh timing metrics (aggregate from ready_chunks)
+    self.present_refinement(output, ready_chunks, &stats)
+  }
+
+  /// Record metrics for a meshed refinement and build its presentation batch.
+  fn present_refinement(
+    &mut self,
+    output: &RefinementOutput,
+    ready_chunks: Vec<ReadyChunk>,
+    _stats: &ProcessingStats,
+  ) -> PresentationBatch {
+    // 3. Record mesh timing and per-stage metrics
     #[cfg(feature = "metrics")]
     {
-      // Sum all mesh timings from this batch
-      let total_mesh_us: u64 = ready_chunks.iter().map(|c| c.timing_us).sum();
-      if total_mesh_us > 0 {
-        self.metrics.record_mesh_timing(total_mesh_us);
-      }
-      self.metrics.record_chunks_meshed(ready_chunks.len());
+      self.record_mesh_metrics(&ready_chunks);
+      self.metrics.record_stage_timings(_stats);
     }
 
     // 4. Build presentation batch
-    self.build_presentation_batch(&output, ready_chunks)
+    let batch = self.build_presentation_batch(output, ready_chunks);
+    #[cfg(feature = "metrics")]
+    self.metrics.record_presentation(&batch);
+    batch
+  }
+
+  /// Record mesh timing metrics aggregated over a batch of ready chunks.
+  #[cfg(feature = "metrics")]
+  fn record_mesh_metrics(&mut self, ready_chunks: &[ReadyChunk]) {
+    // Sum all mesh timings from this batch
+    let total_mesh_us: u64 = ready_chunks.iter().map(|c| c.timing_us).sum();
+    if total_mesh_us > 0 {
+      self.metrics.record_mesh_timing(total_mesh_us);
+    }
+    self.metrics.record_chunks_meshed(ready_chunks.len());
+  }
+
+  /// Sampler combining the base sampler with all brushes and edits.
+  pub fn edited_sampler(&self) -> EditedSampler<'_, S> {
+    EditedSampler {
+      base: &self.sampler,
+      brushes: &self.brushes,
+      edits: &self.edits,
+    }
+  }
+
+  /// Sample a node's 32³ volume and materials, edits included.
+  ///
+  /// Returns exactly what the pipeline meshes for `node`, for consumers that
+  /// need the SDF itself (navmesh builders, physics, analysis).
+  pub fn sample_node_volume(
+    &self,
+    node: &OctreeNode,
+  ) -> (Box<[SdfSample; SAMPLE_SIZE_CB]>, Box<[MaterialId; SAMPLE_SIZE_CB]>) {
+    let sampled = sample_volume_for_node(node, &self.edited_sampler(), &self.config);
+    (sampled.volume, sampled.materials)
+  }
+
+  /// Apply an SDF brush on top of the sampler's base SDF.
+  ///
+  /// The edit is stored and combined with the base SDF whenever the world is
+  /// sampled. Every leaf whose samples the brush can change is marked dirty.
+  ///
+  /// Returns the leaves needing a remesh for this edit. Call
+  /// `remesh_dirty()` to mesh them.
+  pub fn apply_edit(&mut self, brush: SdfBrush) -> HashSet<OctreeNode> {
+    self.undone_edits.clear();
+    self.undo_depth = (self.undo_depth + 1).min(self.undo_limit);
+    self.push_edit(brush)
+  }
+
+  /// Take back the most recent undoable edit.
+  ///
+  /// Returns the leaves needing a remesh, or `None` when there is nothing
+  /// left to undo. Call `remesh_dirty()` to mesh them.
+  pub fn undo_edit(&mut self) -> Option<HashSet<OctreeNode>> {
+    if self.undo_depth == 0 {
+      return None;
+    }
+    let brush = self.edits.pop()?;
+    self.undo_depth -= 1;
+
+    let affected = self.mark_brush_dirty(&brush);
+    self.undone_edits.push(brush);
+    Some(affected)
+  }
+
+  /// Reapply the most recently undone edit.
+  ///
+  /// Returns the leaves needing a remesh, or `None` when there is nothing
+  /// to redo.
+  pub fn redo_edit(&mut self) -> Option<HashSet<OctreeNode>> {
+    let brush = self.undone_edits.pop()?;
+    self.undo_depth += 1;
+    Some(self.push_edit(brush))
+  }
+
+  /// Replace the scene brushes.
+  ///
+  /// Leaves touched by the old or the new brushes are marked dirty; the
+  /// edits and their undo history are kept. Returns those leaves. Call
+  /// `remesh_dirty()` to mesh them.
+  pub fn set_brushes(&mut self, brushes: Vec<SdfBrush>) -> HashSet<OctreeNode> {
+    let old = std::mem::take(&mut self.brushes);
+    let mut affected = HashSet::new();
+    for brush in old.iter().chain(&brushes) {
+      affected.extend(self.mark_brush_dirty(brush));
+    }
+
+    self.brushes = brushes;
+    affected
+  }
+
+  /// Encode the world's edits for saving (see `edit::edits_to_bytes`).
+  pub fn save_edits(&self) -> Vec<u8> {
+    edits_to_bytes(&self.edits)
+  }
+
+  /// Replace the world's edits with ones saved by `save_edits()`.
+  ///
+  /// Leaves touched by the old or the loaded edits are marked dirty and the
+  /// undo history is cleared. Returns those leaves, or `None` (leaving the
+  /// world unchanged) if `bytes` don't decode.
+  pub fn load_edits(&mut self, bytes: &[u8]) -> Option<HashSet<OctreeNode>> {
+    let loaded = edits_from_bytes(bytes)?;
+
+    let old = std::mem::take(&mut self.edits);
+    let mut affected = HashSet::new();
+    for brush in old.iter().chain(&loaded) {
+      affected.extend(self.mark_brush_dirty(brush));
+    }
+
+    self.edits = loaded;
+    self.undone_edits.clear();
+    self.undo_depth = 0;
+    Some(affected)
+  }
+
+  /// Append `brush` to `edits` and dirty the leaves it touches.
+  fn push_edit(&mut self, brush: SdfBrush) -> HashSet<OctreeNode> {
+    let affected = self.mark_brush_dirty(&brush);
+    self.edits.push(brush);
+    affected
+  }
+
+  /// Mark every leaf whose samples `brush` can change dirty.
+  fn mark_brush_dirty(&mut self, brush: &SdfBrush) -> HashSet<OctreeNode> {
+    let affected: HashSet<OctreeNode> = self
+      .leaves
+      .iter()
+      .filter(|node| {
+        let voxel_size = self.config.get_voxel_size(node.lod);
+        // Samples span one voxel past the cell on the positive faces
+        let min = self.config.get_node_min(node);
+        let max = min + DVec3::splat((SAMPLE_SIZE - 1) as f64 * voxel_size);
+        brush
+          .influence_aabb(voxel_size)
+          .overlaps(&DAabb3::new(min, max))
+      })
+      .copied()
+      .collect();
+
+    for node in &affected {
+      let region = self.brush_sample_region(node, brush);
+      self
+        .dirty_regions
+        .entry(*node)
+        .and_modify(|dirty| *dirty = dirty.union(&region))
+        .or_insert(region);
+    }
+
+    self.dirty.extend(affected.iter().copied());
+    affected
+  }
+
+  /// Samples of `node`'s 32³ volume that `brush` can change.
+  fn brush_sample_region(&self, node: &OctreeNode, brush: &SdfBrush) -> VoxelRegion {
+    let voxel_size = self.config.get_voxel_size(node.lod);
+    let min = self.config.get_node_min(node);
+    let influence = brush.influence_aabb(voxel_size);
+    let last = (SAMPLE_SIZE - 1) as f64;
+    let to_sample = |v: DVec3| ((v - min) / voxel_size).clamp(DVec3::ZERO, DVec3::splat(last));
+    let lo = to_sample(influence.min).ceil();
+    let hi = to_sample(influence.max).floor();
+    VoxelRegion::new(
+      [lo.x as usize, lo.y as usize, lo.z as usize],
+      [hi.x as usize, hi.y as usize, hi.z as usize],
+    )
+  }
+
+  /// Take a dirty leaf out of the dirty set, returning its modified region.
+  ///
+  /// For callers that keep each chunk's `MeshOutput` and remesh edits
+  /// themselves with `surface_nets::generate_region` instead of
+  /// `remesh_dirty()`.
+  pub fn take_dirty_region(&mut self, node: &OctreeNode) -> Option<VoxelRegion> {
+    self.dirty.remove(node);
+    self.dirty_regions.remove(node)
+  }
+
+  /// Remesh all dirty leaves through the pipeline's invalidation path.
+  ///
+  /// Every dirty leaf is listed in `to_despawn`; leaves that still contain a
+  /// surface are respawned with an `Immediate` hint. Dirty nodes that are no
+  /// longer leaves are dropped (refinement already remeshed them).
+  pub fn remesh_dirty(&mut self) -> PresentationBatch {
+    let dirty = std::mem::take(&mut self.dirty);
+    self.dirty_regions.clear();
+    let nodes: Vec<OctreeNode> = dirty
+      .into_iter()
+      .filter(|node| self.leaves.contains(node))
+      .collect();
+
+    if nodes.is_empty() {
+      return PresentationBatch::default();
+    }
+
+    let ready_chunks = process_invalidations_with_mesh_config(
+      self.id,
+      &nodes,
+      &self.edited_sampler(),
+      self.leaves.as_set(),
+      &self.config,
+      &self.mesh_config,
+    );
+
+    #[cfg(feature = "metrics")]
+    self.record_mesh_metrics(&ready_chunks);
+
+    let batch = PresentationBatch {
+      to_despawn: nodes,
+      to_spawn: self.present_chunks(ready_chunks),
+    };
+    #[cfg(feature = "metrics")]
+    self.metrics.record_presentation(&batch);
+    batch
   }
 
   /// Build presentation batch from refinement output and ready chunks.
@@ -257,7 +792,15 @@ impl<S: VolumeSampler> VoxelWorld<S> {
       .flat_map(|g| g.nodes_to_remove.iter().copied())
       .collect();
 
-    let to_spawn = ready_chunks
+    PresentationBatch {
+      to_despawn,
+      to_spawn: self.present_chunks(ready_chunks),
+    }
+  }
+
+  /// Position ready chunks in the world for presentation.
+  fn present_chunks(&self, ready_chunks: Vec<ReadyChunk>) -> Vec<ChunkPresentation> {
+    ready_chunks
       .into_iter()
       .map(|chunk| {
         let position = self.config.get_node_min(&chunk.node);
@@ -270,19 +813,234 @@ impl<S: VolumeSampler> VoxelWorld<S> {
           hint: chunk.hint,
         }
       })
-      .collect();
+      .collect()
+  }
+}
 
-    PresentationBatch { to_despawn, to_spawn }
+/// Leaf of `leaves` containing `world_pos`; see `VoxelWorld::leaf_at`.
+fn leaf_containing(
+  config: &OctreeConfig,
+  leaves: &OctreeLeaves,
+  world_pos: DVec3,
+) -> Option<OctreeNode> {
+  if let Some(bounds) = &config.world_bounds {
+    if !bounds.contains_point(world_pos) {
+      return None;
+    }
   }
+
+  let coarsest = leaves.effective_max_lod().min(config.max_lod);
+  (config.min_lod..=coarsest)
+    .rev()
+    .map(|lod| config.get_node_containing(world_pos, lod))
+    .find(|node| leaves.contains(node))
 }
 
+/// March a ray through `leaves`; see `VoxelWorld::raycast`.
+fn raycast_leaves(
+  config: &OctreeConfig,
+  leaves: &OctreeLeaves,
+  sampler: &impl VolumeSampler,
+  origin: DVec3,
+  dir: DVec3,
+  max_dist: f64,
+) -> Option<RayHit> {
+  let dir = dir.try_normalize()?;
+  if !origin.is_finite() {
+    return None;
+  }
+
+  let (mut t, end) = match &config.world_bounds {
+    Some(bounds) => {
+      let (enter, exit) = ray_aabb_interval(bounds, origin, dir)?;
+      (enter.max(0.0), exit.min(max_dist))
+    }
+    None => (0.0, max_dist),
+  };
+  if t > end || !end.is_finite() {
+    return None;
+  }
+
+  let sample = |p: DVec3, voxel_size: f64| sampler.sample_point(p.to_array(), voxel_size) as f64;
+  // Previous air sample: (t, value)
+  let mut previous: Option<(f64, f64)> = None;
+
+  loop {
+    let position = origin + dir * t;
+    let Some(node) = leaf_containing(config, leaves, position) else {
+      previous = None;
+      if t >= end {
+        return None;
+      }
+      t = (t + config.get_voxel_size(config.min_lod)).min(end);
+      continue;
+    };
+
+    let voxel_size = config.get_voxel_size(node.lod);
+    let value = sample(position, voxel_size);
+
+    if value < 0.0 {
+      let distance = match previous {
+        Some((air_t, air_value)) => air_t + (t - air_t) * air_value / (air_value - value),
+        None => t,
+      };
+      let position = origin + dir * distance;
+      // Central differences half a voxel either side
+      let h = 0.5 * voxel_size;
+      let diff = |axis: DVec3| {
+        sample(position + axis * h, voxel_size) - sample(position - axis * h, voxel_size)
+      };
+      let normal = DVec3::new(diff(DVec3::X), diff(DVec3::Y), diff(DVec3::Z))
+        .try_normalize()
+        .unwrap_or(-dir);
+      return Some(RayHit {
+        position,
+        normal,
+        distance,
+        node,
+      });
+    }
+
+    previous = Some((t, value));
+    if t >= end {
+      return None;
+    }
+    t = (t + 0.5 * voxel_size).min(end);
+  }
+}
+
+/// Parameter interval `[enter, exit]` where `origin + dir * t` lies inside
+/// `bounds`, or `None` if the ray's line misses it.
+fn ray_aabb_interval(bounds: &DAabb3, origin: DVec3, dir: DVec3) -> Option<(f64, f64)> {
+  let mut enter = f64::NEG_INFINITY;
+  let mut exit = f64::INFINITY;
+  for axis in 0..3 {
+    if dir[axis] == 0.0 {
+      if origin[axis] < bounds.min[axis] || origin[axis] > bounds.max[axis] {
+        return None;
+      }
+      continue;
+    }
+    let t0 = (bounds.min[axis] - origin[axis]) / dir[axis];
+    let t1 = (bounds.max[axis] - origin[axis]) / dir[axis];
+    enter = enter.max(t0.min(t1));
+    exit = exit.min(t0.max(t1));
+  }
+  (enter <= exit).then_some((enter, exit))
+}
+
+/// Trilinear interpolation of a sample volume at `local` (in samples).
+pub(crate) fn sample_trilinear(volume: &[SdfSample; SAMPLE_SIZE_CB], local: DVec3) -> f64 {
+  let max = (SAMPLE_SIZE - 1) as f64;
+  let p = local.clamp(DVec3::ZERO, DVec3::splat(max));
+  let base = p.floor().min(DVec3::splat(max - 1.0));
+  let f = p - base;
+  let (x, y, z) = (base.x as usize, base.y as usize, base.z as usize);
+
+  let mut value = 0.0;
+  for corner in 0..8 {
+    let (dx, dy, dz) = (corner & 1, (corner >> 1) & 1, (corner >> 2) & 1);
+    let w = (if dx == 1 { f.x } else { 1.0 - f.x })
+      * (if dy == 1 { f.y } else { 1.0 - f.y })
+      * (if dz == 1 { f.z } else { 1.0 - f.z });
+    value += w * volume[coord_to_index(x + dx, y + dy, z + dz)] as f64;
+  }
+  value
+}
+
+/// Central-difference gradient of the interpolated SDF at `local`.
+pub(crate) fn sdf_gradient(volume: &[SdfSample; SAMPLE_SIZE_CB], local: DVec3) -> DVec3 {
+  let h = 0.5;
+  let diff = |axis: DVec3| {
+    sample_trilinear(volume, local + axis * h) - sample_trilinear(volume, local - axis * h)
+  };
+  DVec3::new(diff(DVec3::X), diff(DVec3::Y), diff(DVec3::Z))
+}
+
+// =============================================================================
+// UpdateJob - refinement meshing detached from its world
+// =============================================================================
+
+/// Meshing of one refinement, detached from its world so it can run on
+/// another thread. Created by `VoxelWorld::begin_update_multi`.
+pub struct UpdateJob<S> {
+  world_id: WorldId,
+  output: RefinementOutput,
+  sampler: S,
+  brushes: Vec<SdfBrush>,
+  edits: Vec<SdfBrush>,
+  config: OctreeConfig,
+  mesh_config: MeshConfig,
+}
+
+/// Raycasting state detached from its world, so rays can be cast on another
+/// thread or after the world's lock is released. Created by
+/// `VoxelWorld::begin_raycast`; reflects the world at that time.
+pub struct RaycastJob<S> {
+  sampler: S,
+  brushes: Vec<SdfBrush>,
+  edits: Vec<SdfBrush>,
+  config: OctreeConfig,
+  leaves: OctreeLeaves,
+}
+
+impl<S: VolumeSampler> RaycastJob<S> {
+  /// Cast a ray as `VoxelWorld::raycast` would.
+  pub fn cast(&self, origin: DVec3, dir: DVec3, max_dist: f64) -> Option<RayHit> {
+    let sampler = EditedSampler {
+      base: &self.sampler,
+      brushes: &self.brushes,
+      edits: &self.edits,
+    };
+    raycast_leaves(&self.config, &self.leaves, &sampler, origin, dir, max_dist)
+  }
+}
+
+/// Meshed [`UpdateJob`], for `VoxelWorld::finish_update`.
+pub struct UpdateResult {
+  output: RefinementOutput,
+  ready_chunks: Vec<ReadyChunk>,
+  stats: ProcessingStats,
+  /// Brushes and edits the job meshed with.
+  brushes: Vec<SdfBrush>,
+  edits: Vec<SdfBrush>,
+}
+
+impl<S: VolumeSampler> UpdateJob<S> {
+  /// Mesh the job's transition groups.
+  pub fn run(self) -> UpdateResult {
+    let sampler = EditedSampler {
+      base: &self.sampler,
+      brushes: &self.brushes,
+      edits: &self.edits,
+    };
+    let (ready_chunks, stats) = process_transitions_timed_with_mesh_config(
+      self.world_id,
+      &self.output.transition_groups,
+      &sampler,
+      &self.output.next_leaves,
+      &self.config,
+      &self.mesh_config,
+    );
+
+    UpdateResult {
+      output: self.output,
+      ready_chunks,
+      stats,
+      brushes: self.brushes,
+      edits: self.edits,
+    }
+  }
+}
 
 #[cfg(test)]
 mod tests {
   use super::*;
   use crate::constants::SAMPLE_SIZE_CB;
-  use crate::octree::DAabb3;
-  use crate::types::{MaterialId, SdfSample};
+  use crate::edit::EditOp;
+  use crate::pipeline::PresentationHint;
+  use crate::sdf_samplers::GroundPlaneSampler;
+  use crate::types::{MaterialId, SdfConvention, SdfSample};
 
   /// Mock sampler for testing.
   struct MockSampler;
@@ -299,6 +1057,10 @@ mod tests {
       volume.fill(127);
       materials.fill(0);
     }
+
+    fn sample_point(&self, _position: [f64; 3], _voxel_size: f64) -> SdfSample {
+      127
+    }
   }
 
   #[test]
@@ -346,6 +1108,542 @@ mod tests {
     assert!((global_pos - back_to_global).length() < 1e-10);
   }
 
+  #[test]
+  fn test_refine_until_stable_settles_world() {
+    let config = OctreeConfig {
+      max_lod: 6,
+      world_bounds: Some(DAabb3::from_center_half_extents(
+        DVec3::ZERO,
+        DVec3::splat(500.0),
+      )),
+      ..OctreeConfig::default()
+    };
+    let mut world = VoxelWorld::new_with_initial_lod(config, MockSampler, 6);
+    world.budget = RefinementBudget {
+      max_subdivisions: 4,
+      max_collapses: 4,
+      ..RefinementBudget::NO_NEIGHBOR_ENFORCEMENT
+    };
+
+    let viewer = DVec3::new(10.0, 10.0, 10.0);
+    let batches = world.refine_until_stable(viewer, 256);
+    assert!(batches.len() > 1, "Small budget should need several rounds");
+
+    let output = world.refine(viewer);
+    assert!(
+      output.transition_groups.is_empty(),
+      "World should be stable, got {} transitions",
+      output.transition_groups.len()
+    );
+  }
+
+  /// Bounded ground plane world starting from one LOD 6 leaf.
+  fn ground_world() -> VoxelWorld<GroundPlaneSampler> {
+    let config = OctreeConfig {
+      max_lod: 6,
+      world_bounds: Some(DAabb3::from_center_half_extents(
+        DVec3::ZERO,
+        DVec3::splat(500.0),
+      )),
+      ..OctreeConfig::default()
+    };
+    VoxelWorld::new_with_initial_lod(config, GroundPlaneSampler::new(0.3), 6)
+  }
+
+  #[test]
+  fn test_update_job_matches_update_multi() {
+    let viewer = DVec3::new(10.0, 10.0, 10.0);
+    let mut direct = ground_world();
+    let mut detached = ground_world();
+
+    let expected = direct.update_multi(viewer, &[]);
+    let job = detached.begin_update_multi(viewer, &[]).unwrap();
+    assert_eq!(detached.leaves.as_set(), direct.leaves.as_set());
+    let batch = detached.finish_update(job.run());
+
+    assert!(!batch.to_spawn.is_empty());
+    assert_eq!(batch.to_despawn, expected.to_despawn);
+    let nodes = |batch: &PresentationBatch| -> Vec<OctreeNode> {
+      batch.to_spawn.iter().map(|chunk| chunk.node).collect()
+    };
+    assert_eq!(nodes(&batch), nodes(&expected));
+    assert!(detached.dirty.is_empty());
+  }
+
+  #[test]
+  fn test_update_job_marks_leaves_dirty_after_concurrent_edit() {
+    let mut world = ground_world();
+    let job = world
+      .begin_update_multi(DVec3::new(10.0, 10.0, 10.0), &[])
+      .unwrap();
+
+    // Far outside the world, so the edit itself dirties nothing
+    assert!(world
+      .apply_edit(SdfBrush::Sphere {
+        center: DVec3::splat(10_000.0),
+        radius: 1.0,
+        op: EditOp::Remove,
+      })
+      .is_empty());
+    let batch = world.finish_update(job.run());
+
+    // Every leaf meshed without the edit is remeshed with it
+    assert!(!batch.to_spawn.is_empty());
+    for chunk in &batch.to_spawn {
+      assert!(world.dirty.contains(&chunk.node));
+    }
+    let remeshed = world.remesh_dirty();
+    for chunk in &batch.to_spawn {
+      assert!(remeshed.to_despawn.contains(&chunk.node));
+    }
+  }
+
+  #[test]
+  fn test_mesh_config_analytic_normals_reach_chunks() {
+    use crate::sdf_samplers::SphereSampler;
+
+    // LOD 0 node spanning -14..14, around a sphere at the origin
+    let config = OctreeConfig {
+      world_origin: DVec3::splat(-14.0),
+      ..OctreeConfig::default()
+    };
+    let node = OctreeNode::new(0, 0, 0, 0);
+    let remesh = |analytic: bool| {
+      let mut world = VoxelWorld::new(config.clone(), SphereSampler::new(10.0));
+      world.mesh_config.prefer_analytic_normals = analytic;
+      world.leaves.insert(node);
+      world.dirty.insert(node);
+      world.remesh_dirty().to_spawn.remove(0).output
+    };
+    // Largest 1 - cos(angle) between vertex normals and the radial direction
+    let worst_error = |output: &crate::types::MeshOutput| {
+      output
+        .vertices
+        .iter()
+        .map(|v| {
+          let radial = (glam::Vec3::from_array(v.position) - glam::Vec3::splat(14.0)).normalize();
+          1.0 - glam::Vec3::from_array(v.normal).dot(radial)
+        })
+        .fold(0.0f32, f32::max)
+    };
+
+    let (gradient, analytic) = (remesh(false), remesh(true));
+    assert_eq!(analytic.indices, gradient.indices);
+    let (analytic_error, gradient_error) = (worst_error(&analytic), worst_error(&gradient));
+    assert!(analytic_error < 1e-3, "analytic error {}", analytic_error);
+    assert!(analytic_error < gradient_error);
+  }
+
+  #[cfg(feature = "metrics")]
+  #[test]
+  fn test_metrics_go_idle_when_world_is_static() {
+    let config = OctreeConfig {
+      max_lod: 6,
+      world_bounds: Some(DAabb3::from_center_half_extents(
+        DVec3::ZERO,
+        DVec3::splat(500.0),
+      )),
+      ..OctreeConfig::default()
+    };
+    let mut world = VoxelWorld::new_with_initial_lod(config, MockSampler, 6);
+    world.budget = RefinementBudget::NO_NEIGHBOR_ENFORCEMENT;
+    world.metrics.idle_timeout_frames = 4;
+
+    let viewer = DVec3::new(10.0, 10.0, 10.0);
+    world.refine_until_stable(viewer, 256);
+    assert!(!world.metrics.is_idle(), "Settling just ended");
+
+    for _ in 0..4 {
+      world.update(viewer);
+    }
+
+    let snapshot = world.metrics.snapshot();
+    assert!(snapshot.idle, "No-op updates should mark metrics idle");
+    assert_eq!(snapshot.refine.sample_count, 0);
+
+    // Idle refinements are not sampled
+    let sampled = world.metrics.refine_timings.len();
+    world.update(viewer);
+    assert_eq!(world.metrics.refine_timings.len(), sampled);
+  }
+
+  /// World with one LOD 1 leaf next to the 8 LOD 0 children of its sibling.
+  fn mixed_lod_world() -> VoxelWorld<MockSampler> {
+    let config = OctreeConfig {
+      world_bounds: Some(DAabb3::new(DVec3::ZERO, DVec3::new(112.0, 56.0, 56.0))),
+      ..OctreeConfig::default()
+    };
+    let mut world = VoxelWorld::new(config, MockSampler);
+    let fine_parent = OctreeNode::new(0, 0, 0, 1);
+    for octant in 0..8 {
+      world.leaves.insert(fine_parent.get_child(octant).unwrap());
+    }
+    world.leaves.insert(OctreeNode::new(1, 0, 0, 1));
+    world
+  }
+
+  #[test]
+  fn test_leaf_at_node_centers() {
+    let world = mixed_lod_world();
+
+    for node in world.leaves.iter() {
+      let center = world.config.get_node_center(node);
+      assert_eq!(world.leaf_at(center), Some(*node));
+    }
+  }
+
+  #[test]
+  fn test_leaf_at_shared_face_picks_positive_side() {
+    let world = mixed_lod_world();
+
+    // Face between two LOD 0 leaves at x = 28
+    assert_eq!(
+      world.leaf_at(DVec3::new(28.0, 10.0, 10.0)),
+      Some(OctreeNode::new(1, 0, 0, 0))
+    );
+    // Face between a LOD 0 leaf and the LOD 1 leaf at x = 56
+    assert_eq!(
+      world.leaf_at(DVec3::new(56.0, 10.0, 10.0)),
+      Some(OctreeNode::new(1, 0, 0, 1))
+    );
+  }
+
+  #[test]
+  fn test_leaf_at_outside_bounds() {
+    let world = mixed_lod_world();
+
+    assert_eq!(world.leaf_at(DVec3::new(-1.0, 10.0, 10.0)), None);
+    assert_eq!(world.leaf_at(DVec3::new(10.0, 100.0, 10.0)), None);
+  }
+
+  #[test]
+  fn test_remove_sphere_on_shared_face_dirties_both_leaves() {
+    let mut world = mixed_lod_world();
+    let left = OctreeNode::new(0, 0, 0, 0);
+    let right = OctreeNode::new(1, 0, 0, 0);
+
+    // Sphere centered on the face between the two LOD 0 leaves at x = 28
+    let dirty = world.apply_edit(SdfBrush::Sphere {
+      center: DVec3::new(28.0, 14.0, 14.0),
+      radius: 3.0,
+      op: EditOp::Remove,
+    });
+
+    assert_eq!(dirty, HashSet::from([left, right]));
+    assert_eq!(world.dirty, dirty);
+    assert_eq!(world.edits.len(), 1);
+
+    // Influence spans x 24..32 and y/z 10..18, split across the shared face
+    assert_eq!(
+      world.dirty_regions[&left],
+      VoxelRegion::new([24, 10, 10], [31, 18, 18])
+    );
+    assert_eq!(
+      world.dirty_regions[&right],
+      VoxelRegion::new([0, 10, 10], [4, 18, 18])
+    );
+
+    // Remeshing consumes the dirty set and despawns both leaves
+    let batch = world.remesh_dirty();
+    assert_eq!(batch.to_despawn.len(), 2);
+    assert!(batch.to_despawn.contains(&left));
+    assert!(batch.to_despawn.contains(&right));
+    assert!(world.dirty.is_empty());
+    assert!(world.dirty_regions.is_empty());
+  }
+
+  #[test]
+  fn test_remesh_dirty_carves_base_terrain() {
+    let config = OctreeConfig {
+      world_bounds: Some(DAabb3::new(DVec3::ZERO, DVec3::splat(56.0))),
+      ..OctreeConfig::default()
+    };
+    let mut world = VoxelWorld::new(config, GroundPlaneSampler::new(14.3));
+    world.leaves.insert(OctreeNode::new(0, 0, 0, 0));
+
+    let center = DVec3::new(14.0, 14.3, 14.0);
+    assert!((world.surface_height(14.0, 14.0).unwrap() - 14.3).abs() <= 1.0);
+
+    world.apply_edit(SdfBrush::Sphere {
+      center,
+      radius: 4.0,
+      op: EditOp::Remove,
+    });
+    let batch = world.remesh_dirty();
+    assert_eq!(batch.to_spawn.len(), 1);
+    assert!(matches!(
+      batch.to_spawn[0].hint,
+      PresentationHint::Immediate
+    ));
+
+    // Crater floor below the untouched ground
+    assert!((world.surface_height(14.0, 14.0).unwrap() - 10.3).abs() <= 1.0);
+    assert!((world.surface_height(2.0, 2.0).unwrap() - 14.3).abs() <= 1.0);
+  }
+
+  #[test]
+  fn test_surface_height_bisects_tall_column() {
+    let config = OctreeConfig {
+      world_bounds: Some(DAabb3::new(DVec3::ZERO, DVec3::new(64.0, 4096.0, 64.0))),
+      ..OctreeConfig::default()
+    };
+    let world = VoxelWorld::new(config, GroundPlaneSampler::new(1234.3));
+    assert!((world.surface_height(10.0, 10.0).unwrap() - 1234.3).abs() < 0.25);
+
+    let buried = VoxelWorld::new(world.config.clone(), GroundPlaneSampler::new(-5.0));
+    assert_eq!(buried.surface_height(10.0, 10.0), None);
+  }
+
+  #[test]
+  fn test_raycast_job_keeps_world_at_creation() {
+    let config = OctreeConfig {
+      world_bounds: Some(DAabb3::new(DVec3::ZERO, DVec3::splat(56.0))),
+      ..OctreeConfig::default()
+    };
+    let mut world = VoxelWorld::new(config, GroundPlaneSampler::new(14.3));
+    world.leaves.insert(OctreeNode::new(0, 0, 0, 0));
+
+    let origin = DVec3::new(14.0, 50.0, 14.0);
+    let ground = world.raycast(origin, DVec3::NEG_Y, 100.0).unwrap();
+    assert!((ground.position.y - 14.3).abs() < 0.25);
+    assert!(ground.normal.dot(DVec3::Y) > 0.99);
+
+    let job = world.begin_raycast();
+    world.apply_edit(SdfBrush::Sphere {
+      center: DVec3::new(14.0, 14.3, 14.0),
+      radius: 4.0,
+      op: EditOp::Remove,
+    });
+
+    // The job still sees the ground; the world and new jobs see the crater
+    assert_eq!(job.cast(origin, DVec3::NEG_Y, 100.0), Some(ground));
+    let crater = world.raycast(origin, DVec3::NEG_Y, 100.0).unwrap();
+    assert!((crater.position.y - 10.3).abs() < 0.5);
+    assert_eq!(
+      world.begin_raycast().cast(origin, DVec3::NEG_Y, 100.0),
+      Some(crater)
+    );
+  }
+
+  /// `GroundPlaneSampler` with its signs flipped: positive inside solid.
+  struct PositiveInsideGround(GroundPlaneSampler);
+
+  impl VolumeSampler for PositiveInsideGround {
+    fn sample_volume(
+      &self,
+      grid_offset: [i64; 3],
+      voxel_size: f64,
+      volume: &mut [SdfSample; SAMPLE_SIZE_CB],
+      materials: &mut [MaterialId; SAMPLE_SIZE_CB],
+    ) {
+      self
+        .0
+        .sample_volume(grid_offset, voxel_size, volume, materials);
+      for sample in volume.iter_mut() {
+        *sample = sample.saturating_neg();
+      }
+    }
+
+    fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> SdfSample {
+      self.0.sample_point(position, voxel_size).saturating_neg()
+    }
+
+    fn sdf_convention(&self) -> SdfConvention {
+      SdfConvention::PositiveInside
+    }
+  }
+
+  #[test]
+  fn test_positive_inside_sampler_matches_negative_inside() {
+    let config = OctreeConfig {
+      world_bounds: Some(DAabb3::new(DVec3::ZERO, DVec3::splat(56.0))),
+      ..OctreeConfig::default()
+    };
+    let node = OctreeNode::new(0, 0, 0, 0);
+    let crater = SdfBrush::Sphere {
+      center: DVec3::new(14.0, 14.3, 14.0),
+      radius: 4.0,
+      op: EditOp::Remove,
+    };
+
+    let mut native = VoxelWorld::new(config.clone(), GroundPlaneSampler::new(14.3));
+    native.leaves.insert(node);
+    native.apply_edit(crater);
+    let mut flipped = VoxelWorld::new(config, PositiveInsideGround(GroundPlaneSampler::new(14.3)));
+    flipped.leaves.insert(node);
+    flipped.apply_edit(crater);
+
+    // Edits, meshing input, surface queries and raycasts all see the
+    // normalised samples
+    assert_eq!(
+      flipped.sample_node_volume(&node).0,
+      native.sample_node_volume(&node).0
+    );
+    assert_eq!(
+      flipped.surface_height(14.0, 14.0),
+      native.surface_height(14.0, 14.0)
+    );
+    let origin = DVec3::new(14.0, 50.0, 14.0);
+    let hit = native.raycast(origin, DVec3::NEG_Y, 100.0);
+    assert!(hit.is_some());
+    assert_eq!(flipped.raycast(origin, DVec3::NEG_Y, 100.0), hit);
+  }
+
+  #[test]
+  fn test_sample_node_volume_matches_meshed_volume() {
+    let config = OctreeConfig {
+      world_bounds: Some(DAabb3::new(DVec3::ZERO, DVec3::splat(56.0))),
+      ..OctreeConfig::default()
+    };
+    let mut world = VoxelWorld::new(config, GroundPlaneSampler::new(14.3));
+    let node = OctreeNode::new(0, 0, 0, 0);
+    world.leaves.insert(node);
+    world.apply_edit(SdfBrush::Sphere {
+      center: DVec3::new(14.0, 14.3, 14.0),
+      radius: 4.0,
+      op: EditOp::Remove,
+    });
+    let batch = world.remesh_dirty();
+    assert_eq!(batch.to_spawn.len(), 1);
+
+    let (volume, materials) = world.sample_node_volume(&node);
+
+    // Edits are applied: the crater is air where the base ground is solid
+    let crater = coord_to_index(14, 12, 14);
+    let (base, _) = VoxelWorld::new(world.config.clone(), GroundPlaneSampler::new(14.3))
+      .sample_node_volume(&node);
+    assert!(base[crater] < 0);
+    assert!(volume[crater] >= 0);
+
+    // Meshing it as the pipeline does (single leaf: no coarser neighbours)
+    let mesh_config =
+      crate::pipeline::node_mesh_config(&world.mesh_config, &node, 0, &world.config);
+    let output = crate::surface_nets::generate(&volume, &materials, &mesh_config);
+    assert_eq!(output.vertices, batch.to_spawn[0].output.vertices);
+    assert_eq!(output.indices, batch.to_spawn[0].output.indices);
+  }
+
+  #[test]
+  fn test_undo_edit_restores_original_volume() {
+    let config = OctreeConfig {
+      world_bounds: Some(DAabb3::new(DVec3::ZERO, DVec3::splat(56.0))),
+      ..OctreeConfig::default()
+    };
+    let mut world = VoxelWorld::new(config, GroundPlaneSampler::new(14.3));
+    let node = OctreeNode::new(0, 0, 0, 0);
+    world.leaves.insert(node);
+    let (original, original_materials) = world.sample_node_volume(&node);
+
+    let brush = SdfBrush::Sphere {
+      center: DVec3::new(14.0, 14.3, 14.0),
+      radius: 4.0,
+      op: EditOp::Remove,
+    };
+    world.apply_edit(brush);
+    world.remesh_dirty();
+    assert_ne!(world.sample_node_volume(&node).0, original);
+
+    let dirty = world.undo_edit().unwrap();
+    assert_eq!(dirty, HashSet::from([node]));
+    assert!(world.edits.is_empty());
+    let batch = world.remesh_dirty();
+    assert_eq!(batch.to_despawn, vec![node]);
+
+    let (volume, materials) = world.sample_node_volume(&node);
+    assert_eq!(volume, original);
+    assert_eq!(materials, original_materials);
+    assert!(world.undo_edit().is_none());
+
+    // Redo brings the crater back; a new edit clears the redo stack
+    assert!(world.redo_edit().is_some());
+    assert_eq!(world.edits, vec![brush]);
+    world.apply_edit(brush);
+    assert!(world.redo_edit().is_none());
+  }
+
+  #[test]
+  fn test_loaded_edits_match_edited_volume() {
+    let config = OctreeConfig {
+      world_bounds: Some(DAabb3::new(DVec3::ZERO, DVec3::splat(56.0))),
+      ..OctreeConfig::default()
+    };
+    let node = OctreeNode::new(0, 0, 0, 0);
+    let mut edited = VoxelWorld::new(config.clone(), GroundPlaneSampler::new(14.3));
+    edited.leaves.insert(node);
+    edited.apply_edit(SdfBrush::Sphere {
+      center: DVec3::new(14.0, 14.3, 14.0),
+      radius: 4.0,
+      op: EditOp::Remove,
+    });
+    edited.apply_edit(SdfBrush::Box {
+      center: DVec3::new(6.0, 16.0, 6.0),
+      half_extents: DVec3::splat(2.0),
+      op: EditOp::place(1, 4),
+    });
+    let saved = edited.save_edits();
+
+    let mut loaded = VoxelWorld::new(config, GroundPlaneSampler::new(14.3));
+    loaded.leaves.insert(node);
+    assert_eq!(loaded.load_edits(&saved), Some(HashSet::from([node])));
+    assert_eq!(loaded.edits, edited.edits);
+    assert_eq!(
+      loaded.sample_node_volume(&node),
+      edited.sample_node_volume(&node)
+    );
+    assert_eq!(loaded.remesh_dirty().to_despawn, vec![node]);
+
+    // Undecodable saves leave the world alone
+    assert!(loaded.load_edits(&saved[..saved.len() - 1]).is_none());
+    assert_eq!(loaded.edits.len(), 2);
+  }
+
+  #[test]
+  fn test_brushes_apply_but_are_not_saved() {
+    let config = OctreeConfig {
+      world_bounds: Some(DAabb3::new(DVec3::ZERO, DVec3::splat(56.0))),
+      ..OctreeConfig::default()
+    };
+    let mut world = VoxelWorld::new(config, GroundPlaneSampler::new(14.3));
+    let node = OctreeNode::new(0, 0, 0, 0);
+    world.leaves.insert(node);
+    let (original, _) = world.sample_node_volume(&node);
+    let empty_save = world.save_edits();
+
+    let brush = SdfBrush::Sphere {
+      center: DVec3::new(14.0, 14.3, 14.0),
+      radius: 4.0,
+      op: EditOp::Remove,
+    };
+    assert_eq!(world.set_brushes(vec![brush]), HashSet::from([node]));
+    assert_ne!(world.sample_node_volume(&node).0, original);
+    assert_eq!(world.save_edits(), empty_save);
+    assert!(world.undo_edit().is_none());
+
+    // Clearing the brushes dirties the leaves they touched
+    world.remesh_dirty();
+    assert_eq!(world.set_brushes(Vec::new()), HashSet::from([node]));
+    assert_eq!(world.sample_node_volume(&node).0, original);
+  }
+
+  #[test]
+  fn test_undo_limited_to_recent_edits() {
+    let mut world = mixed_lod_world();
+    world.undo_limit = 2;
+    for i in 0..4 {
+      world.apply_edit(SdfBrush::Sphere {
+        center: DVec3::new(10.0 + i as f64, 10.0, 10.0),
+        radius: 2.0,
+        op: EditOp::Place,
+      });
+    }
+
+    assert!(world.undo_edit().is_some());
+    assert!(world.undo_edit().is_some());
+    assert!(world.undo_edit().is_none());
+    assert_eq!(world.edits.len(), 2);
+    assert_eq!(world.undone_edits.len(), 2);
+  }
+
   /// Integration test: Simulate the bug scenario where camera at far position
   /// causes infinite subdivision cascade at world boundaries.
   ///
@@ -371,6 +1669,7 @@ mod tests {
       min_lod: 0,
       max_lod: 31,
       lod_exponent: 1.0,
+      lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
       world_bounds: Some(world_bounds),
     };
 
@@ -474,6 +1773,7 @@ mod tests {
       min_lod: 0,
       max_lod: 31,
       lod_exponent: 1.0,
+      lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
       world_bounds: Some(world_bounds),
     };
 
@@ -557,6 +1857,7 @@ mod tests {
       min_lod: 0,
       max_lod: 31,
       lod_exponent: 1.0,
+      lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
       world_bounds: Some(world_bounds),
     };
 
@@ -31,23 +31,34 @@
 //! - Refinement timing (avg, min, max, last from 128-sample window)
 //! - Mesh generation timing (same)
 //! - Cumulative operation counts (refine calls, chunks meshed, transitions)
+//!
+//! `voxel_world_reset_metrics()` zeroes the cumulative counts, e.g. at the
+//! start of a level.
 
 use std::collections::HashMap;
-use std::ffi::CStr;
+use std::cell::{Cell, RefCell};
+use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 use std::sync::atomic::{AtomicI32, Ordering};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 use glam::DVec3;
 
 use voxel_plugin::{
     noise::FastNoise2Terrain,
-    octree::{DAabb3, OctreeConfig, OctreeNode, TransitionType},
-    pipeline::VolumeSampler,
-    process_transitions,
+    octree::{
+        DAabb3, OctreeConfig, OctreeNode, RefinementBudget, RefinementStats, TransitionGroup,
+        TransitionType,
+    },
+    pipeline::{
+        compute_neighbor_mask, node_mesh_config, process_invalidations, process_transitions_timed,
+        Epoch, ReadyChunk, VolumeSampler,
+    },
+    threading,
     types::Vertex,
     world::VoxelWorld,
-    MetaballsSampler, NormalMode,
+    EditOp, HeightmapSampler, MetaballsSampler, MinMaxAABB, NoiseHeightmapSampler, NormalMode, SdfBrush,
+    SdfConvention,
 };
 
 // =============================================================================
@@ -104,6 +115,87 @@ pub struct FfiWorldConfig {
     pub lod_exponent: f32,
     /// FastNoise2 encoded string (null = default terrain)
     pub noise_encoded: *const c_char,
+    /// Per-axis half-extents (X, Y, Z) for non-cubic worlds. Axes set to 0
+    /// fall back to `world_half_extent`.
+    pub world_half_extents: [f32; 3],
+    /// Worker threads for this world's meshing. 0 = share the global rayon
+    /// pool; otherwise the world gets a dedicated pool of this size.
+    pub thread_count: u32,
+    /// Multiplier on the terrain noise (0 = default 1.0). For noise
+    /// heightmap worlds, the surface displacement in world units (0 = 64).
+    pub noise_amplitude: f32,
+    /// Noise sampling frequency; smaller = larger features (0 = default 0.1)
+    pub noise_frequency: f32,
+}
+
+impl Default for FfiWorldConfig {
+    /// Small default-terrain world: unit voxels, LODs 0-4, ±100 bounds.
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            voxel_size: 1.0,
+            lod_min: 0,
+            lod_max: 4,
+            _pad: [0; 2],
+            world_half_extent: 100.0,
+            lod_exponent: 1.0,
+            noise_encoded: std::ptr::null(),
+            world_half_extents: [0.0; 3],
+            thread_count: 0,
+            noise_amplitude: 0.0,
+            noise_frequency: 0.0,
+        }
+    }
+}
+
+impl FfiWorldConfig {
+    /// Resolved per-axis half-extents of the world bounds.
+    fn half_extents(&self) -> DVec3 {
+        let axis = |extent: f32| {
+            let extent = if extent > 0.0 { extent } else { self.world_half_extent };
+            extent as f64
+        };
+        let [x, y, z] = self.world_half_extents;
+        DVec3::new(axis(x), axis(y), axis(z))
+    }
+
+    /// FastNoise2 terrain for this config, with the optional noise fields
+    /// applied.
+    fn terrain(&self, encoded: Option<&str>) -> FastNoise2Terrain {
+        let mut terrain = match encoded {
+            Some(enc) => FastNoise2Terrain::with_encoded(leak_encoded(enc), self.seed),
+            None => FastNoise2Terrain::new(self.seed),
+        };
+        if self.noise_amplitude > 0.0 {
+            terrain = terrain.with_amplitude(self.noise_amplitude as f64);
+        }
+        if self.noise_frequency > 0.0 {
+            terrain = terrain.with_frequency(self.noise_frequency);
+        }
+        terrain
+    }
+
+    /// FastNoise2 heightmap for this config, with the optional noise fields
+    /// applied.
+    fn noise_heightmap(&self, encoded: Option<&str>) -> NoiseHeightmapSampler {
+        let mut heightmap = match encoded {
+            Some(enc) => NoiseHeightmapSampler::with_encoded(leak_encoded(enc), self.seed),
+            None => NoiseHeightmapSampler::new(self.seed),
+        };
+        if self.noise_amplitude > 0.0 {
+            heightmap = heightmap.with_amplitude(self.noise_amplitude as f64);
+        }
+        if self.noise_frequency > 0.0 {
+            heightmap = heightmap.with_frequency(self.noise_frequency as f64);
+        }
+        heightmap
+    }
+}
+
+/// Leak an encoded node tree to get a 'static lifetime (acceptable for a
+/// long-lived world).
+fn leak_encoded(encoded: &str) -> &'static str {
+    Box::leak(encoded.to_string().into_boxed_str())
 }
 
 /// Chunk presentation data with pre-calculated world position and scale.
@@ -127,6 +219,20 @@ pub struct FfiChunkPresentation {
     pub indices_ptr: *const u16,
     /// Number of indices
     pub indices_count: u32,
+    /// Scale of the coarser chunk this one replaces (2 * scale for subdivide
+    /// children). Equals `scale` when morphing is disabled.
+    pub parent_scale: f64,
+    /// 1 if the chunk should geomorph from `parent_scale` geometry (subdivide
+    /// children), 0 otherwise
+    pub morph_enabled: u8,
+    pub _pad: [u8; 3],
+    /// Mesh bounds minimum in chunk-local voxel units (scale like vertices).
+    /// Greater than `max` for empty meshes.
+    pub min: [f32; 3],
+    /// Mesh bounds maximum in chunk-local voxel units
+    pub max: [f32; 3],
+    /// Number of triangles (`indices_count / 3`)
+    pub triangle_count: u32,
 }
 
 /// A transition group that must be applied atomically.
@@ -156,6 +262,9 @@ pub struct FfiPresentationBatch {
     pub _pad: u32,
 }
 
+/// Callback invoked with the world's ID when a presentation batch is ready.
+pub type FfiReadyCallback = extern "C" fn(world_id: i32);
+
 // SAFETY: FFI types contain raw pointers that are only valid within a single
 // FFI call context. The WorldState owns all backing data, so pointers remain
 // valid as long as the world is locked. These are not actually sent between
@@ -171,15 +280,51 @@ unsafe impl Sync for FfiPresentationBatch {}
 // Legacy FFI Types (backward compatibility)
 // =============================================================================
 
+/// Legacy FFI vertex for voxel_chunk_generate.
+///
+/// Frozen at the v0.2 `Vertex` layout (52 bytes), so v0.2 bindings keep
+/// their stride while `Vertex` gains attributes.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FfiLegacyVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub material_weights: [f32; 4],
+    pub cell_position: [i32; 3],
+}
+
+impl From<&Vertex> for FfiLegacyVertex {
+    fn from(vertex: &Vertex) -> Self {
+        Self {
+            position: vertex.position,
+            normal: vertex.normal,
+            material_weights: vertex.material_weights,
+            cell_position: vertex.cell_position,
+        }
+    }
+}
+
 /// Legacy FFI mesh result for voxel_chunk_generate.
 #[repr(C)]
 pub struct FfiMeshResult {
-    pub vertices_ptr: *const Vertex,
+    pub vertices_ptr: *const FfiLegacyVertex,
     pub vertices_count: u32,
     pub indices_ptr: *const u16,
     pub indices_count: u32,
 }
 
+/// Positions-only chunk mesh for physics proxies.
+///
+/// Positions are tightly packed XYZ f32 triples in chunk-local voxel units
+/// (scale by the chunk's voxel size, as for rendered chunks).
+#[repr(C)]
+pub struct FfiCollisionMesh {
+    pub positions_ptr: *const [f32; 3],
+    pub positions_count: u32,
+    pub indices_ptr: *const u16,
+    pub indices_count: u32,
+}
+
 /// Legacy world config for backward compatibility.
 #[repr(C)]
 pub struct FfiLegacyWorldConfig {
@@ -246,6 +391,72 @@ pub struct FfiMetricsSnapshot {
     pub total_subdivisions: u64,
     /// Total collapses this session.
     pub total_collapses: u64,
+
+    /// 1 if the world has been static long enough that timing stats are
+    /// stale (sample counts read 0), 0 otherwise.
+    pub idle: u8,
+    /// Padding for alignment.
+    pub _pad: [u8; 7],
+
+    // Pipeline stage breakdown (cumulative). Presample and meshing are
+    // summed over worker threads, so they can exceed wall time.
+    /// Total presample stage time in microseconds.
+    pub total_presample_us: u64,
+    /// Total surface-nets meshing time in microseconds.
+    pub total_meshing_us: u64,
+    /// Total composition stage time in microseconds.
+    pub total_composition_us: u64,
+    /// Total presentation stage time in microseconds.
+    pub total_presentation_us: u64,
+
+    // Presented geometry. Counts only chunks delivered through batches.
+    /// Vertices across currently presented chunks.
+    pub current_vertices: u64,
+    /// Indices across currently presented chunks.
+    pub current_indices: u64,
+    /// Highest `current_vertices` this session.
+    pub peak_vertices: u64,
+    /// Highest `current_indices` this session.
+    pub peak_indices: u64,
+    /// Highest approximate mesh memory of presented chunks, in bytes.
+    pub peak_mesh_memory_bytes: u64,
+
+    // 95th percentiles of the timing windows, kept out of `FfiTimingStats`
+    // so the fields above stay where v0.3 bindings read them.
+    /// 95th percentile of `refine` in microseconds.
+    pub refine_p95_us: u64,
+    /// 95th percentile of `mesh` in microseconds.
+    pub mesh_p95_us: u64,
+    /// 95th percentile of `sample` in microseconds.
+    pub sample_p95_us: u64,
+}
+
+/// Refinement budget exchanged over FFI. All limits use 0 = unlimited.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FfiRefineBudget {
+    /// Maximum subdivisions per update.
+    pub max_subdivisions: u32,
+    /// Maximum collapses per update.
+    pub max_collapses: u32,
+    /// Wall-clock limit for applying transitions, in milliseconds.
+    pub max_millis: u32,
+    /// Padding for alignment.
+    pub _pad: u32,
+}
+
+/// Terrain raycast hit returned by `voxel_world_raycast`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FfiRayHit {
+    /// Hit position in world space.
+    pub position: [f64; 3],
+    /// Unit surface normal at the hit, pointing out of the terrain.
+    pub normal: [f64; 3],
+    /// Distance along the ray from its origin.
+    pub distance: f64,
+    /// Leaf chunk containing the hit.
+    pub key: FfiChunkKey,
 }
 
 // =============================================================================
@@ -258,6 +469,10 @@ enum SamplerVariant {
     Terrain(FastNoise2Terrain),
     /// Legacy metaballs sampler
     Metaballs(MetaballsSampler),
+    /// Externally authored heightmap
+    Heightmap(HeightmapSampler),
+    /// FastNoise2 read as a 2D heightmap (no overhangs)
+    NoiseHeightmap(NoiseHeightmapSampler),
 }
 
 impl VolumeSampler for SamplerVariant {
@@ -271,6 +486,97 @@ impl VolumeSampler for SamplerVariant {
         match self {
             SamplerVariant::Terrain(t) => t.sample_volume(grid_offset, voxel_size, volume, materials),
             SamplerVariant::Metaballs(m) => m.sample_volume(grid_offset, voxel_size, volume, materials),
+            SamplerVariant::Heightmap(h) => h.sample_volume(grid_offset, voxel_size, volume, materials),
+            SamplerVariant::NoiseHeightmap(h) => h.sample_volume(grid_offset, voxel_size, volume, materials),
+        }
+    }
+
+    fn sample_volume_with_phase(
+        &self,
+        grid_offset: [i64; 3],
+        voxel_size: f64,
+        phase: [f64; 3],
+        volume: &mut [i8; voxel_plugin::SAMPLE_SIZE_CB],
+        materials: &mut [u8; voxel_plugin::SAMPLE_SIZE_CB],
+    ) {
+        match self {
+            SamplerVariant::Terrain(t) => {
+                t.sample_volume_with_phase(grid_offset, voxel_size, phase, volume, materials)
+            }
+            SamplerVariant::Metaballs(m) => {
+                m.sample_volume_with_phase(grid_offset, voxel_size, phase, volume, materials)
+            }
+            SamplerVariant::Heightmap(h) => {
+                h.sample_volume_with_phase(grid_offset, voxel_size, phase, volume, materials)
+            }
+            SamplerVariant::NoiseHeightmap(h) => {
+                h.sample_volume_with_phase(grid_offset, voxel_size, phase, volume, materials)
+            }
+        }
+    }
+
+    fn sample_normals(
+        &self,
+        grid_offset: [i64; 3],
+        voxel_size: f64,
+        phase: [f64; 3],
+        normals: &mut [[f32; 3]; voxel_plugin::SAMPLE_SIZE_CB],
+    ) -> bool {
+        match self {
+            SamplerVariant::Terrain(t) => {
+                t.sample_normals(grid_offset, voxel_size, phase, normals)
+            }
+            SamplerVariant::Metaballs(m) => {
+                m.sample_normals(grid_offset, voxel_size, phase, normals)
+            }
+            SamplerVariant::Heightmap(h) => {
+                h.sample_normals(grid_offset, voxel_size, phase, normals)
+            }
+            SamplerVariant::NoiseHeightmap(h) => {
+                h.sample_normals(grid_offset, voxel_size, phase, normals)
+            }
+        }
+    }
+
+    fn sample_volume16(
+        &self,
+        grid_offset: [i64; 3],
+        voxel_size: f64,
+        phase: [f64; 3],
+        volume: &mut [i16; voxel_plugin::SAMPLE_SIZE_CB],
+        materials: &mut [u8; voxel_plugin::SAMPLE_SIZE_CB],
+    ) {
+        match self {
+            SamplerVariant::Terrain(t) => {
+                t.sample_volume16(grid_offset, voxel_size, phase, volume, materials)
+            }
+            SamplerVariant::Metaballs(m) => {
+                m.sample_volume16(grid_offset, voxel_size, phase, volume, materials)
+            }
+            SamplerVariant::Heightmap(h) => {
+                h.sample_volume16(grid_offset, voxel_size, phase, volume, materials)
+            }
+            SamplerVariant::NoiseHeightmap(h) => {
+                h.sample_volume16(grid_offset, voxel_size, phase, volume, materials)
+            }
+        }
+    }
+
+    fn sample_point(&self, position: [f64; 3], voxel_size: f64) -> i8 {
+        match self {
+            SamplerVariant::Terrain(t) => t.sample_point(position, voxel_size),
+            SamplerVariant::Metaballs(m) => m.sample_point(position, voxel_size),
+            SamplerVariant::Heightmap(h) => h.sample_point(position, voxel_size),
+            SamplerVariant::NoiseHeightmap(h) => h.sample_point(position, voxel_size),
+        }
+    }
+
+    fn sdf_convention(&self) -> SdfConvention {
+        match self {
+            SamplerVariant::Terrain(t) => t.sdf_convention(),
+            SamplerVariant::Metaballs(m) => m.sdf_convention(),
+            SamplerVariant::Heightmap(h) => h.sdf_convention(),
+            SamplerVariant::NoiseHeightmap(h) => h.sdf_convention(),
         }
     }
 }
@@ -280,6 +586,8 @@ impl Clone for SamplerVariant {
         match self {
             SamplerVariant::Terrain(t) => SamplerVariant::Terrain(t.clone()),
             SamplerVariant::Metaballs(m) => SamplerVariant::Metaballs(m.clone()),
+            SamplerVariant::Heightmap(h) => SamplerVariant::Heightmap(h.clone()),
+            SamplerVariant::NoiseHeightmap(h) => SamplerVariant::NoiseHeightmap(h.clone()),
         }
     }
 }
@@ -288,13 +596,77 @@ impl Clone for SamplerVariant {
 // World State - Phase 2
 // =============================================================================
 
+/// Chunk mesh buffers, shared between the chunk cache and presented groups.
+struct ChunkBuffers {
+    vertices: Vec<Vertex>,
+    indices: Vec<u16>,
+    /// Bounds of `vertices`. Unlike `MeshOutput::bounds`, these ignore LOD
+    /// seam displacement, which the shipped vertices don't carry either.
+    bounds: MinMaxAABB,
+}
+
+/// Cached buffers for a node, valid while `epoch` matches the world's data epoch.
+struct CachedChunk {
+    epoch: Epoch,
+    /// `mesh_hash` of `buffers`, so a hit doesn't compare whole buffers
+    hash: u64,
+    buffers: Arc<ChunkBuffers>,
+}
+
+/// Hash of a chunk mesh's vertex and index bytes.
+fn mesh_hash(vertices: &[Vertex], indices: &[u16]) -> u64 {
+    use std::hash::Hasher;
+
+    // SAFETY: Vertex is #[repr(C)] with only 4-byte fields, so it has no
+    // padding and every byte is initialized
+    let vertex_bytes = unsafe {
+        std::slice::from_raw_parts(
+            vertices.as_ptr() as *const u8,
+            std::mem::size_of_val(vertices),
+        )
+    };
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    hasher.write(vertex_bytes);
+    for &index in indices {
+        hasher.write_u16(index);
+    }
+    hasher.finish()
+}
+
+/// Cached chunks beyond this count are trimmed to the current leaves.
+const MAX_CACHED_CHUNKS: usize = 1024;
+
 /// Retained chunk mesh data for pointer validity across FFI boundary.
 struct RetainedChunk {
     key: FfiChunkKey,
     world_pos: DVec3,
     scale: f64,
-    vertices: Vec<Vertex>,
-    indices: Vec<u16>,
+    parent_scale: f64,
+    morph_enabled: bool,
+    buffers: Arc<ChunkBuffers>,
+}
+
+impl RetainedChunk {
+    /// FFI view of this chunk; pointers borrow `self.buffers`.
+    fn to_ffi(&self) -> FfiChunkPresentation {
+        FfiChunkPresentation {
+            key: self.key,
+            world_pos_x: self.world_pos.x,
+            world_pos_y: self.world_pos.y,
+            world_pos_z: self.world_pos.z,
+            scale: self.scale,
+            parent_scale: self.parent_scale,
+            vertices_ptr: self.buffers.vertices.as_ptr(),
+            vertices_count: self.buffers.vertices.len() as u32,
+            indices_ptr: self.buffers.indices.as_ptr(),
+            indices_count: self.buffers.indices.len() as u32,
+            morph_enabled: self.morph_enabled as u8,
+            _pad: [0; 3],
+            min: self.buffers.bounds.min,
+            max: self.buffers.bounds.max,
+            triangle_count: (self.buffers.indices.len() / 3) as u32,
+        }
+    }
 }
 
 /// Retained transition group data for pointer validity across FFI boundary.
@@ -322,27 +694,54 @@ struct WorldState {
     ffi_groups: Vec<FfiTransitionGroup>,
     /// Whether this is a new world needing initial population
     needs_initial_population: bool,
-    /// Legacy: last generated mesh (for voxel_chunk_generate compatibility)
-    last_mesh: Option<voxel_plugin::MeshOutput>,
+    /// Legacy: last generated mesh (vertices, indices) from voxel_chunk_generate
+    last_mesh: Option<(Vec<FfiLegacyVertex>, Vec<u16>)>,
+    /// Last collision mesh (positions, indices) from voxel_chunk_generate_collision
+    last_collision: Option<(Vec<[f32; 3]>, Vec<u16>)>,
+    /// Bumped whenever the sampler's data changes, invalidating cached chunks
+    data_epoch: Epoch,
+    /// Buffers of previously presented chunks, reused when a node is
+    /// re-presented with an unchanged mesh
+    chunk_cache: HashMap<OctreeNode, CachedChunk>,
+    /// Statistics from the most recent refinement
+    last_refine_stats: RefinementStats,
+    /// Dedicated meshing pool (None = global rayon pool). Its threads exit
+    /// when the world is dropped by `voxel_world_destroy`.
+    pool: Option<rayon::ThreadPool>,
+    /// Notified after `update` produces a batch; cleared on destroy
+    ready_callback: Option<FfiReadyCallback>,
+}
+
+/// Run `f` on `pool`, or on the global rayon pool if there is none.
+fn in_pool<R: Send>(pool: Option<&rayon::ThreadPool>, f: impl FnOnce() -> R + Send) -> R {
+    match pool {
+        Some(pool) => pool.install(f),
+        None => f(),
+    }
 }
 
 impl WorldState {
     /// Create a new world with FastNoise2 terrain.
-    fn new_terrain(seed: i32, voxel_size: f64, lod_min: i32, lod_max: i32, world_half_extent: f64, lod_exponent: f64, encoded: Option<&str>) -> Self {
-        let sampler = match encoded {
-            Some(enc) => {
-                // Leak the string to get 'static lifetime (acceptable for long-lived world)
-                let static_str: &'static str = Box::leak(enc.to_string().into_boxed_str());
-                SamplerVariant::Terrain(FastNoise2Terrain::with_encoded(static_str, seed))
-            }
-            None => SamplerVariant::Terrain(FastNoise2Terrain::new(seed)),
-        };
+    fn new_terrain(terrain: FastNoise2Terrain, voxel_size: f64, lod_min: i32, lod_max: i32, world_half_extents: DVec3, lod_exponent: f64) -> Self {
+        Self::new_bounded(SamplerVariant::Terrain(terrain), voxel_size, lod_min, lod_max, world_half_extents, lod_exponent)
+    }
 
-        // Create world bounds from half-extent (centered at origin)
-        let world_bounds = DAabb3::from_center_half_extents(
-            DVec3::ZERO,
-            DVec3::splat(world_half_extent),
-        );
+    /// Create a new world driven by an external heightmap.
+    fn new_heightmap(heightmap: HeightmapSampler, voxel_size: f64, lod_min: i32, lod_max: i32, world_half_extents: DVec3, lod_exponent: f64) -> Self {
+        Self::new_bounded(
+            SamplerVariant::Heightmap(heightmap),
+            voxel_size,
+            lod_min,
+            lod_max,
+            world_half_extents,
+            lod_exponent,
+        )
+    }
+
+    /// Create a world with bounds of ±half_extents around the origin.
+    fn new_bounded(sampler: SamplerVariant, voxel_size: f64, lod_min: i32, lod_max: i32, world_half_extents: DVec3, lod_exponent: f64) -> Self {
+        // Create world bounds from half-extents (centered at origin)
+        let world_bounds = DAabb3::from_center_half_extents(DVec3::ZERO, world_half_extents);
 
         let config = OctreeConfig {
             voxel_size,
@@ -350,6 +749,7 @@ impl WorldState {
             min_lod: lod_min,
             max_lod: lod_max,
             lod_exponent,
+            lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
             world_bounds: Some(world_bounds),
         };
 
@@ -359,7 +759,26 @@ impl WorldState {
             ffi_groups: Vec::new(),
             needs_initial_population: true,
             last_mesh: None,
+            last_collision: None,
+            data_epoch: Epoch::new(),
+            chunk_cache: HashMap::new(),
+            last_refine_stats: RefinementStats::default(),
+            pool: None,
+            ready_callback: None,
+        }
+    }
+
+    /// Give the world a dedicated pool of `thread_count` meshing threads
+    /// (capped at `MAX_WORKERS`); 0 keeps the global rayon pool.
+    fn with_thread_count(mut self, thread_count: u32) -> Result<Self, rayon::ThreadPoolBuildError> {
+        if thread_count > 0 {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads((thread_count as usize).min(threading::MAX_WORKERS))
+                .thread_name(|i| format!("voxel-world-{}", i))
+                .build()?;
+            self.pool = Some(pool);
         }
+        Ok(self)
     }
 
     /// Create a new world with legacy metaballs sampler.
@@ -374,6 +793,7 @@ impl WorldState {
             min_lod: 0,
             max_lod: 8,
             lod_exponent: 1.0,
+            lod_hysteresis: OctreeConfig::DEFAULT_LOD_HYSTERESIS,
             world_bounds: None,
         };
 
@@ -383,6 +803,12 @@ impl WorldState {
             ffi_groups: Vec::new(),
             needs_initial_population: false, // Legacy mode uses manual chunk requests
             last_mesh: None,
+            last_collision: None,
+            data_epoch: Epoch::new(),
+            chunk_cache: HashMap::new(),
+            last_refine_stats: RefinementStats::default(),
+            pool: None,
+            ready_callback: None,
         }
     }
 
@@ -396,6 +822,50 @@ impl WorldState {
         self.world.config.get_voxel_size(node.lod)
     }
 
+    /// Get retained buffers for a ready chunk.
+    ///
+    /// Reuses the cached buffers (and their pointers) when the node was
+    /// presented before in the current data epoch with the same mesh hash;
+    /// otherwise takes ownership of the chunk's mesh data.
+    fn retain_buffers(&mut self, chunk: ReadyChunk) -> Arc<ChunkBuffers> {
+        let hash = mesh_hash(&chunk.output.vertices, &chunk.output.indices);
+        if let Some(cached) = self.chunk_cache.get(&chunk.node) {
+            if cached.epoch == self.data_epoch && cached.hash == hash {
+                return Arc::clone(&cached.buffers);
+            }
+        }
+
+        let mut bounds = MinMaxAABB::empty();
+        for vertex in &chunk.output.vertices {
+            bounds.encapsulate(vertex.position);
+        }
+        let buffers = Arc::new(ChunkBuffers {
+            vertices: chunk.output.vertices,
+            indices: chunk.output.indices,
+            bounds,
+        });
+        self.chunk_cache.insert(
+            chunk.node,
+            CachedChunk {
+                epoch: self.data_epoch,
+                hash,
+                buffers: Arc::clone(&buffers),
+            },
+        );
+        buffers
+    }
+
+    /// Drop stale cache entries, and non-leaf entries once over capacity.
+    fn trim_chunk_cache(&mut self) {
+        let epoch = self.data_epoch;
+        self.chunk_cache.retain(|_, cached| cached.epoch == epoch);
+
+        if self.chunk_cache.len() > MAX_CACHED_CHUNKS {
+            let leaves = self.world.leaves.as_set();
+            self.chunk_cache.retain(|node, _| leaves.contains(node));
+        }
+    }
+
     /// Create initial leaves based on world bounds and suggested LOD.
     fn populate_initial_leaves(&mut self) {
         let initial_lod = self.world.config.suggest_initial_lod();
@@ -404,6 +874,84 @@ impl WorldState {
         self.needs_initial_population = false;
     }
 
+    /// Apply an edit brush, bumping the data epoch if it touched any leaf.
+    ///
+    /// Brushes entirely outside the world bounds are ignored. Returns the
+    /// number of leaves marked dirty.
+    fn apply_edit(&mut self, brush: SdfBrush) -> usize {
+        if let Some(bounds) = &self.world.config.world_bounds {
+            if !bounds.overlaps(&brush.aabb()) {
+                return 0;
+            }
+        }
+
+        let dirty = self.world.apply_edit(brush);
+        self.data_epoch.increment();
+        dirty.len()
+    }
+
+    /// Remesh leaves dirtied by edits and queue one group per leaf.
+    ///
+    /// Leaves just added by `transition_groups` were meshed with the edits
+    /// already; dirty nodes that are no longer leaves are dropped.
+    fn push_edited_leaves(&mut self, transition_groups: &[TransitionGroup]) {
+        let dirty = std::mem::take(&mut self.world.dirty);
+        self.world.dirty_regions.clear();
+        let nodes: Vec<OctreeNode> = dirty
+            .into_iter()
+            .filter(|node| self.world.leaves.contains(node))
+            .filter(|node| !transition_groups.iter().any(|g| g.nodes_to_add.contains(node)))
+            .collect();
+        if nodes.is_empty() {
+            return;
+        }
+
+        let world = &self.world;
+        let ready_chunks = in_pool(self.pool.as_ref(), || {
+            process_invalidations(
+                world.id,
+                &nodes,
+                &world.edited_sampler(),
+                world.leaves.as_set(),
+                &world.config,
+            )
+        });
+
+        #[cfg(feature = "metrics")]
+        self.world.metrics.record_chunks_meshed(ready_chunks.len());
+
+        let mut ready_by_node: HashMap<OctreeNode, ReadyChunk> = ready_chunks
+            .into_iter()
+            .map(|c| (c.node, c))
+            .collect();
+
+        // Replace each edited leaf in place: despawn, then respawn if it
+        // still has a surface
+        for node in nodes {
+            let key: FfiChunkKey = node.into();
+            let mut to_add = Vec::new();
+            if let Some(chunk) = ready_by_node.remove(&node) {
+                let scale = self.node_scale(&node);
+                to_add.push(RetainedChunk {
+                    key,
+                    world_pos: self.node_world_pos(&node),
+                    scale,
+                    parent_scale: scale,
+                    morph_enabled: false,
+                    buffers: self.retain_buffers(chunk),
+                });
+            }
+
+            self.pending_groups.push(RetainedTransitionGroup {
+                group_key: key,
+                is_collapse: false,
+                to_remove: vec![key],
+                to_add,
+                presentations: Vec::new(),
+            });
+        }
+    }
+
     /// Update world state with new viewer position.
     /// Uses synchronous refinement with parallel mesh generation via voxel_plugin core.
     /// Returns true if events are ready.
@@ -424,22 +972,26 @@ impl WorldState {
 
         // Run synchronous refinement - computes transitions and updates leaves
         let output = self.world.refine(viewer_pos);
+        self.last_refine_stats = output.stats;
 
-        // Check if there are any transitions
-        if output.transition_groups.is_empty() {
+        // Check if there are any transitions or edits to remesh
+        if output.transition_groups.is_empty() && self.world.dirty.is_empty() {
             return false;
         }
 
-        // Use centralized process_transitions for parallel mesh generation
+        // Use centralized process_transitions_timed for parallel mesh generation
         // This handles: presample, surface crossing check, neighbor mask, meshing
-        // Note: process_transitions has its own tracing instrumentation via voxel_plugin
-        let ready_chunks = process_transitions(
-            self.world.id,
-            &output.transition_groups,
-            &self.world.sampler,
-            self.world.leaves.as_set(),
-            &self.world.config,
-        );
+        // Note: it has its own tracing instrumentation via voxel_plugin
+        let world = &self.world;
+        let (ready_chunks, _stats) = in_pool(self.pool.as_ref(), || {
+            process_transitions_timed(
+                world.id,
+                &output.transition_groups,
+                &world.edited_sampler(),
+                world.leaves.as_set(),
+                &world.config,
+            )
+        });
 
         // Record mesh timing metrics (aggregate from ready_chunks)
         #[cfg(feature = "metrics")]
@@ -449,10 +1001,11 @@ impl WorldState {
                 self.world.metrics.record_mesh_timing(total_mesh_us);
             }
             self.world.metrics.record_chunks_meshed(ready_chunks.len());
+            self.world.metrics.record_stage_timings(&_stats);
         }
 
         // Build hashmap for O(1) lookup when grouping
-        let ready_by_node: HashMap<OctreeNode, voxel_plugin::pipeline::ReadyChunk> = ready_chunks
+        let mut ready_by_node: HashMap<OctreeNode, ReadyChunk> = ready_chunks
             .into_iter()
             .map(|c| (c.node, c))
             .collect();
@@ -461,6 +1014,10 @@ impl WorldState {
         for group in &output.transition_groups {
             let is_collapse = matches!(group.transition_type, TransitionType::Merge);
 
+            // Subdivide children morph in from the parent (group key); merged
+            // parents have nothing coarser to morph from
+            let parent_scale = self.node_scale(&group.group_key);
+
             // Get to_remove keys
             let to_remove: Vec<FfiChunkKey> = group
                 .nodes_to_remove
@@ -468,23 +1025,22 @@ impl WorldState {
                 .map(|n| (*n).into())
                 .collect();
 
-            // Get ready chunks for this group
-            let to_add: Vec<RetainedChunk> = group
-                .nodes_to_add
-                .iter()
-                .filter_map(|node| ready_by_node.get(node))
-                .map(|chunk| {
-                    let world_pos = self.node_world_pos(&chunk.node);
-                    let scale = self.node_scale(&chunk.node);
-                    RetainedChunk {
-                        key: chunk.node.into(),
-                        world_pos,
-                        scale,
-                        vertices: chunk.output.vertices.clone(),
-                        indices: chunk.output.indices.clone(),
-                    }
-                })
-                .collect();
+            // Get ready chunks for this group (reusing unchanged buffers)
+            let mut to_add: Vec<RetainedChunk> = Vec::with_capacity(group.nodes_to_add.len());
+            for node in &group.nodes_to_add {
+                let Some(chunk) = ready_by_node.remove(node) else {
+                    continue;
+                };
+                let scale = self.node_scale(node);
+                to_add.push(RetainedChunk {
+                    key: (*node).into(),
+                    world_pos: self.node_world_pos(node),
+                    scale,
+                    parent_scale: if is_collapse { scale } else { parent_scale },
+                    morph_enabled: !is_collapse,
+                    buffers: self.retain_buffers(chunk),
+                });
+            }
 
             self.pending_groups.push(RetainedTransitionGroup {
                 group_key: group.group_key.into(),
@@ -495,23 +1051,30 @@ impl WorldState {
             });
         }
 
-        // Build FFI presentations (must be done after all groups are stored for pointer stability)
-        for group in &mut self.pending_groups {
-            group.presentations = group
-                .to_add
+        self.push_edited_leaves(&output.transition_groups);
+
+        // Track the geometry C# is about to present
+        #[cfg(feature = "metrics")]
+        self.world.metrics.record_presented(
+            self.pending_groups
                 .iter()
-                .map(|chunk| FfiChunkPresentation {
-                    key: chunk.key,
-                    world_pos_x: chunk.world_pos.x,
-                    world_pos_y: chunk.world_pos.y,
-                    world_pos_z: chunk.world_pos.z,
-                    scale: chunk.scale,
-                    vertices_ptr: chunk.vertices.as_ptr(),
-                    vertices_count: chunk.vertices.len() as u32,
-                    indices_ptr: chunk.indices.as_ptr(),
-                    indices_count: chunk.indices.len() as u32,
+                .flat_map(|group| group.to_remove.iter().map(|&key| key.into())),
+            self.pending_groups.iter().flat_map(|group| {
+                group.to_add.iter().map(|chunk| {
+                    (
+                        chunk.key.into(),
+                        chunk.buffers.vertices.len() as u64,
+                        chunk.buffers.indices.len() as u64,
+                    )
                 })
-                .collect();
+            }),
+        );
+
+        self.trim_chunk_cache();
+
+        // Build FFI presentations (must be done after all groups are stored for pointer stability)
+        for group in &mut self.pending_groups {
+            group.presentations = group.to_add.iter().map(RetainedChunk::to_ffi).collect();
         }
 
         // Build FFI groups (points into pending_groups data)
@@ -556,14 +1119,103 @@ fn ensure_worlds_initialized(guard: &mut Option<HashMap<i32, WorldState>>) {
     }
 }
 
+// =============================================================================
+// Error Reporting
+// =============================================================================
+
+thread_local! {
+    /// Message for the last failed FFI call on this thread (empty if none).
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+    /// World whose ready callback is running on this thread (0 if none).
+    static IN_READY_CALLBACK: Cell<i32> = const { Cell::new(0) };
+}
+
+/// Clear this thread's last error. Called at the start of every FFI call.
+fn clear_last_error() {
+    LAST_ERROR.with(|error| *error.borrow_mut() = CString::default());
+}
+
+/// Record `message` as this thread's last error and return `code`.
+fn fail(code: i32, message: impl Into<String>) -> i32 {
+    let message = message.into().replace('\0', " ");
+    let message = format!("{} (code {})", message, code);
+    LAST_ERROR.with(|error| *error.borrow_mut() = CString::new(message).unwrap_or_default());
+    code
+}
+
+/// -2: the world registry mutex was poisoned by a panic on another thread.
+fn lock_failed() -> i32 {
+    fail(-2, "failed to acquire world lock (poisoned by an earlier panic)")
+}
+
+/// -3: no world with this id exists.
+fn world_not_found(world_id: i32) -> i32 {
+    fail(-3, format!("world {} not found", world_id))
+}
+
+/// -5: the call would free the batch the world's ready callback is reading.
+fn reentered_from_callback(world_id: i32) -> i32 {
+    fail(
+        -5,
+        format!("world {} cannot be updated or destroyed from its ready callback", world_id),
+    )
+}
+
+/// Whether `world_id`'s ready callback is running on this thread.
+fn in_ready_callback(world_id: i32) -> bool {
+    IN_READY_CALLBACK.with(|current| current.get() == world_id)
+}
+
+/// Run `world_id`'s ready callback, marking it as running for the duration.
+fn run_ready_callback(world_id: i32, callback: FfiReadyCallback) {
+    let previous = IN_READY_CALLBACK.with(|current| current.replace(world_id));
+    callback(world_id);
+    IN_READY_CALLBACK.with(|current| current.set(previous));
+}
+
 // =============================================================================
 // FFI Functions - Phase 3
 // =============================================================================
 
+/// Describe the last failed `voxel_*` call on the calling thread.
+///
+/// Set whenever a call returns a negative code (or when
+/// `voxel_world_surface_height` returns NaN because the lookup failed) and
+/// cleared at the start of every other call, so read it right after the
+/// failing call.
+///
+/// # Returns
+/// - Null-terminated UTF-8 message, empty if the last call succeeded. The
+///   buffer is owned by Rust and valid until the next `voxel_*` call on this
+///   thread.
+#[no_mangle]
+pub extern "C" fn voxel_last_error() -> *const c_char {
+    LAST_ERROR.with(|error| error.borrow().as_ptr())
+}
+
 /// Returns the library version as a packed u32: 0xMMmmpp (major.minor.patch).
+///
+/// The minor version changes whenever a `#[repr(C)]` layout shared with C#
+/// changes, so bindings should refuse to run against a version they were
+/// not written for:
+/// - v0.4.0: `Vertex` gains `tangent`, `uv`, `ao`, `material_weights_hi` and
+///   `curvature` (stride 52 -> 100 bytes)
+/// - v0.5.0: `FfiTimingStats::p95_us` and `FfiChunkPresentation`'s morph
+///   fields move after the v0.3 fields instead of between them
+/// - v0.6.0: `Vertex` drops `material_weights_hi`, which moves to
+///   `MeshOutput::material_weights_hi` (stride 100 -> 84 bytes)
+/// - v0.7.0: `FfiChunkPresentation` gains `min`, `max` and `triangle_count`
+///   after the morph fields
+/// - v0.8.0: `FfiWorldConfig` gains `noise_amplitude` and `noise_frequency`
+///   after `thread_count`
+/// - v0.9.0: `FfiTimingStats` drops `p95_us` (back to 40 bytes), which moves
+///   to `FfiMetricsSnapshot::{refine,mesh,sample}_p95_us` after
+///   `peak_mesh_memory_bytes`; the legacy `voxel_chunk_generate` returns
+///   `FfiLegacyVertex` (the 52-byte v0.2 layout) instead of `Vertex`
 #[no_mangle]
 pub extern "C" fn voxel_version() -> u32 {
-    0x000300 // v0.3.0
+    clear_last_error();
+    0x000900 // v0.9.0
 }
 
 /// Create a new voxel world with v0.3 configuration.
@@ -573,12 +1225,15 @@ pub extern "C" fn voxel_version() -> u32 {
 ///
 /// # Returns
 /// - Positive world_id on success
-/// - -1 if config is null
+/// - -1 if config is null or the worker pool for `config.thread_count`
+///   could not be created
 /// - -2 if failed to acquire lock
 #[no_mangle]
 pub unsafe extern "C" fn voxel_world_create_v3(config: *const FfiWorldConfig) -> i32 {
+    clear_last_error();
+
     if config.is_null() {
-        return -1;
+        return fail(-1, "config is null");
     }
 
     let cfg = &*config;
@@ -594,17 +1249,20 @@ pub unsafe extern "C" fn voxel_world_create_v3(config: *const FfiWorldConfig) ->
     };
 
     let state = WorldState::new_terrain(
-        cfg.seed,
+        cfg.terrain(encoded),
         cfg.voxel_size as f64,
         cfg.lod_min as i32,
         cfg.lod_max as i32,
-        cfg.world_half_extent as f64,
+        cfg.half_extents(),
         cfg.lod_exponent as f64,
-        encoded,
     );
+    let state = match state.with_thread_count(cfg.thread_count) {
+        Ok(state) => state,
+        Err(e) => return fail(-1, format!("failed to build worker pool: {}", e)),
+    };
 
     let Ok(mut guard) = WORLDS.lock() else {
-        return -2;
+        return lock_failed();
     };
 
     ensure_worlds_initialized(&mut guard);
@@ -616,91 +1274,280 @@ pub unsafe extern "C" fn voxel_world_create_v3(config: *const FfiWorldConfig) ->
     world_id
 }
 
-/// Update viewer position and poll for presentation events.
+/// Create a new voxel world whose terrain is driven by an external heightmap.
+///
+/// The heights are copied and owned by the world, so the caller may free
+/// `heights` after this returns. `config.seed` and `config.noise_encoded`
+/// are ignored.
 ///
 /// # Safety
-/// - `out` must point to a valid FfiPresentationBatch struct.
+/// - `config` must point to a valid FfiWorldConfig struct.
+/// - `heights` must point to `width * height` f32 values, row-major with X
+///   fastest (`heights[z * width + x]`), in world units.
 ///
 /// # Parameters
-/// - `world_id`: ID returned by voxel_world_create_v3
-/// - `viewer_x/y/z`: Viewer position in world space
-/// - `out`: Output struct to receive presentation batch
+/// - `width`/`height`: Heightmap dimensions in texels (X and Z)
+/// - `world_scale`: World units between adjacent texels. The heightmap is
+///   centered on the world origin.
 ///
 /// # Returns
-/// - 0 = no events ready (pipeline still working)
-/// - 1 = events ready (check out.groups)
-/// - -1 if out is null
+/// - Positive world_id on success
+/// - -1 if config or heights is null, the heightmap is empty, or the worker
+///   pool could not be created
 /// - -2 if failed to acquire lock
-/// - -3 if world_id not found
 #[no_mangle]
-pub unsafe extern "C" fn voxel_world_update(
-    world_id: i32,
-    viewer_x: f64,
-    viewer_y: f64,
-    viewer_z: f64,
-    out: *mut FfiPresentationBatch,
+pub unsafe extern "C" fn voxel_world_create_heightmap(
+    config: *const FfiWorldConfig,
+    heights: *const f32,
+    width: u32,
+    height: u32,
+    world_scale: f32,
 ) -> i32 {
-    if out.is_null() {
-        return -1;
+    clear_last_error();
+
+    if config.is_null() || heights.is_null() || width == 0 || height == 0 {
+        return fail(-1, "config or heights is null, or the heightmap is empty");
     }
 
-    let Ok(mut guard) = WORLDS.lock() else {
-        return -2;
-    };
+    let cfg = &*config;
+    let (width, depth) = (width as usize, height as usize);
+    let data = std::slice::from_raw_parts(heights, width * depth).to_vec();
+    let heightmap = HeightmapSampler::new(data, width, depth, world_scale as f64);
 
-    let Some(ref mut worlds) = *guard else {
-        return -3;
+    let state = WorldState::new_heightmap(
+        heightmap,
+        cfg.voxel_size as f64,
+        cfg.lod_min as i32,
+        cfg.lod_max as i32,
+        cfg.half_extents(),
+        cfg.lod_exponent as f64,
+    );
+    let state = match state.with_thread_count(cfg.thread_count) {
+        Ok(state) => state,
+        Err(e) => return fail(-1, format!("failed to build worker pool: {}", e)),
     };
 
-    let Some(state) = worlds.get_mut(&world_id) else {
-        return -3;
+    let Ok(mut guard) = WORLDS.lock() else {
+        return lock_failed();
     };
 
-    let viewer_pos = DVec3::new(viewer_x, viewer_y, viewer_z);
-    let has_events = state.update(viewer_pos);
+    ensure_worlds_initialized(&mut guard);
+    let worlds = guard.as_mut().unwrap();
 
-    if has_events {
-        // Build output batch with pointers into state's retained FFI groups
-        (*out) = FfiPresentationBatch {
-            groups: if state.ffi_groups.is_empty() {
-                std::ptr::null()
-            } else {
-                state.ffi_groups.as_ptr()
-            },
-            groups_count: state.ffi_groups.len() as u32,
-            _pad: 0,
-        };
-        1
+    let world_id = NEXT_WORLD_ID.fetch_add(1, Ordering::SeqCst);
+    worlds.insert(world_id, state);
+
+    world_id
+}
+
+/// Create a new voxel world whose terrain is FastNoise2 read as a 2D
+/// heightmap: no overhangs, at a fraction of the sampling cost of the 3D
+/// terrain from `voxel_world_create_v3`.
+///
+/// `config.seed`, `config.noise_encoded`, `config.noise_amplitude` and
+/// `config.noise_frequency` configure the noise.
+///
+/// # Safety
+/// - `config` must point to a valid FfiWorldConfig struct.
+///
+/// # Returns
+/// - Positive world_id on success
+/// - -1 if config is null or the worker pool could not be created
+/// - -2 if failed to acquire lock
+#[no_mangle]
+pub unsafe extern "C" fn voxel_world_create_noise_heightmap(config: *const FfiWorldConfig) -> i32 {
+    clear_last_error();
+
+    if config.is_null() {
+        return fail(-1, "config is null");
+    }
+
+    let cfg = &*config;
+
+    let encoded = if cfg.noise_encoded.is_null() {
+        None
     } else {
+        match CStr::from_ptr(cfg.noise_encoded).to_str() {
+            Ok(s) if !s.is_empty() => Some(s),
+            _ => None,
+        }
+    };
+
+    let state = WorldState::new_bounded(
+        SamplerVariant::NoiseHeightmap(cfg.noise_heightmap(encoded)),
+        cfg.voxel_size as f64,
+        cfg.lod_min as i32,
+        cfg.lod_max as i32,
+        cfg.half_extents(),
+        cfg.lod_exponent as f64,
+    );
+    let state = match state.with_thread_count(cfg.thread_count) {
+        Ok(state) => state,
+        Err(e) => return fail(-1, format!("failed to build worker pool: {}", e)),
+    };
+
+    let Ok(mut guard) = WORLDS.lock() else {
+        return lock_failed();
+    };
+
+    ensure_worlds_initialized(&mut guard);
+    let worlds = guard.as_mut().unwrap();
+
+    let world_id = NEXT_WORLD_ID.fetch_add(1, Ordering::SeqCst);
+    worlds.insert(world_id, state);
+
+    world_id
+}
+
+/// Update viewer position and poll for presentation events.
+///
+/// When a batch is ready, the callback registered with
+/// `voxel_world_set_ready_callback` fires before this returns. The batch in
+/// `out` stays valid while it runs: updating or destroying this world from
+/// inside the callback is rejected with -5.
+///
+/// # Safety
+/// - `out` must point to a valid FfiPresentationBatch struct.
+///
+/// # Parameters
+/// - `world_id`: ID returned by voxel_world_create_v3
+/// - `viewer_x/y/z`: Viewer position in world space
+/// - `out`: Output struct to receive presentation batch
+///
+/// # Returns
+/// - 0 = no events ready (pipeline still working)
+/// - 1 = events ready (check out.groups)
+/// - -1 if out is null
+/// - -2 if failed to acquire lock
+/// - -3 if world_id not found
+/// - -5 if called from this world's ready callback
+#[no_mangle]
+pub unsafe extern "C" fn voxel_world_update(
+    world_id: i32,
+    viewer_x: f64,
+    viewer_y: f64,
+    viewer_z: f64,
+    out: *mut FfiPresentationBatch,
+) -> i32 {
+    clear_last_error();
+
+    if out.is_null() {
+        return fail(-1, "out is null");
+    }
+    if in_ready_callback(world_id) {
+        return reentered_from_callback(world_id);
+    }
+
+    let Ok(mut guard) = WORLDS.lock() else {
+        return lock_failed();
+    };
+
+    let Some(ref mut worlds) = *guard else {
+        return world_not_found(world_id);
+    };
+
+    let Some(state) = worlds.get_mut(&world_id) else {
+        return world_not_found(world_id);
+    };
+
+    let viewer_pos = DVec3::new(viewer_x, viewer_y, viewer_z);
+    let has_events = state.update(viewer_pos);
+
+    if !has_events {
         (*out) = FfiPresentationBatch {
             groups: std::ptr::null(),
             groups_count: 0,
             _pad: 0,
         };
-        0
+        return 0;
+    }
+
+    // Build output batch with pointers into state's retained FFI groups
+    (*out) = FfiPresentationBatch {
+        groups: if state.ffi_groups.is_empty() {
+            std::ptr::null()
+        } else {
+            state.ffi_groups.as_ptr()
+        },
+        groups_count: state.ffi_groups.len() as u32,
+        _pad: 0,
+    };
+
+    // Release the lock first so the callback may call back into the API.
+    // The batch buffers stay put: only update/destroy of this world touch
+    // them, and those are rejected while the callback runs.
+    let callback = state.ready_callback;
+    drop(guard);
+    if let Some(callback) = callback {
+        run_ready_callback(world_id, callback);
     }
+    1
 }
 
-/// Destroy a voxel world and free its resources.
+/// Register a callback fired when `voxel_world_update` has a batch ready.
+///
+/// The callback runs on the thread that called `voxel_world_update`, after
+/// the world registry lock is released, so it may call back into this API:
+/// edits, queries and other worlds are fine. `voxel_world_update` and
+/// `voxel_world_destroy` on the same world return -5 from inside the
+/// callback, since they would free the batch it is being handed. Pass null
+/// to clear it; destroying the world also clears it.
+///
+/// # Returns
+/// - 0 on success
+/// - -2 if failed to acquire lock
+/// - -3 if world_id not found
+#[no_mangle]
+pub extern "C" fn voxel_world_set_ready_callback(
+    world_id: i32,
+    callback: Option<FfiReadyCallback>,
+) -> i32 {
+    clear_last_error();
+
+    let Ok(mut guard) = WORLDS.lock() else {
+        return lock_failed();
+    };
+
+    let Some(ref mut worlds) = *guard else {
+        return world_not_found(world_id);
+    };
+
+    let Some(state) = worlds.get_mut(&world_id) else {
+        return world_not_found(world_id);
+    };
+
+    state.ready_callback = callback;
+    0
+}
+
+/// Destroy a voxel world and free its resources, including its dedicated
+/// worker pool and ready callback if it has them.
 ///
 /// # Returns
 /// - 0 on success
 /// - -2 if failed to acquire lock
 /// - -3 if world_id not found
+/// - -5 if called from this world's ready callback
 #[no_mangle]
 pub extern "C" fn voxel_world_destroy(world_id: i32) -> i32 {
+    clear_last_error();
+
+    if in_ready_callback(world_id) {
+        return reentered_from_callback(world_id);
+    }
+
     let Ok(mut guard) = WORLDS.lock() else {
-        return -2;
+        return lock_failed();
     };
 
     let Some(ref mut worlds) = *guard else {
-        return -3;
+        return world_not_found(world_id);
     };
 
     if worlds.remove(&world_id).is_some() {
         0
     } else {
-        -3
+        world_not_found(world_id)
     }
 }
 
@@ -727,28 +1574,30 @@ pub unsafe extern "C" fn voxel_world_get_metrics(
     world_id: i32,
     out: *mut FfiMetricsSnapshot,
 ) -> i32 {
+    clear_last_error();
+
     #[cfg(not(feature = "metrics"))]
     {
         let _ = (world_id, out);
-        return -4; // Metrics not enabled
+        return fail(-4, "metrics feature not enabled");
     }
 
     #[cfg(feature = "metrics")]
     {
         if out.is_null() {
-            return -1;
+            return fail(-1, "out is null");
         }
 
         let Ok(guard) = WORLDS.lock() else {
-            return -2;
+            return lock_failed();
         };
 
         let Some(ref worlds) = *guard else {
-            return -3;
+            return world_not_found(world_id);
         };
 
         let Some(state) = worlds.get(&world_id) else {
-            return -3;
+            return world_not_found(world_id);
         };
 
         // Get snapshot from world metrics
@@ -787,129 +1636,632 @@ pub unsafe extern "C" fn voxel_world_get_metrics(
             last_collapses: snapshot.last_collapses,
             total_subdivisions: snapshot.total_subdivisions,
             total_collapses: snapshot.total_collapses,
+            idle: snapshot.idle as u8,
+            _pad: [0; 7],
+            total_presample_us: snapshot.total_presample_us,
+            total_meshing_us: snapshot.total_meshing_us,
+            total_composition_us: snapshot.total_composition_us,
+            total_presentation_us: snapshot.total_presentation_us,
+            current_vertices: snapshot.current_vertices,
+            current_indices: snapshot.current_indices,
+            peak_vertices: snapshot.peak_vertices,
+            peak_indices: snapshot.peak_indices,
+            peak_mesh_memory_bytes: snapshot.peak_mesh_memory_bytes,
+            refine_p95_us: snapshot.refine.p95_us,
+            mesh_p95_us: snapshot.mesh.p95_us,
+            sample_p95_us: snapshot.sample.p95_us,
         };
 
         0
     }
 }
 
-// =============================================================================
-// Legacy FFI Functions (backward compatibility with v0.2)
-// =============================================================================
-
-/// Create a new voxel world with legacy metaballs sampler.
-/// Maintained for backward compatibility with existing Unity code.
+/// Zero a world's cumulative metrics counters and clear its timing windows.
 ///
-/// # Safety
-/// - `config` must point to a valid FfiLegacyWorldConfig struct.
+/// World state (leaves, meshes, edits) is untouched; the next
+/// `voxel_world_update` starts counting again from zero.
+///
+/// # Returns
+/// - 0 on success
+/// - -2 if failed to acquire lock
+/// - -3 if world_id not found
+/// - -4 if metrics feature not enabled (compile-time)
 #[no_mangle]
-pub unsafe extern "C" fn voxel_world_create(config: *const FfiLegacyWorldConfig) -> i32 {
-    if config.is_null() {
-        return -1;
+pub extern "C" fn voxel_world_reset_metrics(world_id: i32) -> i32 {
+    clear_last_error();
+
+    #[cfg(not(feature = "metrics"))]
+    {
+        let _ = world_id;
+        return fail(-4, "metrics feature not enabled");
     }
 
-    let cfg = &*config;
+    #[cfg(feature = "metrics")]
+    {
+        let Ok(mut guard) = WORLDS.lock() else {
+            return lock_failed();
+        };
 
-    let state = WorldState::new_metaballs(
-        cfg.seed,
-        cfg.voxel_size,
-        cfg.metaball_count as usize,
-        cfg.metaball_extent as f64,
-    );
+        let Some(ref mut worlds) = *guard else {
+            return world_not_found(world_id);
+        };
+
+        let Some(state) = worlds.get_mut(&world_id) else {
+            return world_not_found(world_id);
+        };
+
+        state.world.metrics.reset_counters();
+        0
+    }
+}
+
+/// Set the refinement budget for a world at runtime.
+///
+/// Takes effect on the next `voxel_world_update`. Lower it when frame time
+/// spikes. Neighbor enforcement settings are left unchanged.
+///
+/// # Parameters
+/// - `world_id`: ID returned by voxel_world_create_v3
+/// - `max_subdivisions`: Subdivisions per update (0 = unlimited)
+/// - `max_collapses`: Collapses per update (0 = unlimited)
+/// - `max_millis`: Time spent applying transitions per update (0 = unlimited)
+///
+/// # Returns
+/// - 0 on success
+/// - -2 if failed to acquire lock
+/// - -3 if world_id not found
+#[no_mangle]
+pub extern "C" fn voxel_world_set_refine_budget(
+    world_id: i32,
+    max_subdivisions: u32,
+    max_collapses: u32,
+    max_millis: u32,
+) -> i32 {
+    clear_last_error();
 
     let Ok(mut guard) = WORLDS.lock() else {
-        return -2;
+        return lock_failed();
     };
 
-    ensure_worlds_initialized(&mut guard);
-    let worlds = guard.as_mut().unwrap();
+    let Some(ref mut worlds) = *guard else {
+        return world_not_found(world_id);
+    };
 
-    let world_id = NEXT_WORLD_ID.fetch_add(1, Ordering::SeqCst);
-    worlds.insert(world_id, state);
+    let Some(state) = worlds.get_mut(&world_id) else {
+        return world_not_found(world_id);
+    };
 
-    world_id
+    let budget = RefinementBudget {
+        max_subdivisions: max_subdivisions as usize,
+        max_collapses: max_collapses as usize,
+        max_millis,
+        ..state.world.budget
+    };
+    state.world.set_budget(budget);
+
+    0
 }
 
-/// Generate mesh for a specific chunk (legacy API).
-/// For backward compatibility with v0.2 - uses synchronous mesh generation.
+/// Get the current refinement budget for a world.
+///
+/// Limits above `u32::MAX` (e.g. `RefinementBudget::UNLIMITED`) are reported
+/// as 0 (unlimited).
 ///
 /// # Safety
-/// - `out` must point to a valid FfiMeshResult struct.
+/// - `out` must point to a valid FfiRefineBudget struct.
+///
+/// # Returns
+/// - 0 on success
+/// - -1 if out is null
+/// - -2 if failed to acquire lock
+/// - -3 if world_id not found
 #[no_mangle]
-pub unsafe extern "C" fn voxel_chunk_generate(
+pub unsafe extern "C" fn voxel_world_get_refine_budget(
     world_id: i32,
-    grid_x: i32,
-    grid_y: i32,
-    grid_z: i32,
-    lod: i32,
-    out: *mut FfiMeshResult,
+    out: *mut FfiRefineBudget,
 ) -> i32 {
+    clear_last_error();
+
     if out.is_null() {
-        return -1;
+        return fail(-1, "out is null");
     }
 
-    let Ok(mut guard) = WORLDS.lock() else {
-        return -2;
+    let Ok(guard) = WORLDS.lock() else {
+        return lock_failed();
     };
 
-    let Some(ref mut worlds) = *guard else {
-        return -3;
+    let Some(ref worlds) = *guard else {
+        return world_not_found(world_id);
     };
 
-    let Some(state) = worlds.get_mut(&world_id) else {
-        return -3;
+    let Some(state) = worlds.get(&world_id) else {
+        return world_not_found(world_id);
     };
 
-    // Synchronous mesh generation for legacy API
-    let node = OctreeNode::new(grid_x, grid_y, grid_z, lod);
-    let sampled = voxel_plugin::pipeline::sample_volume_for_node(&node, &state.world.sampler, &state.world.config);
+    let to_ffi = |limit: usize| u32::try_from(limit).unwrap_or(0);
+    let budget = state.world.budget;
+    (*out) = FfiRefineBudget {
+        max_subdivisions: to_ffi(budget.max_subdivisions),
+        max_collapses: to_ffi(budget.max_collapses),
+        max_millis: budget.max_millis,
+        _pad: 0,
+    };
 
-    let voxel_size = state.world.config.get_voxel_size(lod);
+    0
+}
 
-    // Generate mesh
-    let config = voxel_plugin::MeshConfig {
-        voxel_size: voxel_size as f32,
-        neighbor_mask: 0,
-        normal_mode: NormalMode::InterpolatedGradient,
-        use_microsplat_encoding: false,
-    };
+/// Sample the terrain surface height at a world XZ position.
+///
+/// Marches the sampler down the column from the top of the world bounds and
+/// returns the height of the highest solid voxel's surface, interpolated to
+/// within a voxel. Useful for spawn points and navigation.
+///
+/// # Returns
+/// - Surface height in world units
+/// - NaN if the column has no solid voxel, the lock failed, or world_id was
+///   not found
+#[no_mangle]
+pub extern "C" fn voxel_world_surface_height(world_id: i32, x: f64, z: f64) -> f64 {
+    clear_last_error();
 
-    let output = voxel_plugin::surface_nets::generate(&sampled.volume, &sampled.materials, &config);
+    let Ok(guard) = WORLDS.lock() else {
+        lock_failed();
+        return f64::NAN;
+    };
 
-    (*out) = FfiMeshResult {
-        vertices_ptr: output.vertices.as_ptr(),
-        vertices_count: output.vertices.len() as u32,
-        indices_ptr: output.indices.as_ptr(),
-        indices_count: output.indices.len() as u32,
+    let Some(ref worlds) = *guard else {
+        world_not_found(world_id);
+        return f64::NAN;
     };
 
-    state.last_mesh = Some(output);
+    let Some(state) = worlds.get(&world_id) else {
+        world_not_found(world_id);
+        return f64::NAN;
+    };
 
-    0
+    state.world.surface_height(x, z).unwrap_or(f64::NAN)
 }
 
-// =============================================================================
-// Tests
-// =============================================================================
-
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Raycast against the terrain SDF, without needing a mesh or collider.
+///
+/// Marches the ray through the current leaf chunks, reading single SDF points
+/// at each leaf's resolution, and reports the first air-to-solid crossing.
+/// Useful for digging and placement targeting before a chunk's mesh exists.
+/// A ray starting inside terrain hits at its origin. The march runs on a
+/// snapshot of the world, after the registry lock is released.
+///
+/// # Safety
+/// - `origin` and `dir` must each point to 3 f64 values (x, y, z).
+/// - `out_hit` must point to a valid FfiRayHit struct.
+///
+/// # Parameters
+/// - `world_id`: ID returned by voxel_world_create_v3
+/// - `origin`: Ray origin in world space
+/// - `dir`: Ray direction (need not be normalized)
+/// - `max_dist`: Maximum distance to march
+/// - `out_hit`: Receives the hit; untouched on a miss
+///
+/// # Returns
+/// - 0 on hit
+/// - 1 on miss (including a zero direction)
+/// - -1 if origin, dir or out_hit is null
+/// - -2 if failed to acquire lock
+/// - -3 if world_id not found
+#[no_mangle]
+pub unsafe extern "C" fn voxel_world_raycast(
+    world_id: i32,
+    origin: *const f64,
+    dir: *const f64,
+    max_dist: f64,
+    out_hit: *mut FfiRayHit,
+) -> i32 {
+    clear_last_error();
 
-    #[test]
-    fn test_version() {
-        assert_eq!(voxel_version(), 0x000300);
+    if origin.is_null() || dir.is_null() || out_hit.is_null() {
+        return fail(-1, "origin, dir or out_hit is null");
     }
 
-    #[test]
-    fn test_ffi_chunk_key_conversion() {
-        let node = OctreeNode::new(1, 2, 3, 4);
-        let key: FfiChunkKey = node.into();
+    let origin = DVec3::from_slice(std::slice::from_raw_parts(origin, 3));
+    let dir = DVec3::from_slice(std::slice::from_raw_parts(dir, 3));
 
-        assert_eq!(key.grid_x, 1);
-        assert_eq!(key.grid_y, 2);
-        assert_eq!(key.grid_z, 3);
-        assert_eq!(key.lod, 4);
+    let Ok(guard) = WORLDS.lock() else {
+        return lock_failed();
+    };
+
+    let Some(ref worlds) = *guard else {
+        return world_not_found(world_id);
+    };
+
+    let Some(state) = worlds.get(&world_id) else {
+        return world_not_found(world_id);
+    };
+
+    // March on a snapshot so other calls aren't blocked behind the ray
+    let job = state.world.begin_raycast();
+    drop(guard);
+
+    let Some(hit) = job.cast(origin, dir, max_dist) else {
+        return 1;
+    };
+
+    *out_hit = FfiRayHit {
+        position: hit.position.to_array(),
+        normal: hit.normal.to_array(),
+        distance: hit.distance,
+        key: hit.node.into(),
+    };
+
+    0
+}
+
+/// Edit the terrain with a sphere brush.
+///
+/// Affected leaves are marked dirty; the next `voxel_world_update` returns a
+/// transition group per dirty leaf that removes its old chunk and adds the
+/// remeshed one (if a surface remains). Edits persist, so chunks meshed later
+/// by refinement include them too.
+///
+/// # Safety
+/// - `center` must point to 3 f64 values (x, y, z).
+///
+/// # Parameters
+/// - `world_id`: ID returned by voxel_world_create_v3
+/// - `center`: Sphere center in world space
+/// - `radius`: Sphere radius in world units
+/// - `op`: 0 = remove (carve), 1 = place (fill), 2 = smooth
+///
+/// Spheres entirely outside the world bounds are ignored; ones straddling
+/// the bounds only affect leaves inside them.
+///
+/// # Returns
+/// - Number of leaves marked dirty (0 if the edit was ignored)
+/// - -1 if center is null, radius is not positive, or op is invalid
+/// - -2 if failed to acquire lock
+/// - -3 if world_id not found
+#[no_mangle]
+pub unsafe extern "C" fn voxel_world_edit(
+    world_id: i32,
+    center: *const f64,
+    radius: f64,
+    op: u8,
+) -> i32 {
+    clear_last_error();
+
+    let op = match op {
+        0 => EditOp::Remove,
+        1 => EditOp::Place,
+        2 => EditOp::Smooth,
+        _ => return fail(-1, format!("invalid edit op {} (expected 0, 1 or 2)", op)),
+    };
+    edit_sphere(world_id, center, radius, |_| op)
+}
+
+/// Fill a sphere with `material`, like `voxel_world_edit` with op 1 but
+/// painting the samples it turns solid.
+///
+/// # Safety
+/// - `center` must point to 3 f64 values (x, y, z).
+///
+/// # Parameters
+/// - `world_id`: ID returned by voxel_world_create_v3
+/// - `center`: Sphere center in world space
+/// - `radius`: Sphere radius in world units
+/// - `material`: Material ID, clamped to the world's material count
+///
+/// # Returns
+/// Same as `voxel_world_edit`.
+#[no_mangle]
+pub unsafe extern "C" fn voxel_world_place_material(
+    world_id: i32,
+    center: *const f64,
+    radius: f64,
+    material: u8,
+) -> i32 {
+    clear_last_error();
+
+    edit_sphere(world_id, center, radius, |state| {
+        EditOp::place(material, state.world.mesh_config.material_count)
+    })
+}
+
+/// Shared body of the sphere edit functions; `op` picks the edit for the
+/// target world.
+unsafe fn edit_sphere(
+    world_id: i32,
+    center: *const f64,
+    radius: f64,
+    op: impl FnOnce(&WorldState) -> EditOp,
+) -> i32 {
+    if center.is_null() {
+        return fail(-1, "center is null");
+    }
+    if !radius.is_finite() || radius <= 0.0 {
+        return fail(-1, format!("invalid radius {}", radius));
+    }
+    let center = DVec3::from_slice(std::slice::from_raw_parts(center, 3));
+
+    let Ok(mut guard) = WORLDS.lock() else {
+        return lock_failed();
+    };
+
+    let Some(ref mut worlds) = *guard else {
+        return world_not_found(world_id);
+    };
+
+    let Some(state) = worlds.get_mut(&world_id) else {
+        return world_not_found(world_id);
+    };
+
+    let op = op(state);
+    state.apply_edit(SdfBrush::Sphere { center, radius, op }) as i32
+}
+
+// =============================================================================
+// Legacy FFI Functions (backward compatibility with v0.2)
+// =============================================================================
+
+/// Create a new voxel world with legacy metaballs sampler.
+/// Maintained for backward compatibility with existing Unity code.
+///
+/// # Safety
+/// - `config` must point to a valid FfiLegacyWorldConfig struct.
+#[no_mangle]
+pub unsafe extern "C" fn voxel_world_create(config: *const FfiLegacyWorldConfig) -> i32 {
+    clear_last_error();
+
+    if config.is_null() {
+        return fail(-1, "config is null");
+    }
+
+    let cfg = &*config;
+
+    let state = WorldState::new_metaballs(
+        cfg.seed,
+        cfg.voxel_size,
+        cfg.metaball_count as usize,
+        cfg.metaball_extent as f64,
+    );
+
+    let Ok(mut guard) = WORLDS.lock() else {
+        return lock_failed();
+    };
+
+    ensure_worlds_initialized(&mut guard);
+    let worlds = guard.as_mut().unwrap();
+
+    let world_id = NEXT_WORLD_ID.fetch_add(1, Ordering::SeqCst);
+    worlds.insert(world_id, state);
+
+    world_id
+}
+
+/// Generate mesh for a specific chunk (legacy API).
+/// For backward compatibility with v0.2 - uses synchronous mesh generation.
+///
+/// # Safety
+/// - `out` must point to a valid FfiMeshResult struct.
+#[no_mangle]
+pub unsafe extern "C" fn voxel_chunk_generate(
+    world_id: i32,
+    grid_x: i32,
+    grid_y: i32,
+    grid_z: i32,
+    lod: i32,
+    out: *mut FfiMeshResult,
+) -> i32 {
+    clear_last_error();
+
+    if out.is_null() {
+        return fail(-1, "out is null");
+    }
+
+    let Ok(mut guard) = WORLDS.lock() else {
+        return lock_failed();
+    };
+
+    let Some(ref mut worlds) = *guard else {
+        return world_not_found(world_id);
+    };
+
+    let Some(state) = worlds.get_mut(&world_id) else {
+        return world_not_found(world_id);
+    };
+
+    // Synchronous mesh generation for legacy API
+    let node = OctreeNode::new(grid_x, grid_y, grid_z, lod);
+    let sampled = voxel_plugin::pipeline::sample_volume_for_node(&node, &state.world.sampler, &state.world.config);
+
+    let voxel_size = state.world.config.get_voxel_size(lod);
+
+    // Generate mesh
+    let config = voxel_plugin::MeshConfig {
+        voxel_size: voxel_size as f32,
+        neighbor_mask: 0,
+        normal_mode: NormalMode::InterpolatedGradient,
+        use_microsplat_encoding: false,
+        ..Default::default()
+    };
+
+    let output = voxel_plugin::surface_nets::generate(&sampled.volume, &sampled.materials, &config);
+
+    let vertices: Vec<FfiLegacyVertex> =
+        output.vertices.iter().map(FfiLegacyVertex::from).collect();
+    let (vertices, indices) = state.last_mesh.insert((vertices, output.indices));
+
+    (*out) = FfiMeshResult {
+        vertices_ptr: vertices.as_ptr(),
+        vertices_count: vertices.len() as u32,
+        indices_ptr: indices.as_ptr(),
+        indices_count: indices.len() as u32,
+    };
+
+    0
+}
+
+/// Generate a positions-only collision mesh for a chunk.
+///
+/// Skips material weights, normals, tangents, UVs and AO, so it is cheaper
+/// than the render path and returns a smaller payload. Edits are included,
+/// and the chunk gets the render path's neighbor mask, so its seams match
+/// the rendered chunk at LOD transitions.
+/// The buffers stay valid until the next call for this world or until the
+/// world is destroyed.
+///
+/// # Safety
+/// - `out` must point to a valid FfiCollisionMesh struct.
+///
+/// # Returns
+/// - 0 on success (counts are 0 if the chunk has no surface)
+/// - -1 if out is null
+/// - -2 if failed to acquire lock
+/// - -3 if world_id not found
+#[no_mangle]
+pub unsafe extern "C" fn voxel_chunk_generate_collision(
+    world_id: i32,
+    key: FfiChunkKey,
+    out: *mut FfiCollisionMesh,
+) -> i32 {
+    clear_last_error();
+
+    if out.is_null() {
+        return fail(-1, "out is null");
+    }
+
+    let Ok(mut guard) = WORLDS.lock() else {
+        return lock_failed();
+    };
+
+    let Some(ref mut worlds) = *guard else {
+        return world_not_found(world_id);
+    };
+
+    let Some(state) = worlds.get_mut(&world_id) else {
+        return world_not_found(world_id);
+    };
+
+    let node: OctreeNode = key.into();
+    let (volume, materials) = state.world.sample_node_volume(&node);
+
+    let neighbor_mask =
+        compute_neighbor_mask(&node, state.world.leaves.as_set(), &state.world.config);
+    let config = node_mesh_config(
+        &voxel_plugin::MeshConfig::default(),
+        &node,
+        neighbor_mask,
+        &state.world.config,
+    )
+    .with_positions_only(true);
+    let output = voxel_plugin::surface_nets::generate(&volume, &materials, &config);
+
+    let positions: Vec<[f32; 3]> = output.vertices.iter().map(|v| v.position).collect();
+    let (positions, indices) = state.last_collision.insert((positions, output.indices));
+
+    (*out) = FfiCollisionMesh {
+        positions_ptr: positions.as_ptr(),
+        positions_count: positions.len() as u32,
+        indices_ptr: indices.as_ptr(),
+        indices_count: indices.len() as u32,
+    };
+
+    0
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version() {
+        assert_eq!(voxel_version(), 0x000900);
+    }
+
+    /// Vertex buffers are handed to C# as raw memory, so any layout change
+    /// must come with a `voxel_version` bump and an update here.
+    #[test]
+    fn test_vertex_layout() {
+        use std::mem::{align_of, offset_of, size_of};
+
+        assert_eq!(size_of::<Vertex>(), 84);
+        assert_eq!(align_of::<Vertex>(), 4);
+        assert_eq!(offset_of!(Vertex, position), 0);
+        assert_eq!(offset_of!(Vertex, normal), 12);
+        assert_eq!(offset_of!(Vertex, material_weights), 24);
+        assert_eq!(offset_of!(Vertex, cell_position), 40);
+        assert_eq!(offset_of!(Vertex, tangent), 52);
+        assert_eq!(offset_of!(Vertex, uv), 68);
+        assert_eq!(offset_of!(Vertex, ao), 76);
+        assert_eq!(offset_of!(Vertex, curvature), 80);
+    }
+
+    /// The legacy v0.2 mesh call keeps its 52-byte vertex stride.
+    #[test]
+    fn test_legacy_vertex_layout() {
+        use std::mem::{align_of, offset_of, size_of};
+
+        assert_eq!(size_of::<FfiLegacyVertex>(), 52);
+        assert_eq!(align_of::<FfiLegacyVertex>(), 4);
+        assert_eq!(offset_of!(FfiLegacyVertex, position), 0);
+        assert_eq!(offset_of!(FfiLegacyVertex, normal), 12);
+        assert_eq!(offset_of!(FfiLegacyVertex, material_weights), 24);
+        assert_eq!(offset_of!(FfiLegacyVertex, cell_position), 40);
+    }
+
+    /// Fields added after v0.3 must come after the v0.3 fields so older
+    /// bindings still read the prefix correctly.
+    #[test]
+    fn test_appended_ffi_fields_keep_v3_prefix() {
+        use std::mem::{offset_of, size_of};
+
+        assert_eq!(offset_of!(FfiTimingStats, sample_count), 32);
+        assert_eq!(size_of::<FfiTimingStats>(), 40);
+
+        // v0.3 snapshot prefix: three timing stats, then the counters
+        assert_eq!(offset_of!(FfiMetricsSnapshot, refine), 0);
+        assert_eq!(offset_of!(FfiMetricsSnapshot, mesh), 40);
+        assert_eq!(offset_of!(FfiMetricsSnapshot, sample), 80);
+        assert_eq!(offset_of!(FfiMetricsSnapshot, total_refine_calls), 120);
+        assert_eq!(offset_of!(FfiMetricsSnapshot, total_chunks_meshed), 128);
+        assert_eq!(offset_of!(FfiMetricsSnapshot, total_transitions), 136);
+        let peak_memory = offset_of!(FfiMetricsSnapshot, peak_mesh_memory_bytes);
+        assert_eq!(
+            offset_of!(FfiMetricsSnapshot, refine_p95_us),
+            peak_memory + 8
+        );
+        assert_eq!(
+            offset_of!(FfiMetricsSnapshot, mesh_p95_us),
+            peak_memory + 16
+        );
+        assert_eq!(
+            offset_of!(FfiMetricsSnapshot, sample_p95_us),
+            peak_memory + 24
+        );
+        assert_eq!(size_of::<FfiMetricsSnapshot>(), peak_memory + 32);
+
+        let indices_count = offset_of!(FfiChunkPresentation, indices_count);
+        assert!(offset_of!(FfiChunkPresentation, parent_scale) > indices_count);
+        assert!(offset_of!(FfiChunkPresentation, morph_enabled) > indices_count);
+        let morph_enabled = offset_of!(FfiChunkPresentation, morph_enabled);
+        assert!(offset_of!(FfiChunkPresentation, min) > morph_enabled);
+        assert!(offset_of!(FfiChunkPresentation, triangle_count) > morph_enabled);
+
+        let thread_count = offset_of!(FfiWorldConfig, thread_count);
+        assert!(offset_of!(FfiWorldConfig, noise_amplitude) > thread_count);
+        assert!(offset_of!(FfiWorldConfig, noise_frequency) > thread_count);
+    }
+
+    #[test]
+    fn test_ffi_chunk_key_conversion() {
+        let node = OctreeNode::new(1, 2, 3, 4);
+        let key: FfiChunkKey = node.into();
+
+        assert_eq!(key.grid_x, 1);
+        assert_eq!(key.grid_y, 2);
+        assert_eq!(key.grid_z, 3);
+        assert_eq!(key.lod, 4);
 
         let back: OctreeNode = key.into();
         assert_eq!(back, node);
@@ -950,13 +2302,9 @@ mod tests {
     fn test_v3_world_create() {
         let config = FfiWorldConfig {
             seed: 42,
-            voxel_size: 1.0,
-            lod_min: 0,
             lod_max: 8,
-            _pad: [0; 2],
             world_half_extent: 500.0,
-            lod_exponent: 1.0,
-            noise_encoded: std::ptr::null(),
+            ..Default::default()
         };
 
         unsafe {
@@ -968,17 +2316,170 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_noise_heightmap_world_create() {
+        let config = FfiWorldConfig {
+            seed: 42,
+            noise_amplitude: 16.0,
+            ..Default::default()
+        };
+
+        unsafe {
+            let world_id = voxel_world_create_noise_heightmap(&config);
+            assert!(world_id > 0, "Expected positive world_id, got {}", world_id);
+
+            {
+                let guard = WORLDS.lock().unwrap();
+                let state = &guard.as_ref().unwrap()[&world_id];
+                let SamplerVariant::NoiseHeightmap(heightmap) = &state.world.sampler else {
+                    panic!("Expected a noise heightmap sampler");
+                };
+                assert_eq!(heightmap.amplitude, 16.0);
+                assert_eq!(heightmap.frequency, 0.005);
+            }
+
+            assert_eq!(voxel_world_destroy(world_id), 0);
+            assert_eq!(voxel_world_create_noise_heightmap(std::ptr::null()), -1);
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_reset_metrics_zeroes_totals() {
+        let config = FfiWorldConfig {
+            seed: 123,
+            ..Default::default()
+        };
+
+        unsafe {
+            let world_id = voxel_world_create_v3(&config);
+            assert!(world_id > 0);
+
+            let mut batch = FfiPresentationBatch {
+                groups: std::ptr::null(),
+                groups_count: 0,
+                _pad: 0,
+            };
+            for _ in 0..3 {
+                voxel_world_update(world_id, 0.0, 0.0, 0.0, &mut batch);
+            }
+
+            let mut metrics = FfiMetricsSnapshot::default();
+            assert_eq!(voxel_world_get_metrics(world_id, &mut metrics), 0);
+            assert_eq!(metrics.total_refine_calls, 3);
+            let leaves_before = WORLDS.lock().unwrap().as_ref().unwrap()[&world_id]
+                .world
+                .leaves
+                .len();
+
+            assert_eq!(voxel_world_reset_metrics(world_id), 0);
+            assert_eq!(voxel_world_get_metrics(world_id, &mut metrics), 0);
+            assert_eq!(metrics.total_refine_calls, 0);
+            assert_eq!(metrics.total_chunks_meshed, 0);
+            assert_eq!(metrics.total_transitions, 0);
+            assert_eq!(metrics.total_subdivisions, 0);
+            assert_eq!(metrics.refine.sample_count, 0);
+            let leaves_after = WORLDS.lock().unwrap().as_ref().unwrap()[&world_id]
+                .world
+                .leaves
+                .len();
+            assert_eq!(leaves_after, leaves_before, "World state must be untouched");
+
+            voxel_world_update(world_id, 0.0, 0.0, 0.0, &mut batch);
+            assert_eq!(voxel_world_get_metrics(world_id, &mut metrics), 0);
+            assert_eq!(metrics.total_refine_calls, 1);
+
+            assert_eq!(voxel_world_reset_metrics(-1), -3);
+            voxel_world_destroy(world_id);
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_metrics_track_presented_geometry() {
+        let config = FfiWorldConfig {
+            seed: 123,
+            ..Default::default()
+        };
+
+        unsafe {
+            let world_id = voxel_world_create_v3(&config);
+            assert!(world_id > 0);
+
+            // Mirror what C# would present, batch by batch
+            let mut presented: HashMap<OctreeNode, u64> = HashMap::new();
+            let mut batch = FfiPresentationBatch {
+                groups: std::ptr::null(),
+                groups_count: 0,
+                _pad: 0,
+            };
+            for viewer_x in [0.0, 60.0, -60.0, 0.0] {
+                if voxel_world_update(world_id, viewer_x, 0.0, 0.0, &mut batch) != 1 {
+                    continue;
+                }
+                let groups = std::slice::from_raw_parts(batch.groups, batch.groups_count as usize);
+                for group in groups {
+                    if group.to_remove_count > 0 {
+                        let count = group.to_remove_count as usize;
+                        for &key in std::slice::from_raw_parts(group.to_remove, count) {
+                            presented.remove(&key.into());
+                        }
+                    }
+                    if group.to_add_count > 0 {
+                        let count = group.to_add_count as usize;
+                        for chunk in std::slice::from_raw_parts(group.to_add, count) {
+                            presented.insert(chunk.key.into(), chunk.vertices_count as u64);
+                        }
+                    }
+                }
+            }
+
+            let mut metrics = FfiMetricsSnapshot::default();
+            assert_eq!(voxel_world_get_metrics(world_id, &mut metrics), 0);
+            assert!(metrics.current_vertices > 0);
+            assert_eq!(metrics.current_vertices, presented.values().sum::<u64>());
+            assert!(metrics.peak_vertices >= metrics.current_vertices);
+            assert!(
+                metrics.peak_mesh_memory_bytes
+                    >= metrics.current_vertices * std::mem::size_of::<Vertex>() as u64
+            );
+
+            voxel_world_destroy(world_id);
+        }
+    }
+
+    #[test]
+    fn test_v3_world_create_non_cubic_bounds() {
+        let config = FfiWorldConfig {
+            seed: 42,
+            lod_max: 8,
+            world_half_extent: 500.0,
+            world_half_extents: [400.0, 100.0, 0.0],
+            ..Default::default()
+        };
+        assert_eq!(config.half_extents(), DVec3::new(400.0, 100.0, 500.0));
+
+        unsafe {
+            let world_id = voxel_world_create_v3(&config);
+            assert!(world_id > 0);
+
+            {
+                let guard = WORLDS.lock().unwrap();
+                let state = &guard.as_ref().unwrap()[&world_id];
+                let bounds = state.world.config.world_bounds.unwrap();
+                assert_eq!(bounds.min, DVec3::new(-400.0, -100.0, -500.0));
+                assert_eq!(bounds.max, DVec3::new(400.0, 100.0, 500.0));
+            }
+
+            assert_eq!(voxel_world_destroy(world_id), 0);
+        }
+    }
+
     #[test]
     fn test_v3_world_update() {
         let config = FfiWorldConfig {
             seed: 123,
-            voxel_size: 1.0,
-            lod_min: 0,
-            lod_max: 4,
-            _pad: [0; 2],
-            world_half_extent: 100.0,
-            lod_exponent: 1.0,
-            noise_encoded: std::ptr::null(),
+            ..Default::default()
         };
 
         unsafe {
@@ -1006,4 +2507,618 @@ mod tests {
             voxel_world_destroy(world_id);
         }
     }
+
+    #[test]
+    fn test_world_with_dedicated_thread_pool() {
+        let config = FfiWorldConfig {
+            thread_count: 2,
+            ..Default::default()
+        };
+        let heights = vec![12.3f32; 16];
+
+        unsafe {
+            let world_id = voxel_world_create_heightmap(&config, heights.as_ptr(), 4, 4, 8.0);
+            assert!(world_id > 0);
+
+            {
+                let guard = WORLDS.lock().unwrap();
+                let state = &guard.as_ref().unwrap()[&world_id];
+                let pool = state.pool.as_ref().expect("world should own a pool");
+                assert_eq!(pool.current_num_threads(), 2);
+            }
+
+            let mut batch = FfiPresentationBatch {
+                groups: std::ptr::null(),
+                groups_count: 0,
+                _pad: 0,
+            };
+            assert_eq!(voxel_world_update(world_id, 0.0, 20.0, 0.0, &mut batch), 1);
+
+            let groups = std::slice::from_raw_parts(batch.groups, batch.groups_count as usize);
+            let added: u32 = groups.iter().map(|g| g.to_add_count).sum();
+            assert!(added > 0, "Meshing on the dedicated pool should produce chunks");
+
+            assert_eq!(voxel_world_destroy(world_id), 0);
+        }
+    }
+
+    static READY_CALLS: AtomicI32 = AtomicI32::new(0);
+    static READY_WORLD: AtomicI32 = AtomicI32::new(0);
+
+    extern "C" fn record_ready(world_id: i32) {
+        READY_CALLS.fetch_add(1, Ordering::SeqCst);
+        READY_WORLD.store(world_id, Ordering::SeqCst);
+        // Re-entering the API must not deadlock on the world registry
+        assert_eq!(voxel_world_set_ready_callback(world_id, Some(record_ready)), 0);
+    }
+
+    #[test]
+    fn test_ready_callback_fires_after_update_with_work() {
+        let config = FfiWorldConfig::default();
+        let heights = vec![12.3f32; 16];
+
+        unsafe {
+            let world_id = voxel_world_create_heightmap(&config, heights.as_ptr(), 4, 4, 8.0);
+            assert!(world_id > 0);
+            assert_eq!(voxel_world_set_ready_callback(world_id, Some(record_ready)), 0);
+
+            let mut batch = FfiPresentationBatch {
+                groups: std::ptr::null(),
+                groups_count: 0,
+                _pad: 0,
+            };
+            assert_eq!(voxel_world_update(world_id, 0.0, 20.0, 0.0, &mut batch), 1);
+            assert!(READY_CALLS.load(Ordering::SeqCst) >= 1);
+            assert_eq!(READY_WORLD.load(Ordering::SeqCst), world_id);
+
+            assert_eq!(voxel_world_destroy(world_id), 0);
+            assert_eq!(voxel_world_set_ready_callback(world_id, None), -3);
+        }
+    }
+
+    static REENTER_UPDATE: AtomicI32 = AtomicI32::new(0);
+    static REENTER_DESTROY: AtomicI32 = AtomicI32::new(0);
+
+    extern "C" fn reenter_ready(world_id: i32) {
+        let mut batch = FfiPresentationBatch {
+            groups: std::ptr::null(),
+            groups_count: 0,
+            _pad: 0,
+        };
+        let update = unsafe { voxel_world_update(world_id, 0.0, 20.0, 0.0, &mut batch) };
+        REENTER_UPDATE.store(update, Ordering::SeqCst);
+        REENTER_DESTROY.store(voxel_world_destroy(world_id), Ordering::SeqCst);
+    }
+
+    #[test]
+    fn test_ready_callback_cannot_free_its_batch() {
+        let config = FfiWorldConfig::default();
+        let heights = vec![12.3f32; 16];
+
+        unsafe {
+            let world_id = voxel_world_create_heightmap(&config, heights.as_ptr(), 4, 4, 8.0);
+            assert!(world_id > 0);
+            assert_eq!(voxel_world_set_ready_callback(world_id, Some(reenter_ready)), 0);
+
+            let mut batch = FfiPresentationBatch {
+                groups: std::ptr::null(),
+                groups_count: 0,
+                _pad: 0,
+            };
+            assert_eq!(voxel_world_update(world_id, 0.0, 20.0, 0.0, &mut batch), 1);
+            assert_eq!(REENTER_UPDATE.load(Ordering::SeqCst), -5);
+            assert_eq!(REENTER_DESTROY.load(Ordering::SeqCst), -5);
+
+            // The batch survived the callback
+            let groups = std::slice::from_raw_parts(batch.groups, batch.groups_count as usize);
+            assert!(groups.iter().map(|g| g.to_add_count).sum::<u32>() > 0);
+
+            // Outside the callback both work again
+            assert_eq!(voxel_world_set_ready_callback(world_id, None), 0);
+            assert!(voxel_world_update(world_id, 0.0, 20.0, 0.0, &mut batch) >= 0);
+            assert_eq!(voxel_world_destroy(world_id), 0);
+        }
+    }
+
+    #[test]
+    fn test_collision_mesh_matches_full_mesh_with_smaller_payload() {
+        let config = FfiWorldConfig::default();
+        let heights = vec![12.3f32; 16];
+
+        unsafe {
+            let world_id = voxel_world_create_heightmap(&config, heights.as_ptr(), 4, 4, 8.0);
+            assert!(world_id > 0);
+
+            let mut full = FfiMeshResult {
+                vertices_ptr: std::ptr::null(),
+                vertices_count: 0,
+                indices_ptr: std::ptr::null(),
+                indices_count: 0,
+            };
+            assert_eq!(voxel_chunk_generate(world_id, 0, 0, 0, 0, &mut full), 0);
+
+            let mut collision = FfiCollisionMesh {
+                positions_ptr: std::ptr::null(),
+                positions_count: 0,
+                indices_ptr: std::ptr::null(),
+                indices_count: 0,
+            };
+            let key: FfiChunkKey = OctreeNode::new(0, 0, 0, 0).into();
+            assert_eq!(voxel_chunk_generate_collision(world_id, key, &mut collision), 0);
+
+            assert!(full.vertices_count > 0);
+            assert_eq!(collision.positions_count, full.vertices_count);
+            assert_eq!(collision.indices_count, full.indices_count);
+
+            let full_bytes = full.vertices_count as usize * std::mem::size_of::<FfiLegacyVertex>();
+            let collision_bytes = collision.positions_count as usize * std::mem::size_of::<[f32; 3]>();
+            assert!(collision_bytes < full_bytes);
+
+            let vertices = std::slice::from_raw_parts(full.vertices_ptr, full.vertices_count as usize);
+            let positions =
+                std::slice::from_raw_parts(collision.positions_ptr, collision.positions_count as usize);
+            for (vertex, position) in vertices.iter().zip(positions) {
+                assert_eq!(vertex.position, *position);
+            }
+
+            assert_eq!(voxel_world_destroy(world_id), 0);
+        }
+    }
+
+    #[test]
+    fn test_collision_mesh_matches_render_mesh_at_lod_seam() {
+        let config = FfiWorldConfig::default();
+        // Sloped terrain, so seam vertices actually move when displaced
+        let heights: Vec<f32> = (0..256)
+            .map(|i| 8.0 + (i % 16) as f32 * 0.7 + (i / 16) as f32 * 0.4)
+            .collect();
+        let node = OctreeNode::new(1, 0, 0, 0);
+        let coarser = OctreeNode::new(1, 0, 0, 1);
+
+        unsafe {
+            let world_id = voxel_world_create_heightmap(&config, heights.as_ptr(), 16, 16, 8.0);
+            assert!(world_id > 0);
+
+            // Render path: mesh `node` next to a coarser +X neighbor
+            let rendered = {
+                let mut guard = WORLDS.lock().unwrap();
+                let state = guard.as_mut().unwrap().get_mut(&world_id).unwrap();
+                state.world.leaves = [node, coarser]
+                    .into_iter()
+                    .collect::<std::collections::HashSet<_>>()
+                    .into();
+                assert_ne!(
+                    compute_neighbor_mask(&node, state.world.leaves.as_set(), &state.world.config),
+                    0
+                );
+                process_invalidations(
+                    state.world.id,
+                    &[node],
+                    &state.world.edited_sampler(),
+                    state.world.leaves.as_set(),
+                    &state.world.config,
+                )
+                .remove(0)
+                .output
+            };
+
+            let mut collision = FfiCollisionMesh {
+                positions_ptr: std::ptr::null(),
+                positions_count: 0,
+                indices_ptr: std::ptr::null(),
+                indices_count: 0,
+            };
+            assert_eq!(
+                voxel_chunk_generate_collision(world_id, node.into(), &mut collision),
+                0
+            );
+
+            assert!(!rendered.is_empty());
+            let positions = std::slice::from_raw_parts(
+                collision.positions_ptr,
+                collision.positions_count as usize,
+            );
+            let indices =
+                std::slice::from_raw_parts(collision.indices_ptr, collision.indices_count as usize);
+            let rendered_positions: Vec<[f32; 3]> =
+                rendered.vertices.iter().map(|v| v.position).collect();
+            assert_eq!(positions, rendered_positions.as_slice());
+            assert_eq!(indices, rendered.indices.as_slice());
+
+            assert_eq!(voxel_world_destroy(world_id), 0);
+        }
+    }
+
+    #[test]
+    fn test_surface_height_matches_heightmap() {
+        let config = FfiWorldConfig::default();
+        let create = |height: f32| unsafe {
+            let heights = vec![height; 16];
+            voxel_world_create_heightmap(&config, heights.as_ptr(), 4, 4, 8.0)
+        };
+
+        let world_id = create(12.3);
+        assert!(world_id > 0);
+        for (x, z) in [(0.0, 0.0), (5.5, -3.25), (-40.0, 60.0)] {
+            let height = voxel_world_surface_height(world_id, x, z);
+            assert!(
+                (height - 12.3).abs() <= 1.0,
+                "Expected ~12.3 at ({}, {}), got {}",
+                x,
+                z,
+                height
+            );
+        }
+        voxel_world_destroy(world_id);
+
+        // Surface below the world bounds: nothing solid in the column
+        let world_id = create(-500.0);
+        assert!(voxel_world_surface_height(world_id, 0.0, 0.0).is_nan());
+        voxel_world_destroy(world_id);
+
+        assert!(voxel_world_surface_height(-1, 0.0, 0.0).is_nan());
+    }
+
+    #[test]
+    fn test_raycast_hits_flat_terrain() {
+        let config = FfiWorldConfig::default();
+        let heights = vec![12.3f32; 16];
+
+        unsafe {
+            let world_id = voxel_world_create_heightmap(&config, heights.as_ptr(), 4, 4, 8.0);
+            assert!(world_id > 0);
+
+            let mut batch = FfiPresentationBatch {
+                groups: std::ptr::null(),
+                groups_count: 0,
+                _pad: 0,
+            };
+            voxel_world_update(world_id, 0.0, 20.0, 0.0, &mut batch);
+
+            let down = [0.0, -1.0, 0.0];
+            let mut hit = FfiRayHit::default();
+            for origin in [[0.0, 40.0, 0.0], [5.5, 60.0, -3.25]] {
+                let status = voxel_world_raycast(world_id, origin.as_ptr(), down.as_ptr(), 200.0, &mut hit);
+                assert_eq!(status, 0, "Expected a hit from {:?}", origin);
+                assert!(
+                    (hit.position[1] - 12.3).abs() <= 1.0,
+                    "Expected hit near y=12.3, got {:?}",
+                    hit.position
+                );
+                assert!((hit.distance - (origin[1] - hit.position[1])).abs() < 1e-6);
+                assert!(hit.normal[1] > 0.9, "Expected an upward normal, got {:?}", hit.normal);
+            }
+
+            // Pointing away from the terrain, or too short to reach it
+            let up = [0.0, 1.0, 0.0];
+            let origin = [0.0, 40.0, 0.0];
+            assert_eq!(voxel_world_raycast(world_id, origin.as_ptr(), up.as_ptr(), 200.0, &mut hit), 1);
+            assert_eq!(voxel_world_raycast(world_id, origin.as_ptr(), down.as_ptr(), 10.0, &mut hit), 1);
+
+            assert_eq!(voxel_world_raycast(world_id, std::ptr::null(), down.as_ptr(), 10.0, &mut hit), -1);
+            assert_eq!(voxel_world_raycast(-1, origin.as_ptr(), down.as_ptr(), 10.0, &mut hit), -3);
+
+            voxel_world_destroy(world_id);
+        }
+    }
+
+    #[test]
+    fn test_edit_remeshes_edited_leaf_on_next_update() {
+        let config = FfiWorldConfig::default();
+        let heights = vec![12.3f32; 16];
+
+        unsafe {
+            let world_id = voxel_world_create_heightmap(&config, heights.as_ptr(), 4, 4, 8.0);
+            assert!(world_id > 0);
+
+            let mut batch = FfiPresentationBatch {
+                groups: std::ptr::null(),
+                groups_count: 0,
+                _pad: 0,
+            };
+            // Refine until the octree settles around the viewer
+            for _ in 0..32 {
+                if voxel_world_update(world_id, 0.0, 20.0, 0.0, &mut batch) != 1 {
+                    break;
+                }
+            }
+
+            let center = [3.0, 12.3, 3.0];
+            let edited: FfiChunkKey = {
+                let guard = WORLDS.lock().unwrap();
+                let state = guard.as_ref().unwrap().get(&world_id).unwrap();
+                state.world.leaf_at(DVec3::from_array(center)).unwrap().into()
+            };
+
+            assert!(voxel_world_edit(world_id, center.as_ptr(), 2.0, 0) > 0);
+
+            let status = voxel_world_update(world_id, 0.0, 20.0, 0.0, &mut batch);
+            assert_eq!(status, 1, "Edit should produce presentation events");
+
+            let groups = std::slice::from_raw_parts(batch.groups, batch.groups_count as usize);
+            let group = groups
+                .iter()
+                .find(|g| g.group_key == edited)
+                .expect("Expected a group for the edited leaf");
+            assert!(!group.to_remove.is_null() && !group.to_add.is_null());
+            let removed = std::slice::from_raw_parts(group.to_remove, group.to_remove_count as usize);
+            let added = std::slice::from_raw_parts(group.to_add, group.to_add_count as usize);
+            assert_eq!(removed, &[edited]);
+            assert_eq!(added.len(), 1);
+            assert_eq!(added[0].key, edited);
+
+            // Dirty leaves are consumed by the update
+            assert_eq!(voxel_world_update(world_id, 0.0, 20.0, 0.0, &mut batch), 0);
+
+            // Far outside the world bounds: ignored
+            let outside = [0.0, 500.0, 0.0];
+            assert_eq!(voxel_world_edit(world_id, outside.as_ptr(), 2.0, 1), 0);
+
+            assert_eq!(voxel_world_edit(world_id, center.as_ptr(), 2.0, 7), -1);
+            assert!(voxel_world_place_material(world_id, center.as_ptr(), 2.0, 2) > 0);
+            assert_eq!(
+                voxel_world_place_material(world_id, center.as_ptr(), -1.0, 2),
+                -1
+            );
+            assert_eq!(voxel_world_edit(world_id, center.as_ptr(), -1.0, 0), -1);
+            assert_eq!(voxel_world_edit(-1, center.as_ptr(), 2.0, 0), -3);
+
+            voxel_world_destroy(world_id);
+        }
+    }
+
+    #[test]
+    fn test_last_error_reports_missing_world() {
+        let mut batch = FfiPresentationBatch {
+            groups: std::ptr::null(),
+            groups_count: 0,
+            _pad: 0,
+        };
+        let last_error = || unsafe { CStr::from_ptr(voxel_last_error()) }.to_str().unwrap().to_owned();
+
+        let status = unsafe { voxel_world_update(987_654, 0.0, 0.0, 0.0, &mut batch) };
+        assert_eq!(status, -3);
+        let message = last_error();
+        assert!(message.contains("987654"), "Unexpected error message: {:?}", message);
+
+        // Null pointers get their own message
+        let status = unsafe { voxel_world_update(987_654, 0.0, 0.0, 0.0, std::ptr::null_mut()) };
+        assert_eq!(status, -1);
+        assert!(last_error().contains("null"));
+
+        // Any successful call clears it
+        voxel_version();
+        assert!(last_error().is_empty());
+    }
+
+    #[test]
+    fn test_unchanged_chunk_reuses_retained_buffers() {
+        let heightmap = HeightmapSampler::new(vec![0.0; 4], 2, 2, 1.0);
+        let mut state = WorldState::new_heightmap(heightmap, 1.0, 0, 4, DVec3::splat(100.0), 1.0);
+
+        let ready = |state: &WorldState| ReadyChunk {
+            world_id: state.world.id,
+            node: OctreeNode::new(0, 0, 0, 0),
+            output: voxel_plugin::MeshOutput {
+                vertices: vec![Vertex::default(); 3],
+                indices: vec![0, 1, 2],
+                ..Default::default()
+            },
+            hint: voxel_plugin::pipeline::PresentationHint::Immediate,
+            timing_us: 0,
+        };
+
+        // Frame 1: buffers taken from the ready chunk
+        let first = state.retain_buffers(ready(&state));
+        let vertices_ptr = first.vertices.as_ptr();
+        let indices_ptr = first.indices.as_ptr();
+        drop(first); // Pending groups are cleared at the start of each update
+
+        // Frame 2: same node, same mesh - same buffers
+        let second = state.retain_buffers(ready(&state));
+        assert_eq!(second.vertices.as_ptr(), vertices_ptr);
+        assert_eq!(second.indices.as_ptr(), indices_ptr);
+
+        // Changed mesh - fresh buffers
+        let mut changed = ready(&state);
+        changed.output.vertices[0].position = [1.0, 0.0, 0.0];
+        let third = state.retain_buffers(changed);
+        assert!(!Arc::ptr_eq(&second, &third));
+
+        // Data epoch advanced - cached buffers are stale
+        state.data_epoch.increment();
+        let mut reverted = ready(&state);
+        reverted.output.vertices[0].position = [1.0, 0.0, 0.0];
+        let fourth = state.retain_buffers(reverted);
+        assert!(!Arc::ptr_eq(&third, &fourth));
+    }
+
+    #[test]
+    fn test_presentation_reports_mesh_bounds() {
+        let heightmap = HeightmapSampler::new(vec![0.0; 4], 2, 2, 1.0);
+        let mut state = WorldState::new_heightmap(heightmap, 1.0, 0, 4, DVec3::splat(100.0), 1.0);
+
+        // Sphere crossing every face of a unit-voxel node whose neighbors
+        // are all coarser, so seam vertices are displaced
+        let node = OctreeNode::new(0, 0, 0, 0);
+        let sphere = voxel_plugin::SphereSampler::new(20.0).with_center([16.0; 3]);
+        let sampled =
+            voxel_plugin::pipeline::sample_volume_for_node(&node, &sphere, &state.world.config);
+        let output = voxel_plugin::surface_nets::generate(
+            &sampled.volume,
+            &sampled.materials,
+            &voxel_plugin::MeshConfig::default().with_neighbor_mask(0b111_1110),
+        );
+        assert!(!output.is_empty());
+        let mut bounds = MinMaxAABB::empty();
+        for vertex in &output.vertices {
+            bounds.encapsulate(vertex.position);
+        }
+        let triangle_count = (output.indices.len() / 3) as u32;
+
+        let chunk = RetainedChunk {
+            key: node.into(),
+            world_pos: DVec3::ZERO,
+            scale: 1.0,
+            parent_scale: 1.0,
+            morph_enabled: false,
+            buffers: state.retain_buffers(ReadyChunk {
+                world_id: state.world.id,
+                node,
+                output,
+                hint: voxel_plugin::pipeline::PresentationHint::Immediate,
+                timing_us: 0,
+            }),
+        };
+        let ffi = chunk.to_ffi();
+
+        assert_eq!(ffi.min, bounds.min);
+        assert_eq!(ffi.max, bounds.max);
+        assert_eq!(ffi.triangle_count, triangle_count);
+        assert_eq!(ffi.indices_count, triangle_count * 3);
+    }
+
+    #[test]
+    fn test_subdivide_child_morphs_from_parent_scale() {
+        let heightmap = HeightmapSampler::new(vec![0.0; 4], 2, 2, 1.0);
+        let mut state = WorldState::new_heightmap(heightmap, 1.0, 0, 4, DVec3::splat(100.0), 1.0);
+
+        let mut checked = 0;
+        for _ in 0..8 {
+            state.update(DVec3::new(0.0, 2.0, 0.0));
+
+            for group in &state.pending_groups {
+                for chunk in &group.presentations {
+                    if group.is_collapse {
+                        assert_eq!(chunk.morph_enabled, 0);
+                        assert_eq!(chunk.parent_scale, chunk.scale);
+                    } else {
+                        assert_eq!(chunk.morph_enabled, 1);
+                        assert_eq!(chunk.parent_scale, chunk.scale * 2.0);
+                        checked += 1;
+                    }
+                }
+            }
+        }
+        assert!(checked > 0, "Expected subdivide children near the viewer");
+    }
+
+    #[test]
+    fn test_refine_budget_limits_transitions_per_update() {
+        let config = FfiWorldConfig::default();
+        let heights = vec![0.0f32; 4];
+
+        unsafe {
+            let world_id = voxel_world_create_heightmap(&config, heights.as_ptr(), 2, 2, 1.0);
+            assert!(world_id > 0);
+
+            assert_eq!(voxel_world_set_refine_budget(world_id, 1, 1, 0), 0);
+            let mut budget = FfiRefineBudget::default();
+            assert_eq!(voxel_world_get_refine_budget(world_id, &mut budget), 0);
+            assert_eq!(
+                budget,
+                FfiRefineBudget {
+                    max_subdivisions: 1,
+                    max_collapses: 1,
+                    max_millis: 0,
+                    _pad: 0,
+                }
+            );
+
+            let mut batch = FfiPresentationBatch {
+                groups: std::ptr::null(),
+                groups_count: 0,
+                _pad: 0,
+            };
+
+            // Settle near one corner, then teleport to the opposite one
+            let updates_with_work = |viewer: DVec3, batch: &mut FfiPresentationBatch| {
+                let mut count = 0;
+                for _ in 0..64 {
+                    let status =
+                        voxel_world_update(world_id, viewer.x, viewer.y, viewer.z, batch);
+                    assert!(status >= 0);
+
+                    let guard = WORLDS.lock().unwrap();
+                    let stats = guard.as_ref().unwrap()[&world_id].last_refine_stats;
+                    assert!(stats.subdivisions_performed <= 1, "{:?}", stats);
+                    assert!(stats.collapses_performed <= 1, "{:?}", stats);
+                    if stats.total_transitions() > 0 {
+                        count += 1;
+                    }
+                }
+                count
+            };
+
+            updates_with_work(DVec3::new(-90.0, 0.0, -90.0), &mut batch);
+            let teleport_updates = updates_with_work(DVec3::new(90.0, 0.0, 90.0), &mut batch);
+            assert!(
+                teleport_updates > 1,
+                "Teleport should be spread over several updates"
+            );
+
+            assert_eq!(voxel_world_set_refine_budget(-1, 1, 1, 0), -3);
+            voxel_world_destroy(world_id);
+        }
+    }
+
+    #[test]
+    fn test_heightmap_world_follows_ramp() {
+        // 16x16 ramp rising along X: 2 units of height per texel
+        const SIZE: u32 = 16;
+        const TEXEL: f32 = 8.0;
+        let heights: Vec<f32> = (0..SIZE * SIZE).map(|i| (i % SIZE) as f32 * 2.0).collect();
+        let ramp_height = |wx: f64| {
+            let texel = wx / TEXEL as f64 + (SIZE - 1) as f64 * 0.5;
+            texel.clamp(0.0, (SIZE - 1) as f64) * 2.0
+        };
+
+        let config = FfiWorldConfig::default();
+
+        unsafe {
+            let world_id =
+                voxel_world_create_heightmap(&config, heights.as_ptr(), SIZE, SIZE, TEXEL);
+            assert!(world_id > 0, "Expected positive world_id, got {}", world_id);
+            drop(heights); // World owns its copy
+
+            let mut batch = FfiPresentationBatch {
+                groups: std::ptr::null(),
+                groups_count: 0,
+                _pad: 0,
+            };
+            let status = voxel_world_update(world_id, 0.0, 15.0, 0.0, &mut batch);
+            assert_eq!(status, 1, "First update should mesh chunks");
+
+            let groups = std::slice::from_raw_parts(batch.groups, batch.groups_count as usize);
+            let mut checked = 0;
+            for group in groups {
+                if group.to_add.is_null() {
+                    continue;
+                }
+                let chunks = std::slice::from_raw_parts(group.to_add, group.to_add_count as usize);
+                for chunk in chunks {
+                    let vertices =
+                        std::slice::from_raw_parts(chunk.vertices_ptr, chunk.vertices_count as usize);
+                    for v in vertices {
+                        let wx = chunk.world_pos_x + v.position[0] as f64 * chunk.scale;
+                        let wy = chunk.world_pos_y + v.position[1] as f64 * chunk.scale;
+                        // Bilinear heights are exact on a linear ramp; allow for
+                        // vertex placement within a couple of voxels
+                        let tolerance = 2.0 * chunk.scale;
+                        assert!(
+                            (wy - ramp_height(wx)).abs() <= tolerance,
+                            "Vertex at x={:.2}, y={:.2} is off the ramp (expected {:.2})",
+                            wx,
+                            wy,
+                            ramp_height(wx)
+                        );
+                        checked += 1;
+                    }
+                }
+            }
+            assert!(checked > 0, "Expected meshed vertices on the ramp");
+
+            voxel_world_destroy(world_id);
+        }
+    }
 }